@@ -16,10 +16,15 @@ use std::io::Write;
 
 use arrow::array::Array;
 use arrow::chunk::Chunk;
+use arrow::datatypes::Field;
 use arrow::datatypes::Schema;
 use arrow::error::Result;
 use arrow::io::parquet::write::to_parquet_schema;
 use arrow::io::parquet::write::RowGroupIterator;
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::schema::types::ParquetType;
+use parquet2::schema::types::PrimitiveConvertedType;
+use parquet2::schema::types::PrimitiveLogicalType;
 use parquet2::write::FileWriter;
 use parquet2::write::WriteOptions;
 use parquet2::FileMetaData;
@@ -36,7 +41,7 @@ where
     A: AsRef<dyn Array> + 'static + Send + Sync,
     I: Iterator<Item = Result<Chunk<A>>>,
 {
-    let parquet_schema = to_parquet_schema(&schema)?;
+    let parquet_schema = with_decimal_types(&schema, to_parquet_schema(&schema)?);
 
     // Arrow2 should be honored
     let created_by = Some("Arrow2 - Native Rust implementation of Arrow".to_string());
@@ -48,3 +53,42 @@ where
     let (size, file_meta_data) = file_writer.end_ext(None)?;
     Ok((size, file_meta_data))
 }
+
+// the databend Decimal(P, S) is Int64 in arrow, it's annotated as the parquet DECIMAL
+// so that the other readers get the decimals rather than the unscaled integers.
+fn with_decimal_types(schema: &Schema, parquet_schema: SchemaDescriptor) -> SchemaDescriptor {
+    if !schema.fields.iter().any(|f| decimal_of(f).is_some()) {
+        return parquet_schema;
+    }
+
+    let fields = schema
+        .fields
+        .iter()
+        .zip(parquet_schema.fields().iter())
+        .map(
+            |(field, parquet_type)| match (decimal_of(field), parquet_type) {
+                (Some((precision, scale)), ParquetType::PrimitiveType(primitive)) => {
+                    let mut primitive = primitive.clone();
+                    primitive.logical_type = Some(PrimitiveLogicalType::Decimal(precision, scale));
+                    primitive.converted_type =
+                        Some(PrimitiveConvertedType::Decimal(precision, scale));
+                    ParquetType::PrimitiveType(primitive)
+                }
+                _ => parquet_type.clone(),
+            },
+        )
+        .collect();
+    SchemaDescriptor::new(parquet_schema.name().to_string(), fields)
+}
+
+// keep in sync with `ARROW_EXTENSION_NAME` and `ARROW_EXTENSION_META` of common-datavalues
+fn decimal_of(field: &Field) -> Option<(usize, usize)> {
+    match field.metadata.get("ARROW:extension:databend_name") {
+        Some(name) if name == "Decimal" => {
+            let meta = field.metadata.get("ARROW:extension:databend_metadata")?;
+            let (precision, scale) = meta.split_once(',')?;
+            Some((precision.parse().ok()?, scale.parse().ok()?))
+        }
+        _ => None,
+    }
+}
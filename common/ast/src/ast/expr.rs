@@ -20,6 +20,7 @@ use common_datavalues::IntervalKind;
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Identifier;
+use crate::ast::OrderByExpr;
 use crate::ast::Query;
 use crate::parser::token::Token;
 
@@ -138,6 +139,8 @@ pub enum Expr<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        /// Set if the function is called as a window function, like `SUM(a) OVER (PARTITION BY b)`
+        window: Option<Window<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -206,6 +209,9 @@ pub enum Expr<'a> {
         expr1: Box<Expr<'a>>,
         expr2: Box<Expr<'a>>,
     },
+    /// A parameter placeholder `?` or `$<index>` of a prepared statement.
+    /// `?` placeholders are numbered by their position in the statement, starting from 1.
+    Placeholder { span: &'a [Token<'a>], index: usize },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -227,6 +233,38 @@ pub enum Literal {
     Null,
 }
 
+/// The window specification of `OVER (...)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window<'a> {
+    pub partition_by: Vec<Expr<'a>>,
+    pub order_by: Vec<OrderByExpr<'a>>,
+    pub window_frame: Option<WindowFrame>,
+}
+
+/// `ROWS | RANGE BETWEEN <start_bound> AND <end_bound>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound,
+    pub end_bound: WindowFrameBound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowFrameBound {
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `<N> PRECEDING` or `UNBOUNDED PRECEDING`
+    Preceding(Option<u64>),
+    /// `<N> FOLLOWING` or `UNBOUNDED FOLLOWING`
+    Following(Option<u64>),
+}
+
 /// The display style for a map access expression
 #[derive(Debug, Clone, PartialEq)]
 pub enum MapAccessor<'a> {
@@ -251,6 +289,7 @@ pub enum TypeName {
     Int64,
     Float32,
     Float64,
+    Decimal { precision: u64, scale: u64 },
     Date,
     DateTime { precision: Option<u64> },
     Timestamp,
@@ -338,7 +377,8 @@ impl<'a> Expr<'a> {
             | Expr::DateSub { span, .. }
             | Expr::NullIf { span, .. }
             | Expr::Coalesce { span, .. }
-            | Expr::IfNull { span, .. } => span,
+            | Expr::IfNull { span, .. }
+            | Expr::Placeholder { span, .. } => span,
         }
     }
 }
@@ -487,6 +527,9 @@ impl Display for TypeName {
             TypeName::Float64 => {
                 write!(f, "Float64")?;
             }
+            TypeName::Decimal { precision, scale } => {
+                write!(f, "DECIMAL({}, {})", precision, scale)?;
+            }
             TypeName::Date => {
                 write!(f, "DATE")?;
             }
@@ -529,6 +572,64 @@ impl Display for TrimWhere {
     }
 }
 
+impl<'a> Display for Window<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        let mut first = true;
+        if !self.partition_by.is_empty() {
+            first = false;
+            write!(f, "PARTITION BY ")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+        }
+        if !self.order_by.is_empty() {
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            write!(f, "ORDER BY ")?;
+            write_comma_separated_list(f, &self.order_by)?;
+        }
+        if let Some(window_frame) = &self.window_frame {
+            if !first {
+                write!(f, " ")?;
+            }
+            write!(f, "{window_frame}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Display for WindowFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
+
+impl Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WindowFrameUnits::Rows => "ROWS",
+            WindowFrameUnits::Range => "RANGE",
+        })
+    }
+}
+
+impl Display for WindowFrameBound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(Some(n)) => write!(f, "{n} PRECEDING"),
+            WindowFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFrameBound::Following(Some(n)) => write!(f, "{n} FOLLOWING"),
+        }
+    }
+}
+
 impl Display for Literal {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -715,6 +816,7 @@ impl<'a> Display for Expr<'a> {
                 name,
                 args,
                 params,
+                window,
                 ..
             } => {
                 write!(f, "{name}")?;
@@ -729,6 +831,9 @@ impl<'a> Display for Expr<'a> {
                 }
                 write_comma_separated_list(f, args)?;
                 write!(f, ")")?;
+                if let Some(window) = window {
+                    write!(f, " OVER {window}")?;
+                }
             }
             Expr::Case {
                 operand,
@@ -803,6 +908,9 @@ impl<'a> Display for Expr<'a> {
             Expr::IfNull { expr1, expr2, .. } => {
                 write!(f, "IFNULL({expr1}, {expr2})")?;
             }
+            Expr::Placeholder { index, .. } => {
+                write!(f, "${index}")?;
+            }
         }
 
         Ok(())
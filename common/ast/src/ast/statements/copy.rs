@@ -37,7 +37,12 @@ pub struct CopyStmt<'a> {
     pub file_format: BTreeMap<String, String>,
     /// TODO(xuanwo): parse into validation_mode directly.
     pub validation_mode: String,
+    pub on_error: String,
     pub size_limit: usize,
+    /// The max size of each file unloaded by `COPY INTO <location>`, 0 means no limit.
+    pub max_file_size: usize,
+    /// Load the files even if they have been loaded before.
+    pub force: bool,
 }
 
 impl Display for CopyStmt<'_> {
@@ -64,10 +69,22 @@ impl Display for CopyStmt<'_> {
             write!(f, " PATTERN = '{}'", self.pattern)?;
         }
 
+        if !self.on_error.is_empty() {
+            write!(f, " ON_ERROR = {}", self.on_error)?;
+        }
+
         if self.size_limit != 0 {
             write!(f, " SIZE_LIMIT = {}", self.size_limit)?;
         }
 
+        if self.max_file_size != 0 {
+            write!(f, " MAX_FILE_SIZE = {}", self.max_file_size)?;
+        }
+
+        if self.force {
+            write!(f, " FORCE = true")?;
+        }
+
         if !self.validation_mode.is_empty() {
             write!(f, "VALIDATION_MODE = {}", self.validation_mode)?;
         }
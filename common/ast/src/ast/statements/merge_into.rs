@@ -0,0 +1,123 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_comma_separated_list;
+use crate::ast::write_period_separated_list;
+use crate::ast::Expr;
+use crate::ast::Identifier;
+use crate::ast::TableAlias;
+use crate::ast::TableReference;
+use crate::ast::UpdateExpr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeIntoStmt<'a> {
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+    pub alias: Option<TableAlias<'a>>,
+    pub source: TableReference<'a>,
+    pub join_expr: Expr<'a>,
+    pub clauses: Vec<MergeClause<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeClause<'a> {
+    Matched {
+        selection: Option<Expr<'a>>,
+        operation: MatchOperation<'a>,
+    },
+    NotMatched {
+        selection: Option<Expr<'a>>,
+        columns: Vec<Identifier<'a>>,
+        values: Vec<Expr<'a>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchOperation<'a> {
+    Update { update_list: Vec<UpdateExpr<'a>> },
+    Delete,
+}
+
+impl Display for MergeIntoStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MERGE INTO ")?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )?;
+        if let Some(alias) = &self.alias {
+            write!(f, " AS {alias}")?;
+        }
+        write!(f, " USING {} ON {}", self.source, self.join_expr)?;
+        for clause in &self.clauses {
+            write!(f, " {clause}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for MergeClause<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeClause::Matched {
+                selection,
+                operation,
+            } => {
+                write!(f, "WHEN MATCHED")?;
+                if let Some(selection) = selection {
+                    write!(f, " AND {selection}")?;
+                }
+                write!(f, " THEN {operation}")
+            }
+            MergeClause::NotMatched {
+                selection,
+                columns,
+                values,
+            } => {
+                write!(f, "WHEN NOT MATCHED")?;
+                if let Some(selection) = selection {
+                    write!(f, " AND {selection}")?;
+                }
+                write!(f, " THEN INSERT")?;
+                if !columns.is_empty() {
+                    write!(f, " (")?;
+                    write_comma_separated_list(f, columns)?;
+                    write!(f, ")")?;
+                }
+                write!(f, " VALUES (")?;
+                write_comma_separated_list(f, values)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl Display for MatchOperation<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchOperation::Update { update_list } => {
+                write!(f, "UPDATE SET ")?;
+                write_comma_separated_list(f, update_list)
+            }
+            MatchOperation::Delete => write!(f, "DELETE"),
+        }
+    }
+}
@@ -18,10 +18,12 @@ mod database;
 mod explain;
 mod insert;
 mod kill;
+mod merge_into;
 mod show;
 mod stage;
 mod statement;
 mod table;
+mod update;
 mod user;
 mod view;
 
@@ -31,9 +33,11 @@ pub use database::*;
 pub use explain::*;
 pub use insert::*;
 pub use kill::*;
+pub use merge_into::*;
 pub use show::*;
 pub use stage::*;
 pub use statement::*;
 pub use table::*;
+pub use update::*;
 pub use user::*;
 pub use view::*;
@@ -64,6 +64,10 @@ pub enum Statement<'a> {
         table: Identifier<'a>,
         selection: Option<Expr<'a>>,
     },
+
+    Update(UpdateStmt<'a>),
+    MergeInto(MergeIntoStmt<'a>),
+
     // Databases
     ShowDatabases(ShowDatabasesStmt<'a>),
     ShowCreateDatabase(ShowCreateDatabaseStmt<'a>),
@@ -187,6 +191,8 @@ impl<'a> Display for Statement<'a> {
                     write!(f, "WHERE {conditions} ")?;
                 }
             }
+            Statement::Update(stmt) => write!(f, "{stmt}")?,
+            Statement::MergeInto(stmt) => write!(f, "{stmt}")?,
             Statement::Copy(stmt) => write!(f, "{stmt}")?,
             Statement::ShowSettings { like } => {
                 write!(f, "SHOW SETTINGS")?;
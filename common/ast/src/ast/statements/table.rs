@@ -22,6 +22,7 @@ use crate::ast::write_space_seperated_list;
 use crate::ast::Expr;
 use crate::ast::Identifier;
 use crate::ast::Query;
+use crate::ast::TimeTravelPoint;
 use crate::ast::TypeName;
 
 #[derive(Debug, Clone, PartialEq)] // Tables
@@ -155,6 +156,12 @@ pub enum CreateTableSource<'a> {
         database: Option<Identifier<'a>>,
         table: Identifier<'a>,
     },
+    Clone {
+        catalog: Option<Identifier<'a>>,
+        database: Option<Identifier<'a>>,
+        table: Identifier<'a>,
+        travel_point: Option<TimeTravelPoint<'a>>,
+    },
 }
 
 impl Display for CreateTableSource<'_> {
@@ -173,6 +180,20 @@ impl Display for CreateTableSource<'_> {
                 write!(f, "LIKE ")?;
                 write_period_separated_list(f, catalog.iter().chain(database).chain(Some(table)))
             }
+            CreateTableSource::Clone {
+                catalog,
+                database,
+                table,
+                travel_point,
+            } => {
+                write!(f, "CLONE ")?;
+                write_period_separated_list(f, catalog.iter().chain(database).chain(Some(table)))?;
+                match travel_point {
+                    Some(TimeTravelPoint::Snapshot(sid)) => write!(f, " AT (SNAPSHOT => {sid})"),
+                    Some(TimeTravelPoint::Timestamp(ts)) => write!(f, " AT (TIMESTAMP => {ts})"),
+                    None => Ok(()),
+                }
+            }
         }
     }
 }
@@ -274,9 +295,26 @@ impl Display for AlterTableStmt<'_> {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AlterTableAction<'a> {
-    RenameTable { new_table: Identifier<'a> },
-    AlterTableClusterKey { cluster_by: Vec<Expr<'a>> },
+    RenameTable {
+        new_table: Identifier<'a>,
+    },
+    AddColumn {
+        column: ColumnDefinition<'a>,
+    },
+    DropColumn {
+        column: Identifier<'a>,
+    },
+    RenameColumn {
+        old_column: Identifier<'a>,
+        new_column: Identifier<'a>,
+    },
+    AlterTableClusterKey {
+        cluster_by: Vec<Expr<'a>>,
+    },
     DropTableClusterKey,
+    ReclusterTable {
+        is_final: bool,
+    },
 }
 
 impl Display for AlterTableAction<'_> {
@@ -285,6 +323,18 @@ impl Display for AlterTableAction<'_> {
             AlterTableAction::RenameTable { new_table } => {
                 write!(f, "RENAME TO {new_table}")
             }
+            AlterTableAction::AddColumn { column } => {
+                write!(f, "ADD COLUMN {column}")
+            }
+            AlterTableAction::DropColumn { column } => {
+                write!(f, "DROP COLUMN {column}")
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                write!(f, "RENAME COLUMN {old_column} TO {new_column}")
+            }
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 write!(f, "CLUSTER BY ")?;
                 write_comma_separated_list(f, cluster_by)
@@ -292,6 +342,13 @@ impl Display for AlterTableAction<'_> {
             AlterTableAction::DropTableClusterKey => {
                 write!(f, "DROP CLUSTER KEY")
             }
+            AlterTableAction::ReclusterTable { is_final } => {
+                write!(f, "RECLUSTER")?;
+                if *is_final {
+                    write!(f, " FINAL")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -363,6 +420,7 @@ pub struct OptimizeTableStmt<'a> {
     pub database: Option<Identifier<'a>>,
     pub table: Identifier<'a>,
     pub action: Option<OptimizeTableAction>,
+    pub dry_run: bool,
 }
 
 impl Display for OptimizeTableStmt<'_> {
@@ -378,6 +436,9 @@ impl Display for OptimizeTableStmt<'_> {
         if let Some(action) = &self.action {
             write!(f, " {action}")?;
         }
+        if self.dry_run {
+            write!(f, " DRY RUN")?;
+        }
 
         Ok(())
     }
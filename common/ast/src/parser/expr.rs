@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_datavalues::IntervalKind;
+use common_datavalues::DECIMAL_DEFAULT_PRECISION;
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::combinator::consumed;
@@ -234,6 +235,7 @@ pub enum ExprElement<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        window: Option<Window<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -287,6 +289,9 @@ pub enum ExprElement<'a> {
         expr1: Expr<'a>,
         expr2: Expr<'a>,
     },
+    Placeholder {
+        index: usize,
+    },
 }
 
 struct ExprParser;
@@ -416,12 +421,14 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 name,
                 args,
                 params,
+                window,
             } => Expr::FunctionCall {
                 span: elem.span.0,
                 distinct,
                 name,
                 args,
                 params,
+                window,
             },
             ExprElement::Case {
                 operand,
@@ -489,6 +496,10 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 expr1: Box::new(expr1),
                 expr2: Box::new(expr2),
             },
+            ExprElement::Placeholder { index } => Expr::Placeholder {
+                span: elem.span.0,
+                index,
+            },
             _ => unreachable!(),
         };
         Ok(expr)
@@ -725,9 +736,26 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             trim_where: Some((trim_where, Box::new(trim_str))),
         },
     );
-    let count_all = value(ExprElement::CountAll, rule! {
-        COUNT ~ "(" ~ "*" ~ ^")"
-    });
+    let count_all = map(
+        rule! {
+            COUNT ~ "(" ~ "*" ~ ^")" ~ #window_spec?
+        },
+        |(count, _, _, _, opt_window)| match opt_window {
+            // `COUNT(*) OVER (...)` is treated as the window function `COUNT()`
+            Some(window) => ExprElement::FunctionCall {
+                distinct: false,
+                name: Identifier {
+                    name: count.text().to_string(),
+                    quote: None,
+                    span: count.clone(),
+                },
+                args: vec![],
+                params: vec![],
+                window: Some(window),
+            },
+            None => ExprElement::CountAll,
+        },
+    );
     let tuple = map(
         rule! {
             "(" ~ #subexpr(0) ~ "," ~ #comma_separated_list1_allow_trailling(subexpr(0))? ~ ","? ~ ^")"
@@ -745,12 +773,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             ~ DISTINCT?
             ~ #comma_separated_list0(subexpr(0))?
             ~ ")"
+            ~ #window_spec?
         },
-        |(name, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params: vec![],
+            window,
         },
     );
     let function_call_with_param = map(
@@ -758,12 +788,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             #function_name
             ~ "(" ~ #comma_separated_list1(literal) ~ ")"
             ~ "(" ~ DISTINCT? ~ #comma_separated_list0(subexpr(0))? ~ ")"
+            ~ #window_spec?
         },
-        |(name, _, params, _, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, params, _, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params,
+            window,
         },
     );
     let case = map(
@@ -821,6 +853,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     let binary_op = map(binary_op, |op| ExprElement::BinaryOp { op });
     let unary_op = map(unary_op, |op| ExprElement::UnaryOp { op });
     let literal = map(literal, |lit| ExprElement::Literal { lit });
+    let placeholder = map(placeholder, |index| ExprElement::Placeholder { index });
     let map_access = map(map_access, |accessor| ExprElement::MapAccess { accessor });
     let array = map(
         // Array that contains a single literal item will be parsed as a bracket map access,
@@ -927,6 +960,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             | #function_call_with_param : "<function>"
             | #function_call : "<function>"
             | #literal : "<literal>"
+            | #placeholder : "`?` | `$<index>`"
             | #case : "`CASE ... END`"
             | #subquery : "`(SELECT ...)`"
             | #group
@@ -939,6 +973,72 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     Ok((rest, WithSpan { span, elem }))
 }
 
+pub fn window_spec(i: Input) -> IResult<Window> {
+    map(
+        rule! {
+            OVER ~ "("
+            ~ ( PARTITION ~ ^BY ~ ^#comma_separated_list1(subexpr(0)) )?
+            ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
+            ~ #window_frame?
+            ~ ^")"
+        },
+        |(_, _, opt_partition_by, opt_order_by, window_frame, _)| Window {
+            partition_by: opt_partition_by
+                .map(|(_, _, exprs)| exprs)
+                .unwrap_or_default(),
+            order_by: opt_order_by.map(|(_, _, exprs)| exprs).unwrap_or_default(),
+            window_frame,
+        },
+    )(i)
+}
+
+pub fn window_frame(i: Input) -> IResult<WindowFrame> {
+    let units = alt((
+        value(WindowFrameUnits::Rows, rule! { ROWS }),
+        value(WindowFrameUnits::Range, rule! { RANGE }),
+    ));
+    let between = map(
+        rule! {
+            BETWEEN ~ ^#window_frame_bound ~ ^AND ~ ^#window_frame_bound
+        },
+        |(_, start_bound, _, end_bound)| (start_bound, end_bound),
+    );
+    // `ROWS <bound>` is the short form of `ROWS BETWEEN <bound> AND CURRENT ROW`
+    let start_only = map(window_frame_bound, |start_bound| {
+        (start_bound, WindowFrameBound::CurrentRow)
+    });
+    map(
+        rule! {
+            #units ~ ^( #between | #start_only )
+        },
+        |(units, (start_bound, end_bound))| WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        },
+    )(i)
+}
+
+pub fn window_frame_bound(i: Input) -> IResult<WindowFrameBound> {
+    let current_row = value(WindowFrameBound::CurrentRow, rule! { CURRENT ~ ^ROW });
+    let preceding = map(rule! { #window_frame_offset ~ PRECEDING }, |(offset, _)| {
+        WindowFrameBound::Preceding(offset)
+    });
+    let following = map(rule! { #window_frame_offset ~ FOLLOWING }, |(offset, _)| {
+        WindowFrameBound::Following(offset)
+    });
+    rule!(
+        #current_row : "CURRENT ROW"
+        | #preceding : "`UNBOUNDED PRECEDING` or `<offset> PRECEDING`"
+        | #following : "`UNBOUNDED FOLLOWING` or `<offset> FOLLOWING`"
+    )(i)
+}
+
+/// Parses `UNBOUNDED` as `None` or a constant row offset as `Some(offset)`.
+fn window_frame_offset(i: Input) -> IResult<Option<u64>> {
+    alt((value(None, rule! { UNBOUNDED }), map(literal_u64, Some)))(i)
+}
+
 pub fn unary_op(i: Input) -> IResult<UnaryOperator> {
     // Plus and Minus are parsed as binary op at first.
     value(UnaryOperator::Not, rule! { NOT })(i)
@@ -1048,6 +1148,10 @@ pub fn literal_u64(i: Input) -> IResult<u64> {
     )(i)
 }
 
+pub fn literal_bool(i: Input) -> IResult<bool> {
+    alt((value(true, rule! { TRUE }), value(false, rule! { FALSE })))(i)
+}
+
 pub fn literal_f64(i: Input) -> IResult<f64> {
     map_res(
         rule! {
@@ -1075,6 +1179,36 @@ pub fn literal_string(i: Input) -> IResult<String> {
     )(i)
 }
 
+/// Parse a parameter placeholder into its 1-based index.
+pub fn placeholder(i: Input) -> IResult<usize> {
+    let positional = map(
+        rule! {
+            Placeholder
+        },
+        |token| {
+            // `?` has no explicit index, so count the `?` placeholders before it.
+            Tokenizer::new(&token.source[..token.span.start])
+                .filter(|prev| matches!(prev, Ok(prev) if prev.kind == Placeholder))
+                .count()
+                + 1
+        },
+    );
+    let numbered = map_res(
+        rule! {
+            NumberedPlaceholder
+        },
+        |token| match token.text()[1..].parse::<usize>()? {
+            0 => Err(ErrorKind::Other("placeholder index starts from 1")),
+            index => Ok(index),
+        },
+    );
+
+    rule!(
+        #positional
+        | #numbered
+    )(i)
+}
+
 pub fn at_string(i: Input) -> IResult<String> {
     match_token(AtString)(i)
         .map(|(i2, token)| (i2, token.text()[1..token.text().len()].to_string()))
@@ -1116,6 +1250,19 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
     );
     let ty_float32 = value(TypeName::Float32, rule! { FLOAT32 | FLOAT });
     let ty_float64 = value(TypeName::Float64, rule! { FLOAT64 | DOUBLE });
+    let ty_decimal = map(
+        rule! { DECIMAL ~ ( "(" ~ #literal_u64 ~ ( "," ~ #literal_u64 )? ~ ")" )? },
+        |(_, opt_args)| match opt_args {
+            Some((_, precision, opt_scale, _)) => TypeName::Decimal {
+                precision,
+                scale: opt_scale.map(|(_, scale)| scale).unwrap_or(0),
+            },
+            None => TypeName::Decimal {
+                precision: DECIMAL_DEFAULT_PRECISION as u64,
+                scale: 0,
+            },
+        },
+    );
     let ty_array = map(
         rule! { ARRAY ~ ( "(" ~ #type_name ~ ")" )? },
         |(_, opt_item_type)| TypeName::Array {
@@ -1149,6 +1296,7 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
         | #ty_int64
         | #ty_float32
         | #ty_float64
+        | #ty_decimal
         | #ty_array
         | #ty_date
         | #ty_datetime
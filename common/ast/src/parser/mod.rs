@@ -37,6 +37,28 @@ pub fn tokenize_sql(sql: &str) -> Result<Vec<Token>> {
     Tokenizer::new(sql).collect::<Result<Vec<_>>>()
 }
 
+/// Returns the number of parameters a statement expects, that is the number of `?`
+/// placeholders or the largest index of the `$<index>` placeholders, whichever is greater.
+pub fn placeholder_count(sql_tokens: &[Token]) -> Result<usize> {
+    let mut positional = 0;
+    let mut numbered = 0;
+    for token in sql_tokens {
+        match token.kind {
+            TokenKind::Placeholder => positional += 1,
+            TokenKind::NumberedPlaceholder => {
+                let index = token.text()[1..].parse::<usize>().map_err(|_| {
+                    ErrorCode::SyntaxException(
+                        token.display_error("invalid placeholder index".to_string()),
+                    )
+                })?;
+                numbered = numbered.max(index);
+            }
+            _ => (),
+        }
+    }
+    Ok(positional.max(numbered))
+}
+
 /// Parse a SQL string into `Statement`s.
 pub fn parse_sql<'a>(
     sql_tokens: &'a [Token<'a>],
@@ -32,15 +32,20 @@ use crate::util::*;
 pub fn query(i: Input) -> IResult<Query> {
     map(
         consumed(rule! {
-            #set_operation
+            #with?
+            ~ #set_operation
             ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
             ~ ( LIMIT ~ ^#comma_separated_list1(expr) )?
             ~ ( OFFSET ~ ^#expr )?
             ~ ( FORMAT ~ #ident )?
             : "`SELECT ...`"
         }),
-        |(span, (body, opt_order_by_block, opt_limit_block, opt_offset_block, opt_format))| Query {
+        |(
+            span,
+            (with, body, opt_order_by_block, opt_limit_block, opt_offset_block, opt_format),
+        )| Query {
             span: span.0,
+            with,
             body,
             order_by: opt_order_by_block
                 .map(|(_, _, order_by)| order_by)
@@ -52,6 +57,43 @@ pub fn query(i: Input) -> IResult<Query> {
     )(i)
 }
 
+pub fn with(i: Input) -> IResult<With> {
+    map(
+        consumed(rule! {
+            WITH ~ RECURSIVE? ~ ^#comma_separated_list1(cte)
+        }),
+        |(span, (_, opt_recursive, ctes))| With {
+            span: span.0,
+            recursive: opt_recursive.is_some(),
+            ctes,
+        },
+    )(i)
+}
+
+pub fn cte(i: Input) -> IResult<CTE> {
+    let column_list = map(
+        rule! {
+            "(" ~ ^#comma_separated_list1(ident) ~ ^")"
+        },
+        |(_, columns, _)| columns,
+    );
+
+    map(
+        consumed(rule! {
+            #ident ~ #column_list? ~ ^AS ~ MATERIALIZED? ~ ^#parenthesized_query
+        }),
+        |(span, (name, opt_columns, _, opt_materialized, query))| CTE {
+            span: span.0,
+            alias: TableAlias {
+                name,
+                columns: opt_columns.unwrap_or_default(),
+            },
+            materialized: opt_materialized.is_some(),
+            query,
+        },
+    )(i)
+}
+
 pub fn select_target(i: Input) -> IResult<SelectTarget> {
     let qualified_wildcard = map(
         rule! {
@@ -296,6 +338,47 @@ pub fn order_by_expr(i: Input) -> IResult<OrderByExpr> {
     )(i)
 }
 
+pub fn group_by_items(i: Input) -> IResult<GroupBy> {
+    let grouping_sets = map(
+        rule! {
+            GROUPING ~ SETS ~ "(" ~ ^#comma_separated_list1(grouping_set) ~ ^")"
+        },
+        |(_, _, _, sets, _)| GroupBy::GroupingSets(sets),
+    );
+    let rollup = map(
+        rule! {
+            ROLLUP ~ "(" ~ ^#comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, _, exprs, _)| GroupBy::Rollup(exprs),
+    );
+    let cube = map(
+        rule! {
+            CUBE ~ "(" ~ ^#comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, _, exprs, _)| GroupBy::Cube(exprs),
+    );
+    let normal = map(rule! { ^#comma_separated_list1(expr) }, GroupBy::Normal);
+
+    rule!(
+        #grouping_sets
+        | #rollup
+        | #cube
+        | #normal
+    )(i)
+}
+
+pub fn grouping_set(i: Input) -> IResult<Vec<Expr>> {
+    alt((
+        map(
+            rule! {
+                "(" ~ #comma_separated_list0(expr) ~ ")"
+            },
+            |(_, exprs, _)| exprs,
+        ),
+        map(expr, |expr| vec![expr]),
+    ))(i)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SetOperationElement<'a> {
     SelectStmt {
@@ -303,7 +386,7 @@ pub enum SetOperationElement<'a> {
         select_list: Box<Vec<SelectTarget<'a>>>,
         from: Box<Vec<TableReference<'a>>>,
         selection: Box<Option<Expr<'a>>>,
-        group_by: Box<Vec<Expr<'a>>>,
+        group_by: Box<Option<GroupBy<'a>>>,
         having: Box<Option<Expr<'a>>>,
     },
     SetOperation {
@@ -337,7 +420,7 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
              SELECT ~ DISTINCT? ~ ^#comma_separated_list1(select_target)
                 ~ ( FROM ~ ^#comma_separated_list1(table_reference) )?
                 ~ ( WHERE ~ ^#expr )?
-                ~ ( GROUP ~ ^BY ~ ^#comma_separated_list1(expr) )?
+                ~ ( GROUP ~ ^BY ~ ^#group_by_items )?
                 ~ ( HAVING ~ ^#expr )?
         },
         |(
@@ -358,11 +441,7 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
                         .unwrap_or_default(),
                 ),
                 selection: Box::new(opt_where_block.map(|(_, selection)| selection)),
-                group_by: Box::new(
-                    opt_group_by_block
-                        .map(|(_, _, group_by)| group_by)
-                        .unwrap_or_default(),
-                ),
+                group_by: Box::new(opt_group_by_block.map(|(_, _, group_by)| group_by)),
                 having: Box::new(opt_having_block.map(|(_, having)| having)),
             }
         },
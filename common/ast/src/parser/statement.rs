@@ -81,6 +81,41 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             selection: opt_where_block.map(|(_, selection)| selection),
         },
     );
+    let update = map(
+        rule! {
+            UPDATE ~ #peroid_separated_idents_1_to_3
+            ~ SET ~ ^#comma_separated_list1(update_expr)
+            ~ ( WHERE ~ ^#expr )?
+        },
+        |(_, (catalog, database, table), _, update_list, opt_where_block)| {
+            Statement::Update(UpdateStmt {
+                catalog,
+                database,
+                table,
+                update_list,
+                selection: opt_where_block.map(|(_, selection)| selection),
+            })
+        },
+    );
+    let merge_into = map(
+        rule! {
+            MERGE ~ INTO ~ #peroid_separated_idents_1_to_3 ~ #table_alias?
+            ~ USING ~ ^#table_reference
+            ~ ON ~ ^#expr
+            ~ #merge_clause+
+        },
+        |(_, _, (catalog, database, table), alias, _, source, _, join_expr, clauses)| {
+            Statement::MergeInto(MergeIntoStmt {
+                catalog,
+                database,
+                table,
+                alias,
+                source,
+                join_expr,
+                clauses,
+            })
+        },
+    );
     let show_settings = map(
         rule! {
             SHOW ~ SETTINGS ~ (LIKE ~ #literal_string)?
@@ -336,14 +371,15 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let optimize_table = map(
         rule! {
-            OPTIMIZE ~ TABLE ~ #peroid_separated_idents_1_to_3 ~ #optimize_table_action?
+            OPTIMIZE ~ TABLE ~ #peroid_separated_idents_1_to_3 ~ #optimize_table_action? ~ ( DRY ~ RUN )?
         },
-        |(_, _, (catalog, database, table), action)| {
+        |(_, _, (catalog, database, table), action, opt_dry_run)| {
             Statement::OptimizeTable(OptimizeTableStmt {
                 catalog,
                 database,
                 table,
                 action,
+                dry_run: opt_dry_run.is_some(),
             })
         },
     );
@@ -663,9 +699,26 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             ~ ( PATTERN ~ "=" ~ #literal_string)?
             ~ ( FILE_FORMAT ~ "=" ~ #options)?
             ~ ( VALIDATION_MODE ~ "=" ~ #literal_string)?
+            ~ ( ON_ERROR ~ "=" ~ #ident)?
             ~ ( SIZE_LIMIT ~ "=" ~ #literal_u64)?
+            ~ ( MAX_FILE_SIZE ~ "=" ~ #literal_u64)?
+            ~ ( FORCE ~ "=" ~ #literal_bool)?
         },
-        |(_, _, dst, _, src, files, pattern, file_format, validation_mode, size_limit)| {
+        |(
+            _,
+            _,
+            dst,
+            _,
+            src,
+            files,
+            pattern,
+            file_format,
+            validation_mode,
+            on_error,
+            size_limit,
+            max_file_size,
+            force,
+        )| {
             Statement::Copy(CopyStmt {
                 src,
                 dst,
@@ -674,6 +727,9 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
                 file_format: file_format.map(|v| v.2).unwrap_or_default(),
                 validation_mode: validation_mode.map(|v| v.2).unwrap_or_default(),
                 size_limit: size_limit.map(|v| v.2).unwrap_or_default() as usize,
+                max_file_size: max_file_size.map(|v| v.2).unwrap_or_default() as usize,
+                on_error: on_error.map(|v| v.2.to_string()).unwrap_or_default(),
+                force: force.map(|v| v.2).unwrap_or_default(),
             })
         },
     );
@@ -696,6 +752,8 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #explain : "`EXPLAIN [PIPELINE | GRAPH] <statement>`"
             | #insert : "`INSERT INTO [TABLE] <table> [(<column>, ...)] (FORMAT <format> | VALUES <values> | <query>)`"
             | #delete : "`DELETE FROM <table> [WHERE ...]`"
+            | #update : "`UPDATE <table> SET <column> = <expr> [, <column> = <expr> , ... ] [WHERE ...]`"
+            | #merge_into : "`MERGE INTO <table> USING <source> ON <expr> WHEN [NOT] MATCHED [AND <expr>] THEN <action> ...`"
             | #show_settings : "`SHOW SETTINGS [<show_limit>]`"
             | #show_stages : "`SHOW STAGES`"
             | #show_process_list : "`SHOW PROCESSLIST`"
@@ -721,7 +779,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #alter_table : "`ALTER TABLE [<database>.]<table> <action>`"
             | #rename_table : "`RENAME TABLE [<database>.]<table> TO <new_table>`"
             | #truncate_table : "`TRUNCATE TABLE [<database>.]<table> [PURGE]`"
-            | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE | COMPACT)`"
+            | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE | COMPACT) [DRY RUN]`"
             | #exists_table : "`EXISTS TABLE [<database>.]<table>`"
             | #create_view : "`CREATE VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
             | #drop_view : "`DROP VIEW [IF EXISTS] [<database>.]<view>`"
@@ -873,6 +931,48 @@ pub fn column_def(i: Input) -> IResult<ColumnDefinition> {
     )(i)
 }
 
+pub fn update_expr(i: Input) -> IResult<UpdateExpr> {
+    map(rule! { #ident ~ "=" ~ ^#expr }, |(name, _, expr)| {
+        UpdateExpr { name, expr }
+    })(i)
+}
+
+pub fn merge_clause(i: Input) -> IResult<MergeClause> {
+    let update = map(
+        rule! { UPDATE ~ ^SET ~ ^#comma_separated_list1(update_expr) },
+        |(_, _, update_list)| MatchOperation::Update { update_list },
+    );
+    let delete = value(MatchOperation::Delete, rule! { DELETE });
+    let matched = map(
+        rule! {
+            WHEN ~ MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^( #update | #delete )
+        },
+        |(_, _, opt_selection, _, operation)| MergeClause::Matched {
+            selection: opt_selection.map(|(_, selection)| selection),
+            operation,
+        },
+    );
+    let not_matched = map(
+        rule! {
+            WHEN ~ NOT ~ ^MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^INSERT
+            ~ ( "(" ~ ^#comma_separated_list1(ident) ~ ^")" )?
+            ~ ^VALUES ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, _, _, opt_selection, _, _, opt_columns, _, _, values, _)| MergeClause::NotMatched {
+            selection: opt_selection.map(|(_, selection)| selection),
+            columns: opt_columns
+                .map(|(_, columns, _)| columns)
+                .unwrap_or_default(),
+            values,
+        },
+    );
+
+    rule!(
+        #matched
+        | #not_matched
+    )(i)
+}
+
 pub fn grant_source(i: Input) -> IResult<AccountMgrSource> {
     let role = map(
         rule! {
@@ -986,10 +1086,22 @@ pub fn create_table_source(i: Input) -> IResult<CreateTableSource> {
             table,
         },
     );
+    let clone = map(
+        rule! {
+            CLONE ~ #peroid_separated_idents_1_to_3 ~ #travel_point?
+        },
+        |(_, (catalog, database, table), travel_point)| CreateTableSource::Clone {
+            catalog,
+            database,
+            table,
+            travel_point,
+        },
+    );
 
     rule!(
         #columns
         | #like
+        | #clone
     )(i)
 }
 
@@ -1028,10 +1140,47 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         |(_, _, _)| AlterTableAction::DropTableClusterKey,
     );
 
+    let add_column = map(
+        rule! {
+            ADD ~ COLUMN ~ ^#column_def
+        },
+        |(_, _, column)| AlterTableAction::AddColumn { column },
+    );
+
+    let drop_column = map(
+        rule! {
+            DROP ~ COLUMN ~ ^#ident
+        },
+        |(_, _, column)| AlterTableAction::DropColumn { column },
+    );
+
+    let rename_column = map(
+        rule! {
+            RENAME ~ COLUMN ~ ^#ident ~ ^TO ~ ^#ident
+        },
+        |(_, _, old_column, _, new_column)| AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        },
+    );
+
+    let recluster_table = map(
+        rule! {
+            RECLUSTER ~ FINAL?
+        },
+        |(_, is_final)| AlterTableAction::ReclusterTable {
+            is_final: is_final.is_some(),
+        },
+    );
+
     rule!(
         #rename_table
+        | #rename_column
+        | #add_column
+        | #drop_column
         | #alter_table_cluster_key
         | #drop_table_cluster_key
+        | #recluster_table
     )(i)
 }
 
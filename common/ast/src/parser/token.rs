@@ -125,6 +125,13 @@ pub enum TokenKind {
     #[regex(r"[0-9]+")]
     LiteralInteger,
 
+    /// A positional parameter placeholder `?` in a prepared statement
+    #[token("?")]
+    Placeholder,
+    /// A numbered parameter placeholder like `$1` in a prepared statement
+    #[regex(r"\$[0-9]+")]
+    NumberedPlaceholder,
+
     #[regex(r"[0-9]+e[+-]?[0-9]+")]
     #[regex(r"([0-9]*\.[0-9]+(e[+-]?[0-9]+)?)|([0-9]+\.[0-9]*(e[+-]?[0-9]+)?)")]
     LiteralFloat,
@@ -235,6 +242,8 @@ pub enum TokenKind {
     // 2. Search in this file to see if the new keyword is a commented
     //    out reserverd keyword. If so, uncomment the keyword in the
     //    reserved list.
+    #[token("ADD", ignore(ascii_case))]
+    ADD,
     #[token("ALL", ignore(ascii_case))]
     ALL,
     #[token("ANY", ignore(ascii_case))]
@@ -279,8 +288,12 @@ pub enum TokenKind {
     CAST,
     #[token("CENTURY", ignore(ascii_case))]
     CENTURY,
+    #[token("CLONE", ignore(ascii_case))]
+    CLONE,
     #[token("CLUSTER", ignore(ascii_case))]
     CLUSTER,
+    #[token("COLUMN", ignore(ascii_case))]
+    COLUMN,
     #[token("COMMENT", ignore(ascii_case))]
     COMMENT,
     #[token("COMMENTS", ignore(ascii_case))]
@@ -305,6 +318,10 @@ pub enum TokenKind {
     CROSS,
     #[token("CSV", ignore(ascii_case))]
     CSV,
+    #[token("CUBE", ignore(ascii_case))]
+    CUBE,
+    #[token("CURRENT", ignore(ascii_case))]
+    CURRENT,
     #[token("CURRENT_TIMESTAMP", ignore(ascii_case))]
     CURRENT_TIMESTAMP,
     #[token("DATABASE", ignore(ascii_case))]
@@ -323,6 +340,8 @@ pub enum TokenKind {
     DAY,
     #[token("DECADE", ignore(ascii_case))]
     DECADE,
+    #[token("DECIMAL", ignore(ascii_case))]
+    DECIMAL,
     #[token("DEFAULT", ignore(ascii_case))]
     DEFAULT,
     #[token("DELETE", ignore(ascii_case))]
@@ -345,6 +364,8 @@ pub enum TokenKind {
     DOY,
     #[token("DROP", ignore(ascii_case))]
     DROP,
+    #[token("DRY", ignore(ascii_case))]
+    DRY,
     #[token("EXCEPT", ignore(ascii_case))]
     EXCEPT,
     #[token("ELSE", ignore(ascii_case))]
@@ -373,14 +394,20 @@ pub enum TokenKind {
     FILE_FORMAT,
     #[token("FILES", ignore(ascii_case))]
     FILES,
+    #[token("FINAL", ignore(ascii_case))]
+    FINAL,
     #[token("FLOAT", ignore(ascii_case))]
     FLOAT,
     #[token("FLOAT32", ignore(ascii_case))]
     FLOAT32,
     #[token("FLOAT64", ignore(ascii_case))]
     FLOAT64,
+    #[token("FOLLOWING", ignore(ascii_case))]
+    FOLLOWING,
     #[token("FOR", ignore(ascii_case))]
     FOR,
+    #[token("FORCE", ignore(ascii_case))]
+    FORCE,
     #[token("FORMAT", ignore(ascii_case))]
     FORMAT,
     #[token("FROM", ignore(ascii_case))]
@@ -399,6 +426,8 @@ pub enum TokenKind {
     GRAPH,
     #[token("GROUP", ignore(ascii_case))]
     GROUP,
+    #[token("GROUPING", ignore(ascii_case))]
+    GROUPING,
     #[token("HAVING", ignore(ascii_case))]
     HAVING,
     #[token("HISTORY", ignore(ascii_case))]
@@ -453,6 +482,8 @@ pub enum TokenKind {
     KILL,
     #[token("ROLES", ignore(ascii_case))]
     ROLES,
+    #[token("ROLLUP", ignore(ascii_case))]
+    ROLLUP,
     #[token("LEADING", ignore(ascii_case))]
     LEADING,
     #[token("LEFT", ignore(ascii_case))]
@@ -467,8 +498,16 @@ pub enum TokenKind {
     MAP,
     #[token("MASTER_KEY", ignore(ascii_case))]
     MASTER_KEY,
+    #[token("MATCHED", ignore(ascii_case))]
+    MATCHED,
+    #[token("MATERIALIZED", ignore(ascii_case))]
+    MATERIALIZED,
+    #[token("MAX_FILE_SIZE", ignore(ascii_case))]
+    MAX_FILE_SIZE,
     #[token("MEMORY", ignore(ascii_case))]
     MEMORY,
+    #[token("MERGE", ignore(ascii_case))]
+    MERGE,
     #[token("METRICS", ignore(ascii_case))]
     METRICS,
     #[token("MICROSECONDS", ignore(ascii_case))]
@@ -509,10 +548,14 @@ pub enum TokenKind {
     OUTER,
     #[token("ON_ERROR", ignore(ascii_case))]
     ON_ERROR,
+    #[token("OVER", ignore(ascii_case))]
+    OVER,
     #[token("OVERWRITE", ignore(ascii_case))]
     OVERWRITE,
     #[token("PARQUET", ignore(ascii_case))]
     PARQUET,
+    #[token("PARTITION", ignore(ascii_case))]
+    PARTITION,
     #[token("PATTERN", ignore(ascii_case))]
     PATTERN,
     #[token("PIPELINE", ignore(ascii_case))]
@@ -521,6 +564,8 @@ pub enum TokenKind {
     PLAINTEXT_PASSWORD,
     #[token("POSITION", ignore(ascii_case))]
     POSITION,
+    #[token("PRECEDING", ignore(ascii_case))]
+    PRECEDING,
     #[token("PROCESSLIST", ignore(ascii_case))]
     PROCESSLIST,
     #[token("PURGE", ignore(ascii_case))]
@@ -529,8 +574,14 @@ pub enum TokenKind {
     QUARTER,
     #[token("QUERY", ignore(ascii_case))]
     QUERY,
+    #[token("RANGE", ignore(ascii_case))]
+    RANGE,
+    #[token("RECLUSTER", ignore(ascii_case))]
+    RECLUSTER,
     #[token("RECORD_DELIMITER", ignore(ascii_case))]
     RECORD_DELIMITER,
+    #[token("RECURSIVE", ignore(ascii_case))]
+    RECURSIVE,
     #[token("REGEXP", ignore(ascii_case))]
     REGEXP,
     #[token("RENAME", ignore(ascii_case))]
@@ -551,6 +602,12 @@ pub enum TokenKind {
     RIGHT,
     #[token("RLIKE", ignore(ascii_case))]
     RLIKE,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
+    #[token("ROWS", ignore(ascii_case))]
+    ROWS,
+    #[token("RUN", ignore(ascii_case))]
+    RUN,
     #[token("SCHEMA", ignore(ascii_case))]
     SCHEMA,
     #[token("SCHEMAS", ignore(ascii_case))]
@@ -563,6 +620,8 @@ pub enum TokenKind {
     SET,
     #[token("SETTINGS", ignore(ascii_case))]
     SETTINGS,
+    #[token("SETS", ignore(ascii_case))]
+    SETS,
     #[token("STAGES", ignore(ascii_case))]
     STAGES,
     #[token("SHA256_PASSWORD", ignore(ascii_case))]
@@ -639,6 +698,8 @@ pub enum TokenKind {
     UINT64,
     #[token("UINT8", ignore(ascii_case))]
     UINT8,
+    #[token("UNBOUNDED", ignore(ascii_case))]
+    UNBOUNDED,
     #[token("UNDROP", ignore(ascii_case))]
     UNDROP,
     #[token("UNSIGNED", ignore(ascii_case))]
@@ -696,6 +757,8 @@ impl TokenKind {
                 | MySQLLiteralHex
                 | LiteralInteger
                 | LiteralFloat
+                | Placeholder
+                | NumberedPlaceholder
                 | DoubleEq
                 | Eq
                 | NotEq
@@ -762,7 +825,7 @@ impl TokenKind {
             // | TokenKind::CHECK
             | TokenKind::COALESCE
             // | TokenKind::COLLATE
-            // | TokenKind::COLUMN
+            | TokenKind::COLUMN
             // | TokenKind::CONSTRAINT
             // | TokenKind::CURRENT_CATALOG
             // | TokenKind::CURRENT_DATE
@@ -899,7 +962,7 @@ impl TokenKind {
             // | TokenKind::CHECK
             // | TokenKind::COLLATE
             // | TokenKind::COLLATION
-            // | TokenKind::COLUMN
+            | TokenKind::COLUMN
             // | TokenKind::CONCURRENTLY
             // | TokenKind::CONSTRAINT
             | TokenKind::CROSS
@@ -76,6 +76,7 @@ fn test_statement() {
         r#"create table if not exists a.b (c integer default 1 not null, b varchar) as select * from t;"#,
         r#"create table a.b like c.d;"#,
         r#"create table t like t2 engine = memory;"#,
+        r#"create table t clone t2;"#,
         r#"truncate table a;"#,
         r#"truncate table "a".b;"#,
         r#"drop table a;"#,
@@ -122,6 +123,8 @@ fn test_statement() {
         r#"insert into t (c1, c2) values (1, 2), (3, 4);"#,
         r#"insert into table t format json;"#,
         r#"insert into table t select * from t2;"#,
+        r#"update t set a = a + 1, b = 2 where c > 3;"#,
+        r#"merge into t using s on a = b when matched then delete when not matched then insert values (b);"#,
         r#"select parse_json('{"k1": [0, 1, 2]}').k1[0];"#,
         r#"CREATE STAGE IF NOT EXISTS test_stage url='s3://load/files/' credentials=(aws_key_id='1a2b3c' aws_secret_key='4x5y6z') file_format=(FORMAT = CSV compression = GZIP record_delimiter=',')"#,
         r#"list @stage_a;"#,
@@ -132,6 +135,10 @@ fn test_statement() {
         r#"drop role if exists 'test'"#,
         r#"ALTER TABLE t CLUSTER BY(c1);"#,
         r#"ALTER TABLE t DROP CLUSTER KEY;"#,
+        r#"ALTER TABLE t ADD COLUMN c2 int default 1;"#,
+        r#"ALTER TABLE t DROP COLUMN c2;"#,
+        r#"ALTER TABLE t RENAME COLUMN c1 TO c2;"#,
+        r#"ALTER TABLE t RECLUSTER FINAL;"#,
         r#"ALTER DATABASE IF EXISTS catalog.c RENAME TO a;"#,
         r#"ALTER DATABASE c RENAME TO a;"#,
         r#"ALTER DATABASE catalog.c RENAME TO a;"#,
@@ -179,6 +186,19 @@ fn test_statement() {
                     skip_header = 1
                 )
                 size_limit=10;"#,
+        r#"COPY INTO mytable
+                FROM @my_stage
+                FILE_FORMAT = (
+                    type = 'CSV'
+                    field_delimiter = ','
+                    record_delimiter = '\n'
+                    skip_header = 1
+                )
+                on_error=skip_file_3
+                size_limit=10;"#,
+        r#"COPY INTO mytable
+                FROM @my_stage
+                force=true;"#,
         r#"COPY INTO 's3://mybucket/data.csv'
                 FROM mytable
                 FILE_FORMAT = (
@@ -197,6 +217,13 @@ fn test_statement() {
                     skip_header = 1
                 )
                 size_limit=10;"#,
+        r#"COPY INTO @my_stage/unload/
+                FROM mytable
+                FILE_FORMAT = (
+                    type = 'TSV'
+                    compression = 'GZIP'
+                )
+                max_file_size=1024;"#,
         r#"COPY INTO mytable
                 FROM 's3://mybucket/data.csv'
                 CREDENTIALS = (
@@ -316,6 +343,9 @@ fn test_query() {
         r#"select * from t1 union select * from t2 intersect select * from t3"#,
         r#"(select * from t1 union select * from t2) union select * from t3"#,
         r#"select * from t1 union (select * from t2 union select * from t3)"#,
+        r#"with t (a) as (select * from b) select * from t"#,
+        r#"select a from t group by rollup (a, b)"#,
+        r#"select a, b from t group by grouping sets ((a, b), a, ())"#,
     ];
 
     for case in cases {
@@ -380,6 +410,7 @@ fn test_expr() {
         r#"covar_samp(number, number)"#,
         r#"CAST(col1 AS BIGINT UNSIGNED)"#,
         r#"TRY_CAST(col1 AS BIGINT UNSIGNED)"#,
+        r#"CAST(col1 AS DECIMAL(10, 2))"#,
         r#"trim(leading 'abc' from 'def')"#,
         r#"extract(year from d)"#,
         r#"position('a' in str)"#,
@@ -407,6 +438,8 @@ fn test_expr() {
         r#"1 is distinct from 2"#,
         r#"a is distinct from b"#,
         r#"1 is not distinct from null"#,
+        r#"sum(a) over (partition by b order by c desc rows between 1 preceding and current row)"#,
+        r#"? + ? * $1"#,
     ];
 
     for case in cases {
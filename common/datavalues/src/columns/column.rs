@@ -177,7 +177,9 @@ where A: AsRef<dyn Array>
             Int8 => Arc::new(Int8Column::from_arrow_array(self.as_ref())),
             Int16 => Arc::new(Int16Column::from_arrow_array(self.as_ref())),
             Int32 | Date => Arc::new(Int32Column::from_arrow_array(self.as_ref())),
-            Int64 | Interval | Timestamp => Arc::new(Int64Column::from_arrow_array(self.as_ref())),
+            Int64 | Interval | Timestamp | Decimal => {
+                Arc::new(Int64Column::from_arrow_array(self.as_ref()))
+            }
             Float32 => Arc::new(Float32Column::from_arrow_array(self.as_ref())),
             Float64 => Arc::new(Float64Column::from_arrow_array(self.as_ref())),
             Array => Arc::new(ArrayColumn::from_arrow_array(self.as_ref())),
@@ -31,6 +31,7 @@ use common_arrow::arrow::datatypes::DataType as ArrowDataType;
 use common_arrow::arrow::datatypes::TimeUnit;
 pub use iterator::*;
 pub use mutable::*;
+use num::NumCast;
 
 use crate::prelude::*;
 
@@ -120,6 +121,26 @@ impl<T: PrimitiveType> PrimitiveColumn<T> {
                     let array = unary(array, |x| (x as usize / p) as u32, expected_arrow);
                     Self::from_arrow_array(&array)
                 }
+                ArrowDataType::Decimal(_, scale) => {
+                    let factor = 10f64.powi(*scale as i32);
+                    let array = array
+                        .as_any()
+                        .downcast_ref::<PrimitiveArray<i128>>()
+                        .expect("primitive cast should be ok");
+                    let array = unary(
+                        array,
+                        |x| {
+                            let v: Option<T> = if T::FLOATING {
+                                NumCast::from(x as f64 / factor)
+                            } else {
+                                NumCast::from(x)
+                            };
+                            v.unwrap_or_default()
+                        },
+                        expected_arrow,
+                    );
+                    Self::from_arrow_array(&array)
+                }
                 _ => unreachable!(),
             }
         } else {
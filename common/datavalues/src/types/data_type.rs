@@ -24,6 +24,7 @@ use enum_dispatch::enum_dispatch;
 use super::type_array::ArrayType;
 use super::type_boolean::BooleanType;
 use super::type_date::DateType;
+use super::type_decimal::DecimalType;
 use super::type_id::TypeID;
 use super::type_nullable::NullableType;
 use super::type_primitive::Float32Type;
@@ -71,6 +72,7 @@ pub enum DataTypeImpl {
     VariantArray(VariantArrayType),
     VariantObject(VariantObjectType),
     Interval(IntervalType),
+    Decimal(DecimalType),
 }
 
 #[enum_dispatch]
@@ -187,6 +189,9 @@ pub fn from_arrow_type(dt: &ArrowType) -> DataTypeImpl {
 
         ArrowType::Timestamp(_, _) => DataTypeImpl::Timestamp(TimestampType::create(0)),
         ArrowType::Date32 | ArrowType::Date64 => DataTypeImpl::Date(DateType::default()),
+        ArrowType::Decimal(precision, scale) if *precision <= DECIMAL_MAX_PRECISION => {
+            DataTypeImpl::Decimal(DecimalType::create(*precision, *scale))
+        }
 
         ArrowType::Struct(fields) => {
             let names = fields.iter().map(|f| f.name.clone()).collect();
@@ -208,6 +213,38 @@ pub fn from_arrow_type(dt: &ArrowType) -> DataTypeImpl {
     }
 }
 
+/// Like `from_arrow_type`, but for the arrow types that come from outside (e.g. the schema of
+/// an external file), which may not be representable, such as DECIMALs of a precision larger
+/// than `DECIMAL_MAX_PRECISION`.
+pub fn try_from_arrow_type(dt: &ArrowType) -> Result<DataTypeImpl> {
+    check_arrow_type(dt)?;
+    Ok(from_arrow_type(dt))
+}
+
+/// Like `from_arrow_field`, but returns an error for the unrepresentable arrow types.
+pub fn try_from_arrow_field(f: &ArrowField) -> Result<DataTypeImpl> {
+    if f.metadata.get(ARROW_EXTENSION_NAME).is_none() {
+        check_arrow_type(f.data_type())?;
+    }
+    Ok(from_arrow_field(f))
+}
+
+fn check_arrow_type(dt: &ArrowType) -> Result<()> {
+    match dt {
+        ArrowType::Decimal(precision, scale) => {
+            DecimalType::try_create(*precision, *scale)?;
+        }
+        ArrowType::LargeList(f) => check_arrow_type(f.data_type())?,
+        ArrowType::Struct(fields) => {
+            for f in fields {
+                check_arrow_type(f.data_type())?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 pub fn from_arrow_field(f: &ArrowField) -> DataTypeImpl {
     if let Some(custom_name) = f.metadata.get(ARROW_EXTENSION_NAME) {
         let metadata = f.metadata.get(ARROW_EXTENSION_META).cloned();
@@ -222,6 +259,15 @@ pub fn from_arrow_field(f: &ArrowField) -> DataTypeImpl {
                 None => return TimestampType::new_impl(0),
             },
             "Interval" => return IntervalType::new_impl(metadata.unwrap().into()),
+            "Decimal" => {
+                if let Some((precision, scale)) = metadata.as_ref().and_then(|m| m.split_once(','))
+                {
+                    return DecimalType::new_impl(
+                        precision.parse().unwrap(),
+                        scale.parse().unwrap(),
+                    );
+                }
+            }
             "Variant" => return VariantType::new_impl(),
             "VariantArray" => return VariantArrayType::new_impl(),
             "VariantObject" => return VariantObjectType::new_impl(),
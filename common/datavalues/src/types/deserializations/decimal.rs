@@ -0,0 +1,169 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::*;
+use common_io::prelude::*;
+
+use crate::columns::MutableColumn;
+use crate::prelude::*;
+
+pub struct DecimalDeserializer {
+    pub builder: MutablePrimitiveColumn<i64>,
+    pub data_type: DecimalType,
+}
+
+impl DecimalDeserializer {
+    fn parse(&self, text: &[u8]) -> Result<i64> {
+        self.data_type.parse_value(text).ok_or_else(|| {
+            ErrorCode::BadBytes(format!(
+                "Cannot parse value:{:?} to {}",
+                String::from_utf8_lossy(text),
+                self.data_type.name()
+            ))
+        })
+    }
+
+    fn read_text<R: BufferRead>(&self, reader: &mut NestedCheckpointReader<R>) -> Result<i64> {
+        let mut buf = Vec::with_capacity(20);
+        reader.keep_read(&mut buf, |f| {
+            f.is_ascii_digit() || matches!(f, b'.' | b'-' | b'+' | b'e' | b'E')
+        })?;
+        self.parse(&buf)
+    }
+
+    fn check(&self, value: i64) -> Result<i64> {
+        self.data_type.check_unscaled(value as i128).ok_or_else(|| {
+            ErrorCode::Overflow(format!(
+                "Decimal value {} overflows {}",
+                value,
+                self.data_type.name()
+            ))
+        })
+    }
+}
+
+impl TypeDeserializer for DecimalDeserializer {
+    fn de_binary(&mut self, reader: &mut &[u8], _format: &FormatSettings) -> Result<()> {
+        let value: i64 = reader.read_scalar()?;
+        let value = self.check(value)?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_default(&mut self, _format: &FormatSettings) {
+        self.builder.append_value(i64::default());
+    }
+
+    fn de_fixed_binary_batch(
+        &mut self,
+        reader: &[u8],
+        step: usize,
+        rows: usize,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        for row in 0..rows {
+            let mut reader = &reader[step * row..];
+            let value: i64 = reader.read_scalar()?;
+            let value = self.check(value)?;
+            self.builder.append_value(value);
+        }
+        Ok(())
+    }
+
+    fn de_json(&mut self, value: &serde_json::Value, _format: &FormatSettings) -> Result<()> {
+        let value = match value {
+            serde_json::Value::Number(v) => self.parse(v.to_string().as_bytes())?,
+            serde_json::Value::String(v) => self.parse(v.as_bytes())?,
+            _ => {
+                return Err(ErrorCode::BadBytes(
+                    "Incorrect json value, must be number or string",
+                ));
+            }
+        };
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_whole_text(&mut self, reader: &[u8], _format: &FormatSettings) -> Result<()> {
+        let value = self.parse(reader)?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_text<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let value = self.read_text(reader)?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_text_csv<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let maybe_quote = reader.ignore(|f| f == b'\'' || f == b'"')?;
+        let value = self.read_text(reader)?;
+        if maybe_quote {
+            reader.must_ignore(|f| f == b'\'' || f == b'"')?;
+        }
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_text_json<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let maybe_quote = reader.ignore_byte(b'"')?;
+        let value = self.read_text(reader)?;
+        if maybe_quote {
+            reader.must_ignore_byte(b'"')?;
+        }
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_text_quoted<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let maybe_quote = reader.ignore_byte(b'\'')?;
+        let value = self.read_text(reader)?;
+        if maybe_quote {
+            reader.must_ignore_byte(b'\'')?;
+        }
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn append_data_value(&mut self, value: DataValue, _format: &FormatSettings) -> Result<()> {
+        let value = self.check(value.as_i64()?)?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn pop_data_value(&mut self) -> Result<DataValue> {
+        self.builder.pop_data_value()
+    }
+
+    fn finish_to_column(&mut self) -> ColumnRef {
+        self.builder.to_column()
+    }
+}
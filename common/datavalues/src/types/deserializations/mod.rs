@@ -22,6 +22,7 @@ use crate::prelude::*;
 mod array;
 mod boolean;
 mod date;
+mod decimal;
 mod null;
 mod nullable;
 mod number;
@@ -33,6 +34,7 @@ mod variant;
 pub use array::*;
 pub use boolean::*;
 pub use date::*;
+pub use decimal::*;
 pub use null::*;
 pub use nullable::*;
 pub use number::*;
@@ -121,6 +123,7 @@ pub enum TypeDeserializerImpl {
     Date(DateDeserializer<i32>),
     Interval(DateDeserializer<i64>),
     Timestamp(TimestampDeserializer),
+    Decimal(DecimalDeserializer),
     String(StringDeserializer),
     Struct(StructDeserializer),
     Variant(VariantDeserializer),
@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use super::type_array::ArrayType;
+use super::type_decimal::DecimalType;
 use super::type_nullable::NullableType;
 use super::type_struct::StructType;
 use super::type_timestamp::TimestampType;
@@ -60,6 +61,13 @@ pub fn equal(lhs: &DataTypeImpl, rhs: &DataTypeImpl) -> bool {
             lhs.precision() == rhs.precision()
         }
 
+        Decimal => {
+            let lhs: DecimalType = lhs.to_owned().try_into().unwrap();
+            let rhs: DecimalType = rhs.to_owned().try_into().unwrap();
+
+            lhs == rhs
+        }
+
         Nullable => {
             let lhs: NullableType = lhs.to_owned().try_into().unwrap();
             let rhs: NullableType = rhs.to_owned().try_into().unwrap();
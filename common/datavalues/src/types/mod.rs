@@ -18,6 +18,7 @@ pub mod type_array;
 pub mod type_boolean;
 pub mod type_coercion;
 pub mod type_date;
+pub mod type_decimal;
 pub mod type_interval;
 pub mod type_null;
 pub mod type_nullable;
@@ -48,6 +49,7 @@ pub use serializations::TypeSerializerImpl;
 pub use type_array::*;
 pub use type_boolean::*;
 pub use type_date::*;
+pub use type_decimal::*;
 pub use type_factory::*;
 pub use type_id::*;
 pub use type_interval::*;
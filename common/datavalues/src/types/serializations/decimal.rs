@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use opensrv_clickhouse::types::column::ArcColumnData;
+use opensrv_clickhouse::types::column::ArcColumnWrapper;
+use opensrv_clickhouse::types::column::ColumnFrom;
+use serde_json::Value;
+
+use crate::ColumnRef;
+use crate::DecimalType;
+use crate::PrimitiveColumn;
+use crate::Series;
+use crate::TypeSerializer;
+
+#[derive(Debug, Clone)]
+pub struct DecimalSerializer<'a> {
+    pub(crate) data_type: DecimalType,
+    pub(crate) values: &'a [i64],
+}
+
+impl<'a> DecimalSerializer<'a> {
+    pub fn try_create(data_type: DecimalType, col: &'a ColumnRef) -> Result<Self> {
+        let col: &PrimitiveColumn<i64> = Series::check_get(col)?;
+        Ok(Self {
+            data_type,
+            values: col.values(),
+        })
+    }
+}
+
+impl<'a> TypeSerializer<'a> for DecimalSerializer<'a> {
+    fn need_quote(&self) -> bool {
+        false
+    }
+
+    fn write_field(&self, row_index: usize, buf: &mut Vec<u8>, _format: &FormatSettings) {
+        let s = self.data_type.format_value(self.values[row_index]);
+        buf.extend_from_slice(s.as_bytes())
+    }
+
+    // the decimals are kept as strings in JSON, since they may not be represented by floats
+    fn serialize_json_values(&self, _format: &FormatSettings) -> Result<Vec<Value>> {
+        let result: Vec<Value> = self
+            .values
+            .iter()
+            .map(|v| Value::String(self.data_type.format_value(*v)))
+            .collect();
+        Ok(result)
+    }
+
+    fn serialize_clickhouse_const(
+        &self,
+        _format: &FormatSettings,
+        size: usize,
+    ) -> Result<ArcColumnData> {
+        let strings: Vec<String> = self
+            .values
+            .iter()
+            .map(|v| self.data_type.format_value(*v))
+            .collect();
+        let mut values: Vec<&[u8]> = Vec::with_capacity(self.values.len() * size);
+        for _ in 0..size {
+            for v in strings.iter() {
+                values.push(v.as_bytes())
+            }
+        }
+        Ok(Vec::column_from::<ArcColumnWrapper>(values))
+    }
+
+    fn serialize_clickhouse_column(&self, _format: &FormatSettings) -> Result<ArcColumnData> {
+        let strings: Vec<String> = self
+            .values
+            .iter()
+            .map(|v| self.data_type.format_value(*v))
+            .collect();
+        let values: Vec<&[u8]> = strings.iter().map(|v| v.as_bytes()).collect();
+        Ok(Vec::column_from::<ArcColumnWrapper>(values))
+    }
+
+    fn serialize_json_object(
+        &self,
+        _valids: Option<&Bitmap>,
+        format: &FormatSettings,
+    ) -> Result<Vec<Value>> {
+        self.serialize_json_values(format)
+    }
+}
@@ -16,6 +16,7 @@ mod array;
 mod boolean;
 mod const_;
 mod date;
+mod decimal;
 pub mod helper;
 mod null;
 mod nullable;
@@ -34,6 +35,7 @@ use common_exception::ToErrorCode;
 use common_io::prelude::FormatSettings;
 pub use const_::ConstSerializer;
 pub use date::DateSerializer;
+pub use decimal::DecimalSerializer;
 use enum_dispatch::enum_dispatch;
 pub use helper::escape::write_escaped_string;
 pub use helper::json::write_json_string;
@@ -149,6 +151,7 @@ pub enum TypeSerializerImpl<'a> {
     Date(DateSerializer<'a, i32>),
     Interval(DateSerializer<'a, i64>),
     Timestamp(TimestampSerializer<'a>),
+    Decimal(DecimalSerializer<'a>),
     String(StringSerializer<'a>),
     Array(ArraySerializer<'a>),
     Struct(StructSerializer<'a>),
@@ -148,6 +148,36 @@ pub fn numerical_coercion(
     construct_numeric_type(has_signed, has_float, max_size)
 }
 
+/// Returns the type to hold the values of both types, one of which is decimal and the other
+/// is decimal or numeric. The decimals are coerced to Float64 with floats, and to the decimal
+/// with enough integral digits and scale with the others.
+pub fn decimal_coercion(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Result<DataTypeImpl> {
+    let lhs_id = lhs_type.data_type_id();
+    let rhs_id = rhs_type.data_type_id();
+    if lhs_id.is_floating() || rhs_id.is_floating() {
+        return Ok(Float64Type::new_impl());
+    }
+
+    let to_decimal = |data_type: &DataTypeImpl| -> Result<DecimalType> {
+        match data_type {
+            DataTypeImpl::Decimal(v) => Ok(*v),
+            _ => DecimalType::from_integer(data_type.data_type_id()).ok_or_else(|| {
+                ErrorCode::BadDataValueType(format!(
+                    "Can't construct type from {:?} and {:?}",
+                    lhs_type, rhs_type
+                ))
+            }),
+        }
+    };
+    let lhs = to_decimal(lhs_type)?;
+    let rhs = to_decimal(rhs_type)?;
+
+    let scale = cmp::max(lhs.scale(), rhs.scale());
+    let integral_digits = cmp::max(lhs.precision() - lhs.scale(), rhs.precision() - rhs.scale());
+    let precision = cmp::min(integral_digits + scale, DECIMAL_MAX_PRECISION);
+    Ok(DecimalType::new_impl(precision, scale))
+}
+
 #[inline]
 pub fn numerical_arithmetic_coercion(
     op: &DataValueBinaryOperator,
@@ -241,6 +271,12 @@ pub fn compare_coercion(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Res
         return numerical_coercion(lhs_type, rhs_type, true);
     }
 
+    if (lhs_id.is_decimal() && (rhs_id.is_numeric() || rhs_id.is_decimal()))
+        || (rhs_id.is_decimal() && lhs_id.is_numeric())
+    {
+        return decimal_coercion(lhs_type, rhs_type);
+    }
+
     //  one of is nothing
     {
         if lhs_id == TypeID::Null {
@@ -253,7 +289,9 @@ pub fn compare_coercion(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Res
     }
 
     // one of is String and other is number
-    if (lhs_id.is_numeric() && rhs_id.is_string()) || (rhs_id.is_numeric() && lhs_id.is_string()) {
+    if ((lhs_id.is_numeric() || lhs_id.is_decimal()) && rhs_id.is_string())
+        || ((rhs_id.is_numeric() || rhs_id.is_decimal()) && lhs_id.is_string())
+    {
         return Ok(Float64Type::new_impl());
     }
 
@@ -362,6 +400,10 @@ pub fn merge_types(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Result<D
             }
             if lhs_id.is_numeric() && rhs_id.is_numeric() {
                 numerical_coercion(lhs_type, rhs_type, false)
+            } else if (lhs_id.is_decimal() || lhs_id.is_numeric())
+                && (rhs_id.is_decimal() || rhs_id.is_numeric())
+            {
+                decimal_coercion(lhs_type, rhs_type)
             } else {
                 Result::Err(ErrorCode::BadDataValueType(format!(
                     "Can't merge types from {:?} and {:?}",
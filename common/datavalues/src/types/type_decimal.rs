@@ -0,0 +1,309 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use rand::prelude::*;
+
+use super::data_type::DataType;
+use super::data_type::ARROW_EXTENSION_META;
+use super::data_type::ARROW_EXTENSION_NAME;
+use super::type_id::TypeID;
+use crate::prelude::*;
+use crate::serializations::DecimalSerializer;
+use crate::serializations::TypeSerializerImpl;
+
+/// The unscaled values of decimal are stored as Int64, thus at most 18 digits.
+pub const DECIMAL_MAX_PRECISION: usize = 18;
+/// The precision of `DECIMAL` without arguments, the same as MySQL.
+pub const DECIMAL_DEFAULT_PRECISION: usize = 10;
+
+/// Returns 10 to the power of `exp`, or [None] if it overflows.
+#[inline]
+pub fn decimal_pow10(exp: usize) -> Option<i128> {
+    10i128.checked_pow(exp as u32)
+}
+
+/// Divides and rounds half away from zero, the rounding of SQL standard.
+#[inline]
+pub fn decimal_div_round(n: i128, d: i128) -> i128 {
+    let q = n / d;
+    let r = n % d;
+    if r.unsigned_abs() * 2 >= d.unsigned_abs() {
+        if (n < 0) == (d < 0) {
+            q + 1
+        } else {
+            q - 1
+        }
+    } else {
+        q
+    }
+}
+
+/// Decimal(P, S) stores a number of P digits, S of which are after the decimal point.
+/// Underneath it is stored as Int64, the number multiplied by 10^S.
+#[derive(Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct DecimalType {
+    precision: usize,
+    scale: usize,
+}
+
+impl DecimalType {
+    pub fn try_create(precision: usize, scale: usize) -> Result<Self> {
+        if precision == 0 || precision > DECIMAL_MAX_PRECISION {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "The precision of DECIMAL(P, S) only ranges from 1~{}, {} is invalid",
+                DECIMAL_MAX_PRECISION, precision
+            )));
+        }
+        if scale > precision {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "The scale of DECIMAL(P, S) only ranges from 0~P, {} is invalid for precision {}",
+                scale, precision
+            )));
+        }
+        Ok(DecimalType { precision, scale })
+    }
+
+    pub fn create(precision: usize, scale: usize) -> Self {
+        Self::try_create(precision, scale).unwrap()
+    }
+
+    pub fn new_impl(precision: usize, scale: usize) -> DataTypeImpl {
+        DataTypeImpl::Decimal(Self::create(precision, scale))
+    }
+
+    /// Returns the decimal type to hold the values of the integer type, e.g. Decimal(10, 0) for Int32.
+    /// The 64-bit integers are treated as Decimal(18, 0), the values beyond which overflow.
+    pub fn from_integer(type_id: TypeID) -> Option<Self> {
+        let precision = match type_id {
+            TypeID::Int8 | TypeID::UInt8 => 3,
+            TypeID::Int16 | TypeID::UInt16 => 5,
+            TypeID::Int32 | TypeID::UInt32 => 10,
+            TypeID::Int64 | TypeID::UInt64 => DECIMAL_MAX_PRECISION,
+            _ => return None,
+        };
+        Some(DecimalType {
+            precision,
+            scale: 0,
+        })
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// The largest unscaled value, i.e. P nines.
+    #[inline]
+    pub fn max_unscaled(&self) -> i64 {
+        10i64.pow(self.precision as u32) - 1
+    }
+
+    /// Checks that the unscaled value fits in the precision.
+    #[inline]
+    pub fn check_unscaled(&self, v: i128) -> Option<i64> {
+        let max = self.max_unscaled() as i128;
+        if (-max..=max).contains(&v) {
+            Some(v as i64)
+        } else {
+            None
+        }
+    }
+
+    /// Converts the unscaled value of another scale to this type, rounding if the scale decreases.
+    #[inline]
+    pub fn rescale(&self, v: i128, from_scale: usize) -> Option<i64> {
+        let v = if from_scale <= self.scale {
+            v.checked_mul(decimal_pow10(self.scale - from_scale)?)?
+        } else {
+            match decimal_pow10(from_scale - self.scale) {
+                Some(d) => decimal_div_round(v, d),
+                None => 0,
+            }
+        };
+        self.check_unscaled(v)
+    }
+
+    #[inline]
+    pub fn from_f64(&self, v: f64) -> Option<i64> {
+        let v = (v * 10f64.powi(self.scale as i32)).round();
+        if !v.is_finite() || v.abs() > self.max_unscaled() as f64 {
+            return None;
+        }
+        Some(v as i64)
+    }
+
+    #[inline]
+    pub fn to_f64(&self, v: i64) -> f64 {
+        v as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    /// Parses the text like `-123.45` or `1.2345e2`, rounding the digits beyond the scale.
+    pub fn parse_value(&self, s: &[u8]) -> Option<i64> {
+        let (negative, s) = match s.first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (mantissa, exp) = match s.iter().position(|c| *c == b'e' || *c == b'E') {
+            Some(pos) => {
+                let exp = std::str::from_utf8(&s[pos + 1..])
+                    .ok()?
+                    .parse::<i32>()
+                    .ok()?;
+                (&s[..pos], exp)
+            }
+            None => (s, 0),
+        };
+
+        let mut digits = 0i128;
+        let mut frac_digits = 0i32;
+        let mut has_digit = false;
+        let mut has_point = false;
+        for c in mantissa {
+            match c {
+                b'0'..=b'9' => {
+                    has_digit = true;
+                    // the fractional digits beyond 36 digits could not affect the result
+                    if digits >= 10i128.pow(36) {
+                        if has_point {
+                            continue;
+                        }
+                        return None;
+                    }
+                    digits = digits * 10 + (c - b'0') as i128;
+                    if has_point {
+                        frac_digits += 1;
+                    }
+                }
+                b'.' if !has_point => has_point = true,
+                _ => return None,
+            }
+        }
+        if !has_digit {
+            return None;
+        }
+
+        let digits = if negative { -digits } else { digits };
+        let shift = exp - frac_digits + self.scale as i32;
+        let v = if shift >= 0 {
+            digits.checked_mul(decimal_pow10(shift as usize)?)?
+        } else {
+            match decimal_pow10((-shift) as usize) {
+                Some(d) => decimal_div_round(digits, d),
+                None => 0,
+            }
+        };
+        self.check_unscaled(v)
+    }
+
+    pub fn format_value(&self, v: i64) -> String {
+        if self.scale == 0 {
+            return v.to_string();
+        }
+        let factor = 10u64.pow(self.scale as u32);
+        let abs = v.unsigned_abs();
+        format!(
+            "{}{}.{:0width$}",
+            if v < 0 { "-" } else { "" },
+            abs / factor,
+            abs % factor,
+            width = self.scale
+        )
+    }
+}
+
+impl DataType for DecimalType {
+    fn data_type_id(&self) -> TypeID {
+        TypeID::Decimal
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> String {
+        format!("Decimal({}, {})", self.precision, self.scale)
+    }
+
+    fn default_value(&self) -> DataValue {
+        DataValue::Int64(0)
+    }
+
+    fn random_value(&self) -> DataValue {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        let max = self.max_unscaled();
+        DataValue::Int64(rng.gen_range(-max..=max))
+    }
+
+    fn create_constant_column(&self, data: &DataValue, size: usize) -> Result<ColumnRef> {
+        let value = data.as_i64()?;
+        let column = Series::from_data(&[value]);
+        Ok(Arc::new(ConstColumn::new(column, size)))
+    }
+
+    fn create_column(&self, data: &[DataValue]) -> Result<ColumnRef> {
+        let value = data
+            .iter()
+            .map(|v| v.as_i64())
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Series::from_data(&value))
+    }
+
+    fn arrow_type(&self) -> ArrowType {
+        ArrowType::Int64
+    }
+
+    fn custom_arrow_meta(&self) -> Option<BTreeMap<String, String>> {
+        let mut mp = BTreeMap::new();
+        mp.insert(ARROW_EXTENSION_NAME.to_string(), "Decimal".to_string());
+        mp.insert(
+            ARROW_EXTENSION_META.to_string(),
+            format!("{},{}", self.precision, self.scale),
+        );
+        Some(mp)
+    }
+
+    fn create_serializer_inner<'a>(&self, col: &'a ColumnRef) -> Result<TypeSerializerImpl<'a>> {
+        Ok(DecimalSerializer::<'a>::try_create(*self, col)?.into())
+    }
+
+    fn create_deserializer(&self, capacity: usize) -> TypeDeserializerImpl {
+        DecimalDeserializer {
+            builder: MutablePrimitiveColumn::<i64>::with_capacity(capacity),
+            data_type: *self,
+        }
+        .into()
+    }
+
+    fn create_mutable(&self, capacity: usize) -> Box<dyn MutableColumn> {
+        Box::new(MutablePrimitiveColumn::<i64>::with_capacity(capacity))
+    }
+}
+
+impl std::fmt::Debug for DecimalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -60,6 +60,15 @@ static TYPE_FACTORY: Lazy<Arc<TypeFactory>> = Lazy::new(|| {
         }
     }
 
+    // Decimal is a special case too, the conversion functions of which are not registered
+    {
+        for precision in 1..=DECIMAL_MAX_PRECISION {
+            for scale in 0..=precision {
+                type_factory.register_without_conversion(DecimalType::new_impl(precision, scale));
+            }
+        }
+    }
+
     type_factory.add_array_wrapper();
     type_factory.add_nullable_wrapper();
 
@@ -108,6 +117,11 @@ impl TypeFactory {
         }
     }
 
+    pub fn register_without_conversion(&mut self, data_type: DataTypeImpl) {
+        self.case_insensitive_types
+            .insert(data_type.name().to_lowercase(), data_type);
+    }
+
     pub fn add_array_wrapper(&mut self) {
         let mut arrays = HashMap::new();
         for (k, v) in self.case_insensitive_types.iter() {
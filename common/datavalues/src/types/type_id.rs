@@ -59,6 +59,10 @@ pub enum TypeID {
     /// Underneath Interval is stored as int64, so it supports negative values.
     Interval,
 
+    /// Decimal(P, S) is a number of P digits, S of which are after the decimal point.
+    /// Underneath it's stored as int64, the number multiplied by 10^S.
+    Decimal,
+
     Array,
     Struct,
 
@@ -146,6 +150,11 @@ impl TypeID {
         self.is_integer() || self.is_floating()
     }
 
+    #[inline]
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, TypeID::Decimal)
+    }
+
     #[inline]
     pub fn is_interval(&self) -> bool {
         matches!(self, TypeID::Interval)
@@ -208,7 +217,7 @@ impl TypeID {
             Int16 => PhysicalTypeID::Int16,
 
             Int32 | Date => PhysicalTypeID::Int32,
-            Int64 | Interval | Timestamp => PhysicalTypeID::Int64,
+            Int64 | Interval | Timestamp | Decimal => PhysicalTypeID::Int64,
 
             UInt8 => PhysicalTypeID::UInt8,
             UInt16 => PhysicalTypeID::UInt16,
@@ -0,0 +1,133 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_decimal_type() -> Result<()> {
+    assert!(DecimalType::try_create(0, 0).is_err());
+    assert!(DecimalType::try_create(19, 2).is_err());
+    assert!(DecimalType::try_create(2, 3).is_err());
+
+    let decimal = DecimalType::create(10, 2);
+    assert_eq!(decimal.name(), "Decimal(10, 2)");
+    assert_eq!(decimal.max_unscaled(), 9_999_999_999);
+
+    let data_type = from_arrow_field(&decimal.to_arrow_field("a"));
+    assert_eq!(data_type, DecimalType::new_impl(10, 2));
+    Ok(())
+}
+
+#[test]
+fn test_decimal_from_external_arrow_type() -> Result<()> {
+    let data_type = try_from_arrow_type(&ArrowType::Decimal(18, 2))?;
+    assert_eq!(data_type, DecimalType::new_impl(18, 2));
+
+    // DECIMALs of a larger precision are not representable
+    let r = try_from_arrow_type(&ArrowType::Decimal(38, 2));
+    assert_eq!(
+        r.unwrap_err().message(),
+        "The precision of DECIMAL(P, S) only ranges from 1~18, 38 is invalid"
+    );
+
+    let inner = ArrowField::new("item", ArrowType::Decimal(20, 0), true);
+    assert!(try_from_arrow_type(&ArrowType::LargeList(Box::new(inner.clone()))).is_err());
+    assert!(try_from_arrow_type(&ArrowType::Struct(vec![inner.clone()])).is_err());
+    assert!(try_from_arrow_field(&inner).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decimal_parse_and_format() -> Result<()> {
+    struct Test {
+        input: &'static str,
+        expect: Option<&'static str>,
+    }
+
+    let decimal = DecimalType::create(5, 2);
+    let tests = vec![
+        Test {
+            input: "1.2",
+            expect: Some("1.20"),
+        },
+        Test {
+            input: "+001.23",
+            expect: Some("1.23"),
+        },
+        Test {
+            input: "1.235",
+            expect: Some("1.24"),
+        },
+        Test {
+            input: "-1.235",
+            expect: Some("-1.24"),
+        },
+        Test {
+            input: "-0.004",
+            expect: Some("0.00"),
+        },
+        Test {
+            input: "1.5e2",
+            expect: Some("150.00"),
+        },
+        Test {
+            input: "15E-3",
+            expect: Some("0.02"),
+        },
+        Test {
+            input: "999.994",
+            expect: Some("999.99"),
+        },
+        Test {
+            input: "999.995",
+            expect: None,
+        },
+        Test {
+            input: "1.2.3",
+            expect: None,
+        },
+        Test {
+            input: "abc",
+            expect: None,
+        },
+        Test {
+            input: "",
+            expect: None,
+        },
+    ];
+
+    for test in tests {
+        let value = decimal.parse_value(test.input.as_bytes());
+        let value = value.map(|v| decimal.format_value(v));
+        assert_eq!(value.as_deref(), test.expect, "parse {}", test.input);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_decimal_rescale() -> Result<()> {
+    let decimal = DecimalType::create(5, 2);
+    assert_eq!(decimal.rescale(12345, 3), Some(1235));
+    assert_eq!(decimal.rescale(-12345, 3), Some(-1235));
+    assert_eq!(decimal.rescale(12, 0), Some(1200));
+    assert_eq!(decimal.rescale(1000, 0), None);
+    assert_eq!(decimal.from_f64(1.5), Some(150));
+    assert_eq!(decimal.from_f64(f64::NAN), None);
+    assert_eq!(decimal.to_f64(-150), -1.5);
+    Ok(())
+}
@@ -14,6 +14,7 @@
 
 mod create_column;
 mod date_ts_bound;
+mod decimal;
 mod deserializations;
 mod serializations;
 mod viewer;
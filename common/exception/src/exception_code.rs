@@ -158,6 +158,9 @@ build_exceptions! {
 
     TableInfoError(1106),
     ReadTableDataError(1107),
+
+    // Prepared statement error codes.
+    UnknownPreparedStatement(1108),
 }
 
 // Metasvr errors [2001, 3000].
@@ -172,6 +175,7 @@ build_exceptions! {
     OCCRetryFailure(2011),
     TableNotWritable(2012),
     TableHistoricalDataNotFound(2013),
+    TableWriteConflict(2014),
 
     // User api error codes.
     UnknownUser(2201),
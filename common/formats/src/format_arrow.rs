@@ -0,0 +1,262 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ListArray;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::compute::cast;
+use common_arrow::arrow::compute::cast::CastOptions;
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::VariantValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use serde_json::Value as JsonValue;
+
+/// Builds a block of `schema` from a chunk read with the file's own schema.
+///
+/// Columns are matched by name (case-insensitive), so the file may order its fields
+/// differently or carry extra ones. Table columns missing from the file are filled
+/// with their type's default value, primitive columns are cast to the table type,
+/// and any column can be loaded into a variant column as JSON.
+pub fn resolve_chunk<A: AsRef<dyn Array>>(
+    schema: &DataSchemaRef,
+    file_fields: &[ArrowField],
+    chunk: &Chunk<A>,
+) -> Result<DataBlock> {
+    let num_rows = chunk.len();
+    let mut columns = Vec::with_capacity(schema.num_fields());
+
+    for field in schema.fields() {
+        let position = file_fields
+            .iter()
+            .position(|f| f.name.eq_ignore_ascii_case(field.name()));
+
+        let column = match position {
+            None => {
+                let data_type = field.data_type();
+                data_type
+                    .create_constant_column(&data_type.default_value(), num_rows)?
+                    .convert_full_column()
+            }
+            Some(idx) => {
+                let array = normalize_array(chunk.columns()[idx].as_ref())?;
+                if is_variant(field) {
+                    let values = array_to_json(&array)?
+                        .into_iter()
+                        .map(VariantValue::from)
+                        .collect::<Vec<_>>();
+                    let column = VariantColumn::new_from_vec(values).arc();
+                    match field.is_nullable() {
+                        true => NullableColumn::wrap_inner(column, array.validity().cloned()),
+                        false => column,
+                    }
+                } else {
+                    let array = cast_to_field(array, field)?;
+                    match field.is_nullable() {
+                        true => array.into_nullable_column(),
+                        false => array.into_column(),
+                    }
+                }
+            }
+        };
+        columns.push(column);
+    }
+
+    Ok(DataBlock::create(schema.clone(), columns))
+}
+
+// Rewrites the array into the arrow layout datavalues expects:
+// lists use i64 offsets and dictionaries are unpacked into their values.
+pub fn normalize_array(array: &dyn Array) -> Result<Arc<dyn Array>> {
+    match array.data_type() {
+        ArrowType::Struct(fields) => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let values = array
+                .values()
+                .iter()
+                .map(|v| normalize_array(v.as_ref()))
+                .collect::<Result<Vec<_>>>()?;
+            let fields = fields
+                .iter()
+                .zip(values.iter())
+                .map(|(f, v)| ArrowField::new(&f.name, v.data_type().clone(), f.is_nullable))
+                .collect();
+            Ok(Arc::new(StructArray::from_data(
+                ArrowType::Struct(fields),
+                values,
+                array.validity().cloned(),
+            )))
+        }
+        ArrowType::List(field) => {
+            let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+            let offsets = array
+                .offsets()
+                .iter()
+                .map(|o| *o as i64)
+                .collect::<Vec<_>>();
+            let values = normalize_array(array.values().as_ref())?;
+            Ok(Arc::new(ListArray::<i64>::from_data(
+                large_list_type(field, values.as_ref()),
+                offsets.into(),
+                values,
+                array.validity().cloned(),
+            )))
+        }
+        ArrowType::LargeList(field) => {
+            let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+            let values = normalize_array(array.values().as_ref())?;
+            Ok(Arc::new(ListArray::<i64>::from_data(
+                large_list_type(field, values.as_ref()),
+                array.offsets().clone(),
+                values,
+                array.validity().cloned(),
+            )))
+        }
+        ArrowType::Dictionary(_, values_type, _) => cast_array(array, values_type.as_ref()),
+        ArrowType::FixedSizeBinary(_) => cast_array(array, &ArrowType::Binary),
+        _ => cast_array(array, array.data_type()),
+    }
+}
+
+fn large_list_type(field: &ArrowField, values: &dyn Array) -> ArrowType {
+    let inner = ArrowField::new(&field.name, values.data_type().clone(), field.is_nullable);
+    ArrowType::LargeList(Box::new(inner))
+}
+
+pub fn cast_to_field(array: Arc<dyn Array>, field: &DataField) -> Result<Arc<dyn Array>> {
+    let expected = remove_nullable(field.data_type());
+    let actual = try_from_arrow_type(array.data_type())?;
+    if actual == expected {
+        return Ok(array);
+    }
+
+    match expected.data_type_id() {
+        TypeID::Array | TypeID::Struct => Err(ErrorCode::BadBytes(format!(
+            "Cannot resolve column '{}': file type {} does not match table type {}",
+            field.name(),
+            actual.name(),
+            expected.name()
+        ))),
+        _ => cast_array(array.as_ref(), &expected.arrow_type()).map_err(|e| {
+            ErrorCode::BadBytes(format!(
+                "Cannot resolve column '{}': {}",
+                field.name(),
+                e.message()
+            ))
+        }),
+    }
+}
+
+fn is_variant(field: &DataField) -> bool {
+    matches!(
+        remove_nullable(field.data_type()).data_type_id(),
+        TypeID::Variant | TypeID::VariantArray | TypeID::VariantObject
+    )
+}
+
+// Converts every row of a normalized array into JSON: structs become objects keyed by
+// the field names, lists become arrays and strings are parsed as JSON documents, or kept
+// as JSON strings if they are not.
+fn array_to_json(array: &Arc<dyn Array>) -> Result<Vec<JsonValue>> {
+    let mut values = match array.data_type() {
+        ArrowType::Struct(fields) => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let children = array
+                .values()
+                .iter()
+                .map(array_to_json)
+                .collect::<Result<Vec<_>>>()?;
+            (0..array.len())
+                .map(|row| {
+                    let object = fields
+                        .iter()
+                        .zip(children.iter())
+                        .map(|(f, values)| (f.name.clone(), values[row].clone()))
+                        .collect();
+                    JsonValue::Object(object)
+                })
+                .collect()
+        }
+        ArrowType::LargeList(_) => {
+            let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+            let items = array_to_json(array.values())?;
+            array
+                .offsets()
+                .windows(2)
+                .map(|w| JsonValue::Array(items[w[0] as usize..w[1] as usize].to_vec()))
+                .collect()
+        }
+        ArrowType::Utf8 | ArrowType::LargeUtf8 => {
+            let array = cast_array(array.as_ref(), &ArrowType::LargeUtf8)?;
+            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
+            array
+                .iter()
+                .map(|v| match v {
+                    Some(v) => {
+                        serde_json::from_str(v).unwrap_or_else(|_| JsonValue::String(v.to_string()))
+                    }
+                    None => JsonValue::Null,
+                })
+                .collect()
+        }
+        _ => {
+            let data_type = wrap_nullable(&try_from_arrow_type(array.data_type())?);
+            let column = array.clone().into_nullable_column();
+            data_type
+                .create_serializer(&column)?
+                .serialize_json_values(&FormatSettings::default())?
+        }
+    };
+
+    if let Some(validity) = array.validity() {
+        for (value, is_valid) in values.iter_mut().zip(validity.iter()) {
+            if !is_valid {
+                *value = JsonValue::Null;
+            }
+        }
+    }
+    Ok(values)
+}
+
+// Casts the array strictly: arrow takes the values that can't be cast as nulls, which
+// would be lost in a non-nullable column, so they are errors instead.
+fn cast_array(array: &dyn Array, to: &ArrowType) -> Result<Arc<dyn Array>> {
+    let options = CastOptions {
+        wrapped: false,
+        partial: false,
+    };
+    let cast: Arc<dyn Array> = cast::cast(array, to, options)
+        .map(Arc::from)
+        .map_err(|e| ErrorCode::BadBytes(format!("Cannot cast column: {}", e)))?;
+
+    if cast.null_count() > array.null_count() {
+        if let Some(row) = (0..array.len()).find(|row| array.is_valid(*row) && cast.is_null(*row)) {
+            return Err(ErrorCode::BadBytes(format!(
+                "Cannot cast the value at row {} from {:?} to {:?}",
+                row,
+                array.data_type(),
+                to
+            )));
+        }
+    }
+    Ok(cast)
+}
@@ -0,0 +1,130 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::io::Cursor;
+
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::io::avro::read;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+
+use crate::format_arrow::resolve_chunk;
+use crate::FormatFactory;
+use crate::InputFormat;
+use crate::InputState;
+
+pub struct AvroInputState {
+    pub memory: Vec<u8>,
+}
+
+impl InputState for AvroInputState {
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Reads Avro object container files.
+///
+/// The writer schema embedded in the file header is resolved against the table schema
+/// by field name, so records only need to carry the columns of the table.
+pub struct AvroInputFormat {
+    schema: DataSchemaRef,
+}
+
+impl AvroInputFormat {
+    pub fn register(factory: &mut FormatFactory) {
+        factory.register_input(
+            "avro",
+            Box::new(
+                |name: &str, schema: DataSchemaRef, _settings: FormatSettings| {
+                    AvroInputFormat::try_create(name, schema)
+                },
+            ),
+        )
+    }
+
+    pub fn try_create(_name: &str, schema: DataSchemaRef) -> Result<Box<dyn InputFormat>> {
+        Ok(Box::new(AvroInputFormat { schema }))
+    }
+
+    fn projection(&self, file_fields: &[ArrowField]) -> Vec<bool> {
+        file_fields
+            .iter()
+            .map(|f| {
+                self.schema
+                    .fields()
+                    .iter()
+                    .any(|field| field.name().eq_ignore_ascii_case(&f.name))
+            })
+            .collect()
+    }
+}
+
+impl InputFormat for AvroInputFormat {
+    fn create_state(&self) -> Box<dyn InputState> {
+        Box::new(AvroInputState { memory: vec![] })
+    }
+
+    fn deserialize_data(&self, state: &mut Box<dyn InputState>) -> Result<Vec<DataBlock>> {
+        let mut state = std::mem::replace(state, self.create_state());
+        let state = state.as_any().downcast_mut::<AvroInputState>().unwrap();
+
+        if state.memory.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut cursor = Cursor::new(&state.memory);
+        let (avro_schemas, file_schema, codec, marker) = read::read_metadata(&mut cursor)
+            .map_err(|e| ErrorCode::BadBytes(format!("Invalid avro file: {}", e)))?;
+
+        let projection = self.projection(&file_schema.fields);
+        let projected_fields = file_schema
+            .fields
+            .iter()
+            .zip(projection.iter())
+            .filter(|(_, projected)| **projected)
+            .map(|(f, _)| f.clone())
+            .collect::<Vec<_>>();
+
+        let reader = read::Reader::new(
+            read::Decompressor::new(read::BlockStreamIterator::new(cursor, marker), codec),
+            avro_schemas,
+            file_schema.fields,
+            Some(projection),
+        );
+
+        let mut data_blocks = vec![];
+        for chunk in reader {
+            let chunk =
+                chunk.map_err(|e| ErrorCode::BadBytes(format!("Invalid avro block: {}", e)))?;
+            data_blocks.push(resolve_chunk(&self.schema, &projected_fields, &chunk)?);
+        }
+
+        Ok(data_blocks)
+    }
+
+    fn read_buf(&self, buf: &[u8], state: &mut Box<dyn InputState>) -> Result<usize> {
+        let state = state.as_any().downcast_mut::<AvroInputState>().unwrap();
+        state.memory.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn skip_header(&self, _: &[u8], _: &mut Box<dyn InputState>) -> Result<usize> {
+        Ok(0)
+    }
+}
@@ -25,7 +25,10 @@ use strum::IntoEnumIterator;
 
 use super::format_tsv::TsvInputFormat;
 use crate::format::InputFormat;
+use crate::format_avro::AvroInputFormat;
 use crate::format_csv::CsvInputFormat;
+use crate::format_json_each_row::JsonEachRowInputFormat;
+use crate::format_orc::OrcInputFormat;
 use crate::format_parquet::ParquetInputFormat;
 use crate::output_format::OutputFormatType;
 
@@ -43,6 +46,9 @@ static FORMAT_FACTORY: Lazy<Arc<FormatFactory>> = Lazy::new(|| {
     CsvInputFormat::register(&mut format_factory);
     TsvInputFormat::register(&mut format_factory);
     ParquetInputFormat::register(&mut format_factory);
+    JsonEachRowInputFormat::register(&mut format_factory);
+    AvroInputFormat::register(&mut format_factory);
+    OrcInputFormat::register(&mut format_factory);
 
     format_factory.register_outputs();
     Arc::new(format_factory)
@@ -0,0 +1,253 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::borrow::Cow;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::TypeDeserializer;
+use common_datavalues::TypeDeserializerImpl;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+
+use crate::FormatFactory;
+use crate::InputFormat;
+use crate::InputState;
+
+pub struct JsonEachRowInputState {
+    pub memory: Vec<u8>,
+    pub accepted_rows: usize,
+    pub accepted_bytes: usize,
+    pub need_more_data: bool,
+}
+
+impl InputState for JsonEachRowInputState {
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Newline-delimited JSON, every line is a JSON object whose keys are mapped to the
+/// columns by name.
+pub struct JsonEachRowInputFormat {
+    schema: DataSchemaRef,
+    min_accepted_rows: usize,
+    min_accepted_bytes: usize,
+    settings: FormatSettings,
+}
+
+impl JsonEachRowInputFormat {
+    pub fn register(factory: &mut FormatFactory) {
+        macro_rules! register {
+            ($name: expr) => {
+                factory.register_input(
+                    $name,
+                    Box::new(
+                        |name: &str, schema: DataSchemaRef, settings: FormatSettings| {
+                            JsonEachRowInputFormat::try_create(
+                                name,
+                                schema,
+                                settings,
+                                8192,
+                                10 * 1024 * 1024,
+                            )
+                        },
+                    ),
+                );
+            };
+        }
+
+        register! { "JsonEachRow" }
+        register! { "NDJson" }
+    }
+
+    pub fn try_create(
+        _name: &str,
+        schema: DataSchemaRef,
+        settings: FormatSettings,
+        min_accepted_rows: usize,
+        min_accepted_bytes: usize,
+    ) -> Result<Box<dyn InputFormat>> {
+        Ok(Box::new(JsonEachRowInputFormat {
+            schema,
+            settings,
+            min_accepted_rows,
+            min_accepted_bytes,
+        }))
+    }
+
+    // A raw newline never appears inside a JSON value, so every newline ends a row.
+    fn find_delimiter(&self, buf: &[u8], pos: usize, state: &mut JsonEachRowInputState) -> usize {
+        match buf[pos..].iter().position(|byte| *byte == b'\n') {
+            None => buf.len(),
+            Some(offset) => {
+                state.accepted_rows += 1;
+                state.accepted_bytes += offset + 1;
+
+                if state.accepted_rows >= self.min_accepted_rows
+                    || state.accepted_bytes >= self.min_accepted_bytes
+                {
+                    state.need_more_data = false;
+                }
+
+                pos + offset + 1
+            }
+        }
+    }
+}
+
+impl InputFormat for JsonEachRowInputFormat {
+    fn support_parallel(&self) -> bool {
+        true
+    }
+
+    fn create_state(&self) -> Box<dyn InputState> {
+        Box::new(JsonEachRowInputState {
+            memory: vec![],
+            accepted_rows: 0,
+            accepted_bytes: 0,
+            need_more_data: false,
+        })
+    }
+
+    fn deserialize_data(&self, state: &mut Box<dyn InputState>) -> Result<Vec<DataBlock>> {
+        let mut deserializers = Vec::with_capacity(self.schema.num_fields());
+        for field in self.schema.fields() {
+            let data_type = field.data_type();
+            deserializers.push(data_type.create_deserializer(self.min_accepted_rows));
+        }
+
+        let mut state = std::mem::replace(state, self.create_state());
+        let state = state
+            .as_any()
+            .downcast_mut::<JsonEachRowInputState>()
+            .unwrap();
+        let memory = std::mem::take(&mut state.memory);
+
+        let mut row_index = 0;
+        for line in memory.split(|byte| *byte == b'\n') {
+            if line.iter().all(|byte| byte.is_ascii_whitespace()) {
+                continue;
+            }
+
+            let row: serde_json::Value = serde_json::from_slice(line).map_err(|cause| {
+                ErrorCode::BadBytes(format!(
+                    "Parse JSONEachRow error at row {}: {}",
+                    row_index, cause
+                ))
+            })?;
+            deserialize_json_row(
+                &self.schema,
+                &mut deserializers,
+                &row,
+                row_index,
+                &self.settings,
+            )?;
+            row_index += 1;
+        }
+
+        let mut columns = Vec::with_capacity(deserializers.len());
+        for deserializer in &mut deserializers {
+            columns.push(deserializer.finish_to_column());
+        }
+
+        Ok(vec![DataBlock::create(self.schema.clone(), columns)])
+    }
+
+    fn read_buf(&self, buf: &[u8], state: &mut Box<dyn InputState>) -> Result<usize> {
+        let mut index = 0;
+        let state = state
+            .as_any()
+            .downcast_mut::<JsonEachRowInputState>()
+            .unwrap();
+
+        state.need_more_data = true;
+        while index < buf.len() && state.need_more_data {
+            index = self.find_delimiter(buf, index, state);
+        }
+
+        state.memory.extend_from_slice(&buf[0..index]);
+        Ok(index)
+    }
+
+    fn skip_header(&self, _buf: &[u8], _state: &mut Box<dyn InputState>) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+/// Append a JSON object to the deserializers of `schema`, the keys are matched with the
+/// names of fields. A missing key takes the default value of the column, and nested values
+/// are accepted by `Variant` columns as they are.
+///
+/// On error, the values already appended for this row are popped, so the caller may skip it.
+pub fn deserialize_json_row(
+    schema: &DataSchemaRef,
+    deserializers: &mut [TypeDeserializerImpl],
+    row: &serde_json::Value,
+    row_index: usize,
+    settings: &FormatSettings,
+) -> Result<()> {
+    let object = row.as_object().ok_or_else(|| {
+        ErrorCode::BadBytes(format!(
+            "Parse JSONEachRow error at row {}: expect a JSON object, but got {}",
+            row_index,
+            maybe_truncated(&row.to_string(), 1024),
+        ))
+    })?;
+
+    let mut result = Ok(());
+    let mut appended = 0;
+    for (field, deserializer) in schema.fields().iter().zip(deserializers.iter_mut()) {
+        match object.get(field.name()) {
+            None => deserializer.de_default(settings),
+            Some(value) => {
+                if let Err(cause) = deserializer.de_json(value, settings) {
+                    result = Err(ErrorCode::BadBytes(format!(
+                        "error at row {} column {}: type={}, err={}, value={}",
+                        row_index,
+                        field.name(),
+                        field.data_type().name(),
+                        cause.message(),
+                        maybe_truncated(&value.to_string(), 1024),
+                    )));
+                    break;
+                }
+            }
+        }
+        appended += 1;
+    }
+
+    if result.is_err() {
+        for deserializer in deserializers.iter_mut().take(appended) {
+            deserializer.pop_data_value()?;
+        }
+    }
+    result
+}
+
+fn maybe_truncated(s: &str, limit: usize) -> Cow<'_, str> {
+    if s.len() > limit {
+        // backs off to the boundary of a char, not to split a multibyte char
+        let mut end = limit;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        Cow::Owned(format!("(first {}B of {}B): {}", end, s.len(), &s[..end]))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
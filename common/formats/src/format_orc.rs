@@ -0,0 +1,139 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::orc::format;
+use common_arrow::arrow::io::orc::read;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+
+use crate::format_arrow::resolve_chunk;
+use crate::FormatFactory;
+use crate::InputFormat;
+use crate::InputState;
+
+fn orc_error(e: impl std::fmt::Display) -> ErrorCode {
+    ErrorCode::BadBytes(format!("Invalid orc file: {}", e))
+}
+
+pub struct OrcInputState {
+    pub memory: Vec<u8>,
+}
+
+impl InputState for OrcInputState {
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Reads ORC files stripe by stripe, one block per stripe.
+///
+/// Like Avro, the file schema is resolved against the table schema by column name.
+pub struct OrcInputFormat {
+    schema: DataSchemaRef,
+}
+
+impl OrcInputFormat {
+    pub fn register(factory: &mut FormatFactory) {
+        factory.register_input(
+            "orc",
+            Box::new(
+                |name: &str, schema: DataSchemaRef, _settings: FormatSettings| {
+                    OrcInputFormat::try_create(name, schema)
+                },
+            ),
+        )
+    }
+
+    pub fn try_create(_name: &str, schema: DataSchemaRef) -> Result<Box<dyn InputFormat>> {
+        Ok(Box::new(OrcInputFormat { schema }))
+    }
+}
+
+impl InputFormat for OrcInputFormat {
+    fn create_state(&self) -> Box<dyn InputState> {
+        Box::new(OrcInputState { memory: vec![] })
+    }
+
+    fn deserialize_data(&self, state: &mut Box<dyn InputState>) -> Result<Vec<DataBlock>> {
+        let mut state = std::mem::replace(state, self.create_state());
+        let state = state.as_any().downcast_mut::<OrcInputState>().unwrap();
+
+        if state.memory.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut cursor = Cursor::new(&state.memory);
+        let metadata = format::read::read_metadata(&mut cursor).map_err(orc_error)?;
+        let file_schema = read::infer_schema(&metadata.footer)
+            .map_err(|e| ErrorCode::BadBytes(format!("Unsupported orc schema: {}", e)))?;
+
+        // Columns are numbered by the pre-order traversal of the type tree, so the ids of the
+        // top level fields are the subtypes of the root struct, column 0.
+        let column_ids = metadata
+            .footer
+            .types
+            .first()
+            .map(|root| root.subtypes.clone())
+            .unwrap_or_default();
+
+        let mut scratch = vec![];
+        let mut data_blocks = Vec::with_capacity(metadata.stripes.len());
+        for stripe in 0..metadata.stripes.len() {
+            let mut footer =
+                format::read::read_stripe_footer(&mut cursor, &metadata, stripe, &mut scratch)
+                    .map_err(orc_error)?;
+
+            let mut arrays: Vec<Arc<dyn Array>> = Vec::with_capacity(file_schema.fields.len());
+            for (field, column_id) in file_schema.fields.iter().zip(column_ids.iter()) {
+                let column = format::read::read_stripe_column(
+                    &mut cursor,
+                    &metadata,
+                    stripe,
+                    footer,
+                    *column_id,
+                    scratch,
+                )
+                .map_err(orc_error)?;
+
+                let array = read::deserialize(field.data_type.clone(), &column)?;
+                arrays.push(Arc::from(array));
+                (footer, scratch) = column.into_inner();
+            }
+
+            let chunk = Chunk::try_new(arrays)?;
+            data_blocks.push(resolve_chunk(&self.schema, &file_schema.fields, &chunk)?);
+        }
+
+        Ok(data_blocks)
+    }
+
+    fn read_buf(&self, buf: &[u8], state: &mut Box<dyn InputState>) -> Result<usize> {
+        let state = state.as_any().downcast_mut::<OrcInputState>().unwrap();
+        state.memory.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn skip_header(&self, _: &[u8], _: &mut Box<dyn InputState>) -> Result<usize> {
+        Ok(0)
+    }
+}
@@ -13,8 +13,12 @@
 // limitations under the License.
 
 pub mod format;
+pub mod format_arrow;
+mod format_avro;
 pub mod format_csv;
 mod format_factory;
+pub mod format_json_each_row;
+mod format_orc;
 mod format_parquet;
 pub mod format_tsv;
 pub mod output_format;
@@ -0,0 +1,255 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::DictionaryArray;
+use common_arrow::arrow::array::Int32Array;
+use common_arrow::arrow::array::Int64Array;
+use common_arrow::arrow::array::ListArray;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_datablocks::assert_blocks_eq;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_formats::format_arrow::cast_to_field;
+use common_formats::format_arrow::normalize_array;
+use common_formats::format_arrow::resolve_chunk;
+
+fn tags_array() -> Arc<dyn Array> {
+    let item = ArrowField::new("item", ArrowType::Utf8, true);
+    Arc::new(ListArray::<i32>::from_data(
+        ArrowType::List(Box::new(item)),
+        vec![0, 2, 2, 3].into(),
+        Arc::new(Utf8Array::<i32>::from_slice(["a", "b", "c"])),
+        None,
+    ))
+}
+
+fn point_array() -> Arc<dyn Array> {
+    let fields = vec![
+        ArrowField::new("x", ArrowType::Int64, false),
+        ArrowField::new("y", ArrowType::Int64, true),
+    ];
+    Arc::new(StructArray::from_data(
+        ArrowType::Struct(fields),
+        vec![
+            Arc::new(Int64Array::from_slice([1, 2, 3])),
+            Arc::new(Int64Array::from(&[Some(4), None, Some(6)])),
+        ],
+        Some(Bitmap::from([true, true, false])),
+    ))
+}
+
+#[test]
+fn test_resolve_chunk() -> Result<()> {
+    let file_fields = vec![
+        ArrowField::new("ID", ArrowType::Int32, false),
+        ArrowField::new("extra", ArrowType::Utf8, false),
+        ArrowField::new("name", ArrowType::Utf8, true),
+        ArrowField::new("tags", tags_array().data_type().clone(), false),
+        ArrowField::new("point", point_array().data_type().clone(), true),
+    ];
+    let chunk = Chunk::try_new(vec![
+        Arc::new(Int32Array::from_slice([1, 2, 3])) as Arc<dyn Array>,
+        Arc::new(Utf8Array::<i32>::from_slice(["x", "y", "z"])),
+        Arc::new(Utf8Array::<i32>::from(&[Some("n1"), None, Some("n3")])),
+        tags_array(),
+        point_array(),
+    ])?;
+
+    // The columns are matched by name case-insensitively, in the order of the table,
+    // and the columns missing from the file are filled with the default values.
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("point", VariantType::new_impl()),
+        DataField::new("id", Int64Type::new_impl()),
+        DataField::new("age", UInt8Type::new_impl()),
+        DataField::new_nullable("name", StringType::new_impl()),
+        DataField::new_nullable("comment", StringType::new_impl()),
+        DataField::new("tags", VariantType::new_impl()),
+    ]);
+    let block = resolve_chunk(&schema, &file_fields, &chunk)?;
+    assert_eq!(block.schema(), &schema);
+    assert_blocks_eq(
+        vec![
+            "+------------------+----+-----+------+---------+-----------+",
+            "| point            | id | age | name | comment | tags      |",
+            "+------------------+----+-----+------+---------+-----------+",
+            "| {\"x\":1,\"y\":4}    | 1  | 0   | n1   | NULL    | [\"a\",\"b\"] |",
+            "| {\"x\":2,\"y\":null} | 2  | 0   | NULL | NULL    | []        |",
+            "| null             | 3  | 0   | n3   | NULL    | [\"c\"]     |",
+            "+------------------+----+-----+------+---------+-----------+",
+        ],
+        &[block],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_resolve_chunk_variant_from_primitives() -> Result<()> {
+    let file_fields = vec![
+        ArrowField::new("n", ArrowType::Int64, true),
+        ArrowField::new("doc", ArrowType::Utf8, true),
+    ];
+    let chunk = Chunk::try_new(vec![
+        Arc::new(Int64Array::from(&[Some(1), None])) as Arc<dyn Array>,
+        Arc::new(Utf8Array::<i32>::from(&[Some("{\"a\":[1,2]}"), None])),
+    ])?;
+
+    // Strings are parsed as JSON documents, and nulls stay nulls in nullable columns.
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new_nullable("n", VariantType::new_impl()),
+        DataField::new_nullable("doc", VariantType::new_impl()),
+    ]);
+    let block = resolve_chunk(&schema, &file_fields, &chunk)?;
+    assert_blocks_eq(
+        vec![
+            "+------+-------------+",
+            "| n    | doc         |",
+            "+------+-------------+",
+            "| 1    | {\"a\":[1,2]} |",
+            "| NULL | NULL        |",
+            "+------+-------------+",
+        ],
+        &[block],
+    );
+
+    // Strings that are not JSON documents are kept as JSON strings.
+    let chunk = Chunk::try_new(vec![
+        Arc::new(Int64Array::from(&[Some(1)])) as Arc<dyn Array>,
+        Arc::new(Utf8Array::<i32>::from(&[Some("not json")])),
+    ])?;
+    let block = resolve_chunk(&schema, &file_fields, &chunk)?;
+    assert_blocks_eq(
+        vec![
+            "+---+------------+",
+            "| n | doc        |",
+            "+---+------------+",
+            "| 1 | \"not json\" |",
+            "+---+------------+",
+        ],
+        &[block],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_normalize_array() -> Result<()> {
+    // Lists are rewritten with i64 offsets, recursively.
+    let array = normalize_array(tags_array().as_ref())?;
+    match array.data_type() {
+        ArrowType::LargeList(field) => assert_eq!(field.data_type(), &ArrowType::Utf8),
+        other => panic!("unexpected type {:?}", other),
+    }
+    let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+    assert_eq!(array.offsets().as_slice(), &[0, 2, 2, 3]);
+
+    let item = ArrowField::new("item", tags_array().data_type().clone(), true);
+    let nested = ListArray::<i32>::from_data(
+        ArrowType::List(Box::new(item)),
+        vec![0, 1, 3].into(),
+        tags_array(),
+        None,
+    );
+    let array = normalize_array(&nested)?;
+    match array.data_type() {
+        ArrowType::LargeList(field) => {
+            assert!(matches!(field.data_type(), ArrowType::LargeList(_)))
+        }
+        other => panic!("unexpected type {:?}", other),
+    }
+
+    // The fields of structs are normalized, and the validity is kept.
+    let array = StructArray::from_data(
+        ArrowType::Struct(vec![ArrowField::new(
+            "tags",
+            tags_array().data_type().clone(),
+            true,
+        )]),
+        vec![tags_array()],
+        Some(Bitmap::from([true, false, true])),
+    );
+    let array = normalize_array(&array)?;
+    match array.data_type() {
+        ArrowType::Struct(fields) => {
+            assert!(matches!(fields[0].data_type(), ArrowType::LargeList(_)))
+        }
+        other => panic!("unexpected type {:?}", other),
+    }
+    assert_eq!(array.null_count(), 1);
+
+    // Dictionaries are unpacked into their values.
+    let array = DictionaryArray::<i32>::from_data(
+        Int32Array::from_slice([0, 1, 0]),
+        Arc::new(Utf8Array::<i32>::from_slice(["x", "y"])),
+    );
+    let array = normalize_array(&array)?;
+    assert_eq!(array.data_type(), &ArrowType::Utf8);
+    let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+    assert_eq!(array.iter().collect::<Vec<_>>(), vec![
+        Some("x"),
+        Some("y"),
+        Some("x")
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_cast_to_field() -> Result<()> {
+    // Arrays of the table type are kept as they are.
+    let array: Arc<dyn Array> = Arc::new(Int64Array::from_slice([1, 2]));
+    let field = DataField::new_nullable("a", Int64Type::new_impl());
+    let cast = cast_to_field(array.clone(), &field)?;
+    assert!(Arc::ptr_eq(&array, &cast));
+
+    // Primitives are cast, and the nulls are kept.
+    let array: Arc<dyn Array> = Arc::new(Utf8Array::<i32>::from(&[Some("1"), None, Some("3")]));
+    let field = DataField::new_nullable("a", Int32Type::new_impl());
+    let cast = cast_to_field(array, &field)?;
+    assert_eq!(cast.data_type(), &ArrowType::Int32);
+    let cast = cast.as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(cast.iter().map(|v| v.copied()).collect::<Vec<_>>(), vec![
+        Some(1),
+        None,
+        Some(3)
+    ]);
+
+    // The values that can't be cast are errors, rather than nulls.
+    let array: Arc<dyn Array> = Arc::new(Utf8Array::<i32>::from_slice(["1", "x", "3"]));
+    let field = DataField::new("a", Int32Type::new_impl());
+    let cause = cast_to_field(array, &field).unwrap_err();
+    assert_eq!(cause.code(), ErrorCode::BadBytes("").code());
+    assert!(cause.message().contains("column 'a'"));
+    assert!(cause.message().contains("row 1"));
+
+    // Nested columns are not cast to a different nested type.
+    let array = normalize_array(point_array().as_ref())?;
+    let field = DataField::new(
+        "a",
+        ArrayType::new_impl(wrap_nullable(&Int64Type::new_impl())),
+    );
+    let result = cast_to_field(array, &field);
+    assert_eq!(result.unwrap_err().code(), ErrorCode::BadBytes("").code());
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_formats::FormatFactory;
+use common_io::prelude::FormatSettings;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+const SYNC_MARKER: [u8; 16] = [7; 16];
+
+const AVRO_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "t",
+    "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "extra", "type": "long"},
+        {"name": "name", "type": "string"},
+        {"name": "tags", "type": {"type": "array", "items": "string"}},
+        {"name": "point", "type": {"type": "record", "name": "p", "fields": [{"name": "x", "type": "long"}]}},
+        {"name": "note", "type": ["null", "string"]}
+    ]
+}"#;
+
+fn write_long(buf: &mut Vec<u8>, v: i64) {
+    let mut z = ((v << 1) ^ (v >> 63)) as u64;
+    loop {
+        let byte = (z & 0x7f) as u8;
+        z >>= 7;
+        if z == 0 {
+            buf.push(byte);
+            break;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn write_bytes(buf: &mut Vec<u8>, v: &[u8]) {
+    write_long(buf, v.len() as i64);
+    buf.extend_from_slice(v);
+}
+
+fn write_record(buf: &mut Vec<u8>, id: i64, name: &str, tags: &[&str], x: i64, note: Option<&str>) {
+    write_long(buf, id);
+    write_long(buf, id * 100);
+    write_bytes(buf, name.as_bytes());
+    if !tags.is_empty() {
+        write_long(buf, tags.len() as i64);
+        for tag in tags {
+            write_bytes(buf, tag.as_bytes());
+        }
+    }
+    write_long(buf, 0);
+    write_long(buf, x);
+    match note {
+        None => write_long(buf, 0),
+        Some(note) => {
+            write_long(buf, 1);
+            write_bytes(buf, note.as_bytes());
+        }
+    }
+}
+
+// Encodes an object container file with a single block of two records.
+fn avro_file(codec: &str) -> Result<Vec<u8>> {
+    let mut records = vec![];
+    write_record(&mut records, 1, "a", &["x", "y"], 10, None);
+    write_record(&mut records, 2, "b", &[], 20, Some("hi"));
+
+    let data = match codec {
+        "deflate" => {
+            let mut encoder = DeflateEncoder::new(vec![], Compression::default());
+            encoder.write_all(&records)?;
+            encoder.finish()?
+        }
+        _ => records,
+    };
+
+    let mut file = b"Obj\x01".to_vec();
+    write_long(&mut file, 2);
+    write_bytes(&mut file, b"avro.schema");
+    write_bytes(&mut file, AVRO_SCHEMA.as_bytes());
+    write_bytes(&mut file, b"avro.codec");
+    write_bytes(&mut file, codec.as_bytes());
+    write_long(&mut file, 0);
+    file.extend_from_slice(&SYNC_MARKER);
+
+    write_long(&mut file, 2);
+    write_bytes(&mut file, &data);
+    file.extend_from_slice(&SYNC_MARKER);
+    Ok(file)
+}
+
+fn read_avro(schema: DataSchemaRef, file: &[u8]) -> Result<Vec<DataBlock>> {
+    let format = FormatFactory::instance().get_input("avro", schema, FormatSettings::default())?;
+    let mut state = format.create_state();
+    // Feed the file in pieces, the way the stage source does.
+    for piece in file.chunks(16) {
+        format.read_buf(piece, &mut state)?;
+    }
+    format.deserialize_data(&mut state)
+}
+
+fn table_schema() -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![
+        DataField::new("id", Int32Type::new_impl()),
+        DataField::new("NAME", StringType::new_impl()),
+        DataField::new("tags", VariantType::new_impl()),
+        DataField::new("point", VariantType::new_impl()),
+        DataField::new_nullable("note", StringType::new_impl()),
+        DataField::new("age", UInt8Type::new_impl()),
+    ])
+}
+
+#[test]
+fn test_avro_input() -> Result<()> {
+    for codec in ["null", "deflate"] {
+        let blocks = read_avro(table_schema(), &avro_file(codec)?)?;
+        assert_eq!(blocks.len(), 1, "codec: {}", codec);
+        assert_eq!(blocks[0].schema(), &table_schema());
+        assert_blocks_eq(
+            vec![
+                "+----+------+-----------+----------+------+-----+",
+                "| id | NAME | tags      | point    | note | age |",
+                "+----+------+-----------+----------+------+-----+",
+                "| 1  | a    | [\"x\",\"y\"] | {\"x\":10} | NULL | 0   |",
+                "| 2  | b    | []        | {\"x\":20} | hi   | 0   |",
+                "+----+------+-----------+----------+------+-----+",
+            ],
+            &blocks,
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_avro_input_empty_and_invalid() -> Result<()> {
+    let blocks = read_avro(table_schema(), &[])?;
+    assert!(blocks.is_empty());
+
+    let result = read_avro(table_schema(), b"not an avro file");
+    assert_eq!(result.unwrap_err().code(), ErrorCode::BadBytes("").code());
+
+    // A variant column can't be loaded into a nested table type.
+    let schema = DataSchemaRefExt::create(vec![DataField::new(
+        "tags",
+        StructType::new_impl(vec!["x".to_string()], vec![Int64Type::new_impl()]),
+    )]);
+    let result = read_avro(schema, &avro_file("null")?);
+    assert_eq!(result.unwrap_err().code(), ErrorCode::BadBytes("").code());
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::assert_blocks_eq;
+use common_datavalues::type_primitive::UInt32Type;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::NullableType;
+use common_datavalues::StringType;
+use common_datavalues::VariantType;
+use common_exception::Result;
+use common_formats::format_json_each_row::JsonEachRowInputFormat;
+use common_formats::format_json_each_row::JsonEachRowInputState;
+use common_io::prelude::FormatSettings;
+
+#[test]
+fn test_accepted_rows() -> Result<()> {
+    let input_format = JsonEachRowInputFormat::try_create(
+        "JsonEachRow",
+        Arc::new(DataSchema::empty()),
+        FormatSettings::default(),
+        2,
+        10 * 1024 * 1024,
+    )?;
+
+    let mut input_state = input_format.create_state();
+
+    let bytes = "{\"a\": \"\\n\"}\n{\"a\"".as_bytes();
+    assert_eq!(bytes.len(), input_format.read_buf(bytes, &mut input_state)?);
+
+    let bytes = ": 1}\n{\"a\": 2}\n".as_bytes();
+    assert_eq!(5, input_format.read_buf(bytes, &mut input_state)?);
+    assert_eq!(
+        "{\"a\": \"\\n\"}\n{\"a\": 1}\n".as_bytes(),
+        input_state
+            .as_any()
+            .downcast_mut::<JsonEachRowInputState>()
+            .unwrap()
+            .memory
+    );
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_rows() -> Result<()> {
+    let input_format = JsonEachRowInputFormat::try_create(
+        "JsonEachRow",
+        Arc::new(DataSchema::new(vec![
+            DataField::new("a", DataTypeImpl::UInt32(UInt32Type::default())),
+            DataField::new(
+                "b",
+                NullableType::new_impl(DataTypeImpl::String(StringType::default())),
+            ),
+            DataField::new("c", VariantType::new_impl()),
+        ])),
+        FormatSettings::default(),
+        8192,
+        10 * 1024 * 1024,
+    )?;
+
+    let mut input_state = input_format.create_state();
+
+    input_format.read_buf(
+        concat!(
+            "{\"a\": 1, \"b\": \"x\", \"c\": {\"k\": [1, 2]}}\n",
+            "\n",
+            "{\"c\": 3, \"a\": 2, \"unknown\": true}\n",
+            "{\"b\": null, \"c\": \"s\"}",
+        )
+        .as_bytes(),
+        &mut input_state,
+    )?;
+    assert_blocks_eq(
+        vec![
+            "+---+------+-------------+",
+            "| a | b    | c           |",
+            "+---+------+-------------+",
+            "| 1 | x    | {\"k\":[1,2]} |",
+            "| 2 | NULL | 3           |",
+            "| 0 | NULL | \"s\"         |",
+            "+---+------+-------------+",
+        ],
+        &input_format.deserialize_data(&mut input_state)?,
+    );
+
+    let mut input_state = input_format.create_state();
+    input_format.read_buf("[1, \"x\"]\n".as_bytes(), &mut input_state)?;
+    assert!(input_format.deserialize_data(&mut input_state).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_invalid_row_of_multibyte_chars() -> Result<()> {
+    let input_format = JsonEachRowInputFormat::try_create(
+        "JsonEachRow",
+        Arc::new(DataSchema::new(vec![DataField::new(
+            "a",
+            DataTypeImpl::UInt32(UInt32Type::default()),
+        )])),
+        FormatSettings::default(),
+        8192,
+        10 * 1024 * 1024,
+    )?;
+
+    // the value shown in the error is truncated on the boundary of a char
+    let row = format!("[\"{}\"]\n", "中".repeat(400));
+    let mut input_state = input_format.create_state();
+    input_format.read_buf(row.as_bytes(), &mut input_state)?;
+    let cause = input_format.deserialize_data(&mut input_state).unwrap_err();
+    assert!(cause.message().contains("(first 1022B of 1204B)"));
+    Ok(())
+}
@@ -0,0 +1,213 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_formats::FormatFactory;
+use common_io::prelude::FormatSettings;
+
+// Type kinds, stream kinds and column encodings of orc_proto.proto.
+const KIND_LONG: u64 = 4;
+const KIND_STRING: u64 = 7;
+const KIND_STRUCT: u64 = 12;
+const STREAM_PRESENT: u64 = 0;
+const STREAM_DATA: u64 = 1;
+const STREAM_LENGTH: u64 = 2;
+const ENCODING_DIRECT: u64 = 0;
+const ENCODING_DIRECT_V2: u64 = 2;
+
+fn write_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn write_uint_field(buf: &mut Vec<u8>, field: u64, v: u64) {
+    write_varint(buf, field << 3);
+    write_varint(buf, v);
+}
+
+fn write_bytes_field(buf: &mut Vec<u8>, field: u64, v: &[u8]) {
+    write_varint(buf, (field << 3) | 2);
+    write_varint(buf, v.len() as u64);
+    buf.extend_from_slice(v);
+}
+
+fn orc_type(kind: u64, subtypes: &[u64], field_names: &[&str]) -> Vec<u8> {
+    let mut buf = vec![];
+    write_uint_field(&mut buf, 1, kind);
+    if !subtypes.is_empty() {
+        let mut packed = vec![];
+        subtypes.iter().for_each(|v| write_varint(&mut packed, *v));
+        write_bytes_field(&mut buf, 2, &packed);
+    }
+    for name in field_names {
+        write_bytes_field(&mut buf, 3, name.as_bytes());
+    }
+    buf
+}
+
+// RLE v2 direct run with 8 bits per value.
+fn rle_v2(values: &[u64]) -> Vec<u8> {
+    let len = values.len() as u64 - 1;
+    let mut buf = vec![0x40 | (7 << 1) | (len >> 8) as u8, len as u8];
+    buf.extend(values.iter().map(|v| *v as u8));
+    buf
+}
+
+fn signed_rle_v2(values: &[i64]) -> Vec<u8> {
+    let zigzag = values
+        .iter()
+        .map(|v| ((v << 1) ^ (v >> 63)) as u64)
+        .collect::<Vec<_>>();
+    rle_v2(&zigzag)
+}
+
+// Byte RLE literal run of a boolean stream, one byte holds up to 8 rows.
+fn present(valid: &[bool]) -> Vec<u8> {
+    let byte = valid
+        .iter()
+        .enumerate()
+        .fold(0u8, |acc, (i, v)| acc | ((*v as u8) << (7 - i)));
+    vec![0xff, byte]
+}
+
+// Encodes a file with a single stripe of the columns (id LONG, name STRING, extra LONG).
+fn orc_file() -> Vec<u8> {
+    let streams: Vec<(u64, u64, Vec<u8>)> = vec![
+        (1, STREAM_PRESENT, present(&[true, true])),
+        (1, STREAM_DATA, signed_rle_v2(&[1, 2])),
+        (2, STREAM_PRESENT, present(&[true, false])),
+        (2, STREAM_DATA, b"ab".to_vec()),
+        (2, STREAM_LENGTH, rle_v2(&[2])),
+        (3, STREAM_PRESENT, present(&[true, true])),
+        (3, STREAM_DATA, signed_rle_v2(&[5, 6])),
+    ];
+
+    let mut file = b"ORC".to_vec();
+    let mut stripe_footer = vec![];
+    for (column, kind, data) in streams.iter() {
+        let mut stream = vec![];
+        write_uint_field(&mut stream, 1, *kind);
+        write_uint_field(&mut stream, 2, *column);
+        write_uint_field(&mut stream, 3, data.len() as u64);
+        write_bytes_field(&mut stripe_footer, 1, &stream);
+        file.extend_from_slice(data);
+    }
+    let data_length = file.len() as u64 - 3;
+    for kind in [
+        ENCODING_DIRECT,
+        ENCODING_DIRECT_V2,
+        ENCODING_DIRECT_V2,
+        ENCODING_DIRECT_V2,
+    ] {
+        let mut encoding = vec![];
+        write_uint_field(&mut encoding, 1, kind);
+        write_bytes_field(&mut stripe_footer, 2, &encoding);
+    }
+    file.extend_from_slice(&stripe_footer);
+
+    let mut stripe = vec![];
+    write_uint_field(&mut stripe, 1, 3);
+    write_uint_field(&mut stripe, 2, 0);
+    write_uint_field(&mut stripe, 3, data_length);
+    write_uint_field(&mut stripe, 4, stripe_footer.len() as u64);
+    write_uint_field(&mut stripe, 5, 2);
+
+    let mut footer = vec![];
+    write_uint_field(&mut footer, 1, 3);
+    write_uint_field(&mut footer, 2, file.len() as u64 - 3);
+    write_bytes_field(&mut footer, 3, &stripe);
+    let types = [
+        orc_type(KIND_STRUCT, &[1, 2, 3], &["id", "name", "extra"]),
+        orc_type(KIND_LONG, &[], &[]),
+        orc_type(KIND_STRING, &[], &[]),
+        orc_type(KIND_LONG, &[], &[]),
+    ];
+    for t in types.iter() {
+        write_bytes_field(&mut footer, 4, t);
+    }
+    write_uint_field(&mut footer, 6, 2);
+    file.extend_from_slice(&footer);
+
+    let mut postscript = vec![];
+    write_uint_field(&mut postscript, 1, footer.len() as u64);
+    write_uint_field(&mut postscript, 2, 0);
+    write_bytes_field(&mut postscript, 4, &[0, 12]);
+    write_uint_field(&mut postscript, 5, 0);
+    write_bytes_field(&mut postscript, 8000, b"ORC");
+    file.extend_from_slice(&postscript);
+    file.push(postscript.len() as u8);
+    file
+}
+
+fn read_orc(schema: DataSchemaRef, file: &[u8]) -> Result<Vec<DataBlock>> {
+    let format = FormatFactory::instance().get_input("orc", schema, FormatSettings::default())?;
+    let mut state = format.create_state();
+    for piece in file.chunks(16) {
+        format.read_buf(piece, &mut state)?;
+    }
+    format.deserialize_data(&mut state)
+}
+
+#[test]
+fn test_orc_input() -> Result<()> {
+    // The columns are resolved by name, cast to the table types, and the missing
+    // ones are filled with defaults.
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new_nullable("name", StringType::new_impl()),
+        DataField::new("ID", Int32Type::new_impl()),
+        DataField::new("age", UInt8Type::new_impl()),
+        DataField::new_nullable("doc", VariantType::new_impl()),
+        DataField::new("extra", VariantType::new_impl()),
+    ]);
+    let blocks = read_orc(schema.clone(), &orc_file())?;
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].schema(), &schema);
+    assert_blocks_eq(
+        vec![
+            "+------+----+-----+------+-------+",
+            "| name | ID | age | doc  | extra |",
+            "+------+----+-----+------+-------+",
+            "| ab   | 1  | 0   | NULL | 5     |",
+            "| NULL | 2  | 0   | NULL | 6     |",
+            "+------+----+-----+------+-------+",
+        ],
+        &blocks,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_orc_input_empty_and_mismatched() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("id", Int64Type::new_impl())]);
+    let blocks = read_orc(schema, &[])?;
+    assert!(blocks.is_empty());
+
+    // A string column can't be loaded into a nested table type.
+    let schema = DataSchemaRefExt::create(vec![DataField::new(
+        "name",
+        ArrayType::new_impl(StringType::new_impl()),
+    )]);
+    let result = read_orc(schema, &orc_file());
+    assert_eq!(result.unwrap_err().code(), ErrorCode::BadBytes("").code());
+
+    Ok(())
+}
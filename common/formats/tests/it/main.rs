@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod format_arrow;
+mod format_avro;
 mod format_csv;
 mod format_factory;
+mod format_json_each_row;
+mod format_orc;
 mod output_format_json_each_row;
 mod output_format_tcsv;
 mod output_format_utils;
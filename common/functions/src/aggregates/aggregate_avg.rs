@@ -33,6 +33,7 @@ use super::aggregate_sum::sum_primitive;
 use super::StateAddr;
 use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
 use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateDecimalFunction;
 use crate::aggregates::AggregateFunction;
 use crate::aggregates::AggregateFunctionRef;
 use crate::scalars::default_column_cast;
@@ -219,6 +220,9 @@ pub fn try_create_aggregate_avg_function(
     if data_type.data_type_id() == TypeID::Boolean {
         return AggregateAvgFunction::<u8, u64>::try_create(display_name, arguments);
     }
+    if let DataTypeImpl::Decimal(decimal) = data_type {
+        return AggregateDecimalFunction::try_create_avg(display_name, *decimal);
+    }
 
     let mut phid = data_type.data_type_id();
     // null use dummy func, it's already covered in `AggregateNullResultFunction`
@@ -0,0 +1,216 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::cmp;
+use std::fmt;
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::StateAddr;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+/// The extra fractional digits of the average, the same as `div_precision_increment` of MySQL.
+const DECIMAL_AVG_SCALE_INCREMENT: usize = 4;
+
+// the unscaled sum is kept in i128, so it never overflows before the result is built
+#[derive(Serialize, Deserialize)]
+struct AggregateDecimalState {
+    pub sum: i128,
+    pub count: u64,
+}
+
+impl AggregateDecimalState {
+    #[inline(always)]
+    fn add(&mut self, value: i64, count: u64) {
+        self.sum += value as i128;
+        self.count += count;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DecimalAggregateKind {
+    Sum,
+    Avg,
+}
+
+/// The exact `sum` and `avg` of decimals.
+///
+/// `sum(Decimal(P, S))` returns `Decimal(18, S)`, `avg(Decimal(P, S))` returns
+/// `Decimal(18, S + 4)`, the sums overflowing 18 digits are errors.
+#[derive(Clone)]
+pub struct AggregateDecimalFunction {
+    display_name: String,
+    kind: DecimalAggregateKind,
+    input_type: DecimalType,
+    result_type: DecimalType,
+}
+
+impl AggregateDecimalFunction {
+    pub fn try_create_sum(
+        display_name: &str,
+        input_type: DecimalType,
+    ) -> Result<AggregateFunctionRef> {
+        let result_type = DecimalType::try_create(DECIMAL_MAX_PRECISION, input_type.scale())?;
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            kind: DecimalAggregateKind::Sum,
+            input_type,
+            result_type,
+        }))
+    }
+
+    pub fn try_create_avg(
+        display_name: &str,
+        input_type: DecimalType,
+    ) -> Result<AggregateFunctionRef> {
+        let scale = cmp::min(
+            input_type.scale() + DECIMAL_AVG_SCALE_INCREMENT,
+            DECIMAL_MAX_PRECISION,
+        );
+        let result_type = DecimalType::try_create(DECIMAL_MAX_PRECISION, scale)?;
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            kind: DecimalAggregateKind::Avg,
+            input_type,
+            result_type,
+        }))
+    }
+}
+
+impl AggregateFunction for AggregateDecimalFunction {
+    fn name(&self) -> &str {
+        "AggregateDecimalFunction"
+    }
+
+    fn return_type(&self) -> Result<DataTypeImpl> {
+        Ok(DataTypeImpl::Decimal(self.result_type))
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateDecimalState { sum: 0, count: 0 });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateDecimalState>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[ColumnRef],
+        validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column: &Int64Column = Series::check_get(&columns[0])?;
+        let state = place.get::<AggregateDecimalState>();
+        match validity {
+            Some(validity) => column
+                .iter()
+                .zip(validity.iter())
+                .filter(|(_, valid)| *valid)
+                .for_each(|(v, _)| state.add(*v, 1)),
+            None => column.iter().for_each(|v| state.add(*v, 1)),
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: &[ColumnRef],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column: &Int64Column = unsafe { Series::static_cast(&columns[0]) };
+        column.iter().zip(places.iter()).for_each(|(v, place)| {
+            let place = place.next(offset);
+            let state = place.get::<AggregateDecimalState>();
+            state.add(*v, 1);
+        });
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: &[ColumnRef], row: usize) -> Result<()> {
+        let column: &Int64Column = unsafe { Series::static_cast(&columns[0]) };
+        let v = unsafe { column.value_unchecked(row) };
+        let state = place.get::<AggregateDecimalState>();
+        state.add(v, 1);
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        serialize_into_buf(writer, state)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        *state = deserialize_from_slice(reader)?;
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let rhs = rhs.get::<AggregateDecimalState>();
+        let state = place.get::<AggregateDecimalState>();
+        state.sum += rhs.sum;
+        state.count += rhs.count;
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr, array: &mut dyn MutableColumn) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        let value = match self.kind {
+            DecimalAggregateKind::Sum => Some(state.sum),
+            DecimalAggregateKind::Avg if state.count == 0 => Some(0),
+            DecimalAggregateKind::Avg => {
+                let exp = self.result_type.scale() - self.input_type.scale();
+                decimal_pow10(exp)
+                    .and_then(|factor| state.sum.checked_mul(factor))
+                    .map(|v| decimal_div_round(v, state.count as i128))
+            }
+        };
+
+        let value = value
+            .and_then(|v| self.result_type.check_unscaled(v))
+            .ok_or_else(|| {
+                ErrorCode::Overflow(format!(
+                    "Decimal overflow in {}, the result exceeds {}",
+                    self.display_name,
+                    self.result_type.name()
+                ))
+            })?;
+        let builder: &mut MutablePrimitiveColumn<i64> = Series::check_get_mutable_column(array)?;
+        builder.append_value(value);
+        Ok(())
+    }
+
+    fn convert_const_to_full(&self) -> bool {
+        true
+    }
+}
+
+impl fmt::Display for AggregateDecimalFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
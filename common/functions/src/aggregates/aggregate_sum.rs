@@ -33,6 +33,7 @@ use super::aggregate_function::AggregateFunctionRef;
 use super::aggregate_function_factory::AggregateFunctionDescription;
 use super::StateAddr;
 use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateDecimalFunction;
 use crate::scalars::default_column_cast;
 
 struct AggregateSumState<T> {
@@ -215,6 +216,9 @@ pub fn try_create_aggregate_sum_function(
     if data_type.data_type_id() == TypeID::Boolean {
         return AggregateSumFunction::<u8, u64>::try_create(display_name, arguments);
     }
+    if let DataTypeImpl::Decimal(decimal) = data_type {
+        return AggregateDecimalFunction::try_create_sum(display_name, *decimal);
+    }
 
     let mut phid = data_type.data_type_id();
     // null use dummy func, it's already covered in `AggregateNullResultFunction`
@@ -23,7 +23,10 @@ pub mod window;
 
 use aggregates::AggregateFunctionFactory;
 use scalars::FunctionFactory;
+use window::is_builtin_window_function;
 
 pub fn is_builtin_function(name: &str) -> bool {
-    FunctionFactory::instance().check(name) || AggregateFunctionFactory::instance().check(name)
+    FunctionFactory::instance().check(name)
+        || AggregateFunctionFactory::instance().check(name)
+        || is_builtin_window_function(name)
 }
@@ -0,0 +1,202 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp;
+
+use common_datavalues::prelude::*;
+use common_datavalues::with_match_physical_primitive_type_error;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::BinaryArithmeticFunction;
+use crate::scalars::EvalContext;
+use crate::scalars::Function;
+
+/// The extra fractional digits of the quotient, the same as `div_precision_increment` of MySQL.
+const DECIMAL_DIV_SCALE_INCREMENT: usize = 4;
+
+/// Creates the arithmetic function of which at least one argument is decimal.
+///
+/// With a float argument the result is Float64, otherwise the integers are treated as
+/// decimals of scale 0 and the result is the exact decimal. The result overflowing
+/// the precision of 18 digits is an error.
+pub fn decimal_arithmetic(
+    op: DataValueBinaryOperator,
+    args: &[&DataTypeImpl],
+) -> Result<Box<dyn Function>> {
+    let left_type = args[0].data_type_id();
+    let right_type = args[1].data_type_id();
+
+    let (lhs, rhs) = match (to_decimal(args[0]), to_decimal(args[1])) {
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+        _ => {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "DataValue Error: Unsupported arithmetic ({:?}) {} ({:?})",
+                left_type, op, right_type
+            )));
+        }
+    };
+
+    if left_type.is_floating() || right_type.is_floating() {
+        let calc = float_calc(&op)?;
+        let lhs_factor = 10f64.powi(lhs.scale() as i32);
+        let rhs_factor = 10f64.powi(rhs.scale() as i32);
+        return with_match_physical_primitive_type_error!(left_type.to_physical_type(), |$T| {
+            with_match_physical_primitive_type_error!(right_type.to_physical_type(), |$D| {
+                BinaryArithmeticFunction::<$T, $D, f64, _>::try_create_func(
+                    op,
+                    Float64Type::new_impl(),
+                    move |l: $T, r: $D, _ctx: &mut EvalContext| {
+                        calc(l as f64 / lhs_factor, r as f64 / rhs_factor)
+                    },
+                )
+            })
+        });
+    }
+
+    let arithmetic = DecimalArithmetic {
+        op: op.clone(),
+        lhs_scale: lhs.scale(),
+        rhs_scale: rhs.scale(),
+        result_type: result_type(&op, &lhs, &rhs)?,
+    };
+    let result_type = DataTypeImpl::Decimal(arithmetic.result_type);
+    with_match_physical_primitive_type_error!(left_type.to_physical_type(), |$T| {
+        with_match_physical_primitive_type_error!(right_type.to_physical_type(), |$D| {
+            let arithmetic = arithmetic.clone();
+            BinaryArithmeticFunction::<$T, $D, i64, _>::try_create_func(
+                op,
+                result_type,
+                move |l: $T, r: $D, ctx: &mut EvalContext| {
+                    arithmetic.eval(l as i128, r as i128, ctx)
+                },
+            )
+        })
+    })
+}
+
+/// The integers are treated as decimals of scale 0, and the floats are only scaled by 1.
+fn to_decimal(data_type: &DataTypeImpl) -> Option<DecimalType> {
+    match data_type {
+        DataTypeImpl::Decimal(v) => Some(*v),
+        _ if data_type.data_type_id().is_floating() => Some(DecimalType::create(1, 0)),
+        _ => DecimalType::from_integer(data_type.data_type_id()),
+    }
+}
+
+fn float_calc(op: &DataValueBinaryOperator) -> Result<fn(f64, f64) -> f64> {
+    match op {
+        DataValueBinaryOperator::Plus => Ok(|l, r| l + r),
+        DataValueBinaryOperator::Minus => Ok(|l, r| l - r),
+        DataValueBinaryOperator::Mul => Ok(|l, r| l * r),
+        DataValueBinaryOperator::Div => Ok(|l, r| l / r),
+        _ => Err(ErrorCode::BadDataValueType(format!(
+            "DataValue Error: Unsupported decimal arithmetic {}",
+            op
+        ))),
+    }
+}
+
+/// The result types follow MySQL, the precision is capped at 18.
+fn result_type(
+    op: &DataValueBinaryOperator,
+    lhs: &DecimalType,
+    rhs: &DecimalType,
+) -> Result<DecimalType> {
+    let (precision, scale) = match op {
+        DataValueBinaryOperator::Plus | DataValueBinaryOperator::Minus => {
+            let scale = cmp::max(lhs.scale(), rhs.scale());
+            let integral_digits =
+                cmp::max(lhs.precision() - lhs.scale(), rhs.precision() - rhs.scale());
+            (integral_digits + 1 + scale, scale)
+        }
+        DataValueBinaryOperator::Mul => {
+            // the scale is reduced rather than the integral digits, if the precision exceeds 18
+            let integral_digits = (lhs.precision() - lhs.scale()) + (rhs.precision() - rhs.scale());
+            let scale = cmp::min(
+                lhs.scale() + rhs.scale(),
+                DECIMAL_MAX_PRECISION.saturating_sub(integral_digits),
+            );
+            (integral_digits + scale, scale)
+        }
+        DataValueBinaryOperator::Div => {
+            let scale = cmp::min(
+                lhs.scale() + DECIMAL_DIV_SCALE_INCREMENT,
+                DECIMAL_MAX_PRECISION,
+            );
+            (lhs.precision() - lhs.scale() + rhs.scale() + scale, scale)
+        }
+        _ => {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "DataValue Error: Unsupported decimal arithmetic {}",
+                op
+            )));
+        }
+    };
+    DecimalType::try_create(cmp::min(precision, DECIMAL_MAX_PRECISION), scale)
+}
+
+#[derive(Clone)]
+struct DecimalArithmetic {
+    op: DataValueBinaryOperator,
+    lhs_scale: usize,
+    rhs_scale: usize,
+    result_type: DecimalType,
+}
+
+impl DecimalArithmetic {
+    /// Calculates with the unscaled values in i128, which hold the products of two Int64.
+    #[inline]
+    fn eval(&self, l: i128, r: i128, ctx: &mut EvalContext) -> i64 {
+        let scale = self.result_type.scale();
+        let value = match self.op {
+            DataValueBinaryOperator::Plus => scale_up(l, scale - self.lhs_scale)
+                .zip(scale_up(r, scale - self.rhs_scale))
+                .and_then(|(l, r)| l.checked_add(r)),
+            DataValueBinaryOperator::Minus => scale_up(l, scale - self.lhs_scale)
+                .zip(scale_up(r, scale - self.rhs_scale))
+                .and_then(|(l, r)| l.checked_sub(r)),
+            DataValueBinaryOperator::Mul => l.checked_mul(r).map(|v| {
+                let exp = self.lhs_scale + self.rhs_scale - scale;
+                decimal_div_round(v, 10i128.pow(exp as u32))
+            }),
+            DataValueBinaryOperator::Div => {
+                if std::intrinsics::unlikely(r == 0) {
+                    ctx.set_error(ErrorCode::BadArguments("Division by zero"));
+                    return 0;
+                }
+                scale_up(l, scale + self.rhs_scale - self.lhs_scale)
+                    .map(|v| decimal_div_round(v, r))
+            }
+            _ => unreachable!(),
+        };
+
+        match value.and_then(|v| self.result_type.check_unscaled(v)) {
+            Some(v) => v,
+            None => {
+                ctx.set_error(ErrorCode::Overflow(format!(
+                    "Decimal overflow in {} of {}",
+                    self.op,
+                    self.result_type.name()
+                )));
+                0
+            }
+        }
+    }
+}
+
+#[inline]
+fn scale_up(v: i128, exp: usize) -> Option<i128> {
+    decimal_pow10(exp).and_then(|factor| v.checked_mul(factor))
+}
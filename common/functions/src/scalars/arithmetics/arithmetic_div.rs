@@ -18,6 +18,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use num::traits::AsPrimitive;
 
+use super::arithmetic_decimal::decimal_arithmetic;
 use super::arithmetic_mul::arithmetic_mul_div_monotonicity;
 use crate::scalars::BinaryArithmeticFunction;
 use crate::scalars::EvalContext;
@@ -38,6 +39,10 @@ impl ArithmeticDivFunction {
         _display_name: &str,
         args: &[&DataTypeImpl],
     ) -> Result<Box<dyn Function>> {
+        if args[0].data_type_id().is_decimal() || args[1].data_type_id().is_decimal() {
+            return decimal_arithmetic(DataValueBinaryOperator::Div, args);
+        }
+
         with_match_primitive_types_error!(args[0].data_type_id(), |$T| {
             with_match_primitive_types_error!(args[1].data_type_id(), |$D| {
                 BinaryArithmeticFunction::<$T, $D, f64, _>::try_create_func(
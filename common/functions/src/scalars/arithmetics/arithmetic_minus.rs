@@ -23,6 +23,7 @@ use common_exception::Result;
 use num::traits::AsPrimitive;
 use num_traits::WrappingSub;
 
+use super::arithmetic_decimal::decimal_arithmetic;
 use crate::scalars::BinaryArithmeticFunction;
 use crate::scalars::EvalContext;
 use crate::scalars::Function;
@@ -60,6 +61,10 @@ impl ArithmeticMinusFunction {
         let left_type = args[0].data_type_id();
         let right_type = args[1].data_type_id();
 
+        if left_type.is_decimal() || right_type.is_decimal() {
+            return decimal_arithmetic(op, args);
+        }
+
         if left_type.is_date_or_date_time() {
             return with_match_date_type_error!(left_type, |$T| {
                 with_match_primitive_type_id!(right_type, |$D| {
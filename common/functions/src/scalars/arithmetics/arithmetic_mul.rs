@@ -21,6 +21,7 @@ use common_exception::Result;
 use num::traits::AsPrimitive;
 use num_traits::WrappingMul;
 
+use super::arithmetic_decimal::decimal_arithmetic;
 use crate::scalars::BinaryArithmeticFunction;
 use crate::scalars::EvalContext;
 use crate::scalars::Function;
@@ -55,6 +56,10 @@ impl ArithmeticMulFunction {
     ) -> Result<Box<dyn Function>> {
         let op = DataValueBinaryOperator::Mul;
 
+        if args[0].data_type_id().is_decimal() || args[1].data_type_id().is_decimal() {
+            return decimal_arithmetic(op, args);
+        }
+
         with_match_primitive_types_error!(args[0].data_type_id(), |$T| {
             with_match_primitive_types_error!(args[1].data_type_id(), |$D| {
                 let result_type = <($T, $D) as ResultTypeOfBinary>::AddMul::to_data_type();
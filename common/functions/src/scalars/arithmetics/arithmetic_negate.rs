@@ -47,6 +47,15 @@ impl ArithmeticNegateFunction {
     ) -> Result<Box<dyn Function>> {
         let op = DataValueUnaryOperator::Negate;
 
+        // the range of decimal is symmetric, thus negation never overflows
+        if args[0].data_type_id().is_decimal() {
+            return UnaryArithmeticFunction::<i64, i64, _>::try_create_func(
+                op,
+                args[0].clone(),
+                neg,
+            );
+        }
+
         with_match_primitive_types_error!(args[0].data_type_id(), |$T| {
             let result_type = <$T as ResultTypeOfUnary>::Negate::to_data_type();
             match result_type.data_type_id() {
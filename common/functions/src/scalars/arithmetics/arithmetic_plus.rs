@@ -23,6 +23,7 @@ use common_exception::Result;
 use num::traits::AsPrimitive;
 use num_traits::WrappingAdd;
 
+use super::arithmetic_decimal::decimal_arithmetic;
 use crate::scalars::BinaryArithmeticFunction;
 use crate::scalars::EvalContext;
 use crate::scalars::Function;
@@ -60,6 +61,10 @@ impl ArithmeticPlusFunction {
         let left_type = args[0].data_type_id();
         let right_type = args[1].data_type_id();
 
+        if left_type.is_decimal() || right_type.is_decimal() {
+            return decimal_arithmetic(op, args);
+        }
+
         let error_fn = || -> Result<Box<dyn Function>> {
             Err(ErrorCode::BadDataValueType(format!(
                 "DataValue Error: Unsupported arithmetic ({:?}) {} ({:?})",
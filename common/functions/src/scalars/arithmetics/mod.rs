@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod arithmetic;
+mod arithmetic_decimal;
 mod arithmetic_div;
 mod arithmetic_intdiv;
 mod arithmetic_minus;
@@ -0,0 +1,105 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::cast_with_type::arrow_cast_compute;
+use super::cast_with_type::new_mutable_bitmap;
+use super::cast_with_type::CastOptions;
+use crate::scalars::FunctionContext;
+
+pub fn cast_from_decimal(
+    column: &ColumnRef,
+    from_type: &DataTypeImpl,
+    data_type: &DataTypeImpl,
+    cast_options: &CastOptions,
+    func_ctx: &FunctionContext,
+) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let c = Series::remove_nullable(column);
+    let c: &Int64Column = Series::check_get(&c)?;
+    let size = c.len();
+
+    let decimal: DecimalType = from_type.to_owned().try_into()?;
+
+    match data_type.data_type_id() {
+        TypeID::String => {
+            let mut builder = MutableStringColumn::with_capacity(size);
+            for v in c.iter() {
+                builder.append_value(decimal.format_value(*v).as_bytes());
+            }
+            Ok((builder.to_column(), None))
+        }
+
+        TypeID::Decimal => {
+            let target: DecimalType = data_type.to_owned().try_into()?;
+            let mut builder = ColumnBuilder::<i64>::with_capacity(size);
+            let mut bitmap = new_mutable_bitmap(size, true);
+            for (row, v) in c.iter().enumerate() {
+                match target.rescale(*v as i128, decimal.scale()) {
+                    Some(v) => builder.append(v),
+                    None => {
+                        builder.append(0);
+                        bitmap.set(row, false);
+                    }
+                }
+            }
+            Ok((builder.build(size), Some(bitmap.into())))
+        }
+
+        TypeID::Float32 | TypeID::Float64 => {
+            let it = c.iter().map(|v| decimal.to_f64(*v));
+            let result: ColumnRef = Arc::new(Float64Column::from_iterator(it));
+            arrow_cast_compute(
+                &result,
+                &Float64Type::new_impl(),
+                data_type,
+                cast_options,
+                func_ctx,
+            )
+        }
+
+        TypeID::Boolean => {
+            let it = c.iter().map(|v| *v != 0);
+            let result = Arc::new(BooleanColumn::from_iterator(it));
+            Ok((result, None))
+        }
+
+        // rounds half away from zero, then casts as Int64
+        type_id if type_id.is_integer() => {
+            let factor = 10i128.pow(decimal.scale() as u32);
+            let it = c
+                .iter()
+                .map(|v| decimal_div_round(*v as i128, factor) as i64);
+            let result: ColumnRef = Arc::new(Int64Column::from_iterator(it));
+            arrow_cast_compute(
+                &result,
+                &Int64Type::new_impl(),
+                data_type,
+                cast_options,
+                func_ctx,
+            )
+        }
+
+        _ => Err(ErrorCode::BadDataValueType(format!(
+            "Cast error happens in casting from {} to {}",
+            from_type.name(),
+            data_type.name()
+        ))),
+    }
+}
@@ -80,6 +80,20 @@ pub fn cast_from_string(
             }
             Ok((builder.build(size), Some(bitmap.into())))
         }
+        TypeID::Decimal => {
+            let decimal: DecimalType = data_type.to_owned().try_into()?;
+            let mut builder = ColumnBuilder::<i64>::with_capacity(size);
+            for (row, v) in str_column.iter().enumerate() {
+                match decimal.parse_value(v) {
+                    Some(v) => builder.append(v),
+                    None => {
+                        builder.append(0);
+                        bitmap.set(row, false);
+                    }
+                }
+            }
+            Ok((builder.build(size), Some(bitmap.into())))
+        }
         TypeID::Interval => todo!(),
         _ => arrow_cast_compute(column, from_type, data_type, cast_options, func_ctx),
     }
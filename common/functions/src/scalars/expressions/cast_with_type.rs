@@ -26,6 +26,7 @@ use common_exception::Result;
 use common_io::prelude::FormatSettings;
 
 use super::cast_from_datetimes::cast_from_date;
+use super::cast_from_decimal::cast_from_decimal;
 use super::cast_from_string::cast_from_string;
 use super::cast_from_variant::cast_from_variant;
 use crate::scalars::expressions::cast_from_datetimes::cast_from_timestamp;
@@ -151,6 +152,13 @@ pub fn cast_with_type(
             cast_options,
             func_ctx,
         ),
+        TypeID::Decimal => cast_from_decimal(
+            column,
+            &nonull_from_type,
+            &nonull_data_type,
+            cast_options,
+            func_ctx,
+        ),
         TypeID::Variant | TypeID::VariantArray | TypeID::VariantObject => {
             cast_from_variant(column, &nonull_data_type, func_ctx)
         }
@@ -301,6 +309,53 @@ pub fn cast_to_timestamp(
     return Ok((builder.build(size), None));
 }
 
+pub fn cast_to_decimal(
+    column: &ColumnRef,
+    from_type: &DataTypeImpl,
+    data_type: &DataTypeImpl,
+) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let column = Series::remove_nullable(column);
+    let size = column.len();
+    let decimal: DecimalType = data_type.to_owned().try_into()?;
+    let is_floating = from_type.data_type_id().is_floating();
+
+    let mut builder = ColumnBuilder::<i64>::with_capacity(size);
+    let mut bitmap = new_mutable_bitmap(size, true);
+    let mut append = |row: usize, v: Option<i64>| match v {
+        Some(v) => builder.append(v),
+        None => {
+            // the value overflows the precision
+            builder.append(0);
+            bitmap.set(row, false);
+        }
+    };
+
+    with_match_primitive_type_id!(from_type.data_type_id(), |$T| {
+        let col: &PrimitiveColumn<$T> = Series::check_get(&column)?;
+        for (row, v) in col.iter().enumerate() {
+            if is_floating {
+                append(row, decimal.from_f64(*v as f64));
+            } else {
+                append(row, decimal.rescale(*v as i128, 0));
+            }
+        }
+    }, {
+        if from_type.data_type_id() == TypeID::Boolean {
+            let col: &BooleanColumn = Series::check_get(&column)?;
+            for (row, v) in col.iter().enumerate() {
+                append(row, decimal.rescale(v as i128, 0));
+            }
+        } else {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Cast error happens in casting from {} to {}",
+                from_type.name(),
+                data_type.name()
+            )));
+        }
+    });
+    Ok((builder.build(size), Some(bitmap.into())))
+}
+
 // cast using arrow's cast compute
 pub fn arrow_cast_compute(
     column: &ColumnRef,
@@ -313,6 +368,8 @@ pub fn arrow_cast_compute(
         return cast_to_variant(column, from_type, data_type, func_ctx);
     } else if data_type.data_type_id() == TypeID::Timestamp {
         return cast_to_timestamp(column, from_type);
+    } else if data_type.data_type_id() == TypeID::Decimal {
+        return cast_to_decimal(column, from_type, data_type);
     }
 
     let arrow_array = column.as_arrow_array();
@@ -12,7 +12,69 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
+
 pub enum WindowFunction {
     AggregateFunction,
     BuiltInFunction,
 }
+
+/// Functions that can only be evaluated over a window, i.e. called with `OVER (...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum BuiltInWindowFunction {
+    /// Number of the current row within its partition, counting from 1
+    RowNumber,
+    /// Rank of the current row with gaps
+    Rank,
+    /// Rank of the current row without gaps
+    DenseRank,
+    /// Value of the row `offset` rows before the current row within the partition
+    Lag,
+    /// Value of the row `offset` rows after the current row within the partition
+    Lead,
+    /// Value of the first row of the window frame
+    FirstValue,
+    /// Value of the last row of the window frame
+    LastValue,
+    /// Bucket number ranging from 1 to `n`, dividing the partition as equally as possible
+    Ntile,
+}
+
+impl BuiltInWindowFunction {
+    pub fn try_from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "row_number" => Some(Self::RowNumber),
+            "rank" => Some(Self::Rank),
+            "dense_rank" => Some(Self::DenseRank),
+            "lag" => Some(Self::Lag),
+            "lead" => Some(Self::Lead),
+            "first_value" => Some(Self::FirstValue),
+            "last_value" => Some(Self::LastValue),
+            "ntile" => Some(Self::Ntile),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RowNumber => "row_number",
+            Self::Rank => "rank",
+            Self::DenseRank => "dense_rank",
+            Self::Lag => "lag",
+            Self::Lead => "lead",
+            Self::FirstValue => "first_value",
+            Self::LastValue => "last_value",
+            Self::Ntile => "ntile",
+        }
+    }
+}
+
+impl fmt::Display for BuiltInWindowFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub fn is_builtin_window_function(name: &str) -> bool {
+    BuiltInWindowFunction::try_from_name(name).is_some()
+}
@@ -50,16 +50,13 @@ pub struct WindowFrame {
     pub end_bound: WindowFrameBound,
 }
 
-impl TryFrom<ast::WindowFrame> for WindowFrame {
-    type Error = ErrorCode;
-
-    fn try_from(value: ast::WindowFrame) -> Result<Self, Self::Error> {
-        let start_bound = value.start_bound.into();
-        let end_bound = value
-            .end_bound
-            .map(WindowFrameBound::from)
-            .unwrap_or(WindowFrameBound::CurrentRow);
-
+impl WindowFrame {
+    /// Creates a window frame and checks that its bounds are valid.
+    pub fn try_create(
+        units: WindowFrameUnits,
+        start_bound: WindowFrameBound,
+        end_bound: WindowFrameBound,
+    ) -> Result<Self, ErrorCode> {
         if let WindowFrameBound::Following(None) = start_bound {
             Err(ErrorCode::LogicalError(
                 "Invalid window frame: start bound cannot be unbounded following".to_owned(),
@@ -74,7 +71,6 @@ impl TryFrom<ast::WindowFrame> for WindowFrame {
                 start_bound, end_bound
             )))
         } else {
-            let units = value.units.into();
             Ok(Self {
                 units,
                 start_bound,
@@ -84,6 +80,29 @@ impl TryFrom<ast::WindowFrame> for WindowFrame {
     }
 }
 
+impl TryFrom<ast::WindowFrame> for WindowFrame {
+    type Error = ErrorCode;
+
+    fn try_from(value: ast::WindowFrame) -> Result<Self, Self::Error> {
+        let start_bound = value.start_bound.into();
+        let end_bound = value
+            .end_bound
+            .map(WindowFrameBound::from)
+            .unwrap_or(WindowFrameBound::CurrentRow);
+        Self::try_create(value.units.into(), start_bound, end_bound)
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
+
 impl Default for WindowFrame {
     fn default() -> Self {
         WindowFrame {
@@ -600,6 +600,7 @@ impl KVApiTestSuite {
                 key: txn_key.clone(),
                 value: b"new_v1".to_vec(),
                 prev_value: true,
+                expire_at: None,
             })),
         }];
 
@@ -769,6 +770,7 @@ impl KVApiTestSuite {
                     key: txn_key.clone(),
                     value: b"new_v1".to_vec(),
                     prev_value: true,
+                    expire_at: None,
                 })),
             }];
 
@@ -826,6 +828,7 @@ impl KVApiTestSuite {
                     key: txn_key1.clone(),
                     value: b"new_v1".to_vec(),
                     prev_value: true,
+                    expire_at: None,
                 })),
             }];
 
@@ -892,6 +895,7 @@ impl KVApiTestSuite {
                         key: txn_key1.clone(),
                         value: val1_new.to_vec(),
                         prev_value: true,
+                        expire_at: None,
                     })),
                 },
                 // change k2
@@ -900,6 +904,7 @@ impl KVApiTestSuite {
                         key: txn_key2.clone(),
                         value: b"new_v2".to_vec(),
                         prev_value: true,
+                        expire_at: None,
                     })),
                 },
                 // get k1
@@ -1005,6 +1010,7 @@ impl KVApiTestSuite {
                         key: txn_key1.clone(),
                         value: val1_new.to_vec(),
                         prev_value: true,
+                        expire_at: None,
                     })),
                 },
                 // get k1
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::sync::Arc;
 
@@ -38,6 +39,8 @@ use common_meta_app::schema::DropDatabaseReq;
 use common_meta_app::schema::DropTableReply;
 use common_meta_app::schema::DropTableReq;
 use common_meta_app::schema::GetDatabaseReq;
+use common_meta_app::schema::GetTableCopiedFileReply;
+use common_meta_app::schema::GetTableCopiedFileReq;
 use common_meta_app::schema::GetTableReq;
 use common_meta_app::schema::ListDatabaseReq;
 use common_meta_app::schema::ListTableReq;
@@ -45,6 +48,8 @@ use common_meta_app::schema::RenameDatabaseReply;
 use common_meta_app::schema::RenameDatabaseReq;
 use common_meta_app::schema::RenameTableReply;
 use common_meta_app::schema::RenameTableReq;
+use common_meta_app::schema::TableCopiedFileInfo;
+use common_meta_app::schema::TableCopiedFileKey;
 use common_meta_app::schema::TableId;
 use common_meta_app::schema::TableIdList;
 use common_meta_app::schema::TableIdListKey;
@@ -52,12 +57,16 @@ use common_meta_app::schema::TableIdent;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::TableNameIdent;
+use common_meta_app::schema::TruncateTableReply;
+use common_meta_app::schema::TruncateTableReq;
 use common_meta_app::schema::UndropDatabaseReply;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReply;
 use common_meta_app::schema::UndropTableReq;
 use common_meta_app::schema::UpdateTableMetaReply;
 use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_app::schema::UpsertTableCopiedFileReply;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_meta_app::schema::UpsertTableOptionReply;
 use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::app_error::AppError;
@@ -88,6 +97,7 @@ use common_meta_types::MetaError;
 use common_meta_types::MetaId;
 use common_meta_types::Operation;
 use common_meta_types::TxnCondition;
+use common_meta_types::TxnDeleteByPrefixRequest;
 use common_meta_types::TxnDeleteRequest;
 use common_meta_types::TxnOp;
 use common_meta_types::TxnOpResponse;
@@ -930,6 +940,7 @@ impl<KV: KVApi> SchemaApi for KV {
                         txn_op_del(&dbid_tbname), // (db_id, tb_name) -> tb_id
                         txn_op_put(&tbid, serialize_struct(&tb_meta)?), // (tenant, db_id, tb_id) -> tb_meta
                         txn_op_put(&tb_count_key, serialize_u64(tb_count - 1)?), // _fd_table_count/tenant -> tb_count
+                        txn_op_del_by_prefix(table_copied_files_prefix(table_id)), // _fd_table_copied_files/table_id/*
                     ],
                     else_then: vec![],
                 };
@@ -1082,6 +1093,7 @@ impl<KV: KVApi> SchemaApi for KV {
                         //txn_op_put(&dbid_tbname_idlist, serialize_struct(&tb_id_list)?)?, // _fd_table_id_list/db_id/table_name -> tb_id_list
                         txn_op_put(&tbid, serialize_struct(&tb_meta)?), // (tenant, db_id, tb_id) -> tb_meta
                         txn_op_put(&tb_count_key, serialize_u64(tb_count + 1)?), // _fd_table_count/tenant -> tb_count
+                        txn_op_del_by_prefix(table_copied_files_prefix(table_id)), // _fd_table_copied_files/table_id/*
                     ],
                     else_then: vec![],
                 };
@@ -1645,14 +1657,25 @@ impl<KV: KVApi> SchemaApi for KV {
                 )));
             }
 
+            let mut if_then = vec![
+                txn_op_put(&tbid, serialize_struct(&req.new_table_meta)?), // tb_id -> tb_meta
+            ];
+            // The loaded files are removed or recorded only if the table meta is committed.
+            if req.truncate_copied_files {
+                if_then.push(txn_op_del_by_prefix(table_copied_files_prefix(
+                    req.table_id,
+                )));
+            }
+            if let Some(copied_files) = &req.copied_files {
+                if_then.extend(build_upsert_table_copied_file_info_ops(copied_files)?);
+            }
+
             let txn_req = TxnRequest {
                 condition: vec![
                     // table is not changed
                     txn_cond_seq(&tbid, Eq, tb_meta_seq),
                 ],
-                if_then: vec![
-                    txn_op_put(&tbid, serialize_struct(&req.new_table_meta)?), // tb_id -> tb_meta
-                ],
+                if_then,
                 else_then: vec![],
             };
 
@@ -1748,6 +1771,89 @@ impl<KV: KVApi> SchemaApi for KV {
         Ok(CountTablesReply { count })
     }
 
+    /// Get the files among `req.files` that were loaded into a table.
+    ///
+    /// The records expire with the kv, thus expired files are not returned.
+    #[tracing::instrument(level = "debug", ret, err, skip_all)]
+    async fn get_table_copied_file_info(
+        &self,
+        req: GetTableCopiedFileReq,
+    ) -> Result<GetTableCopiedFileReply, MetaError> {
+        tracing::debug!(req = debug(&req), "SchemaApi: {}", func_name!());
+
+        let keys = req
+            .files
+            .iter()
+            .map(|file| {
+                TableCopiedFileKey {
+                    table_id: req.table_id,
+                    file: file.clone(),
+                }
+                .to_key()
+            })
+            .collect::<Vec<_>>();
+
+        let res = self.mget_kv(&keys).await?;
+
+        let mut file_info = BTreeMap::new();
+        for (file, seq_v) in req.files.into_iter().zip(res.into_iter()) {
+            if let Some(seq_v) = seq_v {
+                let info: TableCopiedFileInfo = deserialize_struct(&seq_v.data)?;
+                file_info.insert(file, info);
+            }
+        }
+
+        Ok(GetTableCopiedFileReply { file_info })
+    }
+
+    /// Record the files loaded into a table, the existing records of the files are overridden.
+    #[tracing::instrument(level = "debug", ret, err, skip_all)]
+    async fn upsert_table_copied_file_info(
+        &self,
+        req: UpsertTableCopiedFileReq,
+    ) -> Result<UpsertTableCopiedFileReply, MetaError> {
+        tracing::debug!(req = debug(&req), "SchemaApi: {}", func_name!());
+
+        let txn_req = TxnRequest {
+            condition: vec![],
+            if_then: build_upsert_table_copied_file_info_ops(&req)?,
+            else_then: vec![],
+        };
+
+        let (succ, _responses) = send_txn(self, txn_req).await?;
+
+        tracing::debug!(
+            table_id = display(req.table_id),
+            succ = display(succ),
+            "upsert_table_copied_file_info"
+        );
+
+        Ok(UpsertTableCopiedFileReply {})
+    }
+
+    #[tracing::instrument(level = "debug", ret, err, skip_all)]
+    async fn truncate_table(&self, req: TruncateTableReq) -> Result<TruncateTableReply, MetaError> {
+        tracing::debug!(req = debug(&req), "SchemaApi: {}", func_name!());
+
+        let txn_req = TxnRequest {
+            condition: vec![],
+            if_then: vec![txn_op_del_by_prefix(table_copied_files_prefix(
+                req.table_id,
+            ))],
+            else_then: vec![],
+        };
+
+        let (succ, _responses) = send_txn(self, txn_req).await?;
+
+        tracing::debug!(
+            table_id = display(req.table_id),
+            succ = display(succ),
+            "truncate_table"
+        );
+
+        Ok(TruncateTableReply {})
+    }
+
     fn name(&self) -> String {
         "SchemaApiImpl".to_string()
     }
@@ -2186,6 +2292,23 @@ pub fn txn_op_put(key: &impl KVApiKey, value: Vec<u8>) -> TxnOp {
             key: key.to_key(),
             value,
             prev_value: true,
+            expire_at: None,
+        })),
+    }
+}
+
+/// Build a txn operation that puts a record with an optional expiration time in seconds since 1970.
+pub fn txn_op_put_with_expire(
+    key: &impl KVApiKey,
+    value: Vec<u8>,
+    expire_at: Option<u64>,
+) -> TxnOp {
+    TxnOp {
+        request: Some(Request::Put(TxnPutRequest {
+            key: key.to_key(),
+            value,
+            prev_value: true,
+            expire_at,
         })),
     }
 }
@@ -2200,6 +2323,39 @@ pub fn txn_op_del(key: &impl KVApiKey) -> TxnOp {
     }
 }
 
+/// Build a txn operation that deletes all the records with the given prefix.
+pub fn txn_op_del_by_prefix(prefix: String) -> TxnOp {
+    TxnOp {
+        request: Some(Request::DeleteByPrefix(TxnDeleteByPrefixRequest { prefix })),
+    }
+}
+
+/// The prefix of all the copied file records of a table: `__fd_table_copied_files/<table_id>/`.
+///
+/// The trailing slash prevents the records of table 10 from matching the prefix of table 1.
+fn table_copied_files_prefix(table_id: u64) -> String {
+    format!("{}/{}/", TableCopiedFileKey::PREFIX, table_id)
+}
+
+/// Build the txn operations that record the files loaded into a table.
+fn build_upsert_table_copied_file_info_ops(
+    req: &UpsertTableCopiedFileReq,
+) -> Result<Vec<TxnOp>, MetaError> {
+    let mut ops = Vec::with_capacity(req.file_info.len());
+    for (file, info) in req.file_info.iter() {
+        let key = TableCopiedFileKey {
+            table_id: req.table_id,
+            file: file.clone(),
+        };
+        ops.push(txn_op_put_with_expire(
+            &key,
+            serialize_struct(info)?,
+            req.expire_at,
+        ));
+    }
+    Ok(ops)
+}
+
 async fn send_txn(
     kv_api: &impl KVApi,
     txn_req: TxnRequest,
@@ -21,6 +21,7 @@ use common_meta_app::schema::DBIdTableName;
 use common_meta_app::schema::DatabaseId;
 use common_meta_app::schema::DatabaseNameIdent;
 use common_meta_app::schema::DbIdListKey;
+use common_meta_app::schema::TableCopiedFileKey;
 use common_meta_app::schema::TableId;
 use common_meta_app::schema::TableIdListKey;
 use kv_api_key::check_segment;
@@ -42,6 +43,7 @@ const PREFIX_TABLE_BY_ID: &str = "__fd_table_by_id";
 const PREFIX_TABLE_ID_LIST: &str = "__fd_table_id_list";
 const PREFIX_ID_GEN: &str = "__fd_id_gen";
 const PREFIX_TABLE_COUNT: &str = "__fd_table_count";
+const PREFIX_TABLE_COPIED_FILES: &str = "__fd_table_copied_files";
 
 /// Key for database id generator
 #[derive(Debug)]
@@ -276,3 +278,29 @@ impl KVApiKey for CountTablesKey {
         Ok(CountTablesKey { tenant })
     }
 }
+
+/// "__fd_table_copied_files/<table_id>/<file_name>" -> TableCopiedFileInfo
+impl KVApiKey for TableCopiedFileKey {
+    const PREFIX: &'static str = PREFIX_TABLE_COPIED_FILES;
+
+    fn to_key(&self) -> String {
+        format!("{}/{}/{}", Self::PREFIX, self.table_id, escape(&self.file))
+    }
+
+    fn from_key(s: &str) -> Result<Self, KVApiKeyError> {
+        let mut elts = s.split('/');
+
+        let prefix = check_segment_present(elts.next(), 0, s)?;
+        check_segment(prefix, 0, Self::PREFIX)?;
+
+        let table_id = check_segment_present(elts.next(), 1, s)?;
+        let table_id = decode_id(table_id)?;
+
+        let file = check_segment_present(elts.next(), 2, s)?;
+        let file = unescape(file)?;
+
+        check_segment_absent(elts.next(), 3, s)?;
+
+        Ok(TableCopiedFileKey { table_id, file })
+    }
+}
@@ -35,11 +35,13 @@ use common_meta_app::schema::DbIdListKey;
 use common_meta_app::schema::DropDatabaseReq;
 use common_meta_app::schema::DropTableReq;
 use common_meta_app::schema::GetDatabaseReq;
+use common_meta_app::schema::GetTableCopiedFileReq;
 use common_meta_app::schema::GetTableReq;
 use common_meta_app::schema::ListDatabaseReq;
 use common_meta_app::schema::ListTableReq;
 use common_meta_app::schema::RenameDatabaseReq;
 use common_meta_app::schema::RenameTableReq;
+use common_meta_app::schema::TableCopiedFileInfo;
 use common_meta_app::schema::TableId;
 use common_meta_app::schema::TableIdList;
 use common_meta_app::schema::TableIdListKey;
@@ -48,9 +50,11 @@ use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::TableNameIdent;
 use common_meta_app::schema::TableStatistics;
+use common_meta_app::schema::TruncateTableReq;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReq;
 use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::GCDroppedDataReq;
 use common_meta_types::MatchSeq;
@@ -239,6 +243,10 @@ impl SchemaApiTestSuite {
             .table_drop_out_of_retention_time_history(&b.build().await)
             .await?;
         suite.get_table_by_id(&b.build().await).await?;
+        suite.table_copied_file_info(&b.build().await).await?;
+        suite
+            .table_copied_file_info_with_table(&b.build().await)
+            .await?;
 
         Ok(())
     }
@@ -1673,6 +1681,8 @@ impl SchemaApiTestSuite {
                     table_id,
                     seq: MatchSeq::Exact(table_version),
                     new_table_meta: new_table_meta.clone(),
+                    copied_files: None,
+                    truncate_copied_files: false,
                 })
                 .await?;
 
@@ -1692,6 +1702,8 @@ impl SchemaApiTestSuite {
                         table_id,
                         seq: MatchSeq::Exact(table_version + 1),
                         new_table_meta: new_table_meta.clone(),
+                        copied_files: None,
+                        truncate_copied_files: false,
                     })
                     .await;
 
@@ -2744,6 +2756,284 @@ impl SchemaApiTestSuite {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn table_copied_file_info<MT: SchemaApi>(&self, mt: &MT) -> anyhow::Result<()> {
+        let table_id = 1;
+
+        let file_info = |etag: &str, content_length| TableCopiedFileInfo {
+            etag: Some(etag.to_string()),
+            content_length,
+            last_modified: Some(Utc::now()),
+        };
+
+        let get_req = GetTableCopiedFileReq {
+            table_id,
+            files: vec![
+                "a.csv".to_string(),
+                "b/c.csv".to_string(),
+                "d.csv".to_string(),
+            ],
+        };
+
+        tracing::info!("--- get copied files of a table without files");
+        {
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert!(got.file_info.is_empty());
+        }
+
+        tracing::info!("--- upsert and get copied files");
+        {
+            let file_infos = maplit::btreemap! {
+                "a.csv".to_string() => file_info("etag-a", 10),
+                "b/c.csv".to_string() => file_info("etag-c", 20),
+            };
+            mt.upsert_table_copied_file_info(UpsertTableCopiedFileReq {
+                table_id,
+                file_info: file_infos.clone(),
+                expire_at: None,
+            })
+            .await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert_eq!(file_infos, got.file_info);
+
+            tracing::info!("--- files of other tables are not affected");
+            let got = mt
+                .get_table_copied_file_info(GetTableCopiedFileReq {
+                    table_id: table_id + 1,
+                    ..get_req.clone()
+                })
+                .await?;
+            assert!(got.file_info.is_empty());
+        }
+
+        tracing::info!("--- upsert overrides the existing file");
+        {
+            let info = file_info("etag-a-2", 11);
+            mt.upsert_table_copied_file_info(UpsertTableCopiedFileReq {
+                table_id,
+                file_info: maplit::btreemap! {"a.csv".to_string() => info.clone()},
+                expire_at: None,
+            })
+            .await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert_eq!(2, got.file_info.len());
+            assert_eq!(Some(&info), got.file_info.get("a.csv"));
+        }
+
+        tracing::info!("--- expired files are not returned");
+        {
+            let expire_at = (Utc::now() - Duration::seconds(10)).timestamp() as u64;
+            mt.upsert_table_copied_file_info(UpsertTableCopiedFileReq {
+                table_id,
+                file_info: maplit::btreemap! {"d.csv".to_string() => file_info("etag-d", 30)},
+                expire_at: Some(expire_at),
+            })
+            .await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert_eq!(2, got.file_info.len());
+            assert!(got.file_info.get("d.csv").is_none());
+        }
+
+        tracing::info!("--- truncate table removes its copied files only");
+        {
+            // table 10 shares the key prefix "<PREFIX>/1" with table 1.
+            let other_table_id = 10;
+            mt.upsert_table_copied_file_info(UpsertTableCopiedFileReq {
+                table_id: other_table_id,
+                file_info: maplit::btreemap! {"a.csv".to_string() => file_info("etag-a", 10)},
+                expire_at: None,
+            })
+            .await?;
+
+            mt.truncate_table(TruncateTableReq { table_id }).await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert!(got.file_info.is_empty());
+
+            let got = mt
+                .get_table_copied_file_info(GetTableCopiedFileReq {
+                    table_id: other_table_id,
+                    ..get_req.clone()
+                })
+                .await?;
+            assert_eq!(1, got.file_info.len());
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn table_copied_file_info_with_table<MT: SchemaApi>(
+        &self,
+        mt: &MT,
+    ) -> anyhow::Result<()> {
+        let tenant = "tenant1";
+        let db_name = "db1";
+        let tbl_name = "tb1";
+
+        let name_ident = TableNameIdent {
+            tenant: tenant.to_string(),
+            db_name: db_name.to_string(),
+            table_name: tbl_name.to_string(),
+        };
+
+        let file_infos = maplit::btreemap! {
+            "a.csv".to_string() => TableCopiedFileInfo {
+                etag: Some("etag-a".to_string()),
+                content_length: 10,
+                last_modified: Some(Utc::now()),
+            },
+        };
+
+        tracing::info!("--- prepare db and table");
+        let table_id = {
+            mt.create_database(CreateDatabaseReq {
+                if_not_exists: false,
+                name_ident: DatabaseNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: db_name.to_string(),
+                },
+                meta: DatabaseMeta {
+                    engine: "".to_string(),
+                    ..DatabaseMeta::default()
+                },
+            })
+            .await?;
+
+            let res = mt
+                .create_table(CreateTableReq {
+                    if_not_exists: false,
+                    name_ident: name_ident.clone(),
+                    table_meta: TableMeta {
+                        schema: Arc::new(DataSchema::new(vec![DataField::new(
+                            "number",
+                            u64::to_data_type(),
+                        )])),
+                        engine: "JSON".to_string(),
+                        ..TableMeta::default()
+                    },
+                })
+                .await?;
+            res.table_id
+        };
+
+        let get_req = GetTableCopiedFileReq {
+            table_id,
+            files: vec!["a.csv".to_string()],
+        };
+
+        tracing::info!("--- update table meta records the copied files");
+        {
+            let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+            mt.update_table_meta(UpdateTableMetaReq {
+                table_id,
+                seq: MatchSeq::Exact(table.ident.seq),
+                new_table_meta: table.meta.clone(),
+                copied_files: Some(UpsertTableCopiedFileReq {
+                    table_id,
+                    file_info: file_infos.clone(),
+                    expire_at: None,
+                }),
+                truncate_copied_files: false,
+            })
+            .await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert_eq!(file_infos, got.file_info);
+        }
+
+        tracing::info!("--- copied files are not recorded if table meta is not updated");
+        {
+            let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+            let res = mt
+                .update_table_meta(UpdateTableMetaReq {
+                    table_id,
+                    seq: MatchSeq::Exact(table.ident.seq + 1),
+                    new_table_meta: table.meta.clone(),
+                    copied_files: Some(UpsertTableCopiedFileReq {
+                        table_id,
+                        file_info: maplit::btreemap! {
+                            "b.csv".to_string() => TableCopiedFileInfo::default(),
+                        },
+                        expire_at: None,
+                    }),
+                    truncate_copied_files: true,
+                })
+                .await;
+            assert!(res.is_err());
+
+            let got = mt
+                .get_table_copied_file_info(GetTableCopiedFileReq {
+                    table_id,
+                    files: vec!["b.csv".to_string()],
+                })
+                .await?;
+            assert!(got.file_info.is_empty());
+
+            // the files loaded before are not truncated either.
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert_eq!(file_infos, got.file_info);
+        }
+
+        tracing::info!("--- update table meta truncates the copied files");
+        {
+            let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+            mt.update_table_meta(UpdateTableMetaReq {
+                table_id,
+                seq: MatchSeq::Exact(table.ident.seq),
+                new_table_meta: table.meta.clone(),
+                copied_files: None,
+                truncate_copied_files: true,
+            })
+            .await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert!(got.file_info.is_empty());
+
+            mt.upsert_table_copied_file_info(UpsertTableCopiedFileReq {
+                table_id,
+                file_info: file_infos.clone(),
+                expire_at: None,
+            })
+            .await?;
+        }
+
+        tracing::info!("--- drop table removes the copied files");
+        {
+            mt.drop_table(DropTableReq {
+                if_exists: false,
+                name_ident: name_ident.clone(),
+            })
+            .await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert!(got.file_info.is_empty());
+        }
+
+        tracing::info!("--- undrop table removes the copied files");
+        {
+            mt.upsert_table_copied_file_info(UpsertTableCopiedFileReq {
+                table_id,
+                file_info: file_infos.clone(),
+                expire_at: None,
+            })
+            .await?;
+
+            mt.undrop_table(UndropTableReq {
+                name_ident: name_ident.clone(),
+            })
+            .await?;
+
+            let got = mt.get_table_copied_file_info(get_req.clone()).await?;
+            assert!(got.file_info.is_empty());
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn table_list<MT: SchemaApi>(&self, mt: &MT) -> anyhow::Result<()> {
         let tenant = "tenant1";
@@ -42,10 +42,14 @@ pub use table::CreateTableReq;
 pub use table::DBIdTableName;
 pub use table::DropTableReply;
 pub use table::DropTableReq;
+pub use table::GetTableCopiedFileReply;
+pub use table::GetTableCopiedFileReq;
 pub use table::GetTableReq;
 pub use table::ListTableReq;
 pub use table::RenameTableReply;
 pub use table::RenameTableReq;
+pub use table::TableCopiedFileInfo;
+pub use table::TableCopiedFileKey;
 pub use table::TableId;
 pub use table::TableIdList;
 pub use table::TableIdListKey;
@@ -54,9 +58,13 @@ pub use table::TableInfo;
 pub use table::TableMeta;
 pub use table::TableNameIdent;
 pub use table::TableStatistics;
+pub use table::TruncateTableReply;
+pub use table::TruncateTableReq;
 pub use table::UndropTableReply;
 pub use table::UndropTableReq;
 pub use table::UpdateTableMetaReply;
 pub use table::UpdateTableMetaReq;
+pub use table::UpsertTableCopiedFileReply;
+pub use table::UpsertTableCopiedFileReq;
 pub use table::UpsertTableOptionReply;
 pub use table::UpsertTableOptionReq;
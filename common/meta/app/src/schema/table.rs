@@ -502,6 +502,11 @@ pub struct UpdateTableMetaReq {
     pub table_id: u64,
     pub seq: MatchSeq,
     pub new_table_meta: TableMeta,
+    /// Files loaded by `COPY INTO <table>`, recorded in the same transaction as the table meta.
+    pub copied_files: Option<UpsertTableCopiedFileReq>,
+    /// Remove all the files loaded into the table before, e.g. on `TRUNCATE TABLE`, in the same
+    /// transaction as the table meta, and before the `copied_files` are recorded.
+    pub truncate_copied_files: bool,
 }
 
 impl UpsertTableOptionReq {
@@ -611,3 +616,58 @@ pub struct CountTablesReq {
 pub struct CountTablesReply {
     pub count: u64,
 }
+
+/// The metadata of a file that was loaded into a table by `COPY INTO <table>`.
+///
+/// A file is considered unchanged since its last load if all the fields are equal.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+pub struct TableCopiedFileInfo {
+    pub etag: Option<String>,
+    pub content_length: u64,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// Key of a file loaded into a table: `table_id` and the file path in the stage.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+pub struct TableCopiedFileKey {
+    pub table_id: u64,
+    pub file: String,
+}
+
+impl Display for TableCopiedFileKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}'.'{}'", self.table_id, self.file)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GetTableCopiedFileReq {
+    pub table_id: u64,
+    pub files: Vec<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct GetTableCopiedFileReply {
+    /// The loaded files among the requested ones, files not found are absent.
+    pub file_info: BTreeMap<String, TableCopiedFileInfo>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpsertTableCopiedFileReq {
+    pub table_id: u64,
+    pub file_info: BTreeMap<String, TableCopiedFileInfo>,
+    /// Expiration time in seconds since 1970, the files are kept forever if it is `None`.
+    pub expire_at: Option<u64>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpsertTableCopiedFileReply {}
+
+/// Remove the table data related records kept in the meta service, e.g. the copied files.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TruncateTableReq {
+    pub table_id: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TruncateTableReply {}
@@ -535,7 +535,9 @@ impl StateMachine {
             &put.key,
             &MatchSeq::Any,
             Operation::Update(put.value.clone()),
-            None,
+            put.expire_at.map(|expire_at| KVMeta {
+                expire_at: Some(expire_at),
+            }),
         )?;
 
         let put_resp = TxnPutResponse {
@@ -146,8 +146,8 @@ impl Display for TxnPutRequest {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Put key={}, need prev_value: {}",
-            self.key, self.prev_value
+            "Put key={}, need prev_value: {}, expire_at: {:?}",
+            self.key, self.prev_value, self.expire_at
         )
     }
 }
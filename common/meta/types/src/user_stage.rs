@@ -117,6 +117,7 @@ impl FromStr for StageFileCompression {
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
 pub enum StageFileFormatType {
     Csv,
+    Tsv,
     Json,
     Avro,
     Orc,
@@ -135,13 +136,14 @@ impl FromStr for StageFileFormatType {
     fn from_str(s: &str) -> std::result::Result<Self, String> {
         match s.to_uppercase().as_str() {
             "CSV" => Ok(StageFileFormatType::Csv),
-            "JSON" => Ok(StageFileFormatType::Json),
+            "TSV" | "TABSEPARATED" => Ok(StageFileFormatType::Tsv),
+            "JSON" | "NDJSON" => Ok(StageFileFormatType::Json),
             "AVRO" => Ok(StageFileFormatType::Avro),
             "ORC" => Ok(StageFileFormatType::Orc),
             "PARQUET" => Ok(StageFileFormatType::Parquet),
             "XML" => Ok(StageFileFormatType::Xml),
             _ => Err(
-                "Unknown file format type, must one of { CSV | JSON | AVRO | ORC | PARQUET | XML }"
+                "Unknown file format type, must one of { CSV | TSV | JSON | NDJSON | AVRO | ORC | PARQUET | XML }"
                     .to_string(),
             ),
         }
@@ -220,6 +222,8 @@ impl FromStr for OnErrorMode {
 pub struct CopyOptions {
     pub on_error: OnErrorMode,
     pub size_limit: usize,
+    // Max size of each file unloaded by `COPY INTO <location>`, 0 means no limit.
+    pub max_file_size: usize,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, Eq, PartialEq)]
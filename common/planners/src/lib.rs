@@ -44,6 +44,7 @@ mod plan_kill;
 mod plan_limit;
 mod plan_limit_by;
 mod plan_list;
+mod plan_merge_into;
 mod plan_node;
 mod plan_node_builder;
 mod plan_node_display;
@@ -81,17 +82,22 @@ mod plan_show_users;
 mod plan_sink;
 mod plan_sort;
 mod plan_subqueries_set;
+mod plan_table_add_column;
 mod plan_table_alter_cluster_key;
 mod plan_table_create;
 mod plan_table_describe;
 mod plan_table_drop;
 mod plan_table_drop_cluster_key;
+mod plan_table_drop_column;
 mod plan_table_exists;
 mod plan_table_optimize;
+mod plan_table_recluster;
 mod plan_table_rename;
+mod plan_table_rename_column;
 mod plan_table_show_create;
 mod plan_table_truncate;
 mod plan_table_undrop;
+mod plan_update;
 mod plan_use_database;
 mod plan_user_alter;
 mod plan_user_create;
@@ -173,6 +179,10 @@ pub use plan_kill::KillPlan;
 pub use plan_limit::LimitPlan;
 pub use plan_limit_by::LimitByPlan;
 pub use plan_list::ListPlan;
+pub use plan_merge_into::MergeIntoPlan;
+pub use plan_merge_into::MergeMatchedClause;
+pub use plan_merge_into::MergeMatchedOperation;
+pub use plan_merge_into::MergeUnmatchedClause;
 pub use plan_node::PlanNode;
 pub use plan_node_builder::PlanBuilder;
 pub use plan_node_extras::Extras;
@@ -218,20 +228,25 @@ pub use plan_sink::SinkPlan;
 pub use plan_sink::SINK_SCHEMA;
 pub use plan_sort::SortPlan;
 pub use plan_subqueries_set::SubQueriesSetPlan;
+pub use plan_table_add_column::AddTableColumnPlan;
 pub use plan_table_alter_cluster_key::AlterTableClusterKeyPlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableOptions;
 pub use plan_table_describe::DescribeTablePlan;
 pub use plan_table_drop::DropTablePlan;
 pub use plan_table_drop_cluster_key::DropTableClusterKeyPlan;
+pub use plan_table_drop_column::DropTableColumnPlan;
 pub use plan_table_exists::ExistsTablePlan;
 pub use plan_table_optimize::OptimizeTableAction;
 pub use plan_table_optimize::OptimizeTablePlan;
+pub use plan_table_recluster::ReclusterTablePlan;
 pub use plan_table_rename::RenameTableEntity;
 pub use plan_table_rename::RenameTablePlan;
+pub use plan_table_rename_column::RenameTableColumnPlan;
 pub use plan_table_show_create::ShowCreateTablePlan;
 pub use plan_table_truncate::TruncateTablePlan;
 pub use plan_table_undrop::UndropTablePlan;
+pub use plan_update::UpdatePlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_user_alter::AlterUserPlan;
 pub use plan_user_create::CreateUserPlan;
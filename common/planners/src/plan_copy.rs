@@ -72,6 +72,8 @@ pub enum CopyMode {
         tbl_id: MetaId,
         files: Vec<String>,
         pattern: String,
+        /// Load the files even if they have been loaded before.
+        force: bool,
         schema: DataSchemaRef,
         from: ReadDataSourcePlan,
     },
@@ -102,6 +104,7 @@ impl Debug for CopyPlan {
                 tbl_name,
                 files,
                 pattern,
+                force,
                 from,
                 ..
             } => {
@@ -113,6 +116,9 @@ impl Debug for CopyPlan {
                 if !pattern.is_empty() {
                     write!(f, " ,pattern:{:?}", pattern)?;
                 }
+                if *force {
+                    write!(f, " ,force:{:?}", force)?;
+                }
                 write!(f, " ,validation_mode:{:?}", self.validation_mode)?;
             }
             CopyMode::IntoStage {
@@ -0,0 +1,74 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_meta_app::schema::TableIdent;
+
+use crate::Expression;
+
+/// Merges the rows of the source into the target table.
+///
+/// The expressions of the plan are evaluated against the joined rows, in which the columns of
+/// the target table are named by `target_columns`, and the columns of the source are named by
+/// the fields of `source_schema`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeIntoPlan {
+    pub catalog_name: String,
+    pub database_name: String,
+    pub table_name: String,
+    pub table_id: TableIdent,
+    /// The names of the columns of the target table, in the order of the table schema.
+    pub target_columns: Vec<String>,
+    pub source_schema: DataSchemaRef,
+    /// The equi-join keys of the `ON` clause, a target row matches a source row
+    /// if all the `target_keys` equal to the corresponding `source_keys`.
+    pub target_keys: Vec<Expression>,
+    pub source_keys: Vec<Expression>,
+    /// The rest of the `ON` clause, which should be satisfied by the matched rows as well.
+    pub join_filter: Option<Expression>,
+    pub matched_clauses: Vec<MergeMatchedClause>,
+    pub unmatched_clauses: Vec<MergeUnmatchedClause>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeMatchedClause {
+    pub condition: Option<Expression>,
+    pub operation: MergeMatchedOperation,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum MergeMatchedOperation {
+    /// The index of the column being updated, and the expression of its new value.
+    Update {
+        update_list: Vec<(usize, Expression)>,
+    },
+    Delete,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeUnmatchedClause {
+    /// The condition refers to the columns of the source only.
+    pub condition: Option<Expression>,
+    /// The values of the inserted row, one for each column of the target table.
+    pub values: Vec<Expression>,
+}
+
+impl MergeIntoPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
@@ -14,8 +14,7 @@
 
 use std::sync::Arc;
 
-use common_datavalues::DataSchema;
-use common_datavalues::DataSchemaRef;
+use common_datavalues::prelude::*;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct OptimizeTablePlan {
@@ -23,11 +22,17 @@ pub struct OptimizeTablePlan {
     pub database: String,
     pub table: String,
     pub action: OptimizeTableAction,
+    /// lists the files that would be purged, instead of purging them
+    pub dry_run: bool,
 }
 
 impl OptimizeTablePlan {
     pub fn schema(&self) -> DataSchemaRef {
-        Arc::new(DataSchema::empty())
+        if self.dry_run {
+            DataSchemaRefExt::create(vec![DataField::new("file", Vu8::to_data_type())])
+        } else {
+            Arc::new(DataSchema::empty())
+        }
     }
 }
 
@@ -126,6 +126,7 @@ impl FromToProto<pb::DataType> for dv::DataTypeImpl {
                 Ok(dv::DataTypeImpl::VariantObject(dv::VariantObjectType {}))
             }
             Dt::IntervalType(x) => Ok(dv::DataTypeImpl::Interval(dv::IntervalType::from_pb(x)?)),
+            Dt::DecimalType(x) => Ok(dv::DataTypeImpl::Decimal(dv::DecimalType::from_pb(x)?)),
         }
     }
 
@@ -318,6 +319,16 @@ impl FromToProto<pb::DataType> for dv::DataTypeImpl {
                 };
                 Ok(p)
             }
+            dv::DataTypeImpl::Decimal(x) => {
+                let inn = x.to_pb()?;
+
+                let p = pb::DataType {
+                    ver: VER,
+                    min_compatible: MIN_COMPATIBLE_VER,
+                    dt: Some(Dt::DecimalType(inn)),
+                };
+                Ok(p)
+            }
         }
     }
 }
@@ -370,6 +381,29 @@ impl FromToProto<pb::Timestamp> for dv::TimestampType {
     }
 }
 
+impl FromToProto<pb::Decimal> for dv::DecimalType {
+    fn from_pb(p: pb::Decimal) -> Result<Self, Incompatible>
+    where Self: Sized {
+        check_ver(p.ver, p.min_compatible)?;
+        dv::DecimalType::try_create(p.precision as usize, p.scale as usize).map_err(|e| {
+            Incompatible {
+                reason: format!("invalid Decimal: {}", e.message()),
+            }
+        })
+    }
+
+    fn to_pb(&self) -> Result<pb::Decimal, Incompatible> {
+        let p = pb::Decimal {
+            ver: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            precision: self.precision() as u64,
+            scale: self.scale() as u64,
+        };
+
+        Ok(p)
+    }
+}
+
 impl FromToProto<pb::Struct> for dv::StructType {
     fn from_pb(p: pb::Struct) -> Result<Self, Incompatible>
     where Self: Sized {
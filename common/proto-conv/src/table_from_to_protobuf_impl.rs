@@ -219,3 +219,33 @@ impl FromToProto<pb::TableIdList> for mt::TableIdList {
         Ok(p)
     }
 }
+
+impl FromToProto<pb::TableCopiedFileInfo> for mt::TableCopiedFileInfo {
+    fn from_pb(p: pb::TableCopiedFileInfo) -> Result<Self, Incompatible> {
+        check_ver(p.ver, p.min_compatible)?;
+
+        let v = Self {
+            etag: p.etag,
+            content_length: p.content_length,
+            last_modified: match p.last_modified {
+                Some(last_modified) => Some(DateTime::<Utc>::from_pb(last_modified)?),
+                None => None,
+            },
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::TableCopiedFileInfo, Incompatible> {
+        let p = pb::TableCopiedFileInfo {
+            ver: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            etag: self.etag.clone(),
+            content_length: self.content_length,
+            last_modified: match self.last_modified {
+                Some(last_modified) => Some(last_modified.to_pb()?),
+                None => None,
+            },
+        };
+        Ok(p)
+    }
+}
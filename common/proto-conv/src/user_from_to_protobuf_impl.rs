@@ -308,6 +308,7 @@ impl FromToProto<pb::user_stage_info::StageFileFormatType> for mt::StageFileForm
                 Ok(mt::StageFileFormatType::Parquet)
             }
             pb::user_stage_info::StageFileFormatType::Xml => Ok(mt::StageFileFormatType::Xml),
+            pb::user_stage_info::StageFileFormatType::Tsv => Ok(mt::StageFileFormatType::Tsv),
         }
     }
 
@@ -321,6 +322,7 @@ impl FromToProto<pb::user_stage_info::StageFileFormatType> for mt::StageFileForm
                 Ok(pb::user_stage_info::StageFileFormatType::Parquet)
             }
             mt::StageFileFormatType::Xml => Ok(pb::user_stage_info::StageFileFormatType::Xml),
+            mt::StageFileFormatType::Tsv => Ok(pb::user_stage_info::StageFileFormatType::Tsv),
         }
     }
 }
@@ -543,9 +545,16 @@ impl FromToProto<pb::user_stage_info::CopyOptions> for mt::CopyOptions {
         let size_limit = usize::try_from(p.size_limit).map_err(|err| Incompatible {
             reason: format!("CopyOptions.size_limit cannot be convert to usize: {}", err),
         })?;
+        let max_file_size = usize::try_from(p.max_file_size).map_err(|err| Incompatible {
+            reason: format!(
+                "CopyOptions.max_file_size cannot be convert to usize: {}",
+                err
+            ),
+        })?;
         Ok(mt::CopyOptions {
             on_error,
             size_limit,
+            max_file_size,
         })
     }
 
@@ -554,9 +563,16 @@ impl FromToProto<pb::user_stage_info::CopyOptions> for mt::CopyOptions {
         let size_limit = u64::try_from(self.size_limit).map_err(|err| Incompatible {
             reason: format!("CopyOptions.size_limit cannot be convert to u64: {}", err),
         })?;
+        let max_file_size = u64::try_from(self.max_file_size).map_err(|err| Incompatible {
+            reason: format!(
+                "CopyOptions.max_file_size cannot be convert to u64: {}",
+                err
+            ),
+        })?;
         Ok(pb::user_stage_info::CopyOptions {
             on_error: Some(on_error),
             size_limit,
+            max_file_size,
         })
     }
 }
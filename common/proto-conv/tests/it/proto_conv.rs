@@ -133,6 +133,15 @@ fn test_pb_from_to() -> anyhow::Result<()> {
     let got = mt::TableInfo::from_pb(p)?;
     assert_eq!(tbl, got);
 
+    let file_info = mt::TableCopiedFileInfo {
+        etag: Some(s("a7fe2bd0bc3aef6d4d2d3b3f5c4b1d2e")),
+        content_length: 1024,
+        last_modified: Some(Utc.ymd(2014, 11, 28).and_hms(12, 0, 9)),
+    };
+    let p = file_info.to_pb()?;
+    let got = mt::TableCopiedFileInfo::from_pb(p)?;
+    assert_eq!(file_info, got);
+
     Ok(())
 }
 
@@ -83,6 +83,7 @@ fn test_user_stage_info() -> mt::UserStageInfo {
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
+            max_file_size: 0,
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -14,12 +14,15 @@
 
 mod source;
 mod source_csv;
+mod source_format;
 mod source_ndjson;
 mod source_parquet;
 
+pub use source::RowErrors;
 pub use source::Source;
 pub use source_csv::CsvSource;
 pub use source_csv::CsvSourceBuilder;
+pub use source_format::InputFormatSource;
 pub use source_ndjson::NDJsonSource;
 pub use source_ndjson::NDJsonSourceBuilder;
 pub use source_parquet::ParquetSource;
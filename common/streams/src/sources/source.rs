@@ -16,7 +16,29 @@ use async_trait::async_trait;
 use common_datablocks::DataBlock;
 use common_exception::Result;
 
+/// The rows a source skipped because they could not be parsed.
+#[derive(Debug, Clone, Default)]
+pub struct RowErrors {
+    pub num_errors: usize,
+    /// The line number and the message of the first error.
+    pub first_error: Option<(usize, String)>,
+}
+
+impl RowErrors {
+    pub fn add(&mut self, line: usize, message: impl Into<String>) {
+        if self.first_error.is_none() {
+            self.first_error = Some((line, message.into()));
+        }
+        self.num_errors += 1;
+    }
+}
+
 #[async_trait]
 pub trait Source: Send {
     async fn read(&mut self) -> Result<Option<DataBlock>>;
+
+    /// Rows skipped so far, only the sources built with `skip_error_rows` skip rows.
+    fn row_errors(&self) -> RowErrors {
+        RowErrors::default()
+    }
 }
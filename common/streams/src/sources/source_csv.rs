@@ -18,16 +18,19 @@ use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
 use common_datavalues::TypeDeserializer;
+use common_datavalues::TypeDeserializerImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
 use common_io::prelude::FormatSettings;
 use csv_async::AsyncReader;
 use csv_async::AsyncReaderBuilder;
+use csv_async::ByteRecord;
 use csv_async::Terminator;
 use futures::stream::StreamExt;
 use futures::AsyncRead;
 
+use crate::RowErrors;
 use crate::Source;
 
 #[derive(Debug, Clone)]
@@ -39,6 +42,7 @@ pub struct CsvSourceBuilder {
     size_limit: usize,
     field_delimiter: u8,
     record_delimiter: Terminator,
+    skip_error_rows: bool,
     tz: Tz,
 }
 
@@ -71,6 +75,7 @@ impl CsvSourceBuilder {
             empty_as_default,
             block_size: 10000,
             size_limit: usize::MAX,
+            skip_error_rows: false,
             tz,
         }
     }
@@ -85,6 +90,12 @@ impl CsvSourceBuilder {
         self
     }
 
+    // Whether to skip the rows which can't be parsed instead of failing, see `Source::row_errors`.
+    pub fn skip_error_rows(&mut self, skip_error_rows: bool) -> &mut Self {
+        self.skip_error_rows = skip_error_rows;
+        self
+    }
+
     // Whether to skip the header
     pub fn skip_header(&mut self, skip_header: bool) -> &mut Self {
         self.skip_header = skip_header;
@@ -129,6 +140,8 @@ pub struct CsvSource<R> {
     builder: CsvSourceBuilder,
     reader: AsyncReader<R>,
     rows: usize,
+    lines: usize,
+    errors: RowErrors,
 }
 
 impl<R> CsvSource<R>
@@ -145,8 +158,43 @@ where R: AsyncRead + Unpin + Send
             builder,
             reader,
             rows: 0,
+            lines: 0,
+            errors: RowErrors::default(),
         })
     }
+
+    // Append the values of a record to the deserializers.
+    // On error, the values already appended for this record are popped.
+    fn deserialize_record(
+        builder: &CsvSourceBuilder,
+        record: &ByteRecord,
+        packs: &mut [TypeDeserializerImpl],
+        format: &FormatSettings,
+    ) -> Result<()> {
+        let mut result = Ok(());
+        let mut appended = 0;
+        for (col, pack) in packs.iter_mut().enumerate() {
+            match record.get(col) {
+                Some(bytes) => {
+                    if bytes.is_empty() && builder.empty_as_default {
+                        pack.de_default(format);
+                    } else if let Err(cause) = pack.de_whole_text(bytes, format) {
+                        result = Err(cause);
+                        break;
+                    }
+                }
+                None => pack.de_default(format),
+            }
+            appended += 1;
+        }
+
+        if result.is_err() {
+            for pack in packs.iter_mut().take(appended) {
+                pack.pop_data_value()?;
+            }
+        }
+        result
+    }
 }
 
 #[async_trait]
@@ -170,30 +218,32 @@ where R: AsyncRead + Unpin + Send
         let mut rows = 0;
         let mut records = self.reader.byte_records();
 
+        let format = FormatSettings {
+            timezone: self.builder.tz,
+            ..Default::default()
+        };
+
         while let Some(record) = records.next().await {
-            let record = record.map_err_to_code(ErrorCode::BadBytes, || {
-                format!("Parse csv error at line {}", self.rows)
-            })?;
+            self.lines += 1;
+            let line = self.lines + self.builder.skip_header as usize;
 
-            if record.is_empty() {
-                break;
-            }
-            let format = FormatSettings {
-                timezone: self.builder.tz,
-                ..Default::default()
+            let record = record.map_err_to_code(ErrorCode::BadBytes, || {
+                format!("Parse csv error at line {}", line)
+            });
+            let result = match record {
+                Ok(record) if record.is_empty() => break,
+                Ok(record) => Self::deserialize_record(&self.builder, &record, &mut packs, &format),
+                Err(cause) => Err(cause),
             };
-            for (col, pack) in packs.iter_mut().enumerate() {
-                match record.get(col) {
-                    Some(bytes) => {
-                        if bytes.is_empty() && self.builder.empty_as_default {
-                            pack.de_default(&format);
-                        } else {
-                            pack.de_whole_text(bytes, &format)?
-                        }
-                    }
-                    None => pack.de_default(&format),
+
+            if let Err(cause) = result {
+                if !self.builder.skip_error_rows {
+                    return Err(cause);
                 }
+                self.errors.add(line, cause.message());
+                continue;
             }
+
             rows += 1;
             self.rows += 1;
 
@@ -219,4 +269,8 @@ where R: AsyncRead + Unpin + Send
 
         Ok(Some(DataBlock::create(self.builder.schema.clone(), series)))
     }
+
+    fn row_errors(&self) -> RowErrors {
+        self.errors.clone()
+    }
 }
@@ -0,0 +1,91 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_formats::InputFormat;
+use futures::AsyncRead;
+use futures::AsyncReadExt;
+
+use crate::Source;
+
+/// A source for file formats which can only be decoded as a whole (e.g. Avro, ORC).
+///
+/// The reader is drained on the first read, then the decoded blocks are returned one by one.
+pub struct InputFormatSource<R> {
+    reader: R,
+    input_format: Box<dyn InputFormat>,
+    blocks: Option<VecDeque<DataBlock>>,
+    size_limit: usize,
+    rows: usize,
+}
+
+impl<R> InputFormatSource<R>
+where R: AsyncRead + Unpin + Send
+{
+    pub fn create(reader: R, input_format: Box<dyn InputFormat>) -> Self {
+        InputFormatSource {
+            reader,
+            input_format,
+            blocks: None,
+            size_limit: usize::MAX,
+            rows: 0,
+        }
+    }
+
+    pub fn size_limit(mut self, size_limit: usize) -> Self {
+        self.size_limit = size_limit;
+        self
+    }
+
+    async fn decode(&mut self) -> Result<VecDeque<DataBlock>> {
+        let mut buf = vec![];
+        self.reader.read_to_end(&mut buf).await?;
+
+        let mut state = self.input_format.create_state();
+        self.input_format.read_buf(&buf, &mut state)?;
+        Ok(self.input_format.deserialize_data(&mut state)?.into())
+    }
+}
+
+#[async_trait]
+impl<R> Source for InputFormatSource<R>
+where R: AsyncRead + Unpin + Send
+{
+    async fn read(&mut self) -> Result<Option<DataBlock>> {
+        if self.blocks.is_none() {
+            self.blocks = Some(self.decode().await?);
+        }
+
+        if self.rows >= self.size_limit {
+            return Ok(None);
+        }
+
+        let block = match self.blocks.as_mut().and_then(|blocks| blocks.pop_front()) {
+            None => return Ok(None),
+            Some(block) => block,
+        };
+
+        self.rows += block.num_rows();
+        if self.rows > self.size_limit {
+            let cut = self.rows - self.size_limit;
+            return Ok(Some(block.slice(0, block.num_rows() - cut)));
+        }
+
+        Ok(Some(block))
+    }
+}
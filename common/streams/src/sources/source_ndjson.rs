@@ -12,8 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::borrow::Cow;
-
 use async_trait::async_trait;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
@@ -22,10 +20,12 @@ use common_datavalues::TypeDeserializer;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
+use common_formats::format_json_each_row::deserialize_json_row;
 use common_io::prelude::FormatSettings;
 use futures::AsyncBufRead;
 use futures::AsyncBufReadExt;
 
+use crate::RowErrors;
 use crate::Source;
 
 #[derive(Debug, Clone)]
@@ -33,6 +33,7 @@ pub struct NDJsonSourceBuilder {
     schema: DataSchemaRef,
     block_size: usize,
     size_limit: usize,
+    skip_error_rows: bool,
     format: FormatSettings,
 }
 
@@ -42,6 +43,7 @@ impl NDJsonSourceBuilder {
             schema,
             block_size: 10000,
             size_limit: usize::MAX,
+            skip_error_rows: false,
             format,
         }
     }
@@ -56,6 +58,12 @@ impl NDJsonSourceBuilder {
         self
     }
 
+    // Whether to skip the rows which can't be parsed instead of failing, see `Source::row_errors`.
+    pub fn skip_error_rows(&mut self, skip_error_rows: bool) -> &mut Self {
+        self.skip_error_rows = skip_error_rows;
+        self
+    }
+
     pub fn build<R>(&self, reader: R) -> Result<NDJsonSource<R>>
     where R: AsyncBufRead + Unpin + Send {
         NDJsonSource::try_create(self.clone(), reader)
@@ -66,6 +74,8 @@ pub struct NDJsonSource<R> {
     builder: NDJsonSourceBuilder,
     reader: R,
     rows: usize,
+    lines: usize,
+    errors: RowErrors,
     buffer: String,
 }
 
@@ -77,24 +87,13 @@ where R: AsyncBufRead + Unpin + Send
             builder,
             reader,
             rows: 0,
+            lines: 0,
+            errors: RowErrors::default(),
             buffer: String::new(),
         })
     }
 }
 
-fn maybe_truncated(s: &str, limit: usize) -> Cow<'_, str> {
-    if s.len() > limit {
-        Cow::Owned(format!(
-            "(first {}B of {}B): {}",
-            limit,
-            s.len(),
-            &s[..limit]
-        ))
-    } else {
-        Cow::Borrowed(s)
-    }
-}
-
 #[async_trait]
 impl<R> Source for NDJsonSource<R>
 where R: AsyncBufRead + Unpin + Send
@@ -113,14 +112,6 @@ where R: AsyncBufRead + Unpin + Send
             .map(|f| f.data_type().create_deserializer(self.builder.block_size))
             .collect::<Vec<_>>();
 
-        let fields = self
-            .builder
-            .schema
-            .fields()
-            .iter()
-            .map(|f| (f.name(), f.data_type().name()))
-            .collect::<Vec<_>>();
-
         let mut rows = 0;
 
         loop {
@@ -131,32 +122,36 @@ where R: AsyncBufRead + Unpin + Send
                 .read_line(&mut self.buffer)
                 .await
                 .map_err_to_code(ErrorCode::BadBytes, || {
-                    format!("Parse NDJson error at line {}", self.rows)
+                    format!("Parse NDJson error at line {}", self.lines + 1)
                 })?
                 == 0
             {
                 break;
             }
 
+            self.lines += 1;
             if self.buffer.trim().is_empty() {
                 continue;
             }
 
-            let json: serde_json::Value = serde_json::from_reader(self.buffer.as_bytes())?;
-
-            for ((name, type_name), deser) in fields.iter().zip(packs.iter_mut()) {
-                let value = &json[name];
-                deser.de_json(value, &self.builder.format).map_err(|e| {
-                    let value_str = format!("{:?}", value);
-                    ErrorCode::BadBytes(format!(
-                        "error at row {} column {}: type={}, err={}, value={}",
+            let result = serde_json::from_reader::<_, serde_json::Value>(self.buffer.as_bytes())
+                .map_err(ErrorCode::from)
+                .and_then(|json| {
+                    deserialize_json_row(
+                        &self.builder.schema,
+                        &mut packs,
+                        &json,
                         rows,
-                        name,
-                        type_name,
-                        e.message(),
-                        maybe_truncated(&value_str, 1024),
-                    ))
-                })?;
+                        &self.builder.format,
+                    )
+                });
+
+            if let Err(cause) = result {
+                if !self.builder.skip_error_rows {
+                    return Err(cause);
+                }
+                self.errors.add(self.lines, cause.message());
+                continue;
             }
 
             rows += 1;
@@ -184,4 +179,8 @@ where R: AsyncBufRead + Unpin + Send
 
         Ok(Some(DataBlock::create(self.builder.schema.clone(), series)))
     }
+
+    fn row_errors(&self) -> RowErrors {
+        self.errors.clone()
+    }
 }
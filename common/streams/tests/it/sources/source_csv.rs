@@ -162,3 +162,74 @@ async fn test_parse_csv_text() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_parse_csv_skip_error_rows() -> Result<()> {
+    let dir = tempfile::tempdir().unwrap();
+    let name = "my-temporary-note.txt";
+    let file_path = dir.path().join(name);
+    let mut file = File::create(file_path).unwrap();
+
+    // The last column of the second row is not a number.
+    write!(
+        file,
+        r#"1,'Beijing',100
+2,'Shanghai',abc
+3,'Guangzhou',60"#
+    )
+    .unwrap();
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i8::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+        DataField::new("c", f64::to_data_type()),
+    ]);
+
+    let local = Operator::new(
+        fs::Backend::build()
+            .root(dir.path().to_str().unwrap())
+            .finish()
+            .await
+            .unwrap(),
+    );
+
+    let mut builder = CsvSourceBuilder::create(schema, FormatSettings::default());
+    builder.skip_header(false);
+    builder.field_delimiter(",");
+    builder.record_delimiter("\n");
+    builder.block_size(10);
+
+    // Fails on the bad row by default.
+    let reader = local.object(name).reader().await?;
+    let mut csv_source = builder.build(reader)?;
+    assert!(csv_source.read().await.is_err());
+
+    builder.skip_error_rows(true);
+    let reader = local.object(name).reader().await?;
+    let mut csv_source = builder.build(reader)?;
+
+    let block = csv_source.read().await?.unwrap();
+    assert_blocks_eq(
+        vec![
+            "+---+-------------+-----+",
+            "| a | b           | c   |",
+            "+---+-------------+-----+",
+            "| 1 | 'Beijing'   | 100 |",
+            "| 3 | 'Guangzhou' | 60  |",
+            "+---+-------------+-----+",
+        ],
+        &[block],
+    );
+
+    let block = csv_source.read().await?;
+    assert!(block.is_none());
+
+    let errors = csv_source.row_errors();
+    assert_eq!(errors.num_errors, 1);
+    assert_eq!(errors.first_error.map(|(line, _)| line), Some(2));
+
+    drop(file);
+    dir.close().unwrap();
+
+    Ok(())
+}
@@ -34,6 +34,7 @@ use databend_query::servers::MySQLHandler;
 use databend_query::servers::Server;
 use databend_query::servers::ShutdownHandle;
 use databend_query::sessions::SessionManager;
+use databend_query::storages::fuse::AutoCompactionService;
 use databend_query::Config;
 use databend_query::QUERY_SEMVER;
 
@@ -214,6 +215,18 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
         tracing::info!("Databend async insert has been enabled.")
     }
 
+    // Auto compaction of fuse tables
+    {
+        let auto_compaction = AutoCompactionService::create(session_manager.clone());
+        if auto_compaction.is_enabled() {
+            auto_compaction.start();
+            tracing::info!(
+                "Databend auto compaction has been enabled, checking tables every {}s.",
+                conf.query.auto_compaction_interval_secs
+            );
+        }
+    }
+
     tracing::info!("Ready for connections.");
     shutdown_handle.wait_for_termination_request().await;
     tracing::info!("Shutdown server.");
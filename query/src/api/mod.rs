@@ -16,6 +16,7 @@
 
 pub use http_service::HttpService;
 pub use rpc::BroadcastAction;
+pub use rpc::BroadcastExchange;
 pub use rpc::CancelAction;
 pub use rpc::DataExchange;
 pub use rpc::DataExchangeManager;
@@ -25,12 +26,14 @@ pub use rpc::ExecutePartialQueryPacket;
 pub use rpc::FlightAction;
 pub use rpc::FlightClient;
 pub use rpc::FlightTicket;
+pub use rpc::FragmentPayload;
 pub use rpc::FragmentPlanPacket;
 pub use rpc::InitNodesChannelPacket;
 pub use rpc::MergeExchange;
 pub use rpc::QueryFragmentsPlanPacket;
 pub use rpc::ShuffleAction;
 pub use rpc::ShuffleDataExchange;
+pub use rpc::ShuffleDataExchangeV2;
 pub use rpc::StreamTicket;
 pub use rpc_service::RpcService;
 
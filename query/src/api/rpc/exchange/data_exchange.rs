@@ -14,11 +14,15 @@
 
 use common_planners::Expression;
 
+use crate::sql::exec::PhysicalScalar;
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum DataExchange {
     // None,
     Merge(MergeExchange),
     ShuffleDataExchange(ShuffleDataExchange),
+    ShuffleDataExchangeV2(ShuffleDataExchangeV2),
+    Broadcast(BroadcastExchange),
 }
 
 impl DataExchange {
@@ -27,6 +31,8 @@ impl DataExchange {
             // DataExchange::None => vec![],
             DataExchange::Merge(exchange) => vec![exchange.destination_id.clone()],
             DataExchange::ShuffleDataExchange(exchange) => exchange.destination_ids.clone(),
+            DataExchange::ShuffleDataExchangeV2(exchange) => exchange.destination_ids.clone(),
+            DataExchange::Broadcast(exchange) => exchange.destination_ids.clone(),
         }
     }
 }
@@ -46,6 +52,32 @@ impl ShuffleDataExchange {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShuffleDataExchangeV2 {
+    pub destination_ids: Vec<String>,
+    pub shuffle_keys: Vec<PhysicalScalar>,
+}
+
+impl ShuffleDataExchangeV2 {
+    pub fn create(destination_ids: Vec<String>, shuffle_keys: Vec<PhysicalScalar>) -> DataExchange {
+        DataExchange::ShuffleDataExchangeV2(ShuffleDataExchangeV2 {
+            destination_ids,
+            shuffle_keys,
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BroadcastExchange {
+    pub destination_ids: Vec<String>,
+}
+
+impl BroadcastExchange {
+    pub fn create(destination_ids: Vec<String>) -> DataExchange {
+        DataExchange::Broadcast(BroadcastExchange { destination_ids })
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MergeExchange {
     pub destination_id: String,
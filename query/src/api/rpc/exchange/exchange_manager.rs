@@ -27,7 +27,6 @@ use common_base::infallible::ReentrantMutex;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
-use common_planners::PlanNode;
 use futures::StreamExt;
 use tonic::Streaming;
 
@@ -40,10 +39,13 @@ use crate::api::rpc::exchange::exchange_params::MergeExchangeParams;
 use crate::api::rpc::exchange::exchange_params::ShuffleExchangeParams;
 use crate::api::rpc::exchange::exchange_sink::ExchangeSink;
 use crate::api::rpc::exchange::exchange_source::ExchangeSource;
+use crate::api::rpc::flight_scatter_broadcast::BroadcastFlightScatter;
 use crate::api::rpc::flight_scatter_hash::HashFlightScatter;
+use crate::api::rpc::flight_scatter_hash_v2::HashFlightScatterV2;
 use crate::api::rpc::packets::DataPacket;
 use crate::api::rpc::Packet;
 use crate::api::DataExchange;
+use crate::api::FragmentPayload;
 use crate::api::FragmentPlanPacket;
 use crate::api::InitNodesChannelPacket;
 use crate::api::QueryFragmentsPlanPacket;
@@ -54,6 +56,7 @@ use crate::pipelines::new::NewPipe;
 use crate::pipelines::new::NewPipeline;
 use crate::pipelines::new::QueryPipelineBuilder;
 use crate::sessions::QueryContext;
+use crate::sql::exec::PipelineBuilder;
 use crate::Config;
 
 pub struct DataExchangeManager {
@@ -280,6 +283,8 @@ struct QueryCoordinator {
     subscribe_channel: HashMap<String, Sender<Result<DataPacket>>>,
     subscribe_fragments: HashMap<usize, FragmentReceiver>,
     fragments_coordinator: HashMap<usize, Box<FragmentCoordinator>>,
+    // Pipelines of the fragments merged into other pipelines, e.g. build side of hash join
+    sub_pipelines: Vec<NewPipeline>,
 
     shutdown_cause: Arc<Mutex<Option<ErrorCode>>>,
     executor: Option<Arc<PipelineCompleteExecutor>>,
@@ -304,6 +309,7 @@ impl QueryCoordinator {
         Ok(QueryCoordinator {
             ctx: ctx.clone(),
             fragments_coordinator,
+            sub_pipelines: vec![],
             executor: None,
             exchange_senders: vec![],
             exchange_receivers: vec![],
@@ -331,6 +337,8 @@ impl QueryCoordinator {
         for fragment_id in fragments_id {
             if let Some(coordinator) = self.fragments_coordinator.get_mut(&fragment_id) {
                 coordinator.prepare_pipeline(&self.ctx)?;
+                let sub_pipelines = std::mem::take(&mut coordinator.sub_pipelines);
+                self.sub_pipelines.extend(sub_pipelines);
             }
         }
 
@@ -375,7 +383,7 @@ impl QueryCoordinator {
     }
 
     pub fn execute_pipeline(&mut self) -> Result<()> {
-        if self.fragments_coordinator.is_empty() {
+        if self.fragments_coordinator.is_empty() && self.sub_pipelines.is_empty() {
             // Empty fragments if it is a request server, because the pipelines may have been linked.
             return Ok(());
         }
@@ -383,6 +391,11 @@ impl QueryCoordinator {
         let max_threads = self.ctx.get_settings().get_max_threads()?;
         let mut pipelines = Vec::with_capacity(self.fragments_coordinator.len());
 
+        for mut pipeline in std::mem::take(&mut self.sub_pipelines) {
+            pipeline.set_max_threads(max_threads as usize);
+            pipelines.push(pipeline);
+        }
+
         let mut params = Vec::with_capacity(self.fragments_coordinator.len());
         for coordinator in self.fragments_coordinator.values() {
             params.push(coordinator.create_exchange_params(self)?);
@@ -519,6 +532,10 @@ impl QueryCoordinator {
             }
         }
 
+        for pipeline in &mut self.sub_pipelines {
+            Self::init_pipeline(pipeline)?;
+        }
+
         Ok(())
     }
 
@@ -595,6 +612,8 @@ impl QueryCoordinator {
         // Merge pipelines if exist locally pipeline
         if let Some(mut fragment_coordinator) = self.fragments_coordinator.remove(&fragment_id) {
             fragment_coordinator.prepare_pipeline(&self.ctx)?;
+            self.sub_pipelines
+                .extend(std::mem::take(&mut fragment_coordinator.sub_pipelines));
 
             if fragment_coordinator.pipeline.is_none() {
                 return Err(ErrorCode::LogicalError(
@@ -625,21 +644,23 @@ impl QueryCoordinator {
 }
 
 struct FragmentCoordinator {
-    node: PlanNode,
+    payload: FragmentPayload,
     initialized: bool,
     fragment_id: usize,
     data_exchange: Option<DataExchange>,
     pipeline: Option<NewPipeline>,
+    sub_pipelines: Vec<NewPipeline>,
 }
 
 impl FragmentCoordinator {
     pub fn create(packet: &FragmentPlanPacket) -> Box<FragmentCoordinator> {
         Box::new(FragmentCoordinator {
             initialized: false,
-            node: packet.node.clone(),
+            payload: packet.payload.clone(),
             fragment_id: packet.fragment_id,
             data_exchange: packet.data_exchange.clone(),
             pipeline: None,
+            sub_pipelines: vec![],
         })
     }
 
@@ -648,7 +669,7 @@ impl FragmentCoordinator {
             None => Err(ErrorCode::LogicalError("Cannot found data exchange.")),
             Some(DataExchange::Merge(exchange)) => {
                 Ok(ExchangeParams::MergeExchange(MergeExchangeParams {
-                    schema: self.node.schema(),
+                    schema: self.payload.schema()?,
                     fragment_id: self.fragment_id,
                     query_id: query.query_id.to_string(),
                     destination_id: exchange.destination_id.clone(),
@@ -656,27 +677,67 @@ impl FragmentCoordinator {
             }
             Some(DataExchange::ShuffleDataExchange(exchange)) => {
                 Ok(ExchangeParams::ShuffleExchange(ShuffleExchangeParams {
-                    schema: self.node.schema(),
+                    schema: self.payload.schema()?,
                     fragment_id: self.fragment_id,
                     query_id: query.query_id.to_string(),
                     executor_id: query.executor_id.to_string(),
                     destination_ids: exchange.destination_ids.to_owned(),
                     shuffle_scatter: Arc::new(Box::new(HashFlightScatter::try_create(
                         query.ctx.clone(),
-                        self.node.schema(),
+                        self.payload.schema()?,
                         Some(exchange.exchange_expression.clone()),
                         exchange.destination_ids.len(),
                     )?)),
                 }))
             }
+            Some(DataExchange::ShuffleDataExchangeV2(exchange)) => {
+                Ok(ExchangeParams::ShuffleExchange(ShuffleExchangeParams {
+                    schema: self.payload.schema()?,
+                    fragment_id: self.fragment_id,
+                    query_id: query.query_id.to_string(),
+                    executor_id: query.executor_id.to_string(),
+                    destination_ids: exchange.destination_ids.to_owned(),
+                    shuffle_scatter: Arc::new(Box::new(HashFlightScatterV2::try_create(
+                        query.ctx.clone(),
+                        &exchange.shuffle_keys,
+                        exchange.destination_ids.len(),
+                    )?)),
+                }))
+            }
+            Some(DataExchange::Broadcast(exchange)) => {
+                Ok(ExchangeParams::ShuffleExchange(ShuffleExchangeParams {
+                    schema: self.payload.schema()?,
+                    fragment_id: self.fragment_id,
+                    query_id: query.query_id.to_string(),
+                    executor_id: query.executor_id.to_string(),
+                    destination_ids: exchange.destination_ids.to_owned(),
+                    shuffle_scatter: Arc::new(Box::new(BroadcastFlightScatter::try_create(
+                        query.ctx.clone(),
+                        self.payload.schema()?,
+                        None,
+                        exchange.destination_ids.len(),
+                    )?)),
+                }))
+            }
         }
     }
 
     pub fn prepare_pipeline(&mut self, ctx: &Arc<QueryContext>) -> Result<()> {
         if !self.initialized {
             self.initialized = true;
-            let pipeline_builder = QueryPipelineBuilder::create(ctx.clone());
-            self.pipeline = Some(pipeline_builder.finalize(&self.node)?);
+            match &self.payload {
+                FragmentPayload::PlanV1(node) => {
+                    let pipeline_builder = QueryPipelineBuilder::create(ctx.clone());
+                    self.pipeline = Some(pipeline_builder.finalize(node)?);
+                }
+                FragmentPayload::PlanV2(plan) => {
+                    let mut pipeline = NewPipeline::create();
+                    let mut pipeline_builder = PipelineBuilder::new();
+                    pipeline_builder.build_pipeline(ctx.clone(), plan, &mut pipeline)?;
+                    self.pipeline = Some(pipeline);
+                    self.sub_pipelines = pipeline_builder.pipelines;
+                }
+            }
         }
 
         Ok(())
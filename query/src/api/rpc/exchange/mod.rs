@@ -25,7 +25,9 @@ mod exchange_source;
 mod exchange_source_merge;
 mod exchange_source_shuffle;
 
+pub use data_exchange::BroadcastExchange;
 pub use data_exchange::DataExchange;
 pub use data_exchange::MergeExchange;
 pub use data_exchange::ShuffleDataExchange;
+pub use data_exchange::ShuffleDataExchangeV2;
 pub use exchange_manager::DataExchangeManager;
@@ -0,0 +1,98 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+use common_functions::scalars::FunctionFactory;
+
+use crate::api::rpc::flight_scatter::FlightScatter;
+use crate::common::EvalNode;
+use crate::common::Evaluator;
+use crate::sessions::QueryContext;
+use crate::sql::exec::ColumnID;
+use crate::sql::exec::PhysicalScalar;
+
+/// Scatter data blocks by hash of the shuffle keys of planner v2, the hash
+/// expression is evaluated with `Evaluator` instead of `ExpressionExecutor`.
+#[derive(Clone)]
+pub struct HashFlightScatterV2 {
+    func_ctx: FunctionContext,
+    indices_scalar: EvalNode<ColumnID>,
+    scattered_size: usize,
+}
+
+impl HashFlightScatterV2 {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        hash_keys: &[PhysicalScalar],
+        num: usize,
+    ) -> Result<Self> {
+        if hash_keys.is_empty() {
+            return Err(ErrorCode::LogicalError(
+                "Hash flight scatter need at least one key.",
+            ));
+        }
+
+        let indices_scalar = Self::indices_scalar(hash_keys, num)?;
+        Ok(HashFlightScatterV2 {
+            func_ctx: ctx.try_get_function_context()?,
+            indices_scalar: Evaluator::eval_physical_scalar(&indices_scalar)?,
+            scattered_size: num,
+        })
+    }
+
+    // modulo(siphash64(key_1) + siphash64(key_2) + ..., num)
+    fn indices_scalar(hash_keys: &[PhysicalScalar], num: usize) -> Result<PhysicalScalar> {
+        let mut sum_hash: Option<PhysicalScalar> = None;
+        for key in hash_keys {
+            let hash = Self::function("siphash64", vec![key.clone()])?;
+            sum_hash = Some(match sum_hash {
+                None => hash,
+                Some(sum) => Self::function("plus", vec![sum, hash])?,
+            });
+        }
+
+        let num = PhysicalScalar::Constant {
+            value: DataValue::UInt64(num as u64),
+            data_type: u64::to_data_type(),
+        };
+        Self::function("modulo", vec![sum_hash.unwrap(), num])
+    }
+
+    fn function(name: &str, args: Vec<PhysicalScalar>) -> Result<PhysicalScalar> {
+        let args_type = args.iter().map(|arg| arg.data_type()).collect::<Vec<_>>();
+        let func = FunctionFactory::instance().get(name, &args_type.iter().collect::<Vec<_>>())?;
+        Ok(PhysicalScalar::Function {
+            name: name.to_string(),
+            args: args.into_iter().zip(args_type).collect(),
+            return_type: func.return_type(),
+        })
+    }
+}
+
+impl FlightScatter for HashFlightScatterV2 {
+    fn execute(&self, data_block: &DataBlock, _num: usize) -> Result<Vec<DataBlock>> {
+        let indices = self.indices_scalar.eval(&self.func_ctx, data_block)?;
+        let indices = Series::remove_nullable(&indices.vector().convert_full_column());
+
+        let col: &PrimitiveColumn<u64> = Series::check_get(&indices)?;
+        let indices: Vec<usize> = col.iter().map(|c| *c as usize).collect();
+        DataBlock::scatter_block(data_block, &indices, self.scattered_size)
+    }
+}
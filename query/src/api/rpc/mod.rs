@@ -30,16 +30,20 @@ mod flight_dispatcher;
 mod flight_scatter;
 mod flight_scatter_broadcast;
 mod flight_scatter_hash;
+mod flight_scatter_hash_v2;
 mod flight_service;
 mod flight_service_stream;
 mod flight_tickets;
 mod packets;
 
+pub use exchange::BroadcastExchange;
 pub use exchange::DataExchange;
 pub use exchange::DataExchangeManager;
 pub use exchange::MergeExchange;
 pub use exchange::ShuffleDataExchange;
+pub use exchange::ShuffleDataExchangeV2;
 pub use packets::ExecutePartialQueryPacket;
+pub use packets::FragmentPayload;
 pub use packets::FragmentPlanPacket;
 pub use packets::InitNodesChannelPacket;
 pub use packets::Packet;
@@ -15,13 +15,38 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
 use common_planners::PlanNode;
 
 use crate::api::DataExchange;
+use crate::sql::exec::PhysicalPlan;
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum FragmentPayload {
+    PlanV1(PlanNode),
+    PlanV2(PhysicalPlan),
+}
+
+impl FragmentPayload {
+    pub fn schema(&self) -> Result<DataSchemaRef> {
+        match self {
+            FragmentPayload::PlanV1(node) => Ok(node.schema()),
+            FragmentPayload::PlanV2(plan) => plan.output_schema(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            FragmentPayload::PlanV1(node) => node.name(),
+            FragmentPayload::PlanV2(_) => "PhysicalPlan",
+        }
+    }
+}
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct FragmentPlanPacket {
-    pub node: PlanNode,
+    pub payload: FragmentPayload,
     pub fragment_id: usize,
     pub data_exchange: Option<DataExchange>,
 }
@@ -29,11 +54,11 @@ pub struct FragmentPlanPacket {
 impl FragmentPlanPacket {
     pub fn create(
         fragment_id: usize,
-        node: PlanNode,
+        payload: FragmentPayload,
         data_exchange: Option<DataExchange>,
     ) -> FragmentPlanPacket {
         FragmentPlanPacket {
-            node,
+            payload,
             fragment_id,
             data_exchange,
         }
@@ -43,7 +68,7 @@ impl FragmentPlanPacket {
 impl Debug for FragmentPlanPacket {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FragmentPacket")
-            .field("node", &self.node.name())
+            .field("node", &self.payload.name())
             .field("fragment_id", &self.fragment_id)
             .field("exchange", &self.data_exchange)
             .finish()
@@ -19,6 +19,7 @@ use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::Partitions;
@@ -247,6 +248,7 @@ impl Table for HiveTable {
         _ctx: Arc<QueryContext>,
         _catalog_name: &str,
         _operations: Vec<DataBlock>,
+        _copied_files: Option<UpsertTableCopiedFileReq>,
         _overwrite: bool,
     ) -> Result<()> {
         Err(ErrorCode::UnImplement(format!(
@@ -90,6 +90,12 @@ impl Evaluator {
             Scalar::AggregateFunction(_) => Err(ErrorCode::LogicalError(
                 "Cannot evaluate aggregate function",
             )),
+            Scalar::WindowFunction(_) => {
+                Err(ErrorCode::LogicalError("Cannot evaluate window function"))
+            }
+            Scalar::Placeholder(_) => Err(ErrorCode::LogicalError(
+                "Cannot evaluate unbound placeholder",
+            )),
         }
     }
 }
@@ -15,6 +15,11 @@
 pub mod context_function;
 mod evaluator;
 mod expression_evaluator;
+mod runtime_filter;
+mod spill;
 
 pub use evaluator::*;
 pub use expression_evaluator::ExpressionEvaluator;
+pub use runtime_filter::RuntimeFilter;
+pub use runtime_filter::RuntimeFilterValues;
+pub use spill::*;
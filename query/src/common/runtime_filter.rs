@@ -0,0 +1,164 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use common_base::infallible::Mutex;
+use common_base::infallible::RwLock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::aggregates::eval_aggr;
+
+/// The maximum number of distinct keys kept in the IN-list of a runtime filter, only the
+/// range of the keys is kept beyond it.
+const MAX_IN_LIST_SIZE: usize = 1024;
+
+/// A filter on a join key, collected from the build side of a hash join and applied to the
+/// table scan of the probe side, to skip the blocks and rows that can't be joined.
+///
+/// The filter is only applied after the build side is finished, it filters nothing before.
+pub struct RuntimeFilter {
+    collecting: Mutex<RuntimeFilterValues>,
+    published: RwLock<Option<Arc<RuntimeFilterValues>>>,
+}
+
+/// The non-null keys of the build side.
+pub struct RuntimeFilterValues {
+    min: Option<DataValue>,
+    max: Option<DataValue>,
+    /// The distinct keys, None if there are too many of them
+    in_list: Option<BTreeSet<DataValue>>,
+}
+
+impl RuntimeFilter {
+    pub fn create() -> Arc<RuntimeFilter> {
+        Arc::new(RuntimeFilter {
+            collecting: Mutex::new(RuntimeFilterValues {
+                min: None,
+                max: None,
+                in_list: Some(BTreeSet::new()),
+            }),
+            published: RwLock::new(None),
+        })
+    }
+
+    /// Collects the keys of a block of the build side.
+    pub fn collect(&self, keys: &ColumnRef) -> Result<()> {
+        let rows = keys.len();
+        if rows == 0 {
+            return Ok(());
+        }
+
+        let keys = keys.convert_full_column();
+        let field = DataField::new("key", keys.data_type());
+        let column = ColumnWithField::new(keys.clone(), field);
+        let min = eval_aggr("min", vec![], &[column.clone()], rows)?.get(0);
+        let max = eval_aggr("max", vec![], &[column], rows)?.get(0);
+
+        let mut values = self.collecting.lock();
+        if !min.is_null() && values.min.as_ref().map_or(true, |v| min < *v) {
+            values.min = Some(min);
+        }
+        if !max.is_null() && values.max.as_ref().map_or(true, |v| max > *v) {
+            values.max = Some(max);
+        }
+        if let Some(in_list) = values.in_list.as_mut() {
+            for row in 0..rows {
+                let key = keys.get(row);
+                if !key.is_null() {
+                    in_list.insert(key);
+                }
+                if in_list.len() > MAX_IN_LIST_SIZE {
+                    break;
+                }
+            }
+            if in_list.len() > MAX_IN_LIST_SIZE {
+                values.in_list = None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes the collected keys visible to the scans, called once the build side is finished.
+    pub fn publish(&self) {
+        let mut collecting = self.collecting.lock();
+        let values = RuntimeFilterValues {
+            min: collecting.min.take(),
+            max: collecting.max.take(),
+            in_list: collecting.in_list.take(),
+        };
+        *self.published.write() = Some(Arc::new(values));
+    }
+
+    /// The keys of the build side, None if the build side is not finished yet.
+    pub fn values(&self) -> Option<Arc<RuntimeFilterValues>> {
+        self.published.read().clone()
+    }
+}
+
+impl RuntimeFilterValues {
+    /// Whether any key may be in the range [min, max], used to prune blocks by their
+    /// column statistics.
+    pub fn may_intersect(&self, min: &DataValue, max: &DataValue) -> bool {
+        let (keys_min, keys_max) = match (&self.min, &self.max) {
+            (Some(keys_min), Some(keys_max)) => (keys_min, keys_max),
+            // No key at all, nothing can be joined.
+            _ => return false,
+        };
+
+        // Values of different types can't be compared, keep the block.
+        if min.value_type() != keys_min.value_type() || max.value_type() != keys_max.value_type() {
+            return true;
+        }
+
+        if max < keys_min || min > keys_max {
+            return false;
+        }
+
+        match &self.in_list {
+            Some(in_list) => in_list.range(min.clone()..=max.clone()).next().is_some(),
+            None => true,
+        }
+    }
+
+    /// Whether the value may be one of the keys.
+    pub fn may_contain(&self, value: &DataValue) -> bool {
+        if value.is_null() {
+            return false;
+        }
+
+        match (&self.min, &self.max) {
+            (Some(keys_min), Some(_)) if value.value_type() != keys_min.value_type() => true,
+            (Some(keys_min), Some(keys_max)) => {
+                if value < keys_min || value > keys_max {
+                    return false;
+                }
+                match &self.in_list {
+                    Some(in_list) => in_list.contains(value),
+                    None => true,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Evaluates the filter on a column, returns a boolean column as the predicate of the rows.
+    pub fn filter(&self, column: &ColumnRef) -> ColumnRef {
+        let predicate = (0..column.len())
+            .map(|row| self.may_contain(&column.get(row)))
+            .collect::<Vec<bool>>();
+        Series::from_data(predicate)
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// The memory budget of a query, shared by all the operators which are able to spill.
+/// A budget with zero limit is unlimited, which means spilling is disabled.
+pub struct MemoryBudget {
+    limit: usize,
+    used: AtomicUsize,
+}
+
+impl MemoryBudget {
+    pub fn create(limit: usize) -> Arc<MemoryBudget> {
+        Arc::new(MemoryBudget {
+            limit,
+            used: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.limit == 0
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    fn try_grow(&self, bytes: usize) -> bool {
+        if self.is_unlimited() {
+            self.used.fetch_add(bytes, Ordering::Relaxed);
+            return true;
+        }
+
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                match used.checked_add(bytes) {
+                    Some(new_used) if new_used <= self.limit => Some(new_used),
+                    _ => None,
+                }
+            })
+            .is_ok()
+    }
+
+    fn shrink(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+/// The memory reserved from a [`MemoryBudget`] by one operator, released when dropped.
+pub struct MemoryReservation {
+    budget: Arc<MemoryBudget>,
+    size: usize,
+}
+
+impl MemoryReservation {
+    pub fn create(budget: Arc<MemoryBudget>) -> MemoryReservation {
+        MemoryReservation { budget, size: 0 }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the operator should spill when the reservation can't grow.
+    pub fn can_spill(&self) -> bool {
+        !self.budget.is_unlimited()
+    }
+
+    /// Try to resize the reservation to `size` bytes.
+    /// Returns false and keeps the reservation unchanged if the budget is exceeded.
+    pub fn try_resize(&mut self, size: usize) -> bool {
+        if size > self.size {
+            if !self.budget.try_grow(size - self.size) {
+                return false;
+            }
+        } else {
+            self.budget.shrink(self.size - size);
+        }
+
+        self.size = size;
+        true
+    }
+
+    pub fn free(&mut self) {
+        self.budget.shrink(self.size);
+        self.size = 0;
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.free();
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use byteorder::BigEndian;
+use byteorder::ReadBytesExt;
+use byteorder::WriteBytesExt;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::ipc::write::default_ipc_fields;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow::io::ipc::IpcSchema;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_base::base::Runtime;
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::Series;
+use common_datavalues::SmallVu8;
+use common_exception::Result;
+use common_tracing::tracing;
+use opendal::Operator;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+
+enum SpillStorage {
+    Local(PathBuf),
+    Operator {
+        operator: Operator,
+        runtime: Arc<Runtime>,
+    },
+}
+
+/// Spiller writes data blocks of an operator into partitions of files, and reads them back.
+///
+/// The files are placed in the local spill folder or the configured storage, depending on
+/// the setting `spill_to_storage`, and removed when the spiller is dropped.
+pub struct Spiller {
+    storage: SpillStorage,
+    /// Location of the files relative to the spill root, unique for each spiller
+    location_prefix: String,
+    schema: DataSchemaRef,
+    arrow_schema: ArrowSchema,
+    /// Spilled files of each partition, in the order of spilling
+    files: BTreeMap<usize, Vec<String>>,
+    next_file_id: usize,
+}
+
+impl Spiller {
+    pub fn create(ctx: &Arc<QueryContext>, name: &str, schema: DataSchemaRef) -> Result<Spiller> {
+        let storage = if ctx.get_settings().get_spill_to_storage()? != 0 {
+            SpillStorage::Operator {
+                operator: ctx.get_storage_operator()?,
+                runtime: ctx.get_storage_runtime(),
+            }
+        } else {
+            let spill_local_path = ctx.get_config().query.spill_local_path;
+            let root = match spill_local_path.is_empty() {
+                true => std::env::temp_dir().join("databend-spill"),
+                false => PathBuf::from(spill_local_path),
+            };
+            SpillStorage::Local(root)
+        };
+
+        Ok(Spiller {
+            storage,
+            location_prefix: format!("{}/{}-{}", ctx.get_id(), name, Uuid::new_v4()),
+            arrow_schema: schema.to_arrow(),
+            schema,
+            files: BTreeMap::new(),
+            next_file_id: 0,
+        })
+    }
+
+    pub fn schema(&self) -> &DataSchemaRef {
+        &self.schema
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The partitions which have been spilled, in ascending order.
+    pub fn partitions(&self) -> Vec<usize> {
+        self.files.keys().cloned().collect()
+    }
+
+    pub fn num_files(&self, partition: usize) -> usize {
+        self.files.get(&partition).map_or(0, |files| files.len())
+    }
+
+    /// Write the blocks into a new file of `partition`.
+    pub fn spill(&mut self, partition: usize, blocks: &[DataBlock]) -> Result<()> {
+        let blocks = blocks
+            .iter()
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let data = self.serialize_blocks(&blocks)?;
+        let location = format!(
+            "{}/{}_{}",
+            self.location_prefix, partition, self.next_file_id
+        );
+        self.next_file_id += 1;
+        self.write_file(&location, data)?;
+        self.files.entry(partition).or_default().push(location);
+        Ok(())
+    }
+
+    /// Write the i-th block into partition i, usually the output of [`scatter_by_hash`].
+    pub fn spill_scattered(&mut self, blocks: &[DataBlock]) -> Result<()> {
+        for (partition, block) in blocks.iter().enumerate() {
+            self.spill(partition, std::slice::from_ref(block))?;
+        }
+        Ok(())
+    }
+
+    /// Read the blocks of the `index`-th file of `partition`.
+    pub fn read_file(&self, partition: usize, index: usize) -> Result<Vec<DataBlock>> {
+        match self
+            .files
+            .get(&partition)
+            .and_then(|files| files.get(index))
+        {
+            None => Ok(vec![]),
+            Some(location) => {
+                let data = self.read_location(location)?;
+                self.deserialize_blocks(&data)
+            }
+        }
+    }
+
+    /// Read all the blocks of `partition`.
+    pub fn read(&self, partition: usize) -> Result<Vec<DataBlock>> {
+        let mut blocks = vec![];
+        for index in 0..self.num_files(partition) {
+            blocks.extend(self.read_file(partition, index)?);
+        }
+        Ok(blocks)
+    }
+
+    /// Remove the files of `partition`, it's useful to free the disk space after the
+    /// partition has been processed.
+    pub fn remove(&mut self, partition: usize) -> Result<()> {
+        if let Some(files) = self.files.remove(&partition) {
+            for location in files {
+                self.remove_location(&location)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_blocks(&self, blocks: &[&DataBlock]) -> Result<Vec<u8>> {
+        let options = WriteOptions { compression: None };
+        let ipc_fields = default_ipc_fields(&self.arrow_schema.fields);
+
+        let mut data = vec![];
+        for block in blocks {
+            let chunk = Chunk::try_from((*block).clone())?;
+            let (_dicts, flight_data) = serialize_batch(&chunk, &ipc_fields, &options);
+
+            data.write_u64::<BigEndian>(flight_data.data_header.len() as u64)?;
+            data.write_u64::<BigEndian>(flight_data.data_body.len() as u64)?;
+            data.write_all(&flight_data.data_header)?;
+            data.write_all(&flight_data.data_body)?;
+        }
+        Ok(data)
+    }
+
+    fn deserialize_blocks(&self, mut data: &[u8]) -> Result<Vec<DataBlock>> {
+        let ipc_schema = IpcSchema {
+            fields: default_ipc_fields(&self.arrow_schema.fields),
+            is_little_endian: true,
+        };
+
+        let mut blocks = vec![];
+        while !data.is_empty() {
+            let header_len = data.read_u64::<BigEndian>()? as usize;
+            let body_len = data.read_u64::<BigEndian>()? as usize;
+            let mut data_header = vec![0; header_len];
+            let mut data_body = vec![0; body_len];
+            data.read_exact(&mut data_header)?;
+            data.read_exact(&mut data_body)?;
+
+            let flight_data = FlightData {
+                app_metadata: vec![],
+                flight_descriptor: None,
+                data_header,
+                data_body,
+            };
+            let chunk = deserialize_batch(
+                &flight_data,
+                &self.arrow_schema.fields,
+                &ipc_schema,
+                &Default::default(),
+            )?;
+            blocks.push(DataBlock::from_chunk(&self.schema, &chunk)?);
+        }
+        Ok(blocks)
+    }
+
+    fn write_file(&self, location: &str, data: Vec<u8>) -> Result<()> {
+        match &self.storage {
+            SpillStorage::Local(root) => {
+                let path = root.join(location);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(path, data)?;
+            }
+            SpillStorage::Operator { operator, runtime } => {
+                let location = format!("_spill/{}", location);
+                runtime.block_on(operator.object(&location).write(data))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_location(&self, location: &str) -> Result<Vec<u8>> {
+        match &self.storage {
+            SpillStorage::Local(root) => Ok(std::fs::read(root.join(location))?),
+            SpillStorage::Operator { operator, runtime } => {
+                let location = format!("_spill/{}", location);
+                Ok(runtime.block_on(operator.object(&location).read())?)
+            }
+        }
+    }
+
+    fn remove_location(&self, location: &str) -> Result<()> {
+        match &self.storage {
+            SpillStorage::Local(root) => std::fs::remove_file(root.join(location))?,
+            SpillStorage::Operator { operator, runtime } => {
+                let location = format!("_spill/{}", location);
+                runtime.block_on(operator.object(&location).delete())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Spiller {
+    fn drop(&mut self) {
+        for partition in self.partitions() {
+            if let Err(cause) = self.remove(partition) {
+                tracing::warn!("Cannot remove the spilled files, cause: {:?}", cause);
+            }
+        }
+
+        if let SpillStorage::Local(root) = &self.storage {
+            let _ = std::fs::remove_dir(root.join(&self.location_prefix));
+        }
+    }
+}
+
+/// Scatter the rows of `block` into `num_partitions` blocks by the hash of `keys`,
+/// so that the rows with equal keys are always in the same partition.
+pub fn scatter_by_hash(
+    block: &DataBlock,
+    keys: &[ColumnRef],
+    num_partitions: usize,
+) -> Result<Vec<DataBlock>> {
+    let mut serialized_keys = vec![SmallVu8::new(); block.num_rows()];
+    for key in keys {
+        // Nullable and non-nullable keys with equal values must be in the same partition
+        let key = Series::remove_nullable(&key.convert_full_column());
+        Series::serialize(&key, &mut serialized_keys, None)?;
+    }
+
+    let indices = serialized_keys
+        .iter()
+        .map(|key| {
+            let mut hasher = DefaultHasher::new();
+            key.hash(&mut hasher);
+            (hasher.finish() % num_partitions as u64) as usize
+        })
+        .collect::<Vec<_>>();
+    DataBlock::scatter_block(block, &indices, num_partitions)
+}
@@ -151,6 +151,12 @@ pub struct QueryConfig {
     pub async_insert_max_data_size: u64,
     pub async_insert_busy_timeout: u64,
     pub async_insert_stale_timeout: u64,
+    /// Interval in seconds of checking the tables with auto compaction enabled, 0 to disable.
+    pub auto_compaction_interval_secs: u64,
+    /// Max number of the auto compaction jobs running at the same time.
+    pub auto_compaction_max_concurrent_jobs: u64,
+    /// Local folder of the spilled data, empty to use the temp directory of the system.
+    pub spill_local_path: String,
 }
 
 impl Default for QueryConfig {
@@ -198,6 +204,9 @@ impl Default for QueryConfig {
             async_insert_max_data_size: 10000,
             async_insert_busy_timeout: 200,
             async_insert_stale_timeout: 0,
+            auto_compaction_interval_secs: 60,
+            auto_compaction_max_concurrent_jobs: 1,
+            spill_local_path: "".to_string(),
         }
     }
 }
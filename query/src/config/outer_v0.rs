@@ -639,6 +639,19 @@ pub struct QueryConfig {
     /// The maximum timeout in milliseconds since the last insert before inserting collected data.
     #[clap(long, default_value = "0")]
     pub async_insert_stale_timeout: u64,
+
+    /// The interval in seconds of checking the tables with auto compaction enabled, 0 to disable.
+    #[clap(long, default_value = "60")]
+    pub auto_compaction_interval_secs: u64,
+
+    /// The maximum number of the auto compaction jobs running at the same time.
+    #[clap(long, default_value = "1")]
+    pub auto_compaction_max_concurrent_jobs: u64,
+
+    /// The local folder of the data spilled by queries exceeding the memory budget.
+    /// If empty, the temp directory of the system is used.
+    #[clap(long, default_value_t)]
+    pub spill_local_path: String,
 }
 
 impl Default for QueryConfig {
@@ -694,6 +707,9 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             async_insert_max_data_size: self.async_insert_max_data_size,
             async_insert_busy_timeout: self.async_insert_busy_timeout,
             async_insert_stale_timeout: self.async_insert_stale_timeout,
+            auto_compaction_interval_secs: self.auto_compaction_interval_secs,
+            auto_compaction_max_concurrent_jobs: self.auto_compaction_max_concurrent_jobs,
+            spill_local_path: self.spill_local_path,
         })
     }
 }
@@ -743,6 +759,9 @@ impl From<InnerQueryConfig> for QueryConfig {
             async_insert_max_data_size: inner.async_insert_max_data_size,
             async_insert_busy_timeout: inner.async_insert_busy_timeout,
             async_insert_stale_timeout: inner.async_insert_stale_timeout,
+            auto_compaction_interval_secs: inner.auto_compaction_interval_secs,
+            auto_compaction_max_concurrent_jobs: inner.auto_compaction_max_concurrent_jobs,
+            spill_local_path: inner.spill_local_path,
         }
     }
 }
@@ -26,20 +26,41 @@ use common_planners::PlanNode;
 
 use crate::api::DataExchange;
 use crate::api::ExecutePartialQueryPacket;
+use crate::api::FragmentPayload;
 use crate::api::FragmentPlanPacket;
 use crate::api::InitNodesChannelPacket;
 use crate::api::QueryFragmentsPlanPacket;
 use crate::sessions::QueryContext;
+use crate::sql::exec::PhysicalPlan;
 
 // Query plan fragment with executor name
 pub struct QueryFragmentAction {
-    pub node: PlanNode,
+    pub payload: FragmentPayload,
     pub executor: String,
 }
 
 impl QueryFragmentAction {
     pub fn create(executor: String, node: PlanNode) -> QueryFragmentAction {
-        QueryFragmentAction { node, executor }
+        QueryFragmentAction {
+            payload: FragmentPayload::PlanV1(node),
+            executor,
+        }
+    }
+
+    pub fn create_v2(executor: String, plan: PhysicalPlan) -> QueryFragmentAction {
+        QueryFragmentAction {
+            payload: FragmentPayload::PlanV2(plan),
+            executor,
+        }
+    }
+
+    pub fn get_node(&self) -> Result<&PlanNode> {
+        match &self.payload {
+            FragmentPayload::PlanV1(node) => Ok(node),
+            FragmentPayload::PlanV2(_) => Err(ErrorCode::LogicalError(
+                "Logical error, expect plan node of planner v1 in fragment action",
+            )),
+        }
     }
 }
 
@@ -76,7 +97,7 @@ impl QueryFragmentActions {
     pub fn get_schema(&self) -> Result<DataSchemaRef> {
         let mut actions_schema = Vec::with_capacity(self.fragment_actions.len());
         for fragment_action in &self.fragment_actions {
-            actions_schema.push(fragment_action.node.schema());
+            actions_schema.push(fragment_action.payload.schema()?);
         }
 
         if actions_schema.is_empty() {
@@ -314,7 +335,7 @@ impl QueryFragmentsActions {
             for fragment_action in &fragment_actions.fragment_actions {
                 let fragment_packet = FragmentPlanPacket::create(
                     fragment_actions.fragment_id,
-                    fragment_action.node.clone(),
+                    fragment_action.payload.clone(),
                     fragment_actions.data_exchange.clone(),
                 );
 
@@ -344,7 +365,7 @@ impl Debug for QueryFragmentsActions {
 impl Debug for QueryFragmentAction {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryFragmentAction")
-            .field("node", &self.node)
+            .field("node", &self.payload)
             .field("executor", &self.executor)
             .finish()
     }
@@ -73,14 +73,16 @@ impl QueryFragment for RootQueryFragment {
 
             fragment_actions.add_action(QueryFragmentAction::create(
                 actions.get_local_executor(),
-                self.input.rewrite_remote_plan(&self.node, &action.node)?,
+                self.input
+                    .rewrite_remote_plan(&self.node, action.get_node()?)?,
             ));
         } else {
             // This is an implicit stage. We run remaining plans on the current hosts
             for action in input_actions.get_actions() {
                 fragment_actions.add_action(QueryFragmentAction::create(
                     action.executor.clone(),
-                    self.input.rewrite_remote_plan(&self.node, &action.node)?,
+                    self.input
+                        .rewrite_remote_plan(&self.node, action.get_node()?)?,
                 ));
             }
 
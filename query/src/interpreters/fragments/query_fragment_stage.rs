@@ -88,7 +88,7 @@ impl QueryFragment for StageQueryFragment {
             let fragment_action = QueryFragmentAction::create(
                 actions.get_local_executor(),
                 self.input
-                    .rewrite_remote_plan(&self.stage.input, &action.node)?,
+                    .rewrite_remote_plan(&self.stage.input, action.get_node()?)?,
             );
 
             fragment_actions.add_action(fragment_action);
@@ -98,7 +98,7 @@ impl QueryFragment for StageQueryFragment {
                 let fragment_action = QueryFragmentAction::create(
                     action.executor.clone(),
                     self.input
-                        .rewrite_remote_plan(&self.stage.input, &action.node)?,
+                        .rewrite_remote_plan(&self.stage.input, action.get_node()?)?,
                 );
 
                 fragment_actions.add_action(fragment_action);
@@ -12,17 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::io;
 use std::sync::Arc;
 
+use chrono::Duration;
 use chrono::TimeZone;
 use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::GetTableCopiedFileReq;
+use common_meta_app::schema::TableCopiedFileInfo;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_meta_types::GrantObject;
 use common_meta_types::StageFile;
 use common_meta_types::StageType;
 use common_meta_types::UserStageInfo;
+use common_tracing::tracing::info;
 use common_tracing::tracing::warn;
 use futures::TryStreamExt;
 use regex::Regex;
@@ -219,3 +225,83 @@ pub async fn list_files_from_meta_api(
         .collect::<Vec<_>>();
     Ok(files)
 }
+
+/// Get the metadata of the files to be copied into a table, which tells whether a file changed.
+///
+/// Files failed to stat are absent, they are left to the copy to report the error.
+pub async fn stat_copy_files(
+    ctx: &Arc<QueryContext>,
+    stage: &UserStageInfo,
+    files: &[String],
+) -> Result<BTreeMap<String, TableCopiedFileInfo>> {
+    let op = StageSource::get_op(ctx, stage).await?;
+
+    let stats = futures::future::join_all(files.iter().map(|file| {
+        let object = op.object(file);
+        async move { object.metadata().await }
+    }))
+    .await;
+
+    let mut file_info = BTreeMap::new();
+    for (file, stat) in files.iter().zip(stats.into_iter()) {
+        match stat {
+            Ok(meta) => {
+                file_info.insert(file.clone(), TableCopiedFileInfo {
+                    etag: meta.content_md5().map(str::to_string),
+                    content_length: meta.content_length(),
+                    last_modified: meta
+                        .last_modified()
+                        .map(|t| Utc.timestamp(t.unix_timestamp(), 0)),
+                });
+            }
+            Err(e) => warn!("ignore the copied file info of {file}, because: {:?}", e),
+        }
+    }
+    Ok(file_info)
+}
+
+/// Filter out the files that were copied into the table and have not changed since then.
+pub async fn filter_copied_files(
+    ctx: &Arc<QueryContext>,
+    catalog_name: &str,
+    table_id: u64,
+    files: Vec<String>,
+    file_info: &BTreeMap<String, TableCopiedFileInfo>,
+) -> Result<Vec<String>> {
+    let catalog = ctx.get_catalog(catalog_name)?;
+    let req = GetTableCopiedFileReq {
+        table_id,
+        files: file_info.keys().cloned().collect(),
+    };
+    let copied = catalog.get_table_copied_file_info(req).await?.file_info;
+
+    let (skipped, files): (Vec<_>, Vec<_>) = files.into_iter().partition(|file| {
+        matches!((copied.get(file), file_info.get(file)), (Some(old), Some(new)) if old == new)
+    });
+
+    if !skipped.is_empty() {
+        info!("skip the files copied before: {:?}", skipped);
+    }
+    Ok(files)
+}
+
+/// Build the request that remembers the files copied into the table for
+/// `load_file_metadata_expire_hours`, it is committed along with the copied data.
+pub fn build_copied_files_req(
+    ctx: &Arc<QueryContext>,
+    table_id: u64,
+    file_info: BTreeMap<String, TableCopiedFileInfo>,
+) -> Result<Option<UpsertTableCopiedFileReq>> {
+    if file_info.is_empty() {
+        return Ok(None);
+    }
+
+    let expire_hours = ctx.get_settings().get_load_file_metadata_expire_hours()?;
+    let expire_at = Utc::now() + Duration::hours(expire_hours as i64);
+
+    Ok(Some(UpsertTableCopiedFileReq {
+        table_id,
+        file_info,
+        expire_at: Some(expire_at.timestamp() as u64),
+    }))
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -32,6 +33,9 @@ use futures::TryStreamExt;
 use regex::Regex;
 
 use super::SelectInterpreter;
+use crate::interpreters::interpreter_common::build_copied_files_req;
+use crate::interpreters::interpreter_common::filter_copied_files;
+use crate::interpreters::interpreter_common::stat_copy_files;
 use crate::interpreters::stream::ProcessorExecutorStream;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
@@ -39,6 +43,7 @@ use crate::pipelines::new::executor::PipelineCompleteExecutor;
 use crate::pipelines::new::executor::PipelinePullingExecutor;
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
+use crate::storages::stage::CopyFileStatus;
 use crate::storages::stage::StageSource;
 use crate::storages::stage::StageTable;
 
@@ -195,6 +200,7 @@ impl CopyInterpreter {
                 self.ctx.clone(),
                 &self.ctx.get_current_catalog(),
                 results.try_collect().await?,
+                None,
                 false,
             )
             .await?;
@@ -223,8 +229,10 @@ impl Interpreter for CopyInterpreter {
                 catalog_name,
                 db_name,
                 tbl_name,
+                tbl_id,
                 files,
                 pattern,
+                force,
                 from,
                 ..
             } => {
@@ -247,6 +255,19 @@ impl Interpreter for CopyInterpreter {
                     files = matched_files;
                 }
 
+                // Skip the files copied before unless FORCE = true.
+                let file_info = match &from.source_info {
+                    SourceInfo::StageSource(table_info) => {
+                        stat_copy_files(&self.ctx, &table_info.stage_info, &files).await?
+                    }
+                    _ => BTreeMap::new(),
+                };
+                if !*force {
+                    files =
+                        filter_copied_files(&self.ctx, catalog_name, *tbl_id, files, &file_info)
+                            .await?;
+                }
+
                 tracing::info!("copy file list:{:?}, pattern:{}", &files, pattern,);
 
                 let write_results = self
@@ -255,15 +276,36 @@ impl Interpreter for CopyInterpreter {
 
                 let table = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
 
-                // Commit.
+                // One row per file with the rows loaded and the errors seen.
+                let copy_status = self.ctx.consume_copy_file_status();
+
+                // Remember the copied files, the skipped ones can be copied again.
+                let copied_files = copy_status
+                    .iter()
+                    .filter(|status| !status.skipped)
+                    .filter_map(|status| {
+                        file_info
+                            .get(&status.file)
+                            .map(|info| (status.file.clone(), info.clone()))
+                    })
+                    .collect();
+                let copied_files = build_copied_files_req(&self.ctx, *tbl_id, copied_files)?;
+
+                // Commit, the copied files are recorded in the same transaction as the data.
                 table
-                    .commit_insertion(self.ctx.clone(), catalog_name, write_results, false)
+                    .commit_insertion(
+                        self.ctx.clone(),
+                        catalog_name,
+                        write_results,
+                        copied_files,
+                        false,
+                    )
                     .await?;
 
                 Ok(Box::pin(DataBlockStream::create(
-                    self.plan.schema(),
+                    CopyFileStatus::schema(),
                     None,
-                    vec![],
+                    vec![CopyFileStatus::to_data_block(&copy_status)],
                 )))
             }
             CopyMode::IntoStage {
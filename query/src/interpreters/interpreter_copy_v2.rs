@@ -11,6 +11,7 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -28,6 +29,9 @@ use common_tracing::tracing;
 use futures::TryStreamExt;
 use regex::Regex;
 
+use crate::interpreters::interpreter_common::build_copied_files_req;
+use crate::interpreters::interpreter_common::filter_copied_files;
+use crate::interpreters::interpreter_common::stat_copy_files;
 use crate::interpreters::stream::ProcessorExecutorStream;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
@@ -38,6 +42,7 @@ use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
 use crate::sql::plans::CopyPlanV2;
 use crate::sql::plans::Plan;
+use crate::storages::stage::CopyFileStatus;
 use crate::storages::stage::StageSource;
 use crate::storages::stage::StageTable;
 
@@ -181,6 +186,7 @@ impl CopyInterpreterV2 {
     async fn execute_copy_into_stage(
         &self,
         stage: &UserStageInfo,
+        path: &str,
         query: &Plan,
     ) -> Result<SendableDataBlockStream> {
         let (s_expr, metadata, bind_context) = match query {
@@ -215,7 +221,7 @@ impl CopyInterpreterV2 {
         let stage_table_info = StageTableInfo {
             schema: data_schema,
             stage_info: stage.clone(),
-            path: "".to_string(),
+            path: path.to_string(),
             files: vec![],
         };
 
@@ -229,6 +235,7 @@ impl CopyInterpreterV2 {
                 self.ctx.clone(),
                 &self.ctx.get_current_catalog(),
                 results.try_collect().await?,
+                None,
                 false,
             )
             .await?;
@@ -259,8 +266,10 @@ impl Interpreter for CopyInterpreterV2 {
                 catalog_name,
                 database_name,
                 table_name,
+                table_id,
                 files,
                 pattern,
+                force,
                 from,
                 ..
             } => {
@@ -284,6 +293,19 @@ impl Interpreter for CopyInterpreterV2 {
                     files = matched_files;
                 }
 
+                // Skip the files copied before unless FORCE = true.
+                let file_info = match &from.source_info {
+                    SourceInfo::StageSource(table_info) => {
+                        stat_copy_files(&self.ctx, &table_info.stage_info, &files).await?
+                    }
+                    _ => BTreeMap::new(),
+                };
+                if !*force {
+                    files =
+                        filter_copied_files(&self.ctx, catalog_name, *table_id, files, &file_info)
+                            .await?;
+                }
+
                 tracing::info!("copy file list:{:?}, pattern:{}", &files, pattern,);
 
                 let write_results = self
@@ -295,21 +317,41 @@ impl Interpreter for CopyInterpreterV2 {
                     .get_table(catalog_name, database_name, table_name)
                     .await?;
 
-                // Commit.
+                // One row per file with the rows loaded and the errors seen.
+                let copy_status = self.ctx.consume_copy_file_status();
+
+                // Remember the copied files, the skipped ones can be copied again.
+                let copied_files = copy_status
+                    .iter()
+                    .filter(|status| !status.skipped)
+                    .filter_map(|status| {
+                        file_info
+                            .get(&status.file)
+                            .map(|info| (status.file.clone(), info.clone()))
+                    })
+                    .collect();
+                let copied_files = build_copied_files_req(&self.ctx, *table_id, copied_files)?;
+
+                // Commit, the copied files are recorded in the same transaction as the data.
                 table
-                    .commit_insertion(self.ctx.clone(), catalog_name, write_results, false)
+                    .commit_insertion(
+                        self.ctx.clone(),
+                        catalog_name,
+                        write_results,
+                        copied_files,
+                        false,
+                    )
                     .await?;
 
                 Ok(Box::pin(DataBlockStream::create(
-                    // TODO(xuanwo): Is this correct?
-                    Arc::new(DataSchema::new(vec![])),
+                    CopyFileStatus::schema(),
                     None,
-                    vec![],
+                    vec![CopyFileStatus::to_data_block(&copy_status)],
                 )))
             }
-            CopyPlanV2::IntoStage { stage, from, .. } => {
-                self.execute_copy_into_stage(stage, from).await
-            }
+            CopyPlanV2::IntoStage {
+                stage, path, from, ..
+            } => self.execute_copy_into_stage(stage, path, from).await,
         }
     }
 }
@@ -170,6 +170,18 @@ impl InterpreterFactoryV2 {
                     *drop_table_cluster_key.clone(),
                 )
             }
+            Plan::AddTableColumn(add_table_column) => {
+                AddTableColumnInterpreter::try_create(ctx.clone(), *add_table_column.clone())
+            }
+            Plan::DropTableColumn(drop_table_column) => {
+                DropTableColumnInterpreter::try_create(ctx.clone(), *drop_table_column.clone())
+            }
+            Plan::RenameTableColumn(rename_table_column) => {
+                RenameTableColumnInterpreter::try_create(ctx.clone(), *rename_table_column.clone())
+            }
+            Plan::ReclusterTable(recluster_table) => {
+                ReclusterTableInterpreter::try_create(ctx.clone(), *recluster_table.clone())
+            }
             Plan::TruncateTable(truncate_table) => {
                 TruncateTableInterpreter::try_create(ctx.clone(), *truncate_table.clone())
             }
@@ -208,6 +220,10 @@ impl InterpreterFactoryV2 {
             }
 
             Plan::Delete(delete) => DeleteInterpreter::try_create(ctx.clone(), *delete.clone()),
+            Plan::Update(update) => UpdateInterpreter::try_create(ctx.clone(), *update.clone()),
+            Plan::MergeInto(merge_into) => {
+                MergeIntoInterpreter::try_create(ctx.clone(), *merge_into.clone())
+            }
 
             // Roles
             Plan::ShowRoles => ShowRolesInterpreter::try_create(ctx.clone()),
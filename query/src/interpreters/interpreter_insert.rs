@@ -192,7 +192,7 @@ impl InsertInterpreter {
         // We must put the commit operation to global runtime, which will avoid the "dispatch dropped without returning error" in tower
         let handler = self.ctx.get_storage_runtime().spawn(async move {
             table
-                .commit_insertion(context, &catalog_name, append_entries, overwrite)
+                .commit_insertion(context, &catalog_name, append_entries, None, overwrite)
                 .await
         });
 
@@ -321,6 +321,7 @@ impl Interpreter for InsertInterpreter {
                 self.ctx.clone(),
                 &self.plan.catalog,
                 append_logs.try_collect().await?,
+                None,
                 self.plan.overwrite,
             )
             .await?;
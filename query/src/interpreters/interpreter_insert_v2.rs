@@ -196,7 +196,7 @@ impl InsertInterpreterV2 {
         // We must put the commit operation to global runtime, which will avoid the "dispatch dropped without returning error" in tower
         let handler = self.ctx.get_storage_runtime().spawn(async move {
             table
-                .commit_insertion(context, &catalog_name, append_entries, overwrite)
+                .commit_insertion(context, &catalog_name, append_entries, None, overwrite)
                 .await
         });
 
@@ -0,0 +1,142 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::MergeMatchedOperation;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::interpreters::SelectInterpreterV2;
+use crate::sessions::QueryContext;
+use crate::sql::plans::MergeInto;
+use crate::sql::plans::Plan;
+
+/// interprets MergeInto
+pub struct MergeIntoInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: MergeInto,
+}
+
+impl MergeIntoInterpreter {
+    /// Create the MergeIntoInterpreter from MergeInto
+    pub fn try_create(ctx: Arc<QueryContext>, plan: MergeInto) -> Result<InterpreterPtr> {
+        Ok(Arc::new(MergeIntoInterpreter { ctx, plan }))
+    }
+
+    async fn validate_privileges(&self) -> Result<()> {
+        let plan = &self.plan.plan;
+        let mut privileges = vec![];
+        for clause in &plan.matched_clauses {
+            let privilege = match clause.operation {
+                MergeMatchedOperation::Update { .. } => UserPrivilegeType::Update,
+                MergeMatchedOperation::Delete => UserPrivilegeType::Delete,
+            };
+            if !privileges.contains(&privilege) {
+                privileges.push(privilege);
+            }
+        }
+        if !plan.unmatched_clauses.is_empty() {
+            privileges.push(UserPrivilegeType::Insert);
+        }
+
+        let object = GrantObject::Table(
+            plan.catalog_name.clone(),
+            plan.database_name.clone(),
+            plan.table_name.clone(),
+        );
+        for privilege in privileges {
+            self.ctx
+                .get_current_session()
+                .validate_privilege(&object, privilege)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Executes the source query, of which the blocks are streamed with the columns named as
+    /// the merge expressions refer to them.
+    async fn read_source(&self) -> Result<SendableDataBlockStream> {
+        let select_interpreter = match self.plan.source.as_ref() {
+            Plan::Query {
+                s_expr,
+                metadata,
+                bind_context,
+            } => SelectInterpreterV2::try_create(
+                self.ctx.clone(),
+                *bind_context.clone(),
+                s_expr.clone(),
+                metadata.clone(),
+            )?,
+            other => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "the source of MERGE INTO should be a query, but got {}",
+                    other
+                )));
+            }
+        };
+        let stream = select_interpreter.execute(None).await?;
+
+        let source_schema = self.plan.plan.source_schema.clone();
+        Ok(Box::pin(stream.map_ok(move |block| {
+            DataBlock::create(source_schema.clone(), block.columns().to_vec())
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for MergeIntoInterpreter {
+    /// Get the name of current interpreter
+    fn name(&self) -> &str {
+        "MergeIntoInterpreter"
+    }
+
+    /// Get the schema of MergeInto
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "merge_into_interpreter_execute", skip(self, _input_stream), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        self.validate_privileges().await?;
+
+        let plan = &self.plan.plan;
+        let tbl = self
+            .ctx
+            .get_table(&plan.catalog_name, &plan.database_name, &plan.table_name)
+            .await?;
+        let source = self.read_source().await?;
+        tbl.merge_into(self.ctx.clone(), plan.clone(), source)
+            .await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
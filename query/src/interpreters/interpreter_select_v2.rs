@@ -14,11 +14,13 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
+use crate::interpreters::plan_schedulers::schedule_query_v2;
 use crate::interpreters::stream::ProcessorExecutorStream;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
@@ -26,11 +28,13 @@ use crate::pipelines::new::executor::PipelineExecutor;
 use crate::pipelines::new::executor::PipelinePullingExecutor;
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
+use crate::sql::exec::PhysicalPlan;
 use crate::sql::exec::PhysicalPlanBuilder;
 use crate::sql::exec::PipelineBuilder;
 use crate::sql::optimizer::SExpr;
 use crate::sql::BindContext;
 use crate::sql::MetadataRef;
+use crate::storages::result::ResultCache;
 
 /// Interpret SQL query with new SQL planner
 pub struct SelectInterpreterV2 {
@@ -47,43 +51,67 @@ impl SelectInterpreterV2 {
         s_expr: SExpr,
         metadata: MetadataRef,
     ) -> Result<InterpreterPtr> {
-        Ok(Arc::new(SelectInterpreterV2 {
+        Ok(Arc::new(Self::create(ctx, bind_context, s_expr, metadata)))
+    }
+
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        bind_context: BindContext,
+        s_expr: SExpr,
+        metadata: MetadataRef,
+    ) -> Self {
+        SelectInterpreterV2 {
             ctx,
             s_expr,
             bind_context,
             metadata,
-        }))
-    }
-}
-
-#[async_trait::async_trait]
-impl Interpreter for SelectInterpreterV2 {
-    fn name(&self) -> &str {
-        "SelectInterpreterV2"
+        }
     }
 
-    #[tracing::instrument(level = "debug", name = "select_interpreter_v2_execute", skip(self, _input_stream), fields(ctx.id = self.ctx.get_id().as_str()))]
-    async fn execute(
-        &self,
-        _input_stream: Option<SendableDataBlockStream>,
-    ) -> Result<SendableDataBlockStream> {
+    /// Execute the query with the pipelines of this node. The result is never handed over to
+    /// the http query even if there is one, so it's used to run the queries inside a query.
+    pub fn execute_local(&self) -> Result<SendableDataBlockStream> {
         let builder = PhysicalPlanBuilder::new(self.metadata.clone());
         let physical_plan = builder.build(&self.s_expr)?;
+        let last_schema = physical_plan.output_schema()?;
+        let (root_pipeline, pipelines) = self.build_local_pipeline(&physical_plan)?;
+        self.execute_pipelines(last_schema, root_pipeline, pipelines)
+    }
 
-        if let Some(handle) = self.ctx.get_http_query() {
-            return handle
-                .execute(self.ctx.clone(), &physical_plan, &self.bind_context.columns)
-                .await;
+    /// Build the pipelines of physical plan, the plan is split into fragments and
+    /// scheduled to the nodes of cluster if it contains `Exchange`.
+    async fn build_pipeline(
+        &self,
+        physical_plan: &PhysicalPlan,
+    ) -> Result<(NewPipeline, Vec<NewPipeline>)> {
+        if physical_plan.is_distributed_plan() {
+            // Sub-pipelines are executed by the exchange manager with the fragments
+            let root_pipeline = schedule_query_v2(self.ctx.clone(), physical_plan).await?;
+            return Ok((root_pipeline, vec![]));
         }
 
-        let last_schema = physical_plan.output_schema()?;
+        self.build_local_pipeline(physical_plan)
+    }
 
+    fn build_local_pipeline(
+        &self,
+        physical_plan: &PhysicalPlan,
+    ) -> Result<(NewPipeline, Vec<NewPipeline>)> {
         let mut pb = PipelineBuilder::new();
         let mut root_pipeline = NewPipeline::create();
-        pb.build_pipeline(self.ctx.clone(), &physical_plan, &mut root_pipeline)?;
+        pb.build_pipeline(self.ctx.clone(), physical_plan, &mut root_pipeline)?;
+        Ok((root_pipeline, pb.pipelines))
+    }
+
+    fn execute_pipelines(
+        &self,
+        last_schema: DataSchemaRef,
+        mut root_pipeline: NewPipeline,
+        mut pipelines: Vec<NewPipeline>,
+    ) -> Result<SendableDataBlockStream> {
         // Render result set with given output schema
+        let mut pb = PipelineBuilder::new();
         pb.render_result_set(last_schema, &self.bind_context.columns, &mut root_pipeline)?;
-        let mut pipelines = pb.pipelines;
         let async_runtime = self.ctx.get_storage_runtime();
         let query_need_abort = self.ctx.query_need_abort();
 
@@ -109,6 +137,57 @@ impl Interpreter for SelectInterpreterV2 {
         let stream = ProcessorExecutorStream::create(executor)?;
         Ok(Box::pin(Box::pin(stream)))
     }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for SelectInterpreterV2 {
+    fn name(&self) -> &str {
+        "SelectInterpreterV2"
+    }
+
+    #[tracing::instrument(level = "debug", name = "select_interpreter_v2_execute", skip(self, _input_stream), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute(
+        &self,
+        _input_stream: Option<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        let builder = PhysicalPlanBuilder::new(self.metadata.clone());
+        let physical_plan = builder.build(&self.s_expr)?;
+        let last_schema = physical_plan.output_schema()?;
+
+        let result_cache =
+            ResultCache::try_create(&self.ctx, &self.s_expr, &self.metadata, &physical_plan)?;
+        let cached_blocks = match &result_cache {
+            Some(result_cache) => result_cache.try_read(&last_schema).await,
+            None => None,
+        };
+
+        let (root_pipeline, pipelines) = match (result_cache, cached_blocks) {
+            (Some(result_cache), Some(blocks)) => {
+                let mut root_pipeline = NewPipeline::create();
+                result_cache.build_source(blocks, &mut root_pipeline)?;
+                (root_pipeline, vec![])
+            }
+            (Some(result_cache), None) => {
+                let (mut root_pipeline, pipelines) = self.build_pipeline(&physical_plan).await?;
+                result_cache.add_writer(last_schema.clone(), &mut root_pipeline)?;
+                (root_pipeline, pipelines)
+            }
+            (None, _) => self.build_pipeline(&physical_plan).await?,
+        };
+
+        if let Some(handle) = self.ctx.get_http_query() {
+            return handle
+                .execute(
+                    self.ctx.clone(),
+                    root_pipeline,
+                    pipelines,
+                    &self.bind_context.columns,
+                )
+                .await;
+        }
+
+        self.execute_pipelines(last_schema, root_pipeline, pipelines)
+    }
 
     /// This method will create a new pipeline
     /// The QueryPipelineBuilder will use the optimized plan to generate a NewPipeline
@@ -116,12 +195,11 @@ impl Interpreter for SelectInterpreterV2 {
         let builder = PhysicalPlanBuilder::new(self.metadata.clone());
         let physical_plan = builder.build(&self.s_expr)?;
         let last_schema = physical_plan.output_schema()?;
+        let (mut root_pipeline, pipelines) = self.build_pipeline(&physical_plan).await?;
         let mut pb = PipelineBuilder::new();
-        let mut root_pipeline = NewPipeline::create();
-        pb.build_pipeline(self.ctx.clone(), &physical_plan, &mut root_pipeline)?;
         pb.render_result_set(last_schema, &self.bind_context.columns, &mut root_pipeline)?;
         root_pipeline.set_max_threads(self.ctx.get_settings().get_max_threads()? as usize);
-        if !pb.pipelines.is_empty() {
+        if !pipelines.is_empty() {
             return Err(ErrorCode::UnImplement(
                 "Unsupported run query with sub-pipeline".to_string(),
             ));
@@ -12,13 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::GrantObject;
+use common_meta_types::MetaId;
 use common_meta_types::UserPrivilegeType;
 use common_planners::CreateTablePlan;
 use common_planners::InsertInputSource;
@@ -31,6 +34,9 @@ use super::InsertInterpreter;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_CLONED_FROM_TABLE_IDS;
+use crate::sql::OPT_KEY_CLONE_SNAPSHOT_LOCATIONS;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
 use crate::storages::StorageDescription;
 
 pub struct CreateTableInterpreter {
@@ -178,6 +184,7 @@ impl CreateTableInterpreter {
     async fn create_table(&self) -> Result<SendableDataBlockStream> {
         let catalog = self.ctx.get_catalog(self.plan.catalog.as_str())?;
         catalog.create_table(self.plan.clone().into()).await?;
+        self.register_clone().await?;
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
@@ -185,4 +192,48 @@ impl CreateTableInterpreter {
             vec![],
         )))
     }
+
+    /// Records the snapshot that the table is cloned at in the tables it is cloned from, which
+    /// keeps the data shared with the table from being purged by them.
+    async fn register_clone(&self) -> Result<()> {
+        let options = &self.plan.options;
+        let (source_ids, snapshot_loc) = match (
+            options.get(OPT_KEY_CLONED_FROM_TABLE_IDS),
+            options.get(OPT_KEY_SNAPSHOT_LOCATION),
+        ) {
+            (Some(ids), Some(loc)) => (ids, loc),
+            // nothing is shared, if the table is cloned from an empty table
+            _ => return Ok(()),
+        };
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = self.ctx.get_catalog(&self.plan.catalog)?;
+        let clone = catalog
+            .get_table(tenant.as_str(), &self.plan.database, &self.plan.table)
+            .await?;
+        for id in source_ids.split(',') {
+            let table_id = id.parse::<MetaId>()?;
+            loop {
+                let (ident, meta) = catalog.get_table_meta_by_id(table_id).await?;
+                let mut locations: BTreeMap<MetaId, String> =
+                    match meta.options.get(OPT_KEY_CLONE_SNAPSHOT_LOCATIONS) {
+                        Some(v) => serde_json::from_str(v)?,
+                        None => BTreeMap::new(),
+                    };
+                locations.insert(clone.get_id(), snapshot_loc.clone());
+                let req = UpsertTableOptionReq::new(
+                    &ident,
+                    OPT_KEY_CLONE_SNAPSHOT_LOCATIONS,
+                    serde_json::to_string(&locations)?,
+                );
+                match catalog.upsert_table_option(req).await {
+                    // the table is changed concurrently, e.g. cloned by others
+                    Err(e) if e.code() == ErrorCode::table_version_mismatched_code() => continue,
+                    Err(e) => return Err(e),
+                    Ok(_) => break,
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -14,6 +14,9 @@
 
 use std::sync::Arc;
 
+use common_datablocks::DataBlock;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
 use common_exception::Result;
 use common_planners::OptimizeTableAction;
 use common_planners::OptimizeTablePlan;
@@ -51,6 +54,17 @@ impl Interpreter for OptimizeTableInterpreter {
             .get_table(&plan.catalog, &plan.database, &plan.table)
             .await?;
 
+        if plan.dry_run {
+            let files = table.optimize_dry_run(self.ctx.clone(), true).await?;
+            let column = Series::from_data(files.iter().map(|f| f.as_bytes()).collect::<Vec<_>>());
+            let block = DataBlock::create(plan.schema(), vec![column]);
+            return Ok(Box::pin(DataBlockStream::create(
+                plan.schema(),
+                None,
+                vec![block],
+            )));
+        }
+
         let action = &plan.action;
         let do_purge = matches!(
             action,
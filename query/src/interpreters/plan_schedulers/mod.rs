@@ -25,6 +25,7 @@ pub use plan_scheduler_error::handle_error;
 pub use plan_scheduler_insert::InsertWithPlan;
 pub use plan_scheduler_query::schedule_query;
 pub use plan_scheduler_query::schedule_query_new;
+pub use plan_scheduler_query::schedule_query_v2;
 pub use plan_scheduler_rewriter::apply_plan_rewrite;
 pub use plan_scheduler_stream::Scheduled;
 pub use plan_scheduler_stream::ScheduledStream;
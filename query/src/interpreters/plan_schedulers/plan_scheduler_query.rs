@@ -32,6 +32,8 @@ use crate::pipelines::new::NewPipeline;
 use crate::pipelines::new::QueryPipelineBuilder;
 use crate::pipelines::processors::PipelineBuilder;
 use crate::sessions::QueryContext;
+use crate::sql::exec::Fragmenter;
+use crate::sql::exec::PhysicalPlan;
 
 #[tracing::instrument(level = "debug", skip(ctx), fields(ctx.id = ctx.get_id().as_str()))]
 pub async fn schedule_query(
@@ -94,3 +96,21 @@ pub async fn schedule_query_new(ctx: Arc<QueryContext>, plan: &PlanNode) -> Resu
     pipeline.set_max_threads(settings.get_max_threads()? as usize);
     Ok(pipeline)
 }
+
+pub async fn schedule_query_v2(ctx: Arc<QueryContext>, plan: &PhysicalPlan) -> Result<NewPipeline> {
+    let fragmenter = Fragmenter::create(ctx.clone());
+    let root_fragment = fragmenter.build_fragment(plan.clone())?;
+
+    let mut fragments_actions = QueryFragmentsActions::create(ctx.clone());
+    root_fragment.get_actions(&mut fragments_actions)?;
+
+    debug!("QueryFragments actions: {:?}", fragments_actions);
+
+    let settings = ctx.get_settings();
+    let exchange_manager = ctx.get_exchange_manager();
+    let mut pipeline = exchange_manager
+        .commit_actions(ctx, fragments_actions)
+        .await?;
+    pipeline.set_max_threads(settings.get_max_threads()? as usize);
+    Ok(pipeline)
+}
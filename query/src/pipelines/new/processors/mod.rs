@@ -51,6 +51,7 @@ pub use transforms::BlockCompactor;
 pub use transforms::ExpressionTransform;
 pub use transforms::HashJoinState;
 pub use transforms::HashTable;
+pub use transforms::JoinBuffer;
 pub use transforms::JoinHashTable;
 pub use transforms::KeyU128HashTable;
 pub use transforms::KeyU16HashTable;
@@ -62,6 +63,7 @@ pub use transforms::KeyU8HashTable;
 pub use transforms::ProjectionTransform;
 pub use transforms::SerializerHashTable;
 pub use transforms::SinkBuildHashTable;
+pub use transforms::SinkJoinBuffer;
 pub use transforms::SortMergeCompactor;
 pub use transforms::SubQueriesPuller;
 pub use transforms::TransformAddOn;
@@ -72,11 +74,17 @@ pub use transforms::TransformCastSchema;
 pub use transforms::TransformCompact;
 pub use transforms::TransformCreateSets;
 pub use transforms::TransformDummy;
+pub use transforms::TransformExpandGroupingSets;
 pub use transforms::TransformFilter;
 pub use transforms::TransformHashJoinProbe;
 pub use transforms::TransformHaving;
 pub use transforms::TransformLimit;
 pub use transforms::TransformLimitBy;
 pub use transforms::TransformMax1Row;
+pub use transforms::TransformMergeJoin;
+pub use transforms::TransformNestedLoopJoin;
 pub use transforms::TransformSortMerge;
 pub use transforms::TransformSortPartial;
+pub use transforms::TransformSortSpill;
+pub use transforms::TransformWindowFunc;
+pub use transforms::WindowFuncCompactor;
@@ -35,6 +35,8 @@ use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 
+use super::aggregator_spill::serialize_state_groups;
+use super::aggregator_spill::AggregatorSpill;
 use crate::pipelines::new::processors::transforms::transform_aggregator::Aggregator;
 use crate::pipelines::new::processors::AggregatorParams;
 use crate::pipelines::transforms::group_by::AggregatorState;
@@ -74,6 +76,7 @@ pub struct FinalAggregator<
     // used for deserialization only, so we can reuse it during the loop
     temp_place: Option<StateAddr>,
     ctx: Arc<QueryContext>,
+    spill: AggregatorSpill,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
@@ -91,6 +94,7 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
             state.alloc_layout2(&params)
         };
 
+        let spill = AggregatorSpill::try_create(ctx.clone())?;
         Ok(Self {
             is_generated: false,
             states_dropped: false,
@@ -99,8 +103,37 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
             params,
             temp_place,
             ctx,
+            spill,
         })
     }
+
+    /// Drop the hash table and create an empty one, used after the groups are spilled.
+    fn reset_state(&mut self) {
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.temp_place = if self.params.aggregate_functions.is_empty() {
+            None
+        } else {
+            self.state.alloc_layout2(&self.params)
+        };
+        self.states_dropped = false;
+        self.spill.free();
+    }
+
+    /// Partition the hash table to disk if it exceeds the memory budget.
+    /// After that all the incoming blocks are partitioned to disk directly.
+    fn try_spill(&mut self, block: &DataBlock, groups_before: usize) -> Result<()> {
+        let new_groups = self.state.len() - groups_before;
+        if self.spill.need_spill(block, new_groups, &self.params) {
+            let spilled =
+                serialize_state_groups(&self.method, &self.state, &self.params, block.schema())?;
+            self.reset_state();
+            self.spill
+                .spill_partitioned(spilled, self.params.aggregate_functions.len())?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<true, Method> {
@@ -140,6 +173,41 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
     const NAME: &'static str = "FinalAggregatorWithAggregateFunction";
 
     fn consume(&mut self, block: DataBlock) -> Result<()> {
+        if self.spill.is_partitioned() {
+            let keys_index = self.params.aggregate_functions.len();
+            return self.spill.spill_partitioned(block, keys_index);
+        }
+
+        let groups_before = self.state.len();
+        self.consume_groups(&block)?;
+        self.try_spill(&block, groups_before)
+    }
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        loop {
+            if self.state.len() != 0 && !self.is_generated {
+                self.is_generated = true;
+                return self.generate_groups().map(Some);
+            }
+
+            // Aggregate the spilled partitions one by one.
+            match self.spill.take_partition()? {
+                None => {
+                    self.drop_states();
+                    return Ok(None);
+                }
+                Some(block) => {
+                    self.reset_state();
+                    self.consume_groups(&block)?;
+                    self.is_generated = false;
+                }
+            }
+        }
+    }
+}
+
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<true, Method> {
+    fn consume_groups(&mut self, block: &DataBlock) -> Result<()> {
         // 1.1 and 1.2.
         let aggregate_function_len = self.params.aggregate_functions.len();
         let keys_column = block.column(aggregate_function_len);
@@ -182,55 +250,45 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         Ok(())
     }
 
-    fn generate(&mut self) -> Result<Option<DataBlock>> {
-        match self.state.len() == 0 || self.is_generated {
-            true => {
-                self.drop_states();
-                Ok(None)
-            }
-            false => {
-                self.is_generated = true;
-                let mut group_columns_builder = self
-                    .method
-                    .group_columns_builder(self.state.len(), &self.params);
-
-                let aggregate_functions = &self.params.aggregate_functions;
-                let offsets_aggregate_states = &self.params.offsets_aggregate_states;
+    fn generate_groups(&mut self) -> Result<DataBlock> {
+        let mut group_columns_builder = self
+            .method
+            .group_columns_builder(self.state.len(), &self.params);
 
-                let mut aggregates_column_builder: Vec<Box<dyn MutableColumn>> = {
-                    let mut values = vec![];
-                    for aggregate_function in aggregate_functions {
-                        let builder = aggregate_function.return_type()?.create_mutable(1024);
-                        values.push(builder)
-                    }
-                    values
-                };
+        let aggregate_functions = &self.params.aggregate_functions;
+        let offsets_aggregate_states = &self.params.offsets_aggregate_states;
 
-                for group_entity in self.state.iter() {
-                    let place: StateAddr = (*group_entity.get_state_value()).into();
+        let mut aggregates_column_builder: Vec<Box<dyn MutableColumn>> = {
+            let mut values = vec![];
+            for aggregate_function in aggregate_functions {
+                let builder = aggregate_function.return_type()?.create_mutable(1024);
+                values.push(builder)
+            }
+            values
+        };
 
-                    for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
-                        let arg_place = place.next(offsets_aggregate_states[idx]);
-                        let builder: &mut dyn MutableColumn =
-                            aggregates_column_builder[idx].borrow_mut();
-                        aggregate_function.merge_result(arg_place, builder)?;
-                    }
+        for group_entity in self.state.iter() {
+            let place: StateAddr = (*group_entity.get_state_value()).into();
 
-                    group_columns_builder.append_value(group_entity.get_state_key());
-                }
+            for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+                let arg_place = place.next(offsets_aggregate_states[idx]);
+                let builder: &mut dyn MutableColumn = aggregates_column_builder[idx].borrow_mut();
+                aggregate_function.merge_result(arg_place, builder)?;
+            }
 
-                // Build final state block.
-                let fields_len = self.params.schema.fields().len();
-                let mut columns = Vec::with_capacity(fields_len);
+            group_columns_builder.append_value(group_entity.get_state_key());
+        }
 
-                for mut array in aggregates_column_builder {
-                    columns.push(array.to_column());
-                }
+        // Build final state block.
+        let fields_len = self.params.schema.fields().len();
+        let mut columns = Vec::with_capacity(fields_len);
 
-                columns.extend_from_slice(&group_columns_builder.finish()?);
-                Ok(Some(DataBlock::create(self.params.schema.clone(), columns)))
-            }
+        for mut array in aggregates_column_builder {
+            columns.push(array.to_column());
         }
+
+        columns.extend_from_slice(&group_columns_builder.finish()?);
+        Ok(DataBlock::create(self.params.schema.clone(), columns))
     }
 }
 
@@ -240,27 +298,18 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
     const NAME: &'static str = "";
 
     fn consume(&mut self, block: DataBlock) -> Result<()> {
-        let key_array = block.column(0);
-        let keys_iter = self.method.keys_iter_from_column(key_array)?;
-
-        let group_by_two_level_threshold =
-            self.ctx.get_settings().get_group_by_two_level_threshold()? as usize;
-        if !self.state.is_two_level() && self.state.len() >= group_by_two_level_threshold {
-            self.state.convert_to_two_level();
-        }
-
-        let mut inserted = true;
-        for keys_ref in keys_iter.get_slice() {
-            self.state.entity_by_key(keys_ref, &mut inserted);
+        if self.spill.is_partitioned() {
+            return self.spill.spill_partitioned(block, 0);
         }
 
-        Ok(())
+        let groups_before = self.state.len();
+        self.consume_groups(&block)?;
+        self.try_spill(&block, groups_before)
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
-        match self.state.len() == 0 || self.is_generated {
-            true => Ok(None),
-            false => {
+        loop {
+            if self.state.len() != 0 && !self.is_generated {
                 self.is_generated = true;
                 let mut columns_builder = self
                     .method
@@ -270,12 +319,42 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
                 }
 
                 let columns = columns_builder.finish()?;
-                Ok(Some(DataBlock::create(self.params.schema.clone(), columns)))
+                return Ok(Some(DataBlock::create(self.params.schema.clone(), columns)));
+            }
+
+            // Aggregate the spilled partitions one by one.
+            match self.spill.take_partition()? {
+                None => return Ok(None),
+                Some(block) => {
+                    self.reset_state();
+                    self.consume_groups(&block)?;
+                    self.is_generated = false;
+                }
             }
         }
     }
 }
 
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<false, Method> {
+    fn consume_groups(&mut self, block: &DataBlock) -> Result<()> {
+        let key_array = block.column(0);
+        let keys_iter = self.method.keys_iter_from_column(key_array)?;
+
+        let group_by_two_level_threshold =
+            self.ctx.get_settings().get_group_by_two_level_threshold()? as usize;
+        if !self.state.is_two_level() && self.state.len() >= group_by_two_level_threshold {
+            self.state.convert_to_two_level();
+        }
+
+        let mut inserted = true;
+        for keys_ref in keys_iter.get_slice() {
+            self.state.entity_by_key(keys_ref, &mut inserted);
+        }
+
+        Ok(())
+    }
+}
+
 impl<const FINAL: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     FinalAggregator<FINAL, Method>
 {
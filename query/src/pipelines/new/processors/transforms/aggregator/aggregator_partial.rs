@@ -14,7 +14,6 @@
 
 use std::sync::Arc;
 
-use bytes::BytesMut;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
 use common_datablocks::HashMethodKeysU128;
@@ -27,13 +26,12 @@ use common_datablocks::HashMethodKeysU8;
 use common_datablocks::HashMethodSerializer;
 use common_datablocks::HashMethodSingleString;
 use common_datavalues::ColumnRef;
-use common_datavalues::MutableColumn;
-use common_datavalues::MutableStringColumn;
-use common_datavalues::ScalarColumnBuilder;
 use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 
+use super::aggregator_spill::serialize_state_groups;
+use super::aggregator_spill::AggregatorSpill;
 use crate::pipelines::new::processors::transforms::transform_aggregator::Aggregator;
 use crate::pipelines::new::processors::AggregatorParams;
 use crate::pipelines::transforms::group_by::AggregatorState;
@@ -75,21 +73,28 @@ pub struct PartialAggregator<
     state: Method::State,
     params: Arc<AggregatorParams>,
     ctx: Arc<QueryContext>,
+    spill: AggregatorSpill,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     PartialAggregator<HAS_AGG, Method>
 {
-    pub fn create(ctx: Arc<QueryContext>, method: Method, params: Arc<AggregatorParams>) -> Self {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        method: Method,
+        params: Arc<AggregatorParams>,
+    ) -> Result<Self> {
         let state = method.aggregate_state();
-        Self {
+        let spill = AggregatorSpill::try_create(ctx.clone())?;
+        Ok(Self {
             is_generated: false,
             states_dropped: false,
             state,
             method,
             params,
             ctx,
-        }
+            spill,
+        })
     }
 
     #[inline(always)]
@@ -192,41 +197,29 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
         }
 
         self.is_generated = true;
-        let state_groups_len = self.state.len();
-        let aggregator_params = self.params.as_ref();
-        let funcs = &aggregator_params.aggregate_functions;
-        let aggr_len = funcs.len();
-        let offsets_aggregate_states = &aggregator_params.offsets_aggregate_states;
-
-        // Builders.
-        let mut state_builders: Vec<MutableStringColumn> = (0..aggr_len)
-            .map(|_| MutableStringColumn::with_capacity(state_groups_len * 4))
-            .collect();
-
-        let mut group_key_builder = self.method.keys_column_builder(state_groups_len);
-
-        let mut bytes = BytesMut::new();
-        for group_entity in self.state.iter() {
-            let place: StateAddr = (*group_entity.get_state_value()).into();
-
-            for (idx, func) in funcs.iter().enumerate() {
-                let arg_place = place.next(offsets_aggregate_states[idx]);
-                func.serialize(arg_place, &mut bytes)?;
-                state_builders[idx].append_value(&bytes[..]);
-                bytes.clear();
-            }
-
-            group_key_builder.append_value(group_entity.get_state_key());
-        }
+        let block =
+            serialize_state_groups(&self.method, &self.state, &self.params, &self.params.schema)?;
+        Ok(Some(block))
+    }
 
-        let schema = &self.params.schema;
-        let mut columns: Vec<ColumnRef> = Vec::with_capacity(schema.fields().len());
-        for mut builder in state_builders {
-            columns.push(builder.to_column());
+    /// Flush the hash table to disk if it exceeds the memory budget.
+    fn try_spill(&mut self, block: &DataBlock, groups_before: usize) -> Result<()> {
+        let new_groups = self.state.len() - groups_before;
+        if self.spill.need_spill(block, new_groups, &self.params) {
+            let block = serialize_state_groups(
+                &self.method,
+                &self.state,
+                &self.params,
+                &self.params.schema,
+            )?;
+            self.drop_states();
+            self.state = self.method.aggregate_state();
+            self.states_dropped = false;
+            self.spill.free();
+            self.spill.spill_flush(block)?;
         }
 
-        columns.push(group_key_builder.finish());
-        Ok(Some(DataBlock::create(schema.clone(), columns)))
+        Ok(())
     }
 }
 
@@ -246,12 +239,17 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             self.state.convert_to_two_level();
         }
 
+        let groups_before = self.state.len();
         let places = Self::lookup_state(&self.params, group_keys, &mut self.state);
-        Self::execute(&self.params, &block, &places)
+        Self::execute(&self.params, &block, &places)?;
+        self.try_spill(&block, groups_before)
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
-        self.generate_data()
+        match self.generate_data()? {
+            Some(block) => Ok(Some(block)),
+            None => self.spill.take_partition(),
+        }
     }
 }
 
@@ -271,15 +269,16 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             self.state.convert_to_two_level();
         }
 
+        let groups_before = self.state.len();
         Self::lookup_key(group_keys, &mut self.state);
-        Ok(())
+        self.try_spill(&block, groups_before)
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
         match self.state.len() == 0 || self.is_generated {
             true => {
                 self.drop_states();
-                Ok(None)
+                self.spill.take_partition()
             }
             false => {
                 self.is_generated = true;
@@ -0,0 +1,179 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::MutableColumn;
+use common_datavalues::MutableStringColumn;
+use common_datavalues::ScalarColumnBuilder;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+
+use crate::common::scatter_by_hash;
+use crate::common::MemoryReservation;
+use crate::common::Spiller;
+use crate::pipelines::new::processors::AggregatorParams;
+use crate::pipelines::transforms::group_by::AggregatorState;
+use crate::pipelines::transforms::group_by::KeysColumnBuilder;
+use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
+use crate::pipelines::transforms::group_by::StateEntity;
+use crate::sessions::QueryContext;
+
+/// Serialize the groups of the hash table into the output format of the partial aggregator:
+/// the serialized state of each aggregate function, followed by the group keys.
+pub fn serialize_state_groups<Method: HashMethod + PolymorphicKeysHelper<Method>>(
+    method: &Method,
+    state: &Method::State,
+    params: &AggregatorParams,
+    schema: &DataSchemaRef,
+) -> Result<DataBlock> {
+    let state_groups_len = state.len();
+    let funcs = &params.aggregate_functions;
+    let offsets_aggregate_states = &params.offsets_aggregate_states;
+
+    // Builders.
+    let mut state_builders: Vec<MutableStringColumn> = (0..funcs.len())
+        .map(|_| MutableStringColumn::with_capacity(state_groups_len * 4))
+        .collect();
+
+    let mut group_key_builder = method.keys_column_builder(state_groups_len);
+
+    let mut bytes = BytesMut::new();
+    for group_entity in state.iter() {
+        let place: StateAddr = (*group_entity.get_state_value()).into();
+
+        for (idx, func) in funcs.iter().enumerate() {
+            let arg_place = place.next(offsets_aggregate_states[idx]);
+            func.serialize(arg_place, &mut bytes)?;
+            state_builders[idx].append_value(&bytes[..]);
+            bytes.clear();
+        }
+
+        group_key_builder.append_value(group_entity.get_state_key());
+    }
+
+    let mut columns: Vec<ColumnRef> = Vec::with_capacity(schema.fields().len());
+    for mut builder in state_builders {
+        columns.push(builder.to_column());
+    }
+
+    columns.push(group_key_builder.finish());
+    Ok(DataBlock::create(schema.clone(), columns))
+}
+
+/// Spills the hash table of an aggregator when it exceeds the memory budget of the query.
+///
+/// The partial aggregator flushes its whole hash table into a new partition each time, while
+/// the final aggregator scatters its groups and all the following blocks into partitions by
+/// the hash of the group keys, so that each partition can be aggregated on its own.
+pub struct AggregatorSpill {
+    ctx: Arc<QueryContext>,
+    reservation: MemoryReservation,
+    spiller: Option<Spiller>,
+    num_partitions: usize,
+    next_partition: usize,
+    is_partitioned: bool,
+}
+
+impl AggregatorSpill {
+    pub fn try_create(ctx: Arc<QueryContext>) -> Result<AggregatorSpill> {
+        let reservation = MemoryReservation::create(ctx.get_memory_budget()?);
+        let num_partitions = ctx.get_settings().get_spill_partitions()?.max(1) as usize;
+
+        Ok(AggregatorSpill {
+            ctx,
+            reservation,
+            spiller: None,
+            num_partitions,
+            next_partition: 0,
+            is_partitioned: false,
+        })
+    }
+
+    /// Whether the incoming blocks should be partitioned to disk directly.
+    pub fn is_partitioned(&self) -> bool {
+        self.is_partitioned
+    }
+
+    /// Account the memory of the groups inserted by `block`.
+    /// Returns true if the hash table exceeds the budget and should be spilled.
+    pub fn need_spill(
+        &mut self,
+        block: &DataBlock,
+        new_groups: usize,
+        params: &AggregatorParams,
+    ) -> bool {
+        if new_groups == 0 || block.num_rows() == 0 {
+            return false;
+        }
+
+        // The size of group keys is estimated by the size of the rows.
+        let keys_size = block.memory_size() * new_groups / block.num_rows();
+        let states_size = params.layout.map_or(0, |layout| layout.size()) * new_groups;
+        let size = self.reservation.size() + keys_size + states_size;
+        !self.reservation.try_resize(size) && self.reservation.can_spill()
+    }
+
+    /// Release the memory of the spilled hash table.
+    pub fn free(&mut self) {
+        self.reservation.free();
+    }
+
+    /// Spill `block` into a new partition.
+    pub fn spill_flush(&mut self, block: DataBlock) -> Result<()> {
+        let partition = self.next_partition;
+        self.next_partition += 1;
+        self.spiller(&block)?.spill(partition, &[block])
+    }
+
+    /// Scatter `block` into the partitions by the hash of the column at `keys_index`.
+    pub fn spill_partitioned(&mut self, block: DataBlock, keys_index: usize) -> Result<()> {
+        self.is_partitioned = true;
+        let num_partitions = self.num_partitions;
+        let keys = block.column(keys_index).clone();
+        let blocks = scatter_by_hash(&block, &[keys], num_partitions)?;
+        self.spiller(&block)?.spill_scattered(&blocks)
+    }
+
+    /// Read back and remove the first spilled partition.
+    pub fn take_partition(&mut self) -> Result<Option<DataBlock>> {
+        let spiller = match self.spiller.as_mut() {
+            None => return Ok(None),
+            Some(spiller) => spiller,
+        };
+
+        match spiller.partitions().first() {
+            None => Ok(None),
+            Some(partition) => {
+                let blocks = spiller.read(*partition)?;
+                spiller.remove(*partition)?;
+                Ok(Some(DataBlock::concat_blocks(&blocks)?))
+            }
+        }
+    }
+
+    fn spiller(&mut self, block: &DataBlock) -> Result<&mut Spiller> {
+        if self.spiller.is_none() {
+            let schema = block.schema().clone();
+            self.spiller = Some(Spiller::create(&self.ctx, "aggregator", schema)?);
+        }
+
+        Ok(self.spiller.as_mut().unwrap())
+    }
+}
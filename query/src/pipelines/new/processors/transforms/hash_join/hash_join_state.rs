@@ -34,6 +34,16 @@ pub trait HashJoinState: Send + Sync {
     /// Is building finished.
     fn is_finished(&self) -> Result<bool>;
 
+    /// Attach a probe processor to state
+    fn attach_probe(&self) -> Result<()>;
+
+    /// Detach a probe processor from state, returns true if it's the last one.
+    fn detach_probe(&self) -> Result<bool>;
+
+    /// Probe the next spilled partition, returns None if there are no more partitions.
+    /// It's only called by the last detached probe processor.
+    fn probe_spilled(&self, probe_state: &mut ProbeState) -> Result<Option<Vec<DataBlock>>>;
+
     /// Finish building hash table, will be called only once as soon as all handles
     /// have been detached from current state.
     fn finish(&self) -> Result<()>;
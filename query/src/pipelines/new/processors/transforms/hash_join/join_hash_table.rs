@@ -15,6 +15,8 @@
 use std::borrow::BorrowMut;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -39,8 +41,12 @@ use primitive_types::U256;
 use primitive_types::U512;
 
 use super::ProbeState;
+use crate::common::scatter_by_hash;
 use crate::common::EvalNode;
 use crate::common::Evaluator;
+use crate::common::MemoryReservation;
+use crate::common::RuntimeFilter;
+use crate::common::Spiller;
 use crate::pipelines::new::processors::transforms::hash_join::row::Chunk;
 use crate::pipelines::new::processors::transforms::hash_join::row::RowPtr;
 use crate::pipelines::new::processors::transforms::hash_join::row::RowSpace;
@@ -119,6 +125,32 @@ pub struct HashJoinDesc {
     pub(crate) marker_index: Option<IndexType>,
 }
 
+/// The parameters the join hash table is created with, used to create the hash tables
+/// of the spilled partitions.
+#[derive(Clone)]
+struct JoinHashTableParams {
+    join_type: JoinType,
+    build_keys: Vec<PhysicalScalar>,
+    probe_keys: Vec<PhysicalScalar>,
+    other_predicate: Option<PhysicalScalar>,
+    build_schema: DataSchemaRef,
+    marker_index: Option<IndexType>,
+    /// The hash tables of the spilled partitions never spill again
+    spillable: bool,
+}
+
+/// State of the grace hash join. Once the build side exceeds the memory budget, both sides
+/// are partitioned to disk by the hash of the join keys, and joined partition by partition
+/// after all the probe processors are detached.
+struct JoinSpillState {
+    reservation: MemoryReservation,
+    num_partitions: usize,
+    build_spiller: Option<Spiller>,
+    probe_spiller: Option<Spiller>,
+    /// The number of attached probe processors
+    probe_count: usize,
+}
+
 pub struct JoinHashTable {
     pub(crate) ctx: Arc<QueryContext>,
     /// Reference count
@@ -128,9 +160,18 @@ pub struct JoinHashTable {
     pub(crate) hash_table: RwLock<HashTable>,
     pub(crate) row_space: RowSpace,
     pub(crate) hash_join_desc: HashJoinDesc,
+
+    params: JoinHashTableParams,
+    /// Whether the join can be spilled, it's false for the hash tables of spilled partitions.
+    spill_enabled: bool,
+    is_spilled: AtomicBool,
+    spill_state: Mutex<JoinSpillState>,
+    /// The runtime filters collected from the build keys at the indexes
+    runtime_filters: Vec<(usize, Arc<RuntimeFilter>)>,
 }
 
 impl JoinHashTable {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_join_state(
         ctx: Arc<QueryContext>,
         join_type: JoinType,
@@ -139,110 +180,125 @@ impl JoinHashTable {
         other_predicate: Option<&PhysicalScalar>,
         build_schema: DataSchemaRef,
         marker_index: Option<IndexType>,
+        runtime_filters: Vec<(usize, Arc<RuntimeFilter>)>,
     ) -> Result<Arc<JoinHashTable>> {
-        let hash_key_types: Vec<DataTypeImpl> =
-            build_keys.iter().map(|expr| expr.data_type()).collect();
+        let params = JoinHashTableParams {
+            join_type,
+            build_keys: build_keys.to_vec(),
+            probe_keys: probe_keys.to_vec(),
+            other_predicate: other_predicate.cloned(),
+            build_schema,
+            marker_index,
+            spillable: true,
+        };
+        Self::create_with_params(ctx, params, runtime_filters)
+    }
+
+    fn create_with_params(
+        ctx: Arc<QueryContext>,
+        params: JoinHashTableParams,
+        runtime_filters: Vec<(usize, Arc<RuntimeFilter>)>,
+    ) -> Result<Arc<JoinHashTable>> {
+        let hash_key_types: Vec<DataTypeImpl> = params
+            .build_keys
+            .iter()
+            .map(|expr| expr.data_type())
+            .collect();
         let method = DataBlock::choose_hash_method_with_types(&hash_key_types)?;
         let hash_join_desc = HashJoinDesc {
-            build_keys: build_keys
+            build_keys: params
+                .build_keys
                 .iter()
                 .map(Evaluator::eval_physical_scalar)
                 .collect::<Result<_>>()?,
-            probe_keys: probe_keys
+            probe_keys: params
+                .probe_keys
                 .iter()
                 .map(Evaluator::eval_physical_scalar)
                 .collect::<Result<_>>()?,
-            join_type,
-            other_predicate: other_predicate
+            join_type: params.join_type.clone(),
+            other_predicate: params
+                .other_predicate
+                .as_ref()
                 .map(Evaluator::eval_physical_scalar)
                 .transpose()?,
             marker: RwLock::new(vec![]),
-            marker_index,
+            marker_index: params.marker_index,
         };
-        Ok(match method {
+        let hash_table = match method {
             HashMethodKind::SingleString(_) | HashMethodKind::Serializer(_) => {
-                Arc::new(JoinHashTable::try_create(
-                    ctx,
-                    HashTable::SerializerHashTable(SerializerHashTable {
-                        hash_table: HashMap::<KeysRef, Vec<RowPtr>>::create(),
-                        hash_method: HashMethodSerializer::default(),
-                    }),
-                    build_schema,
-                    hash_join_desc,
-                )?)
+                HashTable::SerializerHashTable(SerializerHashTable {
+                    hash_table: HashMap::<KeysRef, Vec<RowPtr>>::create(),
+                    hash_method: HashMethodSerializer::default(),
+                })
             }
-            HashMethodKind::KeysU8(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU8HashTable(KeyU8HashTable {
-                    hash_table: HashMap::<u8, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU16(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU16HashTable(KeyU16HashTable {
-                    hash_table: HashMap::<u16, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU32(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU32HashTable(KeyU32HashTable {
-                    hash_table: HashMap::<u32, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU64(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU64HashTable(KeyU64HashTable {
-                    hash_table: HashMap::<u64, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU128(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+            HashMethodKind::KeysU8(hash_method) => HashTable::KeyU8HashTable(KeyU8HashTable {
+                hash_table: HashMap::<u8, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU16(hash_method) => HashTable::KeyU16HashTable(KeyU16HashTable {
+                hash_table: HashMap::<u16, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU32(hash_method) => HashTable::KeyU32HashTable(KeyU32HashTable {
+                hash_table: HashMap::<u32, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU64(hash_method) => HashTable::KeyU64HashTable(KeyU64HashTable {
+                hash_table: HashMap::<u64, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU128(hash_method) => {
                 HashTable::KeyU128HashTable(KeyU128HashTable {
                     hash_table: HashMap::<u128, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU256(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+                })
+            }
+            HashMethodKind::KeysU256(hash_method) => {
                 HashTable::KeyU256HashTable(KeyU256HashTable {
                     hash_table: HashMap::<U256, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU512(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+                })
+            }
+            HashMethodKind::KeysU512(hash_method) => {
                 HashTable::KeyU512HashTable(KeyU512HashTable {
                     hash_table: HashMap::<U512, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-        })
+                })
+            }
+        };
+        Ok(Arc::new(JoinHashTable::try_create(
+            ctx,
+            hash_table,
+            params.build_schema.clone(),
+            hash_join_desc,
+            params,
+            runtime_filters,
+        )?))
     }
 
-    pub fn try_create(
+    fn try_create(
         ctx: Arc<QueryContext>,
         hash_table: HashTable,
         mut build_data_schema: DataSchemaRef,
         hash_join_desc: HashJoinDesc,
+        params: JoinHashTableParams,
+        runtime_filters: Vec<(usize, Arc<RuntimeFilter>)>,
     ) -> Result<Self> {
+        let spill_state = JoinSpillState {
+            reservation: MemoryReservation::create(ctx.get_memory_budget()?),
+            num_partitions: ctx.get_settings().get_spill_partitions()?.max(1) as usize,
+            build_spiller: None,
+            probe_spiller: None,
+            probe_count: 0,
+        };
+        let spill_enabled = params.spillable
+            && spill_state.reservation.can_spill()
+            && matches!(
+                hash_join_desc.join_type,
+                JoinType::Inner | JoinType::Semi | JoinType::Anti | JoinType::Left
+            );
+
         if hash_join_desc.join_type == JoinType::Left {
             let mut nullable_field = Vec::with_capacity(build_data_schema.fields().len());
             for field in build_data_schema.fields().iter() {
@@ -260,6 +316,11 @@ impl JoinHashTable {
             hash_join_desc,
             ctx,
             hash_table: RwLock::new(hash_table),
+            params,
+            spill_enabled,
+            is_spilled: AtomicBool::new(false),
+            spill_state: Mutex::new(spill_state),
+            runtime_filters,
         })
     }
 
@@ -304,6 +365,62 @@ impl JoinHashTable {
         Ok(replicated_probe_block)
     }
 
+    fn eval_keys(&self, keys: &[EvalNode<ColumnID>], block: &DataBlock) -> Result<Vec<ColumnRef>> {
+        let func_ctx = self.ctx.try_get_function_context()?;
+        keys.iter()
+            .map(|expr| Ok(expr.eval(&func_ctx, block)?.vector().clone()))
+            .collect::<Result<Vec<ColumnRef>>>()
+    }
+
+    fn push_build_block(&self, input: DataBlock, build_cols: Vec<ColumnRef>) -> Result<()> {
+        match &*self.hash_table.read() {
+            HashTable::SerializerHashTable(table) => {
+                let mut build_cols_ref = Vec::with_capacity(build_cols.len());
+                for build_col in build_cols.iter() {
+                    build_cols_ref.push(build_col);
+                }
+                let build_keys = table
+                    .hash_method
+                    .build_keys(&build_cols_ref, input.num_rows())?;
+                // Save build_keys in row_space to avoid memory leak
+                self.row_space.push_keys(input, build_keys)
+            }
+            _ => self.row_space.push_cols(input, build_cols),
+        }
+    }
+
+    fn spill_build_block(&self, state: &mut JoinSpillState, block: &DataBlock) -> Result<()> {
+        let keys = self.eval_keys(&self.hash_join_desc.build_keys, block)?;
+        let blocks = scatter_by_hash(block, &keys, state.num_partitions)?;
+        if state.build_spiller.is_none() {
+            let schema = block.schema().clone();
+            state.build_spiller = Some(Spiller::create(&self.ctx, "join-build", schema)?);
+        }
+
+        state
+            .build_spiller
+            .as_mut()
+            .unwrap()
+            .spill_scattered(&blocks)
+    }
+
+    fn spill_probe_block(&self, block: &DataBlock) -> Result<Vec<DataBlock>> {
+        let keys = self.eval_keys(&self.hash_join_desc.probe_keys, block)?;
+        let mut state = self.spill_state.lock().unwrap();
+        let blocks = scatter_by_hash(block, &keys, state.num_partitions)?;
+        if state.probe_spiller.is_none() {
+            let schema = block.schema().clone();
+            state.probe_spiller = Some(Spiller::create(&self.ctx, "join-probe", schema)?);
+        }
+
+        state
+            .probe_spiller
+            .as_mut()
+            .unwrap()
+            .spill_scattered(&blocks)?;
+        Ok(vec![])
+    }
+
     fn probe_cross_join(
         &self,
         input: &DataBlock,
@@ -396,33 +513,42 @@ impl JoinHashTable {
 
 impl HashJoinState for JoinHashTable {
     fn build(&self, input: DataBlock) -> Result<()> {
-        let func_ctx = self.ctx.try_get_function_context()?;
-        let build_cols = self
-            .hash_join_desc
-            .build_keys
-            .iter()
-            .map(|expr| Ok(expr.eval(&func_ctx, &input)?.vector().clone()))
-            .collect::<Result<Vec<ColumnRef>>>()?;
+        let build_cols = self.eval_keys(&self.hash_join_desc.build_keys, &input)?;
+        for (key_index, runtime_filter) in self.runtime_filters.iter() {
+            runtime_filter.collect(&build_cols[*key_index])?;
+        }
 
-        match &*self.hash_table.read() {
-            HashTable::SerializerHashTable(table) => {
-                let mut build_cols_ref = Vec::with_capacity(build_cols.len());
-                for build_col in build_cols.iter() {
-                    build_cols_ref.push(build_col);
-                }
-                let build_keys = table
-                    .hash_method
-                    .build_keys(&build_cols_ref, input.num_rows())?;
-                // Save build_keys in row_space to avoid memory leak
-                self.row_space.push_keys(input, build_keys)
-            }
-            _ => self.row_space.push_cols(input, build_cols),
+        if !self.spill_enabled {
+            return self.push_build_block(input, build_cols);
         }
+
+        let mut state = self.spill_state.lock().unwrap();
+        if self.is_spilled.load(Ordering::Relaxed) {
+            return self.spill_build_block(&mut state, &input);
+        }
+
+        let size = state.reservation.size() + input.memory_size();
+        if state.reservation.try_resize(size) {
+            return self.push_build_block(input, build_cols);
+        }
+
+        // The build side exceeds the memory budget, partition all of it to disk.
+        let chunks = std::mem::take(&mut *self.row_space.chunks.write().unwrap());
+        for chunk in chunks.iter() {
+            self.spill_build_block(&mut state, &chunk.data_block)?;
+        }
+        self.spill_build_block(&mut state, &input)?;
+        state.reservation.free();
+        self.is_spilled.store(true, Ordering::Relaxed);
+        Ok(())
     }
 
     fn probe(&self, input: &DataBlock, probe_state: &mut ProbeState) -> Result<Vec<DataBlock>> {
         match self.hash_join_desc.join_type {
             JoinType::Inner | JoinType::Semi | JoinType::Anti | JoinType::Left | JoinType::Mark => {
+                if self.is_spilled.load(Ordering::Relaxed) {
+                    return self.spill_probe_block(input);
+                }
                 self.probe_join(input, probe_state)
             }
             JoinType::Cross => self.probe_cross_join(input, probe_state),
@@ -441,6 +567,9 @@ impl HashJoinState for JoinHashTable {
         *count -= 1;
         if *count == 0 {
             self.finish()?;
+            for (_, runtime_filter) in self.runtime_filters.iter() {
+                runtime_filter.publish();
+            }
             let mut is_finished = self.is_finished.lock().unwrap();
             *is_finished = true;
             Ok(())
@@ -453,6 +582,64 @@ impl HashJoinState for JoinHashTable {
         Ok(*self.is_finished.lock().unwrap())
     }
 
+    fn attach_probe(&self) -> Result<()> {
+        let mut state = self.spill_state.lock().unwrap();
+        state.probe_count += 1;
+        Ok(())
+    }
+
+    fn detach_probe(&self) -> Result<bool> {
+        let mut state = self.spill_state.lock().unwrap();
+        state.probe_count -= 1;
+        Ok(state.probe_count == 0)
+    }
+
+    fn probe_spilled(&self, probe_state: &mut ProbeState) -> Result<Option<Vec<DataBlock>>> {
+        if !self.is_spilled.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        let (build_blocks, probe_blocks) = {
+            let mut state = self.spill_state.lock().unwrap();
+            let probe_spiller = match state.probe_spiller.as_mut() {
+                None => return Ok(None),
+                Some(probe_spiller) => probe_spiller,
+            };
+            let partition = match probe_spiller.partitions().first() {
+                None => return Ok(None),
+                Some(partition) => *partition,
+            };
+
+            let probe_blocks = probe_spiller.read(partition)?;
+            probe_spiller.remove(partition)?;
+            let build_blocks = match state.build_spiller.as_mut() {
+                None => vec![],
+                Some(build_spiller) => {
+                    let build_blocks = build_spiller.read(partition)?;
+                    build_spiller.remove(partition)?;
+                    build_blocks
+                }
+            };
+            (build_blocks, probe_blocks)
+        };
+
+        // Join the partition with an in-memory hash table.
+        let mut params = self.params.clone();
+        params.spillable = false;
+        let partition_table = Self::create_with_params(self.ctx.clone(), params, vec![])?;
+        for block in build_blocks {
+            partition_table.build(block)?;
+        }
+        partition_table.finish()?;
+
+        let mut results = vec![];
+        for block in probe_blocks.iter() {
+            probe_state.clear();
+            results.extend(partition_table.probe(block, probe_state)?);
+        }
+        Ok(Some(results))
+    }
+
     fn finish(&self) -> Result<()> {
         let chunks = self.row_space.chunks.read().unwrap();
         let mut marker = self.hash_join_desc.marker.write();
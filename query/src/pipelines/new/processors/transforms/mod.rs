@@ -19,10 +19,12 @@ mod transform_addon;
 mod transform_aggregator;
 mod transform_apply;
 mod transform_block_compact;
+mod transform_buffered_join;
 mod transform_cast_schema;
 mod transform_compact;
 mod transform_create_sets;
 mod transform_dummy;
+mod transform_expand_grouping_sets;
 mod transform_expression;
 mod transform_expression_v2;
 mod transform_filter;
@@ -31,10 +33,14 @@ mod transform_hash_join;
 mod transform_limit;
 mod transform_limit_by;
 mod transform_max_one_row;
+mod transform_merge_join;
+mod transform_nested_loop_join;
 mod transform_project;
 mod transform_rename;
 mod transform_sort_merge;
 mod transform_sort_partial;
+mod transform_sort_spill;
+mod transform_window_func;
 
 pub use aggregator::AggregatorParams;
 pub use aggregator::AggregatorTransformParams;
@@ -54,12 +60,15 @@ pub use transform_aggregator::TransformAggregator;
 pub use transform_apply::TransformApply;
 pub use transform_block_compact::BlockCompactor;
 pub use transform_block_compact::TransformBlockCompact;
+pub use transform_buffered_join::JoinBuffer;
+pub use transform_buffered_join::SinkJoinBuffer;
 pub use transform_cast_schema::TransformCastSchema;
 pub use transform_compact::Compactor;
 pub use transform_compact::TransformCompact;
 pub use transform_create_sets::SubQueriesPuller;
 pub use transform_create_sets::TransformCreateSets;
 pub use transform_dummy::TransformDummy;
+pub use transform_expand_grouping_sets::TransformExpandGroupingSets;
 pub use transform_expression::ExpressionTransform;
 pub use transform_expression::ProjectionTransform;
 pub use transform_expression_v2::ExpressionTransformV2;
@@ -71,8 +80,13 @@ pub use transform_hash_join::TransformHashJoinProbe;
 pub use transform_limit::TransformLimit;
 pub use transform_limit_by::TransformLimitBy;
 pub use transform_max_one_row::TransformMax1Row;
+pub use transform_merge_join::TransformMergeJoin;
+pub use transform_nested_loop_join::TransformNestedLoopJoin;
 pub use transform_project::TransformProject;
 pub use transform_rename::TransformRename;
 pub use transform_sort_merge::SortMergeCompactor;
 pub use transform_sort_merge::TransformSortMerge;
 pub use transform_sort_partial::TransformSortPartial;
+pub use transform_sort_spill::TransformSortSpill;
+pub use transform_window_func::TransformWindowFunc;
+pub use transform_window_func::WindowFuncCompactor;
@@ -167,94 +167,94 @@ impl TransformAggregator {
                 HashMethodKind::KeysU8(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU8PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU8PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU16(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU16PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU16PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU32(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU32PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU32PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU64(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU64PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU64PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU128(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU128PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU128PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU256(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU256PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU256PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU512(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU512PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU512PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::SingleString(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SingleStringPartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    SingleStringPartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::Serializer(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SerializerPartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    SerializerPartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
             },
             false => match transform_params.method {
                 HashMethodKind::KeysU8(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU8PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU8PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU16(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU16PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU16PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU32(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU32PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU32PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU64(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU64PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU64PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU128(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU128PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU128PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU256(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU256PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU256PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU512(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU512PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU512PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::SingleString(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SingleStringPartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    SingleStringPartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::Serializer(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SerializerPartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    SerializerPartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
             },
         }
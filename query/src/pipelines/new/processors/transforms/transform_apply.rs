@@ -72,6 +72,38 @@ impl OuterRefRewriter {
                 join_type: join_type.clone(),
                 marker_index: *marker_index,
             }),
+            PhysicalPlan::MergeJoin {
+                left,
+                right,
+                left_keys,
+                right_keys,
+                other_conditions,
+                join_type,
+            } => Ok(PhysicalPlan::MergeJoin {
+                left: Box::new(self.rewrite_physical_plan(left)?),
+                right: Box::new(self.rewrite_physical_plan(right)?),
+                left_keys: left_keys.clone(),
+                right_keys: right_keys.clone(),
+                other_conditions: other_conditions
+                    .iter()
+                    .map(|cond| self.rewrite_physical_scalar(cond))
+                    .collect::<Result<_>>()?,
+                join_type: join_type.clone(),
+            }),
+            PhysicalPlan::NestedLoopJoin {
+                left,
+                right,
+                conditions,
+                join_type,
+            } => Ok(PhysicalPlan::NestedLoopJoin {
+                left: Box::new(self.rewrite_physical_plan(left)?),
+                right: Box::new(self.rewrite_physical_plan(right)?),
+                conditions: conditions
+                    .iter()
+                    .map(|cond| self.rewrite_physical_scalar(cond))
+                    .collect::<Result<_>>()?,
+                join_type: join_type.clone(),
+            }),
             PhysicalPlan::Limit {
                 input,
                 limit,
@@ -86,6 +118,10 @@ impl OuterRefRewriter {
 
                 order_by: order_by.clone(),
             }),
+            PhysicalPlan::Window { input, window_func } => Ok(PhysicalPlan::Window {
+                input: Box::new(self.rewrite_physical_plan(input)?),
+                window_func: window_func.clone(),
+            }),
             PhysicalPlan::Project { input, projections } => Ok(PhysicalPlan::Project {
                 input: Box::new(self.rewrite_physical_plan(input)?),
                 projections: projections.clone(),
@@ -105,6 +141,17 @@ impl OuterRefRewriter {
                     .map(|(scalar, id)| Ok((self.rewrite_physical_scalar(scalar)?, id.clone())))
                     .collect::<Result<_>>()?,
             }),
+            PhysicalPlan::AggregateExpand {
+                input,
+                group_by,
+                grouping_id,
+                grouping_sets,
+            } => Ok(PhysicalPlan::AggregateExpand {
+                input: Box::new(self.rewrite_physical_plan(input)?),
+                group_by: group_by.clone(),
+                grouping_id: grouping_id.clone(),
+                grouping_sets: grouping_sets.clone(),
+            }),
             PhysicalPlan::AggregatePartial {
                 input,
                 group_by,
@@ -134,6 +181,15 @@ impl OuterRefRewriter {
                 subquery: Box::new(self.rewrite_physical_plan(subquery)?),
                 correlated_columns: correlated_columns.clone(),
             }),
+            PhysicalPlan::Exchange { input, kind, keys } => Ok(PhysicalPlan::Exchange {
+                input: Box::new(self.rewrite_physical_plan(input)?),
+                kind: *kind,
+                keys: keys
+                    .iter()
+                    .map(|key| self.rewrite_physical_scalar(key))
+                    .collect::<Result<_>>()?,
+            }),
+            PhysicalPlan::ExchangeSource { .. } => Ok(plan.clone()),
         }
     }
 
@@ -0,0 +1,234 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::Processor;
+use crate::pipelines::new::processors::Sink;
+
+/// All the rows from the right side of a join, collected by `SinkJoinBuffer`s.
+/// The rows are available after every sink is detached.
+pub struct JoinBuffer {
+    ref_count: Mutex<usize>,
+    blocks: Mutex<Vec<DataBlock>>,
+    /// The buffered rows concatenated into a single block, `None` if there is no row
+    buffered_block: Mutex<Option<DataBlock>>,
+    is_finished: Mutex<bool>,
+}
+
+impl JoinBuffer {
+    pub fn create() -> Arc<JoinBuffer> {
+        Arc::new(JoinBuffer {
+            ref_count: Mutex::new(0),
+            blocks: Mutex::new(vec![]),
+            buffered_block: Mutex::new(None),
+            is_finished: Mutex::new(false),
+        })
+    }
+
+    pub fn attach(&self) {
+        let mut count = self.ref_count.lock().unwrap();
+        *count += 1;
+    }
+
+    pub fn detach(&self) -> Result<()> {
+        let mut count = self.ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            let blocks = std::mem::take(&mut *self.blocks.lock().unwrap());
+            if !blocks.is_empty() {
+                let block = DataBlock::concat_blocks(&blocks)?;
+                *self.buffered_block.lock().unwrap() = Some(block);
+            }
+            *self.is_finished.lock().unwrap() = true;
+        }
+        Ok(())
+    }
+
+    pub fn append(&self, block: DataBlock) {
+        if block.num_rows() != 0 {
+            self.blocks.lock().unwrap().push(block);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        *self.is_finished.lock().unwrap()
+    }
+
+    pub fn buffered_block(&self) -> Option<DataBlock> {
+        self.buffered_block.lock().unwrap().clone()
+    }
+}
+
+pub struct SinkJoinBuffer {
+    buffer: Arc<JoinBuffer>,
+}
+
+impl SinkJoinBuffer {
+    pub fn create(buffer: Arc<JoinBuffer>) -> Self {
+        buffer.attach();
+        SinkJoinBuffer { buffer }
+    }
+}
+
+impl Sink for SinkJoinBuffer {
+    const NAME: &'static str = "JoinBuffer";
+
+    fn on_finish(&mut self) -> Result<()> {
+        self.buffer.detach()
+    }
+
+    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        self.buffer.append(data_block);
+        Ok(())
+    }
+}
+
+/// A join algorithm which joins the blocks of left side with the buffered rows
+/// of right side, e.g. nested-loop join and sort-merge join.
+pub trait BufferedJoin: Send {
+    const NAME: &'static str;
+
+    /// Called once before joining any block, with all the rows of right side.
+    fn prepare(&mut self, right: Option<DataBlock>) -> Result<()>;
+
+    fn join(&mut self, left: &DataBlock) -> Result<Vec<DataBlock>>;
+}
+
+enum BufferedJoinStep {
+    /// Wait for the rows of right side to be buffered
+    Buffer,
+    Prepare,
+    Join,
+    Finished,
+}
+
+pub struct TransformBufferedJoin<J: BufferedJoin + 'static> {
+    join: J,
+    buffer: Arc<JoinBuffer>,
+    step: BufferedJoinStep,
+
+    input_port: Arc<InputPort>,
+    output_port: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data_blocks: VecDeque<DataBlock>,
+}
+
+impl<J: BufferedJoin + 'static> TransformBufferedJoin<J> {
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        buffer: Arc<JoinBuffer>,
+        join: J,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Box::new(TransformBufferedJoin {
+            join,
+            buffer,
+            step: BufferedJoinStep::Buffer,
+            input_port,
+            output_port,
+            input_data: None,
+            output_data_blocks: VecDeque::new(),
+        }))
+    }
+}
+
+impl<J: BufferedJoin + 'static> Processor for TransformBufferedJoin<J> {
+    fn name(&self) -> &'static str {
+        J::NAME
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        match self.step {
+            BufferedJoinStep::Buffer => {
+                if self.buffer.is_finished() {
+                    self.step = BufferedJoinStep::Prepare;
+                    Ok(Event::Sync)
+                } else {
+                    // Idle till the right side is buffered
+                    Ok(Event::NeedData)
+                }
+            }
+            BufferedJoinStep::Prepare => Ok(Event::Sync),
+            BufferedJoinStep::Join => {
+                if self.output_port.is_finished() {
+                    self.input_port.finish();
+                    self.step = BufferedJoinStep::Finished;
+                    return Ok(Event::Finished);
+                }
+
+                if !self.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                if let Some(block) = self.output_data_blocks.pop_front() {
+                    self.output_port.push_data(Ok(block));
+                    return Ok(Event::NeedConsume);
+                }
+
+                if self.input_data.is_some() {
+                    return Ok(Event::Sync);
+                }
+
+                if self.input_port.is_finished() {
+                    self.output_port.finish();
+                    self.step = BufferedJoinStep::Finished;
+                    return Ok(Event::Finished);
+                }
+
+                if let Some(data) = self.input_port.pull_data() {
+                    self.input_data = Some(data?);
+                    return Ok(Event::Sync);
+                }
+
+                self.input_port.set_need_data();
+                Ok(Event::NeedData)
+            }
+            BufferedJoinStep::Finished => Ok(Event::Finished),
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.step {
+            BufferedJoinStep::Prepare => {
+                self.join.prepare(self.buffer.buffered_block())?;
+                self.step = BufferedJoinStep::Join;
+                Ok(())
+            }
+            BufferedJoinStep::Join => {
+                if let Some(data) = self.input_data.take() {
+                    let blocks = self.join.join(&data)?;
+                    self.output_data_blocks
+                        .extend(blocks.into_iter().filter(|block| block.num_rows() != 0));
+                }
+                Ok(())
+            }
+            BufferedJoinStep::Buffer | BufferedJoinStep::Finished => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform::Transform;
+use crate::pipelines::new::processors::transforms::transform::Transformer;
+
+/// Duplicate the input rows for every grouping set. The group items absent from
+/// a grouping set are replaced with NULL, and the grouping id of the set is
+/// appended as the last column.
+pub struct TransformExpandGroupingSets {
+    output_schema: DataSchemaRef,
+    // Positions of group items in input block
+    group_by: Vec<usize>,
+    // For each grouping set, whether the group items are present and its grouping id
+    grouping_sets: Vec<(Vec<bool>, u32)>,
+}
+
+impl TransformExpandGroupingSets {
+    pub fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        output_schema: DataSchemaRef,
+        group_by: Vec<usize>,
+        grouping_sets: Vec<Vec<bool>>,
+    ) -> ProcessorPtr {
+        let grouping_sets = grouping_sets
+            .into_iter()
+            .map(|present| {
+                // The bit of absent group item is set, the 1st item is the highest bit
+                let grouping_id = present
+                    .iter()
+                    .fold(0u32, |id, present| (id << 1) | u32::from(!*present));
+                (present, grouping_id)
+            })
+            .collect();
+        Transformer::create(input, output, Self {
+            output_schema,
+            group_by,
+            grouping_sets,
+        })
+    }
+}
+
+impl Transform for TransformExpandGroupingSets {
+    const NAME: &'static str = "ExpandGroupingSets";
+
+    fn transform(&mut self, data: DataBlock) -> Result<DataBlock> {
+        let num_rows = data.num_rows();
+        let mut blocks = Vec::with_capacity(self.grouping_sets.len());
+        for (present, grouping_id) in self.grouping_sets.iter() {
+            let mut columns: Vec<ColumnRef> = data.columns().to_vec();
+            for (position, present) in self.group_by.iter().zip(present.iter()) {
+                columns[*position] = if *present {
+                    NullableColumn::wrap_inner(columns[*position].convert_full_column(), None)
+                } else {
+                    let data_type = self.output_schema.field(*position).data_type();
+                    data_type
+                        .create_constant_column(&DataValue::Null, num_rows)?
+                        .convert_full_column()
+                };
+            }
+            columns.push(Series::from_data(vec![*grouping_id; num_rows]));
+            blocks.push(DataBlock::create(self.output_schema.clone(), columns));
+        }
+
+        DataBlock::concat_blocks(&blocks)
+    }
+}
@@ -55,6 +55,8 @@ impl Sink for SinkBuildHashTable {
 enum HashJoinStep {
     Build,
     Probe,
+    /// Probe the spilled partitions after all the probe processors are detached
+    ProbeSpilled,
     Finished,
 }
 
@@ -67,6 +69,7 @@ pub struct TransformHashJoinProbe {
     step: HashJoinStep,
     join_state: Arc<dyn HashJoinState>,
     probe_state: ProbeState,
+    spilled_finished: bool,
 }
 
 impl TransformHashJoinProbe {
@@ -76,9 +79,10 @@ impl TransformHashJoinProbe {
         output_port: Arc<OutputPort>,
         join_state: Arc<dyn HashJoinState>,
         _output_schema: DataSchemaRef,
-    ) -> ProcessorPtr {
+    ) -> Result<ProcessorPtr> {
+        join_state.attach_probe()?;
         let default_block_size = ctx.get_settings().get_max_block_size().unwrap_or(102400);
-        ProcessorPtr::create(Box::new(TransformHashJoinProbe {
+        Ok(ProcessorPtr::create(Box::new(TransformHashJoinProbe {
             input_data: None,
             output_data_blocks: vec![],
             input_port,
@@ -86,7 +90,8 @@ impl TransformHashJoinProbe {
             step: HashJoinStep::Build,
             join_state,
             probe_state: ProbeState::with_capacity(default_block_size as usize),
-        }))
+            spilled_finished: false,
+        })))
     }
 
     fn probe(&mut self, block: &DataBlock) -> Result<()> {
@@ -121,6 +126,8 @@ impl Processor for TransformHashJoinProbe {
             HashJoinStep::Probe => {
                 if self.output_port.is_finished() {
                     self.input_port.finish();
+                    self.join_state.detach_probe()?;
+                    self.step = HashJoinStep::Finished;
                     return Ok(Event::Finished);
                 }
 
@@ -139,6 +146,11 @@ impl Processor for TransformHashJoinProbe {
                 }
 
                 if self.input_port.is_finished() {
+                    if self.join_state.detach_probe()? {
+                        self.step = HashJoinStep::ProbeSpilled;
+                        return Ok(Event::Sync);
+                    }
+
                     self.output_port.finish();
                     self.step = HashJoinStep::Finished;
                     return Ok(Event::Finished);
@@ -152,6 +164,30 @@ impl Processor for TransformHashJoinProbe {
                 self.input_port.set_need_data();
                 Ok(Event::NeedData)
             }
+            HashJoinStep::ProbeSpilled => {
+                if self.output_port.is_finished() {
+                    self.step = HashJoinStep::Finished;
+                    return Ok(Event::Finished);
+                }
+
+                if !self.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                if !self.output_data_blocks.is_empty() {
+                    self.output_port
+                        .push_data(Ok(self.output_data_blocks.remove(0)));
+                    return Ok(Event::NeedConsume);
+                }
+
+                if self.spilled_finished {
+                    self.output_port.finish();
+                    self.step = HashJoinStep::Finished;
+                    return Ok(Event::Finished);
+                }
+
+                Ok(Event::Sync)
+            }
             HashJoinStep::Finished => Ok(Event::Finished),
         }
     }
@@ -166,6 +202,13 @@ impl Processor for TransformHashJoinProbe {
                 }
                 Ok(())
             }
+            HashJoinStep::ProbeSpilled => {
+                match self.join_state.probe_spilled(&mut self.probe_state)? {
+                    Some(mut blocks) => self.output_data_blocks.append(&mut blocks),
+                    None => self.spilled_finished = true,
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -0,0 +1,157 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+
+use crate::common::EvalNode;
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform_buffered_join::BufferedJoin;
+use crate::pipelines::new::processors::transforms::transform_buffered_join::JoinBuffer;
+use crate::pipelines::new::processors::transforms::transform_buffered_join::TransformBufferedJoin;
+use crate::sql::exec::ColumnID;
+
+/// Sort-merge inner join. Both sides are sorted in ascending order by the join keys,
+/// so the right rows matching a left row are always found after the ones matching
+/// the previous left rows. The rows with NULL keys never match.
+pub struct MergeJoin {
+    left_keys: Vec<EvalNode<ColumnID>>,
+    right_keys: Vec<EvalNode<ColumnID>>,
+    other_predicate: Option<EvalNode<ColumnID>>,
+    func_ctx: FunctionContext,
+
+    right: Option<DataBlock>,
+    right_key_columns: Vec<ColumnRef>,
+    /// The first right row whose key is not less than the key of last left row
+    cursor: usize,
+}
+
+impl MergeJoin {
+    fn eval_keys(&self, keys: &[EvalNode<ColumnID>], block: &DataBlock) -> Result<Vec<ColumnRef>> {
+        keys.iter()
+            .map(|key| Ok(key.eval(&self.func_ctx, block)?.vector().clone()))
+            .collect()
+    }
+
+    /// Key of the row at `index`, `None` if any of the key is NULL.
+    fn row_key(columns: &[ColumnRef], index: usize) -> Option<Vec<DataValue>> {
+        columns
+            .iter()
+            .map(|column| match column.get(index) {
+                DataValue::Null => None,
+                value => Some(value),
+            })
+            .collect()
+    }
+}
+
+impl BufferedJoin for MergeJoin {
+    const NAME: &'static str = "TransformMergeJoin";
+
+    fn prepare(&mut self, right: Option<DataBlock>) -> Result<()> {
+        if let Some(right) = &right {
+            self.right_key_columns = self.eval_keys(&self.right_keys, right)?;
+        }
+        self.right = right;
+        Ok(())
+    }
+
+    fn join(&mut self, left: &DataBlock) -> Result<Vec<DataBlock>> {
+        let right = match &self.right {
+            Some(right) => right,
+            None => return Ok(vec![]),
+        };
+        let right_rows = right.num_rows();
+        let left_key_columns = self.eval_keys(&self.left_keys, left)?;
+
+        let mut left_indices = vec![];
+        let mut right_indices = vec![];
+        for left_index in 0..left.num_rows() {
+            let left_key = match Self::row_key(&left_key_columns, left_index) {
+                Some(left_key) => left_key,
+                None => continue,
+            };
+
+            // Skip the right rows with smaller or NULL keys
+            while self.cursor < right_rows {
+                match Self::row_key(&self.right_key_columns, self.cursor) {
+                    Some(right_key) if right_key >= left_key => break,
+                    _ => self.cursor += 1,
+                }
+            }
+
+            let mut right_index = self.cursor;
+            while right_index < right_rows
+                && Self::row_key(&self.right_key_columns, right_index).as_ref() == Some(&left_key)
+            {
+                left_indices.push(left_index as u32);
+                right_indices.push(right_index as u32);
+                right_index += 1;
+            }
+        }
+
+        if left_indices.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut block = DataBlock::block_take_by_indices(left, &left_indices)?;
+        let right_block = DataBlock::block_take_by_indices(right, &right_indices)?;
+        for (column, field) in right_block
+            .columns()
+            .iter()
+            .zip(right_block.schema().fields().iter())
+        {
+            block = block.add_column(column.clone(), field.clone())?;
+        }
+
+        match &self.other_predicate {
+            Some(other_predicate) => {
+                let filter = other_predicate.eval(&self.func_ctx, &block)?;
+                Ok(vec![DataBlock::filter_block(block, filter.vector())?])
+            }
+            None => Ok(vec![block]),
+        }
+    }
+}
+
+pub struct TransformMergeJoin;
+
+impl TransformMergeJoin {
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        buffer: Arc<JoinBuffer>,
+        left_keys: Vec<EvalNode<ColumnID>>,
+        right_keys: Vec<EvalNode<ColumnID>>,
+        other_predicate: Option<EvalNode<ColumnID>>,
+        func_ctx: FunctionContext,
+    ) -> ProcessorPtr {
+        TransformBufferedJoin::create(input_port, output_port, buffer, MergeJoin {
+            left_keys,
+            right_keys,
+            other_predicate,
+            func_ctx,
+            right: None,
+            right_key_columns: vec![],
+            cursor: 0,
+        })
+    }
+}
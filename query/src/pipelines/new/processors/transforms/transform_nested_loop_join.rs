@@ -0,0 +1,105 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+
+use crate::common::EvalNode;
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform_buffered_join::BufferedJoin;
+use crate::pipelines::new::processors::transforms::transform_buffered_join::JoinBuffer;
+use crate::pipelines::new::processors::transforms::transform_buffered_join::TransformBufferedJoin;
+use crate::sql::exec::ColumnID;
+
+/// Block nested-loop inner join. Every block of left side is joined with all the
+/// buffered rows of right side, the cartesian product is generated in chunks of
+/// at most `max_block_size` rows and filtered by the join conditions.
+pub struct NestedLoopJoin {
+    predicate: EvalNode<ColumnID>,
+    func_ctx: FunctionContext,
+    max_block_size: usize,
+    right: Option<DataBlock>,
+}
+
+impl BufferedJoin for NestedLoopJoin {
+    const NAME: &'static str = "TransformNestedLoopJoin";
+
+    fn prepare(&mut self, right: Option<DataBlock>) -> Result<()> {
+        self.right = right;
+        Ok(())
+    }
+
+    fn join(&mut self, left: &DataBlock) -> Result<Vec<DataBlock>> {
+        let right = match &self.right {
+            Some(right) => right,
+            None => return Ok(vec![]),
+        };
+        let right_rows = right.num_rows();
+        let chunk_rows = (self.max_block_size / right_rows).max(1);
+
+        let mut results = vec![];
+        let mut start = 0;
+        while start < left.num_rows() {
+            let end = (start + chunk_rows).min(left.num_rows());
+            let mut left_indices = Vec::with_capacity((end - start) * right_rows);
+            let mut right_indices = Vec::with_capacity((end - start) * right_rows);
+            for left_index in start..end {
+                for right_index in 0..right_rows {
+                    left_indices.push(left_index as u32);
+                    right_indices.push(right_index as u32);
+                }
+            }
+
+            let mut block = DataBlock::block_take_by_indices(left, &left_indices)?;
+            let right_block = DataBlock::block_take_by_indices(right, &right_indices)?;
+            for (column, field) in right_block
+                .columns()
+                .iter()
+                .zip(right_block.schema().fields().iter())
+            {
+                block = block.add_column(column.clone(), field.clone())?;
+            }
+
+            let filter = self.predicate.eval(&self.func_ctx, &block)?;
+            results.push(DataBlock::filter_block(block, filter.vector())?);
+            start = end;
+        }
+        Ok(results)
+    }
+}
+
+pub struct TransformNestedLoopJoin;
+
+impl TransformNestedLoopJoin {
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        buffer: Arc<JoinBuffer>,
+        predicate: EvalNode<ColumnID>,
+        func_ctx: FunctionContext,
+        max_block_size: usize,
+    ) -> ProcessorPtr {
+        TransformBufferedJoin::create(input_port, output_port, buffer, NestedLoopJoin {
+            predicate,
+            func_ctx,
+            max_block_size,
+            right: None,
+        })
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::common::MemoryReservation;
+use crate::common::Spiller;
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::Processor;
+use crate::sessions::QueryContext;
+
+const RUN_COLUMN_NAME: &str = "_spill_sort_run";
+
+/// External merge sort, it takes the sorted blocks from the partial sort.
+///
+/// The blocks are buffered in memory until they exceed the memory budget of the query, then
+/// they are merged into a sorted run and spilled, one file per block. After all the input is
+/// consumed, the runs are merged block by block, so only the head block of each run is kept
+/// in memory.
+pub struct TransformSortSpill {
+    ctx: Arc<QueryContext>,
+    input_port: Arc<InputPort>,
+    output_port: Arc<OutputPort>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    max_block_size: usize,
+
+    input_data: Option<DataBlock>,
+    output_data: VecDeque<DataBlock>,
+    buffered_blocks: Vec<DataBlock>,
+    reservation: MemoryReservation,
+
+    spiller: Option<Spiller>,
+    /// The remaining rows of the loaded block of each run
+    run_heads: Vec<Option<DataBlock>>,
+    /// The next file to load of each run
+    run_next_files: Vec<usize>,
+
+    input_finished: bool,
+    merge_started: bool,
+    merge_finished: bool,
+}
+
+impl TransformSortSpill {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+    ) -> Result<ProcessorPtr> {
+        let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+        let reservation = MemoryReservation::create(ctx.get_memory_budget()?);
+        Ok(ProcessorPtr::create(Box::new(TransformSortSpill {
+            ctx,
+            input_port,
+            output_port,
+            sort_columns_descriptions,
+            max_block_size: max_block_size.max(1),
+            input_data: None,
+            output_data: VecDeque::new(),
+            buffered_blocks: vec![],
+            reservation,
+            spiller: None,
+            run_heads: vec![],
+            run_next_files: vec![],
+            input_finished: false,
+            merge_started: false,
+            merge_finished: false,
+        })))
+    }
+
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        if block.is_empty() {
+            return Ok(());
+        }
+
+        let size = self.reservation.size() + block.memory_size();
+        let overflow = !self.reservation.try_resize(size) && self.reservation.can_spill();
+        self.buffered_blocks.push(block);
+
+        if overflow {
+            self.spill_run()?;
+        }
+        Ok(())
+    }
+
+    /// Merge the buffered blocks into a sorted run and spill it.
+    fn spill_run(&mut self) -> Result<()> {
+        if self.buffered_blocks.is_empty() {
+            return Ok(());
+        }
+
+        let blocks = std::mem::take(&mut self.buffered_blocks);
+        let sorted = DataBlock::merge_sort_blocks(&blocks, &self.sort_columns_descriptions, None)?;
+        drop(blocks);
+        self.reservation.free();
+
+        if self.spiller.is_none() {
+            let schema = sorted.schema().clone();
+            self.spiller = Some(Spiller::create(&self.ctx, "sort", schema)?);
+        }
+
+        let run = self.run_heads.len();
+        let spiller = self.spiller.as_mut().unwrap();
+        for block in DataBlock::split_block_by_size(&sorted, self.max_block_size)? {
+            spiller.spill(run, &[block])?;
+        }
+
+        self.run_heads.push(None);
+        self.run_next_files.push(0);
+        Ok(())
+    }
+
+    fn finish_input(&mut self) -> Result<()> {
+        if self.spiller.is_none() {
+            // Everything fits in memory.
+            if !self.buffered_blocks.is_empty() {
+                let sorted = DataBlock::merge_sort_blocks(
+                    &self.buffered_blocks,
+                    &self.sort_columns_descriptions,
+                    None,
+                )?;
+                self.buffered_blocks.clear();
+                self.output_data.push_back(sorted);
+            }
+            self.merge_finished = true;
+            return Ok(());
+        }
+
+        self.spill_run()?;
+        self.merge_started = true;
+        Ok(())
+    }
+
+    /// Merge the head blocks of the runs, and output the rows which are not greater than the
+    /// smallest last row of the heads, since the rows of the following blocks can't be less
+    /// than them.
+    fn merge_step(&mut self) -> Result<()> {
+        let spiller = self.spiller.as_ref().unwrap();
+        let mut heads = vec![];
+        for run in 0..self.run_heads.len() {
+            let head = match self.run_heads[run].take() {
+                Some(head) if !head.is_empty() => Some(head),
+                _ => {
+                    let next_file = self.run_next_files[run];
+                    if next_file < spiller.num_files(run) {
+                        self.run_next_files[run] += 1;
+                        let blocks = spiller.read_file(run, next_file)?;
+                        Some(DataBlock::concat_blocks(&blocks)?)
+                    } else {
+                        None
+                    }
+                }
+            };
+
+            if let Some(head) = head {
+                let runs = Series::from_data(vec![run as u32; head.num_rows()]);
+                let field = DataField::new(RUN_COLUMN_NAME, u32::to_data_type());
+                heads.push((run, head.add_column(runs, field)?));
+            }
+        }
+
+        if heads.is_empty() {
+            self.merge_finished = true;
+            return Ok(());
+        }
+
+        // Find the run whose last row is the smallest.
+        let last_rows = heads
+            .iter()
+            .map(|(_, head)| DataBlock::slice_block(head, head.num_rows() - 1, 1))
+            .collect::<Vec<_>>();
+        let last_rows = DataBlock::concat_blocks(&last_rows)?;
+        let smallest = DataBlock::sort_block(&last_rows, &self.sort_columns_descriptions, Some(1))?;
+        let smallest_run = Self::run_ids(&smallest)?[0];
+
+        let heads = heads.into_iter().map(|(_, head)| head).collect::<Vec<_>>();
+        let merged = DataBlock::merge_sort_blocks(&heads, &self.sort_columns_descriptions, None)?;
+        let runs = Self::run_ids(&merged)?;
+        let boundary = runs.iter().rposition(|run| *run == smallest_run).unwrap() + 1;
+
+        // Put the remaining rows back to their runs.
+        let mut remaining_indices = vec![vec![]; self.run_heads.len()];
+        for (row, run) in runs.iter().enumerate().skip(boundary) {
+            remaining_indices[*run as usize].push(row as u32);
+        }
+        for (run, indices) in remaining_indices.iter().enumerate() {
+            if !indices.is_empty() {
+                let remaining = DataBlock::block_take_by_indices(&merged, indices)?;
+                self.run_heads[run] = Some(remaining.remove_column(RUN_COLUMN_NAME)?);
+            }
+        }
+
+        let output = DataBlock::slice_block(&merged, 0, boundary).remove_column(RUN_COLUMN_NAME)?;
+        self.output_data.extend(DataBlock::split_block_by_size(
+            &output,
+            self.max_block_size,
+        )?);
+        Ok(())
+    }
+
+    fn run_ids(block: &DataBlock) -> Result<Vec<u32>> {
+        let column = block.try_column_by_name(RUN_COLUMN_NAME)?;
+        let runs: &UInt32Column = Series::check_get(column)?;
+        Ok(runs.values().to_vec())
+    }
+}
+
+impl Processor for TransformSortSpill {
+    fn name(&self) -> &'static str {
+        "SortSpillTransform"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output_port.is_finished() {
+            self.input_port.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output_data.is_empty() {
+            if !self.output_port.can_push() {
+                return Ok(Event::NeedConsume);
+            }
+
+            let block = self.output_data.pop_front().unwrap();
+            self.output_port.push_data(Ok(block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input_finished {
+            if self.merge_finished {
+                self.output_port.finish();
+                return Ok(Event::Finished);
+            }
+            return Ok(Event::Sync);
+        }
+
+        if self.input_port.has_data() {
+            self.input_data = Some(self.input_port.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        if self.input_port.is_finished() {
+            self.input_finished = true;
+            return Ok(Event::Sync);
+        }
+
+        self.input_port.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(block) = self.input_data.take() {
+            return self.consume(block);
+        }
+
+        match self.merge_started {
+            false => self.finish_input(),
+            true => self.merge_step(),
+        }
+    }
+}
@@ -0,0 +1,445 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Range;
+
+use bumpalo::Bump;
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::compute::partition::lexicographical_partition_ranges;
+use common_arrow::arrow::compute::sort::SortColumn;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::Column;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_datavalues::MutableColumn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+use common_functions::window::BuiltInWindowFunction;
+use common_functions::window::WindowFrame;
+use common_functions::window::WindowFrameBound;
+use common_functions::window::WindowFrameUnits;
+
+use super::Compactor;
+use super::TransformCompact;
+use crate::sql::exec::WindowFunctionDesc;
+
+enum WindowFunctionImpl {
+    Aggregate(AggregateFunctionRef),
+    BuiltIn(BuiltInWindowFunction),
+}
+
+/// Evaluate a window function over all the input blocks, and append the result
+/// as a new column. The output rows are sorted by partition keys and order keys.
+pub struct WindowFuncCompactor {
+    func: WindowFunctionImpl,
+    params: Vec<DataValue>,
+    args: Vec<String>,
+    partition_by: Vec<String>,
+    order_by: Vec<SortColumnDescription>,
+    frame: WindowFrame,
+    output_field: DataField,
+}
+
+impl WindowFuncCompactor {
+    pub fn try_create(
+        window_func: &WindowFunctionDesc,
+        input_schema: &DataSchemaRef,
+    ) -> Result<Self> {
+        let func = match BuiltInWindowFunction::try_from_name(&window_func.name) {
+            Some(func) => WindowFunctionImpl::BuiltIn(func),
+            None => {
+                let arg_fields = window_func
+                    .args
+                    .iter()
+                    .map(|arg| Ok(input_schema.field_with_name(arg)?.clone()))
+                    .collect::<Result<Vec<_>>>()?;
+                WindowFunctionImpl::Aggregate(AggregateFunctionFactory::instance().get(
+                    &window_func.name,
+                    window_func.params.clone(),
+                    arg_fields,
+                )?)
+            }
+        };
+
+        Ok(WindowFuncCompactor {
+            func,
+            params: window_func.params.clone(),
+            args: window_func.args.clone(),
+            partition_by: window_func.partition_by.clone(),
+            order_by: window_func
+                .order_by
+                .iter()
+                .map(|desc| SortColumnDescription {
+                    column_name: desc.order_by.clone(),
+                    asc: desc.asc,
+                    nulls_first: desc.nulls_first,
+                })
+                .collect(),
+            frame: window_func.frame,
+            output_field: DataField::new(
+                window_func.column_id.as_str(),
+                window_func.return_type.clone(),
+            ),
+        })
+    }
+
+    /// Split the sorted block into ranges of rows with equal values of `columns`.
+    fn partition_ranges(block: &DataBlock, columns: &[String]) -> Result<Vec<Range<usize>>> {
+        if block.num_rows() == 0 {
+            return Ok(vec![]);
+        }
+        if columns.is_empty() {
+            return Ok(vec![0..block.num_rows()]);
+        }
+
+        let arrays = columns
+            .iter()
+            .map(|name| Ok(block.try_column_by_name(name)?.as_arrow_array()))
+            .collect::<Result<Vec<ArrayRef>>>()?;
+        let sort_columns = arrays
+            .iter()
+            .map(|array| SortColumn {
+                values: array.as_ref(),
+                options: None,
+            })
+            .collect::<Vec<_>>();
+        Ok(lexicographical_partition_ranges(&sort_columns)?.collect())
+    }
+
+    /// Values of the order key in the ascending order of rows, which is required to
+    /// compute RANGE frames with offset. NULLs are represented by `None`.
+    fn order_keys(&self, block: &DataBlock) -> Result<Vec<Option<f64>>> {
+        let has_offset = [self.frame.start_bound, self.frame.end_bound]
+            .iter()
+            .any(|bound| {
+                matches!(
+                    bound,
+                    WindowFrameBound::Preceding(Some(_)) | WindowFrameBound::Following(Some(_))
+                )
+            });
+        if self.frame.units != WindowFrameUnits::Range || !has_offset {
+            return Ok(vec![]);
+        }
+
+        let order_by = self.order_by.first().ok_or_else(|| {
+            ErrorCode::LogicalError("RANGE frame with offset requires an ORDER BY column")
+        })?;
+        let sign = if order_by.asc { 1.0 } else { -1.0 };
+        let column = block.try_column_by_name(&order_by.column_name)?;
+        (0..block.num_rows())
+            .map(|row| match column.get(row) {
+                DataValue::Null => Ok(None),
+                value => Ok(Some(sign * value.as_f64()?)),
+            })
+            .collect()
+    }
+
+    /// Compute the frame of `row` as a range of rows within the `partition`.
+    fn frame_of_row(
+        &self,
+        row: usize,
+        partition: &Range<usize>,
+        peers: &Range<usize>,
+        order_keys: &[Option<f64>],
+    ) -> Range<usize> {
+        let start = match (self.frame.units, self.frame.start_bound) {
+            (_, WindowFrameBound::Preceding(None)) => partition.start,
+            (_, WindowFrameBound::Following(None)) => partition.end,
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => row,
+            (WindowFrameUnits::Rows, WindowFrameBound::Preceding(Some(n))) => {
+                row.saturating_sub(n as usize).max(partition.start)
+            }
+            (WindowFrameUnits::Rows, WindowFrameBound::Following(Some(n))) => {
+                row.saturating_add(n as usize).min(partition.end)
+            }
+            (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => peers.start,
+            (WindowFrameUnits::Range, WindowFrameBound::Preceding(Some(n))) => {
+                self.range_bound(row, partition, peers, order_keys, -(n as f64), true)
+            }
+            (WindowFrameUnits::Range, WindowFrameBound::Following(Some(n))) => {
+                self.range_bound(row, partition, peers, order_keys, n as f64, true)
+            }
+        };
+        let end = match (self.frame.units, self.frame.end_bound) {
+            (_, WindowFrameBound::Preceding(None)) => partition.start,
+            (_, WindowFrameBound::Following(None)) => partition.end,
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => row + 1,
+            (WindowFrameUnits::Rows, WindowFrameBound::Preceding(Some(n))) => {
+                (row + 1).saturating_sub(n as usize).max(partition.start)
+            }
+            (WindowFrameUnits::Rows, WindowFrameBound::Following(Some(n))) => {
+                (row + 1).saturating_add(n as usize).min(partition.end)
+            }
+            (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => peers.end,
+            (WindowFrameUnits::Range, WindowFrameBound::Preceding(Some(n))) => {
+                self.range_bound(row, partition, peers, order_keys, -(n as f64), false)
+            }
+            (WindowFrameUnits::Range, WindowFrameBound::Following(Some(n))) => {
+                self.range_bound(row, partition, peers, order_keys, n as f64, false)
+            }
+        };
+
+        // The frame is empty if the start bound is after the end bound
+        start..end.max(start)
+    }
+
+    /// Find the bound of a RANGE frame with offset: the first row with key not less than
+    /// `key + offset` if `is_start`, otherwise the first row with key greater than `key + offset`.
+    /// For rows with NULL key, the frame contains the NULL peers only.
+    fn range_bound(
+        &self,
+        row: usize,
+        partition: &Range<usize>,
+        peers: &Range<usize>,
+        order_keys: &[Option<f64>],
+        offset: f64,
+        is_start: bool,
+    ) -> usize {
+        let target = match order_keys[row] {
+            Some(key) => key + offset,
+            None if is_start => return peers.start,
+            None => return peers.end,
+        };
+        // NULLs are sorted at either end of the partition
+        let nulls_first = self.order_by[0].nulls_first;
+        partition.start
+            + order_keys[partition.clone()].partition_point(|key| match key {
+                Some(key) if is_start => *key < target,
+                Some(key) => *key <= target,
+                None => nulls_first,
+            })
+    }
+
+    fn evaluate_aggregate(
+        &self,
+        func: &AggregateFunctionRef,
+        partitions: &[(Range<usize>, Vec<Range<usize>>)],
+        args: &[ColumnRef],
+        order_keys: &[Option<f64>],
+        builder: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        let arena = Bump::new();
+        let mut aggregator = FrameAggregator::new(func, &arena);
+        for (partition, peers) in partitions {
+            for peer in peers {
+                for row in peer.clone() {
+                    let frame = self.frame_of_row(row, partition, peer, order_keys);
+                    aggregator.merge_frame(frame, args, builder)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate_builtin(
+        &self,
+        func: BuiltInWindowFunction,
+        partitions: &[(Range<usize>, Vec<Range<usize>>)],
+        args: &[ColumnRef],
+        order_keys: &[Option<f64>],
+        builder: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        let offset = match self.params.first() {
+            Some(param) => param.as_u64()? as usize,
+            None => 0,
+        };
+        for (partition, peers) in partitions {
+            for (peer_number, peer) in peers.iter().enumerate() {
+                for row in peer.clone() {
+                    let value = match func {
+                        BuiltInWindowFunction::RowNumber => {
+                            DataValue::UInt64((row - partition.start + 1) as u64)
+                        }
+                        BuiltInWindowFunction::Rank => {
+                            DataValue::UInt64((peer.start - partition.start + 1) as u64)
+                        }
+                        BuiltInWindowFunction::DenseRank => {
+                            DataValue::UInt64(peer_number as u64 + 1)
+                        }
+                        BuiltInWindowFunction::Lag | BuiltInWindowFunction::Lead => {
+                            let target = if func == BuiltInWindowFunction::Lag {
+                                row.checked_sub(offset)
+                            } else {
+                                row.checked_add(offset)
+                            };
+                            match target {
+                                Some(target) if partition.contains(&target) => args[0].get(target),
+                                _ => args.get(1).map_or(DataValue::Null, |arg| arg.get(row)),
+                            }
+                        }
+                        BuiltInWindowFunction::FirstValue | BuiltInWindowFunction::LastValue => {
+                            let frame = self.frame_of_row(row, partition, peer, order_keys);
+                            if frame.is_empty() {
+                                DataValue::Null
+                            } else if func == BuiltInWindowFunction::FirstValue {
+                                args[0].get(frame.start)
+                            } else {
+                                args[0].get(frame.end - 1)
+                            }
+                        }
+                        BuiltInWindowFunction::Ntile => {
+                            // The first `size % buckets` buckets have one more row than others
+                            let buckets = offset;
+                            let size = partition.len();
+                            let (quotient, remainder) = (size / buckets, size % buckets);
+                            let index = row - partition.start;
+                            let large_rows = remainder * (quotient + 1);
+                            let bucket = if index < large_rows {
+                                index / (quotient + 1)
+                            } else {
+                                remainder + (index - large_rows) / quotient
+                            };
+                            DataValue::UInt64(bucket as u64 + 1)
+                        }
+                    };
+                    builder.append_data_value(value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Compactor for WindowFuncCompactor {
+    fn name() -> &'static str {
+        "WindowFuncTransform"
+    }
+
+    fn compact_final(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>> {
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Sort the rows by partition keys and order keys, so that the rows of a
+        // partition and the peers of a row are adjacent.
+        let block = DataBlock::concat_blocks(blocks)?;
+        let sort_columns_descriptions = self
+            .partition_by
+            .iter()
+            .map(|name| SortColumnDescription {
+                column_name: name.clone(),
+                asc: true,
+                nulls_first: false,
+            })
+            .chain(self.order_by.iter().cloned())
+            .collect::<Vec<_>>();
+        let block = if sort_columns_descriptions.is_empty() {
+            block
+        } else {
+            DataBlock::sort_block(&block, &sort_columns_descriptions, None)?
+        };
+
+        let peer_columns = sort_columns_descriptions
+            .iter()
+            .map(|desc| desc.column_name.clone())
+            .collect::<Vec<_>>();
+        let mut peers = Self::partition_ranges(&block, &peer_columns)?
+            .into_iter()
+            .peekable();
+        let mut partitions = vec![];
+        for partition in Self::partition_ranges(&block, &self.partition_by)? {
+            let mut partition_peers = vec![];
+            while let Some(peer) = peers.next_if(|peer| peer.start < partition.end) {
+                partition_peers.push(peer);
+            }
+            partitions.push((partition, partition_peers));
+        }
+
+        let args = self
+            .args
+            .iter()
+            .map(|name| Ok(block.try_column_by_name(name)?.convert_full_column()))
+            .collect::<Result<Vec<_>>>()?;
+        let order_keys = self.order_keys(&block)?;
+
+        let mut builder = self
+            .output_field
+            .data_type()
+            .create_mutable(block.num_rows());
+        match &self.func {
+            WindowFunctionImpl::Aggregate(func) => {
+                self.evaluate_aggregate(func, &partitions, &args, &order_keys, builder.as_mut())?
+            }
+            WindowFunctionImpl::BuiltIn(func) => {
+                self.evaluate_builtin(*func, &partitions, &args, &order_keys, builder.as_mut())?
+            }
+        }
+
+        let block = block.add_column(builder.to_column(), self.output_field.clone())?;
+        Ok(vec![block])
+    }
+}
+
+/// Aggregate the rows of window frames incrementally. The state is reused if the
+/// frame of the next row has the same start and doesn't shrink, which is the case of
+/// the default frame `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`.
+struct FrameAggregator<'a> {
+    func: &'a AggregateFunctionRef,
+    place: StateAddr,
+    /// Rows which have been accumulated into `place`
+    frame: Range<usize>,
+}
+
+impl<'a> FrameAggregator<'a> {
+    fn new(func: &'a AggregateFunctionRef, arena: &Bump) -> Self {
+        let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(place);
+        FrameAggregator {
+            func,
+            place,
+            frame: 0..0,
+        }
+    }
+
+    fn merge_frame(
+        &mut self,
+        frame: Range<usize>,
+        args: &[ColumnRef],
+        builder: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        if frame.start != self.frame.start || frame.end < self.frame.end {
+            self.reset();
+            self.frame = frame.start..frame.start;
+        }
+        for row in self.frame.end..frame.end {
+            self.func.accumulate_row(self.place, args, row)?;
+        }
+        self.frame.end = frame.end;
+        self.func.merge_result(self.place, builder)
+    }
+
+    fn reset(&mut self) {
+        if self.func.need_manual_drop_state() {
+            unsafe { self.func.drop_state(self.place) };
+        }
+        self.func.init_state(self.place);
+    }
+}
+
+impl<'a> Drop for FrameAggregator<'a> {
+    fn drop(&mut self) {
+        if self.func.need_manual_drop_state() {
+            unsafe { self.func.drop_state(self.place) };
+        }
+    }
+}
+
+pub type TransformWindowFunc = TransformCompact<WindowFuncCompactor>;
@@ -47,8 +47,6 @@ use serde::Serialize;
 
 use super::HttpQueryContext;
 use crate::interpreters::InterpreterFactory;
-use crate::pipelines::new::processors::port::OutputPort;
-use crate::pipelines::new::processors::StreamSourceV2;
 use crate::pipelines::new::SourcePipeBuilder;
 use crate::servers::http::v1::multipart_format::MultipartFormat;
 use crate::servers::http::v1::multipart_format::MultipartWorker;
@@ -182,7 +180,7 @@ pub async fn streaming_load(
         != 0
         && context.get_cluster().is_empty()
     {
-        let source_pipe_builder = match &mut plan {
+        return match &mut plan {
             PlanNode::Insert(insert) => match &mut insert.source {
                 InsertInputSource::StreamingWithFormat(format) => {
                     if FormatFactory::instance().has_input(format.as_str()) {
@@ -199,19 +197,10 @@ pub async fn streaming_load(
                         };
                     }
 
-                    if format.to_lowercase().as_str() == "ndjson"
-                        || format.to_lowercase().as_str() == "jsoneachrow"
-                    {
-                        ndjson_source_pipe_builder(context.clone(), &plan, multipart).await
-                    } else {
-                        Err(poem::Error::from_string(
-                            format!(
-                                "Streaming load only supports csv format, but got {}",
-                                format
-                            ),
-                            StatusCode::BAD_REQUEST,
-                        ))
-                    }
+                    Err(poem::Error::from_string(
+                        format!("Unsupported format of streaming load: {}", format),
+                        StatusCode::BAD_REQUEST,
+                    ))
                 }
                 _non_supported_source => Err(poem::Error::from_string(
                     "Only supports streaming upload. e.g. INSERT INTO $table FORMAT CSV",
@@ -225,34 +214,8 @@ pub async fn streaming_load(
                 ),
                 StatusCode::BAD_REQUEST,
             )),
-        }?;
-        let interpreter =
-            InterpreterFactory::get(context.clone(), plan.clone()).map_err(InternalServerError)?;
-        let _ = interpreter
-            .set_source_pipe_builder(Option::from(source_pipe_builder))
-            .map_err(|e| tracing::error!("interpreter.set_source_pipe_builder.error: {:?}", e));
-        interpreter.start().await.map_err(InternalServerError)?;
-        let mut data_stream = interpreter
-            .execute(None)
-            .await
-            .map_err(InternalServerError)?;
-        while let Some(_block) = data_stream.next().await {}
-        // Write Finish to query log table.
-        let _ = interpreter
-            .finish()
-            .await
-            .map_err(|e| tracing::error!("interpreter.finish error: {:?}", e));
-
-        // TODO generate id
-        // TODO duplicate by insert_label
-        let mut id = uuid::Uuid::new_v4().to_string();
-        return Ok(Json(LoadResponse {
-            id,
-            state: "SUCCESS".to_string(),
-            stats: context.get_scan_progress_value(),
-            error: None,
-        }));
-    };
+        };
+    }
 
     // After new processor is ready, the following code can directly delete
     // validate plan
@@ -269,10 +232,7 @@ pub async fn streaming_load(
                     build_ndjson_stream(&plan, multipart)
                 } else {
                     Err(poem::Error::from_string(
-                        format!(
-                            "Streaming load only supports csv format, but got {}",
-                            format
-                        ),
+                        format!("Unsupported format of streaming load: {}", format),
                         StatusCode::BAD_REQUEST,
                     ))
                 }
@@ -419,27 +379,3 @@ fn format_source_pipe_builder(
         format_settings.clone(),
     )
 }
-
-async fn ndjson_source_pipe_builder(
-    ctx: Arc<QueryContext>,
-    plan: &PlanNode,
-    mut multipart: Multipart,
-) -> PoemResult<SourcePipeBuilder> {
-    let builder = NDJsonSourceBuilder::create(plan.schema(), FormatSettings::default());
-    let mut source_pipe_builder = SourcePipeBuilder::create();
-    while let Ok(Some(field)) = multipart.next_field().await {
-        let bytes = field
-            .bytes()
-            .await
-            .map_err_to_code(ErrorCode::BadBytes, || "Read part to field bytes error")
-            .map_err(InternalServerError)?;
-        let cursor = Cursor::new(bytes);
-        let ndjson_source = builder.build(cursor).map_err(InternalServerError)?;
-        let output_port = OutputPort::create();
-        let source =
-            StreamSourceV2::create(ctx.clone(), Box::new(ndjson_source), output_port.clone())
-                .map_err(InternalServerError)?;
-        source_pipe_builder.add_source(output_port, source);
-    }
-    Ok(source_pipe_builder)
-}
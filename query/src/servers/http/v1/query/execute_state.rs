@@ -23,6 +23,8 @@ use common_base::base::ProgressValues;
 use common_base::base::TrySpawn;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
+use common_datavalues::VariantValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::PlanNode;
@@ -36,6 +38,7 @@ use futures::StreamExt;
 use futures_util::FutureExt;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::Value as JsonValue;
 use ExecuteState::*;
 
 use super::http_query::HttpQueryRequest;
@@ -50,8 +53,6 @@ use crate::pipelines::new::NewPipe;
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
 use crate::sessions::SessionRef;
-use crate::sql::exec::PhysicalPlan;
-use crate::sql::exec::PipelineBuilder;
 use crate::sql::ColumnBinding;
 use crate::sql::DfParser;
 use crate::sql::DfStatement;
@@ -180,21 +181,37 @@ impl ExecuteState {
         let start_time = Instant::now();
         ctx.attach_query_str(sql);
 
-        let (stmts, _) = match DfParser::parse_sql(sql, ctx.get_current_session().get_type()) {
-            Ok(t) => t,
-            Err(e) => {
+        let settings = ctx.get_settings();
+        let enable_planner_v2 = settings.get_enable_new_processor_framework()? != 0
+            && !ctx.get_config().query.management_mode
+            && ctx.get_cluster().is_empty()
+            && settings.get_enable_planner_v2()? != 0;
+
+        let use_planner_v2 = if request.params.is_empty() {
+            let (stmts, _) = match DfParser::parse_sql(sql, ctx.get_current_session().get_type()) {
+                Ok(t) => t,
+                Err(e) => {
+                    InterpreterQueryLog::fail_to_start(ctx, e.clone()).await;
+                    return Err(e);
+                }
+            };
+
+            enable_planner_v2 && matches!(stmts.get(0), Some(DfStatement::Query(_)))
+        } else {
+            // Placeholders are only understood by the new planner.
+            if !enable_planner_v2 {
+                let e = ErrorCode::BadArguments(
+                    "Query parameters are only supported by the new planner, which is not enabled for this query",
+                );
                 InterpreterQueryLog::fail_to_start(ctx, e.clone()).await;
                 return Err(e);
             }
+            let params = request.params.iter().map(json_to_data_value).collect();
+            ctx.attach_query_parameters(params);
+            true
         };
 
-        let settings = ctx.get_settings();
-        if settings.get_enable_new_processor_framework()? != 0
-            && !ctx.get_config().query.management_mode
-            && ctx.get_cluster().is_empty()
-            && settings.get_enable_planner_v2()? != 0
-            && matches!(stmts.get(0), Some(DfStatement::Query(_)))
-        {
+        if use_planner_v2 {
             let mut planner = Planner::new(ctx.clone());
             let (plan, _, _) = planner.plan_sql(sql).await?;
             let interpreter = InterpreterFactoryV2::get(ctx.clone(), &plan)?;
@@ -390,15 +407,12 @@ impl HttpQueryHandle {
     pub async fn execute(
         self,
         ctx: Arc<QueryContext>,
-        physical_plan: &PhysicalPlan,
+        mut root_pipeline: NewPipeline,
+        mut pipelines: Vec<NewPipeline>,
         result_columns: &[ColumnBinding],
     ) -> Result<SendableDataBlockStream> {
         let executor = self.executor.clone();
         let block_buffer = self.block_buffer.clone();
-        let mut pb = PipelineBuilder::new();
-        let mut root_pipeline = NewPipeline::create();
-        pb.build_pipeline(ctx.clone(), physical_plan, &mut root_pipeline)?;
-        let mut pipelines = pb.pipelines;
         let async_runtime = ctx.get_storage_runtime();
 
         root_pipeline.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
@@ -486,3 +500,21 @@ impl HttpQueryHandle {
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
     }
 }
+
+fn json_to_data_value(value: &JsonValue) -> DataValue {
+    match value {
+        JsonValue::Null => DataValue::Null,
+        JsonValue::Bool(v) => DataValue::Boolean(*v),
+        JsonValue::Number(v) => {
+            if let Some(v) = v.as_u64() {
+                DataValue::UInt64(v)
+            } else if let Some(v) = v.as_i64() {
+                DataValue::Int64(v)
+            } else {
+                DataValue::Float64(v.as_f64().unwrap_or_default())
+            }
+        }
+        JsonValue::String(v) => DataValue::String(v.as_bytes().to_vec()),
+        JsonValue::Array(_) | JsonValue::Object(_) => DataValue::Variant(VariantValue::from(value)),
+    }
+}
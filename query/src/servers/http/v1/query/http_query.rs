@@ -48,6 +48,9 @@ pub struct HttpQueryRequest {
     pub pagination: PaginationConf,
     #[serde(default)]
     pub string_fields: bool,
+    /// Values of the `?` or `$<index>` placeholders in `sql`, in order.
+    #[serde(default)]
+    pub params: Vec<serde_json::Value>,
 }
 
 const DEFAULT_MAX_ROWS_IN_BUFFER: usize = 5 * 1000 * 1000;
@@ -16,8 +16,11 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Instant;
 
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
 use common_base::base::TrySpawn;
 use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
@@ -26,11 +29,14 @@ use common_tracing::tracing;
 use common_tracing::tracing::Instrument;
 use metrics::histogram;
 use opensrv_mysql::AsyncMysqlShim;
+use opensrv_mysql::Column;
 use opensrv_mysql::ErrorKind;
 use opensrv_mysql::InitWriter;
 use opensrv_mysql::ParamParser;
 use opensrv_mysql::QueryResultWriter;
 use opensrv_mysql::StatementMetaWriter;
+use opensrv_mysql::Value;
+use opensrv_mysql::ValueInner;
 use rand::RngCore;
 use tokio_stream::StreamExt;
 
@@ -38,6 +44,8 @@ use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterFactoryV2;
 use crate::interpreters::InterpreterQueryLog;
+use crate::servers::mysql::writers::convert_param_types;
+use crate::servers::mysql::writers::convert_schema;
 use crate::servers::mysql::writers::DFInitResultWriter;
 use crate::servers::mysql::writers::DFQueryResultWriter;
 use crate::servers::mysql::MySQLFederated;
@@ -245,28 +253,118 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
         Ok(authed)
     }
 
-    async fn do_prepare(&mut self, _: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Prepare is not support in Databend.".as_bytes(),
-        )?;
+    async fn do_prepare(&mut self, query: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
+        match self.prepare_statement(query).await {
+            Ok((id, params, columns)) => {
+                writer.reply(id, &params, &columns)?;
+            }
+            Err(error) => {
+                tracing::error!("OnPrepare Error: {:?}", error);
+                writer.error(ErrorKind::ER_UNKNOWN_ERROR, error.to_string().as_bytes())?;
+            }
+        }
         Ok(())
     }
 
+    async fn prepare_statement(&mut self, query: &str) -> Result<(u32, Vec<Column>, Vec<Column>)> {
+        tracing::info!("Prepare query: {}", query);
+        let context = self.session.create_query_context().await?;
+        context.attach_query_str(query);
+
+        let mut planner = Planner::new(context);
+        let statement = planner.prepare_sql(query).await?;
+        let params = convert_param_types(&statement.param_types)?;
+        let columns = convert_schema(&statement.schema, true)?;
+        let id = self.session.add_prepared_statement(statement);
+        Ok((id, params, columns))
+    }
+
     async fn do_execute(
         &mut self,
-        _: u32,
-        _: ParamParser<'_>,
+        id: u32,
+        params: ParamParser<'_>,
         writer: QueryResultWriter<'_, W>,
     ) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Execute is not support in Databend.".as_bytes(),
-        )?;
-        Ok(())
+        let mut writer = DFQueryResultWriter::create_binary(writer);
+
+        let instant = Instant::now();
+        let params = params
+            .into_iter()
+            .map(|param| Self::param_to_data_value(param.value))
+            .collect::<Result<Vec<_>>>();
+        let blocks = match params {
+            Ok(params) => self.execute_statement(id, params).await,
+            Err(cause) => Err(cause),
+        };
+
+        let format = self
+            .session
+            .get_shared_query_context()
+            .await?
+            .get_format_settings()?;
+        let write_result = writer.write(blocks, &format);
+
+        histogram!(
+            super::mysql_metrics::METRIC_MYSQL_PROCESSOR_REQUEST_DURATION,
+            instant.elapsed()
+        );
+
+        write_result
+    }
+
+    async fn execute_statement(
+        &mut self,
+        id: u32,
+        params: Vec<DataValue>,
+    ) -> Result<(Vec<DataBlock>, String)> {
+        let statement = self.session.get_prepared_statement(id).ok_or_else(|| {
+            ErrorCode::UnknownPreparedStatement(format!("Unknown prepared statement {}", id))
+        })?;
+
+        tracing::info!("Execute prepared query: {}", statement.query);
+        let context = self.session.create_query_context().await?;
+        context.attach_query_str(&statement.query);
+
+        let mut planner = Planner::new(context.clone());
+        let interpreter = planner
+            .plan_prepared(&statement, params)
+            .await
+            .and_then(|v| InterpreterFactoryV2::get(context.clone(), &v.0));
+        match interpreter {
+            Ok(interpreter) => Self::exec_query(interpreter, &context).await,
+            Err(e) => {
+                InterpreterQueryLog::fail_to_start(context, e.clone()).await;
+                Err(e)
+            }
+        }
+    }
+
+    fn param_to_data_value(value: Value<'_>) -> Result<DataValue> {
+        match value.into_inner() {
+            ValueInner::NULL => Ok(DataValue::Null),
+            ValueInner::Int(v) => Ok(DataValue::Int64(v)),
+            ValueInner::UInt(v) => Ok(DataValue::UInt64(v)),
+            ValueInner::Double(v) => Ok(DataValue::Float64(v)),
+            ValueInner::Bytes(v) => Ok(DataValue::String(v.to_vec())),
+            ValueInner::Date(_) => {
+                // Dates and datetimes are bound as strings, and cast by the functions using them.
+                let datetime: NaiveDateTime = value.into();
+                let datetime = if datetime.time() == NaiveTime::from_hms(0, 0, 0) {
+                    datetime.date().to_string()
+                } else {
+                    datetime.to_string()
+                };
+                Ok(DataValue::String(datetime.into_bytes()))
+            }
+            ValueInner::Time(_) => Err(ErrorCode::BadArguments(
+                "Parameters of TIME type are not supported",
+            )),
+        }
     }
 
-    async fn do_close(&mut self, _: u32) {}
+    async fn do_close(&mut self, id: u32) {
+        self.session.remove_prepared_statement(id);
+    }
 
     // Check the query is a federated or driver setup command.
     // Here we fake some values for the command which Databend not supported.
@@ -16,4 +16,6 @@ mod init_result_writer;
 mod query_result_writer;
 
 pub use self::init_result_writer::DFInitResultWriter;
+pub use self::query_result_writer::convert_param_types;
+pub use self::query_result_writer::convert_schema;
 pub use self::query_result_writer::DFQueryResultWriter;
@@ -18,6 +18,7 @@ use common_datavalues::remove_nullable;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
 use common_datavalues::DateConverter;
 use common_datavalues::TypeSerializer;
@@ -31,11 +32,24 @@ use opensrv_mysql::*;
 
 pub struct DFQueryResultWriter<'a, W: std::io::Write> {
     inner: Option<QueryResultWriter<'a, W>>,
+    binary: bool,
 }
 
 impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
     pub fn create(inner: QueryResultWriter<'a, W>) -> DFQueryResultWriter<'a, W> {
-        DFQueryResultWriter::<'a, W> { inner: Some(inner) }
+        DFQueryResultWriter::<'a, W> {
+            inner: Some(inner),
+            binary: false,
+        }
+    }
+
+    /// Create a writer for the results of prepared statements,
+    /// which are sent with the binary protocol.
+    pub fn create_binary(inner: QueryResultWriter<'a, W>) -> DFQueryResultWriter<'a, W> {
+        DFQueryResultWriter::<'a, W> {
+            inner: Some(inner),
+            binary: true,
+        }
     }
 
     pub fn write(
@@ -45,7 +59,9 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
     ) -> Result<()> {
         if let Some(writer) = self.inner.take() {
             match query_result {
-                Ok((blocks, extra_info)) => Self::ok(blocks, extra_info, writer, format)?,
+                Ok((blocks, extra_info)) => {
+                    Self::ok(blocks, extra_info, writer, format, self.binary)?
+                }
                 Err(error) => Self::err(&error, writer)?,
             }
         }
@@ -57,6 +73,7 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
         extra_info: String,
         dataset_writer: QueryResultWriter<'a, W>,
         format: &FormatSettings,
+        binary: bool,
     ) -> Result<()> {
         // XXX: num_columns == 0 may is error?
         let default_response = OkResponse {
@@ -69,52 +86,9 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
             return Ok(());
         }
 
-        fn convert_field_type(field: &DataField) -> Result<ColumnType> {
-            match remove_nullable(field.data_type()).data_type_id() {
-                TypeID::Int8 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::Int16 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::Int32 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::Int64 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::UInt8 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::UInt16 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::UInt32 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::UInt64 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::Float32 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
-                TypeID::Float64 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
-                TypeID::String => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                TypeID::Boolean => Ok(ColumnType::MYSQL_TYPE_SHORT),
-                TypeID::Date => Ok(ColumnType::MYSQL_TYPE_DATE),
-                TypeID::Timestamp => Ok(ColumnType::MYSQL_TYPE_DATETIME),
-                TypeID::Null => Ok(ColumnType::MYSQL_TYPE_NULL),
-                TypeID::Interval => Ok(ColumnType::MYSQL_TYPE_LONG),
-                TypeID::Array => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                TypeID::Struct => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                TypeID::Variant => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                TypeID::VariantArray => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                TypeID::VariantObject => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                _ => Err(ErrorCode::UnImplement(format!(
-                    "Unsupported column type:{:?}",
-                    field.data_type()
-                ))),
-            }
-        }
-
-        fn make_column_from_field(field: &DataField) -> Result<Column> {
-            convert_field_type(field).map(|column_type| Column {
-                table: "".to_string(),
-                column: field.name().to_string(),
-                coltype: column_type,
-                colflags: ColumnFlags::empty(),
-            })
-        }
-
-        fn convert_schema(schema: &DataSchemaRef) -> Result<Vec<Column>> {
-            schema.fields().iter().map(make_column_from_field).collect()
-        }
-
         let block = blocks[0].clone();
         let tz = format.timezone;
-        match convert_schema(block.schema()) {
+        match convert_schema(block.schema(), binary) {
             Err(error) => Self::err(&error, dataset_writer),
             Ok(columns) => {
                 let mut row_writer = dataset_writer.start(&columns)?;
@@ -146,6 +120,10 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                                             .write_col(
                                                 serializer.serialize_field(row_index, format)?,
                                             )?,
+                                        (TypeID::Decimal, DataValue::Int64(_)) => row_writer
+                                            .write_col(
+                                                serializer.serialize_field(row_index, format)?,
+                                            )?,
                                         (TypeID::String, DataValue::String(v)) => {
                                             row_writer.write_col(v)?
                                         }
@@ -174,6 +152,10 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
 
                                         (_, DataValue::UInt64(v)) => row_writer.write_col(v)?,
 
+                                        (_, DataValue::Float64(v)) if binary => {
+                                            row_writer.write_col(v)?
+                                        }
+
                                         (_, DataValue::Float64(_)) => row_writer
                                             // mysql writer use a text protocol,
                                             // it use format!() to serialize number,
@@ -222,3 +204,93 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
         Ok(())
     }
 }
+
+fn convert_field_type(field: &DataField, binary: bool) -> Result<ColumnType> {
+    let type_id = remove_nullable(field.data_type()).data_type_id();
+    if binary {
+        // The binary protocol encodes the values by the column types,
+        // so declare the types that the values are written as.
+        match type_id {
+            TypeID::Int8
+            | TypeID::Int16
+            | TypeID::Int32
+            | TypeID::Int64
+            | TypeID::UInt8
+            | TypeID::UInt16
+            | TypeID::UInt32
+            | TypeID::UInt64
+            | TypeID::Interval => return Ok(ColumnType::MYSQL_TYPE_LONGLONG),
+            TypeID::Float32 | TypeID::Float64 => return Ok(ColumnType::MYSQL_TYPE_DOUBLE),
+            TypeID::Boolean => return Ok(ColumnType::MYSQL_TYPE_TINY),
+            TypeID::Timestamp | TypeID::Decimal => return Ok(ColumnType::MYSQL_TYPE_VAR_STRING),
+            _ => (),
+        }
+    }
+
+    match type_id {
+        TypeID::Int8 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::Int16 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::Int32 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::Int64 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::UInt8 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::UInt16 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::UInt32 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::UInt64 => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::Float32 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
+        TypeID::Float64 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
+        TypeID::String => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+        TypeID::Boolean => Ok(ColumnType::MYSQL_TYPE_SHORT),
+        TypeID::Date => Ok(ColumnType::MYSQL_TYPE_DATE),
+        TypeID::Timestamp => Ok(ColumnType::MYSQL_TYPE_DATETIME),
+        TypeID::Null => Ok(ColumnType::MYSQL_TYPE_NULL),
+        TypeID::Interval => Ok(ColumnType::MYSQL_TYPE_LONG),
+        TypeID::Decimal => Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL),
+        TypeID::Array => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+        TypeID::Struct => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+        TypeID::Variant => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+        TypeID::VariantArray => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+        TypeID::VariantObject => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+        _ => Err(ErrorCode::UnImplement(format!(
+            "Unsupported column type:{:?}",
+            field.data_type()
+        ))),
+    }
+}
+
+fn make_column_from_field(field: &DataField, binary: bool) -> Result<Column> {
+    convert_field_type(field, binary).map(|column_type| Column {
+        table: "".to_string(),
+        column: field.name().to_string(),
+        coltype: column_type,
+        colflags: ColumnFlags::empty(),
+    })
+}
+
+/// Convert a schema to the MySQL column definitions,
+/// `binary` is true if the rows are sent with the binary protocol.
+pub fn convert_schema(schema: &DataSchemaRef, binary: bool) -> Result<Vec<Column>> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| make_column_from_field(field, binary))
+        .collect()
+}
+
+/// Convert the types of the parameters of a prepared statement to the MySQL column definitions,
+/// the parameters whose types are unknown are described as strings which every client can send.
+pub fn convert_param_types(param_types: &[DataTypeImpl]) -> Result<Vec<Column>> {
+    param_types
+        .iter()
+        .map(|param_type| {
+            if param_type.is_null() {
+                return Ok(Column {
+                    table: "".to_string(),
+                    column: "?".to_string(),
+                    coltype: ColumnType::MYSQL_TYPE_VAR_STRING,
+                    colflags: ColumnFlags::empty(),
+                });
+            }
+            make_column_from_field(&DataField::new("?", param_type.clone()), true)
+        })
+        .collect()
+}
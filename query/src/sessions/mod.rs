@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod metrics;
+mod prepared_statement;
 mod query_ctx;
 mod query_ctx_shared;
 mod session;
@@ -26,6 +27,7 @@ mod session_settings;
 mod session_status;
 mod session_type;
 
+pub use prepared_statement::PreparedStatement;
 pub use query_ctx::QueryContext;
 pub use query_ctx_shared::QueryContextShared;
 pub use session::Session;
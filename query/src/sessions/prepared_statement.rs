@@ -0,0 +1,35 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataTypeImpl;
+
+use crate::sql::plans::Plan;
+
+/// A statement prepared by a client, cached in the session until the client closes it.
+#[derive(Clone)]
+pub struct PreparedStatement {
+    /// The SQL text, with `?` or `$<index>` placeholders for the parameters.
+    pub query: String,
+    /// The number of parameters that must be bound before executing the statement.
+    pub num_params: usize,
+    /// The output schema, derived by binding the statement with unbound parameters.
+    pub schema: DataSchemaRef,
+    /// The types of the parameters inferred from where they are used, `Null` if unknown.
+    pub param_types: Vec<DataTypeImpl>,
+    /// The current database when the statement was prepared.
+    pub database: String,
+    /// The bound plan with placeholders, if it can be reused by executions.
+    pub plan: Option<Plan>,
+}
@@ -32,11 +32,15 @@ use common_base::infallible::RwLock;
 use common_contexts::DalContext;
 use common_contexts::DalMetrics;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::scalars::FunctionContext;
 use common_io::prelude::FormatSettings;
+use common_meta_app::schema::TableIdent;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
 use common_meta_types::UserInfo;
 use common_planners::Expression;
 use common_planners::PartInfoPtr;
@@ -56,6 +60,8 @@ use crate::api::DataExchangeManager;
 use crate::catalogs::Catalog;
 use crate::catalogs::CatalogManager;
 use crate::clusters::Cluster;
+use crate::common::MemoryBudget;
+use crate::common::RuntimeFilter;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::ProcessInfo;
 use crate::sessions::QueryContextShared;
@@ -63,6 +69,8 @@ use crate::sessions::Session;
 use crate::sessions::SessionRef;
 use crate::sessions::Settings;
 use crate::storages::cache::CacheManager;
+use crate::storages::memory::MemoryTable;
+use crate::storages::stage::CopyFileStatus;
 use crate::storages::stage::StageTable;
 use crate::storages::Table;
 use crate::users::auth::auth_mgr::AuthMgr;
@@ -75,8 +83,10 @@ pub struct QueryContext {
     version: String,
     statistics: Arc<RwLock<Statistics>>,
     partition_queue: Arc<RwLock<VecDeque<PartInfoPtr>>>,
+    runtime_filters: Arc<RwLock<Vec<(String, Arc<RuntimeFilter>)>>>,
     shared: Arc<QueryContextShared>,
     precommit_blocks: Arc<RwLock<Vec<DataBlock>>>,
+    copy_status: Arc<RwLock<Vec<CopyFileStatus>>>,
     fragment_id: Arc<AtomicUsize>,
 }
 
@@ -93,9 +103,11 @@ impl QueryContext {
         Arc::new(QueryContext {
             statistics: Arc::new(RwLock::new(Statistics::default())),
             partition_queue: Arc::new(RwLock::new(VecDeque::new())),
+            runtime_filters: Arc::new(RwLock::new(Vec::new())),
             version: format!("DatabendQuery {}", *crate::version::DATABEND_COMMIT_VERSION),
             shared,
             precommit_blocks: Arc::new(RwLock::new(Vec::new())),
+            copy_status: Arc::new(RwLock::new(Vec::new())),
             fragment_id: Arc::new(AtomicUsize::new(0)),
         })
     }
@@ -127,6 +139,15 @@ impl QueryContext {
         table_info: &TableInfo,
         table_args: Option<Vec<Expression>>,
     ) -> Result<Arc<dyn Table>> {
+        if let Some(table) = self
+            .shared
+            .query_tables
+            .read()
+            .get(&table_info.ident.table_id)
+        {
+            return Ok(table.clone());
+        }
+
         let catalog = self.get_catalog(catalog_name)?;
         if table_args.is_none() {
             catalog.get_table_by_info(table_info)
@@ -212,6 +233,15 @@ impl QueryContext {
         Ok(())
     }
 
+    // Set the runtime filters on the columns of the table to be read by the pipeline builder.
+    pub fn set_runtime_filters(&self, runtime_filters: Vec<(String, Arc<RuntimeFilter>)>) {
+        *self.runtime_filters.write() = runtime_filters;
+    }
+
+    pub fn get_runtime_filters(&self) -> Vec<(String, Arc<RuntimeFilter>)> {
+        self.runtime_filters.read().clone()
+    }
+
     pub fn try_get_statistics(&self) -> Result<Statistics> {
         let statistics = self.statistics.read();
         Ok((*statistics).clone())
@@ -238,6 +268,16 @@ impl QueryContext {
         self.shared.attach_query_plan(query_plan);
     }
 
+    // Bind the values of the placeholders before planning a prepared statement.
+    pub fn attach_query_parameters(&self, parameters: Vec<DataValue>) {
+        self.shared.attach_query_parameters(parameters);
+    }
+
+    // Get the value bound to the placeholder `$index`, starting from 1.
+    pub fn get_query_parameter(&self, index: usize) -> Option<DataValue> {
+        self.shared.get_query_parameter(index)
+    }
+
     pub fn get_cluster(&self) -> Arc<Cluster> {
         self.shared.get_cluster()
     }
@@ -272,6 +312,34 @@ impl QueryContext {
         self.shared.get_table(catalog, database, table).await
     }
 
+    /// Create a memory table of the given blocks, which is only known by this query and lives
+    /// as long as it, e.g. the materialized result of a common table expression.
+    ///
+    /// The ids of such tables count down from `u64::MAX`, so they never clash with the tables
+    /// of catalogs.
+    pub fn create_query_table(
+        &self,
+        name: &str,
+        schema: DataSchemaRef,
+        blocks: Vec<DataBlock>,
+    ) -> Arc<dyn Table> {
+        let mut query_tables = self.shared.query_tables.write();
+        let table_id = u64::MAX - query_tables.len() as u64;
+        let table_info = TableInfo {
+            desc: format!("'{}'", name),
+            name: name.to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "MEMORY".to_string(),
+                ..Default::default()
+            },
+        };
+        let table: Arc<dyn Table> = MemoryTable::create_with_blocks(table_info, blocks).into();
+        query_tables.insert(table_id, table.clone());
+        table
+    }
+
     pub fn get_id(&self) -> String {
         self.shared.init_query_id.as_ref().read().clone()
     }
@@ -329,6 +397,10 @@ impl QueryContext {
         self.shared.get_settings()
     }
 
+    pub fn get_memory_budget(&self) -> Result<Arc<MemoryBudget>> {
+        self.shared.try_get_memory_budget()
+    }
+
     pub fn get_changed_settings(&self) -> Arc<Settings> {
         self.shared.get_changed_settings()
     }
@@ -465,6 +537,14 @@ impl QueryContext {
         swaped_precommit_blocks
     }
 
+    pub fn push_copy_file_status(&self, status: CopyFileStatus) {
+        self.copy_status.write().push(status);
+    }
+
+    pub fn consume_copy_file_status(&self) -> Vec<CopyFileStatus> {
+        std::mem::take(&mut *self.copy_status.write())
+    }
+
     pub fn try_get_function_context(&self) -> Result<FunctionContext> {
         let tz = String::from_utf8(self.get_settings().get_timezone()?).map_err(|_| {
             ErrorCode::LogicalError("Timezone has been checked and should be valid.")
@@ -25,6 +25,7 @@ use common_base::base::Runtime;
 use common_base::infallible::Mutex;
 use common_base::infallible::RwLock;
 use common_contexts::DalContext;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::FormatSettings;
@@ -35,6 +36,7 @@ use uuid::Uuid;
 
 use crate::catalogs::CatalogManager;
 use crate::clusters::Cluster;
+use crate::common::MemoryBudget;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::Session;
 use crate::sessions::SessionType;
@@ -67,6 +69,8 @@ pub struct QueryContextShared {
     pub(in crate::sessions) error: Arc<Mutex<Option<ErrorCode>>>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
+    /// The memory budget of the operators which are able to spill, see `spill_memory_budget`.
+    pub(in crate::sessions) memory_budget: Arc<RwLock<Option<Arc<MemoryBudget>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
     pub(in crate::sessions) cluster_cache: Arc<Cluster>,
     pub(in crate::sessions) sources_abort_handle: Arc<RwLock<Vec<AbortHandle>>>,
@@ -75,7 +79,11 @@ pub struct QueryContextShared {
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
     pub(in crate::sessions) http_query: Arc<RwLock<Option<HttpQueryHandle>>>,
     pub(in crate::sessions) running_plan: Arc<RwLock<Option<PlanNode>>>,
+    /// Values bound to the placeholders of a prepared statement, `$1` is the first one.
+    pub(in crate::sessions) query_parameters: Arc<RwLock<Vec<DataValue>>>,
     pub(in crate::sessions) tables_refs: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
+    /// Tables only known by this query, e.g. the materialized common table expressions.
+    pub(in crate::sessions) query_tables: Arc<RwLock<HashMap<u64, Arc<dyn Table>>>>,
     pub(in crate::sessions) dal_ctx: Arc<DalContext>,
     pub(in crate::sessions) user_manager: Arc<UserApiProvider>,
     pub(in crate::sessions) auth_manager: Arc<AuthMgr>,
@@ -101,13 +109,16 @@ impl QueryContextShared {
             write_progress: Arc::new(Progress::create()),
             error: Arc::new(Mutex::new(None)),
             runtime: Arc::new(RwLock::new(None)),
+            memory_budget: Arc::new(RwLock::new(None)),
             sources_abort_handle: Arc::new(RwLock::new(Vec::new())),
             ref_count: Arc::new(AtomicUsize::new(0)),
             subquery_index: Arc::new(AtomicUsize::new(1)),
             running_query: Arc::new(RwLock::new(None)),
             http_query: Arc::new(RwLock::new(None)),
             running_plan: Arc::new(RwLock::new(None)),
+            query_parameters: Arc::new(RwLock::new(Vec::new())),
             tables_refs: Arc::new(Mutex::new(HashMap::new())),
+            query_tables: Arc::new(RwLock::new(HashMap::new())),
             dal_ctx: Arc::new(Default::default()),
             user_manager: user_manager.clone(),
             auth_manager: Arc::new(AuthMgr::create(conf, user_manager.clone()).await?),
@@ -258,6 +269,22 @@ impl QueryContextShared {
         }
     }
 
+    /// Init memory budget when first get
+    pub fn try_get_memory_budget(&self) -> Result<Arc<MemoryBudget>> {
+        let mut memory_budget = self.memory_budget.write();
+
+        match &*memory_budget {
+            Some(memory_budget) => Ok(memory_budget.clone()),
+            None => {
+                let settings = self.get_settings();
+                let limit = settings.get_spill_memory_budget()? as usize;
+                let budget = MemoryBudget::create(limit);
+                *memory_budget = Some(budget.clone());
+                Ok(budget)
+            }
+        }
+    }
+
     pub fn attach_http_query_handle(&self, handle: HttpQueryHandle) {
         let mut http_query = self.http_query.write();
         *http_query = Some(handle);
@@ -276,6 +303,17 @@ impl QueryContextShared {
         running_query.as_ref().unwrap_or(&"".to_string()).clone()
     }
 
+    pub fn attach_query_parameters(&self, parameters: Vec<DataValue>) {
+        *self.query_parameters.write() = parameters;
+    }
+
+    pub fn get_query_parameter(&self, index: usize) -> Option<DataValue> {
+        let parameters = self.query_parameters.read();
+        index
+            .checked_sub(1)
+            .and_then(|index| parameters.get(index).cloned())
+    }
+
     pub fn attach_query_plan(&self, plan: &PlanNode) {
         let mut running_plan = self.running_plan.write();
         *running_plan = Some(plan.clone());
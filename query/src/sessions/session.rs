@@ -28,6 +28,7 @@ use futures::channel::*;
 use opendal::Operator;
 
 use crate::catalogs::CatalogManager;
+use crate::sessions::PreparedStatement;
 use crate::sessions::QueryContext;
 use crate::sessions::QueryContextShared;
 use crate::sessions::SessionContext;
@@ -156,6 +157,18 @@ impl Session {
         self.session_ctx.get_current_query_id()
     }
 
+    pub fn add_prepared_statement(self: &Arc<Self>, statement: PreparedStatement) -> u32 {
+        self.session_ctx.add_prepared_statement(statement)
+    }
+
+    pub fn get_prepared_statement(self: &Arc<Self>, id: u32) -> Option<PreparedStatement> {
+        self.session_ctx.get_prepared_statement(id)
+    }
+
+    pub fn remove_prepared_statement(self: &Arc<Self>, id: u32) {
+        self.session_ctx.remove_prepared_statement(id)
+    }
+
     pub fn attach<F>(self: &Arc<Self>, host: Option<SocketAddr>, io_shutdown: F)
     where F: FnOnce() + Send + 'static {
         let (tx, rx) = oneshot::channel();
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
@@ -23,6 +25,7 @@ use common_macros::MallocSizeOf;
 use common_meta_types::UserInfo;
 use futures::channel::oneshot::Sender;
 
+use crate::sessions::PreparedStatement;
 use crate::sessions::QueryContextShared;
 use crate::Config;
 
@@ -42,6 +45,10 @@ pub struct SessionContext {
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
     #[ignore_malloc_size_of = "insignificant"]
     query_context_shared: RwLock<Option<Arc<QueryContextShared>>>,
+    #[ignore_malloc_size_of = "insignificant"]
+    next_statement_id: AtomicU32,
+    #[ignore_malloc_size_of = "insignificant"]
+    prepared_statements: RwLock<HashMap<u32, PreparedStatement>>,
 }
 
 impl SessionContext {
@@ -56,6 +63,8 @@ impl SessionContext {
             current_database: RwLock::new("default".to_string()),
             io_shutdown_tx: Default::default(),
             query_context_shared: Default::default(),
+            next_statement_id: AtomicU32::new(1),
+            prepared_statements: Default::default(),
         })
     }
 
@@ -161,4 +170,24 @@ impl SessionContext {
         let mut lock = self.query_context_shared.write();
         lock.take()
     }
+
+    // Cache a prepared statement and return its id.
+    pub fn add_prepared_statement(&self, statement: PreparedStatement) -> u32 {
+        let id = self.next_statement_id.fetch_add(1, Ordering::Relaxed);
+        let mut lock = self.prepared_statements.write();
+        lock.insert(id, statement);
+        id
+    }
+
+    // Get a prepared statement by id.
+    pub fn get_prepared_statement(&self, id: u32) -> Option<PreparedStatement> {
+        let lock = self.prepared_statements.read();
+        lock.get(&id).cloned()
+    }
+
+    // Remove a prepared statement from the cache.
+    pub fn remove_prepared_statement(&self, id: u32) {
+        let mut lock = self.prepared_statements.write();
+        lock.remove(&id);
+    }
 }
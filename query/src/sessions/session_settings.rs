@@ -168,6 +168,66 @@ impl Settings {
                 user_setting: UserSetting::create("wait_for_async_insert_timeout", DataValue::UInt64(100)),
                 level: ScopeLevel::Session,
                 desc: "The timeout in seconds for waiting for processing of async insert, default value: 100"
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(1000),
+                user_setting: UserSetting::create("cte_max_recursion_depth", DataValue::UInt64(1000)),
+                level: ScopeLevel::Session,
+                desc: "The maximum iterations of a recursive common table expression, default value: 1000",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("spill_memory_budget", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "The memory in bytes a query can use before spilling to disk, 0 to disable, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(16),
+                user_setting: UserSetting::create("spill_partitions", DataValue::UInt64(16)),
+                level: ScopeLevel::Session,
+                desc: "The number of partitions of spilled hash tables, default value: 16",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("spill_to_storage", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "Spill to the configured storage instead of local disk if value != 0, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("enable_cbo", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "Enable cost-based join reordering of planner v2 if value != 0, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(1),
+                user_setting: UserSetting::create("enable_runtime_filter", DataValue::UInt64(1)),
+                level: ScopeLevel::Session,
+                desc: "Enable runtime filters of hash joins in planner v2 if value != 0, default value: 1",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("enable_query_result_cache", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc: "Enable the result cache of planner v2 queries if value != 0, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(1024 * 1024),
+                user_setting: UserSetting::create("query_result_cache_max_bytes", DataValue::UInt64(1024 * 1024)),
+                level: ScopeLevel::Session,
+                desc: "The maximum bytes of a query result to be cached, default value: 1048576",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(300),
+                user_setting: UserSetting::create("query_result_cache_ttl_secs", DataValue::UInt64(300)),
+                level: ScopeLevel::Session,
+                desc: "The seconds a cached query result is served before it expires, default value: 300",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(24 * 7),
+                user_setting: UserSetting::create("load_file_metadata_expire_hours", DataValue::UInt64(24 * 7)),
+                level: ScopeLevel::Session,
+                desc: "How many hours the files loaded by COPY INTO <table> are remembered, default value: 168",
             }
         ];
 
@@ -314,6 +374,61 @@ impl Settings {
         self.try_set_u64(key, val, false)
     }
 
+    pub fn get_cte_max_recursion_depth(&self) -> Result<u64> {
+        let key = "cte_max_recursion_depth";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_spill_memory_budget(&self) -> Result<u64> {
+        let key = "spill_memory_budget";
+        self.try_get_u64(key)
+    }
+
+    pub fn set_spill_memory_budget(&self, val: u64) -> Result<()> {
+        let key = "spill_memory_budget";
+        self.try_set_u64(key, val, false)
+    }
+
+    pub fn get_spill_partitions(&self) -> Result<u64> {
+        let key = "spill_partitions";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_spill_to_storage(&self) -> Result<u64> {
+        let key = "spill_to_storage";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_enable_cbo(&self) -> Result<u64> {
+        let key = "enable_cbo";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_enable_runtime_filter(&self) -> Result<u64> {
+        let key = "enable_runtime_filter";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_enable_query_result_cache(&self) -> Result<u64> {
+        let key = "enable_query_result_cache";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_query_result_cache_max_bytes(&self) -> Result<u64> {
+        let key = "query_result_cache_max_bytes";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_query_result_cache_ttl_secs(&self) -> Result<u64> {
+        let key = "query_result_cache_ttl_secs";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_load_file_metadata_expire_hours(&self) -> Result<u64> {
+        let key = "load_file_metadata_expire_hours";
+        self.try_get_u64(key)
+    }
+
     pub fn has_setting(&self, key: &str) -> bool {
         let settings = self.settings.read();
         settings.get(key).is_some()
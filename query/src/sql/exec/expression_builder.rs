@@ -112,6 +112,8 @@ impl ExpressionBuilder {
                 })
             }
             Scalar::SubqueryExpr(_) => Err(ErrorCode::UnImplement("Unsupported subquery expr")),
+            Scalar::WindowFunction(_) => Err(ErrorCode::UnImplement("Unsupported window function")),
+            Scalar::Placeholder(_) => Err(ErrorCode::LogicalError("Unbound placeholder")),
         }
     }
 
@@ -0,0 +1,255 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::Partitions;
+
+use crate::api::BroadcastExchange;
+use crate::api::DataExchange;
+use crate::api::MergeExchange;
+use crate::api::ShuffleDataExchangeV2;
+use crate::interpreters::QueryFragmentAction;
+use crate::interpreters::QueryFragmentActions;
+use crate::interpreters::QueryFragmentsActions;
+use crate::sessions::QueryContext;
+use crate::sql::exec::ExchangeKind;
+use crate::sql::exec::PhysicalPlan;
+use crate::sql::exec::PhysicalScalar;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FragmentType {
+    /// Executed on the local node only, e.g. root fragment or the fragment
+    /// consuming gathered data.
+    Serial,
+    /// Reading a table, executed on every node with a part of partitions.
+    Source,
+    /// Consuming the data shuffled from other fragments, executed on every node.
+    Intermediate,
+}
+
+/// A part of physical plan which can be executed without exchanging data,
+/// the fragments are split by `Exchange` operators.
+#[derive(Clone, Debug)]
+pub struct PlanFragment {
+    pub plan: PhysicalPlan,
+    pub fragment_id: usize,
+    pub fragment_type: FragmentType,
+    /// How the output of current fragment is sent to its consumer, `None` for root fragment.
+    pub exchange: Option<(ExchangeKind, Vec<PhysicalScalar>)>,
+    pub source_fragments: Vec<PlanFragment>,
+}
+
+#[derive(Default)]
+struct FragmentState {
+    num_scans: usize,
+    read_merged_data: bool,
+    source_fragments: Vec<PlanFragment>,
+}
+
+/// Split a distributed physical plan into `PlanFragment`s.
+pub struct Fragmenter {
+    ctx: Arc<QueryContext>,
+    query_id: String,
+}
+
+impl Fragmenter {
+    pub fn create(ctx: Arc<QueryContext>) -> Self {
+        let query_id = ctx.get_id();
+        Fragmenter { ctx, query_id }
+    }
+
+    /// Build the root fragment, which is always executed on the local node.
+    pub fn build_fragment(&self, plan: PhysicalPlan) -> Result<PlanFragment> {
+        let fragment_id = self.ctx.get_fragment_id();
+        let mut root = self.build_fragment_impl(fragment_id, plan, None)?;
+        root.fragment_type = FragmentType::Serial;
+        Ok(root)
+    }
+
+    fn build_fragment_impl(
+        &self,
+        fragment_id: usize,
+        mut plan: PhysicalPlan,
+        exchange: Option<(ExchangeKind, Vec<PhysicalScalar>)>,
+    ) -> Result<PlanFragment> {
+        let mut state = FragmentState::default();
+        self.visit(&mut plan, &mut state)?;
+
+        // Partitions of different tables can't be split consistently, so the
+        // fragment reading more than one table is executed on the local node.
+        let fragment_type = if state.read_merged_data || state.num_scans > 1 {
+            FragmentType::Serial
+        } else if state.num_scans == 1 {
+            FragmentType::Source
+        } else {
+            FragmentType::Intermediate
+        };
+
+        Ok(PlanFragment {
+            plan,
+            fragment_id,
+            fragment_type,
+            exchange,
+            source_fragments: state.source_fragments,
+        })
+    }
+
+    fn visit(&self, plan: &mut PhysicalPlan, state: &mut FragmentState) -> Result<()> {
+        match plan {
+            PhysicalPlan::Exchange { .. } => {
+                let fragment_id = self.ctx.get_fragment_id();
+                let exchange_source = PhysicalPlan::ExchangeSource {
+                    schema: plan.output_schema()?,
+                    query_id: self.query_id.clone(),
+                    source_fragment_id: fragment_id,
+                };
+
+                if let PhysicalPlan::Exchange { input, kind, keys } =
+                    std::mem::replace(plan, exchange_source)
+                {
+                    state.read_merged_data |= kind == ExchangeKind::Merge;
+                    let source =
+                        self.build_fragment_impl(fragment_id, *input, Some((kind, keys)))?;
+                    state.source_fragments.push(source);
+                }
+                Ok(())
+            }
+            PhysicalPlan::TableScan { .. } => {
+                state.num_scans += 1;
+                Ok(())
+            }
+            _ => {
+                for input in plan.inputs_mut() {
+                    self.visit(input, state)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PlanFragment {
+    /// Generate the actions of current fragment and its source fragments, actions
+    /// of the source fragments are always added before their consumers.
+    pub fn get_actions(&self, actions: &mut QueryFragmentsActions) -> Result<()> {
+        let executors = self.get_executors(actions);
+        for source in &self.source_fragments {
+            source.finalize(&executors, actions)?;
+        }
+
+        let mut fragment_actions = QueryFragmentActions::create(false, self.fragment_id);
+        for executor in executors {
+            fragment_actions
+                .add_action(QueryFragmentAction::create_v2(executor, self.plan.clone()));
+        }
+        actions.add_fragment_actions(fragment_actions)
+    }
+
+    fn finalize(&self, destinations: &[String], actions: &mut QueryFragmentsActions) -> Result<()> {
+        let executors = self.get_executors(actions);
+        for source in &self.source_fragments {
+            source.finalize(&executors, actions)?;
+        }
+
+        let mut fragment_actions = QueryFragmentActions::create(true, self.fragment_id);
+        match self.fragment_type {
+            FragmentType::Source => {
+                let partitions = self.repartition(executors.len());
+                for (executor, parts) in executors.into_iter().zip(partitions) {
+                    let mut plan = self.plan.clone();
+                    Self::replace_partitions(&mut plan, &parts);
+                    fragment_actions.add_action(QueryFragmentAction::create_v2(executor, plan));
+                }
+            }
+            _ => {
+                for executor in executors {
+                    let plan = self.plan.clone();
+                    fragment_actions.add_action(QueryFragmentAction::create_v2(executor, plan));
+                }
+            }
+        }
+
+        fragment_actions.set_exchange(self.create_data_exchange(destinations, actions));
+        actions.add_fragment_actions(fragment_actions)
+    }
+
+    fn create_data_exchange(
+        &self,
+        destinations: &[String],
+        actions: &QueryFragmentsActions,
+    ) -> DataExchange {
+        if destinations.len() == 1 {
+            // Gather data to the consumer which is executed on the local node only
+            return MergeExchange::create(destinations[0].clone());
+        }
+
+        match &self.exchange {
+            Some((ExchangeKind::Hash, keys)) => {
+                ShuffleDataExchangeV2::create(destinations.to_vec(), keys.clone())
+            }
+            Some((ExchangeKind::Broadcast, _)) => BroadcastExchange::create(destinations.to_vec()),
+            Some((ExchangeKind::Merge, _)) | None => {
+                MergeExchange::create(actions.get_local_executor())
+            }
+        }
+    }
+
+    fn get_executors(&self, actions: &QueryFragmentsActions) -> Vec<String> {
+        match self.fragment_type {
+            FragmentType::Serial => vec![actions.get_local_executor()],
+            FragmentType::Source | FragmentType::Intermediate => actions.get_executors(),
+        }
+    }
+
+    fn repartition(&self, new_size: usize) -> Vec<Partitions> {
+        let partitions = Self::get_partitions(&self.plan).unwrap_or_default();
+
+        // We always put adjacent partitions in the same node
+        let parts_per_node = partitions.len() / new_size;
+        let mut nodes_parts = Vec::with_capacity(new_size);
+        for index in 0..new_size {
+            let begin = parts_per_node * index;
+            let end = parts_per_node * (index + 1);
+            nodes_parts.push(partitions[begin..end].to_vec());
+        }
+
+        // For some irregular partitions, we assign them to the head nodes
+        let begin = parts_per_node * new_size;
+        for (index, part) in partitions[begin..].iter().enumerate() {
+            nodes_parts[index].push(part.clone());
+        }
+
+        nodes_parts
+    }
+
+    fn get_partitions(plan: &PhysicalPlan) -> Option<Partitions> {
+        match plan {
+            PhysicalPlan::TableScan { source, .. } => Some(source.parts.clone()),
+            _ => plan.inputs().into_iter().find_map(Self::get_partitions),
+        }
+    }
+
+    fn replace_partitions(plan: &mut PhysicalPlan, parts: &Partitions) {
+        if let PhysicalPlan::TableScan { source, .. } = plan {
+            source.parts = parts.clone();
+            return;
+        }
+
+        for input in plan.inputs_mut() {
+            Self::replace_partitions(input, parts);
+        }
+    }
+}
@@ -13,12 +13,16 @@
 // limitations under the License.
 
 mod expression_builder;
+mod fragmenter;
 mod physical_plan;
 mod physical_plan_builder;
 mod pipeline_builder;
 mod util;
 
 pub use expression_builder::ExpressionBuilder;
+pub use fragmenter::FragmentType;
+pub use fragmenter::Fragmenter;
+pub use fragmenter::PlanFragment;
 pub use physical_plan::*;
 pub use physical_plan_builder::PhysicalPlanBuilder;
 pub use pipeline_builder::PipelineBuilder;
@@ -25,8 +25,10 @@ use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
 use common_datavalues::NullableType;
 use common_datavalues::ToDataType;
+use common_datavalues::UInt32Type;
 use common_datavalues::Vu8;
 use common_exception::Result;
+use common_functions::window::WindowFrame;
 use common_planners::ReadDataSourcePlan;
 
 use crate::sql::plans::JoinType;
@@ -52,6 +54,14 @@ pub enum PhysicalPlan {
         input: Box<PhysicalPlan>,
         scalars: Vec<(PhysicalScalar, ColumnID)>,
     },
+    /// Duplicate each row of `input` for every grouping set, the group items
+    /// absent from a grouping set are replaced with NULL.
+    AggregateExpand {
+        input: Box<PhysicalPlan>,
+        group_by: Vec<ColumnID>,
+        grouping_id: ColumnID,
+        grouping_sets: Vec<Vec<ColumnID>>,
+    },
     AggregatePartial {
         input: Box<PhysicalPlan>,
         group_by: Vec<ColumnID>,
@@ -81,6 +91,22 @@ pub enum PhysicalPlan {
         join_type: JoinType,
         marker_index: Option<IndexType>,
     },
+    /// Sort-merge join, both `left` and `right` are sorted by the join keys.
+    MergeJoin {
+        left: Box<PhysicalPlan>,
+        right: Box<PhysicalPlan>,
+        left_keys: Vec<PhysicalScalar>,
+        right_keys: Vec<PhysicalScalar>,
+        other_conditions: Vec<PhysicalScalar>,
+        join_type: JoinType,
+    },
+    /// Block nested-loop join, `right` is buffered and joined with every block of `left`.
+    NestedLoopJoin {
+        left: Box<PhysicalPlan>,
+        right: Box<PhysicalPlan>,
+        conditions: Vec<PhysicalScalar>,
+        join_type: JoinType,
+    },
     CrossApply {
         input: Box<PhysicalPlan>,
         subquery: Box<PhysicalPlan>,
@@ -89,6 +115,34 @@ pub enum PhysicalPlan {
     Max1Row {
         input: Box<PhysicalPlan>,
     },
+    Window {
+        input: Box<PhysicalPlan>,
+        window_func: WindowFunctionDesc,
+    },
+
+    /// Redistribute output of `input` among the nodes of cluster.
+    Exchange {
+        input: Box<PhysicalPlan>,
+        kind: ExchangeKind,
+        keys: Vec<PhysicalScalar>,
+    },
+    /// Receive data sent by the fragment `source_fragment_id`, only
+    /// available after the plan is split into fragments.
+    ExchangeSource {
+        schema: DataSchemaRef,
+        query_id: String,
+        source_fragment_id: usize,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ExchangeKind {
+    /// Shuffle data by hash of keys
+    Hash,
+    /// Send all data to every node
+    Broadcast,
+    /// Gather data to the local node
+    Merge,
 }
 
 impl PhysicalPlan {
@@ -124,6 +178,27 @@ impl PhysicalPlan {
                 }
                 Ok(DataSchemaRefExt::create(fields))
             }
+            PhysicalPlan::AggregateExpand {
+                input,
+                group_by,
+                grouping_id,
+                ..
+            } => {
+                let input_schema = input.output_schema()?;
+                let mut fields = Vec::with_capacity(input_schema.num_fields() + 1);
+                for field in input_schema.fields() {
+                    if group_by.contains(field.name()) {
+                        fields.push(DataField::new(
+                            field.name().as_str(),
+                            wrap_nullable(field.data_type()),
+                        ));
+                    } else {
+                        fields.push(field.clone());
+                    }
+                }
+                fields.push(DataField::new(grouping_id.as_str(), UInt32Type::new_impl()));
+                Ok(DataSchemaRefExt::create(fields))
+            }
             PhysicalPlan::AggregatePartial {
                 input,
                 group_by,
@@ -205,6 +280,15 @@ impl PhysicalPlan {
                 }
                 Ok(DataSchemaRefExt::create(fields))
             }
+            PhysicalPlan::MergeJoin { left, right, .. }
+            | PhysicalPlan::NestedLoopJoin { left, right, .. } => Ok(DataSchemaRefExt::create(
+                left.output_schema()?
+                    .fields()
+                    .iter()
+                    .chain(right.output_schema()?.fields().iter())
+                    .cloned()
+                    .collect(),
+            )),
             PhysicalPlan::CrossApply {
                 input, subquery, ..
             } => Ok(DataSchemaRefExt::create(
@@ -217,8 +301,77 @@ impl PhysicalPlan {
                     .collect(),
             )),
             PhysicalPlan::Max1Row { input, .. } => input.output_schema(),
+            PhysicalPlan::Window { input, window_func } => {
+                let input_schema = input.output_schema()?;
+                let mut fields = input_schema.fields().clone();
+                fields.push(DataField::new(
+                    window_func.column_id.as_str(),
+                    window_func.return_type.clone(),
+                ));
+                Ok(DataSchemaRefExt::create(fields))
+            }
+            PhysicalPlan::Exchange { input, .. } => input.output_schema(),
+            PhysicalPlan::ExchangeSource { schema, .. } => Ok(schema.clone()),
+        }
+    }
+
+    /// Inputs of current plan. The subquery of `CrossApply` is excluded
+    /// since it's always executed locally.
+    pub fn inputs(&self) -> Vec<&PhysicalPlan> {
+        match self {
+            PhysicalPlan::TableScan { .. } | PhysicalPlan::ExchangeSource { .. } => vec![],
+            PhysicalPlan::Filter { input, .. }
+            | PhysicalPlan::Project { input, .. }
+            | PhysicalPlan::EvalScalar { input, .. }
+            | PhysicalPlan::AggregateExpand { input, .. }
+            | PhysicalPlan::AggregatePartial { input, .. }
+            | PhysicalPlan::AggregateFinal { input, .. }
+            | PhysicalPlan::Sort { input, .. }
+            | PhysicalPlan::Limit { input, .. }
+            | PhysicalPlan::CrossApply { input, .. }
+            | PhysicalPlan::Max1Row { input }
+            | PhysicalPlan::Window { input, .. }
+            | PhysicalPlan::Exchange { input, .. } => vec![input.as_ref()],
+            PhysicalPlan::HashJoin { build, probe, .. } => vec![build.as_ref(), probe.as_ref()],
+            PhysicalPlan::MergeJoin { left, right, .. }
+            | PhysicalPlan::NestedLoopJoin { left, right, .. } => {
+                vec![left.as_ref(), right.as_ref()]
+            }
+        }
+    }
+
+    /// Mutable version of `inputs`.
+    pub fn inputs_mut(&mut self) -> Vec<&mut PhysicalPlan> {
+        match self {
+            PhysicalPlan::TableScan { .. } | PhysicalPlan::ExchangeSource { .. } => vec![],
+            PhysicalPlan::Filter { input, .. }
+            | PhysicalPlan::Project { input, .. }
+            | PhysicalPlan::EvalScalar { input, .. }
+            | PhysicalPlan::AggregateExpand { input, .. }
+            | PhysicalPlan::AggregatePartial { input, .. }
+            | PhysicalPlan::AggregateFinal { input, .. }
+            | PhysicalPlan::Sort { input, .. }
+            | PhysicalPlan::Limit { input, .. }
+            | PhysicalPlan::CrossApply { input, .. }
+            | PhysicalPlan::Max1Row { input }
+            | PhysicalPlan::Window { input, .. }
+            | PhysicalPlan::Exchange { input, .. } => vec![input.as_mut()],
+            PhysicalPlan::HashJoin { build, probe, .. } => vec![build.as_mut(), probe.as_mut()],
+            PhysicalPlan::MergeJoin { left, right, .. }
+            | PhysicalPlan::NestedLoopJoin { left, right, .. } => {
+                vec![left.as_mut(), right.as_mut()]
+            }
         }
     }
+
+    /// Whether the plan contains `Exchange` and needs to be executed in cluster.
+    pub fn is_distributed_plan(&self) -> bool {
+        matches!(self, PhysicalPlan::Exchange { .. })
+            || self
+                .inputs()
+                .iter()
+                .any(|input| input.is_distributed_plan())
+    }
 }
 
 /// Serializable and desugared representation of `Scalar`.
@@ -275,3 +428,16 @@ pub struct SortDesc {
     pub nulls_first: bool,
     pub order_by: ColumnID,
 }
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowFunctionDesc {
+    /// Name of a built-in window function or an aggregate function
+    pub name: String,
+    pub params: Vec<DataValue>,
+    pub args: Vec<ColumnID>,
+    pub partition_by: Vec<ColumnID>,
+    pub order_by: Vec<SortDesc>,
+    pub frame: WindowFrame,
+    pub return_type: DataTypeImpl,
+    pub column_id: ColumnID,
+}
@@ -21,10 +21,15 @@ use itertools::Itertools;
 use crate::sql::exec::AggregateFunctionDesc;
 use crate::sql::exec::AggregateFunctionSignature;
 use crate::sql::exec::ColumnID;
+use crate::sql::exec::ExchangeKind;
 use crate::sql::exec::PhysicalPlan;
 use crate::sql::exec::PhysicalScalar;
 use crate::sql::exec::SortDesc;
+use crate::sql::exec::WindowFunctionDesc;
 use crate::sql::optimizer::SExpr;
+use crate::sql::plans::Aggregate;
+use crate::sql::plans::AggregateMode;
+use crate::sql::plans::Exchange;
 use crate::sql::plans::RelOperator;
 use crate::sql::plans::Scalar;
 use crate::sql::MetadataRef;
@@ -87,6 +92,56 @@ impl PhysicalPlanBuilder {
                     marker_index: join.marker_index,
                 })
             }
+            RelOperator::PhysicalMergeJoin(join) => {
+                let left = self.build(s_expr.child(0)?)?;
+                let right = self.build(s_expr.child(1)?)?;
+                Ok(PhysicalPlan::MergeJoin {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    left_keys: join
+                        .left_keys
+                        .iter()
+                        .map(|v| {
+                            let mut builder = PhysicalScalarBuilder;
+                            builder.build(v)
+                        })
+                        .collect::<Result<_>>()?,
+                    right_keys: join
+                        .right_keys
+                        .iter()
+                        .map(|v| {
+                            let mut builder = PhysicalScalarBuilder;
+                            builder.build(v)
+                        })
+                        .collect::<Result<_>>()?,
+                    other_conditions: join
+                        .other_conditions
+                        .iter()
+                        .map(|v| {
+                            let mut builder = PhysicalScalarBuilder;
+                            builder.build(v)
+                        })
+                        .collect::<Result<_>>()?,
+                    join_type: join.join_type.clone(),
+                })
+            }
+            RelOperator::PhysicalNestedLoopJoin(join) => {
+                let left = self.build(s_expr.child(0)?)?;
+                let right = self.build(s_expr.child(1)?)?;
+                Ok(PhysicalPlan::NestedLoopJoin {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    conditions: join
+                        .conditions
+                        .iter()
+                        .map(|v| {
+                            let mut builder = PhysicalScalarBuilder;
+                            builder.build(v)
+                        })
+                        .collect::<Result<_>>()?,
+                    join_type: join.join_type.clone(),
+                })
+            }
             RelOperator::Project(project) => {
                 let input = self.build(s_expr.child(0)?)?;
                 let input_schema = input.output_schema()?;
@@ -125,51 +180,14 @@ impl PhysicalPlanBuilder {
             }),
             RelOperator::Aggregate(agg) => {
                 let input = self.build(s_expr.child(0)?)?;
-                let group_items: Vec<ColumnID> = agg
-                    .group_items
-                    .iter()
-                    .map(|v| v.index.to_string())
-                    .collect();
-                let agg_funcs: Vec<AggregateFunctionDesc> = agg.aggregate_functions.iter().map(|v| {
-                    if let Scalar::AggregateFunction(agg) = &v.scalar {
-                        Ok(AggregateFunctionDesc {
-                            sig: AggregateFunctionSignature {
-                                name: agg.func_name.clone(),
-                                args: agg.args.iter().map(|s| {
-                                    s.data_type()
-                                }).collect(),
-                                params: agg.params.clone(),
-                                return_type: agg.return_type.clone(),
-                            },
-                            column_id: v.index.to_string(),
-                            args: agg.args.iter().map(|arg| {
-                                if let Scalar::BoundColumnRef(col) = arg {
-                                    Ok(col.column.index.to_string())
-                                } else {
-                                    Err(ErrorCode::LogicalError(
-                                        "Aggregate function argument must be a BoundColumnRef".to_string()
-                                    ))
-                                }
-                            }).collect::<Result<_>>()?,
-                        })
-                    } else {
-                        Err(ErrorCode::LogicalError("Expected aggregate function".to_string()))
+                match agg.mode {
+                    AggregateMode::Partial => self.build_aggregate_partial(agg, input),
+                    AggregateMode::Final => self.build_aggregate_final(agg, input),
+                    AggregateMode::Initial => {
+                        let partial_agg = self.build_aggregate_partial(agg, input)?;
+                        self.build_aggregate_final(agg, partial_agg)
                     }
-                }).collect::<Result<_>>()?;
-                let before_group_by_schema = input.output_schema()?;
-                let partial_agg = PhysicalPlan::AggregatePartial {
-                    input: Box::new(input),
-                    group_by: group_items.clone(),
-                    agg_funcs: agg_funcs.clone(),
-                };
-                let final_agg = PhysicalPlan::AggregateFinal {
-                    input: Box::new(partial_agg),
-                    group_by: group_items,
-                    agg_funcs,
-                    before_group_by_schema,
-                };
-
-                Ok(final_agg)
+                }
             }
             RelOperator::Sort(sort) => Ok(PhysicalPlan::Sort {
                 input: Box::new(self.build(s_expr.child(0)?)?),
@@ -200,6 +218,76 @@ impl PhysicalPlanBuilder {
             RelOperator::Max1Row(_) => Ok(PhysicalPlan::Max1Row {
                 input: Box::new(self.build(s_expr.child(0)?)?),
             }),
+            RelOperator::Window(window) => {
+                let column_id = |scalar: &Scalar| {
+                    if let Scalar::BoundColumnRef(col) = scalar {
+                        Ok(col.column.index.to_string())
+                    } else {
+                        Err(ErrorCode::LogicalError(
+                            "Window function argument must be a BoundColumnRef".to_string(),
+                        ))
+                    }
+                };
+                let func = &window.function;
+                Ok(PhysicalPlan::Window {
+                    input: Box::new(self.build(s_expr.child(0)?)?),
+                    window_func: WindowFunctionDesc {
+                        name: func.func_name.clone(),
+                        params: func.params.clone(),
+                        args: func.args.iter().map(column_id).collect::<Result<_>>()?,
+                        partition_by: func
+                            .partition_by
+                            .iter()
+                            .map(column_id)
+                            .collect::<Result<_>>()?,
+                        order_by: func
+                            .order_by
+                            .iter()
+                            .map(|item| {
+                                Ok(SortDesc {
+                                    asc: item.asc,
+                                    nulls_first: item.nulls_first,
+                                    order_by: column_id(&item.expr)?,
+                                })
+                            })
+                            .collect::<Result<_>>()?,
+                        frame: func.frame,
+                        return_type: func.return_type.clone(),
+                        column_id: window.index.to_string(),
+                    },
+                })
+            }
+            RelOperator::Exchange(exchange) => {
+                let input = Box::new(self.build(s_expr.child(0)?)?);
+                let (kind, keys) = match exchange {
+                    // The partial states are shuffled by the hash key of groups
+                    Exchange::Hash(_)
+                        if matches!(*input, PhysicalPlan::AggregatePartial { .. }) =>
+                    {
+                        let group_by_key = input
+                            .output_schema()?
+                            .field_with_name("_group_by_key")?
+                            .data_type()
+                            .clone();
+                        (ExchangeKind::Hash, vec![PhysicalScalar::Variable {
+                            column_id: "_group_by_key".to_string(),
+                            data_type: group_by_key,
+                        }])
+                    }
+                    Exchange::Hash(keys) => (
+                        ExchangeKind::Hash,
+                        keys.iter()
+                            .map(|key| {
+                                let mut builder = PhysicalScalarBuilder;
+                                builder.build(key)
+                            })
+                            .collect::<Result<_>>()?,
+                    ),
+                    Exchange::Broadcast => (ExchangeKind::Broadcast, vec![]),
+                    Exchange::Merge => (ExchangeKind::Merge, vec![]),
+                };
+                Ok(PhysicalPlan::Exchange { input, kind, keys })
+            }
 
             _ => Err(ErrorCode::LogicalError(format!(
                 "Unsupported physical plan: {:?}",
@@ -207,6 +295,106 @@ impl PhysicalPlanBuilder {
             ))),
         }
     }
+
+    fn build_aggregate_partial(
+        &self,
+        agg: &Aggregate,
+        input: PhysicalPlan,
+    ) -> Result<PhysicalPlan> {
+        let (group_items, agg_funcs) = Self::aggregate_items(agg)?;
+        // Expand the rows for grouping sets before aggregation, the grouping id
+        // is aggregated as a group item to distinguish the grouping sets.
+        let input = match &agg.grouping_sets {
+            Some(grouping_sets) => PhysicalPlan::AggregateExpand {
+                input: Box::new(input),
+                group_by: group_items[..group_items.len() - 1].to_vec(),
+                grouping_id: grouping_sets.grouping_id_index.to_string(),
+                grouping_sets: grouping_sets
+                    .sets
+                    .iter()
+                    .map(|set| set.iter().map(|index| index.to_string()).collect())
+                    .collect(),
+            },
+            None => input,
+        };
+        Ok(PhysicalPlan::AggregatePartial {
+            input: Box::new(input),
+            group_by: group_items,
+            agg_funcs,
+        })
+    }
+
+    fn build_aggregate_final(&self, agg: &Aggregate, input: PhysicalPlan) -> Result<PhysicalPlan> {
+        let (group_items, agg_funcs) = Self::aggregate_items(agg)?;
+        // The partial aggregation may be shuffled by an exchange
+        let partial_agg = match &input {
+            PhysicalPlan::Exchange { input, .. } => input.as_ref(),
+            input => input,
+        };
+        let before_group_by_schema = match partial_agg {
+            PhysicalPlan::AggregatePartial { input, .. } => input.output_schema()?,
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "Final aggregation must be built upon a partial aggregation".to_string(),
+                ));
+            }
+        };
+        Ok(PhysicalPlan::AggregateFinal {
+            input: Box::new(input),
+            group_by: group_items,
+            agg_funcs,
+            before_group_by_schema,
+        })
+    }
+
+    // Returns the group items, including the grouping id of grouping sets if any,
+    // and the aggregate functions of the aggregation.
+    fn aggregate_items(agg: &Aggregate) -> Result<(Vec<ColumnID>, Vec<AggregateFunctionDesc>)> {
+        let mut group_items: Vec<ColumnID> = agg
+            .group_items
+            .iter()
+            .map(|v| v.index.to_string())
+            .collect();
+        if let Some(grouping_sets) = &agg.grouping_sets {
+            group_items.push(grouping_sets.grouping_id_index.to_string());
+        }
+        let agg_funcs: Vec<AggregateFunctionDesc> = agg
+            .aggregate_functions
+            .iter()
+            .map(|v| {
+                if let Scalar::AggregateFunction(agg) = &v.scalar {
+                    Ok(AggregateFunctionDesc {
+                        sig: AggregateFunctionSignature {
+                            name: agg.func_name.clone(),
+                            args: agg.args.iter().map(|s| s.data_type()).collect(),
+                            params: agg.params.clone(),
+                            return_type: agg.return_type.clone(),
+                        },
+                        column_id: v.index.to_string(),
+                        args: agg
+                            .args
+                            .iter()
+                            .map(|arg| {
+                                if let Scalar::BoundColumnRef(col) = arg {
+                                    Ok(col.column.index.to_string())
+                                } else {
+                                    Err(ErrorCode::LogicalError(
+                                        "Aggregate function argument must be a BoundColumnRef"
+                                            .to_string(),
+                                    ))
+                                }
+                            })
+                            .collect::<Result<_>>()?,
+                    })
+                } else {
+                    Err(ErrorCode::LogicalError(
+                        "Expected aggregate function".to_string(),
+                    ))
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok((group_items, agg_funcs))
+    }
 }
 
 pub struct PhysicalScalarBuilder;
@@ -17,6 +17,7 @@ use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use common_datablocks::SortColumnDescription;
+use common_datavalues::remove_nullable;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
@@ -29,6 +30,7 @@ use common_planners::ReadDataSourcePlan;
 
 use crate::common::EvalNode;
 use crate::common::Evaluator;
+use crate::common::RuntimeFilter;
 use crate::pipelines::new::processors::port::InputPort;
 use crate::pipelines::new::processors::transforms::ExpressionTransformV2;
 use crate::pipelines::new::processors::transforms::TransformFilterV2;
@@ -37,17 +39,25 @@ use crate::pipelines::new::processors::transforms::TransformRename;
 use crate::pipelines::new::processors::AggregatorParams;
 use crate::pipelines::new::processors::AggregatorTransformParams;
 use crate::pipelines::new::processors::HashJoinState;
+use crate::pipelines::new::processors::JoinBuffer;
 use crate::pipelines::new::processors::JoinHashTable;
 use crate::pipelines::new::processors::SinkBuildHashTable;
+use crate::pipelines::new::processors::SinkJoinBuffer;
 use crate::pipelines::new::processors::Sinker;
 use crate::pipelines::new::processors::SortMergeCompactor;
 use crate::pipelines::new::processors::TransformAggregator;
 use crate::pipelines::new::processors::TransformApply;
+use crate::pipelines::new::processors::TransformExpandGroupingSets;
 use crate::pipelines::new::processors::TransformHashJoinProbe;
 use crate::pipelines::new::processors::TransformLimit;
 use crate::pipelines::new::processors::TransformMax1Row;
+use crate::pipelines::new::processors::TransformMergeJoin;
+use crate::pipelines::new::processors::TransformNestedLoopJoin;
 use crate::pipelines::new::processors::TransformSortMerge;
 use crate::pipelines::new::processors::TransformSortPartial;
+use crate::pipelines::new::processors::TransformSortSpill;
+use crate::pipelines::new::processors::TransformWindowFunc;
+use crate::pipelines::new::processors::WindowFuncCompactor;
 use crate::pipelines::new::NewPipeline;
 use crate::pipelines::new::SinkPipeBuilder;
 use crate::sessions::QueryContext;
@@ -56,6 +66,7 @@ use crate::sql::exec::physical_plan::PhysicalPlan;
 use crate::sql::exec::AggregateFunctionDesc;
 use crate::sql::exec::PhysicalScalar;
 use crate::sql::exec::SortDesc;
+use crate::sql::exec::WindowFunctionDesc;
 use crate::sql::plans::JoinType;
 use crate::sql::ColumnBinding;
 use crate::sql::IndexType;
@@ -63,6 +74,8 @@ use crate::sql::IndexType;
 #[derive(Default)]
 pub struct PipelineBuilder {
     pub pipelines: Vec<NewPipeline>,
+    /// The runtime filters of hash joins to be pushed down to the table scans
+    runtime_filters: Vec<(ColumnID, Arc<RuntimeFilter>)>,
 }
 
 impl PipelineBuilder {
@@ -76,6 +89,21 @@ impl PipelineBuilder {
         plan: &PhysicalPlan,
         pipeline: &mut NewPipeline,
     ) -> Result<()> {
+        // Runtime filters can only be pushed down through the operators that neither
+        // combine nor limit the rows of the scans below them.
+        if !matches!(
+            plan,
+            PhysicalPlan::TableScan { .. }
+                | PhysicalPlan::Filter { .. }
+                | PhysicalPlan::Project { .. }
+                | PhysicalPlan::EvalScalar { .. }
+                | PhysicalPlan::Sort { .. }
+                | PhysicalPlan::HashJoin { .. }
+                | PhysicalPlan::Exchange { .. }
+        ) {
+            self.runtime_filters.clear();
+        }
+
         match plan {
             PhysicalPlan::TableScan {
                 name_mapping,
@@ -100,6 +128,20 @@ impl PipelineBuilder {
                 self.build_pipeline(context.clone(), input, pipeline)?;
                 self.build_eval_scalar(context, scalars, pipeline)
             }
+            PhysicalPlan::AggregateExpand {
+                input,
+                group_by,
+                grouping_sets,
+                ..
+            } => {
+                self.build_pipeline(context, input, pipeline)?;
+                self.build_aggregate_expand(
+                    plan.output_schema()?,
+                    group_by,
+                    grouping_sets,
+                    pipeline,
+                )
+            }
             PhysicalPlan::AggregatePartial {
                 input,
                 group_by,
@@ -152,9 +194,19 @@ impl PipelineBuilder {
                 join_type,
                 marker_index,
             } => {
+                let runtime_filters =
+                    Self::create_runtime_filters(&context, build_keys, probe_keys, join_type)?;
+                let mut probe_side_filters = std::mem::take(&mut self.runtime_filters);
+                for (key_index, runtime_filter) in runtime_filters.iter() {
+                    if let PhysicalScalar::Variable { column_id, .. } = &probe_keys[*key_index] {
+                        probe_side_filters.push((column_id.clone(), runtime_filter.clone()));
+                    }
+                }
+
                 let mut build_side_pipeline = NewPipeline::create();
                 let build_side_context = QueryContext::create_from(context.clone());
                 self.build_pipeline(build_side_context, build, &mut build_side_pipeline)?;
+                self.runtime_filters = probe_side_filters;
                 self.build_pipeline(context.clone(), probe, pipeline)?;
                 self.build_hash_join(
                     context,
@@ -165,11 +217,45 @@ impl PipelineBuilder {
                     other_conditions,
                     join_type.clone(),
                     *marker_index,
+                    runtime_filters,
                     build_side_pipeline,
                     pipeline,
                 )?;
                 Ok(())
             }
+            PhysicalPlan::MergeJoin {
+                left,
+                right,
+                left_keys,
+                right_keys,
+                other_conditions,
+                ..
+            } => {
+                let mut right_side_pipeline = NewPipeline::create();
+                let right_side_context = QueryContext::create_from(context.clone());
+                self.build_pipeline(right_side_context, right, &mut right_side_pipeline)?;
+                self.build_pipeline(context.clone(), left, pipeline)?;
+                self.build_merge_join(
+                    context,
+                    left_keys,
+                    right_keys,
+                    other_conditions,
+                    right_side_pipeline,
+                    pipeline,
+                )
+            }
+            PhysicalPlan::NestedLoopJoin {
+                left,
+                right,
+                conditions,
+                ..
+            } => {
+                let mut right_side_pipeline = NewPipeline::create();
+                let right_side_context = QueryContext::create_from(context.clone());
+                self.build_pipeline(right_side_context, right, &mut right_side_pipeline)?;
+                self.build_pipeline(context.clone(), left, pipeline)?;
+                self.build_nested_loop_join(context, conditions, right_side_pipeline, pipeline)
+            }
             v @ PhysicalPlan::CrossApply {
                 input,
                 subquery,
@@ -188,6 +274,22 @@ impl PipelineBuilder {
                 self.build_pipeline(context, input, pipeline)?;
                 self.build_max_one_row(pipeline)
             }
+            PhysicalPlan::Window { input, window_func } => {
+                self.build_pipeline(context, input, pipeline)?;
+                self.build_window(input.output_schema()?, window_func, pipeline)
+            }
+            // Exchange is a no-op while executing plan on a single node
+            PhysicalPlan::Exchange { input, .. } => self.build_pipeline(context, input, pipeline),
+            PhysicalPlan::ExchangeSource {
+                schema,
+                query_id,
+                source_fragment_id,
+            } => context.get_exchange_manager().get_fragment_source(
+                query_id.clone(),
+                *source_fragment_id,
+                schema.clone(),
+                pipeline,
+            ),
         }
     }
 
@@ -234,7 +336,19 @@ impl PipelineBuilder {
     ) -> Result<()> {
         let table = context.build_table_from_source_plan(source)?;
         context.try_set_partitions(source.parts.clone())?;
+        let runtime_filters = std::mem::take(&mut self.runtime_filters)
+            .into_iter()
+            .filter_map(|(column_id, runtime_filter)| {
+                name_mapping
+                    .iter()
+                    .find(|(_, id)| **id == column_id)
+                    .map(|(name, _)| (name.clone(), runtime_filter))
+            })
+            .collect();
+        // The runtime filters are taken by the table while building its source pipeline.
+        context.set_runtime_filters(runtime_filters);
         table.read2(context.clone(), source, pipeline)?;
+        context.set_runtime_filters(vec![]);
         let schema = source.schema();
         let projections = name_mapping
             .iter()
@@ -358,6 +472,35 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    pub fn build_aggregate_expand(
+        &mut self,
+        output_schema: DataSchemaRef,
+        group_by: &[ColumnID],
+        grouping_sets: &[Vec<ColumnID>],
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        let group_by_positions = group_by
+            .iter()
+            .map(|id| output_schema.index_of(id.as_str()))
+            .collect::<Result<Vec<_>>>()?;
+        let grouping_sets: Vec<Vec<bool>> = grouping_sets
+            .iter()
+            .map(|set| group_by.iter().map(|id| set.contains(id)).collect())
+            .collect();
+
+        pipeline.add_transform(|input, output| {
+            Ok(TransformExpandGroupingSets::create(
+                input,
+                output,
+                output_schema.clone(),
+                group_by_positions.clone(),
+                grouping_sets.clone(),
+            ))
+        })?;
+
+        Ok(())
+    }
+
     pub fn build_aggregate_final(
         &mut self,
         context: Arc<QueryContext>,
@@ -430,7 +573,7 @@ impl PipelineBuilder {
 
     pub fn build_sort(
         &mut self,
-        _context: Arc<QueryContext>,
+        context: Arc<QueryContext>,
         order_by: &[SortDesc],
         pipeline: &mut NewPipeline,
     ) -> Result<()> {
@@ -448,6 +591,15 @@ impl PipelineBuilder {
             TransformSortPartial::try_create(input, output, None, sort_desc.clone())
         })?;
 
+        if context.get_settings().get_spill_memory_budget()? != 0 {
+            // External merge sort in single thread, which spills to disk if exceeding the budget
+            pipeline.resize(1)?;
+            pipeline.add_transform(|input, output| {
+                TransformSortSpill::try_create(context.clone(), input, output, sort_desc.clone())
+            })?;
+            return Ok(());
+        }
+
         // Merge
         pipeline.add_transform(|input, output| {
             TransformSortMerge::try_create(
@@ -495,12 +647,47 @@ impl PipelineBuilder {
         other_conditions: &[PhysicalScalar],
         join_type: JoinType,
         marker_index: Option<IndexType>,
+        runtime_filters: Vec<(usize, Arc<RuntimeFilter>)>,
         mut child_pipeline: NewPipeline,
         pipeline: &mut NewPipeline,
     ) -> Result<()> {
-        let predicate = other_conditions.iter().cloned().fold(
-            Result::<Option<PhysicalScalar>>::Ok(None),
-            |acc, next| {
+        let predicate = Self::conjunct_predicates(other_conditions)?;
+        let hash_join_state = JoinHashTable::create_join_state(
+            ctx.clone(),
+            join_type,
+            build_keys,
+            probe_keys,
+            predicate.as_ref(),
+            build_schema,
+            marker_index,
+            runtime_filters,
+        )?;
+
+        // Build side
+        self.build_sink_hash_table(hash_join_state.clone(), &mut child_pipeline)?;
+
+        // Probe side
+        pipeline.add_transform(|input, output| {
+            TransformHashJoinProbe::create(
+                ctx.clone(),
+                input,
+                output,
+                hash_join_state.clone(),
+                output_schema.clone(),
+            )
+        })?;
+
+        self.pipelines.push(child_pipeline);
+
+        Ok(())
+    }
+
+    /// Combines the predicates with `and`, returns `None` if there is no predicate.
+    fn conjunct_predicates(predicates: &[PhysicalScalar]) -> Result<Option<PhysicalScalar>> {
+        predicates
+            .iter()
+            .cloned()
+            .fold(Result::<Option<PhysicalScalar>>::Ok(None), |acc, next| {
                 if let Ok(None) = acc {
                     Ok(Some(next))
                 } else if let Ok(Some(prev)) = acc {
@@ -519,37 +706,147 @@ impl PipelineBuilder {
                 } else {
                     acc
                 }
-            },
-        )?;
-        let hash_join_state = JoinHashTable::create_join_state(
-            ctx.clone(),
-            join_type,
-            build_keys,
-            probe_keys,
-            predicate.as_ref(),
-            build_schema,
-            marker_index,
-        )?;
+            })
+    }
 
-        // Build side
-        self.build_sink_hash_table(hash_join_state.clone(), &mut child_pipeline)?;
+    fn build_merge_join(
+        &mut self,
+        ctx: Arc<QueryContext>,
+        left_keys: &[PhysicalScalar],
+        right_keys: &[PhysicalScalar],
+        other_conditions: &[PhysicalScalar],
+        mut right_side_pipeline: NewPipeline,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        // The order of rows is only preserved within a single stream
+        if pipeline.output_len() != 1 || right_side_pipeline.output_len() != 1 {
+            return Err(ErrorCode::LogicalError(
+                "Inputs of merge join must be single sorted streams".to_string(),
+            ));
+        }
 
-        // Probe side
+        let left_keys = left_keys
+            .iter()
+            .map(Evaluator::eval_physical_scalar)
+            .collect::<Result<Vec<EvalNode<ColumnID>>>>()?;
+        let right_keys = right_keys
+            .iter()
+            .map(Evaluator::eval_physical_scalar)
+            .collect::<Result<Vec<EvalNode<ColumnID>>>>()?;
+        let other_predicate = match Self::conjunct_predicates(other_conditions)? {
+            Some(predicate) => Some(Evaluator::eval_physical_scalar(&predicate)?),
+            None => None,
+        };
+        let func_ctx = ctx.try_get_function_context()?;
+
+        // Right side
+        let buffer = JoinBuffer::create();
+        self.build_sink_join_buffer(buffer.clone(), &mut right_side_pipeline)?;
+
+        // Left side
         pipeline.add_transform(|input, output| {
-            Ok(TransformHashJoinProbe::create(
-                ctx.clone(),
+            Ok(TransformMergeJoin::create(
                 input,
                 output,
-                hash_join_state.clone(),
-                output_schema.clone(),
+                buffer.clone(),
+                left_keys.clone(),
+                right_keys.clone(),
+                other_predicate.clone(),
+                func_ctx.clone(),
             ))
         })?;
 
-        self.pipelines.push(child_pipeline);
+        self.pipelines.push(right_side_pipeline);
 
         Ok(())
     }
 
+    fn build_nested_loop_join(
+        &mut self,
+        ctx: Arc<QueryContext>,
+        conditions: &[PhysicalScalar],
+        mut right_side_pipeline: NewPipeline,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        let predicate = Self::conjunct_predicates(conditions)?.ok_or_else(|| {
+            ErrorCode::LogicalError("Invalid empty condition list of nested-loop join")
+        })?;
+        let predicate = Evaluator::eval_physical_scalar(&predicate)?;
+        let func_ctx = ctx.try_get_function_context()?;
+        let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+
+        // Right side
+        let buffer = JoinBuffer::create();
+        self.build_sink_join_buffer(buffer.clone(), &mut right_side_pipeline)?;
+
+        // Left side
+        pipeline.add_transform(|input, output| {
+            Ok(TransformNestedLoopJoin::create(
+                input,
+                output,
+                buffer.clone(),
+                predicate.clone(),
+                func_ctx.clone(),
+                max_block_size,
+            ))
+        })?;
+
+        self.pipelines.push(right_side_pipeline);
+
+        Ok(())
+    }
+
+    fn build_sink_join_buffer(
+        &mut self,
+        buffer: Arc<JoinBuffer>,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        let mut sink_pipeline_builder = SinkPipeBuilder::create();
+        for _ in 0..pipeline.output_len() {
+            let input_port = InputPort::create();
+            sink_pipeline_builder.add_sink(
+                input_port.clone(),
+                Sinker::<SinkJoinBuffer>::create(
+                    input_port,
+                    SinkJoinBuffer::create(buffer.clone()),
+                ),
+            );
+        }
+
+        pipeline.add_pipe(sink_pipeline_builder.finalize());
+        Ok(())
+    }
+
+    /// Creates the runtime filters on the probe keys that are columns of the probe side,
+    /// returns them with the indexes of the keys.
+    fn create_runtime_filters(
+        ctx: &Arc<QueryContext>,
+        build_keys: &[PhysicalScalar],
+        probe_keys: &[PhysicalScalar],
+        join_type: &JoinType,
+    ) -> Result<Vec<(usize, Arc<RuntimeFilter>)>> {
+        // Only the probe rows without matches are dropped by these joins.
+        if ctx.get_settings().get_enable_runtime_filter()? == 0
+            || !matches!(join_type, JoinType::Inner | JoinType::Semi)
+        {
+            return Ok(vec![]);
+        }
+
+        let mut runtime_filters = vec![];
+        for (key_index, (build_key, probe_key)) in build_keys.iter().zip(probe_keys).enumerate() {
+            if let PhysicalScalar::Variable { data_type, .. } = probe_key {
+                let probe_type = remove_nullable(data_type);
+                let type_id = probe_type.data_type_id();
+                let comparable =
+                    type_id.is_numeric() || type_id.is_string() || type_id.is_date_or_date_time();
+                if comparable && probe_type == remove_nullable(&build_key.data_type()) {
+                    runtime_filters.push((key_index, RuntimeFilter::create()));
+                }
+            }
+        }
+        Ok(runtime_filters)
+    }
+
     fn build_sink_hash_table(
         &mut self,
         state: Arc<dyn HashJoinState>,
@@ -598,4 +895,24 @@ impl PipelineBuilder {
 
         Ok(())
     }
+
+    pub fn build_window(
+        &mut self,
+        input_schema: DataSchemaRef,
+        window_func: &WindowFunctionDesc,
+        pipeline: &mut NewPipeline,
+    ) -> Result<()> {
+        // Window function is evaluated over whole partitions, so collect all the data
+        // into a single processor.
+        pipeline.resize(1)?;
+        pipeline.add_transform(|input, output| {
+            TransformWindowFunc::try_create(
+                input,
+                output,
+                WindowFuncCompactor::try_create(window_func, &input_schema)?,
+            )
+        })?;
+
+        Ok(())
+    }
 }
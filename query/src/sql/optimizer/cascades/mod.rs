@@ -15,19 +15,23 @@
 mod explore_rules;
 mod implement_rules;
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+
 use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::sql::optimizer::cascades::explore_rules::get_explore_rule_set;
 use crate::sql::optimizer::cascades::implement_rules::get_implement_rule_set;
-use crate::sql::optimizer::group::Group;
+use crate::sql::optimizer::cost::Cost;
+use crate::sql::optimizer::cost::CostModel;
+use crate::sql::optimizer::cost::DefaultCostModel;
 use crate::sql::optimizer::m_expr::MExpr;
 use crate::sql::optimizer::memo::Memo;
 use crate::sql::optimizer::optimize_context::OptimizeContext;
-use crate::sql::optimizer::rule::RulePtr;
 use crate::sql::optimizer::rule::RuleSet;
 use crate::sql::optimizer::rule::TransformState;
-use crate::sql::optimizer::RequiredProperty;
+use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Operator;
 use crate::sql::IndexType;
@@ -35,56 +39,80 @@ use crate::sql::IndexType;
 /// A cascades-style search engine to enumerate possible alternations of a relational expression and
 /// find the optimal one.
 ///
-/// NOTICE: we don't support cost-based optimization and lower bound searching for now.
-#[allow(dead_code)]
+/// Join orders are enumerated with the commutativity and associativity rules of inner join, and
+/// the plan with the lowest cost estimated by `CostModel` is chosen.
+///
+/// NOTICE: we don't support lower bound searching for now.
 pub struct CascadesOptimizer {
     optimize_context: OptimizeContext,
     memo: Memo,
     explore_rules: RuleSet,
     implement_rules: RuleSet,
+    cost_model: Box<dyn CostModel>,
+
+    explored_groups: HashSet<IndexType>,
+    /// The optimal plan of each optimized group with its cost
+    optimal_plans: HashMap<IndexType, (Cost, SExpr)>,
 }
 
 impl CascadesOptimizer {
-    #[allow(dead_code)]
     pub fn create(optimize_context: OptimizeContext) -> Self {
         CascadesOptimizer {
             optimize_context,
             memo: Memo::create(),
             explore_rules: get_explore_rule_set(),
             implement_rules: get_implement_rule_set(),
+            cost_model: Box::new(DefaultCostModel::new()),
+
+            explored_groups: HashSet::new(),
+            optimal_plans: HashMap::new(),
         }
     }
 
-    #[allow(dead_code)]
     fn init(&mut self, expression: SExpr) -> Result<()> {
         self.memo.init(expression)?;
 
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn optimize(&mut self, expression: SExpr) -> Result<SExpr> {
         self.init(expression)?;
 
-        self.explore_group(self.memo.root().unwrap().group_index())?;
+        let root_index = self.root_group_index()?;
+
+        self.explore_group(root_index)?;
 
-        self.implement_group(self.memo.root().unwrap().group_index())?;
+        self.implement()?;
 
-        self.find_optimal_plan()
+        self.find_optimal_plan(root_index)
+    }
+
+    fn root_group_index(&self) -> Result<IndexType> {
+        self.memo
+            .root()
+            .map(|group| group.group_index())
+            .ok_or_else(|| {
+                ErrorCode::LogicalError("Root group cannot be None after initialization")
+            })
     }
 
-    #[allow(dead_code)]
     fn explore_group(&mut self, group_index: IndexType) -> Result<()> {
-        let group = self.memo.group(group_index);
-        let expressions: Vec<MExpr> = group.iter().cloned().collect();
-        for m_expr in expressions {
+        if !self.explored_groups.insert(group_index) {
+            return Ok(());
+        }
+
+        // Expressions generated by exploration are appended to the group,
+        // they will be explored as well.
+        let mut index = 0;
+        while index < self.memo.group(group_index).num_exprs() {
+            let m_expr = self.memo.group(group_index).m_expr(index).clone();
             self.explore_expr(m_expr)?;
+            index += 1;
         }
 
         Ok(())
     }
 
-    #[allow(dead_code)]
     fn explore_expr(&mut self, m_expr: MExpr) -> Result<()> {
         for child in m_expr.children() {
             self.explore_group(*child)?;
@@ -99,33 +127,22 @@ impl CascadesOptimizer {
         Ok(())
     }
 
-    #[allow(dead_code)]
-    fn implement_group(&mut self, group_index: IndexType) -> Result<()> {
-        let group = self.memo.group(group_index);
-        let expressions: Vec<MExpr> = group.iter().cloned().collect();
-        for m_expr in expressions {
-            self.implement_expr(m_expr)?;
-        }
-
-        Ok(())
-    }
-
-    #[allow(dead_code)]
-    fn implement_expr(&mut self, m_expr: MExpr) -> Result<()> {
-        for child in m_expr.children() {
-            self.implement_group(*child)?;
-        }
-
-        let mut state = TransformState::new();
-        for rule in self.implement_rules.iter() {
-            m_expr.apply_rule(&self.memo, rule, &mut state)?;
+    /// Implement the logical expressions of every group, which won't create new groups.
+    fn implement(&mut self) -> Result<()> {
+        for group_index in 0..self.memo.num_groups() {
+            let expressions: Vec<MExpr> = self.memo.group(group_index).iter().cloned().collect();
+            for m_expr in expressions {
+                let mut state = TransformState::new();
+                for rule in self.implement_rules.iter() {
+                    m_expr.apply_rule(&self.memo, rule, &mut state)?;
+                }
+                self.insert_from_transform_state(group_index, state)?;
+            }
         }
-        self.insert_from_transform_state(m_expr.group_index(), state)?;
 
         Ok(())
     }
 
-    #[allow(dead_code)]
     fn insert_from_transform_state(
         &mut self,
         group_index: IndexType,
@@ -138,68 +155,68 @@ impl CascadesOptimizer {
         Ok(())
     }
 
-    #[allow(dead_code)]
     fn insert_expression(&mut self, group_index: IndexType, expression: &SExpr) -> Result<()> {
         self.memo.insert(Some(group_index), expression.clone())?;
 
         Ok(())
     }
 
-    #[allow(dead_code)]
-    fn apply_rule(&self, m_expr: &MExpr, rule: &RulePtr, state: &mut TransformState) -> Result<()> {
-        m_expr.apply_rule(&self.memo, rule, state)?;
+    fn find_optimal_plan(&mut self, root_index: IndexType) -> Result<SExpr> {
+        if let Some(relational_prop) = self.memo.group(root_index).relational_prop() {
+            // TODO: derive the physical property
+            let physical_prop = PhysicalProperty::default();
+            if !self
+                .optimize_context
+                .required_prop()
+                .provided_by(relational_prop, &physical_prop)
+            {
+                return Err(ErrorCode::LogicalError(
+                    "Required property cannot be provided by the plan",
+                ));
+            }
+        }
 
-        Ok(())
+        let (_, plan) = self.optimize_group(root_index)?;
+        Ok(plan)
     }
 
-    #[allow(dead_code)]
-    fn find_optimal_plan(&self) -> Result<SExpr> {
-        let root_group = self.memo.root().unwrap();
-
-        let required_prop = self.optimize_context.required_prop().clone();
+    /// Find the physical plan with the lowest cost in a group, the costs of children
+    /// are included.
+    fn optimize_group(&mut self, group_index: IndexType) -> Result<(Cost, SExpr)> {
+        if let Some(optimal) = self.optimal_plans.get(&group_index) {
+            return Ok(optimal.clone());
+        }
 
-        self.optimize_group(root_group, &required_prop)
-    }
+        let expressions: Vec<MExpr> = self
+            .memo
+            .group(group_index)
+            .iter()
+            .filter(|m_expr| m_expr.plan().is_physical())
+            .cloned()
+            .collect();
 
-    /// We don't have cost mechanism for evaluate cost of plans, so we just extract
-    /// first physical plan in a group that satisfies given RequiredProperty.
-    #[allow(dead_code)]
-    fn optimize_group(&self, group: &Group, required_prop: &RequiredProperty) -> Result<SExpr> {
-        for m_expr in group.iter() {
-            if m_expr.plan().is_physical() {
-                let plan = m_expr.plan();
-
-                // TODO: Check properties
-                // let physical = plan.as_physical_plan().unwrap();
-                // let relational_prop = group.relational_prop().unwrap();
-                // let dummy_physical_prop = PhysicalProperty::default();
-                // let required_prop = physical.compute_required_prop(required_prop);
-                //
-                // if !required_prop.provided_by(relational_prop, &dummy_physical_prop) {
-                //     continue;
-                // }
-
-                let children = self.optimize_m_expr(m_expr, required_prop)?;
-                let result = SExpr::create(plan.clone(), children, None);
-                return Ok(result);
+        let mut optimal: Option<(Cost, SExpr)> = None;
+        for m_expr in expressions {
+            let mut cost = self.cost_model.compute_cost(&self.memo, &m_expr)?;
+            let mut children = Vec::with_capacity(m_expr.arity());
+            for child in m_expr.children() {
+                let (child_cost, child_plan) = self.optimize_group(*child)?;
+                cost += child_cost;
+                children.push(child_plan);
             }
-        }
 
-        Err(ErrorCode::LogicalError("Cannot find an appropriate plan"))
-    }
-
-    #[allow(dead_code)]
-    fn optimize_m_expr(
-        &self,
-        m_expr: &MExpr,
-        required_prop: &RequiredProperty,
-    ) -> Result<Vec<SExpr>> {
-        let mut children = vec![];
-        for child in m_expr.children() {
-            let group = self.memo.group(*child);
-            children.push(self.optimize_group(group, required_prop)?);
+            // Prefer the earlier expression if costs are equal, which keeps the original order.
+            if optimal
+                .as_ref()
+                .map_or(true, |(optimal_cost, _)| cost < *optimal_cost)
+            {
+                optimal = Some((cost, SExpr::create(m_expr.plan().clone(), children, None)));
+            }
         }
 
-        Ok(children)
+        let optimal =
+            optimal.ok_or_else(|| ErrorCode::LogicalError("Cannot find an appropriate plan"))?;
+        self.optimal_plans.insert(group_index, optimal.clone());
+        Ok(optimal)
     }
 }
@@ -0,0 +1,96 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::MExpr;
+use crate::sql::optimizer::Memo;
+use crate::sql::plans::RelOperator;
+use crate::sql::IndexType;
+
+pub type Cost = f64;
+
+/// A cost model estimates the cost of evaluating a physical `MExpr`, excluding the
+/// cost of its children.
+pub trait CostModel {
+    fn compute_cost(&self, memo: &Memo, m_expr: &MExpr) -> Result<Cost>;
+}
+
+/// The default cost model, which is measured with the number of rows processed by
+/// each operator.
+pub struct DefaultCostModel {
+    /// Factor of the cost of inserting a row into hash table, comparing with
+    /// probing the hash table with a row.
+    hash_table_build_factor: f64,
+}
+
+impl DefaultCostModel {
+    pub fn new() -> Self {
+        DefaultCostModel {
+            hash_table_build_factor: 2.0,
+        }
+    }
+
+    fn cardinality(memo: &Memo, group_index: IndexType) -> Result<f64> {
+        memo.group(group_index)
+            .relational_prop()
+            .map(|prop| prop.cardinality)
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!(
+                    "Cardinality of group {} should have been derived",
+                    group_index
+                ))
+            })
+    }
+}
+
+impl CostModel for DefaultCostModel {
+    fn compute_cost(&self, memo: &Memo, m_expr: &MExpr) -> Result<Cost> {
+        match m_expr.plan() {
+            RelOperator::PhysicalScan(_) => Self::cardinality(memo, m_expr.group_index()),
+            RelOperator::PhysicalHashJoin(join) => {
+                let probe_cardinality = Self::cardinality(memo, m_expr.children()[0])?;
+                let build_cardinality = Self::cardinality(memo, m_expr.children()[1])?;
+                let build_cost = build_cardinality * self.hash_table_build_factor;
+                if join.build_keys.is_empty() && !join.other_conditions.is_empty() {
+                    // Without join keys, every pair of rows is checked with the join filters
+                    Ok(probe_cardinality * build_cardinality + build_cost)
+                } else {
+                    Ok(probe_cardinality + build_cost)
+                }
+            }
+            RelOperator::PhysicalMergeJoin(_) => {
+                let left_cardinality = Self::cardinality(memo, m_expr.children()[0])?;
+                let right_cardinality = Self::cardinality(memo, m_expr.children()[1])?;
+                Ok(left_cardinality + right_cardinality)
+            }
+            RelOperator::PhysicalNestedLoopJoin(_) => {
+                let left_cardinality = Self::cardinality(memo, m_expr.children()[0])?;
+                let right_cardinality = Self::cardinality(memo, m_expr.children()[1])?;
+                Ok(left_cardinality * right_cardinality)
+            }
+            RelOperator::LogicalGet(_) | RelOperator::LogicalInnerJoin(_) => Err(
+                ErrorCode::LogicalError("Cannot compute cost of logical plan".to_string()),
+            ),
+            _ => {
+                let mut cost = 0.0;
+                for child in m_expr.children() {
+                    cost += Self::cardinality(memo, *child)?;
+                }
+                Ok(cost)
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::Aggregate;
+use crate::sql::plans::AggregateMode;
+use crate::sql::plans::Exchange;
+use crate::sql::plans::Operator;
+use crate::sql::plans::RelOperator;
+
+/// Insert `Exchange` operators into the physical plan to enforce the distribution
+/// required by each operator, the result of query is always gathered to the local node.
+pub fn optimize_distributed_query(s_expr: &SExpr) -> Result<SExpr> {
+    let required = RequiredProperty::default().with_distribution(Distribution::Serial);
+    require_property(&required, s_expr)
+}
+
+fn require_property(required: &RequiredProperty, s_expr: &SExpr) -> Result<SExpr> {
+    if let RelOperator::Aggregate(agg) = s_expr.plan() {
+        if agg.mode == AggregateMode::Initial {
+            return require_property(required, &split_aggregate(agg, s_expr)?);
+        }
+    }
+
+    let physical = s_expr.plan().as_physical().ok_or_else(|| {
+        ErrorCode::LogicalError(format!(
+            "Cannot distribute logical plan: {:?}",
+            s_expr.plan()
+        ))
+    })?;
+
+    let rel_expr = RelExpr::with_s_expr(s_expr);
+    let mut children = Vec::with_capacity(s_expr.arity());
+    for index in 0..s_expr.arity() {
+        let child = s_expr.child(index)?;
+        if matches!(s_expr.plan(), RelOperator::CrossApply(_)) && index == 1 {
+            // Subquery of `CrossApply` is always evaluated locally
+            children.push(child.clone());
+            continue;
+        }
+        let required_child = physical.compute_required_prop_child(&rel_expr, index, required)?;
+        children.push(require_property(&required_child, child)?);
+    }
+
+    let optimized = SExpr::create(s_expr.plan().clone(), children, s_expr.original_group());
+    let physical_prop = RelExpr::with_s_expr(&optimized).derive_physical_prop()?;
+    enforce_property(optimized, &physical_prop, required)
+}
+
+fn enforce_property(
+    s_expr: SExpr,
+    physical_prop: &PhysicalProperty,
+    required: &RequiredProperty,
+) -> Result<SExpr> {
+    if physical_prop.distribution.satisfy(required.distribution()) {
+        return Ok(s_expr);
+    }
+
+    let exchange = match required.distribution() {
+        Distribution::Serial => Exchange::Merge,
+        Distribution::Broadcast => Exchange::Broadcast,
+        Distribution::Hash(keys) => Exchange::Hash(keys.clone()),
+        Distribution::Any | Distribution::Random => return Ok(s_expr),
+    };
+    Ok(SExpr::create_unary(exchange.into(), s_expr))
+}
+
+/// Split the aggregation into a partial one and a final one, so that the exchange required by
+/// the final aggregation is placed between them, and only the partial states are transferred.
+fn split_aggregate(agg: &Aggregate, s_expr: &SExpr) -> Result<SExpr> {
+    let partial = Aggregate {
+        mode: AggregateMode::Partial,
+        ..agg.clone()
+    };
+    let final_agg = Aggregate {
+        mode: AggregateMode::Final,
+        ..agg.clone()
+    };
+    Ok(SExpr::create_unary(
+        final_agg.into(),
+        SExpr::create_unary(partial.into(), s_expr.child(0)?.clone()),
+    ))
+}
@@ -47,6 +47,14 @@ impl Group {
         self.expressions.iter()
     }
 
+    pub fn num_exprs(&self) -> usize {
+        self.expressions.len()
+    }
+
+    pub fn m_expr(&self, index: IndexType) -> &MExpr {
+        &self.expressions[index]
+    }
+
     pub fn insert(&mut self, group_expression: MExpr) -> Result<()> {
         self.expressions.push(group_expression);
         Ok(())
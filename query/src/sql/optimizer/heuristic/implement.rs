@@ -20,8 +20,16 @@ use crate::sql::optimizer::rule::RuleSet;
 use crate::sql::optimizer::rule::TransformState;
 use crate::sql::optimizer::SExpr;
 
-static DEFAULT_IMPLEMENT_RULES: Lazy<Vec<RuleID>> =
-    Lazy::new(|| vec![RuleID::ImplementGet, RuleID::ImplementHashJoin]);
+/// Rules are tried in order, a logical plan is implemented by the first rule
+/// producing any result, so the more specific join rules come first.
+static DEFAULT_IMPLEMENT_RULES: Lazy<Vec<RuleID>> = Lazy::new(|| {
+    vec![
+        RuleID::ImplementGet,
+        RuleID::ImplementMergeJoin,
+        RuleID::ImplementNestedLoopJoin,
+        RuleID::ImplementHashJoin,
+    ]
+});
 
 pub struct HeuristicImplementor {
     implement_rule_list: RuleSet,
@@ -38,7 +46,9 @@ impl HeuristicImplementor {
         for rule in self.implement_rule_list.iter() {
             if s_expr.match_pattern(rule.pattern()) {
                 rule.apply(s_expr, state)?;
-                break;
+                if !state.results().is_empty() {
+                    break;
+                }
             }
         }
         Ok(())
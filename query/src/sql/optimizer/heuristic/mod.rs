@@ -79,11 +79,17 @@ impl HeuristicOptimizer {
     }
 
     pub fn optimize(&mut self, s_expr: SExpr) -> Result<SExpr> {
+        let rewritten = self.rewrite(s_expr)?;
+        let result = self.implement_expression(&rewritten)?;
+        Ok(result)
+    }
+
+    /// Apply the transformation rules without implementing the logical plans, the result
+    /// can be further optimized by `CascadesOptimizer`.
+    pub fn rewrite(&mut self, s_expr: SExpr) -> Result<SExpr> {
         let pre_optimized = self.pre_optimize(s_expr)?;
         let optimized = self.optimize_expression(&pre_optimized)?;
-        let post_optimized = self.post_optimize(optimized)?;
-        let result = self.implement_expression(&post_optimized)?;
-        Ok(result)
+        self.post_optimize(optimized)
     }
 
     fn optimize_expression(&self, s_expr: &SExpr) -> Result<SExpr> {
@@ -26,6 +26,7 @@ use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Aggregate;
 use crate::sql::plans::AggregateFunction;
+use crate::sql::plans::AggregateMode;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::CastExpr;
@@ -114,15 +115,22 @@ impl SubqueryRewriter {
                 self.rewrite(s_expr.child(1)?)?,
             )),
 
-            RelOperator::Project(_) | RelOperator::Limit(_) | RelOperator::Sort(_) => Ok(
-                SExpr::create_unary(s_expr.plan().clone(), self.rewrite(s_expr.child(0)?)?),
-            ),
+            RelOperator::Project(_)
+            | RelOperator::Limit(_)
+            | RelOperator::Sort(_)
+            | RelOperator::Window(_) => Ok(SExpr::create_unary(
+                s_expr.plan().clone(),
+                self.rewrite(s_expr.child(0)?)?,
+            )),
 
             RelOperator::LogicalGet(_) => Ok(s_expr.clone()),
 
             RelOperator::CrossApply(_)
             | RelOperator::Max1Row(_)
             | RelOperator::PhysicalHashJoin(_)
+            | RelOperator::PhysicalMergeJoin(_)
+            | RelOperator::PhysicalNestedLoopJoin(_)
+            | RelOperator::Exchange(_)
             | RelOperator::Pattern(_)
             | RelOperator::PhysicalScan(_) => Err(ErrorCode::LogicalError("Invalid plan type")),
         }
@@ -208,6 +216,8 @@ impl SubqueryRewriter {
                         index: agg_func_index,
                     }],
                     from_distinct: false,
+                    grouping_sets: None,
+                    mode: AggregateMode::Initial,
                 };
 
                 // COUNT(*) = 1 or COUNT(*) = 0
@@ -365,7 +375,9 @@ impl SubqueryRewriter {
         match scalar {
             Scalar::BoundColumnRef(_) => Ok((scalar.clone(), s_expr.clone())),
 
-            Scalar::ConstantExpr(_) => Ok((scalar.clone(), s_expr.clone())),
+            Scalar::ConstantExpr(_) | Scalar::Placeholder(_) => {
+                Ok((scalar.clone(), s_expr.clone()))
+            }
 
             Scalar::AndExpr(expr) => {
                 // Notice that the conjunctions has been flattened in binder, if we encounter
@@ -412,7 +424,9 @@ impl SubqueryRewriter {
                 ))
             }
 
-            Scalar::AggregateFunction(_) => Ok((scalar.clone(), s_expr.clone())),
+            Scalar::AggregateFunction(_) | Scalar::WindowFunction(_) => {
+                Ok((scalar.clone(), s_expr.clone()))
+            }
 
             Scalar::FunctionCall(func) => {
                 let mut args = vec![];
@@ -17,6 +17,9 @@ use common_exception::Result;
 use crate::sql::optimizer::group::Group;
 use crate::sql::optimizer::m_expr::MExpr;
 use crate::sql::optimizer::s_expr::SExpr;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::plans::Operator;
+use crate::sql::plans::RelOperator;
 use crate::sql::IndexType;
 
 /// `Memo` is a search space which memoize possible plans of a query.
@@ -57,6 +60,11 @@ impl Memo {
         target_group: Option<IndexType>,
         expression: SExpr,
     ) -> Result<IndexType> {
+        if let (None, Some(group_index)) = (target_group, expression.original_group()) {
+            // The expression is extracted by PatternExtractor, no need to reinsert.
+            return Ok(group_index);
+        }
+
         let mut children_group = vec![];
         for expr in expression.children() {
             // Insert children expressions recursively and collect their group indices
@@ -64,36 +72,36 @@ impl Memo {
             children_group.push(group);
         }
 
-        if let Some(group_index) = expression.original_group() {
-            // The expression is extracted by PatternExtractor, no need to reinsert.
+        let plan = expression.plan();
+
+        if let Some(group_index) = self.find_m_expr(target_group, plan, &children_group) {
+            // The expression has been memoized already
             return Ok(group_index);
         }
 
-        let mut _new_group = false;
-
         // Create new group if not specified
+        let new_group = target_group.is_none();
         let group_index = match target_group {
             Some(index) => index,
-            _ => {
-                _new_group = true;
-                self.add_group()
-            }
+            None => self.add_group(),
         };
 
-        // if new_group {
-        // let group = self.group_mut(group_index);
-        // let relational_prop = expression.compute_relational_prop();
-        // group.set_relational_prop(relational_prop);
-        // }
-
-        let plan = expression.plan();
-
         let group_expression = MExpr::create(group_index, plan.clone(), children_group);
+        if new_group && plan.is_logical() {
+            let relational_prop =
+                RelExpr::with_m_expr(&group_expression, self).derive_relational_prop()?;
+            self.group_mut(group_index)
+                .set_relational_prop(relational_prop);
+        }
         self.insert_m_expr(group_index, group_expression)?;
 
         Ok(group_index)
     }
 
+    pub fn num_groups(&self) -> usize {
+        self.groups.len()
+    }
+
     pub fn group(&self, index: IndexType) -> &Group {
         &self.groups[index]
     }
@@ -106,6 +114,27 @@ impl Memo {
         &mut self.groups[index]
     }
 
+    /// Find the group which contains an expression equivalent to the given one, only
+    /// `target_group` is searched if it's specified.
+    fn find_m_expr(
+        &self,
+        target_group: Option<IndexType>,
+        plan: &RelOperator,
+        children: &[IndexType],
+    ) -> Option<IndexType> {
+        let is_equivalent =
+            |m_expr: &MExpr| m_expr.plan() == plan && m_expr.children().as_slice() == children;
+        match target_group {
+            Some(index) if self.groups[index].iter().any(is_equivalent) => Some(index),
+            Some(_) => None,
+            None => self
+                .groups
+                .iter()
+                .find(|group| group.iter().any(is_equivalent))
+                .map(|group| group.group_index()),
+        }
+    }
+
     fn add_group(&mut self) -> IndexType {
         let group_index = self.groups.len();
         let group = Group::create(group_index);
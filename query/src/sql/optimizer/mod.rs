@@ -13,6 +13,8 @@
 // limitations under the License.
 
 mod cascades;
+mod cost;
+mod distributed;
 mod group;
 mod heuristic;
 mod m_expr;
@@ -25,22 +27,32 @@ mod s_expr;
 
 use std::sync::Arc;
 
+pub use cascades::CascadesOptimizer;
 use common_exception::Result;
+pub use distributed::optimize_distributed_query;
 pub use heuristic::HeuristicOptimizer;
 pub use heuristic::DEFAULT_REWRITE_RULES;
 pub use m_expr::MExpr;
 pub use memo::Memo;
 pub use optimize_context::OptimizeContext;
 pub use pattern_extractor::PatternExtractor;
+pub use property::estimate_join_cardinality;
+pub use property::estimate_selectivity;
 pub use property::ColumnSet;
+pub use property::ColumnStat;
+pub use property::ColumnStatSet;
+pub use property::Distribution;
 pub use property::PhysicalProperty;
 pub use property::RelExpr;
 pub use property::RelationalProperty;
 pub use property::RequiredProperty;
+pub use property::Statistics;
+pub use property::DEFAULT_CARDINALITY;
 pub use rule::RuleFactory;
 pub use s_expr::SExpr;
 
 use super::plans::Plan;
+use super::plans::RelOperator;
 use crate::sessions::QueryContext;
 pub use crate::sql::optimizer::heuristic::RuleList;
 pub use crate::sql::optimizer::rule::RuleID;
@@ -73,11 +85,32 @@ pub fn optimize_query(
     s_expr: SExpr,
 ) -> Result<SExpr> {
     let rules = RuleList::create(DEFAULT_REWRITE_RULES.clone())?;
-    let mut heuristic = HeuristicOptimizer::new(ctx, metadata, rules);
-    let optimized = heuristic.optimize(s_expr)?;
-    // TODO: enable cascades optimizer
-    // let mut cascades = CascadesOptimizer::create(ctx);
-    // cascades.optimize(s_expr)
+    let mut heuristic = HeuristicOptimizer::new(ctx.clone(), metadata.clone(), rules);
+    let optimized = if ctx.get_settings().get_enable_cbo()? == 0 {
+        heuristic.optimize(s_expr)?
+    } else {
+        // Reorder joins with cascades optimizer based on the rewritten logical plan
+        let rewritten = heuristic.rewrite(s_expr)?;
+        let optimize_context = OptimizeContext::create(RequiredProperty::default(), vec![]);
+        let mut cascades = CascadesOptimizer::create(optimize_context);
+        cascades.optimize(rewritten)?
+    };
+
+    if !ctx.get_cluster().is_empty() && !contains_local_table_scan(&optimized, &metadata) {
+        return optimize_distributed_query(&optimized);
+    }
 
     Ok(optimized)
 }
+
+fn contains_local_table_scan(s_expr: &SExpr, metadata: &MetadataRef) -> bool {
+    s_expr
+        .children()
+        .iter()
+        .any(|child| contains_local_table_scan(child, metadata))
+        || if let RelOperator::PhysicalScan(scan) = s_expr.plan() {
+            metadata.read().table(scan.table_index).table.is_local()
+        } else {
+            false
+        }
+}
@@ -23,7 +23,6 @@ pub struct OptimizeContext {
 }
 
 impl OptimizeContext {
-    #[allow(dead_code)]
     pub fn create(
         required_prop: RequiredProperty,
         _output_named_columns: Vec<NamedColumn>,
@@ -32,11 +32,7 @@ impl PatternExtractor {
 
         if pattern.is_pattern() {
             // Pattern operator is `Pattern`, we can return current operator.
-            return vec![SExpr::create(
-                m_expr.plan().clone(),
-                vec![],
-                Some(m_expr.group_index()),
-            )];
+            return vec![Self::create_expression(memo, m_expr, vec![])];
         }
 
         let pattern_children = pattern.children();
@@ -53,7 +49,7 @@ impl PatternExtractor {
             children_results.push(result);
         }
 
-        Self::generate_expression_with_children(m_expr, children_results)
+        Self::generate_expression_with_children(memo, m_expr, children_results)
     }
 
     fn extract_group(&mut self, memo: &Memo, group: &Group, pattern: &SExpr) -> Vec<SExpr> {
@@ -66,7 +62,17 @@ impl PatternExtractor {
         results
     }
 
+    fn create_expression(memo: &Memo, m_expr: &MExpr, children: Vec<SExpr>) -> SExpr {
+        SExpr::create_with_rel_prop(
+            m_expr.plan().clone(),
+            children,
+            m_expr.group_index(),
+            memo.group(m_expr.group_index()).relational_prop().cloned(),
+        )
+    }
+
     fn generate_expression_with_children(
+        memo: &Memo,
         m_expr: &MExpr,
         candidates: Vec<Vec<SExpr>>,
     ) -> Vec<SExpr> {
@@ -83,11 +89,7 @@ impl PatternExtractor {
         }
 
         if cursors.is_empty() {
-            results.push(SExpr::create(
-                m_expr.plan().clone(),
-                vec![],
-                Some(m_expr.group_index()),
-            ));
+            results.push(Self::create_expression(memo, m_expr, vec![]));
             return results;
         }
 
@@ -96,11 +98,7 @@ impl PatternExtractor {
             for (index, cursor) in cursors.iter().enumerate() {
                 children.push(candidates[index][*cursor].clone());
             }
-            results.push(SExpr::create(
-                m_expr.plan().clone(),
-                children,
-                Some(m_expr.group_index()),
-            ));
+            results.push(Self::create_expression(memo, m_expr, children));
 
             let mut shifted = false;
             // Shift cursor
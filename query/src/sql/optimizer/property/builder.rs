@@ -17,6 +17,7 @@ use common_exception::Result;
 
 use crate::sql::optimizer::MExpr;
 use crate::sql::optimizer::Memo;
+use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelationalProperty;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Operator;
@@ -38,6 +39,12 @@ impl<'a> RelExpr<'a> {
     }
 
     pub fn derive_relational_prop(&self) -> Result<RelationalProperty> {
+        if let RelExpr::SExpr { expr } = self {
+            if let Some(rel_prop) = expr.rel_prop() {
+                return Ok(rel_prop.clone());
+            }
+        }
+
         let plan = match self {
             RelExpr::SExpr { expr } => expr.plan(),
             RelExpr::MExpr { expr, .. } => expr.plan(),
@@ -60,15 +67,47 @@ impl<'a> RelExpr<'a> {
                 let rel_expr = RelExpr::with_s_expr(child);
                 rel_expr.derive_relational_prop()
             }
-            RelExpr::MExpr { expr, memo } => memo
-                .group(expr.group_index())
-                .relational_prop()
-                .cloned()
-                .ok_or_else(|| {
-                    ErrorCode::LogicalError(
-                        "Relational property should have been filled".to_string(),
-                    )
-                }),
+            RelExpr::MExpr { expr, memo } => {
+                let child = expr.children().get(index).ok_or_else(|| {
+                    ErrorCode::LogicalError(format!("Invalid children index: {}", index))
+                })?;
+                memo.group(*child)
+                    .relational_prop()
+                    .cloned()
+                    .ok_or_else(|| {
+                        ErrorCode::LogicalError(
+                            "Relational property should have been filled".to_string(),
+                        )
+                    })
+            }
+        }
+    }
+
+    pub fn derive_physical_prop(&self) -> Result<PhysicalProperty> {
+        let plan = match self {
+            RelExpr::SExpr { expr } => expr.plan(),
+            RelExpr::MExpr { expr, .. } => expr.plan(),
+        };
+
+        if let Some(physical) = plan.as_physical() {
+            physical.derive_physical_prop(self)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot derive physical property from logical plan".to_string(),
+            ))
+        }
+    }
+
+    pub fn derive_physical_prop_child(&self, index: usize) -> Result<PhysicalProperty> {
+        match self {
+            RelExpr::SExpr { expr } => {
+                let child = expr.child(index)?;
+                let rel_expr = RelExpr::with_s_expr(child);
+                rel_expr.derive_physical_prop()
+            }
+            RelExpr::MExpr { .. } => Err(ErrorCode::LogicalError(
+                "Physical property of group is not available".to_string(),
+            )),
         }
     }
 }
@@ -13,12 +13,21 @@
 // limitations under the License.
 
 mod builder;
+mod stat;
 
 use std::collections::HashSet;
 
 pub use builder::RelExpr;
+pub use stat::estimate_join_cardinality;
+pub use stat::estimate_selectivity;
+pub use stat::ColumnStat;
+pub use stat::ColumnStatSet;
+pub use stat::Statistics;
+pub use stat::DEFAULT_CARDINALITY;
 
 use crate::sql::common::IndexType;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::SortItem;
 
 pub type ColumnSet = HashSet<IndexType>;
 
@@ -30,32 +39,99 @@ pub struct NamedColumn {
 #[derive(Default, Clone)]
 pub struct RequiredProperty {
     required_columns: ColumnSet,
+    distribution: Distribution,
 }
 
 impl RequiredProperty {
     pub fn create(required_columns: ColumnSet) -> Self {
-        RequiredProperty { required_columns }
+        RequiredProperty {
+            required_columns,
+            distribution: Distribution::Any,
+        }
+    }
+
+    pub fn with_distribution(mut self, distribution: Distribution) -> Self {
+        self.distribution = distribution;
+        self
     }
 
     pub fn required_columns(&self) -> &ColumnSet {
         &self.required_columns
     }
 
+    pub fn distribution(&self) -> &Distribution {
+        &self.distribution
+    }
+
     pub fn provided_by(
         &self,
         relational_prop: &RelationalProperty,
-        _physical_prop: &PhysicalProperty,
+        physical_prop: &PhysicalProperty,
     ) -> bool {
         self.required_columns()
             .is_subset(&relational_prop.output_columns)
+            && physical_prop.distribution.satisfy(self.distribution())
     }
 }
 
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Debug)]
 pub struct RelationalProperty {
     pub output_columns: ColumnSet,
     pub outer_columns: ColumnSet,
+
+    /// Estimated number of rows
+    pub cardinality: f64,
+    /// Estimated statistics of output columns
+    pub column_stats: ColumnStatSet,
 }
 
-#[derive(Default, Clone)]
-pub struct PhysicalProperty {}
+#[derive(Default, Clone, Debug)]
+pub struct PhysicalProperty {
+    pub distribution: Distribution,
+    /// Sort order of the output rows, empty if the rows are not ordered.
+    /// Only a single ordered stream of rows is regarded as sorted.
+    pub ordering: Vec<SortItem>,
+}
+
+impl PhysicalProperty {
+    /// Check if the output rows are sorted in ascending order by the given columns,
+    /// i.e. the columns are a prefix of the ordering.
+    pub fn ordered_by(&self, columns: &[IndexType]) -> bool {
+        columns.len() <= self.ordering.len()
+            && columns
+                .iter()
+                .zip(self.ordering.iter())
+                .all(|(column, item)| *column == item.index && item.asc.unwrap_or(true))
+    }
+}
+
+/// How the data of a relation is distributed among the nodes of cluster.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Distribution {
+    /// No requirement on distribution, only used as a required property
+    Any,
+    /// Arbitrarily distributed among the nodes, e.g. result of scanning a remote table
+    Random,
+    /// All the data is on the local node
+    Serial,
+    /// Every node holds a full copy of the data
+    Broadcast,
+    /// Rows are distributed by hash of the given keys
+    Hash(Vec<Scalar>),
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Distribution::Any
+    }
+}
+
+impl Distribution {
+    /// Check if the distribution can satisfy the required one
+    pub fn satisfy(&self, required: &Distribution) -> bool {
+        match required {
+            Distribution::Any => true,
+            _ => self == required,
+        }
+    }
+}
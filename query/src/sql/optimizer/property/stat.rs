@@ -0,0 +1,132 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+
+use crate::sql::plans::ComparisonOp;
+use crate::sql::plans::Scalar;
+use crate::sql::IndexType;
+use crate::storages::index::ColumnStatistics;
+
+/// Cardinality of a table that doesn't provide statistics.
+pub const DEFAULT_CARDINALITY: f64 = 1000.0;
+
+/// Selectivity of a predicate that we are not able to estimate.
+pub const DEFAULT_SELECTIVITY: f64 = 0.2;
+
+/// Estimated statistics of a column.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnStat {
+    /// Number of distinct values
+    pub ndv: f64,
+    /// Number of null values
+    pub null_count: f64,
+}
+
+impl ColumnStat {
+    /// Derive column statistics from the statistics kept by storage. Since storage doesn't
+    /// record the number of distinct values, it's estimated with the value range of integral
+    /// columns and bounded by the number of non-null values.
+    pub fn from_column_statistics(stat: &ColumnStatistics, num_rows: u64) -> Self {
+        let non_null_rows = num_rows.saturating_sub(stat.null_count) as f64;
+        let ndv = match (&stat.min, &stat.max) {
+            (DataValue::Int64(min), DataValue::Int64(max)) if max >= min => {
+                ((*max as f64) - (*min as f64) + 1.0).min(non_null_rows)
+            }
+            (DataValue::UInt64(min), DataValue::UInt64(max)) if max >= min => {
+                ((*max - *min) as f64 + 1.0).min(non_null_rows)
+            }
+            (DataValue::Boolean(min), DataValue::Boolean(max)) => {
+                (if min == max { 1.0 } else { 2.0 }).min(non_null_rows)
+            }
+            _ => non_null_rows,
+        };
+
+        ColumnStat {
+            ndv,
+            null_count: stat.null_count as f64,
+        }
+    }
+}
+
+pub type ColumnStatSet = HashMap<IndexType, ColumnStat>;
+
+/// Statistics of a base table, which are collected by binder and used to
+/// derive the cardinality of relational expressions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Statistics {
+    /// Number of rows, `None` if the table doesn't provide it.
+    pub num_rows: Option<u64>,
+    pub column_stats: ColumnStatSet,
+}
+
+/// Estimate the fraction of rows that satisfy `predicate`.
+pub fn estimate_selectivity(predicate: &Scalar, column_stats: &ColumnStatSet) -> f64 {
+    match predicate {
+        Scalar::ComparisonExpr(expr) if expr.op == ComparisonOp::Equal => {
+            match (expr.left.as_ref(), expr.right.as_ref()) {
+                (Scalar::BoundColumnRef(column), Scalar::ConstantExpr(_))
+                | (Scalar::ConstantExpr(_), Scalar::BoundColumnRef(column)) => column_stats
+                    .get(&column.column.index)
+                    .map(|stat| 1.0 / stat.ndv.max(1.0))
+                    .unwrap_or(DEFAULT_SELECTIVITY),
+                _ => DEFAULT_SELECTIVITY,
+            }
+        }
+        Scalar::AndExpr(expr) => {
+            estimate_selectivity(&expr.left, column_stats)
+                * estimate_selectivity(&expr.right, column_stats)
+        }
+        Scalar::OrExpr(expr) => {
+            let left = estimate_selectivity(&expr.left, column_stats);
+            let right = estimate_selectivity(&expr.right, column_stats);
+            left + right - left * right
+        }
+        Scalar::ConstantExpr(expr) => match expr.value {
+            DataValue::Boolean(true) => 1.0,
+            DataValue::Boolean(false) | DataValue::Null => 0.0,
+            _ => DEFAULT_SELECTIVITY,
+        },
+        _ => DEFAULT_SELECTIVITY,
+    }
+}
+
+/// Estimate the cardinality of an equi-join with `|L| * |R| / max(ndv(l), ndv(r))`, the most
+/// selective join key is used if there are multiple ones. A key without statistics is
+/// assumed to be unique on its side.
+pub fn estimate_join_cardinality(
+    left_cardinality: f64,
+    right_cardinality: f64,
+    left_keys: &[Scalar],
+    right_keys: &[Scalar],
+    column_stats: &ColumnStatSet,
+) -> f64 {
+    let key_ndv = |key: &Scalar, cardinality: f64| match key {
+        Scalar::BoundColumnRef(column) => column_stats
+            .get(&column.column.index)
+            .map(|stat| stat.ndv.min(cardinality))
+            .unwrap_or(cardinality),
+        _ => cardinality,
+    };
+
+    let divisor = left_keys
+        .iter()
+        .zip(right_keys.iter())
+        .map(|(left, right)| key_ndv(left, left_cardinality).max(key_ndv(right, right_cardinality)))
+        .fold(1.0, f64::max);
+
+    left_cardinality * right_cardinality / divisor
+}
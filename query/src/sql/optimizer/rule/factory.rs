@@ -28,6 +28,11 @@ use crate::sql::optimizer::rule::rewrite::RuleMergeProject;
 use crate::sql::optimizer::rule::rewrite::RulePushDownFilterCrossApply;
 use crate::sql::optimizer::rule::rule_implement_get::RuleImplementGet;
 use crate::sql::optimizer::rule::rule_implement_hash_join::RuleImplementHashJoin;
+use crate::sql::optimizer::rule::rule_implement_merge_join::RuleImplementMergeJoin;
+use crate::sql::optimizer::rule::rule_implement_nested_loop_join::RuleImplementNestedLoopJoin;
+use crate::sql::optimizer::rule::transform::RuleCommuteJoin;
+use crate::sql::optimizer::rule::transform::RuleLeftAssociateJoin;
+use crate::sql::optimizer::rule::transform::RuleRightAssociateJoin;
 use crate::sql::optimizer::rule::RuleID;
 use crate::sql::optimizer::rule::RulePtr;
 
@@ -42,6 +47,8 @@ impl RuleFactory {
         match id {
             RuleID::ImplementGet => Ok(Box::new(RuleImplementGet::new())),
             RuleID::ImplementHashJoin => Ok(Box::new(RuleImplementHashJoin::new())),
+            RuleID::ImplementMergeJoin => Ok(Box::new(RuleImplementMergeJoin::new())),
+            RuleID::ImplementNestedLoopJoin => Ok(Box::new(RuleImplementNestedLoopJoin::new())),
             RuleID::EliminateEvalScalar => Ok(Box::new(RuleEliminateEvalScalar::new())),
             RuleID::PushDownFilterProject => Ok(Box::new(RulePushDownFilterProject::new())),
             RuleID::PushDownFilterEvalScalar => Ok(Box::new(RulePushDownFilterEvalScalar::new())),
@@ -56,6 +63,9 @@ impl RuleFactory {
             RuleID::NormalizeDisjunctiveFilter => {
                 Ok(Box::new(RuleNormalizeDisjunctiveFilter::new()))
             }
+            RuleID::CommuteJoin => Ok(Box::new(RuleCommuteJoin::new())),
+            RuleID::LeftAssociateJoin => Ok(Box::new(RuleLeftAssociateJoin::new())),
+            RuleID::RightAssociateJoin => Ok(Box::new(RuleRightAssociateJoin::new())),
         }
     }
 }
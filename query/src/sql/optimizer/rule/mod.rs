@@ -23,7 +23,10 @@ mod factory;
 mod rewrite;
 mod rule_implement_get;
 mod rule_implement_hash_join;
+mod rule_implement_merge_join;
+mod rule_implement_nested_loop_join;
 mod rule_set;
+mod transform;
 mod transform_state;
 
 pub use factory::RuleFactory;
@@ -57,9 +60,16 @@ pub enum RuleID {
     MergeEvalScalar,
     MergeFilter,
 
+    // Exploration rules
+    CommuteJoin,
+    LeftAssociateJoin,
+    RightAssociateJoin,
+
     // Implementation rules
     ImplementGet,
     ImplementHashJoin,
+    ImplementMergeJoin,
+    ImplementNestedLoopJoin,
 }
 
 impl Display for RuleID {
@@ -67,6 +77,8 @@ impl Display for RuleID {
         match self {
             RuleID::ImplementGet => write!(f, "ImplementGet"),
             RuleID::ImplementHashJoin => write!(f, "ImplementHashJoin"),
+            RuleID::ImplementMergeJoin => write!(f, "ImplementMergeJoin"),
+            RuleID::ImplementNestedLoopJoin => write!(f, "ImplementNestedLoopJoin"),
             RuleID::PushDownFilterProject => write!(f, "PushDownFilterProject"),
             RuleID::PushDownFilterEvalScalar => write!(f, "PushDownFilterEvalScalar"),
             RuleID::PushDownFilterJoin => write!(f, "PushDownFilterJoin"),
@@ -79,6 +91,9 @@ impl Display for RuleID {
             RuleID::MergeFilter => write!(f, "MergeFilter"),
             RuleID::NormalizeScalarFilter => write!(f, "NormalizeScalarFilter"),
             RuleID::NormalizeDisjunctiveFilter => write!(f, "NormalizeDisjunctiveFilter"),
+            RuleID::CommuteJoin => write!(f, "CommuteJoin"),
+            RuleID::LeftAssociateJoin => write!(f, "LeftAssociateJoin"),
+            RuleID::RightAssociateJoin => write!(f, "RightAssociateJoin"),
         }
     }
 }
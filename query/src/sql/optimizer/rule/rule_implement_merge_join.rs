@@ -0,0 +1,124 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::JoinType;
+use crate::sql::plans::LogicalInnerJoin;
+use crate::sql::plans::PatternPlan;
+use crate::sql::plans::PhysicalMergeJoin;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+use crate::sql::IndexType;
+
+/// Implement an inner equi-join with sort-merge join if both of its children are
+/// already sorted by the join keys, so the rows can be merged without building
+/// a hash table.
+pub struct RuleImplementMergeJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleImplementMergeJoin {
+    pub fn new() -> Self {
+        RuleImplementMergeJoin {
+            id: RuleID::ImplementMergeJoin,
+            pattern: SExpr::create_binary(
+                PatternPlan {
+                    plan_type: RelOp::LogicalInnerJoin,
+                }
+                .into(),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+            ),
+        }
+    }
+
+    /// Return the indices of the columns if all the keys are plain column references.
+    fn key_columns(keys: &[Scalar]) -> Option<Vec<IndexType>> {
+        keys.iter()
+            .map(|key| match key {
+                Scalar::BoundColumnRef(column_ref) => Some(column_ref.column.index),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn ordered_by(s_expr: &SExpr, columns: &[IndexType]) -> bool {
+        // The ordering of an unimplemented child is unknown, regard it as unordered.
+        RelExpr::with_s_expr(s_expr)
+            .derive_physical_prop()
+            .map(|prop| prop.ordered_by(columns))
+            .unwrap_or(false)
+    }
+}
+
+impl Rule for RuleImplementMergeJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let logical_join: LogicalInnerJoin = expression.plan().clone().try_into()?;
+        if logical_join.join_type != JoinType::Inner || logical_join.left_conditions.is_empty() {
+            return Ok(());
+        }
+
+        let left_columns = Self::key_columns(&logical_join.left_conditions);
+        let right_columns = Self::key_columns(&logical_join.right_conditions);
+        let (left_columns, right_columns) = match (left_columns, right_columns) {
+            (Some(left_columns), Some(right_columns)) => (left_columns, right_columns),
+            _ => return Ok(()),
+        };
+        if !Self::ordered_by(expression.child(0)?, &left_columns)
+            || !Self::ordered_by(expression.child(1)?, &right_columns)
+        {
+            return Ok(());
+        }
+
+        let result = SExpr::create(
+            PhysicalMergeJoin {
+                left_keys: logical_join.left_conditions,
+                right_keys: logical_join.right_conditions,
+                other_conditions: logical_join.other_conditions,
+                join_type: logical_join.join_type,
+            }
+            .into(),
+            expression.children().to_vec(),
+            expression.original_group(),
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
 use std::collections::HashSet;
 
 use common_exception::ErrorCode;
@@ -22,16 +21,14 @@ use crate::sql::optimizer::rule::factory::RuleFactory;
 use crate::sql::optimizer::rule::RuleID;
 use crate::sql::optimizer::rule::RulePtr;
 
-/// Set of `Rule`
+/// Set of `Rule`, rules are iterated in the order of insertion to make optimization deterministic.
 pub struct RuleSet {
-    rules: HashMap<RuleID, RulePtr>,
+    rules: Vec<RulePtr>,
 }
 
 impl RuleSet {
     pub fn create() -> Self {
-        RuleSet {
-            rules: HashMap::new(),
-        }
+        RuleSet { rules: vec![] }
     }
 
     pub fn create_with_ids(ids: Vec<RuleID>) -> Result<Self> {
@@ -48,15 +45,16 @@ impl RuleSet {
     }
 
     pub fn insert(&mut self, rule: RulePtr) {
-        self.rules.insert(rule.id(), rule);
+        self.rules.retain(|r| r.id() != rule.id());
+        self.rules.push(rule);
     }
 
     pub fn contains(&self, id: &RuleID) -> bool {
-        self.rules.contains_key(id)
+        self.rules.iter().any(|rule| rule.id() == *id)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &RulePtr> {
-        self.rules.values()
+        self.rules.iter()
     }
 }
 
@@ -0,0 +1,128 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform::util::as_inner_join;
+use crate::sql::optimizer::rule::transform::util::create_inner_join;
+use crate::sql::optimizer::rule::transform::util::join_conditions;
+use crate::sql::optimizer::rule::transform::util::output_columns;
+use crate::sql::optimizer::rule::transform::util::take_join_conditions;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::rule::TransformState;
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::PatternPlan;
+use crate::sql::plans::RelOp;
+
+/// Rotate a left-deep join tree to the right, i.e. `(A ⋈ B) ⋈ C` into `A ⋈ (B ⋈ C)`.
+/// Only inner equi-joins are reordered, and the rotation is skipped if it introduces
+/// a cartesian product.
+pub struct RuleLeftAssociateJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleLeftAssociateJoin {
+    pub fn new() -> Self {
+        RuleLeftAssociateJoin {
+            id: RuleID::LeftAssociateJoin,
+            // InnerJoin
+            // | \
+            // |  *
+            // InnerJoin
+            // | \
+            // |  *
+            // *
+            pattern: SExpr::create_binary(
+                PatternPlan {
+                    plan_type: RelOp::LogicalInnerJoin,
+                }
+                .into(),
+                SExpr::create_binary(
+                    PatternPlan {
+                        plan_type: RelOp::LogicalInnerJoin,
+                    }
+                    .into(),
+                    SExpr::create_leaf(
+                        PatternPlan {
+                            plan_type: RelOp::Pattern,
+                        }
+                        .into(),
+                    ),
+                    SExpr::create_leaf(
+                        PatternPlan {
+                            plan_type: RelOp::Pattern,
+                        }
+                        .into(),
+                    ),
+                ),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleLeftAssociateJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, s_expr: &SExpr, state: &mut TransformState) -> Result<()> {
+        let (top_join, bottom_join) =
+            match (as_inner_join(s_expr)?, as_inner_join(s_expr.child(0)?)?) {
+                (Some(top_join), Some(bottom_join)) => (top_join, bottom_join),
+                _ => return Ok(()),
+            };
+
+        let a = s_expr.child(0)?.child(0)?;
+        let b = s_expr.child(0)?.child(1)?;
+        let c = s_expr.child(1)?;
+        let a_columns = output_columns(a)?;
+        let b_columns = output_columns(b)?;
+        let c_columns = output_columns(c)?;
+
+        let mut conditions = join_conditions(bottom_join);
+        conditions.extend(join_conditions(top_join));
+
+        let b_c_conditions = take_join_conditions(&mut conditions, &b_columns, &c_columns);
+        if b_c_conditions.is_empty() {
+            return Ok(());
+        }
+        let b_c_columns: ColumnSet = b_columns.union(&c_columns).cloned().collect();
+        let a_b_c_conditions = take_join_conditions(&mut conditions, &a_columns, &b_c_columns);
+        if a_b_c_conditions.is_empty() || !conditions.is_empty() {
+            return Ok(());
+        }
+
+        let result = create_inner_join(
+            a_b_c_conditions,
+            a.clone(),
+            create_inner_join(b_c_conditions, b.clone(), c.clone()),
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::JoinType;
+use crate::sql::plans::LogicalInnerJoin;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+
+/// Equi-join conditions in the form of `(left key, right key)`
+pub(super) type JoinConditions = Vec<(Scalar, Scalar)>;
+
+/// Return the join if `s_expr` is an inner equi-join, which is free to be reordered.
+pub(super) fn as_inner_join(s_expr: &SExpr) -> Result<Option<LogicalInnerJoin>> {
+    let join: LogicalInnerJoin = s_expr.plan().clone().try_into()?;
+    if join.join_type != JoinType::Inner
+        || join.left_conditions.is_empty()
+        || !join.other_conditions.is_empty()
+        || join.marker_index.is_some()
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(join))
+}
+
+pub(super) fn join_conditions(join: LogicalInnerJoin) -> JoinConditions {
+    join.left_conditions
+        .into_iter()
+        .zip(join.right_conditions)
+        .collect()
+}
+
+pub(super) fn output_columns(s_expr: &SExpr) -> Result<ColumnSet> {
+    Ok(RelExpr::with_s_expr(s_expr)
+        .derive_relational_prop()?
+        .output_columns)
+}
+
+/// Take the conditions joining `left` with `right` out of `conditions`, the keys of
+/// taken conditions are swapped if necessary to match the sides.
+pub(super) fn take_join_conditions(
+    conditions: &mut JoinConditions,
+    left: &ColumnSet,
+    right: &ColumnSet,
+) -> JoinConditions {
+    let mut taken = vec![];
+    let mut rest = vec![];
+    for (left_key, right_key) in conditions.drain(..) {
+        let left_used = left_key.used_columns();
+        let right_used = right_key.used_columns();
+        if left_used.is_subset(left) && right_used.is_subset(right) {
+            taken.push((left_key, right_key));
+        } else if left_used.is_subset(right) && right_used.is_subset(left) {
+            taken.push((right_key, left_key));
+        } else {
+            rest.push((left_key, right_key));
+        }
+    }
+    *conditions = rest;
+
+    taken
+}
+
+pub(super) fn create_inner_join(conditions: JoinConditions, left: SExpr, right: SExpr) -> SExpr {
+    let (left_conditions, right_conditions) = conditions.into_iter().unzip();
+    SExpr::create_binary(
+        LogicalInnerJoin {
+            left_conditions,
+            right_conditions,
+            other_conditions: vec![],
+            join_type: JoinType::Inner,
+            marker_index: None,
+        }
+        .into(),
+        left,
+        right,
+    )
+}
@@ -17,6 +17,7 @@ use common_exception::Result;
 
 use crate::sql::optimizer::rule::AppliedRules;
 use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::RelationalProperty;
 use crate::sql::plans::Operator;
 use crate::sql::plans::RelOp;
 use crate::sql::plans::RelOperator;
@@ -30,6 +31,10 @@ pub struct SExpr {
 
     original_group: Option<IndexType>,
 
+    /// Relational property of the `Group` this expression is extracted from, children of
+    /// an extracted expression may be omitted so the property can't be derived again.
+    rel_prop: Option<RelationalProperty>,
+
     /// A bitmap to record applied rules on current SExpr, to prevent
     /// redundant transformations.
     pub(super) applied_rules: AppliedRules,
@@ -45,6 +50,23 @@ impl SExpr {
             plan,
             children,
             original_group,
+            rel_prop: None,
+
+            applied_rules: AppliedRules::default(),
+        }
+    }
+
+    pub fn create_with_rel_prop(
+        plan: RelOperator,
+        children: Vec<SExpr>,
+        original_group: IndexType,
+        rel_prop: Option<RelationalProperty>,
+    ) -> Self {
+        SExpr {
+            plan,
+            children,
+            original_group: Some(original_group),
+            rel_prop,
 
             applied_rules: AppliedRules::default(),
         }
@@ -88,6 +110,10 @@ impl SExpr {
         self.original_group
     }
 
+    pub fn rel_prop(&self) -> Option<&RelationalProperty> {
+        self.rel_prop.as_ref()
+    }
+
     pub fn match_pattern(&self, pattern: &SExpr) -> bool {
         if pattern.plan.rel_op() != RelOp::Pattern {
             // Pattern is plan
@@ -115,6 +115,7 @@ impl<'a> DfParser<'a> {
          copyOptions ::=
          ON_ERROR = { CONTINUE | SKIP_FILE | SKIP_FILE_<num> | SKIP_FILE_<num>% | ABORT_STATEMENT }
          SIZE_LIMIT = <num>
+         FORCE = TRUE | FALSE
         */
         let mut on_error = "".to_string();
         if self.consume_token("ON_ERROR") {
@@ -128,6 +129,13 @@ impl<'a> DfParser<'a> {
             size_limit = self.parse_value_or_ident()?;
         }
 
+        // FORCE = TRUE | FALSE
+        let mut force = "".to_string();
+        if self.consume_token("FORCE") {
+            self.expect_token("=")?;
+            force = self.parse_value_or_ident()?;
+        }
+
         // VALIDATION_MODE = RETURN_<n>_ROWS | RETURN_ERRORS | RETURN_ALL_ERRORS
         let mut validation_mode = "".to_string();
         if self.consume_token("VALIDATION_MODE") {
@@ -146,6 +154,7 @@ impl<'a> DfParser<'a> {
             pattern,
             on_error,
             size_limit,
+            force,
             validation_mode,
             query,
         }))
@@ -20,32 +20,48 @@ use sqlparser::keywords::Keyword;
 use sqlparser::parser::ParserError;
 use sqlparser::tokenizer::Token;
 
+use crate::parser_err;
 use crate::sql::statements::DfOptimizeTable;
 use crate::sql::DfParser;
 use crate::sql::DfStatement;
 
 impl<'a> DfParser<'a> {
     pub(crate) fn parse_optimize(&mut self) -> Result<DfStatement<'a>, ParserError> {
-        // syntax: "optimize TABLE t [purge | compact | all]",  default action is "purge"
+        // syntax: "optimize TABLE t [purge | compact | all] [dry run]",  default action is "purge"
         self.expect_token("OPTIMIZE")?;
         self.parser.expect_keyword(Keyword::TABLE)?;
         let object_name = self.parser.parse_object_name()?;
-        let action = match self.parser.next_token() {
+        let action = match self.parser.peek_token() {
             Token::EOF => Ok(OptimizeTableAction::Purge),
-            Token::Word(w) => match w.keyword {
-                Keyword::ALL => Ok(OptimizeTableAction::All),
-                Keyword::PURGE => Ok(OptimizeTableAction::Purge),
-                Keyword::NoKeyword if w.value.to_uppercase().as_str() == "COMPACT" => {
-                    Ok(OptimizeTableAction::Compact)
+            Token::Word(w) if w.value.to_uppercase().as_str() == "DRY" => {
+                Ok(OptimizeTableAction::Purge)
+            }
+            Token::Word(w) => {
+                self.parser.next_token();
+                match w.keyword {
+                    Keyword::ALL => Ok(OptimizeTableAction::All),
+                    Keyword::PURGE => Ok(OptimizeTableAction::Purge),
+                    Keyword::NoKeyword if w.value.to_uppercase().as_str() == "COMPACT" => {
+                        Ok(OptimizeTableAction::Compact)
+                    }
+                    _ => self.expected("one of PURGE, COMPACT, ALL", Token::Word(w)),
                 }
-                _ => self.expected("one of PURGE, COMPACT, ALL", Token::Word(w)),
-            },
+            }
             t => self.expected("Nothing, or one of PURGE, COMPACT, ALL", t),
         }?;
 
+        let dry_run = self.consume_token("DRY");
+        if dry_run {
+            self.expect_token("RUN")?;
+            if action != OptimizeTableAction::Purge {
+                return parser_err!("DRY RUN is only supported by PURGE");
+            }
+        }
+
         Ok(DfStatement::OptimizeTable(DfOptimizeTable {
             name: object_name,
             action,
+            dry_run,
         }))
     }
 }
@@ -16,14 +16,18 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 use common_ast::ast::Expr;
+use common_ast::ast::GroupBy;
 use common_ast::ast::Literal;
 use common_ast::ast::SelectTarget;
 use common_ast::parser::token::Token;
 use common_ast::DisplayError;
+use common_datavalues::wrap_nullable;
 use common_datavalues::DataTypeImpl;
+use common_datavalues::UInt32Type;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use super::scalar_common::contains_window_function;
 use super::scalar_common::split_conjunctions;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::select::SelectList;
@@ -34,6 +38,7 @@ use crate::sql::planner::metadata::MetadataRef;
 use crate::sql::planner::semantic::GroupingChecker;
 use crate::sql::plans::Aggregate;
 use crate::sql::plans::AggregateFunction;
+use crate::sql::plans::AggregateMode;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::CastExpr;
@@ -41,10 +46,13 @@ use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::EvalScalar;
 use crate::sql::plans::Filter;
 use crate::sql::plans::FunctionCall;
+use crate::sql::plans::GroupingSets;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarExpr;
 use crate::sql::plans::ScalarItem;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
 use crate::sql::BindContext;
 
 #[derive(Default, Clone, PartialEq, Debug)]
@@ -74,6 +82,14 @@ pub struct AggregateInfo {
     /// TODO(leiysky): so far we are using `Debug` string of `Scalar` as identifier,
     /// maybe a more reasonable way is needed
     pub group_items_map: HashMap<String, usize>,
+
+    /// Grouping sets of `GROUPING SETS`, `ROLLUP` and `CUBE`, each grouping set is
+    /// represented by the positions of its items in `group_items`.
+    pub grouping_sets: Vec<Vec<usize>>,
+
+    /// Grouping id column of grouping sets, which tells the grouping set a row belongs to.
+    /// The n-th highest bit of the id is set if the n-th group item is absent from the set.
+    pub grouping_id_column: Option<ColumnBinding>,
 }
 
 struct AggregateRewriter<'a> {
@@ -92,7 +108,7 @@ impl<'a> AggregateRewriter<'a> {
     pub fn visit(&mut self, scalar: &Scalar) -> Result<Scalar> {
         match scalar {
             Scalar::BoundColumnRef(_) => Ok(scalar.clone()),
-            Scalar::ConstantExpr(_) => Ok(scalar.clone()),
+            Scalar::ConstantExpr(_) | Scalar::Placeholder(_) => Ok(scalar.clone()),
             Scalar::AndExpr(scalar) => Ok(AndExpr {
                 left: Box::new(self.visit(&scalar.left)?),
                 right: Box::new(self.visit(&scalar.right)?),
@@ -139,6 +155,34 @@ impl<'a> AggregateRewriter<'a> {
             Scalar::SubqueryExpr(_) => Ok(scalar.clone()),
 
             Scalar::AggregateFunction(agg_func) => self.replace_aggregate_function(agg_func),
+
+            // Aggregate functions can be used as the arguments of window functions,
+            // e.g. `SELECT SUM(COUNT(*)) OVER () FROM t GROUP BY a`.
+            Scalar::WindowFunction(window_func) => Ok(WindowFunction {
+                args: window_func
+                    .args
+                    .iter()
+                    .map(|arg| self.visit(arg))
+                    .collect::<Result<Vec<_>>>()?,
+                partition_by: window_func
+                    .partition_by
+                    .iter()
+                    .map(|expr| self.visit(expr))
+                    .collect::<Result<Vec<_>>>()?,
+                order_by: window_func
+                    .order_by
+                    .iter()
+                    .map(|order_by| {
+                        Ok(WindowOrderBy {
+                            expr: self.visit(&order_by.expr)?,
+                            asc: order_by.asc,
+                            nulls_first: order_by.nulls_first,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                ..window_func.clone()
+            }
+            .into()),
         }
     }
 
@@ -242,6 +286,11 @@ impl<'a> Binder {
         let mut scalar_binder =
             ScalarBinder::new(bind_context, self.ctx.clone(), self.metadata.clone());
         let (scalar, _) = scalar_binder.bind(having).await?;
+        if contains_window_function(&scalar)? {
+            return Err(ErrorCode::SemanticError(having.span().display_error(
+                "window functions are not allowed in HAVING".to_string(),
+            )));
+        }
         let mut rewriter = AggregateRewriter::new(bind_context, self.metadata.clone());
         Ok((rewriter.visit(&scalar)?, having.span()))
     }
@@ -254,11 +303,14 @@ impl<'a> Binder {
     ///     `SELECT a as b, COUNT(a) FROM t GROUP BY b`.
     ///   - Scalar expressions that can be evaluated in current scope(doesn't contain aliases), e.g.
     ///     column `a` and expression `a+1` in `SELECT a as b, COUNT(a) FROM t GROUP BY a, a+1`.
+    ///
+    /// The items of `GROUPING SETS`, `ROLLUP` and `CUBE` are resolved in the same way, and
+    /// the grouping sets are recorded in `AggregateInfo`.
     pub async fn analyze_group_items(
         &mut self,
         bind_context: &mut BindContext,
         select_list: &SelectList<'a>,
-        group_by: &[Expr<'a>],
+        group_by: &GroupBy<'a>,
     ) -> Result<()> {
         let mut available_aliases = vec![];
 
@@ -281,8 +333,104 @@ impl<'a> Binder {
             }
         }
 
-        self.resolve_group_items(bind_context, select_list, group_by, &available_aliases)
-            .await
+        // Flatten the group items, a grouping set is a list of positions in the flattened items.
+        let (group_exprs, grouping_sets): (Vec<&Expr<'a>>, Option<Vec<Vec<usize>>>) = match group_by
+        {
+            GroupBy::Normal(exprs) => (exprs.iter().collect(), None),
+            GroupBy::GroupingSets(sets) => {
+                let mut offset = 0;
+                let positions = sets
+                    .iter()
+                    .map(|set| {
+                        offset += set.len();
+                        (offset - set.len()..offset).collect()
+                    })
+                    .collect();
+                (sets.iter().flatten().collect(), Some(positions))
+            }
+            GroupBy::Rollup(exprs) => (
+                exprs.iter().collect(),
+                Some((0..=exprs.len()).rev().map(|n| (0..n).collect()).collect()),
+            ),
+            GroupBy::Cube(exprs) => {
+                let n = exprs.len();
+                if n > u32::BITS as usize {
+                    return Err(ErrorCode::SemanticError("too many items in CUBE"));
+                }
+                // Enumerate all the subsets, the 1st item is the highest bit of the mask
+                let sets = (0..1u64 << n)
+                    .rev()
+                    .map(|mask| (0..n).filter(|i| mask & (1 << (n - 1 - i)) != 0).collect())
+                    .collect();
+                (exprs.iter().collect(), Some(sets))
+            }
+        };
+
+        let positions = self
+            .resolve_group_items(bind_context, select_list, &group_exprs, &available_aliases)
+            .await?;
+
+        if let Some(grouping_sets) = grouping_sets {
+            self.bind_grouping_sets(bind_context, &positions, grouping_sets)?;
+        }
+
+        for item in bind_context.aggregate_info.group_items.iter() {
+            if contains_window_function(&item.scalar)? {
+                return Err(ErrorCode::SemanticError(
+                    "window functions are not allowed in GROUP BY",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the grouping sets in `AggregateInfo`. Since the group items absent from
+    /// a grouping set are replaced with NULL, every group item is given a new nullable
+    /// column, so that the aggregate functions can still read the original values.
+    fn bind_grouping_sets(
+        &mut self,
+        bind_context: &mut BindContext,
+        positions: &[usize],
+        grouping_sets: Vec<Vec<usize>>,
+    ) -> Result<()> {
+        let agg_info = &mut bind_context.aggregate_info;
+        if agg_info.group_items.len() > u32::BITS as usize {
+            return Err(ErrorCode::SemanticError(format!(
+                "too many grouping items, at most {} items are allowed with grouping sets",
+                u32::BITS
+            )));
+        }
+
+        for item in agg_info.group_items.iter_mut() {
+            let name = self.metadata.read().column(item.index).name.clone();
+            item.index = self.metadata.write().add_column(
+                name,
+                wrap_nullable(&item.scalar.data_type()),
+                None,
+            );
+        }
+
+        agg_info.grouping_sets = grouping_sets
+            .into_iter()
+            .map(|set| {
+                let mut items: Vec<usize> = vec![];
+                for position in set.into_iter().map(|i| positions[i]) {
+                    if !items.contains(&position) {
+                        items.push(position);
+                    }
+                }
+                items
+            })
+            .collect();
+        agg_info.grouping_id_column = Some(self.create_column_binding(
+            None,
+            None,
+            "_grouping_id".to_string(),
+            UInt32Type::new_impl(),
+        ));
+
+        Ok(())
     }
 
     pub(super) async fn bind_aggregate(
@@ -312,10 +460,28 @@ impl<'a> Binder {
             new_expr = SExpr::create_unary(eval_scalar.into(), new_expr);
         }
 
+        let agg_info = &bind_context.aggregate_info;
+        let grouping_sets = agg_info
+            .grouping_id_column
+            .as_ref()
+            .map(|grouping_id| GroupingSets {
+                grouping_id_index: grouping_id.index,
+                sets: agg_info
+                    .grouping_sets
+                    .iter()
+                    .map(|set| {
+                        set.iter()
+                            .map(|position| agg_info.group_items[*position].index)
+                            .collect()
+                    })
+                    .collect(),
+            });
         let aggregate_plan = Aggregate {
-            group_items: bind_context.aggregate_info.group_items.clone(),
-            aggregate_functions: bind_context.aggregate_info.aggregate_functions.clone(),
+            group_items: agg_info.group_items.clone(),
+            aggregate_functions: agg_info.aggregate_functions.clone(),
             from_distinct: false,
+            grouping_sets,
+            mode: AggregateMode::Initial,
         };
         new_expr = SExpr::create_unary(aggregate_plan.into(), new_expr);
 
@@ -342,16 +508,18 @@ impl<'a> Binder {
         Ok(SExpr::create_unary(filter.into(), child))
     }
 
+    /// Resolve the group items, returns the position in `group_items` of each expression.
     async fn resolve_group_items(
         &mut self,
         bind_context: &mut BindContext,
         select_list: &SelectList<'a>,
-        group_by: &[Expr<'a>],
+        group_by: &[&Expr<'a>],
         available_aliases: &[(ColumnBinding, Scalar)],
-    ) -> Result<()> {
+    ) -> Result<Vec<usize>> {
+        let mut positions = Vec::with_capacity(group_by.len());
         // Resolve group items with `FROM` context. Since the alias item can not be resolved
         // from the context, we can detect the failure and fallback to resolving with `available_aliases`.
-        for expr in group_by.iter() {
+        for expr in group_by.iter().copied() {
             // If expr is a number literal, then this is a index group item.
             if let Expr::Literal {
                 lit: Literal::Integer(index),
@@ -360,19 +528,23 @@ impl<'a> Binder {
             {
                 let (scalar, alias) = Self::resolve_index_item(expr, *index, select_list)?;
                 let key = format!("{:?}", &scalar);
-                if let Entry::Vacant(entry) = bind_context.aggregate_info.group_items_map.entry(key)
-                {
-                    // Add group item if it's not duplicated
-                    let column_binding = if let Scalar::BoundColumnRef(ref column_ref) = scalar {
-                        column_ref.column.clone()
-                    } else {
-                        self.create_column_binding(None, None, alias, scalar.data_type())
-                    };
-                    bind_context.aggregate_info.group_items.push(ScalarItem {
-                        scalar,
-                        index: column_binding.index,
-                    });
-                    entry.insert(bind_context.aggregate_info.group_items.len() - 1);
+                match bind_context.aggregate_info.group_items_map.entry(key) {
+                    Entry::Vacant(entry) => {
+                        // Add group item if it's not duplicated
+                        let column_binding = if let Scalar::BoundColumnRef(ref column_ref) = scalar
+                        {
+                            column_ref.column.clone()
+                        } else {
+                            self.create_column_binding(None, None, alias, scalar.data_type())
+                        };
+                        bind_context.aggregate_info.group_items.push(ScalarItem {
+                            scalar,
+                            index: column_binding.index,
+                        });
+                        entry.insert(bind_context.aggregate_info.group_items.len() - 1);
+                        positions.push(bind_context.aggregate_info.group_items.len() - 1);
+                    }
+                    Entry::Occupied(entry) => positions.push(*entry.get()),
                 }
                 continue;
             }
@@ -385,13 +557,13 @@ impl<'a> Binder {
                 .await
                 .or_else(|e| Self::resolve_alias_item(bind_context, expr, available_aliases, e))?;
 
-            if bind_context
+            if let Some(position) = bind_context
                 .aggregate_info
                 .group_items_map
                 .get(&format!("{:?}", &scalar_expr))
-                .is_some()
             {
                 // The group key is duplicated
+                positions.push(*position);
                 continue;
             }
 
@@ -415,8 +587,9 @@ impl<'a> Binder {
                 format!("{:?}", &scalar_expr),
                 bind_context.aggregate_info.group_items.len() - 1,
             );
+            positions.push(bind_context.aggregate_info.group_items.len() - 1);
         }
-        Ok(())
+        Ok(positions)
     }
 
     fn resolve_index_item(
@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
 use common_ast::ast::Identifier;
 use common_ast::ast::TableAlias;
 use common_ast::DisplayError;
@@ -20,7 +25,9 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::AggregateInfo;
+use super::WindowInfo;
 use crate::sql::common::IndexType;
+use crate::storages::Table;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ColumnBinding {
@@ -41,6 +48,33 @@ pub struct ColumnBinding {
     pub visible_in_unqualified_wildcard: bool,
 }
 
+/// A common table expression defined with `WITH`, which can be referred to like a table by
+/// the queries in its scope.
+#[derive(Clone)]
+pub struct CteInfo {
+    /// Column names given by the definition, e.g. `a, b` of `WITH t(a, b) AS (...)`
+    pub columns: Vec<String>,
+    /// SQL text of the query, which is parsed and bound at each reference like a view
+    pub query: String,
+    /// Common table expressions visible to the query, the enclosing ones and the
+    /// ones defined before it in the same `WITH` clause
+    pub scope: HashMap<String, CteInfo>,
+    /// The result of a materialized common table expression
+    pub table: Option<Arc<dyn Table>>,
+}
+
+impl Debug for CteInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CteInfo {{ columns: {:?}, query: {:?}, materialized: {} }}",
+            self.columns,
+            self.query,
+            self.table.is_some()
+        )
+    }
+}
+
 /// `BindContext` stores all the free variables in a query and tracks the context of binding procedure.
 #[derive(Clone, Default, Debug)]
 pub struct BindContext {
@@ -50,6 +84,8 @@ pub struct BindContext {
 
     pub aggregate_info: AggregateInfo,
 
+    pub windows: WindowInfo,
+
     /// True if there is aggregation in current context, which means
     /// non-grouping columns cannot be referenced outside aggregation
     /// functions, otherwise a grouping error will be raised.
@@ -57,6 +93,9 @@ pub struct BindContext {
 
     /// Format type of query output.
     pub format: Option<String>,
+
+    /// Common table expressions defined by the `WITH` clause of current query.
+    pub ctes_map: HashMap<String, CteInfo>,
 }
 
 impl BindContext {
@@ -69,8 +108,10 @@ impl BindContext {
             parent: Some(parent),
             columns: vec![],
             aggregate_info: Default::default(),
+            windows: Default::default(),
             in_grouping: false,
             format: None,
+            ctes_map: HashMap::new(),
         }
     }
 
@@ -98,24 +139,57 @@ impl BindContext {
     /// Apply table alias like `SELECT * FROM t AS t1(a, b, c)`.
     /// This method will rename column bindings according to table alias.
     pub fn apply_table_alias(&mut self, alias: &TableAlias) -> Result<()> {
+        let column_names: Vec<String> = alias.columns.iter().map(ToString::to_string).collect();
+        self.rename_columns(&alias.name.name, &column_names)
+    }
+
+    /// Rename the table of column bindings, and the leading columns with `column_names`.
+    pub fn rename_columns(&mut self, table_name: &str, column_names: &[String]) -> Result<()> {
         for column in self.columns.iter_mut() {
             column.database_name = None;
-            column.table_name = Some(alias.name.name.to_lowercase());
+            column.table_name = Some(table_name.to_lowercase());
         }
 
-        if alias.columns.len() > self.columns.len() {
+        if column_names.len() > self.columns.len() {
             return Err(ErrorCode::SemanticError(format!(
                 "table has {} columns available but {} columns specified",
                 self.columns.len(),
-                alias.columns.len()
+                column_names.len()
             )));
         }
-        for (index, column_name) in alias.columns.iter().map(ToString::to_string).enumerate() {
-            self.columns[index].column_name = column_name;
+        for (index, column_name) in column_names.iter().enumerate() {
+            self.columns[index].column_name = column_name.clone();
         }
         Ok(())
     }
 
+    /// Find the common table expression with given name in current context and its parents.
+    pub fn resolve_cte(&self, name: &str) -> Option<&CteInfo> {
+        let mut bind_context: &BindContext = self;
+        loop {
+            if let Some(cte_info) = bind_context.ctes_map.get(name) {
+                return Some(cte_info);
+            }
+            match &bind_context.parent {
+                Some(parent) => bind_context = parent,
+                None => return None,
+            }
+        }
+    }
+
+    /// Returns all the common table expressions visible in current context, the ones of inner
+    /// queries shadow the outer ones with the same names.
+    pub fn visible_ctes(&self) -> HashMap<String, CteInfo> {
+        let mut ctes = match &self.parent {
+            Some(parent) => parent.visible_ctes(),
+            None => HashMap::new(),
+        };
+        for (name, cte_info) in self.ctes_map.iter() {
+            ctes.insert(name.clone(), cte_info.clone());
+        }
+        ctes
+    }
+
     /// Try to find a column binding with given table name and column name.
     /// This method will return error if the given names are ambiguous or invalid.
     pub fn resolve_column(
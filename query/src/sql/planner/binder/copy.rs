@@ -24,6 +24,7 @@ use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::OnErrorMode;
 use common_meta_types::UserStageInfo;
 use common_planners::ReadDataSourcePlan;
 use common_planners::SourceInfo;
@@ -263,6 +264,7 @@ impl<'a> Binder {
             files: stmt.files.clone(),
             pattern: stmt.pattern.clone(),
             validation_mode,
+            force: stmt.force,
         })))
     }
 
@@ -324,6 +326,7 @@ impl<'a> Binder {
             files: stmt.files.clone(),
             pattern: stmt.pattern.clone(),
             validation_mode,
+            force: stmt.force,
         })))
     }
 
@@ -505,17 +508,21 @@ impl<'a> Binder {
 
         // Copy options.
         {
-            // TODO(xuanwo): COPY should handle on error.
             // on_error.
-            // if !stmt.on_error.is_empty() {
-            //     stage_info.copy_options.on_error =
-            //         OnErrorMode::from_str(&self.on_error).map_err(ErrorCode::SyntaxException)?;
-            // }
+            if !stmt.on_error.is_empty() {
+                stage.copy_options.on_error =
+                    OnErrorMode::from_str(&stmt.on_error).map_err(ErrorCode::SyntaxException)?;
+            }
 
             // size_limit.
             if stmt.size_limit != 0 {
                 stage.copy_options.size_limit = stmt.size_limit;
             }
+
+            // max_file_size.
+            if stmt.max_file_size != 0 {
+                stage.copy_options.max_file_size = stmt.max_file_size;
+            }
         }
 
         Ok(())
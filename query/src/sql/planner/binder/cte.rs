@@ -0,0 +1,313 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_ast::ast::Query;
+use common_ast::ast::SetExpr;
+use common_ast::ast::SetOperator;
+use common_ast::ast::Statement;
+use common_ast::ast::TableAlias;
+use common_ast::ast::With;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_ast::DisplayError;
+use common_base::infallible::RwLock;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::cast_with_type;
+use common_functions::scalars::DEFAULT_CAST_OPTIONS;
+use futures::TryStreamExt;
+
+use crate::catalogs::CATALOG_DEFAULT;
+use crate::interpreters::SelectInterpreterV2;
+use crate::sql::binder::Binder;
+use crate::sql::optimizer::optimize_query;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::binder::CteInfo;
+use crate::sql::planner::metadata::Metadata;
+use crate::sql::planner::metadata::MetadataRef;
+use crate::sql::BindContext;
+use crate::storages::Table;
+use crate::storages::ToReadDataSourcePlan;
+
+impl<'a> Binder {
+    /// Bind the `WITH` clause, the common table expressions are added to `bind_context`,
+    /// and each of them can see the ones defined before it.
+    ///
+    /// A `MATERIALIZED` common table expression is evaluated here once, and its result is
+    /// shared by all the references. So is a recursive one of `WITH RECURSIVE`, which is
+    /// evaluated by iterating its recursive term until no more rows are produced.
+    pub(super) async fn bind_with(
+        &mut self,
+        bind_context: &mut BindContext,
+        with: &With<'a>,
+    ) -> Result<()> {
+        let mut scope = bind_context.visible_ctes();
+        for cte in with.ctes.iter() {
+            let name = cte.alias.name.name.to_lowercase();
+            if bind_context.ctes_map.contains_key(&name) {
+                return Err(ErrorCode::SemanticError(cte.alias.name.span.display_error(
+                    format!("duplicate common table expression name {}", name),
+                )));
+            }
+
+            let mut cte_info = CteInfo {
+                columns: cte
+                    .alias
+                    .columns
+                    .iter()
+                    .map(|column| column.name.to_lowercase())
+                    .collect(),
+                query: query_text(&cte.query)?,
+                scope: scope.clone(),
+                table: None,
+            };
+            if with.recursive && is_union(&cte.query.body) {
+                cte_info.table = Some(
+                    self.materialize_recursive_cte(&name, &cte_info, &cte.query)
+                        .await?,
+                );
+            } else if cte.materialized {
+                cte_info.table = Some(self.materialize_cte(&name, &cte_info, &cte.query).await?);
+            }
+
+            scope.insert(name.clone(), cte_info.clone());
+            bind_context.ctes_map.insert(name, cte_info);
+        }
+        Ok(())
+    }
+
+    /// Bind a reference to the common table expression `name`, which is either a scan of its
+    /// materialized result, or its query bound again like a view.
+    pub(super) async fn bind_cte(
+        &mut self,
+        bind_context: &BindContext,
+        name: &str,
+        cte_info: &CteInfo,
+        alias: &Option<TableAlias<'a>>,
+    ) -> Result<(SExpr, BindContext)> {
+        let (s_expr, mut cte_context) = match &cte_info.table {
+            Some(table) => {
+                let database = self.ctx.get_current_database();
+                let source = table
+                    .read_plan_with_catalog(self.ctx.clone(), CATALOG_DEFAULT.to_string(), None)
+                    .await?;
+                let table_index = self.metadata.write().add_table(
+                    CATALOG_DEFAULT.to_string(),
+                    database.clone(),
+                    table.clone(),
+                    source,
+                );
+                self.bind_base_table(bind_context, database.as_str(), table_index)
+                    .await?
+            }
+            None => {
+                let tokens = tokenize_sql(cte_info.query.as_str())?;
+                let backtrace = Backtrace::new();
+                let (stmt, _) = parse_sql(&tokens, &backtrace)?;
+                let query = match &stmt {
+                    Statement::Query(query) => query,
+                    _ => {
+                        return Err(ErrorCode::LogicalError(format!(
+                            "Invalid common table expression: {}",
+                            name
+                        )));
+                    }
+                };
+
+                // The query can only see the common table expressions of its scope.
+                let mut scope_context = BindContext::new();
+                scope_context.ctes_map = cte_info.scope.clone();
+                let (s_expr, mut cte_context) = self.bind_query(&scope_context, query).await?;
+                cte_context.parent = Some(Box::new(bind_context.clone()));
+                (s_expr, cte_context)
+            }
+        };
+
+        cte_context.rename_columns(name, &cte_info.columns)?;
+        if let Some(alias) = alias {
+            cte_context.apply_table_alias(alias)?;
+        }
+        Ok((s_expr, cte_context))
+    }
+
+    async fn materialize_cte(
+        &self,
+        name: &str,
+        cte_info: &CteInfo,
+        query: &Query<'a>,
+    ) -> Result<Arc<dyn Table>> {
+        let set_expr = SetExpr::Query(Box::new(query.clone()));
+        let (mut result_context, blocks, _) = self
+            .execute_cte_query(cte_info.scope.clone(), &set_expr)
+            .await?;
+        result_context.rename_columns(name, &cte_info.columns)?;
+
+        let schema = result_context.output_schema();
+        let blocks = blocks
+            .iter()
+            .map(|block| DataBlock::create(schema.clone(), block.columns().to_vec()))
+            .collect();
+        Ok(self.ctx.create_query_table(name, schema, blocks))
+    }
+
+    /// Evaluate `anchor UNION ALL recursive_term`. The anchor is evaluated first, then the
+    /// recursive term is evaluated repeatedly, the references to `name` in it read the rows
+    /// produced by the last iteration. The iteration stops once no rows are produced.
+    async fn materialize_recursive_cte(
+        &self,
+        name: &str,
+        cte_info: &CteInfo,
+        query: &Query<'a>,
+    ) -> Result<Arc<dyn Table>> {
+        let (anchor, recursive_term) = match &query.body {
+            SetExpr::SetOperation(set_operation)
+                if set_operation.op == SetOperator::Union && set_operation.all =>
+            {
+                (&set_operation.left, &set_operation.right)
+            }
+            _ => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Recursive common table expression {} must be in the form of `anchor UNION ALL recursive_term`",
+                    name
+                )));
+            }
+        };
+        if !query.order_by.is_empty() || !query.limit.is_empty() || query.offset.is_some() {
+            return Err(ErrorCode::UnImplement(format!(
+                "ORDER BY and LIMIT are not supported in recursive common table expression {}",
+                name
+            )));
+        }
+
+        let (mut anchor_context, blocks, _) = self
+            .execute_cte_query(cte_info.scope.clone(), anchor)
+            .await?;
+        anchor_context.rename_columns(name, &cte_info.columns)?;
+        let schema = anchor_context.output_schema();
+
+        let mut working_blocks: Vec<DataBlock> = blocks
+            .iter()
+            .map(|block| DataBlock::create(schema.clone(), block.columns().to_vec()))
+            .collect();
+        let mut result_blocks = working_blocks.clone();
+
+        let func_ctx = self.ctx.try_get_function_context()?;
+        let max_depth = self.ctx.get_settings().get_cte_max_recursion_depth()?;
+        let mut depth = 0;
+        while working_blocks.iter().any(|block| block.num_rows() > 0) {
+            if depth >= max_depth {
+                return Err(ErrorCode::SemanticError(format!(
+                    "Recursive common table expression {} aborted after {} iterations, try increasing cte_max_recursion_depth",
+                    name, depth
+                )));
+            }
+            depth += 1;
+
+            let working_table =
+                self.ctx
+                    .create_query_table(name, schema.clone(), working_blocks.clone());
+            let mut scope = cte_info.scope.clone();
+            scope.insert(name.to_string(), CteInfo {
+                table: Some(working_table.clone()),
+                ..cte_info.clone()
+            });
+
+            let (step_context, blocks, metadata) =
+                self.execute_cte_query(scope, recursive_term).await?;
+            if step_context.columns.len() != schema.num_fields() {
+                return Err(ErrorCode::SemanticError(format!(
+                    "The anchor and recursive term of common table expression {} must have the same number of columns",
+                    name
+                )));
+            }
+
+            working_blocks = Vec::with_capacity(blocks.len());
+            for block in blocks.iter() {
+                let mut columns = Vec::with_capacity(block.num_columns());
+                for ((column, column_binding), field) in block
+                    .columns()
+                    .iter()
+                    .zip(step_context.columns.iter())
+                    .zip(schema.fields().iter())
+                {
+                    columns.push(cast_with_type(
+                        column,
+                        &column_binding.data_type,
+                        field.data_type(),
+                        &DEFAULT_CAST_OPTIONS,
+                        &func_ctx,
+                    )?);
+                }
+                working_blocks.push(DataBlock::create(schema.clone(), columns));
+            }
+            result_blocks.extend(working_blocks.iter().cloned());
+
+            // Without the self reference the recursive term produces the same rows every time.
+            let working_table_id = working_table.get_id();
+            let is_recursive = metadata
+                .read()
+                .tables()
+                .iter()
+                .any(|table| table.table.get_id() == working_table_id);
+            if !is_recursive {
+                break;
+            }
+        }
+
+        Ok(self.ctx.create_query_table(name, schema, result_blocks))
+    }
+
+    /// Bind and execute the query with a separate binder, so the plans of the common table
+    /// expressions don't pollute the metadata of the query referring to them.
+    async fn execute_cte_query(
+        &self,
+        scope: HashMap<String, CteInfo>,
+        set_expr: &SetExpr<'a>,
+    ) -> Result<(BindContext, Vec<DataBlock>, MetadataRef)> {
+        let metadata = Arc::new(RwLock::new(Metadata::create()));
+        let mut binder = Binder::new(self.ctx.clone(), self.catalogs.clone(), metadata.clone());
+        let mut bind_context = BindContext::new();
+        bind_context.ctes_map = scope;
+        let (s_expr, bind_context) = binder.bind_set_expr(&bind_context, set_expr, &[]).await?;
+        let s_expr = optimize_query(self.ctx.clone(), metadata.clone(), s_expr)?;
+
+        let interpreter = SelectInterpreterV2::create(
+            self.ctx.clone(),
+            bind_context.clone(),
+            s_expr,
+            metadata.clone(),
+        );
+        let blocks = interpreter.execute_local()?.try_collect().await?;
+        Ok((bind_context, blocks, metadata))
+    }
+}
+
+fn is_union(set_expr: &SetExpr) -> bool {
+    matches!(set_expr, SetExpr::SetOperation(set_operation) if set_operation.op == SetOperator::Union)
+}
+
+/// Returns the SQL text of the query, which is sliced from the source by the tokens it spans.
+fn query_text(query: &Query) -> Result<String> {
+    match (query.span.first(), query.span.last()) {
+        (Some(first), Some(last)) => Ok(first.source[first.span.start..last.span.end].to_string()),
+        _ => Err(ErrorCode::LogicalError(
+            "Common table expression with empty query",
+        )),
+    }
+}
@@ -26,6 +26,7 @@ use common_datavalues::Vu8;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableStatistics;
 use common_planners::OptimizeTableAction;
 use common_planners::*;
 
@@ -36,7 +37,11 @@ use crate::sql::plans::Plan;
 use crate::sql::BindContext;
 use crate::sql::ColumnBinding;
 use crate::sql::ScalarExpr;
+use crate::sql::OPT_KEY_CLONED_FROM_TABLE_IDS;
 use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::FuseTable;
+use crate::storages::Table;
 
 impl<'a> Binder {
     pub(in crate::sql::planner::binder) async fn bind_show_tables(
@@ -199,11 +204,20 @@ impl<'a> Binder {
             options.insert("TRANSIENT".to_owned(), "T".to_owned());
         }
 
+        if matches!(source, Some(CreateTableSource::Clone { .. })) && engine != Engine::Fuse {
+            return Err(ErrorCode::UnsupportedEngineParams(format!(
+                "Unsupported CREATE TABLE ... CLONE for engine: {}",
+                engine
+            )));
+        }
+
         // Build table schema
+        let mut statistics = TableStatistics::default();
         let (schema, field_comments) = match (&source, &as_query) {
             (Some(source), None) => {
                 // `CREATE TABLE` without `AS SELECT ...`
-                self.analyze_create_table_schema(source).await?
+                self.analyze_create_table_schema(source, &mut options, &mut statistics)
+                    .await?
             }
             (None, Some(query)) => {
                 // `CREATE TABLE AS SELECT ...` without column definitions
@@ -230,6 +244,7 @@ impl<'a> Binder {
             engine: engine.to_string(),
             options: options.clone(),
             field_comments,
+            statistics,
             ..Default::default()
         };
 
@@ -351,6 +366,7 @@ impl<'a> Binder {
             database,
             table,
             action,
+            dry_run,
         } = stmt;
 
         let tenant = self.ctx.get_tenant();
@@ -380,6 +396,42 @@ impl<'a> Binder {
                     entities,
                 })))
             }
+            AlterTableAction::AddColumn { column } => {
+                let bind_context = BindContext::new();
+                let mut scalar_binder =
+                    ScalarBinder::new(&bind_context, self.ctx.clone(), self.metadata.clone());
+                let field = Self::analyze_column_definition(&mut scalar_binder, column).await?;
+                let comment = column.comment.clone().unwrap_or_default();
+
+                Ok(Plan::AddTableColumn(Box::new(AddTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    field,
+                    comment,
+                })))
+            }
+            AlterTableAction::DropColumn { column } => {
+                Ok(Plan::DropTableColumn(Box::new(DropTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    column: column.name.clone(),
+                })))
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => Ok(Plan::RenameTableColumn(Box::new(RenameTableColumnPlan {
+                tenant,
+                catalog,
+                database,
+                table,
+                column: old_column.name.clone(),
+                new_column: new_column.name.clone(),
+            }))),
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
                 let schema = self
                     .ctx
@@ -406,6 +458,15 @@ impl<'a> Binder {
                     table,
                 },
             ))),
+            AlterTableAction::ReclusterTable { is_final } => {
+                Ok(Plan::ReclusterTable(Box::new(ReclusterTablePlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    is_final: *is_final,
+                })))
+            }
         }
     }
 
@@ -502,6 +563,7 @@ impl<'a> Binder {
             database,
             table,
             action,
+            dry_run,
         } = stmt;
 
         let catalog = catalog
@@ -520,12 +582,18 @@ impl<'a> Binder {
                 AstOptimizeTableAction::Compact => OptimizeTableAction::Compact,
             })
             .unwrap_or(OptimizeTableAction::Purge);
+        if *dry_run && action != OptimizeTableAction::Purge {
+            return Err(ErrorCode::SyntaxException(
+                "DRY RUN is only supported by PURGE",
+            ));
+        }
 
         Ok(Plan::OptimizeTable(Box::new(OptimizeTablePlan {
             catalog,
             database,
             table,
             action,
+            dry_run: *dry_run,
         })))
     }
 
@@ -559,6 +627,8 @@ impl<'a> Binder {
     async fn analyze_create_table_schema(
         &self,
         source: &CreateTableSource<'a>,
+        options: &mut BTreeMap<String, String>,
+        statistics: &mut TableStatistics,
     ) -> Result<(DataSchemaRef, Vec<String>)> {
         let bind_context = BindContext::new();
         match source {
@@ -568,21 +638,7 @@ impl<'a> Binder {
                 let mut fields = Vec::with_capacity(columns.len());
                 let mut fields_comments = Vec::with_capacity(columns.len());
                 for column in columns.iter() {
-                    let name = column.name.name.clone();
-                    let mut data_type = TypeFactory::instance()
-                        .get(column.data_type.to_string())?
-                        .clone();
-                    if column.nullable {
-                        data_type = NullableType::new_impl(data_type);
-                    }
-                    let field = DataField::new(&name, data_type).with_default_expr({
-                        if let Some(default_expr) = &column.default_expr {
-                            scalar_binder.bind(default_expr).await?;
-                            Some(default_expr.to_string())
-                        } else {
-                            None
-                        }
-                    });
+                    let field = Self::analyze_column_definition(&mut scalar_binder, column).await?;
                     fields.push(field);
                     fields_comments.push(column.comment.clone().unwrap_or_default());
                 }
@@ -605,9 +661,96 @@ impl<'a> Binder {
                 let table = self.ctx.get_table(&catalog, &database, &table_name).await?;
                 Ok((table.schema(), table.field_comments().clone()))
             }
+            CreateTableSource::Clone {
+                catalog,
+                database,
+                table,
+                travel_point,
+            } => {
+                let catalog = catalog
+                    .as_ref()
+                    .map(|catalog| catalog.name.to_lowercase())
+                    .unwrap_or_else(|| self.ctx.get_current_catalog());
+                let database = database
+                    .as_ref()
+                    .map(|ident| ident.name.to_lowercase())
+                    .unwrap_or_else(|| self.ctx.get_current_database());
+                let table_name = table.name.to_lowercase();
+                let navigation_point = match travel_point {
+                    Some(tp) => Some(self.resolve_data_travel_point(&bind_context, tp).await?),
+                    None => None,
+                };
+                let tenant = self.ctx.get_tenant();
+                let table = self
+                    .resolve_data_source(
+                        &tenant,
+                        &catalog,
+                        &database,
+                        &table_name,
+                        &navigation_point,
+                    )
+                    .await?;
+                self.analyze_clone_options(table.as_ref(), options)?;
+                // the statistics are shared along with the data
+                *statistics = table.get_table_info().meta.statistics.clone();
+                Ok((table.schema(), table.field_comments().clone()))
+            }
         }
     }
 
+    /// The table being cloned shares the snapshot of the source table, rather than copying the
+    /// data, and inherits the options of the source table, unless they are specified.
+    fn analyze_clone_options(
+        &self,
+        source: &dyn Table,
+        options: &mut BTreeMap<String, String>,
+    ) -> Result<()> {
+        let source = FuseTable::try_from_table(source)?;
+        let source_options = source.get_table_info().options();
+        for (k, v) in source_options {
+            if is_reserved_opt_key(k) || k == OPT_KEY_SNAPSHOT_LOCATION || k == "TRANSIENT" {
+                continue;
+            }
+            options.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+
+        if let Some(snapshot_loc) = source.snapshot_loc() {
+            options.insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), snapshot_loc);
+        }
+
+        // the data of the source table, as well as the tables it is cloned from, are shared
+        let mut cloned_from = vec![source.get_id().to_string()];
+        if let Some(ids) = source_options.get(OPT_KEY_CLONED_FROM_TABLE_IDS) {
+            cloned_from.push(ids.clone());
+        }
+        options.insert(
+            OPT_KEY_CLONED_FROM_TABLE_IDS.to_owned(),
+            cloned_from.join(","),
+        );
+        Ok(())
+    }
+
+    async fn analyze_column_definition<'b>(
+        scalar_binder: &mut ScalarBinder<'b>,
+        column: &ColumnDefinition<'b>,
+    ) -> Result<DataField> {
+        let name = column.name.name.clone();
+        let mut data_type = TypeFactory::instance()
+            .get(column.data_type.to_string())?
+            .clone();
+        if column.nullable {
+            data_type = NullableType::new_impl(data_type);
+        }
+        let default_expr = match &column.default_expr {
+            Some(default_expr) => {
+                scalar_binder.bind(default_expr).await?;
+                Some(default_expr.to_string())
+            }
+            None => None,
+        };
+        Ok(DataField::new(&name, data_type).with_default_expr(default_expr))
+    }
+
     fn insert_table_option_with_validation(
         &self,
         options: &mut BTreeMap<String, String>,
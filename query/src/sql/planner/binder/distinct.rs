@@ -21,6 +21,7 @@ use crate::sql::binder::ColumnBinding;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::semantic::GroupingChecker;
 use crate::sql::plans::Aggregate;
+use crate::sql::plans::AggregateMode;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::EvalScalar;
 use crate::sql::plans::Scalar;
@@ -73,6 +74,8 @@ impl Binder {
             group_items,
             aggregate_functions: vec![],
             from_distinct: true,
+            grouping_sets: None,
+            mode: AggregateMode::Initial,
         };
 
         Ok(SExpr::create_unary(distinct_plan.into(), new_expr))
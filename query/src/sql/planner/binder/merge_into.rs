@@ -0,0 +1,349 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::MatchOperation;
+use common_ast::ast::MergeClause;
+use common_ast::ast::MergeIntoStmt;
+use common_ast::ast::UpdateExpr;
+use common_datavalues::type_coercion::merge_types;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::MergeIntoPlan;
+use common_planners::MergeMatchedClause;
+use common_planners::MergeMatchedOperation;
+use common_planners::MergeUnmatchedClause;
+
+use crate::sql::binder::scalar_common::split_conjunctions;
+use crate::sql::binder::scalar_common::split_equivalent_predicate;
+use crate::sql::binder::scalar_common::wrap_cast_if_needed;
+use crate::sql::binder::Binder;
+use crate::sql::binder::ScalarBinder;
+use crate::sql::exec::format_field_name;
+use crate::sql::exec::ExpressionBuilder;
+use crate::sql::optimizer::optimize;
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::plans::MergeInto;
+use crate::sql::plans::Plan;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+use crate::sql::BindContext;
+use crate::sql::ColumnBinding;
+use crate::sql::PlanParser;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_merge_into(
+        &mut self,
+        bind_context: &BindContext,
+        stmt: &MergeIntoStmt<'a>,
+    ) -> Result<Plan> {
+        let MergeIntoStmt {
+            catalog,
+            database,
+            table,
+            alias,
+            source,
+            join_expr,
+            clauses,
+        } = stmt;
+
+        let catalog_name = match catalog {
+            Some(catalog) => catalog.name.clone(),
+            None => self.ctx.get_current_catalog(),
+        };
+        let database_name = match database {
+            Some(database) => database.name.clone(),
+            None => self.ctx.get_current_database(),
+        };
+        let table_name = table.name.clone();
+        let table = self
+            .ctx
+            .get_table(&catalog_name, &database_name, &table_name)
+            .await?;
+        let schema = table.schema();
+
+        // the source is planned as a query on its own, its result is joined with the target
+        // table by the storage while merging.
+        let (s_expr, source_context) = self.bind_table_reference(bind_context, source).await?;
+        let source_plan = optimize(self.ctx.clone(), Plan::Query {
+            s_expr,
+            metadata: self.metadata.clone(),
+            bind_context: Box::new(source_context.clone()),
+        })?;
+        let source_schema = self.merge_source_schema(&source_context);
+
+        // the columns of the target table are referred to by the alias, if any
+        let (target_database, target_table) = match alias {
+            Some(alias) => (None, alias.name.name.clone()),
+            None => (Some(database_name.clone()), table_name.clone()),
+        };
+        let mut target_context = BindContext::with_parent(Box::new(bind_context.clone()));
+        let mut target_columns = Vec::with_capacity(schema.num_fields());
+        for field in schema.fields() {
+            let index = self.metadata.write().add_column(
+                field.name().clone(),
+                field.data_type().clone(),
+                None,
+            );
+            target_columns.push(format_field_name(field.name(), index));
+            target_context.add_column_binding(ColumnBinding {
+                database_name: target_database.clone(),
+                table_name: Some(target_table.clone()),
+                column_name: field.name().to_lowercase(),
+                index,
+                data_type: field.data_type().clone(),
+                visible_in_unqualified_wildcard: true,
+            });
+        }
+
+        let mut merge_context = target_context.clone();
+        for column in source_context.columns.iter() {
+            merge_context.add_column_binding(column.clone());
+        }
+
+        // split the `ON` clause into the equi-join keys and the rest of conditions
+        let target_set = Self::column_set(&target_context);
+        let source_set = Self::column_set(&source_context);
+        let mut scalar_binder =
+            ScalarBinder::new(&merge_context, self.ctx.clone(), self.metadata.clone());
+        let (join_scalar, _) = scalar_binder.bind(join_expr).await?;
+        let mut target_keys = vec![];
+        let mut source_keys = vec![];
+        let mut join_filter: Option<Expression> = None;
+        for predicate in split_conjunctions(&join_scalar) {
+            let keys = split_equivalent_predicate(&predicate).and_then(|(left, right)| {
+                if Self::refers_to_only(&left, &target_set)
+                    && Self::refers_to_only(&right, &source_set)
+                {
+                    Some((left, right))
+                } else if Self::refers_to_only(&left, &source_set)
+                    && Self::refers_to_only(&right, &target_set)
+                {
+                    Some((right, left))
+                } else {
+                    None
+                }
+            });
+            match keys {
+                Some((target_key, source_key)) => {
+                    let key_type = merge_types(&target_key.data_type(), &source_key.data_type())?;
+                    let target_key = wrap_cast_if_needed(target_key, &key_type);
+                    let source_key = wrap_cast_if_needed(source_key, &key_type);
+                    target_keys.push(self.build_merge_expression(&target_key)?);
+                    source_keys.push(self.build_merge_expression(&source_key)?);
+                }
+                None => {
+                    let expr = self.build_merge_expression(&predicate)?;
+                    join_filter = Some(match join_filter {
+                        None => expr,
+                        Some(filter) => filter.and(expr),
+                    });
+                }
+            }
+        }
+        if target_keys.is_empty() {
+            return Err(ErrorCode::SemanticError(
+                "the ON clause of MERGE INTO should contain at least one equality condition between the target table and the source".to_string(),
+            ));
+        }
+
+        let mut matched_clauses = vec![];
+        let mut unmatched_clauses = vec![];
+        for clause in clauses {
+            match clause {
+                MergeClause::Matched {
+                    selection,
+                    operation,
+                } => {
+                    let mut scalar_binder =
+                        ScalarBinder::new(&merge_context, self.ctx.clone(), self.metadata.clone());
+                    let condition = match selection {
+                        Some(expr) => {
+                            let (scalar, _) = scalar_binder.bind(expr).await?;
+                            Some(self.build_merge_expression(&scalar)?)
+                        }
+                        None => None,
+                    };
+                    let operation = match operation {
+                        MatchOperation::Update { update_list } => MergeMatchedOperation::Update {
+                            update_list: self
+                                .bind_merge_update_list(&mut scalar_binder, &schema, update_list)
+                                .await?,
+                        },
+                        MatchOperation::Delete => MergeMatchedOperation::Delete,
+                    };
+                    matched_clauses.push(MergeMatchedClause {
+                        condition,
+                        operation,
+                    });
+                }
+                MergeClause::NotMatched {
+                    selection,
+                    columns,
+                    values,
+                } => {
+                    // there is no target row for the unmatched source rows
+                    let mut scalar_binder =
+                        ScalarBinder::new(&source_context, self.ctx.clone(), self.metadata.clone());
+                    let condition = match selection {
+                        Some(expr) => {
+                            let (scalar, _) = scalar_binder.bind(expr).await?;
+                            Some(self.build_merge_expression(&scalar)?)
+                        }
+                        None => None,
+                    };
+                    let values = self
+                        .bind_merge_insert_values(&mut scalar_binder, &schema, columns, values)
+                        .await?;
+                    unmatched_clauses.push(MergeUnmatchedClause { condition, values });
+                }
+            }
+        }
+
+        let plan = MergeIntoPlan {
+            catalog_name,
+            database_name,
+            table_name,
+            table_id: table.get_table_info().ident.clone(),
+            target_columns,
+            source_schema,
+            target_keys,
+            source_keys,
+            join_filter,
+            matched_clauses,
+            unmatched_clauses,
+        };
+        Ok(Plan::MergeInto(Box::new(MergeInto {
+            source: Box::new(source_plan),
+            plan,
+        })))
+    }
+
+    async fn bind_merge_update_list<'b>(
+        &self,
+        scalar_binder: &mut ScalarBinder<'b>,
+        schema: &DataSchemaRef,
+        update_list: &'b [UpdateExpr<'b>],
+    ) -> Result<Vec<(usize, Expression)>> {
+        let mut update_columns = Vec::with_capacity(update_list.len());
+        for update_expr in update_list {
+            let index = Self::merge_column_index(schema, &update_expr.name)?;
+            if update_columns.iter().any(|(idx, _)| *idx == index) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "multiple assignments to the same column [{}]",
+                    update_expr.name.name.to_lowercase()
+                )));
+            }
+            let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+            update_columns.push((index, self.build_merge_expression(&scalar)?));
+        }
+        Ok(update_columns)
+    }
+
+    /// Binds the values of `INSERT`, the columns which are not listed take their default values.
+    async fn bind_merge_insert_values<'b>(
+        &self,
+        scalar_binder: &mut ScalarBinder<'b>,
+        schema: &DataSchemaRef,
+        columns: &[Identifier<'b>],
+        values: &'b [Expr<'b>],
+    ) -> Result<Vec<Expression>> {
+        let indices = if columns.is_empty() {
+            (0..schema.num_fields()).collect::<Vec<_>>()
+        } else {
+            let mut indices = Vec::with_capacity(columns.len());
+            for column in columns {
+                let index = Self::merge_column_index(schema, column)?;
+                if indices.contains(&index) {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "column [{}] is specified more than once",
+                        column.name.to_lowercase()
+                    )));
+                }
+                indices.push(index);
+            }
+            indices
+        };
+        if indices.len() != values.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "the number of values {} does not match the number of columns {}",
+                values.len(),
+                indices.len()
+            )));
+        }
+
+        let mut bound_values: Vec<Option<Expression>> = vec![None; schema.num_fields()];
+        for (index, value) in indices.into_iter().zip(values) {
+            let (scalar, _) = scalar_binder.bind(value).await?;
+            bound_values[index] = Some(self.build_merge_expression(&scalar)?);
+        }
+
+        bound_values
+            .into_iter()
+            .zip(schema.fields())
+            .map(|(value, field)| match value {
+                Some(value) => Ok(value),
+                None => match field.default_expr() {
+                    Some(default_expr) => PlanParser::parse_expr(default_expr),
+                    None => Ok(Expression::create_literal_with_type(
+                        field.data_type().default_value(),
+                        field.data_type().clone(),
+                    )),
+                },
+            })
+            .collect()
+    }
+
+    fn build_merge_expression(&self, scalar: &Scalar) -> Result<Expression> {
+        ExpressionBuilder::create(self.metadata.clone()).build(scalar)
+    }
+
+    /// The schema of the source rows, whose fields are named in the same way as
+    /// the expressions built by `ExpressionBuilder` refer to them.
+    fn merge_source_schema(&self, source_context: &BindContext) -> DataSchemaRef {
+        let metadata = self.metadata.read();
+        let fields = source_context
+            .columns
+            .iter()
+            .map(|column| {
+                let name = format_field_name(&metadata.column(column.index).name, column.index);
+                DataField::new(&name, column.data_type.clone())
+            })
+            .collect();
+        DataSchemaRefExt::create(fields)
+    }
+
+    fn merge_column_index(schema: &DataSchemaRef, column: &Identifier) -> Result<usize> {
+        let column_name = column.name.to_lowercase();
+        schema
+            .fields()
+            .iter()
+            .position(|f| f.name().to_lowercase() == column_name)
+            .ok_or_else(|| ErrorCode::UnknownColumn(format!("Column [{}] not found", column_name)))
+    }
+
+    fn column_set(bind_context: &BindContext) -> ColumnSet {
+        bind_context.columns.iter().map(|c| c.index).collect()
+    }
+
+    fn refers_to_only(scalar: &Scalar, columns: &ColumnSet) -> bool {
+        let used_columns = scalar.used_columns();
+        !used_columns.is_empty() && used_columns.is_subset(columns)
+    }
+}
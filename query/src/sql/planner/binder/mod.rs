@@ -17,6 +17,7 @@ use std::sync::Arc;
 pub use aggregate::AggregateInfo;
 pub use bind_context::BindContext;
 pub use bind_context::ColumnBinding;
+pub use bind_context::CteInfo;
 use common_ast::ast::Statement;
 use common_datavalues::DataTypeImpl;
 use common_exception::ErrorCode;
@@ -32,8 +33,10 @@ use common_planners::DropUserPlan;
 use common_planners::DropUserStagePlan;
 use common_planners::DropUserUDFPlan;
 use common_planners::ShowGrantsPlan;
+pub use placeholder::rewrite_placeholders;
 pub use scalar::ScalarBinder;
 pub use scalar_common::*;
+pub use window::WindowInfo;
 
 use super::plans::Plan;
 use crate::catalogs::CatalogManager;
@@ -43,12 +46,15 @@ use crate::sql::planner::metadata::MetadataRef;
 mod aggregate;
 mod bind_context;
 mod copy;
+mod cte;
 mod ddl;
 mod delete;
 mod distinct;
 mod insert;
 mod join;
 mod limit;
+mod merge_into;
+mod placeholder;
 mod project;
 mod scalar;
 mod scalar_common;
@@ -57,6 +63,8 @@ mod select;
 mod show;
 mod sort;
 mod table;
+mod update;
+mod window;
 
 /// Binder is responsible to transform AST of a query into a canonical logical SExpr.
 ///
@@ -203,6 +211,8 @@ impl<'a> Binder {
                 self.bind_delete(bind_context, catalog, database, table, selection)
                     .await?
             }
+            Statement::Update(stmt) => self.bind_update(bind_context, stmt).await?,
+            Statement::MergeInto(stmt) => self.bind_merge_into(bind_context, stmt).await?,
 
             // Permissions
             Statement::Grant(stmt) => self.bind_grant(stmt).await?,
@@ -0,0 +1,176 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::Aggregate;
+use crate::sql::plans::AggregateFunction;
+use crate::sql::plans::AndExpr;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::EvalScalar;
+use crate::sql::plans::Filter;
+use crate::sql::plans::FunctionCall;
+use crate::sql::plans::LogicalInnerJoin;
+use crate::sql::plans::OrExpr;
+use crate::sql::plans::Placeholder;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarItem;
+use crate::sql::plans::SubqueryExpr;
+use crate::sql::plans::Window;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
+
+/// Rewrite every placeholder in a bound plan with `f`, including the ones in subqueries.
+///
+/// It's used to collect the types of the parameters of a prepared statement, and to
+/// substitute the parameters with the values bound at execution.
+pub fn rewrite_placeholders<F>(s_expr: &SExpr, f: &mut F) -> Result<SExpr>
+where F: FnMut(&Placeholder) -> Result<Scalar> {
+    let plan: RelOperator = match s_expr.plan() {
+        RelOperator::Filter(filter) => Filter {
+            predicates: rewrite_scalars(&filter.predicates, f)?,
+            is_having: filter.is_having,
+        }
+        .into(),
+        RelOperator::EvalScalar(eval_scalar) => EvalScalar {
+            items: rewrite_items(&eval_scalar.items, f)?,
+        }
+        .into(),
+        RelOperator::Aggregate(agg) => Aggregate {
+            group_items: rewrite_items(&agg.group_items, f)?,
+            aggregate_functions: rewrite_items(&agg.aggregate_functions, f)?,
+            ..agg.clone()
+        }
+        .into(),
+        RelOperator::LogicalInnerJoin(join) => LogicalInnerJoin {
+            left_conditions: rewrite_scalars(&join.left_conditions, f)?,
+            right_conditions: rewrite_scalars(&join.right_conditions, f)?,
+            other_conditions: rewrite_scalars(&join.other_conditions, f)?,
+            ..join.clone()
+        }
+        .into(),
+        RelOperator::Window(window) => Window {
+            function: rewrite_window_function(&window.function, f)?,
+            ..window.clone()
+        }
+        .into(),
+        plan => plan.clone(),
+    };
+
+    let children = s_expr
+        .children()
+        .iter()
+        .map(|child| rewrite_placeholders(child, f))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(SExpr::create(plan, children, s_expr.original_group()))
+}
+
+fn rewrite_scalar<F>(scalar: &Scalar, f: &mut F) -> Result<Scalar>
+where F: FnMut(&Placeholder) -> Result<Scalar> {
+    match scalar {
+        Scalar::BoundColumnRef(_) | Scalar::ConstantExpr(_) => Ok(scalar.clone()),
+        Scalar::AndExpr(scalar) => Ok(AndExpr {
+            left: Box::new(rewrite_scalar(&scalar.left, f)?),
+            right: Box::new(rewrite_scalar(&scalar.right, f)?),
+            return_type: scalar.return_type.clone(),
+        }
+        .into()),
+        Scalar::OrExpr(scalar) => Ok(OrExpr {
+            left: Box::new(rewrite_scalar(&scalar.left, f)?),
+            right: Box::new(rewrite_scalar(&scalar.right, f)?),
+            return_type: scalar.return_type.clone(),
+        }
+        .into()),
+        Scalar::ComparisonExpr(scalar) => Ok(ComparisonExpr {
+            op: scalar.op.clone(),
+            left: Box::new(rewrite_scalar(&scalar.left, f)?),
+            right: Box::new(rewrite_scalar(&scalar.right, f)?),
+            return_type: scalar.return_type.clone(),
+        }
+        .into()),
+        Scalar::AggregateFunction(agg) => Ok(AggregateFunction {
+            args: rewrite_scalars(&agg.args, f)?,
+            ..agg.clone()
+        }
+        .into()),
+        Scalar::WindowFunction(func) => Ok(rewrite_window_function(func, f)?.into()),
+        // The types of the placeholders are kept by the substituted scalars,
+        // so the types of the arguments are still valid.
+        Scalar::FunctionCall(func) => Ok(FunctionCall {
+            arguments: rewrite_scalars(&func.arguments, f)?,
+            ..func.clone()
+        }
+        .into()),
+        Scalar::CastExpr(cast) => Ok(CastExpr {
+            argument: Box::new(rewrite_scalar(&cast.argument, f)?),
+            from_type: cast.from_type.clone(),
+            target_type: cast.target_type.clone(),
+        }
+        .into()),
+        Scalar::SubqueryExpr(subquery) => Ok(SubqueryExpr {
+            subquery: rewrite_placeholders(&subquery.subquery, f)?,
+            child_expr: match &subquery.child_expr {
+                Some(child_expr) => Some(Box::new(rewrite_scalar(child_expr, f)?)),
+                None => None,
+            },
+            ..subquery.clone()
+        }
+        .into()),
+        Scalar::Placeholder(placeholder) => f(placeholder),
+    }
+}
+
+fn rewrite_scalars<F>(scalars: &[Scalar], f: &mut F) -> Result<Vec<Scalar>>
+where F: FnMut(&Placeholder) -> Result<Scalar> {
+    scalars
+        .iter()
+        .map(|scalar| rewrite_scalar(scalar, f))
+        .collect()
+}
+
+fn rewrite_items<F>(items: &[ScalarItem], f: &mut F) -> Result<Vec<ScalarItem>>
+where F: FnMut(&Placeholder) -> Result<Scalar> {
+    items
+        .iter()
+        .map(|item| {
+            Ok(ScalarItem {
+                scalar: rewrite_scalar(&item.scalar, f)?,
+                index: item.index,
+            })
+        })
+        .collect()
+}
+
+fn rewrite_window_function<F>(func: &WindowFunction, f: &mut F) -> Result<WindowFunction>
+where F: FnMut(&Placeholder) -> Result<Scalar> {
+    Ok(WindowFunction {
+        args: rewrite_scalars(&func.args, f)?,
+        partition_by: rewrite_scalars(&func.partition_by, f)?,
+        order_by: func
+            .order_by
+            .iter()
+            .map(|order_by| {
+                Ok(WindowOrderBy {
+                    expr: rewrite_scalar(&order_by.expr, f)?,
+                    asc: order_by.asc,
+                    nulls_first: order_by.nulls_first,
+                })
+            })
+            .collect::<Result<_>>()?,
+        ..func.clone()
+    })
+}
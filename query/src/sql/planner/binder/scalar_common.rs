@@ -37,7 +37,6 @@ impl<'a, F> Finder<'a, F>
 where F: Fn(&Scalar) -> bool
 {
     /// Create a new finder with the `test_fn`
-    #[allow(dead_code)]
     fn new(find_fn: &'a F) -> Self {
         Self {
             find_fn,
@@ -62,6 +61,13 @@ where F: Fn(&Scalar) -> bool
     }
 }
 
+/// Returns true if there is any window function in the scalar expression.
+pub fn contains_window_function(scalar: &Scalar) -> Result<bool> {
+    let find_fn = |scalar: &Scalar| matches!(scalar, Scalar::WindowFunction(_));
+    let finder = scalar.accept(Finder::new(&find_fn))?;
+    Ok(!finder.scalars.is_empty())
+}
+
 pub fn split_conjunctions(scalar: &Scalar) -> Vec<Scalar> {
     match scalar {
         Scalar::AndExpr(AndExpr { left, right, .. }) => {
@@ -21,6 +21,7 @@ use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
+use crate::sql::plans::WindowFunction;
 
 /// Controls how the visitor recursion should proceed.
 pub enum Recursion<V: ScalarVisitor> {
@@ -57,6 +58,20 @@ pub trait ScalarVisitor: Sized {
                                         stack.push(RecursionProcessing::Call(arg));
                                     }
                                 }
+                                Scalar::WindowFunction(WindowFunction {
+                                    args,
+                                    partition_by,
+                                    order_by,
+                                    ..
+                                }) => {
+                                    for arg in args
+                                        .iter()
+                                        .chain(partition_by.iter())
+                                        .chain(order_by.iter().map(|order_by| &order_by.expr))
+                                    {
+                                        stack.push(RecursionProcessing::Call(arg));
+                                    }
+                                }
                                 Scalar::ComparisonExpr(ComparisonExpr { left, right, .. }) => {
                                     stack.push(RecursionProcessing::Call(&**left));
                                     stack.push(RecursionProcessing::Call(&**right));
@@ -78,7 +93,7 @@ pub trait ScalarVisitor: Sized {
                                 Scalar::CastExpr(CastExpr { argument, .. }) => {
                                     stack.push(RecursionProcessing::Call(argument))
                                 }
-                                Scalar::SubqueryExpr(_) => {}
+                                Scalar::SubqueryExpr(_) | Scalar::Placeholder(_) => {}
                             }
 
                             visitor
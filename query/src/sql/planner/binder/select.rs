@@ -26,10 +26,12 @@ use common_ast::ast::SelectTarget;
 use common_ast::ast::SetExpr;
 use common_ast::ast::SetOperator;
 use common_ast::ast::TableReference;
+use common_ast::DisplayError;
 use common_datavalues::type_coercion::merge_types;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::sql::binder::scalar_common::contains_window_function;
 use crate::sql::binder::scalar_common::split_conjunctions;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::binder::scalar::ScalarBinder;
@@ -93,8 +95,10 @@ impl<'a> Binder {
         let (mut scalar_items, projections) = self.analyze_projection(&select_list)?;
 
         // This will potentially add some alias group items to `from_context` if find some.
-        self.analyze_group_items(&mut from_context, &select_list, &stmt.group_by)
-            .await?;
+        if let Some(group_by) = &stmt.group_by {
+            self.analyze_group_items(&mut from_context, &select_list, group_by)
+                .await?;
+        }
 
         self.analyze_aggregate_select(&mut from_context, &mut select_list)?;
 
@@ -107,6 +111,9 @@ impl<'a> Binder {
             None
         };
 
+        // This will replace window functions in `scalar_items` with their output columns.
+        self.analyze_window_select(&mut from_context, &mut scalar_items)?;
+
         let order_items = self.analyze_order_items(
             &from_context,
             &scalar_items,
@@ -116,7 +123,7 @@ impl<'a> Binder {
         )?;
 
         if !from_context.aggregate_info.aggregate_functions.is_empty()
-            || !stmt.group_by.is_empty()
+            || stmt.group_by.is_some()
             || stmt.having.is_some()
         {
             s_expr = self.bind_aggregate(&mut from_context, s_expr).await?;
@@ -128,6 +135,10 @@ impl<'a> Binder {
             }
         }
 
+        if !from_context.windows.window_functions.is_empty() {
+            s_expr = self.bind_window(&mut from_context, s_expr)?;
+        }
+
         if stmt.distinct {
             s_expr = self.bind_distinct(&from_context, &projections, &mut scalar_items, s_expr)?;
         }
@@ -181,6 +192,17 @@ impl<'a> Binder {
         bind_context: &BindContext,
         query: &Query<'_>,
     ) -> Result<(SExpr, BindContext)> {
+        let with_context;
+        let bind_context = match &query.with {
+            Some(with) => {
+                let mut bind_context = bind_context.clone();
+                self.bind_with(&mut bind_context, with).await?;
+                with_context = bind_context;
+                &with_context
+            }
+            None => bind_context,
+        };
+
         let (mut s_expr, mut bind_context) = match query.body {
             SetExpr::Select(_) | SetExpr::Query(_) => {
                 self.bind_set_expr(bind_context, &query.body, &query.order_by)
@@ -235,6 +257,11 @@ impl<'a> Binder {
         let mut scalar_binder =
             ScalarBinder::new(bind_context, self.ctx.clone(), self.metadata.clone());
         let (scalar, _) = scalar_binder.bind(expr).await?;
+        if contains_window_function(&scalar)? {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "window functions are not allowed in WHERE".to_string(),
+            )));
+        }
         let filter_plan = Filter {
             predicates: split_conjunctions(&scalar),
             is_having: false,
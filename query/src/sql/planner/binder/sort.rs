@@ -75,10 +75,17 @@ impl<'a> Binder {
                             expr: order.clone(),
                             index: item.index,
                             name: item.column_name.clone(),
+                            // The item should be projected before sorting unless it's exactly
+                            // the column it refers to, a window function for example is
+                            // replaced with a reference to a different column.
                             need_project: scalar_items.get(&item.index).map_or(
                                 false,
                                 |scalar_item| {
-                                    !matches!(&scalar_item.scalar, Scalar::BoundColumnRef(_))
+                                    !matches!(
+                                        &scalar_item.scalar,
+                                        Scalar::BoundColumnRef(column_ref)
+                                            if column_ref.column.index == item.index
+                                    )
                                 },
                             ),
                         });
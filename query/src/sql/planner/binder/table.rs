@@ -33,7 +33,10 @@ use crate::catalogs::CATALOG_DEFAULT;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::Binder;
 use crate::sql::binder::ColumnBinding;
+use crate::sql::optimizer::ColumnStat;
+use crate::sql::optimizer::ColumnStatSet;
 use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::Statistics;
 use crate::sql::planner::semantic::TypeChecker;
 use crate::sql::plans::ConstantExpr;
 use crate::sql::plans::LogicalGet;
@@ -78,6 +81,7 @@ impl<'a> Binder {
         );
 
         self.bind_base_table(bind_context, database, table_index)
+            .await
     }
 
     pub(super) async fn bind_table_reference(
@@ -94,6 +98,14 @@ impl<'a> Binder {
                 alias,
                 travel_point,
             } => {
+                // A table name without catalog and database may refer to a common table expression
+                if catalog.is_none() && database.is_none() {
+                    let name = table.name.to_lowercase();
+                    if let Some(cte_info) = bind_context.resolve_cte(&name) {
+                        return self.bind_cte(bind_context, &name, cte_info, alias).await;
+                    }
+                }
+
                 // Get catalog name
                 let catalog = catalog
                     .as_ref()
@@ -156,8 +168,9 @@ impl<'a> Binder {
                             source,
                         );
 
-                        let (s_expr, mut bind_context) =
-                            self.bind_base_table(bind_context, database.as_str(), table_index)?;
+                        let (s_expr, mut bind_context) = self
+                            .bind_base_table(bind_context, database.as_str(), table_index)
+                            .await?;
                         if let Some(alias) = alias {
                             bind_context.apply_table_alias(alias)?;
                         }
@@ -212,8 +225,9 @@ impl<'a> Binder {
                     source,
                 );
 
-                let (s_expr, mut bind_context) =
-                    self.bind_base_table(bind_context, "system", table_index)?;
+                let (s_expr, mut bind_context) = self
+                    .bind_base_table(bind_context, "system", table_index)
+                    .await?;
                 if let Some(alias) = alias {
                     bind_context.apply_table_alias(alias)?;
                 }
@@ -234,12 +248,13 @@ impl<'a> Binder {
         }
     }
 
-    fn bind_base_table(
+    pub(super) async fn bind_base_table(
         &mut self,
         bind_context: &BindContext,
         database_name: &str,
         table_index: IndexType,
     ) -> Result<(SExpr, BindContext)> {
+        let statistics = self.collect_table_statistics(table_index).await?;
         let mut bind_context = BindContext::with_parent(Box::new(bind_context.clone()));
         let metadata = self.metadata.read();
         let columns = metadata.columns_by_table_index(table_index);
@@ -260,6 +275,7 @@ impl<'a> Binder {
                 LogicalGet {
                     table_index,
                     columns: columns.into_iter().map(|col| col.column_index).collect(),
+                    statistics,
                 }
                 .into(),
             ),
@@ -267,7 +283,45 @@ impl<'a> Binder {
         ))
     }
 
-    async fn resolve_data_source(
+    /// Collect the statistics of a base table for cost-based optimization, which are only
+    /// needed if `enable_cbo` is set.
+    async fn collect_table_statistics(&self, table_index: IndexType) -> Result<Statistics> {
+        if self.ctx.get_settings().get_enable_cbo()? == 0 {
+            return Ok(Statistics::default());
+        }
+
+        let (table, columns) = {
+            let metadata = self.metadata.read();
+            (
+                metadata.table(table_index).table.clone(),
+                metadata.columns_by_table_index(table_index),
+            )
+        };
+        let num_rows = table
+            .statistics(self.ctx.clone())
+            .await?
+            .and_then(|stat| stat.num_rows);
+        let mut column_stats = ColumnStatSet::new();
+        if let Some(num_rows) = num_rows {
+            let col_stats = table.column_statistics(self.ctx.clone()).await?;
+            // Columns of a table are added into metadata in the order of table schema
+            for (field_index, column) in columns.iter().enumerate() {
+                if let Some(stat) = col_stats.get(&(field_index as u32)) {
+                    column_stats.insert(
+                        column.column_index,
+                        ColumnStat::from_column_statistics(stat, num_rows),
+                    );
+                }
+            }
+        }
+
+        Ok(Statistics {
+            num_rows,
+            column_stats,
+        })
+    }
+
+    pub(in crate::sql::planner::binder) async fn resolve_data_source(
         &self,
         tenant: &str,
         catalog_name: &str,
@@ -285,7 +339,7 @@ impl<'a> Binder {
         Ok(table_meta)
     }
 
-    async fn resolve_data_travel_point(
+    pub(in crate::sql::planner::binder) async fn resolve_data_travel_point(
         &self,
         bind_context: &BindContext,
         travel_point: &TimeTravelPoint<'a>,
@@ -0,0 +1,142 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::UpdateStmt;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::RequireColumnsVisitor;
+use common_planners::RewriteHelper;
+use common_planners::UpdatePlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::binder::ScalarBinder;
+use crate::sql::exec::decode_field_name;
+use crate::sql::exec::ExpressionBuilder;
+use crate::sql::plans::Plan;
+use crate::sql::plans::Scalar;
+use crate::sql::BindContext;
+use crate::sql::ColumnBinding;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_update(
+        &mut self,
+        bind_context: &BindContext,
+        stmt: &UpdateStmt<'a>,
+    ) -> Result<Plan> {
+        let UpdateStmt {
+            catalog,
+            database,
+            table,
+            update_list,
+            selection,
+        } = stmt;
+
+        let catalog_name = match catalog {
+            Some(catalog) => catalog.name.clone(),
+            None => self.ctx.get_current_catalog(),
+        };
+        let database_name = match database {
+            Some(database) => database.name.clone(),
+            None => self.ctx.get_current_database(),
+        };
+        let table_name = table.name.clone();
+        let table = self
+            .ctx
+            .get_table(&catalog_name, &database_name, &table_name)
+            .await?;
+        let schema = table.schema();
+
+        // the columns of the table are visible to both the update list and the selection
+        let mut table_context = BindContext::with_parent(Box::new(bind_context.clone()));
+        for field in schema.fields() {
+            let index = self.metadata.write().add_column(
+                field.name().clone(),
+                field.data_type().clone(),
+                None,
+            );
+            table_context.add_column_binding(ColumnBinding {
+                database_name: Some(database_name.clone()),
+                table_name: Some(table_name.clone()),
+                column_name: field.name().to_lowercase(),
+                index,
+                data_type: field.data_type().clone(),
+                visible_in_unqualified_wildcard: true,
+            });
+        }
+        let mut scalar_binder =
+            ScalarBinder::new(&table_context, self.ctx.clone(), self.metadata.clone());
+
+        let mut update_columns = Vec::with_capacity(update_list.len());
+        for update_expr in update_list {
+            let column_name = update_expr.name.name.to_lowercase();
+            let index = schema
+                .fields()
+                .iter()
+                .position(|f| f.name().to_lowercase() == column_name)
+                .ok_or_else(|| {
+                    ErrorCode::UnknownColumn(format!("Column [{}] not found", column_name))
+                })?;
+            if update_columns.iter().any(|(idx, _)| *idx == index) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "multiple assignments to the same column [{}]",
+                    column_name
+                )));
+            }
+            let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+            update_columns.push((index, self.build_update_expression(&scalar)?));
+        }
+
+        let mut expression = None;
+        let mut projection = vec![];
+        if let Some(expr) = selection {
+            let (scalar, _) = scalar_binder.bind(expr).await?;
+            let pred_expr = self.build_update_expression(&scalar)?;
+            projection = Self::required_columns(&schema, &pred_expr)?;
+            expression = Some(pred_expr);
+        }
+
+        let plan = UpdatePlan {
+            catalog_name,
+            database_name,
+            table_name,
+            table_id: table.get_table_info().ident.clone(),
+            update_list: update_columns,
+            selection: expression,
+            projection,
+        };
+        Ok(Plan::Update(Box::new(plan)))
+    }
+
+    /// Builds the expression which refers to the columns by the names of the table fields,
+    /// so that it can be evaluated against the blocks of the table directly.
+    fn build_update_expression(&self, scalar: &Scalar) -> Result<Expression> {
+        let mut expr = ExpressionBuilder::create(self.metadata.clone()).build(scalar)?;
+        for column in RequireColumnsVisitor::collect_columns_from_expr(&expr)? {
+            let (name, _) = decode_field_name(&column)?;
+            expr = RewriteHelper::rewrite_column_expr(&expr, &column, &name)?;
+        }
+        Ok(expr)
+    }
+
+    fn required_columns(schema: &DataSchemaRef, expr: &Expression) -> Result<Vec<usize>> {
+        let mut projection = RequireColumnsVisitor::collect_columns_from_expr(expr)?
+            .iter()
+            .map(|name| schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        projection.sort_unstable();
+        Ok(projection)
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+
+use common_exception::Result;
+
+use crate::sql::binder::Binder;
+use crate::sql::binder::ColumnBinding;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::metadata::MetadataRef;
+use crate::sql::planner::semantic::GroupingChecker;
+use crate::sql::plans::AndExpr;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::EvalScalar;
+use crate::sql::plans::FunctionCall;
+use crate::sql::plans::OrExpr;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::ScalarItem;
+use crate::sql::plans::Window;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
+use crate::sql::BindContext;
+use crate::sql::IndexType;
+
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct WindowInfo {
+    /// Window functions, the arguments, partition keys and order keys of which
+    /// are not resolved until the window is bound.
+    pub window_functions: Vec<ScalarItem>,
+
+    /// Arguments, partition keys and order keys of window functions
+    /// which are not plain columns and have to be evaluated before window.
+    pub window_arguments: Vec<ScalarItem>,
+
+    /// Mapping: (window function display name) -> (index of window func in `window_functions`)
+    /// This is used to find a window function in current context.
+    pub window_functions_map: HashMap<String, usize>,
+}
+
+impl WindowInfo {
+    /// Returns true if the column is the output column of a window function.
+    pub fn is_window_column(&self, index: IndexType) -> bool {
+        self.window_functions.iter().any(|item| item.index == index)
+    }
+}
+
+/// Replace window functions in a scalar expression with BoundColumnRefs to the
+/// output columns of the window functions, and record the window functions into `WindowInfo`.
+struct WindowRewriter<'a> {
+    pub bind_context: &'a mut BindContext,
+    pub metadata: MetadataRef,
+}
+
+impl<'a> WindowRewriter<'a> {
+    pub fn new(bind_context: &'a mut BindContext, metadata: MetadataRef) -> Self {
+        Self {
+            bind_context,
+            metadata,
+        }
+    }
+
+    pub fn visit(&mut self, scalar: &Scalar) -> Result<Scalar> {
+        match scalar {
+            Scalar::BoundColumnRef(_)
+            | Scalar::ConstantExpr(_)
+            | Scalar::AggregateFunction(_)
+            | Scalar::SubqueryExpr(_)
+            | Scalar::Placeholder(_) => Ok(scalar.clone()),
+            Scalar::AndExpr(scalar) => Ok(AndExpr {
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::OrExpr(scalar) => Ok(OrExpr {
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::ComparisonExpr(scalar) => Ok(ComparisonExpr {
+                op: scalar.op.clone(),
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::FunctionCall(func) => {
+                let new_args = func
+                    .arguments
+                    .iter()
+                    .map(|arg| self.visit(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(FunctionCall {
+                    arguments: new_args,
+                    func_name: func.func_name.clone(),
+                    arg_types: func.arg_types.clone(),
+                    return_type: func.return_type.clone(),
+                }
+                .into())
+            }
+            Scalar::CastExpr(cast) => Ok(CastExpr {
+                argument: Box::new(self.visit(&cast.argument)?),
+                from_type: cast.from_type.clone(),
+                target_type: cast.target_type.clone(),
+            }
+            .into()),
+
+            Scalar::WindowFunction(window_func) => self.replace_window_function(window_func),
+        }
+    }
+
+    fn replace_window_function(&mut self, window_func: &WindowFunction) -> Result<Scalar> {
+        let window_info = &mut self.bind_context.windows;
+        let index = match window_info
+            .window_functions_map
+            .get(&window_func.display_name)
+        {
+            // The same window function appears more than once, reuse its output column
+            Some(position) => window_info.window_functions[*position].index,
+            None => {
+                let index = self.metadata.write().add_column(
+                    window_func.display_name.clone(),
+                    window_func.return_type.clone(),
+                    None,
+                );
+                window_info.window_functions.push(ScalarItem {
+                    scalar: window_func.clone().into(),
+                    index,
+                });
+                window_info.window_functions_map.insert(
+                    window_func.display_name.clone(),
+                    window_info.window_functions.len() - 1,
+                );
+                index
+            }
+        };
+
+        Ok(BoundColumnRef {
+            column: ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: window_func.display_name.clone(),
+                index,
+                data_type: window_func.return_type.clone(),
+                visible_in_unqualified_wildcard: true,
+            },
+        }
+        .into())
+    }
+}
+
+impl Binder {
+    /// Analyze window functions in select clause, this will replace the window functions
+    /// with their output columns. See `WindowRewriter` for more details.
+    pub(super) fn analyze_window_select(
+        &mut self,
+        bind_context: &mut BindContext,
+        scalar_items: &mut HashMap<IndexType, ScalarItem>,
+    ) -> Result<()> {
+        // Visit the items by index to make the order of window functions stable
+        let mut indices: Vec<IndexType> = scalar_items.keys().cloned().collect();
+        indices.sort_unstable();
+        for index in indices {
+            let item = scalar_items.get_mut(&index).unwrap();
+            let mut rewriter = WindowRewriter::new(bind_context, self.metadata.clone());
+            item.scalar = rewriter.visit(&item.scalar)?;
+        }
+
+        Ok(())
+    }
+
+    /// Build an EvalScalar to evaluate the window arguments, followed by a `Window`
+    /// for each window function.
+    pub(super) fn bind_window(
+        &mut self,
+        bind_context: &mut BindContext,
+        child: SExpr,
+    ) -> Result<SExpr> {
+        let context: &BindContext = bind_context;
+        let mut windows = Vec::with_capacity(context.windows.window_functions.len());
+        let mut window_arguments = vec![];
+        for item in context.windows.window_functions.iter() {
+            let window_func: WindowFunction = item.scalar.clone().try_into()?;
+            let mut replace_argument = |scalar: &Scalar, name: String| -> Result<Scalar> {
+                let scalar = if context.in_grouping {
+                    let mut grouping_checker = GroupingChecker::new(context);
+                    grouping_checker.resolve(scalar, None)?
+                } else {
+                    scalar.clone()
+                };
+                if let Scalar::BoundColumnRef(_) = scalar {
+                    return Ok(scalar);
+                }
+
+                let column_binding =
+                    self.create_column_binding(None, None, name, scalar.data_type());
+                window_arguments.push(ScalarItem {
+                    scalar,
+                    index: column_binding.index,
+                });
+                Ok(BoundColumnRef {
+                    column: column_binding,
+                }
+                .into())
+            };
+
+            let func_name = &window_func.func_name;
+            let args = window_func
+                .args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| replace_argument(arg, format!("{func_name}_arg_{i}")))
+                .collect::<Result<Vec<_>>>()?;
+            let partition_by = window_func
+                .partition_by
+                .iter()
+                .enumerate()
+                .map(|(i, expr)| replace_argument(expr, format!("{func_name}_partition_by_{i}")))
+                .collect::<Result<Vec<_>>>()?;
+            let order_by = window_func
+                .order_by
+                .iter()
+                .enumerate()
+                .map(|(i, order_by)| {
+                    Ok(WindowOrderBy {
+                        expr: replace_argument(
+                            &order_by.expr,
+                            format!("{func_name}_order_by_{i}"),
+                        )?,
+                        asc: order_by.asc,
+                        nulls_first: order_by.nulls_first,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            windows.push(Window {
+                index: item.index,
+                function: WindowFunction {
+                    args,
+                    partition_by,
+                    order_by,
+                    ..window_func
+                },
+            });
+        }
+
+        let mut new_expr = child;
+        if !window_arguments.is_empty() {
+            let eval_scalar = EvalScalar {
+                items: window_arguments.clone(),
+            };
+            new_expr = SExpr::create_unary(eval_scalar.into(), new_expr);
+        }
+        bind_context.windows.window_arguments = window_arguments;
+
+        for window in windows {
+            new_expr = SExpr::create_unary(window.into(), new_expr);
+        }
+
+        Ok(new_expr)
+    }
+}
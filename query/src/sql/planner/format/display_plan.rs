@@ -55,6 +55,12 @@ impl Plan {
             Plan::DropTableClusterKey(drop_table_cluster_key) => {
                 Ok(format!("{:?}", drop_table_cluster_key))
             }
+            Plan::AddTableColumn(add_table_column) => Ok(format!("{:?}", add_table_column)),
+            Plan::DropTableColumn(drop_table_column) => Ok(format!("{:?}", drop_table_column)),
+            Plan::RenameTableColumn(rename_table_column) => {
+                Ok(format!("{:?}", rename_table_column))
+            }
+            Plan::ReclusterTable(recluster_table) => Ok(format!("{:?}", recluster_table)),
             Plan::TruncateTable(truncate_table) => Ok(format!("{:?}", truncate_table)),
             Plan::OptimizeTable(optimize_table) => Ok(format!("{:?}", optimize_table)),
             Plan::ExistsTable(exists_table) => Ok(format!("{:?}", exists_table)),
@@ -67,6 +73,8 @@ impl Plan {
             // Insert
             Plan::Insert(insert) => Ok(format!("{:?}", insert)),
             Plan::Delete(delete) => Ok(format!("{:?}", delete)),
+            Plan::Update(update) => Ok(format!("{:?}", update)),
+            Plan::MergeInto(merge_into) => Ok(format!("{:?}", merge_into)),
 
             // Stages
             Plan::ShowStages => Ok("SHOW STAGES".to_string()),
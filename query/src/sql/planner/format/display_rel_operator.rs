@@ -21,22 +21,27 @@ use itertools::Itertools;
 use super::FormatTreeNode;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Aggregate;
+use crate::sql::plans::AggregateMode;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::ComparisonOp;
 use crate::sql::plans::CrossApply;
 use crate::sql::plans::EvalScalar;
+use crate::sql::plans::Exchange;
 use crate::sql::plans::Filter;
 use crate::sql::plans::JoinType;
 use crate::sql::plans::Limit;
 use crate::sql::plans::LogicalGet;
 use crate::sql::plans::LogicalInnerJoin;
 use crate::sql::plans::PhysicalHashJoin;
+use crate::sql::plans::PhysicalMergeJoin;
+use crate::sql::plans::PhysicalNestedLoopJoin;
 use crate::sql::plans::PhysicalScan;
 use crate::sql::plans::Project;
 use crate::sql::plans::RelOperator;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::Sort;
+use crate::sql::plans::Window;
 use crate::sql::MetadataRef;
 use crate::sql::ScalarExpr;
 
@@ -70,6 +75,11 @@ impl Display for FormatContext {
             RelOperator::LogicalInnerJoin(op) => format_logical_inner_join(f, &self.metadata, op),
             RelOperator::PhysicalScan(op) => format_physical_scan(f, &self.metadata, op),
             RelOperator::PhysicalHashJoin(op) => format_hash_join(f, &self.metadata, op),
+            RelOperator::PhysicalMergeJoin(op) => format_merge_join(f, &self.metadata, op),
+            RelOperator::PhysicalNestedLoopJoin(op) => {
+                format_nested_loop_join(f, &self.metadata, op)
+            }
+            RelOperator::Exchange(op) => format_exchange(f, &self.metadata, op),
             RelOperator::Project(op) => format_project(f, &self.metadata, op),
             RelOperator::EvalScalar(op) => format_eval_scalar(f, &self.metadata, op),
             RelOperator::Filter(op) => format_filter(f, &self.metadata, op),
@@ -78,6 +88,7 @@ impl Display for FormatContext {
             RelOperator::Limit(op) => format_limit(f, &self.metadata, op),
             RelOperator::CrossApply(op) => format_cross_apply(f, &self.metadata, op),
             RelOperator::Max1Row(_) => write!(f, "Max1Row"),
+            RelOperator::Window(op) => format_window(f, &self.metadata, op),
             RelOperator::Pattern(_) => write!(f, "Pattern"),
         }
     }
@@ -110,6 +121,7 @@ pub fn format_scalar(metadata: &MetadataRef, scalar: &Scalar) -> String {
             format_scalar(metadata, &comp.right)
         ),
         Scalar::AggregateFunction(agg) => agg.display_name.clone(),
+        Scalar::WindowFunction(window) => window.display_name.clone(),
         Scalar::FunctionCall(func) => {
             format!(
                 "{}({})",
@@ -129,6 +141,7 @@ pub fn format_scalar(metadata: &MetadataRef, scalar: &Scalar) -> String {
             )
         }
         Scalar::SubqueryExpr(_) => "SUBQUERY".to_string(),
+        Scalar::Placeholder(placeholder) => format!("${}", placeholder.index),
     }
 }
 
@@ -217,6 +230,73 @@ pub fn format_hash_join(
     }
 }
 
+pub fn format_merge_join(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &PhysicalMergeJoin,
+) -> std::fmt::Result {
+    let left_keys = op
+        .left_keys
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let right_keys = op
+        .right_keys
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let join_filters = op
+        .other_conditions
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    write!(
+        f,
+        "MergeJoin: {}, left keys: [{}], right keys: [{}], join filters: [{}]",
+        &op.join_type, left_keys, right_keys, join_filters,
+    )
+}
+
+pub fn format_nested_loop_join(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &PhysicalNestedLoopJoin,
+) -> std::fmt::Result {
+    let join_filters = op
+        .conditions
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    write!(
+        f,
+        "NestedLoopJoin: {}, join filters: [{}]",
+        &op.join_type, join_filters,
+    )
+}
+
+pub fn format_exchange(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &Exchange,
+) -> std::fmt::Result {
+    match op {
+        Exchange::Hash(keys) => {
+            let keys = keys
+                .iter()
+                .map(|scalar| format_scalar(metadata, scalar))
+                .collect::<Vec<String>>()
+                .join(", ");
+            write!(f, "Exchange(Hash): keys: [{}]", keys)
+        }
+        Exchange::Broadcast => write!(f, "Exchange(Broadcast)"),
+        Exchange::Merge => write!(f, "Exchange(Merge)"),
+    }
+}
+
 pub fn format_physical_scan(
     f: &mut std::fmt::Formatter<'_>,
     metadata: &MetadataRef,
@@ -297,11 +377,33 @@ pub fn format_aggregate(
         .map(|item| format_scalar(metadata, &item.scalar))
         .collect::<Vec<String>>()
         .join(", ");
+    let mode = match op.mode {
+        AggregateMode::Partial => "(Partial)",
+        AggregateMode::Final => "(Final)",
+        AggregateMode::Initial => "",
+    };
     write!(
         f,
-        "Aggregate: group items: [{}], aggregate functions: [{}]",
-        group_items, agg_funcs
-    )
+        "Aggregate{}: group items: [{}], aggregate functions: [{}]",
+        mode, group_items, agg_funcs
+    )?;
+    if let Some(grouping_sets) = &op.grouping_sets {
+        let sets = grouping_sets
+            .sets
+            .iter()
+            .map(|set| {
+                let items = set
+                    .iter()
+                    .map(|index| metadata.read().column(*index).name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                format!("({})", items)
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(f, ", grouping sets: [{}]", sets)?;
+    }
+    Ok(())
 }
 
 pub fn format_sort(
@@ -345,3 +447,35 @@ pub fn format_cross_apply(
 ) -> std::fmt::Result {
     write!(f, "CrossApply")
 }
+
+pub fn format_window(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &Window,
+) -> std::fmt::Result {
+    let partition_by = op
+        .function
+        .partition_by
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let order_by = op
+        .function
+        .order_by
+        .iter()
+        .map(|item| {
+            format!(
+                "{} {}",
+                format_scalar(metadata, &item.expr),
+                if item.asc { "ASC" } else { "DESC" }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    write!(
+        f,
+        "Window: {}, partition by: [{}], order by: [{}], frame: [{}]",
+        op.function.display_name, partition_by, order_by, op.function.frame
+    )
+}
@@ -115,6 +115,10 @@ impl Metadata {
         self.tables.get(index).unwrap()
     }
 
+    pub fn tables(&self) -> &[TableEntry] {
+        self.tables.as_slice()
+    }
+
     pub fn column(&self, index: IndexType) -> &ColumnEntry {
         self.columns.get(index).unwrap()
     }
@@ -15,15 +15,28 @@
 use std::sync::Arc;
 
 use common_ast::parser::parse_sql;
+use common_ast::parser::placeholder_count;
+use common_ast::parser::token::Token;
+use common_ast::parser::token::TokenKind;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_base::infallible::RwLock;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_datavalues::NullType;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
 pub use plans::ScalarExpr;
 
+use crate::sessions::PreparedStatement;
 use crate::sessions::QueryContext;
+use crate::sql::binder::rewrite_placeholders;
+use crate::sql::binder::wrap_cast_if_needed;
 use crate::sql::optimizer::optimize;
 pub use crate::sql::planner::binder::BindContext;
+use crate::sql::plans::ConstantExpr;
+use crate::storages::fuse::FuseTable;
 
 pub(crate) mod binder;
 mod format;
@@ -56,6 +69,13 @@ impl Planner {
         let backtrace = Backtrace::new();
         let (stmt, format) = parse_sql(&tokens, &backtrace)?;
 
+        let num_params = placeholder_count(&tokens)?;
+        if num_params > 0 && self.ctx.get_query_parameter(num_params).is_none() {
+            return Err(ErrorCode::SemanticError(format!(
+                "No value is bound to placeholder ${num_params}"
+            )));
+        }
+
         // Step 2: bind AST with catalog, and generate a pure logical SExpr
         let metadata = Arc::new(RwLock::new(Metadata::create()));
         let binder = Binder::new(self.ctx.clone(), self.ctx.get_catalogs(), metadata.clone());
@@ -66,4 +86,137 @@ impl Planner {
 
         Ok((optimized_plan, metadata.clone(), format))
     }
+
+    /// Bind a statement with placeholders to validate it and describe its parameters and
+    /// output, so that it can be cached in the session and executed later with bound parameters.
+    pub async fn prepare_sql(&mut self, sql: &str) -> Result<PreparedStatement> {
+        let tokens = tokenize_sql(sql)?;
+        let backtrace = Backtrace::new();
+        let (stmt, _) = parse_sql(&tokens, &backtrace)?;
+        let num_params = placeholder_count(&tokens)?;
+
+        // Leave the placeholders unbound, their types are inferred from where they are used.
+        self.ctx.attach_query_parameters(vec![]);
+        let metadata = Arc::new(RwLock::new(Metadata::create()));
+        let binder = Binder::new(self.ctx.clone(), self.ctx.get_catalogs(), metadata);
+        let plan = binder.bind(&stmt).await?;
+
+        let mut param_types = vec![NullType::new_impl(); num_params];
+        if let Plan::Query { s_expr, .. } = &plan {
+            rewrite_placeholders(s_expr, &mut |placeholder| {
+                if let Some(param_type) = param_types.get_mut(placeholder.index - 1) {
+                    if param_type.is_null() {
+                        *param_type = placeholder.data_type.clone();
+                    }
+                }
+                Ok(placeholder.clone().into())
+            })?;
+        }
+
+        // The bound plan is only reused by queries whose result doesn't depend on when they
+        // are bound, and whose parameters are all typed.
+        let reusable = matches!(plan, Plan::Query { .. })
+            && param_types.iter().all(|param_type| !param_type.is_null())
+            && !has_non_deterministic_function(&tokens);
+
+        Ok(PreparedStatement {
+            query: sql.to_string(),
+            num_params,
+            schema: plan.schema(),
+            param_types,
+            database: self.ctx.get_current_database(),
+            plan: if reusable { Some(plan) } else { None },
+        })
+    }
+
+    /// Plan a prepared statement with the bound parameters.
+    ///
+    /// The plan bound at preparation is reused if the current database and all the tables it
+    /// reads are unchanged, the placeholders are substituted with the parameters cast to their
+    /// types. Otherwise the statement is planned from scratch.
+    pub async fn plan_prepared(
+        &mut self,
+        statement: &PreparedStatement,
+        params: Vec<DataValue>,
+    ) -> Result<(Plan, MetadataRef, Option<String>)> {
+        if params.len() != statement.num_params {
+            return Err(ErrorCode::BadArguments(format!(
+                "Prepared statement expects {} parameters, but got {}",
+                statement.num_params,
+                params.len()
+            )));
+        }
+
+        if let Some(Plan::Query {
+            s_expr,
+            metadata,
+            bind_context,
+        }) = &statement.plan
+        {
+            if self.is_plan_reusable(statement, metadata).await? {
+                // Planning mutates the metadata, which must not be shared with other executions.
+                let metadata = Arc::new(RwLock::new(metadata.read().clone()));
+                let s_expr = rewrite_placeholders(s_expr, &mut |placeholder| {
+                    let value = params[placeholder.index - 1].clone();
+                    let scalar = ConstantExpr {
+                        data_type: value.data_type(),
+                        value,
+                    };
+                    Ok(wrap_cast_if_needed(scalar.into(), &placeholder.data_type))
+                })?;
+                let plan = Plan::Query {
+                    s_expr,
+                    metadata: metadata.clone(),
+                    bind_context: bind_context.clone(),
+                };
+                return Ok((optimize(self.ctx.clone(), plan)?, metadata, None));
+            }
+        }
+
+        self.ctx.attach_query_parameters(params);
+        self.plan_sql(&statement.query).await
+    }
+
+    async fn is_plan_reusable(
+        &self,
+        statement: &PreparedStatement,
+        metadata: &MetadataRef,
+    ) -> Result<bool> {
+        if statement.database != self.ctx.get_current_database() {
+            return Ok(false);
+        }
+
+        let tables = metadata.read().tables().to_vec();
+        for entry in tables {
+            if entry.table.table_args().is_some() {
+                continue;
+            }
+            // Only fuse tables tell whether they are changed, by the version of table meta.
+            if FuseTable::try_from_table(entry.table.as_ref()).is_err() {
+                return Ok(false);
+            }
+            let latest = self
+                .ctx
+                .get_catalog(&entry.catalog)?
+                .get_table(&self.ctx.get_tenant(), &entry.database, &entry.name)
+                .await?;
+            if latest.get_table_info().ident != entry.table.get_table_info().ident {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Whether the statement calls a function whose result depends on when it's evaluated, e.g.
+/// `now()`, which may be folded into a constant at binding.
+pub fn has_non_deterministic_function(tokens: &[Token]) -> bool {
+    tokens.windows(2).any(|tokens| {
+        tokens[0].kind == TokenKind::Ident
+            && tokens[1].kind == TokenKind::LParen
+            && FunctionFactory::instance()
+                .get_features(tokens[0].text())
+                .map(|features| !features.is_deterministic)
+                .unwrap_or(false)
+    })
 }
@@ -14,18 +14,25 @@
 
 use common_exception::Result;
 
+use crate::sql::binder::ColumnBinding;
 use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::ColumnStatSet;
+use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
-use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::LogicalPlan;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalPlan;
 use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
 use crate::sql::plans::ScalarItem;
+use crate::sql::IndexType;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Aggregate {
     // group by scalar expressions, such as: group by col1 asc, col2 desc;
     pub group_items: Vec<ScalarItem>,
@@ -33,6 +40,28 @@ pub struct Aggregate {
     pub aggregate_functions: Vec<ScalarItem>,
     // True if the plan is generated from distinct, else the plan is a normal aggregate;
     pub from_distinct: bool,
+    // Grouping sets of `GROUPING SETS`, `ROLLUP` and `CUBE`, None for a plain `GROUP BY`;
+    pub grouping_sets: Option<GroupingSets>,
+    // Phase of the aggregation, only split into partial and final phases for distributed query;
+    pub mode: AggregateMode,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AggregateMode {
+    /// Aggregate the input into partial states, evaluated on the node where the input is.
+    Partial,
+    /// Merge the partial states into the final results.
+    Final,
+    /// The aggregation as a whole, before it's split into phases.
+    Initial,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupingSets {
+    /// Index of the virtual column which identifies the grouping set of each row.
+    pub grouping_id_index: IndexType,
+    /// Each grouping set is represented by the indices of its group items.
+    pub sets: Vec<Vec<IndexType>>,
 }
 
 impl Operator for Aggregate {
@@ -58,8 +87,47 @@ impl Operator for Aggregate {
 }
 
 impl PhysicalPlan for Aggregate {
-    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
-        todo!()
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        let mut prop = rel_expr.derive_physical_prop_child(0)?;
+        prop.ordering.clear();
+        Ok(prop)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        if self.mode == AggregateMode::Partial {
+            // Partial aggregation is done locally, so that only the partial states are
+            // transferred between nodes
+            return Ok(required.clone().with_distribution(Distribution::Any));
+        }
+
+        if self.group_items.is_empty() {
+            return Ok(required.clone().with_distribution(Distribution::Serial));
+        }
+
+        // Rows of the same group should be placed on the same node
+        let keys = self
+            .group_items
+            .iter()
+            .map(|item| {
+                BoundColumnRef {
+                    column: ColumnBinding {
+                        database_name: None,
+                        table_name: None,
+                        column_name: "group_item".to_string(),
+                        index: item.index,
+                        data_type: item.scalar.data_type(),
+                        visible_in_unqualified_wildcard: false,
+                    },
+                }
+                .into()
+            })
+            .collect();
+        Ok(required.clone().with_distribution(Distribution::Hash(keys)))
     }
 }
 
@@ -75,6 +143,9 @@ impl LogicalPlan for Aggregate {
         for agg in self.aggregate_functions.iter() {
             output_columns.insert(agg.index);
         }
+        if let Some(grouping_sets) = &self.grouping_sets {
+            output_columns.insert(grouping_sets.grouping_id_index);
+        }
 
         // Derive outer columns
         let outer_columns = input_prop
@@ -83,9 +154,44 @@ impl LogicalPlan for Aggregate {
             .cloned()
             .collect();
 
+        // Derive cardinality, which is bounded by the number of distinct group keys
+        let cardinality = if self.group_items.is_empty() {
+            1.0
+        } else {
+            let mut distinct_keys = 1.0;
+            for group_item in self.group_items.iter() {
+                distinct_keys *= match &group_item.scalar {
+                    Scalar::BoundColumnRef(column) => input_prop
+                        .column_stats
+                        .get(&column.column.index)
+                        .map(|stat| stat.ndv)
+                        .unwrap_or(input_prop.cardinality),
+                    _ => input_prop.cardinality,
+                };
+            }
+            // Every grouping set produces its own groups
+            let num_sets = self
+                .grouping_sets
+                .as_ref()
+                .map_or(1, |grouping_sets| grouping_sets.sets.len());
+            distinct_keys.min(input_prop.cardinality) * num_sets as f64
+        };
+
+        // Derive column statistics of group keys
+        let mut column_stats = ColumnStatSet::new();
+        for group_item in self.group_items.iter() {
+            if let Scalar::BoundColumnRef(column) = &group_item.scalar {
+                if let Some(stat) = input_prop.column_stats.get(&column.column.index) {
+                    column_stats.insert(group_item.index, stat.clone());
+                }
+            }
+        }
+
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
+            cardinality,
+            column_stats,
         })
     }
 }
@@ -15,17 +15,18 @@
 use common_exception::Result;
 
 use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
-use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::RequiredProperty;
 use crate::sql::plans::LogicalPlan;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalPlan;
 use crate::sql::plans::RelOp;
 
 /// Cartesian apply join operator.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CrossApply {
     pub correlated_columns: ColumnSet,
 }
@@ -53,8 +54,19 @@ impl Operator for CrossApply {
 }
 
 impl PhysicalPlan for CrossApply {
-    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
-        todo!()
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // Subquery is evaluated locally for each row of input, so the input should be
+        // gathered to the local node.
+        Ok(required.clone().with_distribution(Distribution::Serial))
     }
 }
 
@@ -77,9 +89,16 @@ impl LogicalPlan for CrossApply {
             .cloned()
             .collect();
 
+        // Derive cardinality, every input row is applied with the subquery
+        let cardinality = input_prop.cardinality * subquery_prop.cardinality.max(1.0);
+        let mut column_stats = input_prop.column_stats;
+        column_stats.extend(subquery_prop.column_stats);
+
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
+            cardinality,
+            column_stats,
         })
     }
 }
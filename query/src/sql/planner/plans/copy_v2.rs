@@ -66,6 +66,8 @@ pub enum CopyPlanV2 {
         pattern: String,
         schema: DataSchemaRef,
         validation_mode: ValidationMode,
+        /// Load the files even if they have been loaded before.
+        force: bool,
         from: Box<ReadDataSourcePlan>,
     },
     IntoStage {
@@ -87,9 +89,13 @@ impl Debug for CopyPlanV2 {
                 pattern,
                 from,
                 validation_mode,
+                force,
                 ..
             } => {
                 write!(f, "Copy into {:}.{:}", database_name, table_name)?;
+                if *force {
+                    write!(f, ", force: {}", force)?;
+                }
                 if !files.is_empty() {
                     write!(f, ", files: {:?}", files)?;
 
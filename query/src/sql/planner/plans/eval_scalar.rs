@@ -15,10 +15,11 @@
 use common_exception::Result;
 
 use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
-use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::RequiredProperty;
 use crate::sql::plans::LogicalPlan;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalPlan;
@@ -28,7 +29,7 @@ use crate::sql::plans::ScalarExpr;
 use crate::sql::IndexType;
 
 /// Evaluate scalar expression
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EvalScalar {
     pub items: Vec<ScalarItem>,
 }
@@ -62,8 +63,17 @@ impl Operator for EvalScalar {
 }
 
 impl PhysicalPlan for EvalScalar {
-    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
-        todo!()
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone().with_distribution(Distribution::Any))
     }
 }
 
@@ -92,6 +102,8 @@ impl LogicalPlan for EvalScalar {
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
+            cardinality: input_prop.cardinality,
+            column_stats: input_prop.column_stats,
         })
     }
 }
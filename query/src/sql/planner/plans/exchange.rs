@@ -0,0 +1,81 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalPlan;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalPlan;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+
+/// Redistribute data among the nodes of cluster, it's inserted by optimizer
+/// to enforce the distribution required by the parent operator.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Exchange {
+    /// Shuffle rows by hash of the keys
+    Hash(Vec<Scalar>),
+    /// Send a full copy of data to every node
+    Broadcast,
+    /// Gather all the data to the local node
+    Merge,
+}
+
+impl Operator for Exchange {
+    fn rel_op(&self) -> RelOp {
+        RelOp::Exchange
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        false
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalPlan> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalPlan> {
+        None
+    }
+}
+
+impl PhysicalPlan for Exchange {
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: match self {
+                Exchange::Hash(keys) => Distribution::Hash(keys.clone()),
+                Exchange::Broadcast => Distribution::Broadcast,
+                Exchange::Merge => Distribution::Serial,
+            },
+            ordering: vec![],
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone().with_distribution(Distribution::Any))
+    }
+}
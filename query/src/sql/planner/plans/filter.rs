@@ -14,11 +14,14 @@
 
 use common_exception::Result;
 
+use crate::sql::optimizer::estimate_selectivity;
 use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::ColumnStat;
+use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
-use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::RequiredProperty;
 use crate::sql::plans::LogicalPlan;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalPlan;
@@ -26,7 +29,7 @@ use crate::sql::plans::RelOp;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarExpr;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Filter {
     pub predicates: Vec<Scalar>,
     // True if the plan represents having, else the plan represents where
@@ -56,8 +59,17 @@ impl Operator for Filter {
 }
 
 impl PhysicalPlan for Filter {
-    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
-        todo!()
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone().with_distribution(Distribution::Any))
     }
 }
 
@@ -78,9 +90,27 @@ impl LogicalPlan for Filter {
         }
         outer_columns = outer_columns.difference(&output_columns).cloned().collect();
 
+        // Derive cardinality
+        let selectivity = self
+            .predicates
+            .iter()
+            .map(|predicate| estimate_selectivity(predicate, &input_prop.column_stats))
+            .product::<f64>();
+        let cardinality = input_prop.cardinality * selectivity;
+        let column_stats = input_prop
+            .column_stats
+            .into_iter()
+            .map(|(index, stat)| {
+                let ndv = stat.ndv.min(cardinality);
+                (index, ColumnStat { ndv, ..stat })
+            })
+            .collect();
+
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
+            cardinality,
+            column_stats,
         })
     }
 }
@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_exception::Result;
+
 use super::JoinType;
+use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
-use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RequiredProperty;
 use crate::sql::plans::LogicalPlan;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalPlan;
@@ -22,7 +26,7 @@ use crate::sql::plans::RelOp;
 use crate::sql::plans::Scalar;
 use crate::sql::IndexType;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PhysicalHashJoin {
     pub build_keys: Vec<Scalar>,
     pub probe_keys: Vec<Scalar>,
@@ -54,7 +58,41 @@ impl Operator for PhysicalHashJoin {
 }
 
 impl PhysicalPlan for PhysicalHashJoin {
-    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
-        todo!()
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        // Joined rows are placed on the node where the probe side rows are,
+        // but the order of probe side is not preserved.
+        let mut prop = rel_expr.derive_physical_prop_child(0)?;
+        prop.ordering.clear();
+        Ok(prop)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        let distribution = if !self.build_keys.is_empty() {
+            // Shuffle both sides by join keys, so the rows can be matched are on the same node
+            if child_index == 0 {
+                Distribution::Hash(self.probe_keys.clone())
+            } else {
+                Distribution::Hash(self.build_keys.clone())
+            }
+        } else if matches!(
+            self.join_type,
+            JoinType::Inner | JoinType::Left | JoinType::Semi | JoinType::Anti | JoinType::Cross
+        ) {
+            // Broadcast build side to every node if the unmatched rows of build side
+            // are never emitted, otherwise they would be duplicated.
+            if child_index == 0 {
+                Distribution::Any
+            } else {
+                Distribution::Broadcast
+            }
+        } else {
+            Distribution::Serial
+        };
+        Ok(required.clone().with_distribution(distribution))
     }
 }
@@ -14,16 +14,17 @@
 
 use common_exception::Result;
 
+use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
-use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::RequiredProperty;
 use crate::sql::plans::LogicalPlan;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalPlan;
 use crate::sql::plans::RelOp;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Limit {
     pub limit: Option<usize>,
     pub offset: usize,
@@ -52,13 +53,27 @@ impl Operator for Limit {
 }
 
 impl PhysicalPlan for Limit {
-    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
-        todo!()
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone().with_distribution(Distribution::Serial))
     }
 }
 
 impl LogicalPlan for Limit {
     fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
-        rel_expr.derive_relational_prop_child(0)
+        let mut prop = rel_expr.derive_relational_prop_child(0)?;
+        prop.cardinality = (prop.cardinality - self.offset as f64).max(0.0);
+        if let Some(limit) = self.limit {
+            prop.cardinality = prop.cardinality.min(limit as f64);
+        }
+        Ok(prop)
     }
 }
@@ -17,16 +17,19 @@ use common_exception::Result;
 use crate::sql::optimizer::ColumnSet;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::Statistics;
+use crate::sql::optimizer::DEFAULT_CARDINALITY;
 use crate::sql::plans::LogicalPlan;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalPlan;
 use crate::sql::plans::RelOp;
 use crate::sql::IndexType;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LogicalGet {
     pub table_index: IndexType,
     pub columns: ColumnSet,
+    pub statistics: Statistics,
 }
 
 impl Operator for LogicalGet {
@@ -56,6 +59,12 @@ impl LogicalPlan for LogicalGet {
         Ok(RelationalProperty {
             output_columns: self.columns.clone(),
             outer_columns: Default::default(),
+            cardinality: self
+                .statistics
+                .num_rows
+                .map(|num_rows| num_rows as f64)
+                .unwrap_or(DEFAULT_CARDINALITY),
+            column_stats: self.statistics.column_stats.clone(),
         })
     }
 }
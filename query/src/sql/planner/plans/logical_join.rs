@@ -18,6 +18,7 @@ use std::fmt::Formatter;
 use common_exception::Result;
 
 use super::ScalarExpr;
+use crate::sql::optimizer::estimate_join_cardinality;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
 use crate::sql::plans::LogicalPlan;
@@ -70,7 +71,7 @@ impl Display for JoinType {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LogicalInnerJoin {
     pub left_conditions: Vec<Scalar>,
     pub right_conditions: Vec<Scalar>,
@@ -131,9 +132,34 @@ impl LogicalPlan for LogicalInnerJoin {
         }
         outer_columns = outer_columns.difference(&output_columns).cloned().collect();
 
+        // Derive cardinality
+        let mut column_stats = left_prop.column_stats;
+        column_stats.extend(right_prop.column_stats);
+        let inner_cardinality = estimate_join_cardinality(
+            left_prop.cardinality,
+            right_prop.cardinality,
+            &self.left_conditions,
+            &self.right_conditions,
+            &column_stats,
+        );
+        let cardinality = match self.join_type {
+            JoinType::Inner | JoinType::Cross => inner_cardinality,
+            JoinType::Left => inner_cardinality.max(left_prop.cardinality),
+            JoinType::Right => inner_cardinality.max(right_prop.cardinality),
+            JoinType::Full => inner_cardinality
+                .max(left_prop.cardinality)
+                .max(right_prop.cardinality),
+            JoinType::Semi | JoinType::Anti | JoinType::Mark => left_prop.cardinality,
+        };
+        for stat in column_stats.values_mut() {
+            stat.ndv = stat.ndv.min(cardinality);
+        }
+
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
+            cardinality,
+            column_stats,
         })
     }
 }
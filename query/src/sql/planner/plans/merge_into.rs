@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataSchemaRef;
+use common_planners::MergeIntoPlan;
+
+use super::Plan;
+
+#[derive(Clone)]
+pub struct MergeInto {
+    /// The plan of the source, which is a `Plan::Query`.
+    pub source: Box<Plan>,
+    pub plan: MergeIntoPlan,
+}
+
+impl MergeInto {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+}
+
+impl std::fmt::Debug for MergeInto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MergeInto")
+            .field("plan", &self.plan)
+            .finish()
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use super::JoinType;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalPlan;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalPlan;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+
+/// Sort-merge join, both children must be sorted in ascending order by the join keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhysicalMergeJoin {
+    pub left_keys: Vec<Scalar>,
+    pub right_keys: Vec<Scalar>,
+    pub other_conditions: Vec<Scalar>,
+    pub join_type: JoinType,
+}
+
+impl Operator for PhysicalMergeJoin {
+    fn rel_op(&self) -> RelOp {
+        RelOp::PhysicalMergeJoin
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        false
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalPlan> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalPlan> {
+        None
+    }
+}
+
+impl PhysicalPlan for PhysicalMergeJoin {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        // Joined rows are emitted in the order of left side
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // Sorted streams can only be merged on a single node
+        Ok(required.clone().with_distribution(Distribution::Serial))
+    }
+}
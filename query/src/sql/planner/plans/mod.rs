@@ -16,6 +16,7 @@ mod aggregate;
 mod apply;
 mod copy_v2;
 mod eval_scalar;
+mod exchange;
 mod filter;
 mod hash_join;
 mod insert;
@@ -23,12 +24,16 @@ mod limit;
 mod logical_get;
 mod logical_join;
 mod max_one_row;
+mod merge_into;
+mod merge_join;
+mod nested_loop_join;
 mod operator;
 mod pattern;
 mod physical_scan;
 mod project;
 mod scalar;
 mod sort;
+mod window;
 
 use std::fmt::Display;
 use std::sync::Arc;
@@ -42,6 +47,7 @@ use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::ToDataType;
 use common_datavalues::Vu8;
+use common_planners::AddTableColumnPlan;
 use common_planners::AlterTableClusterKeyPlan;
 use common_planners::AlterUserPlan;
 use common_planners::AlterUserUDFPlan;
@@ -60,6 +66,7 @@ use common_planners::DescribeUserStagePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropRolePlan;
 use common_planners::DropTableClusterKeyPlan;
+use common_planners::DropTableColumnPlan;
 use common_planners::DropTablePlan;
 use common_planners::DropUserPlan;
 use common_planners::DropUserStagePlan;
@@ -70,8 +77,10 @@ use common_planners::GrantPrivilegePlan;
 use common_planners::GrantRolePlan;
 use common_planners::ListPlan;
 use common_planners::OptimizeTablePlan;
+use common_planners::ReclusterTablePlan;
 use common_planners::RemoveUserStagePlan;
 use common_planners::RenameDatabasePlan;
+use common_planners::RenameTableColumnPlan;
 use common_planners::RenameTablePlan;
 use common_planners::RevokePrivilegePlan;
 use common_planners::RevokeRolePlan;
@@ -83,10 +92,12 @@ use common_planners::ShowTablesPlan;
 use common_planners::ShowTablesStatusPlan;
 use common_planners::TruncateTablePlan;
 use common_planners::UndropTablePlan;
+use common_planners::UpdatePlan;
 pub use copy_v2::CopyPlanV2;
 pub use copy_v2::ValidationMode;
 pub use eval_scalar::EvalScalar;
 pub use eval_scalar::ScalarItem;
+pub use exchange::Exchange;
 pub use filter::Filter;
 pub use hash_join::PhysicalHashJoin;
 pub use insert::Insert;
@@ -97,6 +108,9 @@ pub use logical_get::LogicalGet;
 pub use logical_join::JoinType;
 pub use logical_join::LogicalInnerJoin;
 pub use max_one_row::Max1Row;
+pub use merge_into::MergeInto;
+pub use merge_join::PhysicalMergeJoin;
+pub use nested_loop_join::PhysicalNestedLoopJoin;
 pub use operator::*;
 pub use pattern::PatternPlan;
 pub use physical_scan::PhysicalScan;
@@ -104,6 +118,7 @@ pub use project::Project;
 pub use scalar::*;
 pub use sort::Sort;
 pub use sort::SortItem;
+pub use window::Window;
 
 use super::BindContext;
 use super::MetadataRef;
@@ -152,6 +167,10 @@ pub enum Plan {
     RenameTable(Box<RenameTablePlan>),
     AlterTableClusterKey(Box<AlterTableClusterKeyPlan>),
     DropTableClusterKey(Box<DropTableClusterKeyPlan>),
+    AddTableColumn(Box<AddTableColumnPlan>),
+    DropTableColumn(Box<DropTableColumnPlan>),
+    RenameTableColumn(Box<RenameTableColumnPlan>),
+    ReclusterTable(Box<ReclusterTablePlan>),
     TruncateTable(Box<TruncateTablePlan>),
     OptimizeTable(Box<OptimizeTablePlan>),
     ExistsTable(Box<ExistsTablePlan>),
@@ -159,6 +178,8 @@ pub enum Plan {
     // Insert
     Insert(Box<Insert>),
     Delete(Box<DeletePlan>),
+    Update(Box<UpdatePlan>),
+    MergeInto(Box<MergeInto>),
 
     // Views
     CreateView(Box<CreateViewPlan>),
@@ -218,6 +239,10 @@ impl Display for Plan {
             Plan::RenameTable(_) => write!(f, "RenameTable"),
             Plan::AlterTableClusterKey(_) => write!(f, "AlterTableClusterKey"),
             Plan::DropTableClusterKey(_) => write!(f, "DropTableClusterKey"),
+            Plan::AddTableColumn(_) => write!(f, "AddTableColumn"),
+            Plan::DropTableColumn(_) => write!(f, "DropTableColumn"),
+            Plan::RenameTableColumn(_) => write!(f, "RenameTableColumn"),
+            Plan::ReclusterTable(_) => write!(f, "ReclusterTable"),
             Plan::TruncateTable(_) => write!(f, "TruncateTable"),
             Plan::OptimizeTable(_) => write!(f, "OptimizeTable"),
             Plan::ExistsTable(_) => write!(f, "ExistsTable"),
@@ -247,6 +272,8 @@ impl Display for Plan {
             Plan::DropUDF(_) => write!(f, "DropUDF"),
             Plan::Insert(_) => write!(f, "Insert"),
             Plan::Delete(_) => write!(f, "Delete"),
+            Plan::Update(_) => write!(f, "Update"),
+            Plan::MergeInto(_) => write!(f, "MergeInto"),
             Plan::Call(_) => write!(f, "Call"),
         }
     }
@@ -282,6 +309,10 @@ impl Plan {
             Plan::RenameTable(plan) => plan.schema(),
             Plan::AlterTableClusterKey(plan) => plan.schema(),
             Plan::DropTableClusterKey(plan) => plan.schema(),
+            Plan::AddTableColumn(plan) => plan.schema(),
+            Plan::DropTableColumn(plan) => plan.schema(),
+            Plan::RenameTableColumn(plan) => plan.schema(),
+            Plan::ReclusterTable(plan) => plan.schema(),
             Plan::TruncateTable(plan) => plan.schema(),
             Plan::OptimizeTable(plan) => plan.schema(),
             Plan::ExistsTable(plan) => plan.schema(),
@@ -311,6 +342,8 @@ impl Plan {
             Plan::DropUDF(_) => Arc::new(DataSchema::empty()),
             Plan::Insert(plan) => plan.schema(),
             Plan::Delete(_) => Arc::new(DataSchema::empty()),
+            Plan::Update(_) => Arc::new(DataSchema::empty()),
+            Plan::MergeInto(plan) => plan.schema(),
             Plan::Call(_) => Arc::new(DataSchema::empty()),
         }
     }
@@ -0,0 +1,80 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use super::JoinType;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalPlan;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalPlan;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+
+/// Block nested-loop join, every pair of rows from both children is checked
+/// against the join conditions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhysicalNestedLoopJoin {
+    pub conditions: Vec<Scalar>,
+    pub join_type: JoinType,
+}
+
+impl Operator for PhysicalNestedLoopJoin {
+    fn rel_op(&self) -> RelOp {
+        RelOp::PhysicalNestedLoopJoin
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        false
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalPlan> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalPlan> {
+        None
+    }
+}
+
+impl PhysicalPlan for PhysicalNestedLoopJoin {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        let mut prop = rel_expr.derive_physical_prop_child(0)?;
+        prop.ordering.clear();
+        Ok(prop)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // Only inner join is supported, so the right side can be broadcast
+        // to every node without duplicating any unmatched rows.
+        let distribution = if child_index == 0 {
+            Distribution::Any
+        } else {
+            Distribution::Broadcast
+        };
+        Ok(required.clone().with_distribution(distribution))
+    }
+}
@@ -18,20 +18,24 @@ use common_exception::Result;
 use super::aggregate::Aggregate;
 use super::apply::CrossApply;
 use super::eval_scalar::EvalScalar;
+use super::exchange::Exchange;
 use super::filter::Filter;
 use super::hash_join::PhysicalHashJoin;
 use super::limit::Limit;
 use super::logical_get::LogicalGet;
 use super::logical_join::LogicalInnerJoin;
 use super::max_one_row::Max1Row;
+use super::merge_join::PhysicalMergeJoin;
+use super::nested_loop_join::PhysicalNestedLoopJoin;
 use super::pattern::PatternPlan;
 use super::physical_scan::PhysicalScan;
 use super::project::Project;
 use super::sort::Sort;
+use super::window::Window;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
-use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::RequiredProperty;
 
 pub trait Operator {
     fn rel_op(&self) -> RelOp;
@@ -54,7 +58,16 @@ pub trait LogicalPlan {
 }
 
 pub trait PhysicalPlan {
-    fn compute_physical_prop(&self, expression: &SExpr) -> PhysicalProperty;
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty>;
+
+    /// Compute the property required by the child at `child_index` to
+    /// satisfy the `required` property of current operator.
+    fn compute_required_prop_child<'a>(
+        &self,
+        rel_expr: &RelExpr<'a>,
+        child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty>;
 }
 
 /// Relational operator
@@ -67,6 +80,9 @@ pub enum RelOp {
     // Physical operators
     PhysicalScan,
     PhysicalHashJoin,
+    PhysicalMergeJoin,
+    PhysicalNestedLoopJoin,
+    Exchange,
 
     // Operators that are both logical and physical
     Project,
@@ -77,19 +93,23 @@ pub enum RelOp {
     Limit,
     CrossApply,
     Max1Row,
+    Window,
 
     // Pattern
     Pattern,
 }
 
 /// Relational operators
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum RelOperator {
     LogicalGet(LogicalGet),
     LogicalInnerJoin(LogicalInnerJoin),
 
     PhysicalScan(PhysicalScan),
     PhysicalHashJoin(PhysicalHashJoin),
+    PhysicalMergeJoin(PhysicalMergeJoin),
+    PhysicalNestedLoopJoin(PhysicalNestedLoopJoin),
+    Exchange(Exchange),
 
     Project(Project),
     EvalScalar(EvalScalar),
@@ -99,6 +119,7 @@ pub enum RelOperator {
     Limit(Limit),
     CrossApply(CrossApply),
     Max1Row(Max1Row),
+    Window(Window),
 
     Pattern(PatternPlan),
 }
@@ -110,6 +131,9 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.rel_op(),
             RelOperator::PhysicalScan(rel_op) => rel_op.rel_op(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.rel_op(),
+            RelOperator::PhysicalMergeJoin(rel_op) => rel_op.rel_op(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.rel_op(),
+            RelOperator::Exchange(rel_op) => rel_op.rel_op(),
             RelOperator::Project(rel_op) => rel_op.rel_op(),
             RelOperator::EvalScalar(rel_op) => rel_op.rel_op(),
             RelOperator::Filter(rel_op) => rel_op.rel_op(),
@@ -118,6 +142,7 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.rel_op(),
             RelOperator::CrossApply(rel_op) => rel_op.rel_op(),
             RelOperator::Max1Row(rel_op) => rel_op.rel_op(),
+            RelOperator::Window(rel_op) => rel_op.rel_op(),
             RelOperator::Pattern(rel_op) => rel_op.rel_op(),
         }
     }
@@ -128,6 +153,9 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.is_physical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.is_physical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.is_physical(),
+            RelOperator::PhysicalMergeJoin(rel_op) => rel_op.is_physical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.is_physical(),
+            RelOperator::Exchange(rel_op) => rel_op.is_physical(),
             RelOperator::Project(rel_op) => rel_op.is_physical(),
             RelOperator::EvalScalar(rel_op) => rel_op.is_physical(),
             RelOperator::Filter(rel_op) => rel_op.is_physical(),
@@ -136,6 +164,7 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.is_physical(),
             RelOperator::CrossApply(rel_op) => rel_op.is_physical(),
             RelOperator::Max1Row(rel_op) => rel_op.is_physical(),
+            RelOperator::Window(rel_op) => rel_op.is_physical(),
             RelOperator::Pattern(rel_op) => rel_op.is_physical(),
         }
     }
@@ -146,6 +175,9 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.is_logical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.is_logical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.is_logical(),
+            RelOperator::PhysicalMergeJoin(rel_op) => rel_op.is_logical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.is_logical(),
+            RelOperator::Exchange(rel_op) => rel_op.is_logical(),
             RelOperator::Project(rel_op) => rel_op.is_logical(),
             RelOperator::EvalScalar(rel_op) => rel_op.is_logical(),
             RelOperator::Filter(rel_op) => rel_op.is_logical(),
@@ -154,6 +186,7 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.is_logical(),
             RelOperator::CrossApply(rel_op) => rel_op.is_logical(),
             RelOperator::Max1Row(rel_op) => rel_op.is_logical(),
+            RelOperator::Window(rel_op) => rel_op.is_logical(),
             RelOperator::Pattern(rel_op) => rel_op.is_logical(),
         }
     }
@@ -164,6 +197,9 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.as_logical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.as_logical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.as_logical(),
+            RelOperator::PhysicalMergeJoin(rel_op) => rel_op.as_logical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.as_logical(),
+            RelOperator::Exchange(rel_op) => rel_op.as_logical(),
             RelOperator::Project(rel_op) => rel_op.as_logical(),
             RelOperator::EvalScalar(rel_op) => rel_op.as_logical(),
             RelOperator::Filter(rel_op) => rel_op.as_logical(),
@@ -172,6 +208,7 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.as_logical(),
             RelOperator::CrossApply(rel_op) => rel_op.as_logical(),
             RelOperator::Max1Row(rel_op) => rel_op.as_logical(),
+            RelOperator::Window(rel_op) => rel_op.as_logical(),
             RelOperator::Pattern(rel_op) => rel_op.as_logical(),
         }
     }
@@ -182,6 +219,9 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.as_physical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.as_physical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.as_physical(),
+            RelOperator::PhysicalMergeJoin(rel_op) => rel_op.as_physical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.as_physical(),
+            RelOperator::Exchange(rel_op) => rel_op.as_physical(),
             RelOperator::Project(rel_op) => rel_op.as_physical(),
             RelOperator::EvalScalar(rel_op) => rel_op.as_physical(),
             RelOperator::Filter(rel_op) => rel_op.as_physical(),
@@ -190,6 +230,7 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.as_physical(),
             RelOperator::CrossApply(rel_op) => rel_op.as_physical(),
             RelOperator::Max1Row(rel_op) => rel_op.as_physical(),
+            RelOperator::Window(rel_op) => rel_op.as_physical(),
             RelOperator::Pattern(rel_op) => rel_op.as_physical(),
         }
     }
@@ -272,6 +313,63 @@ impl TryFrom<RelOperator> for PhysicalHashJoin {
     }
 }
 
+impl From<PhysicalMergeJoin> for RelOperator {
+    fn from(v: PhysicalMergeJoin) -> Self {
+        Self::PhysicalMergeJoin(v)
+    }
+}
+
+impl TryFrom<RelOperator> for PhysicalMergeJoin {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::PhysicalMergeJoin(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to PhysicalMergeJoin",
+            ))
+        }
+    }
+}
+
+impl From<PhysicalNestedLoopJoin> for RelOperator {
+    fn from(v: PhysicalNestedLoopJoin) -> Self {
+        Self::PhysicalNestedLoopJoin(v)
+    }
+}
+
+impl TryFrom<RelOperator> for PhysicalNestedLoopJoin {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::PhysicalNestedLoopJoin(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to PhysicalNestedLoopJoin",
+            ))
+        }
+    }
+}
+
+impl From<Exchange> for RelOperator {
+    fn from(v: Exchange) -> Self {
+        Self::Exchange(v)
+    }
+}
+
+impl TryFrom<RelOperator> for Exchange {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::Exchange(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to Exchange",
+            ))
+        }
+    }
+}
+
 impl From<Project> for RelOperator {
     fn from(v: Project) -> Self {
         Self::Project(v)
@@ -424,6 +522,25 @@ impl TryFrom<RelOperator> for Max1Row {
     }
 }
 
+impl From<Window> for RelOperator {
+    fn from(v: Window) -> Self {
+        Self::Window(v)
+    }
+}
+
+impl TryFrom<RelOperator> for Window {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::Window(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to Window",
+            ))
+        }
+    }
+}
+
 impl From<PatternPlan> for RelOperator {
     fn from(v: PatternPlan) -> Self {
         Self::Pattern(v)
@@ -19,6 +19,7 @@ use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::scalars::FunctionFactory;
+use common_functions::window::WindowFrame;
 
 use crate::sql::binder::ColumnBinding;
 use crate::sql::optimizer::ColumnSet;
@@ -48,11 +49,13 @@ pub enum Scalar {
     OrExpr(OrExpr),
     ComparisonExpr(ComparisonExpr),
     AggregateFunction(AggregateFunction),
+    WindowFunction(WindowFunction),
     FunctionCall(FunctionCall),
     // TODO(leiysky): maybe we don't need this variant any more
     // after making functions static typed?
     CastExpr(CastExpr),
     SubqueryExpr(SubqueryExpr),
+    Placeholder(Placeholder),
 }
 
 impl ScalarExpr for Scalar {
@@ -64,9 +67,11 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.data_type(),
             Scalar::ComparisonExpr(scalar) => scalar.data_type(),
             Scalar::AggregateFunction(scalar) => scalar.data_type(),
+            Scalar::WindowFunction(scalar) => scalar.data_type(),
             Scalar::FunctionCall(scalar) => scalar.data_type(),
             Scalar::CastExpr(scalar) => scalar.data_type(),
             Scalar::SubqueryExpr(scalar) => scalar.data_type(),
+            Scalar::Placeholder(scalar) => scalar.data_type(),
         }
     }
 
@@ -78,9 +83,11 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.used_columns(),
             Scalar::ComparisonExpr(scalar) => scalar.used_columns(),
             Scalar::AggregateFunction(scalar) => scalar.used_columns(),
+            Scalar::WindowFunction(scalar) => scalar.used_columns(),
             Scalar::FunctionCall(scalar) => scalar.used_columns(),
             Scalar::CastExpr(scalar) => scalar.used_columns(),
             Scalar::SubqueryExpr(scalar) => scalar.used_columns(),
+            Scalar::Placeholder(scalar) => scalar.used_columns(),
         }
     }
 
@@ -92,9 +99,11 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.is_deterministic(),
             Scalar::ComparisonExpr(scalar) => scalar.is_deterministic(),
             Scalar::AggregateFunction(scalar) => scalar.is_deterministic(),
+            Scalar::WindowFunction(scalar) => scalar.is_deterministic(),
             Scalar::FunctionCall(scalar) => scalar.is_deterministic(),
             Scalar::CastExpr(scalar) => scalar.is_deterministic(),
             Scalar::SubqueryExpr(scalar) => scalar.is_deterministic(),
+            Scalar::Placeholder(scalar) => scalar.is_deterministic(),
         }
     }
 }
@@ -209,6 +218,25 @@ impl TryFrom<Scalar> for AggregateFunction {
     }
 }
 
+impl From<WindowFunction> for Scalar {
+    fn from(v: WindowFunction) -> Self {
+        Self::WindowFunction(v)
+    }
+}
+
+impl TryFrom<Scalar> for WindowFunction {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::WindowFunction(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast Scalar to WindowFunction",
+            ))
+        }
+    }
+}
+
 impl From<FunctionCall> for Scalar {
     fn from(v: FunctionCall) -> Self {
         Self::FunctionCall(v)
@@ -266,6 +294,25 @@ impl TryFrom<Scalar> for SubqueryExpr {
     }
 }
 
+impl From<Placeholder> for Scalar {
+    fn from(v: Placeholder) -> Self {
+        Self::Placeholder(v)
+    }
+}
+
+impl TryFrom<Scalar> for Placeholder {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::Placeholder(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast Scalar to Placeholder",
+            ))
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct BoundColumnRef {
     pub column: ColumnBinding,
@@ -306,6 +353,30 @@ impl ScalarExpr for ConstantExpr {
     }
 }
 
+/// A parameter of a prepared statement, which is substituted with the value bound at execution.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Placeholder {
+    /// Index of the parameter, starting from 1.
+    pub index: usize,
+    /// Type inferred from where the parameter is used, or `Null` if it can't be inferred,
+    /// in which case the parameter takes the type of the bound value.
+    pub data_type: DataTypeImpl,
+}
+
+impl ScalarExpr for Placeholder {
+    fn data_type(&self) -> DataTypeImpl {
+        self.data_type.clone()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        ColumnSet::new()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct AndExpr {
     pub left: Box<Scalar>,
@@ -461,6 +532,51 @@ impl ScalarExpr for AggregateFunction {
     }
 }
 
+/// A function evaluated over a window, e.g. `SUM(a) OVER (PARTITION BY b)`.
+/// It's either an aggregate function or a built-in window function like `RANK()`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct WindowFunction {
+    pub display_name: String,
+
+    pub func_name: String,
+    pub params: Vec<DataValue>,
+    pub args: Vec<Scalar>,
+    pub partition_by: Vec<Scalar>,
+    pub order_by: Vec<WindowOrderBy>,
+    pub frame: WindowFrame,
+    pub return_type: DataTypeImpl,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WindowOrderBy {
+    pub expr: Scalar,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+impl ScalarExpr for WindowFunction {
+    fn data_type(&self) -> DataTypeImpl {
+        self.return_type.clone()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        let mut result = ColumnSet::new();
+        for scalar in self
+            .args
+            .iter()
+            .chain(self.partition_by.iter())
+            .chain(self.order_by.iter().map(|order_by| &order_by.expr))
+        {
+            result = result.union(&scalar.used_columns()).cloned().collect();
+        }
+        result
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct FunctionCall {
     pub arguments: Vec<Scalar>,
@@ -0,0 +1,108 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::Result;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalPlan;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalPlan;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::WindowFunction;
+use crate::sql::IndexType;
+
+/// Evaluate a window function and append the result as column `index`.
+/// All the arguments, partition keys and order keys of the window function
+/// are required to be `BoundColumnRef`s evaluated by the child operator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Window {
+    pub index: IndexType,
+    pub function: WindowFunction,
+}
+
+impl Operator for Window {
+    fn rel_op(&self) -> RelOp {
+        RelOp::Window
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalPlan> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalPlan> {
+        Some(self)
+    }
+}
+
+impl PhysicalPlan for Window {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        let mut prop = rel_expr.derive_physical_prop_child(0)?;
+        prop.ordering.clear();
+        Ok(prop)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        let distribution = if self.function.partition_by.is_empty() {
+            Distribution::Serial
+        } else {
+            Distribution::Hash(self.function.partition_by.clone())
+        };
+        Ok(required.clone().with_distribution(distribution))
+    }
+}
+
+impl LogicalPlan for Window {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let input_prop = rel_expr.derive_relational_prop_child(0)?;
+
+        // Derive output columns
+        let mut output_columns = input_prop.output_columns;
+        output_columns.insert(self.index);
+
+        // Derive outer columns
+        let outer_columns = input_prop
+            .outer_columns
+            .union(&self.function.used_columns())
+            .cloned()
+            .collect::<ColumnSet>()
+            .difference(&output_columns)
+            .cloned()
+            .collect();
+
+        Ok(RelationalProperty {
+            output_columns,
+            outer_columns,
+            cardinality: input_prop.cardinality,
+            column_stats: input_prop.column_stats,
+        })
+    }
+}
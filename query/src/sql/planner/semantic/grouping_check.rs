@@ -14,14 +14,22 @@
 
 use common_ast::parser::token::Token;
 use common_ast::DisplayError;
+use common_datavalues::wrap_nullable;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::UInt32Type;
+use common_datavalues::UInt64Type;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
 
 use crate::sql::binder::ColumnBinding;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::CastExpr;
 use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::ConstantExpr;
 use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
@@ -31,6 +39,9 @@ use crate::sql::BindContext;
 /// Check validity of scalar expression in a grouping context.
 /// The matched grouping item will be replaced with a BoundColumnRef
 /// to corresponding grouping item column.
+///
+/// With grouping sets, the group items become nullable, so the types of
+/// the expressions referencing them are derived again.
 pub struct GroupingChecker<'a> {
     bind_context: &'a BindContext,
 }
@@ -48,12 +59,17 @@ impl<'a> GroupingChecker<'a> {
             .get(&format!("{:?}", scalar))
         {
             let column = &self.bind_context.aggregate_info.group_items[*index];
+            let data_type = if self.has_grouping_sets() {
+                wrap_nullable(&column.scalar.data_type())
+            } else {
+                column.scalar.data_type()
+            };
             let column_binding = ColumnBinding {
                 database_name: None,
                 table_name: None,
                 column_name: "group_item".to_string(),
                 index: column.index,
-                data_type: column.scalar.data_type(),
+                data_type,
                 visible_in_unqualified_wildcard: true,
             };
             return Ok(BoundColumnRef {
@@ -63,52 +79,98 @@ impl<'a> GroupingChecker<'a> {
         }
 
         match scalar {
+            Scalar::BoundColumnRef(column)
+                if self
+                    .bind_context
+                    .windows
+                    .is_window_column(column.column.index) =>
+            {
+                // Window functions are evaluated after aggregation, so their outputs are valid
+                Ok(scalar.clone())
+            }
             Scalar::BoundColumnRef(column) => {
                 // If this is a group item, then it should have been replaced with `group_items_map`
                 let mut err_msg = format!("column \"{}\" must appear in the GROUP BY clause or be used in an aggregate function", &column.column.column_name);
                 err_msg = span.map_or(err_msg.clone(), |span| span.display_error(err_msg.clone()));
                 Err(ErrorCode::SemanticError(err_msg))
             }
-            Scalar::ConstantExpr(_) => Ok(scalar.clone()),
-            Scalar::AndExpr(scalar) => Ok(AndExpr {
-                left: Box::new(self.resolve(&scalar.left, span)?),
-                right: Box::new(self.resolve(&scalar.right, span)?),
-                return_type: scalar.return_type.clone(),
+            Scalar::ConstantExpr(_) | Scalar::Placeholder(_) => Ok(scalar.clone()),
+            Scalar::AndExpr(scalar) => {
+                let left = self.resolve(&scalar.left, span)?;
+                let right = self.resolve(&scalar.right, span)?;
+                let return_type = Self::binary_return_type(&left, &right, &scalar.return_type);
+                Ok(AndExpr {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    return_type,
+                }
+                .into())
             }
-            .into()),
-            Scalar::OrExpr(scalar) => Ok(OrExpr {
-                left: Box::new(self.resolve(&scalar.left, span)?),
-                right: Box::new(self.resolve(&scalar.right, span)?),
-                return_type: scalar.return_type.clone(),
+            Scalar::OrExpr(scalar) => {
+                let left = self.resolve(&scalar.left, span)?;
+                let right = self.resolve(&scalar.right, span)?;
+                let return_type = Self::binary_return_type(&left, &right, &scalar.return_type);
+                Ok(OrExpr {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    return_type,
+                }
+                .into())
+            }
+            Scalar::ComparisonExpr(scalar) => {
+                let left = self.resolve(&scalar.left, span)?;
+                let right = self.resolve(&scalar.right, span)?;
+                let return_type = Self::binary_return_type(&left, &right, &scalar.return_type);
+                Ok(ComparisonExpr {
+                    op: scalar.op.clone(),
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    return_type,
+                }
+                .into())
             }
-            .into()),
-            Scalar::ComparisonExpr(scalar) => Ok(ComparisonExpr {
-                op: scalar.op.clone(),
-                left: Box::new(self.resolve(&scalar.left, span)?),
-                right: Box::new(self.resolve(&scalar.right, span)?),
-                return_type: scalar.return_type.clone(),
+            Scalar::FunctionCall(func) if func.func_name == "grouping" => {
+                self.resolve_grouping(&func.arguments, span)
             }
-            .into()),
             Scalar::FunctionCall(func) => {
                 let args = func
                     .arguments
                     .iter()
                     .map(|arg| self.resolve(arg, span))
                     .collect::<Result<Vec<Scalar>>>()?;
+                let arg_types: Vec<DataTypeImpl> = args.iter().map(|arg| arg.data_type()).collect();
+                let return_type = if arg_types == func.arg_types {
+                    func.return_type.clone()
+                } else {
+                    // The arguments have become nullable because of grouping sets
+                    let arg_types_ref: Vec<&DataTypeImpl> = arg_types.iter().collect();
+                    FunctionFactory::instance()
+                        .get(&func.func_name, &arg_types_ref)?
+                        .return_type()
+                };
                 Ok(FunctionCall {
                     arguments: args,
                     func_name: func.func_name.clone(),
-                    arg_types: func.arg_types.clone(),
-                    return_type: func.return_type.clone(),
+                    arg_types,
+                    return_type,
                 }
                 .into())
             }
-            Scalar::CastExpr(cast) => Ok(CastExpr {
-                argument: Box::new(self.resolve(&cast.argument, span)?),
-                from_type: cast.from_type.clone(),
-                target_type: cast.target_type.clone(),
+            Scalar::CastExpr(cast) => {
+                let argument = self.resolve(&cast.argument, span)?;
+                let from_type = argument.data_type();
+                let target_type = if from_type.is_nullable() {
+                    wrap_nullable(&cast.target_type)
+                } else {
+                    cast.target_type.clone()
+                };
+                Ok(CastExpr {
+                    argument: Box::new(argument),
+                    from_type,
+                    target_type,
+                }
+                .into())
             }
-            .into()),
             Scalar::SubqueryExpr(_) => {
                 // TODO(leiysky): check subquery in the future
                 Ok(scalar.clone())
@@ -137,6 +199,132 @@ impl<'a> GroupingChecker<'a> {
                 }
                 Err(ErrorCode::LogicalError("Invalid aggregate function"))
             }
+
+            Scalar::WindowFunction(window_func) => {
+                if let Some(position) = self
+                    .bind_context
+                    .windows
+                    .window_functions_map
+                    .get(&window_func.display_name)
+                {
+                    let window_item = &self.bind_context.windows.window_functions[*position];
+                    let column_binding = ColumnBinding {
+                        database_name: None,
+                        table_name: None,
+                        column_name: window_func.display_name.clone(),
+                        index: window_item.index,
+                        data_type: window_item.scalar.data_type(),
+                        visible_in_unqualified_wildcard: true,
+                    };
+                    return Ok(BoundColumnRef {
+                        column: column_binding,
+                    }
+                    .into());
+                }
+                Err(ErrorCode::LogicalError("Invalid window function"))
+            }
+        }
+    }
+
+    fn has_grouping_sets(&self) -> bool {
+        self.bind_context
+            .aggregate_info
+            .grouping_id_column
+            .is_some()
+    }
+
+    fn binary_return_type(
+        left: &Scalar,
+        right: &Scalar,
+        return_type: &DataTypeImpl,
+    ) -> DataTypeImpl {
+        if left.data_type().is_nullable() || right.data_type().is_nullable() {
+            wrap_nullable(return_type)
+        } else {
+            return_type.clone()
+        }
+    }
+
+    /// Rewrite `GROUPING(a, b, ...)` into an expression over the grouping id column.
+    /// Each argument contributes a bit to the result, which is set if the argument is
+    /// absent from the grouping set of current row, and the last argument is the lowest bit.
+    fn resolve_grouping(&self, args: &[Scalar], span: Option<&[Token<'_>]>) -> Result<Scalar> {
+        let agg_info = &self.bind_context.aggregate_info;
+        let mut positions = Vec::with_capacity(args.len());
+        for arg in args {
+            match agg_info.group_items_map.get(&format!("{:?}", arg)) {
+                Some(position) => positions.push(*position),
+                None => {
+                    let err_msg = "arguments to GROUPING must be grouping expressions".to_string();
+                    let err_msg = span.map_or(err_msg.clone(), |span| span.display_error(err_msg));
+                    return Err(ErrorCode::SemanticError(err_msg));
+                }
+            }
+        }
+
+        let grouping_id = match &agg_info.grouping_id_column {
+            Some(grouping_id) => grouping_id,
+            // Every group item is present in a plain `GROUP BY`
+            None => {
+                return Ok(ConstantExpr {
+                    value: DataValue::UInt64(0),
+                    data_type: UInt32Type::new_impl(),
+                }
+                .into());
+            }
+        };
+
+        let num_items = agg_info.group_items.len();
+        let mut result: Option<Scalar> = None;
+        for (i, position) in positions.iter().enumerate() {
+            // bit = grouping_id / 2^(num_items - 1 - position) % 2
+            let bit = Self::build_function("modulo", vec![
+                Self::build_function("div", vec![
+                    BoundColumnRef {
+                        column: grouping_id.clone(),
+                    }
+                    .into(),
+                    Self::build_constant(1 << (num_items - 1 - position)),
+                ])?,
+                Self::build_constant(2),
+            ])?;
+            let bit = Self::build_function("multiply", vec![
+                bit,
+                Self::build_constant(1 << (positions.len() - 1 - i)),
+            ])?;
+            result = Some(match result {
+                Some(result) => Self::build_function("plus", vec![result, bit])?,
+                None => bit,
+            });
+        }
+        let result = result.ok_or_else(|| ErrorCode::LogicalError("Invalid GROUPING"))?;
+
+        Ok(CastExpr {
+            from_type: result.data_type(),
+            argument: Box::new(result),
+            target_type: UInt32Type::new_impl(),
+        }
+        .into())
+    }
+
+    fn build_constant(value: u64) -> Scalar {
+        ConstantExpr {
+            value: DataValue::UInt64(value),
+            data_type: UInt64Type::new_impl(),
+        }
+        .into()
+    }
+
+    fn build_function(func_name: &str, args: Vec<Scalar>) -> Result<Scalar> {
+        let arg_types: Vec<DataTypeImpl> = args.iter().map(|arg| arg.data_type()).collect();
+        let arg_types_ref: Vec<&DataTypeImpl> = arg_types.iter().collect();
+        let func = FunctionFactory::instance().get(func_name, &arg_types_ref)?;
+        Ok(FunctionCall {
+            arguments: args,
+            func_name: func_name.to_string(),
+            arg_types,
+            return_type: func.return_type(),
         }
+        .into())
     }
 }
@@ -21,18 +21,25 @@ use common_ast::ast::Expr;
 use common_ast::ast::Identifier;
 use common_ast::ast::Literal;
 use common_ast::ast::MapAccessor;
+use common_ast::ast::OrderByExpr;
 use common_ast::ast::Query;
 use common_ast::ast::TrimWhere;
 use common_ast::ast::UnaryOperator;
+use common_ast::ast::Window;
+use common_ast::ast::WindowFrameBound as AstWindowFrameBound;
+use common_ast::ast::WindowFrameUnits as AstWindowFrameUnits;
 use common_ast::parser::parse_expr;
 use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::DisplayError;
+use common_datavalues::remove_nullable;
 use common_datavalues::type_coercion::merge_types;
+use common_datavalues::wrap_nullable;
 use common_datavalues::ArrayType;
 use common_datavalues::BooleanType;
 use common_datavalues::DataField;
+use common_datavalues::DataType;
 use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
 use common_datavalues::IntervalKind;
@@ -40,6 +47,8 @@ use common_datavalues::IntervalType;
 use common_datavalues::NullType;
 use common_datavalues::StringType;
 use common_datavalues::TimestampType;
+use common_datavalues::UInt32Type;
+use common_datavalues::UInt64Type;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
@@ -47,6 +56,10 @@ use common_functions::is_builtin_function;
 use common_functions::scalars::CastFunction;
 use common_functions::scalars::FunctionFactory;
 use common_functions::scalars::TupleFunction;
+use common_functions::window::BuiltInWindowFunction;
+use common_functions::window::WindowFrame;
+use common_functions::window::WindowFrameBound;
+use common_functions::window::WindowFrameUnits;
 
 use crate::common::Evaluator;
 use crate::sessions::QueryContext;
@@ -64,9 +77,12 @@ use crate::sql::plans::ComparisonOp;
 use crate::sql::plans::ConstantExpr;
 use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
+use crate::sql::plans::Placeholder;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::SubqueryExpr;
 use crate::sql::plans::SubqueryType;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
 use crate::sql::BindContext;
 use crate::sql::ScalarExpr;
 
@@ -87,6 +103,10 @@ pub struct TypeChecker<'a> {
     // true if current expr is inside an aggregate function.
     // This is used to check if there is nested aggregate function.
     in_aggregate_function: bool,
+
+    // true if current expr is inside a window function.
+    // This is used to check if there is nested window function.
+    in_window_function: bool,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -100,6 +120,7 @@ impl<'a> TypeChecker<'a> {
             ctx,
             metadata,
             in_aggregate_function: false,
+            in_window_function: false,
         }
     }
 
@@ -405,9 +426,14 @@ impl<'a> TypeChecker<'a> {
                 name,
                 args,
                 params,
-                ..
+                window,
             } => {
                 let func_name = name.name.as_str();
+                // `GROUPING` can only be evaluated with the grouping sets of aggregation,
+                // it will be rewritten by `GroupingChecker`.
+                if func_name.eq_ignore_ascii_case("grouping") {
+                    return self.resolve_grouping(span, args).await;
+                }
                 if !is_builtin_function(func_name) {
                     return self.resolve_udf(span, func_name, args).await;
                 }
@@ -420,6 +446,26 @@ impl<'a> TypeChecker<'a> {
 
                 let args: Vec<&Expr> = args.iter().collect();
 
+                if let Some(window) = window {
+                    return self
+                        .resolve_window_function(
+                            span,
+                            format!("{:#}", expr),
+                            func_name,
+                            *distinct,
+                            params,
+                            &args,
+                            window,
+                        )
+                        .await;
+                }
+
+                if BuiltInWindowFunction::try_from_name(func_name).is_some() {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "window function {func_name} requires an OVER clause"
+                    ))));
+                }
+
                 if AggregateFunctionFactory::instance().check(func_name) {
                     if self.in_aggregate_function {
                         // Reset the state
@@ -723,6 +769,7 @@ impl<'a> TypeChecker<'a> {
                         },
                         args: vec![expr.clone()],
                         params: vec![],
+                        window: None,
                     };
 
                     args.push(is_not_null_expr);
@@ -757,6 +804,35 @@ impl<'a> TypeChecker<'a> {
                 )
                 .await?
             }
+
+            Expr::Placeholder { index, .. } => match self.ctx.get_query_parameter(*index) {
+                Some(value) => {
+                    let data_type = value.data_type();
+                    (
+                        ConstantExpr {
+                            value,
+                            data_type: data_type.clone(),
+                        }
+                        .into(),
+                        data_type,
+                    )
+                }
+                // The statement is being prepared, the value will be substituted at execution.
+                None => {
+                    let data_type = required_type
+                        .as_ref()
+                        .map(wrap_nullable)
+                        .unwrap_or_else(NullType::new_impl);
+                    (
+                        Placeholder {
+                            index: *index,
+                            data_type: data_type.clone(),
+                        }
+                        .into(),
+                        data_type,
+                    )
+                }
+            },
         };
 
         self.post_resolve(&scalar, &data_type)
@@ -796,6 +872,39 @@ impl<'a> TypeChecker<'a> {
         ))
     }
 
+    /// Resolve `GROUPING(expr, ...)`. Whether the arguments are group items is
+    /// checked after the group items are resolved.
+    async fn resolve_grouping(
+        &mut self,
+        span: &[Token<'_>],
+        arguments: &[Expr<'_>],
+    ) -> Result<(Scalar, DataTypeImpl)> {
+        if arguments.is_empty() {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "GROUPING requires at least one argument".to_string(),
+            )));
+        }
+
+        let mut args = vec![];
+        let mut arg_types = vec![];
+        for argument in arguments {
+            let (arg, arg_type) = self.resolve(argument, None).await?;
+            args.push(arg);
+            arg_types.push(arg_type);
+        }
+
+        Ok((
+            FunctionCall {
+                arguments: args,
+                func_name: "grouping".to_string(),
+                arg_types,
+                return_type: UInt32Type::new_impl(),
+            }
+            .into(),
+            UInt32Type::new_impl(),
+        ))
+    }
+
     pub async fn resolve_scalar_function_call(
         &mut self,
         span: &[Token<'_>],
@@ -850,8 +959,19 @@ impl<'a> TypeChecker<'a> {
             | BinaryOperator::BitwiseAnd
             | BinaryOperator::BitwiseXor
             | BinaryOperator::Xor => {
-                self.resolve_function(span, op.to_string().as_str(), &[left, right], required_type)
-                    .await
+                let (mut left, _) = self.resolve(left, None).await?;
+                let (mut right, _) = self.resolve(right, None).await?;
+                infer_placeholder_type(&mut left, &right);
+                infer_placeholder_type(&mut right, &left);
+                let arg_types = vec![left.data_type(), right.data_type()];
+                self.resolve_scalar_function_call(
+                    span,
+                    op.to_string().as_str(),
+                    vec![left, right],
+                    arg_types,
+                    required_type,
+                )
+                .await
             }
             BinaryOperator::Gt
             | BinaryOperator::Lt
@@ -860,8 +980,10 @@ impl<'a> TypeChecker<'a> {
             | BinaryOperator::Eq
             | BinaryOperator::NotEq => {
                 let op = ComparisonOp::try_from(op)?;
-                let (left, _) = self.resolve(left, None).await?;
-                let (right, _) = self.resolve(right, None).await?;
+                let (mut left, _) = self.resolve(left, None).await?;
+                let (mut right, _) = self.resolve(right, None).await?;
+                infer_placeholder_type(&mut left, &right);
+                infer_placeholder_type(&mut right, &left);
                 let func = FunctionFactory::instance()
                     .get(op.to_func_name(), &[&left.data_type(), &right.data_type()])?;
                 Ok((
@@ -1283,6 +1405,250 @@ impl<'a> TypeChecker<'a> {
         ))
     }
 
+    /// Resolve a function call with `OVER` clause into a `WindowFunction`, which can be
+    /// either an aggregate function or a built-in window function like `RANK()`.
+    #[allow(clippy::too_many_arguments)]
+    async fn resolve_window_function(
+        &mut self,
+        span: &[Token<'_>],
+        display_name: String,
+        func_name: &str,
+        distinct: bool,
+        params: &[Literal],
+        args: &[&Expr<'_>],
+        window: &Window<'_>,
+    ) -> Result<(Scalar, DataTypeImpl)> {
+        if self.in_aggregate_function || self.in_window_function {
+            // Reset the state
+            self.in_aggregate_function = false;
+            self.in_window_function = false;
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "window function calls cannot be nested".to_string(),
+            )));
+        }
+
+        self.in_window_function = true;
+        let mut arguments = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            arguments.push(self.resolve(arg, None).await?);
+        }
+        let mut partition_by = Vec::with_capacity(window.partition_by.len());
+        for expr in window.partition_by.iter() {
+            partition_by.push(self.resolve(expr, None).await?.0);
+        }
+        let mut order_by = Vec::with_capacity(window.order_by.len());
+        for order in window.order_by.iter() {
+            order_by.push(WindowOrderBy {
+                expr: self.resolve(&order.expr, None).await?.0,
+                asc: order.asc.unwrap_or(true),
+                nulls_first: order.nulls_first.unwrap_or_default(),
+            });
+        }
+        self.in_window_function = false;
+
+        let frame = match &window.window_frame {
+            Some(frame) => {
+                let convert_bound = |bound: &AstWindowFrameBound| match bound {
+                    AstWindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+                    AstWindowFrameBound::Preceding(n) => WindowFrameBound::Preceding(*n),
+                    AstWindowFrameBound::Following(n) => WindowFrameBound::Following(*n),
+                };
+                let units = match frame.units {
+                    AstWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+                    AstWindowFrameUnits::Range => WindowFrameUnits::Range,
+                };
+                WindowFrame::try_create(
+                    units,
+                    convert_bound(&frame.start_bound),
+                    convert_bound(&frame.end_bound),
+                )
+                .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?
+            }
+            // Without an explicit frame, the frame contains all the rows from the start of the
+            // partition to the last peer of the current row. All the rows of a partition are
+            // peers if there is no `ORDER BY`.
+            None => WindowFrame::default(),
+        };
+
+        if frame.units == WindowFrameUnits::Range {
+            let has_offset = [frame.start_bound, frame.end_bound].iter().any(|bound| {
+                matches!(
+                    bound,
+                    WindowFrameBound::Preceding(Some(_)) | WindowFrameBound::Following(Some(_))
+                )
+            });
+            if has_offset
+                && (order_by.len() != 1
+                    || !remove_nullable(&order_by[0].expr.data_type())
+                        .data_type_id()
+                        .is_numeric())
+            {
+                return Err(ErrorCode::SemanticError(span.display_error(
+                    "RANGE with offset PRECEDING/FOLLOWING requires exactly one numeric ORDER BY expression"
+                        .to_string(),
+                )));
+            }
+        }
+
+        let (func_name, params, args, return_type) =
+            if let Some(func) = BuiltInWindowFunction::try_from_name(func_name) {
+                if distinct || !params.is_empty() {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "window function {func} doesn't accept DISTINCT or parameters"
+                    ))));
+                }
+                self.resolve_builtin_window_function(span, func, arguments)?
+            } else if AggregateFunctionFactory::instance().check(func_name) {
+                let params = params
+                    .iter()
+                    .map(|literal| self.resolve_literal(literal, None).map(|(value, _)| value))
+                    .collect::<Result<Vec<DataValue>>>()?;
+                let data_fields = arguments
+                    .iter()
+                    .map(|(_, data_type)| DataField::new("", data_type.clone()))
+                    .collect();
+
+                // Rewrite `count(distinct ...)` to `count_distinct(...)`, and other
+                // distinct aggregate functions to their `_distinct` combinators.
+                let func_name = match (func_name.eq_ignore_ascii_case("count"), distinct) {
+                    (true, true) => "count_distinct".to_string(),
+                    (false, true) => format!("{func_name}_distinct"),
+                    (_, false) => func_name.to_string(),
+                };
+                let agg_func = AggregateFunctionFactory::instance()
+                    .get(&func_name, params.clone(), data_fields)
+                    .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
+
+                let args = if optimize_remove_count_args(&func_name, false, args) {
+                    vec![]
+                } else {
+                    arguments.into_iter().map(|arg| arg.0).collect()
+                };
+                (func_name, params, args, agg_func.return_type()?)
+            } else {
+                return Err(ErrorCode::SemanticError(span.display_error(format!(
+                    "function {func_name} is neither a window function nor an aggregate function"
+                ))));
+            };
+
+        Ok((
+            WindowFunction {
+                display_name,
+                func_name,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+                return_type: return_type.clone(),
+            }
+            .into(),
+            return_type,
+        ))
+    }
+
+    /// Check the arguments of a built-in window function, returns the function name,
+    /// the constant parameters, the arguments and the return type.
+    fn resolve_builtin_window_function(
+        &self,
+        span: &[Token<'_>],
+        func: BuiltInWindowFunction,
+        mut arguments: Vec<(Scalar, DataTypeImpl)>,
+    ) -> Result<(String, Vec<DataValue>, Vec<Scalar>, DataTypeImpl)> {
+        let num_arguments = arguments.len();
+        let arguments_error = |expected: &str| {
+            ErrorCode::SemanticError(span.display_error(format!(
+                "window function {func} expects {expected}, but got {num_arguments} arguments"
+            )))
+        };
+        let constant_u64 = |scalar: &Scalar, name: &str| match scalar {
+            Scalar::ConstantExpr(ConstantExpr { value, .. }) => value.as_u64().map_err(|_| {
+                ErrorCode::SemanticError(span.display_error(format!(
+                    "{name} of window function {func} must be a non-negative integer"
+                )))
+            }),
+            _ => Err(ErrorCode::SemanticError(span.display_error(format!(
+                "{name} of window function {func} must be a constant"
+            )))),
+        };
+
+        match func {
+            BuiltInWindowFunction::RowNumber
+            | BuiltInWindowFunction::Rank
+            | BuiltInWindowFunction::DenseRank => {
+                if !arguments.is_empty() {
+                    return Err(arguments_error("no argument"));
+                }
+                Ok((func.to_string(), vec![], vec![], UInt64Type::new_impl()))
+            }
+            BuiltInWindowFunction::Lag | BuiltInWindowFunction::Lead => {
+                if arguments.is_empty() || arguments.len() > 3 {
+                    return Err(arguments_error("1 to 3 arguments"));
+                }
+                let offset = match arguments.get(1) {
+                    Some((scalar, _)) => constant_u64(scalar, "offset")?,
+                    None => 1,
+                };
+                let default = if arguments.len() == 3 {
+                    arguments.pop()
+                } else {
+                    None
+                };
+                let (arg, arg_type) = arguments.swap_remove(0);
+                // The result is NULL if the offset row is out of the partition,
+                // unless a non-NULL default value is given.
+                let return_type = match &default {
+                    Some((_, default_type))
+                        if !default_type.is_nullable() && !default_type.is_null() =>
+                    {
+                        arg_type
+                    }
+                    _ => wrap_nullable(&arg_type),
+                };
+                let mut args = vec![arg];
+                if let Some((default, _)) = default {
+                    args.push(wrap_cast_if_needed(default, &return_type));
+                }
+                Ok((
+                    func.to_string(),
+                    vec![DataValue::UInt64(offset)],
+                    args,
+                    return_type,
+                ))
+            }
+            BuiltInWindowFunction::FirstValue | BuiltInWindowFunction::LastValue => {
+                if arguments.len() != 1 {
+                    return Err(arguments_error("1 argument"));
+                }
+                let (arg, arg_type) = arguments.swap_remove(0);
+                // The result is NULL if the window frame is empty.
+                Ok((
+                    func.to_string(),
+                    vec![],
+                    vec![arg],
+                    wrap_nullable(&arg_type),
+                ))
+            }
+            BuiltInWindowFunction::Ntile => {
+                if arguments.len() != 1 {
+                    return Err(arguments_error("1 argument"));
+                }
+                let buckets = constant_u64(&arguments[0].0, "number of buckets")?;
+                if buckets == 0 {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "number of buckets of window function {func} must be positive"
+                    ))));
+                }
+                Ok((
+                    func.to_string(),
+                    vec![DataValue::UInt64(buckets)],
+                    vec![],
+                    UInt64Type::new_impl(),
+                ))
+            }
+        }
+    }
+
     /// Resolve literal values.
     pub fn resolve_literal(
         &self,
@@ -1573,6 +1939,7 @@ impl<'a> TypeChecker<'a> {
                     name,
                     args,
                     params,
+                    window,
                 } => Ok(Expr::FunctionCall {
                     span,
                     distinct: *distinct,
@@ -1582,6 +1949,32 @@ impl<'a> TypeChecker<'a> {
                         .map(|arg| self.clone_expr_with_replacement(arg, replacement_fn))
                         .collect::<Result<Vec<Expr>>>()?,
                     params: params.clone(),
+                    window: if let Some(window) = window {
+                        Some(Window {
+                            partition_by: window
+                                .partition_by
+                                .iter()
+                                .map(|expr| self.clone_expr_with_replacement(expr, replacement_fn))
+                                .collect::<Result<Vec<Expr>>>()?,
+                            order_by: window
+                                .order_by
+                                .iter()
+                                .map(|order_by| {
+                                    Ok(OrderByExpr {
+                                        expr: self.clone_expr_with_replacement(
+                                            &order_by.expr,
+                                            replacement_fn,
+                                        )?,
+                                        asc: order_by.asc,
+                                        nulls_first: order_by.nulls_first,
+                                    })
+                                })
+                                .collect::<Result<Vec<OrderByExpr>>>()?,
+                            window_frame: window.window_frame.clone(),
+                        })
+                    } else {
+                        None
+                    },
                 }),
                 Expr::Case {
                     span,
@@ -1655,3 +2048,14 @@ impl<'a> TypeChecker<'a> {
         }
     }
 }
+
+/// Infer the type of an untyped placeholder from the other operand of a binary operator,
+/// e.g. `$1` in `a = $1` takes the type of `a`.
+fn infer_placeholder_type(scalar: &mut Scalar, other: &Scalar) {
+    if let Scalar::Placeholder(placeholder) = scalar {
+        let other_type = other.data_type();
+        if placeholder.data_type.is_null() && !other_type.is_null() {
+            placeholder.data_type = wrap_nullable(&other_type);
+        }
+    }
+}
@@ -37,7 +37,14 @@ impl SQLCommon {
             | SQLDataType::Text => Ok(Vu8::to_data_type()),
 
             SQLDataType::Float(_) => Ok(f32::to_data_type()),
-            SQLDataType::Decimal(_, _) => Ok(f64::to_data_type()),
+            SQLDataType::Decimal(precision, scale) => {
+                let precision = precision.unwrap_or(DECIMAL_DEFAULT_PRECISION as u64);
+                let scale = scale.unwrap_or(0);
+                Ok(DataTypeImpl::Decimal(DecimalType::try_create(
+                    precision as usize,
+                    scale as usize,
+                )?))
+            }
             SQLDataType::Real | SQLDataType::Double => Ok(f64::to_data_type()),
             SQLDataType::Boolean => Ok(bool::to_data_type()),
             SQLDataType::Date => Ok(DateType::new_impl()),
@@ -55,6 +55,7 @@ pub struct DfCopy {
     pub pattern: String,
     pub on_error: String,
     pub size_limit: String,
+    pub force: String,
     pub validation_mode: String,
     pub query: Option<Query>,
 }
@@ -90,6 +91,18 @@ impl AnalyzableStatement for DfCopy {
             }
         }
 
+        // Force.
+        let force = if self.force.is_empty() {
+            false
+        } else {
+            self.force.parse::<bool>().map_err(|_e| {
+                ErrorCode::SyntaxException(format!(
+                    "force must be true or false, got: {}",
+                    self.force
+                ))
+            })?
+        };
+
         // Validation mode.
         let validation_mode = ValidationMode::from_str(self.validation_mode.as_str())
             .map_err(ErrorCode::SyntaxException)?;
@@ -159,6 +172,7 @@ impl AnalyzableStatement for DfCopy {
                     from,
                     files: self.files.clone(),
                     pattern,
+                    force,
                 },
             }
         };
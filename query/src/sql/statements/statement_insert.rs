@@ -27,8 +27,6 @@ use common_planners::InsertInputSource;
 use common_planners::InsertPlan;
 use common_planners::InsertValueBlock;
 use common_planners::PlanNode;
-use common_streams::NDJsonSourceBuilder;
-use common_streams::Source;
 use common_tracing::tracing;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
@@ -179,18 +177,6 @@ impl<'a> DfInsertStatement<'a> {
                 let block = source.read(&mut reader).await?;
                 Ok(InsertInputSource::Values(InsertValueBlock { block }))
             }
-            Some("JSONEACHROW") => {
-                let builder = NDJsonSourceBuilder::create(schema.clone(), settings);
-                let cursor = futures::io::Cursor::new(stream_str.as_bytes());
-                let mut source = builder.build(cursor)?;
-                let mut blocks = Vec::new();
-                while let Some(v) = source.read().await? {
-                    blocks.push(v);
-                }
-                let block = DataBlock::concat_blocks(&blocks)?;
-                Ok(InsertInputSource::Values(InsertValueBlock { block }))
-            }
-
             // format factory
             Some(name) => {
                 let input_format =
@@ -200,8 +186,17 @@ impl<'a> DfInsertStatement<'a> {
                 let mut input_state = input_format.create_state();
                 let skip_size = input_format.skip_header(data_slice, &mut input_state)?;
 
-                let _ = input_format.read_buf(&data_slice[skip_size..], &mut input_state)?;
-                let blocks = input_format.deserialize_data(&mut input_state)?;
+                // The input format stops reading once enough rows are accepted
+                let mut data_slice = &data_slice[skip_size..];
+                let mut blocks = vec![];
+                loop {
+                    let read_size = input_format.read_buf(data_slice, &mut input_state)?;
+                    data_slice = &data_slice[read_size..];
+                    blocks.extend(input_format.deserialize_data(&mut input_state)?);
+                    if data_slice.is_empty() {
+                        break;
+                    }
+                }
                 let block = DataBlock::concat_blocks(&blocks)?;
                 Ok(InsertInputSource::Values(InsertValueBlock { block }))
             }
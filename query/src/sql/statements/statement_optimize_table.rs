@@ -29,6 +29,7 @@ use crate::sql::statements::AnalyzedResult;
 pub struct DfOptimizeTable {
     pub name: ObjectName,
     pub action: OptimizeTableAction,
+    pub dry_run: bool,
 }
 
 #[async_trait::async_trait]
@@ -41,6 +42,7 @@ impl AnalyzableStatement for DfOptimizeTable {
             database,
             table,
             action: self.action,
+            dry_run: self.dry_run,
         };
         Ok(AnalyzedResult::SimpleQuery(Box::new(
             PlanNode::OptimizeTable(plan_node),
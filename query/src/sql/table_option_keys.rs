@@ -18,6 +18,11 @@ use once_cell::sync::Lazy;
 
 pub const OPT_KEY_DATABASE_ID: &str = "database_id";
 pub const OPT_KEY_SNAPSHOT_LOCATION: &str = "snapshot_location";
+/// Ids of the tables, whose data are shared by the table being cloned from them
+pub const OPT_KEY_CLONED_FROM_TABLE_IDS: &str = "cloned_from_table_ids";
+/// Locations of the snapshots that the tables cloned from the table are cloned at, in JSON,
+/// keyed by the ids of the clones
+pub const OPT_KEY_CLONE_SNAPSHOT_LOCATIONS: &str = "clone_snapshot_locations";
 
 /// Legacy table snapshot location key
 ///
@@ -36,6 +41,8 @@ pub static RESERVED_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_DATABASE_ID);
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
+    r.insert(OPT_KEY_CLONED_FROM_TABLE_IDS);
+    r.insert(OPT_KEY_CLONE_SNAPSHOT_LOCATIONS);
     r
 });
 
@@ -44,6 +51,8 @@ pub static INTERNAL_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
     r.insert(OPT_KEY_DATABASE_ID);
+    r.insert(OPT_KEY_CLONED_FROM_TABLE_IDS);
+    r.insert(OPT_KEY_CLONE_SNAPSHOT_LOCATIONS);
     r
 });
 
@@ -0,0 +1,165 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_base::base::tokio::sync::Semaphore;
+use common_base::base::tokio::time::interval_at;
+use common_base::base::tokio::time::Duration;
+use common_base::base::tokio::time::Instant;
+use common_base::infallible::Mutex;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::OptimizeTableAction;
+use common_planners::OptimizeTablePlan;
+use common_tracing::tracing;
+
+use crate::catalogs::CATALOG_DEFAULT;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::storages::fuse::FuseTable;
+use crate::storages::Table;
+
+/// Compacts the fuse tables with `auto_compaction = true` in the background, once they have
+/// accumulated too many small segments or blocks, see [FuseTable::needs_compaction].
+///
+/// At most `max_concurrent_jobs` tables are compacted at the same time, and a table is never
+/// compacted by two jobs of the same node. Since the compactions are committed in the same way
+/// as the other mutations, they are rebased onto the concurrent insertions.
+pub struct AutoCompactionService {
+    session_mgr: Arc<SessionManager>,
+    interval: Duration,
+    jobs: Arc<Semaphore>,
+    /// ids of the tables being compacted
+    compacting: Mutex<HashSet<u64>>,
+}
+
+impl AutoCompactionService {
+    pub fn create(session_mgr: Arc<SessionManager>) -> Arc<Self> {
+        let conf = session_mgr.get_conf();
+        let max_concurrent_jobs = conf.query.auto_compaction_max_concurrent_jobs.max(1);
+        Arc::new(Self {
+            session_mgr,
+            interval: Duration::from_secs(conf.query.auto_compaction_interval_secs),
+            jobs: Arc::new(Semaphore::new(max_concurrent_jobs as usize)),
+            compacting: Mutex::new(HashSet::new()),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.interval.is_zero()
+    }
+
+    pub fn start(self: Arc<Self>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let runtime = self.session_mgr.get_storage_runtime();
+        runtime.inner().spawn(async move {
+            let mut intv = interval_at(Instant::now() + self.interval, self.interval);
+            loop {
+                intv.tick().await;
+                if let Err(cause) = self.clone().schedule().await {
+                    tracing::warn!("auto compaction: failed to schedule, {}", cause);
+                }
+            }
+        });
+    }
+
+    /// Checks the tables of all the databases, and compacts the ones that need compaction.
+    ///
+    /// Waits for a free job slot before starting each compaction, so that the tables are
+    /// not checked again until the compactions of this round have been started.
+    async fn schedule(self: Arc<Self>) -> Result<()> {
+        let session = self.session_mgr.create_session(SessionType::Dummy).await?;
+        let ctx = session.create_query_context().await?;
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+
+        for db in catalog.list_databases(&tenant).await? {
+            for table in catalog.list_tables(&tenant, db.name()).await? {
+                let table_id = table.get_id();
+                if self.compacting.lock().contains(&table_id) {
+                    continue;
+                }
+                let needs_compaction = match FuseTable::try_from_table(table.as_ref()) {
+                    Ok(tbl) if tbl.is_auto_compaction_enabled() => {
+                        match tbl.needs_compaction(ctx.as_ref()).await {
+                            Ok(v) => v,
+                            Err(cause) => {
+                                tracing::warn!(
+                                    "auto compaction: failed to check table {}.{}, {}",
+                                    db.name(),
+                                    table.name(),
+                                    cause
+                                );
+                                false
+                            }
+                        }
+                    }
+                    _ => false,
+                };
+                if !needs_compaction {
+                    continue;
+                }
+
+                let permit = self
+                    .jobs
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .map_err(|e| ErrorCode::TokioError(e.to_string()))?;
+                self.compacting.lock().insert(table_id);
+
+                let this = self.clone();
+                let db_name = db.name().to_string();
+                let runtime = self.session_mgr.get_storage_runtime();
+                runtime.inner().spawn(async move {
+                    if let Err(cause) = this.compact(&db_name, table.as_ref()).await {
+                        tracing::warn!(
+                            "auto compaction: failed to compact table {}.{}, {}",
+                            db_name,
+                            table.name(),
+                            cause
+                        );
+                    }
+                    this.compacting.lock().remove(&table_id);
+                    drop(permit);
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn compact(&self, db_name: &str, table: &dyn Table) -> Result<()> {
+        let session = self.session_mgr.create_session(SessionType::Dummy).await?;
+        let ctx = session.create_query_context().await?;
+        let plan = OptimizeTablePlan {
+            catalog: CATALOG_DEFAULT.to_string(),
+            database: db_name.to_string(),
+            table: table.name().to_string(),
+            action: OptimizeTableAction::Compact,
+            dry_run: false,
+        };
+        tracing::info!(
+            "auto compaction: compacting table {}.{}",
+            db_name,
+            table.name()
+        );
+        table.compact(ctx, plan).await
+    }
+}
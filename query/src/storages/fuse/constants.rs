@@ -12,14 +12,33 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+pub const FUSE_OPT_KEY_AUTO_COMPACTION: &str = "auto_compaction";
+pub const FUSE_OPT_KEY_AUTO_COMPACTION_SEGMENT_THRESHOLD: &str =
+    "auto_compaction_segment_threshold";
+pub const FUSE_OPT_KEY_AUTO_COMPACTION_SMALL_BLOCK_RATIO: &str =
+    "auto_compaction_small_block_ratio";
 pub const FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD: &str = "block_size_threshold";
 pub const FUSE_OPT_KEY_BLOCK_PER_SEGMENT: &str = "block_per_segment";
+pub const FUSE_OPT_KEY_BLOOM_INDEX_COLUMNS: &str = "bloom_index_columns";
+pub const FUSE_OPT_KEY_DATA_RETENTION_PERIOD_IN_HOURS: &str = "data_retention_period_in_hours";
+pub const FUSE_OPT_KEY_RECLUSTER_BLOCK_SIZE: &str = "recluster_block_size";
 pub const FUSE_OPT_KEY_ROW_PER_BLOCK: &str = "row_per_block";
 
+pub const FUSE_SCHEMA_META_KEY_COLUMN_IDS: &str = "fuse.column_ids";
+pub const FUSE_SCHEMA_META_KEY_DEFAULT_VALUES: &str = "fuse.default_values";
+pub const FUSE_SCHEMA_META_KEY_LEAF_COLUMN_IDS: &str = "fuse.leaf_column_ids";
+pub const FUSE_SCHEMA_META_KEY_NEXT_COLUMN_ID: &str = "fuse.next_column_id";
+pub const FUSE_SCHEMA_META_KEY_NEXT_LEAF_COLUMN_ID: &str = "fuse.next_leaf_column_id";
+
 pub const FUSE_TBL_BLOCK_PREFIX: &str = "_b";
 pub const FUSE_TBL_SEGMENT_PREFIX: &str = "_sg";
 pub const FUSE_TBL_SNAPSHOT_PREFIX: &str = "_ss";
 
+pub const DEFAULT_AUTO_COMPACTION_SEGMENT_THRESHOLD: usize = 10;
+// percentage of the blocks that are smaller than `row_per_block`
+pub const DEFAULT_AUTO_COMPACTION_SMALL_BLOCK_RATIO: u64 = 50;
 pub const DEFAULT_BLOCK_PER_SEGMENT: usize = 1000;
 pub const DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD: usize = 100 * 1024 * 1024;
+// in-memory size of the blocks that a `RECLUSTER` without `FINAL` re-sorts at most
+pub const DEFAULT_RECLUSTER_BLOCK_SIZE: usize = 1024 * 1024 * 1024;
 pub const DEFAULT_ROW_PER_BLOCK: usize = 1000 * 1000;
@@ -22,6 +22,7 @@ use common_planners::PartInfo;
 use common_planners::PartInfoPtr;
 
 use crate::storages::fuse::meta::Compression;
+use crate::storages::index::ColumnStatistics;
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct ColumnMeta {
@@ -48,6 +49,8 @@ pub struct FusePartInfo {
     pub format_version: u64,
     pub nums_rows: usize,
     pub columns_meta: HashMap<usize, ColumnMeta>,
+    /// The statistics of the columns, used to prune the partition by runtime filters
+    pub columns_stats: HashMap<usize, ColumnStatistics>,
     pub compression: Compression,
 }
 
@@ -71,12 +74,14 @@ impl FusePartInfo {
         format_version: u64,
         rows_count: u64,
         columns_meta: HashMap<usize, ColumnMeta>,
+        columns_stats: HashMap<usize, ColumnStatistics>,
         compression: Compression,
     ) -> Arc<Box<dyn PartInfo>> {
         Arc::new(Box::new(FusePartInfo {
             location,
             format_version,
             columns_meta,
+            columns_stats,
             nums_rows: rows_count as usize,
             compression,
         }))
@@ -19,20 +19,24 @@ use std::sync::Arc;
 
 use common_cache::Cache;
 use common_datablocks::DataBlock;
+use common_datavalues::DataField;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_meta_types::MatchSeq;
 use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::MergeIntoPlan;
 use common_planners::OptimizeTablePlan;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use futures::StreamExt;
@@ -45,13 +49,17 @@ use crate::sql::OPT_KEY_DATABASE_ID;
 use crate::sql::OPT_KEY_LEGACY_SNAPSHOT_LOC;
 use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
 use crate::storages::fuse::io::write_meta;
+use crate::storages::fuse::io::BloomIndexWriter;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::ClusterKey;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::Statistics as FuseStatistics;
 use crate::storages::fuse::meta::TableSnapshot;
 use crate::storages::fuse::meta::Versioned;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::FUSE_OPT_KEY_BLOOM_INDEX_COLUMNS;
+use crate::storages::index::StatisticsOfColumns;
 use crate::storages::NavigationPoint;
 use crate::storages::StorageContext;
 use crate::storages::StorageDescription;
@@ -103,6 +111,27 @@ impl FuseTable {
         &self.meta_location_generator
     }
 
+    pub fn column_ids(&self) -> Result<ColumnIds> {
+        ColumnIds::from_schema(&self.table_info.schema())
+    }
+
+    /// Returns the writer of bloom filter indexes, if the table option `bloom_index_columns`
+    /// lists any column that could be indexed.
+    pub fn bloom_index_writer(&self, ctx: &QueryContext) -> Result<Option<BloomIndexWriter>> {
+        match self
+            .table_info
+            .options()
+            .get(FUSE_OPT_KEY_BLOOM_INDEX_COLUMNS)
+        {
+            None => Ok(None),
+            Some(columns) => BloomIndexWriter::try_create(
+                Arc::new(ctx.clone()),
+                &self.table_info.schema(),
+                columns,
+            ),
+        }
+    }
+
     pub fn parse_storage_prefix(table_info: &TableInfo) -> Result<String> {
         let table_id = table_info.ident.table_id;
         let db_id = table_info
@@ -178,6 +207,7 @@ impl FuseTable {
         catalog_name: &str,
         snapshot: &TableSnapshot,
         meta: &mut TableMeta,
+        copied_files: &Option<UpsertTableCopiedFileReq>,
     ) -> Result<()> {
         let uuid = snapshot.snapshot_id;
         let snapshot_loc = self
@@ -198,6 +228,8 @@ impl FuseTable {
             table_id,
             seq: MatchSeq::Exact(table_version),
             new_table_meta: meta.clone(),
+            copied_files: copied_files.clone(),
+            truncate_copied_files: false,
         };
 
         let catalog = ctx.get_catalog(catalog_name)?;
@@ -288,6 +320,7 @@ impl Table for FuseTable {
             catalog_name,
             &new_snapshot,
             &mut new_table_meta,
+            &None,
         )
         .await
     }
@@ -332,10 +365,50 @@ impl Table for FuseTable {
             catalog_name,
             &new_snapshot,
             &mut new_table_meta,
+            &None,
         )
         .await
     }
 
+    async fn add_column(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        field: DataField,
+        comment: String,
+    ) -> Result<()> {
+        self.do_add_column(ctx, catalog_name, field, comment).await
+    }
+
+    async fn drop_column(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        column: &str,
+    ) -> Result<()> {
+        self.do_drop_column(ctx, catalog_name, column).await
+    }
+
+    async fn rename_column(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        column: &str,
+        new_column: &str,
+    ) -> Result<()> {
+        self.do_rename_column(ctx, catalog_name, column, new_column)
+            .await
+    }
+
+    async fn recluster(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        is_final: bool,
+    ) -> Result<()> {
+        self.do_recluster(ctx, catalog_name, is_final).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_read_partitions", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn read_partitions(
         &self,
@@ -391,6 +464,7 @@ impl Table for FuseTable {
         ctx: Arc<QueryContext>,
         catalog_name: &str,
         operations: Vec<DataBlock>,
+        copied_files: Option<UpsertTableCopiedFileReq>,
         overwrite: bool,
     ) -> Result<()> {
         self.check_mutable()?;
@@ -399,8 +473,14 @@ impl Table for FuseTable {
             .iter()
             .map(AppendOperationLogEntry::try_from)
             .collect::<Result<Vec<AppendOperationLogEntry>>>()?;
-        self.do_commit(ctx, catalog_name, append_log_entries, overwrite)
-            .await
+        self.do_commit(
+            ctx,
+            catalog_name,
+            append_log_entries,
+            copied_files,
+            overwrite,
+        )
+        .await
     }
 
     #[tracing::instrument(level = "debug", name = "fuse_table_truncate", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
@@ -420,6 +500,14 @@ impl Table for FuseTable {
         self.do_gc(&ctx, keep_last_snapshot).await
     }
 
+    async fn optimize_dry_run(
+        &self,
+        ctx: Arc<QueryContext>,
+        keep_last_snapshot: bool,
+    ) -> Result<Vec<String>> {
+        self.do_gc_dry_run(&ctx, keep_last_snapshot).await
+    }
+
     async fn statistics(&self, _ctx: Arc<QueryContext>) -> Result<Option<TableStatistics>> {
         let s = &self.table_info.meta.statistics;
         Ok(Some(TableStatistics {
@@ -430,6 +518,13 @@ impl Table for FuseTable {
         }))
     }
 
+    async fn column_statistics(&self, ctx: Arc<QueryContext>) -> Result<StatisticsOfColumns> {
+        let snapshot = self.read_table_snapshot(ctx.as_ref()).await?;
+        Ok(snapshot
+            .map(|s| s.summary.col_stats.clone())
+            .unwrap_or_default())
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_navigate_to", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn navigate_to(
         &self,
@@ -451,6 +546,21 @@ impl Table for FuseTable {
         self.do_delete(ctx, &delete_plan).await
     }
 
+    #[tracing::instrument(level = "debug", name = "fuse_table_update", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn update(&self, ctx: Arc<QueryContext>, update_plan: UpdatePlan) -> Result<()> {
+        self.do_update(ctx, &update_plan).await
+    }
+
+    #[tracing::instrument(level = "debug", name = "fuse_table_merge_into", skip(self, ctx, source), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn merge_into(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: MergeIntoPlan,
+        source: SendableDataBlockStream,
+    ) -> Result<()> {
+        self.do_merge_into(ctx, &plan, source).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_compact", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn compact(&self, ctx: Arc<QueryContext>, plan: OptimizeTablePlan) -> Result<()> {
         self.do_compact(ctx, &plan).await
@@ -43,6 +43,14 @@ impl TableMetaLocationGenerator {
         &self.prefix
     }
 
+    /// Returns true if the file located at `location` belongs to the table, rather than the
+    /// table that it is cloned from.
+    pub fn owns(&self, location: &str) -> bool {
+        location
+            .strip_prefix(&self.prefix)
+            .map_or(false, |rest| rest.starts_with('/'))
+    }
+
     pub fn gen_block_location(&self) -> String {
         let part_uuid = Uuid::new_v4().simple().to_string();
         format!(
@@ -54,6 +62,15 @@ impl TableMetaLocationGenerator {
         )
     }
 
+    /// The bloom filter index of a block is kept next to the block, e.g. the index of
+    /// block `_b/<uuid>_v0.parquet` is located at `_b/<uuid>_v0_bloom.parquet`.
+    pub fn gen_bloom_index_location(block_location: &str) -> String {
+        let stem = block_location
+            .strip_suffix(".parquet")
+            .unwrap_or(block_location);
+        format!("{}_bloom.parquet", stem)
+    }
+
     pub fn gen_segment_info_location(&self) -> String where {
         let segment_uuid = Uuid::new_v4().simple().to_string();
         format!(
@@ -18,6 +18,7 @@ pub mod retry;
 mod write;
 
 pub use locations::TableMetaLocationGenerator;
+pub use read::read_bloom_index;
 pub use read::BlockReader;
 pub use read::MetaReaders;
 pub use read::SegmentInfoReader;
@@ -27,5 +28,6 @@ pub use write::write_meta;
 pub use write::BlockCompactor;
 pub use write::BlockStreamWriter;
 pub use write::BlockWriter;
+pub use write::BloomIndexWriter;
 pub use write::SegmentInfoStream;
 pub use write::SegmentWriter;
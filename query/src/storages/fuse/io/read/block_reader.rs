@@ -27,7 +27,10 @@ use common_arrow::parquet::read::BasicDecompressor;
 use common_arrow::parquet::read::PageMetaData;
 use common_arrow::parquet::read::PageReader;
 use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::PartInfoPtr;
@@ -46,6 +49,7 @@ use crate::storages::fuse::fuse_part::FusePartInfo;
 use crate::storages::fuse::io::retry;
 use crate::storages::fuse::io::retry::Retryable;
 use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::Compression;
 
 #[derive(Clone)]
@@ -55,6 +59,9 @@ pub struct BlockReader {
     arrow_schema: Arc<Schema>,
     projected_schema: DataSchemaRef,
     parquet_schema_descriptor: SchemaDescriptor,
+    column_ids: ColumnIds,
+    /// Values of the projected columns, for the blocks written before the columns were added
+    default_values: Vec<DataValue>,
 }
 
 impl BlockReader {
@@ -64,6 +71,15 @@ impl BlockReader {
         projection: Vec<usize>,
     ) -> Result<Arc<BlockReader>> {
         let projected_schema = DataSchemaRef::new(schema.project(&projection));
+        let column_ids = ColumnIds::from_schema(&schema)?;
+        // columns added by `ALTER TABLE ... ADD COLUMN` take the values evaluated by then
+        let default_values = projection
+            .iter()
+            .map(|idx| match column_ids.default_value(*idx) {
+                Some(value) => value.clone(),
+                None => schema.field(*idx).data_type().default_value(),
+            })
+            .collect();
 
         let arrow_schema = schema.to_arrow();
         let parquet_schema_descriptor = to_parquet_schema(&arrow_schema)?;
@@ -73,6 +89,8 @@ impl BlockReader {
             projected_schema,
             parquet_schema_descriptor,
             arrow_schema: Arc::new(arrow_schema),
+            column_ids,
+            default_values,
         }))
     }
 
@@ -112,8 +130,11 @@ impl BlockReader {
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read_with_block_meta(&self, meta: &BlockMeta) -> Result<DataBlock> {
         let (num_rows, columns_array_iter) = self.read_columns_with_block_meta(meta).await?;
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-        self.try_next_block(&mut deserializer)
+        let missing = self.missing_columns(|index| {
+            meta.col_metas
+                .contains_key(&self.column_ids.leaf_column_id(index))
+        });
+        self.build_block(num_rows, columns_array_iter, &missing)
     }
     // TODO refine these
 
@@ -126,10 +147,11 @@ impl BlockReader {
         let mut column_chunk_futs = Vec::with_capacity(num_cols);
         let mut col_idx = Vec::with_capacity(num_cols);
         for index in &self.projection {
-            let column_meta = meta
-                .col_metas
-                .get(&(*index as u32))
-                .ok_or_else(|| ErrorCode::LogicalError(format!("index out of bound {}", index)))?;
+            let column_meta = match meta.col_metas.get(&self.column_ids.leaf_column_id(*index)) {
+                Some(column_meta) => column_meta,
+                // the column is added after the block was written
+                None => continue,
+            };
             let column_reader = self.operator.object(&meta.location.0);
             let fut = async move {
                 let column_chunk = column_reader
@@ -139,7 +161,7 @@ impl BlockReader {
             }
             .instrument(debug_span!("read_col_chunk"));
             column_chunk_futs.push(fut);
-            col_idx.push(index);
+            col_idx.push((index, column_meta));
         }
 
         let chunks = futures::stream::iter(column_chunk_futs)
@@ -149,13 +171,10 @@ impl BlockReader {
 
         let mut columns_array_iter = Vec::with_capacity(num_cols);
         for (i, column_chunk) in chunks.into_iter().enumerate() {
-            let idx = *col_idx[i];
+            let (idx, column_meta) = col_idx[i];
+            let idx = *idx;
             let field = self.arrow_schema.fields[idx].clone();
             let column_descriptor = &self.parquet_schema_descriptor.columns()[idx];
-            let column_meta = &meta
-                .col_metas
-                .get(&(i as u32))
-                .ok_or_else(|| ErrorCode::LogicalError(format!("index out of bound {}", i)))?;
             let part_col_meta =
                 ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values);
             columns_array_iter.push(Self::to_array_iter(
@@ -180,7 +199,11 @@ impl BlockReader {
         let mut column_chunk_futs = Vec::with_capacity(num_cols);
         let mut col_idx = Vec::with_capacity(num_cols);
         for index in &self.projection {
-            let column_meta = &part.columns_meta[index];
+            let column_meta = match part.columns_meta.get(index) {
+                Some(column_meta) => column_meta,
+                // the column is added after the block was written
+                None => continue,
+            };
             let column_reader = self.operator.object(&part.location);
             let fut = async move {
                 let column_chunk =
@@ -218,18 +241,23 @@ impl BlockReader {
     }
 
     pub fn deserialize(&self, part: PartInfoPtr, chunks: Vec<Vec<u8>>) -> Result<DataBlock> {
-        if self.projection.len() != chunks.len() {
+        let part = FusePartInfo::from_part(&part)?;
+        let missing = self.missing_columns(|index| part.columns_meta.contains_key(&index));
+        if self.projection.len() != chunks.len() + missing.len() {
             return Err(ErrorCode::LogicalError(
                 "Columns chunk len must be equals projections len.",
             ));
         }
 
-        let part = FusePartInfo::from_part(&part)?;
-        let mut columns_array_iter = Vec::with_capacity(self.projection.len());
+        let mut columns_array_iter = Vec::with_capacity(chunks.len());
 
         let num_rows = part.nums_rows;
-        for (index, column_chunk) in chunks.into_iter().enumerate() {
-            let index = self.projection[index];
+        let present_columns = self
+            .projection
+            .iter()
+            .filter(|index| part.columns_meta.contains_key(*index));
+        for (index, column_chunk) in present_columns.zip(chunks.into_iter()) {
+            let index = *index;
             let field = self.arrow_schema.fields[index].clone();
             let column_descriptor = &self.parquet_schema_descriptor.columns()[index];
             let column_meta = &part.columns_meta[&index];
@@ -243,9 +271,7 @@ impl BlockReader {
             )?);
         }
 
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-
-        self.try_next_block(&mut deserializer)
+        self.build_block(num_rows, columns_array_iter, &missing)
     }
 
     pub async fn read_columns_data(&self, part: PartInfoPtr) -> Result<Vec<Vec<u8>>> {
@@ -253,7 +279,11 @@ impl BlockReader {
         let mut join_handlers = Vec::with_capacity(self.projection.len());
 
         for index in &self.projection {
-            let column_meta = &part.columns_meta[index];
+            let column_meta = match part.columns_meta.get(index) {
+                Some(column_meta) => column_meta,
+                // the column is added after the block was written
+                None => continue,
+            };
 
             join_handlers.push(Self::read_column(
                 self.operator.object(&part.location),
@@ -303,9 +333,67 @@ impl BlockReader {
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read(&self, part: PartInfoPtr) -> Result<DataBlock> {
+        let missing = {
+            let part = FusePartInfo::from_part(&part)?;
+            self.missing_columns(|index| part.columns_meta.contains_key(&index))
+        };
         let (num_rows, columns_array_iter) = self.read_columns(part).await?;
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-        self.try_next_block(&mut deserializer)
+        self.build_block(num_rows, columns_array_iter, &missing)
+    }
+
+    /// Returns the positions (in the projection) of the columns that the block does not contain.
+    fn missing_columns(&self, exists: impl Fn(usize) -> bool) -> Vec<usize> {
+        self.projection
+            .iter()
+            .enumerate()
+            .filter(|(_, index)| !exists(**index))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    fn build_block(
+        &self,
+        num_rows: usize,
+        columns_array_iter: Vec<ArrayIter<'static>>,
+        missing: &[usize],
+    ) -> Result<DataBlock> {
+        if missing.is_empty() {
+            let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
+            return self.try_next_block(&mut deserializer);
+        }
+
+        let mut read_columns = if columns_array_iter.is_empty() {
+            vec![]
+        } else {
+            let present = (0..self.projection.len())
+                .filter(|pos| !missing.contains(pos))
+                .collect::<Vec<_>>();
+            let present_schema = DataSchemaRef::new(self.projected_schema.project(&present));
+            let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
+            let block = match deserializer.next() {
+                None => Err(ErrorCode::ParquetError("fail to get a chunk")),
+                Some(Err(cause)) => Err(ErrorCode::from(cause)),
+                Some(Ok(chunk)) => DataBlock::from_chunk(&present_schema, &chunk),
+            }?;
+            block.columns().to_vec()
+        }
+        .into_iter();
+
+        let mut columns: Vec<ColumnRef> = Vec::with_capacity(self.projection.len());
+        for pos in 0..self.projection.len() {
+            if missing.contains(&pos) {
+                let data_type = self.projected_schema.field(pos).data_type();
+                let column =
+                    data_type.create_constant_column(&self.default_values[pos], num_rows)?;
+                columns.push(column.convert_full_column());
+            } else {
+                let column = read_columns.next().ok_or_else(|| {
+                    ErrorCode::LogicalError("Columns chunk len must be equals projections len.")
+                })?;
+                columns.push(column);
+            }
+        }
+        Ok(DataBlock::create(self.projected_schema.clone(), columns))
     }
 
     fn try_next_block(&self, deserializer: &mut RowGroupDeserializer) -> Result<DataBlock> {
@@ -0,0 +1,45 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_metadata_async;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::ParquetSourceBuilder;
+use common_streams::Source;
+use opendal::Operator;
+
+/// Reads the bloom filter index of a block, i.e. a parquet file of one row, of which
+/// each column is the bloom filter of an indexed column.
+pub async fn read_bloom_index(data_accessor: &Operator, location: &str) -> Result<DataBlock> {
+    let object = data_accessor.object(location);
+    let mut reader = object.seekable_reader(..);
+    let meta = read_metadata_async(&mut reader)
+        .await
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    let arrow_schema = infer_schema(&meta).map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    let schema = Arc::new(DataSchema::from(arrow_schema));
+
+    let mut source = ParquetSourceBuilder::create(schema.clone())
+        .meta_data(Some(meta))
+        .build(reader)?;
+    match source.read().await? {
+        Some(block) => Ok(block),
+        None => Ok(DataBlock::empty_with_schema(schema)),
+    }
+}
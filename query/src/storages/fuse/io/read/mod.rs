@@ -13,11 +13,13 @@
 // limitations under the License.
 
 mod block_reader;
+mod bloom_index_reader;
 mod cached_reader;
 mod meta_readers;
 mod versioned_reader;
 
 pub use block_reader::BlockReader;
+pub use bloom_index_reader::read_bloom_index;
 pub use meta_readers::MetaReaders;
 pub use meta_readers::SegmentInfoReader;
 pub use meta_readers::TableSnapshotReader;
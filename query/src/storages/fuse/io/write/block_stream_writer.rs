@@ -28,7 +28,9 @@ use opendal::Operator;
 use super::block_writer;
 use crate::pipelines::transforms::ExpressionExecutor;
 use crate::sessions::QueryContext;
+use crate::storages::fuse::io::BloomIndexWriter;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::Statistics;
 use crate::storages::fuse::operations::column_metas;
@@ -46,6 +48,8 @@ pub struct BlockStreamWriter {
     statistics_accumulator: Option<StatisticsAccumulator>,
     meta_locations: TableMetaLocationGenerator,
     cluster_key_info: Option<ClusterKeyInfo>,
+    column_ids: ColumnIds,
+    bloom_index_writer: Option<BloomIndexWriter>,
     ctx: Arc<QueryContext>,
 }
 
@@ -57,6 +61,8 @@ impl BlockStreamWriter {
         block_per_segment: usize,
         meta_locations: TableMetaLocationGenerator,
         cluster_key_info: Option<ClusterKeyInfo>,
+        column_ids: ColumnIds,
+        bloom_index_writer: Option<BloomIndexWriter>,
     ) -> Result<SegmentInfoStream> {
         // filter out empty blocks
         let block_stream =
@@ -77,6 +83,8 @@ impl BlockStreamWriter {
             meta_locations,
             ctx,
             cluster_key_info,
+            column_ids,
+            bloom_index_writer,
         )?;
         let segments = Self::transform(Box::pin(block_stream), block_writer);
 
@@ -88,6 +96,8 @@ impl BlockStreamWriter {
         meta_locations: TableMetaLocationGenerator,
         ctx: Arc<QueryContext>,
         cluster_key_info: Option<ClusterKeyInfo>,
+        column_ids: ColumnIds,
+        bloom_index_writer: Option<BloomIndexWriter>,
     ) -> Result<Self> {
         let data_accessor = ctx.get_storage_operator()?;
         Ok(Self {
@@ -97,6 +107,8 @@ impl BlockStreamWriter {
             statistics_accumulator: None,
             meta_locations,
             cluster_key_info,
+            column_ids,
+            bloom_index_writer,
             ctx,
         })
     }
@@ -184,13 +196,20 @@ impl BlockStreamWriter {
             }
         }
 
-        let mut acc = self.statistics_accumulator.take().unwrap_or_default();
+        let mut acc = self
+            .statistics_accumulator
+            .take()
+            .unwrap_or_else(|| StatisticsAccumulator::with_column_ids(self.column_ids.clone()));
         let partial_acc = acc.begin(&block, cluster_stats)?;
         let location = self.meta_locations.gen_block_location();
+        let bloom_filter_index_location = match &self.bloom_index_writer {
+            Some(writer) => Some(writer.write(&self.data_accessor, &block, &location).await?),
+            None => None,
+        };
         let (file_size, file_meta_data) =
             block_writer::write_block(block, &self.data_accessor, &location).await?;
         let col_metas = column_metas(&file_meta_data)?;
-        acc = partial_acc.end(file_size, location, col_metas);
+        acc = partial_acc.end(file_size, location, col_metas, bloom_filter_index_location);
         self.number_of_blocks_accumulated += 1;
         if self.number_of_blocks_accumulated >= self.num_block_threshold {
             let summary = acc.summary()?;
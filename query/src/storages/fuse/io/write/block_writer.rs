@@ -22,36 +22,61 @@ use opendal::Operator;
 
 use crate::storages::fuse::io::retry;
 use crate::storages::fuse::io::retry::Retryable;
+use crate::storages::fuse::io::BloomIndexWriter;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::Versioned;
 use crate::storages::fuse::operations::util;
 use crate::storages::fuse::statistics::accumulator;
+use crate::storages::index::ClusterStatistics;
 
 pub struct BlockWriter<'a> {
     location_generator: &'a TableMetaLocationGenerator,
     data_accessor: &'a Operator,
+    column_ids: &'a ColumnIds,
+    bloom_index_writer: &'a Option<BloomIndexWriter>,
 }
 
 impl<'a> BlockWriter<'a> {
     pub fn new(
         data_accessor: &'a Operator,
         location_generator: &'a TableMetaLocationGenerator,
+        column_ids: &'a ColumnIds,
+        bloom_index_writer: &'a Option<BloomIndexWriter>,
     ) -> Self {
         Self {
             location_generator,
             data_accessor,
+            column_ids,
+            bloom_index_writer,
         }
     }
     pub async fn write(&self, block: DataBlock) -> Result<BlockMeta> {
+        let cluster_stats = None; // TODO confirm this with zhyass
+        self.write_with_cluster_stats(block, cluster_stats).await
+    }
+
+    /// Writes the block, along with the statistics of the cluster key of it, which should
+    /// have been evaluated before the cluster key columns being removed from the block.
+    pub async fn write_with_cluster_stats(
+        &self,
+        block: DataBlock,
+        cluster_stats: Option<ClusterStatistics>,
+    ) -> Result<BlockMeta> {
         let location = self.location_generator.gen_block_location();
         let data_accessor = &self.data_accessor;
         let row_count = block.num_rows() as u64;
         let block_size = block.memory_size() as u64;
         let col_stats = accumulator::columns_statistics(&block)?;
+        let bloom_filter_index_location = match self.bloom_index_writer {
+            Some(writer) => Some(writer.write(data_accessor, &block, &location).await?),
+            None => None,
+        };
         let (file_size, file_meta_data) = write_block(block, data_accessor, &location).await?;
         let col_metas = util::column_metas(&file_meta_data)?;
-        let cluster_stats = None; // TODO confirm this with zhyass
+        let col_stats = self.column_ids.index_to_id(col_stats);
+        let col_metas = self.column_ids.leaf_index_to_id(col_metas);
         let location = (location, DataBlock::VERSION);
         let block_meta = BlockMeta::new(
             row_count,
@@ -61,6 +86,7 @@ impl<'a> BlockWriter<'a> {
             col_metas,
             cluster_stats,
             location,
+            bloom_filter_index_location,
         );
         Ok(block_meta)
     }
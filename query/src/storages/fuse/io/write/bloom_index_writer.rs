@@ -0,0 +1,106 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use opendal::Operator;
+
+use super::block_writer::write_block;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::meta::ColumnIds;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::Versioned;
+use crate::storages::index::BloomFilter;
+use crate::storages::index::BloomFilterIndexer;
+
+/// Builds the bloom filter index of the blocks being written, and writes it next to the block.
+///
+/// The bloom filters are named after the ids of the indexed columns, rather than the names,
+/// thus the index of a block is still valid after the columns being renamed or dropped.
+#[derive(Clone)]
+pub struct BloomIndexWriter {
+    ctx: Arc<QueryContext>,
+    /// (index in the table schema, column id) of the indexed columns
+    columns: Vec<(usize, ColumnId)>,
+}
+
+impl BloomIndexWriter {
+    /// Creates the writer of the given columns, which is a comma separated list of column names.
+    ///
+    /// Columns that do not exist, or whose type is not supported by bloom filter, are ignored.
+    /// Returns [None] if none of the columns is indexed.
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        schema: &DataSchema,
+        column_names: &str,
+    ) -> Result<Option<Self>> {
+        let column_ids = ColumnIds::from_schema(schema)?;
+        let mut columns = column_names
+            .split(',')
+            .filter_map(|name| schema.index_of(name.trim()).ok())
+            .filter(|idx| BloomFilter::is_supported_type(schema.field(*idx).data_type()))
+            .map(|idx| (idx, column_ids.column_id(idx)))
+            .collect::<Vec<_>>();
+        columns.sort_unstable();
+        columns.dedup();
+
+        if columns.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Self { ctx, columns }))
+        }
+    }
+
+    /// Builds the bloom filters of the indexed columns of `block`.
+    pub fn build(&self, block: &DataBlock) -> Result<DataBlock> {
+        let fields = self
+            .columns
+            .iter()
+            .map(|(idx, id)| {
+                let field = block.schema().field(*idx);
+                DataField::new(&id.to_string(), field.data_type().clone())
+            })
+            .collect::<Vec<_>>();
+        let columns = self
+            .columns
+            .iter()
+            .map(|(idx, _)| block.column(*idx).clone())
+            .collect::<Vec<_>>();
+        let source = DataBlock::create(DataSchemaRefExt::create(fields), columns);
+
+        let indexer = BloomFilterIndexer::try_create(self.ctx.clone(), &[source])?;
+        Ok(indexer.bloom_block)
+    }
+
+    /// Builds and writes the bloom filter index of the block located at `block_location`.
+    pub async fn write(
+        &self,
+        data_accessor: &Operator,
+        block: &DataBlock,
+        block_location: &str,
+    ) -> Result<Location> {
+        let bloom_block = self.build(block)?;
+        let location = TableMetaLocationGenerator::gen_bloom_index_location(block_location);
+        write_block(bloom_block, data_accessor, &location).await?;
+        Ok((location, DataBlock::VERSION))
+    }
+}
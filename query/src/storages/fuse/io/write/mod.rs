@@ -14,6 +14,7 @@
 
 mod block_stream_writer;
 mod block_writer;
+mod bloom_index_writer;
 mod meta_writer;
 mod segment_writer;
 
@@ -23,5 +24,6 @@ pub use block_stream_writer::BlockStreamWriter;
 pub use block_stream_writer::SegmentInfoStream;
 pub use block_writer::write_block;
 pub use block_writer::BlockWriter;
+pub use bloom_index_writer::BloomIndexWriter;
 pub use meta_writer::write_meta;
 pub use segment_writer::SegmentWriter;
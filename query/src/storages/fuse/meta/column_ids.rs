@@ -0,0 +1,272 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::FUSE_SCHEMA_META_KEY_COLUMN_IDS;
+use crate::storages::fuse::FUSE_SCHEMA_META_KEY_DEFAULT_VALUES;
+use crate::storages::fuse::FUSE_SCHEMA_META_KEY_LEAF_COLUMN_IDS;
+use crate::storages::fuse::FUSE_SCHEMA_META_KEY_NEXT_COLUMN_ID;
+use crate::storages::fuse::FUSE_SCHEMA_META_KEY_NEXT_LEAF_COLUMN_ID;
+
+/// Maps the fields of a table schema to the ids of the columns stored in the blocks.
+///
+/// The column statistics of a block are keyed by the id of the field, while the column metas
+/// are keyed by the id of the leaf column in the parquet file, a nested field (e.g. a tuple)
+/// is stored as several leaf columns. Until a column is dropped, the ids are the indices of
+/// the fields and the leaf columns, and nothing is recorded. Once a column is dropped, the ids
+/// of the remaining columns no longer match their indices, and the mapping is kept in the
+/// metadata of the schema, which is persisted with both the table meta and the snapshots.
+///
+/// The values of the columns added by `ALTER TABLE ... ADD COLUMN` are kept in the metadata of
+/// the schema as well, which are evaluated once the column is added, and fill the column of the
+/// blocks written before.
+///
+/// The default value maps nothing, i.e. column ids are column indices.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnIds {
+    ids: Vec<ColumnId>,
+    next_id: ColumnId,
+    leaf_ids: Vec<ColumnId>,
+    next_leaf_id: ColumnId,
+    // number of the leaf columns of each field
+    num_leaves: Vec<usize>,
+    default_values: BTreeMap<ColumnId, DataValue>,
+}
+
+impl ColumnIds {
+    pub fn from_schema(schema: &DataSchema) -> Result<Self> {
+        let num_fields = schema.num_fields();
+        let num_leaves = schema
+            .fields()
+            .iter()
+            .map(|f| Self::num_leaves_of(f.data_type()))
+            .collect::<Vec<_>>();
+        let total_leaves = num_leaves.iter().sum::<usize>();
+
+        let meta = schema.meta();
+        let ids = Self::parse_ids(meta, FUSE_SCHEMA_META_KEY_COLUMN_IDS, num_fields)?;
+        let next_id = Self::parse_id(meta, FUSE_SCHEMA_META_KEY_NEXT_COLUMN_ID, num_fields)?;
+        let leaf_ids = Self::parse_ids(meta, FUSE_SCHEMA_META_KEY_LEAF_COLUMN_IDS, total_leaves)?;
+        let next_leaf_id =
+            Self::parse_id(meta, FUSE_SCHEMA_META_KEY_NEXT_LEAF_COLUMN_ID, total_leaves)?;
+
+        let default_values = match meta.get(FUSE_SCHEMA_META_KEY_DEFAULT_VALUES) {
+            None => BTreeMap::new(),
+            Some(v) => serde_json::from_str(v).map_err(|e| {
+                ErrorCode::StorageOther(format!("invalid default values [{}] of schema, {}", v, e))
+            })?,
+        };
+
+        Ok(Self {
+            ids,
+            next_id,
+            leaf_ids,
+            next_leaf_id,
+            num_leaves,
+            default_values,
+        })
+    }
+
+    fn parse_ids(
+        meta: &BTreeMap<String, String>,
+        key: &str,
+        expected: usize,
+    ) -> Result<Vec<ColumnId>> {
+        let ids = match meta.get(key) {
+            None => (0..expected as ColumnId).collect::<Vec<_>>(),
+            Some(v) if v.is_empty() => vec![],
+            Some(v) => v
+                .split(',')
+                .map(|id| id.parse::<ColumnId>())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    ErrorCode::StorageOther(format!("invalid {} [{}] of schema, {}", key, v, e))
+                })?,
+        };
+
+        if ids.len() != expected {
+            return Err(ErrorCode::StorageOther(format!(
+                "{} of schema mismatch, expects {} ids, but got {}",
+                key,
+                expected,
+                ids.len()
+            )));
+        }
+        Ok(ids)
+    }
+
+    fn parse_id(meta: &BTreeMap<String, String>, key: &str, default: usize) -> Result<ColumnId> {
+        match meta.get(key) {
+            None => Ok(default as ColumnId),
+            Some(v) => v.parse::<ColumnId>().map_err(|e| {
+                ErrorCode::StorageOther(format!("invalid {} [{}] of schema, {}", key, v, e))
+            }),
+        }
+    }
+
+    /// Returns the number of the leaf columns that a field of `data_type` is stored as.
+    fn num_leaves_of(data_type: &DataTypeImpl) -> usize {
+        match data_type {
+            DataTypeImpl::Nullable(t) => Self::num_leaves_of(t.inner_type()),
+            DataTypeImpl::Array(t) => Self::num_leaves_of(t.inner_type()),
+            DataTypeImpl::Struct(t) => t.types().iter().map(Self::num_leaves_of).sum(),
+            _ => 1,
+        }
+    }
+
+    /// Returns true if the id of each column equals to its index in the schema, and no
+    /// column has ever been dropped, i.e. the blocks can be accessed by column index directly.
+    pub fn is_identity(&self) -> bool {
+        fn is_identity(ids: &[ColumnId], next_id: ColumnId) -> bool {
+            next_id as usize == ids.len()
+                && ids
+                    .iter()
+                    .enumerate()
+                    .all(|(idx, id)| idx as ColumnId == *id)
+        }
+        is_identity(&self.ids, self.next_id) && is_identity(&self.leaf_ids, self.next_leaf_id)
+    }
+
+    pub fn column_id(&self, index: usize) -> ColumnId {
+        self.ids[index]
+    }
+
+    pub fn leaf_column_id(&self, leaf_index: usize) -> ColumnId {
+        self.leaf_ids[leaf_index]
+    }
+
+    /// Returns the value of the column, which fills the blocks written before the column was
+    /// added, if the column is added with a default value.
+    pub fn default_value(&self, index: usize) -> Option<&DataValue> {
+        self.default_values.get(&self.ids[index])
+    }
+
+    /// Appends a new column, which takes ids that have never been used by this table.
+    pub fn add_column(&mut self, field: &DataField, default_value: Option<DataValue>) {
+        if let Some(value) = default_value {
+            self.default_values.insert(self.next_id, value);
+        }
+        self.ids.push(self.next_id);
+        self.next_id += 1;
+
+        let num_leaves = Self::num_leaves_of(field.data_type());
+        self.leaf_ids
+            .extend(self.next_leaf_id..self.next_leaf_id + num_leaves as ColumnId);
+        self.next_leaf_id += num_leaves as ColumnId;
+        self.num_leaves.push(num_leaves);
+    }
+
+    pub fn drop_column(&mut self, index: usize) {
+        let id = self.ids.remove(index);
+        self.default_values.remove(&id);
+
+        let leaf_start = self.num_leaves[..index].iter().sum::<usize>();
+        let num_leaves = self.num_leaves.remove(index);
+        self.leaf_ids.drain(leaf_start..leaf_start + num_leaves);
+    }
+
+    /// Builds a schema of the given fields, which carries the column ids.
+    pub fn to_schema(&self, fields: Vec<DataField>, schema: &DataSchema) -> Result<DataSchema> {
+        fn join(ids: &[ColumnId]) -> String {
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+
+        let mut meta = schema.meta().clone();
+        meta.remove(FUSE_SCHEMA_META_KEY_COLUMN_IDS);
+        meta.remove(FUSE_SCHEMA_META_KEY_NEXT_COLUMN_ID);
+        meta.remove(FUSE_SCHEMA_META_KEY_LEAF_COLUMN_IDS);
+        meta.remove(FUSE_SCHEMA_META_KEY_NEXT_LEAF_COLUMN_ID);
+        meta.remove(FUSE_SCHEMA_META_KEY_DEFAULT_VALUES);
+        if !self.is_identity() {
+            meta.insert(FUSE_SCHEMA_META_KEY_COLUMN_IDS.to_owned(), join(&self.ids));
+            meta.insert(
+                FUSE_SCHEMA_META_KEY_NEXT_COLUMN_ID.to_owned(),
+                self.next_id.to_string(),
+            );
+            meta.insert(
+                FUSE_SCHEMA_META_KEY_LEAF_COLUMN_IDS.to_owned(),
+                join(&self.leaf_ids),
+            );
+            meta.insert(
+                FUSE_SCHEMA_META_KEY_NEXT_LEAF_COLUMN_ID.to_owned(),
+                self.next_leaf_id.to_string(),
+            );
+        }
+        if !self.default_values.is_empty() {
+            let values = serde_json::to_string(&self.default_values)?;
+            meta.insert(FUSE_SCHEMA_META_KEY_DEFAULT_VALUES.to_owned(), values);
+        }
+        Ok(DataSchema::new_from(fields, meta))
+    }
+
+    /// Re-keys the items (of a block being written) from the column index to the column id,
+    /// e.g. the column statistics.
+    pub fn index_to_id<V>(&self, items: HashMap<ColumnId, V>) -> HashMap<ColumnId, V> {
+        if self.is_identity() {
+            return items;
+        }
+        Self::remap_index_to_id(&self.ids, items)
+    }
+
+    /// Re-keys the items (of a block being written) from the leaf column index to the leaf
+    /// column id, e.g. the column metas.
+    pub fn leaf_index_to_id<V>(&self, items: HashMap<ColumnId, V>) -> HashMap<ColumnId, V> {
+        if self.is_identity() {
+            return items;
+        }
+        Self::remap_index_to_id(&self.leaf_ids, items)
+    }
+
+    /// Re-keys the items (of a block being read) from the column id to the column index,
+    /// the items of dropped columns are discarded.
+    pub fn id_to_index<V: Clone>(&self, items: &HashMap<ColumnId, V>) -> HashMap<ColumnId, V> {
+        Self::remap_id_to_index(&self.ids, items)
+    }
+
+    /// Re-keys the items (of a block being read) from the leaf column id to the leaf column
+    /// index, the items of dropped columns are discarded.
+    pub fn leaf_id_to_index<V: Clone>(&self, items: &HashMap<ColumnId, V>) -> HashMap<ColumnId, V> {
+        Self::remap_id_to_index(&self.leaf_ids, items)
+    }
+
+    fn remap_index_to_id<V>(ids: &[ColumnId], items: HashMap<ColumnId, V>) -> HashMap<ColumnId, V> {
+        items
+            .into_iter()
+            .filter_map(|(idx, v)| ids.get(idx as usize).map(|id| (*id, v)))
+            .collect()
+    }
+
+    fn remap_id_to_index<V: Clone>(
+        ids: &[ColumnId],
+        items: &HashMap<ColumnId, V>,
+    ) -> HashMap<ColumnId, V> {
+        ids.iter()
+            .enumerate()
+            .filter_map(|(idx, id)| items.get(id).map(|v| (idx as ColumnId, v.clone())))
+            .collect()
+    }
+}
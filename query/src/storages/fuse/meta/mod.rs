@@ -13,6 +13,7 @@
 //  limitations under the License.
 //
 
+mod column_ids;
 mod common;
 
 /// Re-exports meta data structures of current version, i.e. v1
@@ -21,6 +22,7 @@ mod v0;
 mod v1;
 mod versions;
 
+pub use column_ids::ColumnIds;
 pub use common::ClusterKey;
 pub use common::ColumnId;
 pub use common::Compression;
@@ -52,6 +52,10 @@ pub struct BlockMeta {
     pub cluster_stats: Option<ClusterStatistics>,
     pub location: Location,
 
+    /// Location of the bloom filter index of the block, if any column is indexed
+    #[serde(default)]
+    pub bloom_filter_index_location: Option<Location>,
+
     /// Compression algo used to compress the columns of blocks
     ///
     /// If not specified, the legacy algo `Lz4` will be used.
@@ -70,6 +74,7 @@ impl BlockMeta {
         col_metas: HashMap<ColumnId, ColumnMeta>,
         cluster_stats: Option<ClusterStatistics>,
         location: Location,
+        bloom_filter_index_location: Option<Location>,
     ) -> Self {
         Self {
             row_count,
@@ -79,6 +84,7 @@ impl BlockMeta {
             col_metas,
             cluster_stats,
             location,
+            bloom_filter_index_location,
             compression: Compression::Lz4Raw,
         }
     }
@@ -124,6 +130,7 @@ impl From<v0::BlockMeta> for BlockMeta {
             col_metas: s.col_metas,
             cluster_stats: None,
             location: (s.location.path, DataBlock::VERSION),
+            bloom_filter_index_location: None,
             compression: Compression::Lz4,
         }
     }
@@ -51,8 +51,9 @@ pub struct TableSnapshot {
 
     /// Pointers to SegmentInfos (may be of different format)
     ///
-    /// We rely on background merge tasks to keep merging segments, so that
-    /// this the size of this vector could be kept reasonable
+    /// We rely on compaction, either `OPTIMIZE TABLE .. COMPACT` or the background
+    /// auto compaction, to keep merging segments, so that the size of this vector
+    /// could be kept reasonable
     pub segments: Vec<Location>,
 
     // The metadata of the cluster keys.
@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod auto_compaction;
 pub mod cache;
 mod constants;
 mod fuse_part;
@@ -23,5 +24,6 @@ pub mod pruning;
 pub mod statistics;
 pub mod table_functions;
 
+pub use auto_compaction::AutoCompactionService;
 pub use constants::*;
 pub use fuse_table::FuseTable;
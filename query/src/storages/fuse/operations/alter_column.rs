@@ -0,0 +1,184 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::RequireColumnsVisitor;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::sql::PlanParser;
+use crate::storages::fuse::FuseTable;
+use crate::storages::Table;
+
+impl FuseTable {
+    pub async fn do_add_column(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        field: DataField,
+        comment: String,
+    ) -> Result<()> {
+        self.check_mutable()?;
+
+        let schema = self.schema();
+        if schema.has_field(field.name()) {
+            return Err(ErrorCode::BadArguments(format!(
+                "column {} already exists in table {}",
+                field.name(),
+                self.table_info.desc
+            )));
+        }
+
+        let mut new_table_meta = self.get_table_info().meta.clone();
+        if new_table_meta.field_comments.len() == schema.num_fields() {
+            new_table_meta.field_comments.push(comment);
+        }
+
+        // the default value is evaluated only once, the blocks written before are filled with
+        // the same value every time they are read.
+        let default_value = Self::eval_default_value(&ctx, &field)?;
+        let mut column_ids = self.column_ids()?;
+        column_ids.add_column(&field, default_value);
+        let mut fields = schema.fields().clone();
+        fields.push(field);
+        let new_schema = column_ids.to_schema(fields, schema.as_ref())?;
+
+        self.commit_schema_change(ctx.as_ref(), catalog_name, new_schema, new_table_meta)
+            .await
+    }
+
+    pub async fn do_drop_column(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        column: &str,
+    ) -> Result<()> {
+        self.check_mutable()?;
+
+        let schema = self.schema();
+        let index = schema.index_of(column).map_err(|_| {
+            ErrorCode::UnknownColumn(format!(
+                "column {} does not exist in table {}",
+                column, self.table_info.desc
+            ))
+        })?;
+        if schema.num_fields() == 1 {
+            return Err(ErrorCode::BadArguments(format!(
+                "can not drop the only column {} of table {}",
+                column, self.table_info.desc
+            )));
+        }
+        self.check_not_cluster_key_column(column)?;
+
+        let mut new_table_meta = self.get_table_info().meta.clone();
+        if new_table_meta.field_comments.len() == schema.num_fields() {
+            new_table_meta.field_comments.remove(index);
+        }
+
+        let mut column_ids = self.column_ids()?;
+        column_ids.drop_column(index);
+        let mut fields = schema.fields().clone();
+        fields.remove(index);
+        let new_schema = column_ids.to_schema(fields, schema.as_ref())?;
+
+        self.commit_schema_change(ctx.as_ref(), catalog_name, new_schema, new_table_meta)
+            .await
+    }
+
+    pub async fn do_rename_column(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        column: &str,
+        new_column: &str,
+    ) -> Result<()> {
+        self.check_mutable()?;
+
+        let schema = self.schema();
+        let index = schema.index_of(column).map_err(|_| {
+            ErrorCode::UnknownColumn(format!(
+                "column {} does not exist in table {}",
+                column, self.table_info.desc
+            ))
+        })?;
+        if schema.has_field(new_column) {
+            return Err(ErrorCode::BadArguments(format!(
+                "column {} already exists in table {}",
+                new_column, self.table_info.desc
+            )));
+        }
+        self.check_not_cluster_key_column(column)?;
+
+        // the column keeps its id, so the blocks written before remain readable.
+        let field = schema.field(index);
+        let mut fields = schema.fields().clone();
+        fields[index] = DataField::new(new_column, field.data_type().clone())
+            .with_default_expr(field.default_expr().cloned());
+        let new_schema = self.column_ids()?.to_schema(fields, schema.as_ref())?;
+
+        let new_table_meta = self.get_table_info().meta.clone();
+        self.commit_schema_change(ctx.as_ref(), catalog_name, new_schema, new_table_meta)
+            .await
+    }
+
+    fn eval_default_value(ctx: &Arc<QueryContext>, field: &DataField) -> Result<Option<DataValue>> {
+        let default_expr = match field.default_expr() {
+            None => return Ok(None),
+            Some(default_expr) => default_expr,
+        };
+
+        let expr = PlanParser::parse_expr(default_expr)?;
+        let expr = Expression::Alias(
+            field.name().to_string(),
+            Box::new(Expression::Cast {
+                expr: Box::new(expr),
+                data_type: field.data_type().clone(),
+                pg_style: false,
+            }),
+        );
+
+        let dummy = DataSchemaRefExt::create(vec![DataField::new("dummy", u8::to_data_type())]);
+        let one_row_block = DataBlock::create(dummy.clone(), vec![Series::from_data(vec![1u8])]);
+        let executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "default value of column",
+            dummy,
+            DataSchemaRefExt::create(vec![field.clone()]),
+            vec![expr],
+            true,
+        )?;
+        let res = executor.execute(&one_row_block)?;
+        Ok(Some(res.column(0).get(0)))
+    }
+
+    fn check_not_cluster_key_column(&self, column: &str) -> Result<()> {
+        for expr in &self.cluster_keys {
+            let columns = RequireColumnsVisitor::collect_columns_from_expr(expr)?;
+            if columns.contains(column) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "column {} is used by the cluster key of table {}",
+                    column, self.table_info.desc
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -77,6 +77,8 @@ impl FuseTable {
             block_per_seg,
             self.meta_location_generator().clone(),
             cluster_key_info,
+            self.column_ids()?,
+            self.bloom_index_writer(ctx.as_ref())?,
         )
         .await?;
 
@@ -203,6 +205,8 @@ impl FuseTable {
             });
         }
 
+        let column_ids = self.column_ids()?;
+        let bloom_index_writer = self.bloom_index_writer(ctx.as_ref())?;
         let mut sink_pipeline_builder = SinkPipeBuilder::create();
         for _ in 0..pipeline.output_len() {
             let input_port = InputPort::create();
@@ -215,6 +219,8 @@ impl FuseTable {
                     da.clone(),
                     self.meta_location_generator().clone(),
                     cluster_key_info.clone(),
+                    column_ids.clone(),
+                    bloom_index_writer.clone(),
                 )?,
             );
         }
@@ -13,29 +13,29 @@
 //  limitations under the License.
 //
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
 use backoff::backoff::Backoff;
+use backoff::ExponentialBackoff;
 use backoff::ExponentialBackoffBuilder;
 use common_base::base::ProgressValues;
 use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::TableStatistics;
-use common_meta_app::schema::UpdateTableMetaReply;
-use common_meta_app::schema::UpdateTableMetaReq;
-use common_meta_types::MatchSeq;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_tracing::tracing;
 use common_tracing::tracing::info;
 use common_tracing::tracing::warn;
 use uuid::Uuid;
 
 use crate::sessions::QueryContext;
-use crate::sql::OPT_KEY_LEGACY_SNAPSHOT_LOC;
-use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::meta::ClusterKey;
 use crate::storages::fuse::meta::Location;
 use crate::storages::fuse::meta::SegmentInfo;
@@ -45,6 +45,7 @@ use crate::storages::fuse::meta::Versioned;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
 use crate::storages::fuse::operations::TableOperationLog;
 use crate::storages::fuse::statistics;
+use crate::storages::fuse::statistics::reducers::reduce_statistics;
 use crate::storages::fuse::FuseTable;
 use crate::storages::Table;
 
@@ -58,41 +59,26 @@ impl FuseTable {
         ctx: Arc<QueryContext>,
         catalog_name: impl AsRef<str>,
         operation_log: TableOperationLog,
+        copied_files: Option<UpsertTableCopiedFileReq>,
         overwrite: bool,
     ) -> Result<()> {
         let mut tbl = self;
         let mut latest: Arc<dyn Table>;
 
         let mut retry_times = 0;
-
-        // The initial retry delay in millisecond. By default,  it is 5 ms.
-        let init_delay = OCC_DEFAULT_BACKOFF_INIT_DELAY_MS;
-
-        // The maximum  back off delay in millisecond, once the retry interval reaches this value, it stops increasing.
-        // By default, it is 20 seconds.
-        let max_delay = OCC_DEFAULT_BACKOFF_MAX_DELAY_MS;
-
-        // The maximum elapsed time after the occ starts, beyond which there will be no more retries.
-        // By default, it is 2 minutes
-        let max_elapsed = OCC_DEFAULT_BACKOFF_MAX_ELAPSED_MS;
-
-        // see https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/ for more
-        // informations. (The strategy that crate backoff implements is “Equal Jitter”)
-
-        // To simplify the settings, using fixed common values for randomization_factor and multiplier
-        let mut backoff = ExponentialBackoffBuilder::new()
-            .with_initial_interval(init_delay)
-            .with_max_interval(max_delay)
-            .with_randomization_factor(0.5)
-            .with_multiplier(2.0)
-            .with_max_elapsed_time(Some(max_elapsed))
-            .build();
+        let mut backoff = self::utils::new_occ_backoff();
 
         let transient = self.transient();
         let catalog_name = catalog_name.as_ref();
         loop {
             match tbl
-                .try_commit(ctx.as_ref(), catalog_name, &operation_log, overwrite)
+                .try_commit(
+                    ctx.as_ref(),
+                    catalog_name,
+                    &operation_log,
+                    &copied_files,
+                    overwrite,
+                )
                 .await
             {
                 Ok(_) => {
@@ -158,6 +144,7 @@ impl FuseTable {
         ctx: &QueryContext,
         catalog_name: &str,
         operation_log: &TableOperationLog,
+        copied_files: &Option<UpsertTableCopiedFileReq>,
         overwrite: bool,
     ) -> Result<()> {
         let prev = self.read_table_snapshot(ctx).await?;
@@ -206,8 +193,241 @@ impl FuseTable {
             index_data_bytes: 0, // TODO we do not have it yet
         };
 
-        self.update_table_meta(ctx, catalog_name, &new_snapshot, &mut new_table_meta)
-            .await
+        self.update_table_meta(
+            ctx,
+            catalog_name,
+            &new_snapshot,
+            &mut new_table_meta,
+            copied_files,
+        )
+        .await
+    }
+
+    /// Commits the snapshot produced by a mutation, e.g. deletion, update or compaction, which
+    /// is based on `base_snapshot`.
+    ///
+    /// If the table has been changed by others since `base_snapshot`, the mutation is rebased onto
+    /// the latest snapshot and retried, unless the segments it mutated are mutated by others too.
+    pub async fn commit_mutation(
+        &self,
+        ctx: &QueryContext,
+        base_snapshot: &TableSnapshot,
+        new_snapshot: TableSnapshot,
+        catalog_name: &str,
+    ) -> Result<()> {
+        let mut tbl = self;
+        let mut latest: Arc<dyn Table>;
+        let mut base_snapshot = Arc::new(base_snapshot.clone());
+        let mut new_snapshot = new_snapshot;
+
+        let mut retry_times = 0;
+        let mut backoff = self::utils::new_occ_backoff();
+        loop {
+            let mut new_table_meta = tbl.get_table_info().meta.clone();
+            new_table_meta.statistics = TableStatistics {
+                number_of_rows: new_snapshot.summary.row_count,
+                data_bytes: new_snapshot.summary.uncompressed_byte_size,
+                compressed_data_bytes: new_snapshot.summary.compressed_byte_size,
+                index_data_bytes: 0, // TODO we do not have it yet
+            };
+            match tbl
+                .update_table_meta(ctx, catalog_name, &new_snapshot, &mut new_table_meta, &None)
+                .await
+            {
+                Ok(_) => break Ok(()),
+                Err(e) if e.code() == ErrorCode::table_version_mismatched_code() => {
+                    match backoff.next_backoff() {
+                        Some(d) => {
+                            tracing::debug!(
+                                "got error TableVersionMismatched, mutation will be rebased and retried {} ms later. table name {}, identity {}",
+                                d.as_millis(),
+                                tbl.table_info.name.as_str(),
+                                tbl.table_info.ident
+                            );
+                            common_base::base::tokio::time::sleep(d).await;
+                            latest = tbl.latest(ctx, catalog_name).await?;
+                            tbl = FuseTable::try_from_table(latest.as_ref())?;
+                            let latest_snapshot = tbl.read_table_snapshot(ctx).await?;
+                            new_snapshot = tbl
+                                .rebase_mutation(
+                                    ctx,
+                                    &base_snapshot,
+                                    &new_snapshot,
+                                    &latest_snapshot,
+                                )
+                                .await?;
+                            if let Some(s) = latest_snapshot {
+                                base_snapshot = s;
+                            }
+                            retry_times += 1;
+                            continue;
+                        }
+                        None => {
+                            break Err(ErrorCode::OCCRetryFailure(format!(
+                                "can not fulfill the mutation after retries({} times, {} ms), aborted. table name {}, identity {}",
+                                retry_times,
+                                Instant::now().duration_since(backoff.start_time).as_millis(),
+                                tbl.table_info.name.as_str(),
+                                tbl.table_info.ident,
+                            )));
+                        }
+                    }
+                }
+                Err(e) => break Err(e),
+            }
+        }
+    }
+
+    /// Commits a new snapshot with `schema`, which is changed from the schema of this table by
+    /// `ALTER TABLE ... ADD/DROP/RENAME COLUMN`, and the rest of `new_table_meta`.
+    ///
+    /// If the table has been changed by others, the new snapshot is rebuilt from the latest
+    /// snapshot and retried, unless the schema has been changed by others too.
+    pub async fn commit_schema_change(
+        &self,
+        ctx: &QueryContext,
+        catalog_name: &str,
+        schema: DataSchema,
+        new_table_meta: TableMeta,
+    ) -> Result<()> {
+        let mut tbl = self;
+        let mut latest: Arc<dyn Table>;
+        let base_schema = self.schema();
+        let field_comments = new_table_meta.field_comments.clone();
+        let mut new_table_meta = new_table_meta;
+
+        let mut retry_times = 0;
+        let mut backoff = self::utils::new_occ_backoff();
+        loop {
+            new_table_meta.schema = Arc::new(schema.clone());
+            let prev = tbl.read_table_snapshot(ctx).await?;
+            let prev_version = tbl.snapshot_format_version();
+            let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+            let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
+            let (summary, segments) = if let Some(v) = prev {
+                (v.summary.clone(), v.segments.clone())
+            } else {
+                (Statistics::default(), vec![])
+            };
+            let new_snapshot = TableSnapshot::new(
+                Uuid::new_v4(),
+                &prev_timestamp,
+                prev_snapshot_id,
+                schema.clone(),
+                summary,
+                segments,
+                tbl.cluster_key_meta.clone(),
+            );
+
+            match tbl
+                .update_table_meta(ctx, catalog_name, &new_snapshot, &mut new_table_meta, &None)
+                .await
+            {
+                Ok(_) => break Ok(()),
+                Err(e) if e.code() == ErrorCode::table_version_mismatched_code() => {
+                    match backoff.next_backoff() {
+                        Some(d) => {
+                            tracing::debug!(
+                                "got error TableVersionMismatched, schema change will be retried {} ms later. table name {}, identity {}",
+                                d.as_millis(),
+                                tbl.table_info.name.as_str(),
+                                tbl.table_info.ident
+                            );
+                            common_base::base::tokio::time::sleep(d).await;
+                            latest = tbl.latest(ctx, catalog_name).await?;
+                            tbl = FuseTable::try_from_table(latest.as_ref())?;
+                            if tbl.schema() != base_schema {
+                                break Err(ErrorCode::TableWriteConflict(format!(
+                                    "table {} has been changed by another transaction, the schema has been changed. table identity {}",
+                                    tbl.table_info.name, tbl.table_info.ident
+                                )));
+                            }
+                            new_table_meta = tbl.get_table_info().meta.clone();
+                            new_table_meta.field_comments = field_comments.clone();
+                            retry_times += 1;
+                            continue;
+                        }
+                        None => {
+                            break Err(ErrorCode::OCCRetryFailure(format!(
+                                "can not fulfill the schema change after retries({} times, {} ms), aborted. table name {}, identity {}",
+                                retry_times,
+                                Instant::now().duration_since(backoff.start_time).as_millis(),
+                                tbl.table_info.name.as_str(),
+                                tbl.table_info.ident,
+                            )));
+                        }
+                    }
+                }
+                Err(e) => break Err(e),
+            }
+        }
+    }
+
+    /// Applies the mutation, which turns `base` into `mutated`, onto `latest`.
+    ///
+    /// The segments of `base` that are missing from `mutated` are the ones mutated by us, and the
+    /// segments of `base` that are missing from `latest` are the ones mutated by others, it is a
+    /// write-write conflict if they overlap, or the schema has been changed by others.
+    ///
+    /// Otherwise, the segments created by others are placed in front of the mutated segments,
+    /// in the same way as appending does.
+    async fn rebase_mutation(
+        &self,
+        ctx: &QueryContext,
+        base: &TableSnapshot,
+        mutated: &TableSnapshot,
+        latest: &Option<Arc<TableSnapshot>>,
+    ) -> Result<TableSnapshot> {
+        let conflict = |reason: &str| {
+            ErrorCode::TableWriteConflict(format!(
+                "table {} has been changed by another transaction, {}. table identity {}",
+                self.table_info.name, reason, self.table_info.ident
+            ))
+        };
+        let latest = match latest {
+            Some(latest) => latest,
+            None => return Err(conflict("the table has been truncated")),
+        };
+        if latest.schema != base.schema {
+            return Err(conflict("the schema has been changed"));
+        }
+
+        let base_segments = base.segments.iter().collect::<HashSet<_>>();
+        let mutated_segments = mutated.segments.iter().collect::<HashSet<_>>();
+        let latest_segments = latest.segments.iter().collect::<HashSet<_>>();
+        let conflicted = base_segments
+            .iter()
+            .any(|s| !mutated_segments.contains(s) && !latest_segments.contains(s));
+        if conflicted {
+            return Err(conflict(
+                "the segments being mutated have been mutated by it as well",
+            ));
+        }
+
+        let segments = latest
+            .segments
+            .iter()
+            .filter(|s| !base_segments.contains(s))
+            .chain(
+                mutated
+                    .segments
+                    .iter()
+                    .filter(|s| !base_segments.contains(s) || latest_segments.contains(s)),
+            )
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut summaries = Vec::with_capacity(segments.len());
+        for (loc, ver) in &segments {
+            let segment = reader.read(loc, None, *ver).await?;
+            summaries.push(segment.summary.clone());
+        }
+
+        let mut new_snapshot = TableSnapshot::from_previous(latest.as_ref());
+        new_snapshot.segments = segments;
+        new_snapshot.summary = reduce_statistics(&summaries)?;
+        Ok(new_snapshot)
     }
 
     fn merge_table_operations(
@@ -246,45 +466,6 @@ impl FuseTable {
         Ok(new_snapshot)
     }
 
-    pub async fn commit_to_meta_server(
-        ctx: &QueryContext,
-        catalog_name: &str,
-        table_info: &TableInfo,
-        new_snapshot_location: String,
-        stats: &Statistics,
-    ) -> Result<UpdateTableMetaReply> {
-        let catalog = ctx.get_catalog(catalog_name)?;
-
-        let table_id = table_info.ident.table_id;
-        let table_version = table_info.ident.seq;
-
-        let mut new_table_meta = table_info.meta.clone();
-
-        // set new snapshot location
-        new_table_meta
-            .options
-            .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), new_snapshot_location);
-
-        // remove legacy options
-        self::utils::remove_legacy_options(&mut new_table_meta.options);
-
-        // update statistics
-        new_table_meta.statistics = TableStatistics {
-            number_of_rows: stats.row_count,
-            data_bytes: stats.uncompressed_byte_size,
-            compressed_data_bytes: stats.compressed_byte_size,
-            index_data_bytes: 0, // TODO we do not have it yet
-        };
-
-        let req = UpdateTableMetaReq {
-            table_id,
-            seq: MatchSeq::Exact(table_version),
-            new_table_meta,
-        };
-
-        catalog.update_table_meta(req).await
-    }
-
     pub fn merge_append_operations(
         append_log_entries: &[AppendOperationLogEntry],
     ) -> Result<(Vec<String>, Statistics)> {
@@ -329,8 +510,6 @@ impl FuseTable {
 }
 
 mod utils {
-    use std::collections::BTreeMap;
-
     use super::*;
     #[inline]
     pub async fn abort_operations(
@@ -345,21 +524,44 @@ mod utils {
                 // if deletion operation failed (after DAL retried)
                 // we just left them there, and let the "major GC" collect them
                 let _ = operator.object(block_location).delete().await;
+                if let Some((bloom_index_location, _)) = &block.bloom_filter_index_location {
+                    let _ = operator.object(bloom_index_location).delete().await;
+                }
             }
             let _ = operator.object(&entry.segment_location).delete().await;
         }
         Ok(())
     }
 
+    pub fn new_occ_backoff() -> ExponentialBackoff {
+        // The initial retry delay in millisecond. By default,  it is 5 ms.
+        let init_delay = OCC_DEFAULT_BACKOFF_INIT_DELAY_MS;
+
+        // The maximum  back off delay in millisecond, once the retry interval reaches this value, it stops increasing.
+        // By default, it is 20 seconds.
+        let max_delay = OCC_DEFAULT_BACKOFF_MAX_DELAY_MS;
+
+        // The maximum elapsed time after the occ starts, beyond which there will be no more retries.
+        // By default, it is 2 minutes
+        let max_elapsed = OCC_DEFAULT_BACKOFF_MAX_ELAPSED_MS;
+
+        // see https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/ for more
+        // informations. (The strategy that crate backoff implements is “Equal Jitter”)
+
+        // To simplify the settings, using fixed common values for randomization_factor and multiplier
+        ExponentialBackoffBuilder::new()
+            .with_initial_interval(init_delay)
+            .with_max_interval(max_delay)
+            .with_randomization_factor(0.5)
+            .with_multiplier(2.0)
+            .with_max_elapsed_time(Some(max_elapsed))
+            .build()
+    }
+
     #[inline]
     pub fn is_error_recoverable(e: &ErrorCode, is_table_transient: bool) -> bool {
         let code = e.code();
         code == ErrorCode::table_version_mismatched_code()
             || (is_table_transient && code == ErrorCode::storage_not_found_code())
     }
-
-    // check if there are any fuse table legacy options
-    pub fn remove_legacy_options(table_options: &mut BTreeMap<String, String>) {
-        table_options.remove(OPT_KEY_LEGACY_SNAPSHOT_LOC);
-    }
 }
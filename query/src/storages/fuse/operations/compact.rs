@@ -16,17 +16,21 @@
 use std::sync::Arc;
 
 use common_exception::Result;
-use common_meta_app::schema::TableStatistics;
 use common_planners::OptimizeTablePlan;
 
 use super::mutation::CompactMutator;
 use crate::sessions::QueryContext;
+use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_AUTO_COMPACTION_SEGMENT_THRESHOLD;
+use crate::storages::fuse::DEFAULT_AUTO_COMPACTION_SMALL_BLOCK_RATIO;
 use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
 use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_AUTO_COMPACTION;
+use crate::storages::fuse::FUSE_OPT_KEY_AUTO_COMPACTION_SEGMENT_THRESHOLD;
+use crate::storages::fuse::FUSE_OPT_KEY_AUTO_COMPACTION_SMALL_BLOCK_RATIO;
 use crate::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
 use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
-use crate::storages::storage_table::Table;
 
 impl FuseTable {
     pub async fn do_compact(&self, ctx: Arc<QueryContext>, plan: &OptimizeTablePlan) -> Result<()> {
@@ -55,19 +59,58 @@ impl FuseTable {
         )?;
 
         let new_snapshot = mutator.compact(self).await?;
-        let mut new_table_meta = self.get_table_info().meta.clone(); // update statistics
-        new_table_meta.statistics = TableStatistics {
-            number_of_rows: new_snapshot.summary.row_count,
-            data_bytes: new_snapshot.summary.uncompressed_byte_size,
-            compressed_data_bytes: new_snapshot.summary.compressed_byte_size,
-            index_data_bytes: 0, // TODO we do not have it yet
+        self.commit_mutation(ctx.as_ref(), &snapshot, new_snapshot, &plan.catalog)
+            .await
+    }
+
+    pub fn is_auto_compaction_enabled(&self) -> bool {
+        self.get_option(FUSE_OPT_KEY_AUTO_COMPACTION, false)
+    }
+
+    /// Returns true if the table has too many segments that are not full, or too many blocks
+    /// that are smaller than `row_per_block`, which are left by small and frequent insertions.
+    pub async fn needs_compaction(&self, ctx: &QueryContext) -> Result<bool> {
+        let snapshot = match self.read_table_snapshot(ctx).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(false),
         };
-        self.update_table_meta(
-            ctx.as_ref(),
-            &plan.catalog,
-            &new_snapshot,
-            &mut new_table_meta,
-        )
-        .await
+        if snapshot.summary.block_count <= 1 {
+            return Ok(false);
+        }
+
+        let row_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let block_per_seg =
+            self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
+        // compaction leaves at most one segment and one block unfilled, they are not worth
+        // being compacted again
+        let segment_threshold = self
+            .get_option(
+                FUSE_OPT_KEY_AUTO_COMPACTION_SEGMENT_THRESHOLD,
+                DEFAULT_AUTO_COMPACTION_SEGMENT_THRESHOLD,
+            )
+            .max(2);
+        let small_block_ratio = self.get_option(
+            FUSE_OPT_KEY_AUTO_COMPACTION_SMALL_BLOCK_RATIO,
+            DEFAULT_AUTO_COMPACTION_SMALL_BLOCK_RATIO,
+        );
+
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut small_segments = 0;
+        let mut small_blocks = 0;
+        for (x, ver) in &snapshot.segments {
+            let segment = reader.read(x, None, *ver).await?;
+            if segment.blocks.len() < block_per_seg {
+                small_segments += 1;
+            }
+            small_blocks += segment
+                .blocks
+                .iter()
+                .filter(|b| b.row_count < row_per_block as u64)
+                .count() as u64;
+        }
+
+        Ok(small_segments >= segment_threshold
+            || (small_blocks > 1
+                && small_blocks * 100 >= small_block_ratio * snapshot.summary.block_count))
     }
 }
@@ -27,7 +27,6 @@ use crate::storages::fuse::operations::mutation::mutations_collector::Deletion;
 use crate::storages::fuse::operations::mutation::mutations_collector::DeletionCollector;
 use crate::storages::fuse::pruning::BlockPruner;
 use crate::storages::fuse::FuseTable;
-use crate::storages::Table;
 
 impl FuseTable {
     pub async fn do_delete(&self, ctx: Arc<QueryContext>, plan: &DeletePlan) -> Result<()> {
@@ -68,8 +67,13 @@ impl FuseTable {
         filter: &Expression,
         plan: &DeletePlan,
     ) -> Result<()> {
-        let mut deletion_collector =
-            DeletionCollector::try_create(ctx.as_ref(), &self.meta_location_generator, snapshot)?;
+        let mut deletion_collector = DeletionCollector::try_create(
+            ctx.as_ref(),
+            &self.meta_location_generator,
+            snapshot,
+            self.column_ids()?,
+            self.bloom_index_writer(ctx.as_ref())?,
+        )?;
         let schema = self.table_info.schema();
         // TODO refine pruner
         let extras = Extras {
@@ -101,26 +105,8 @@ impl FuseTable {
                 }
             }
         }
-        self.commit_deletion(ctx.as_ref(), deletion_collector, &plan.catalog_name)
+        let new_snapshot = deletion_collector.into_new_snapshot().await?;
+        self.commit_mutation(ctx.as_ref(), snapshot, new_snapshot, &plan.catalog_name)
             .await
     }
-
-    async fn commit_deletion(
-        &self,
-        ctx: &QueryContext,
-        del_holder: DeletionCollector<'_>,
-        catalog_name: &str,
-    ) -> Result<()> {
-        let (new_snapshot, loc) = del_holder.into_new_snapshot().await?;
-        Self::commit_to_meta_server(
-            ctx,
-            catalog_name,
-            self.get_table_info(),
-            loc,
-            &new_snapshot.summary,
-        )
-        .await?;
-        // TODO check if error is recoverable, and try to resolve the conflict
-        Ok(())
-    }
 }
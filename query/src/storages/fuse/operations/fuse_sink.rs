@@ -30,9 +30,12 @@ use crate::pipelines::new::processors::processor::Event;
 use crate::pipelines::new::processors::processor::ProcessorPtr;
 use crate::pipelines::new::processors::Processor;
 use crate::sessions::QueryContext;
+use crate::storages::fuse::io::BloomIndexWriter;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::Statistics;
+use crate::storages::fuse::meta::Versioned;
 use crate::storages::fuse::statistics::accumulator::BlockStatistics;
 use crate::storages::fuse::statistics::StatisticsAccumulator;
 use crate::storages::index::ClusterKeyInfo;
@@ -45,6 +48,7 @@ enum State {
         size: u64,
         meta_data: Box<FileMetaData>,
         block_statistics: BlockStatistics,
+        bloom_index: Option<SerializedBloomIndex>,
     },
     GenerateSegment,
     SerializedSegment {
@@ -55,6 +59,11 @@ enum State {
     Finished,
 }
 
+struct SerializedBloomIndex {
+    data: Vec<u8>,
+    location: String,
+}
+
 pub struct FuseTableSink {
     state: State,
     input: Arc<InputPort>,
@@ -64,6 +73,8 @@ pub struct FuseTableSink {
     meta_locations: TableMetaLocationGenerator,
     accumulator: StatisticsAccumulator,
     cluster_key_info: Option<ClusterKeyInfo>,
+    column_ids: ColumnIds,
+    bloom_index_writer: Option<BloomIndexWriter>,
 }
 
 impl FuseTableSink {
@@ -74,6 +85,8 @@ impl FuseTableSink {
         data_accessor: Operator,
         meta_locations: TableMetaLocationGenerator,
         cluster_key_info: Option<ClusterKeyInfo>,
+        column_ids: ColumnIds,
+        bloom_index_writer: Option<BloomIndexWriter>,
     ) -> Result<ProcessorPtr> {
         Ok(ProcessorPtr::create(Box::new(FuseTableSink {
             ctx,
@@ -81,9 +94,11 @@ impl FuseTableSink {
             data_accessor,
             meta_locations,
             state: State::None,
-            accumulator: Default::default(),
+            accumulator: StatisticsAccumulator::with_column_ids(column_ids.clone()),
             num_block_threshold: num_block_threshold as u64,
             cluster_key_info,
+            column_ids,
+            bloom_index_writer,
         })))
     }
 }
@@ -151,6 +166,21 @@ impl Processor for FuseTableSink {
                 }
 
                 let location = self.meta_locations.gen_block_location();
+                let bloom_index = match &self.bloom_index_writer {
+                    Some(writer) => {
+                        let bloom_block = writer.build(&block)?;
+                        let mut data = Vec::new();
+                        let schema = bloom_block.schema().clone();
+                        serialize_data_blocks(vec![bloom_block], &schema, &mut data)?;
+                        Some(SerializedBloomIndex {
+                            data,
+                            location: TableMetaLocationGenerator::gen_bloom_index_location(
+                                &location,
+                            ),
+                        })
+                    }
+                    None => None,
+                };
                 let block_statistics = BlockStatistics::from(&block, location, cluster_stats)?;
 
                 // we need a configuration of block size threshold here
@@ -162,10 +192,14 @@ impl Processor for FuseTableSink {
                     size,
                     block_statistics,
                     meta_data: Box::new(meta_data),
+                    bloom_index,
                 };
             }
             State::GenerateSegment => {
-                let acc = std::mem::take(&mut self.accumulator);
+                let acc = std::mem::replace(
+                    &mut self.accumulator,
+                    StatisticsAccumulator::with_column_ids(self.column_ids.clone()),
+                );
                 let col_stats = acc.summary()?;
 
                 let segment_info = SegmentInfo::new(acc.blocks_metas, Statistics {
@@ -196,8 +230,17 @@ impl Processor for FuseTableSink {
                 data,
                 size,
                 meta_data,
-                block_statistics,
+                mut block_statistics,
+                bloom_index,
             } => {
+                if let Some(bloom_index) = bloom_index {
+                    self.data_accessor
+                        .object(&bloom_index.location)
+                        .write(bloom_index.data)
+                        .await?;
+                    block_statistics.block_bloom_filter_index_location =
+                        Some((bloom_index.location, DataBlock::VERSION));
+                }
                 self.data_accessor
                     .object(&block_statistics.block_file_location)
                     .write(data)
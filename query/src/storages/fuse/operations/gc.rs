@@ -13,24 +13,83 @@
 //  limitations under the License.
 //
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
 use common_cache::Cache;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::MetaId;
 use common_tracing::tracing::warn;
 use futures::TryStreamExt;
 use opendal::Operator;
 
 use crate::sessions::QueryContext;
+use crate::sql::OPT_KEY_CLONE_SNAPSHOT_LOCATIONS;
 use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::Location;
 use crate::storages::fuse::meta::SnapshotId;
 use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_OPT_KEY_DATA_RETENTION_PERIOD_IN_HOURS;
+
+/// The files that are no longer referenced by the retained snapshots.
+#[derive(Default)]
+struct Garbage {
+    /// the blocks, and their bloom filter indexes
+    blocks: Vec<String>,
+    segments: HashSet<Location>,
+    /// ordered from the latest to the earliest
+    snapshots: Vec<(SnapshotId, u64)>,
+}
 
 impl FuseTable {
     pub async fn do_gc(&self, ctx: &Arc<QueryContext>, keep_last_snapshot: bool) -> Result<()> {
+        let garbage = self.collect_garbage(ctx, keep_last_snapshot).await?;
+
+        // removed un-referenced blocks
+        let accessor = ctx.get_storage_operator()?;
+        for block in &garbage.blocks {
+            self.remove_location(&accessor, block).await?;
+        }
+
+        self.collect(ctx.as_ref(), garbage.segments, garbage.snapshots)
+            .await
+    }
+
+    /// Returns the locations of the files that `do_gc` would remove, without removing them.
+    pub async fn do_gc_dry_run(
+        &self,
+        ctx: &Arc<QueryContext>,
+        keep_last_snapshot: bool,
+    ) -> Result<Vec<String>> {
+        let garbage = self.collect_garbage(ctx, keep_last_snapshot).await?;
+        let mut files = garbage.blocks;
+        files.extend(garbage.segments.into_iter().map(|(loc, _)| loc));
+        for (id, ver) in garbage.snapshots.iter().rev() {
+            let loc = self
+                .meta_location_generator
+                .snapshot_location_from_uuid(id, *ver)?;
+            files.push(loc);
+        }
+        Ok(files)
+    }
+
+    /// The snapshots that are committed within the data retention period are kept along with
+    /// the last snapshot, as well as the one that precedes them, so that time travel to any
+    /// point of the retention period is still possible.
+    ///
+    /// If `keep_last_snapshot` is false, all the snapshots are collected, regardless of the
+    /// retention period.
+    async fn collect_garbage(
+        &self,
+        ctx: &Arc<QueryContext>,
+        keep_last_snapshot: bool,
+    ) -> Result<Garbage> {
         let r = self.read_table_snapshot(ctx.as_ref()).await;
         let snapshot_opt = match r {
             Err(e) if e.code() == ErrorCode::storage_not_found_code() => {
@@ -41,7 +100,7 @@ impl FuseTable {
                     self.table_info.desc,
                     self.table_info.ident,
                 );
-                return Ok(());
+                return Ok(Garbage::default());
             }
             Err(e) => return Err(e),
             Ok(v) => v,
@@ -51,90 +110,149 @@ impl FuseTable {
             s
         } else {
             // empty table, have nothing to do here
-            return Ok(());
-        };
-
-        let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
-
-        let (prev_id, prev_ver) = if let Some((id, ver)) = last_snapshot.prev_snapshot_id {
-            (id, ver)
-        } else {
-            return if keep_last_snapshot {
-                // short cut:
-                // we do not have previous snapshot, and should keep the last snapshot, nothing to do
-                Ok(())
-            } else {
-                // short cut:
-                // - no previous snapshot
-                // - no need to keep the last snapshot
-                // just drop the whole snapshot,
-                let snapshots = vec![(last_snapshot.snapshot_id, self.snapshot_format_version())];
-                let segments = HashSet::from_iter(last_snapshot.segments.clone());
-                self.purge_blocks(ctx, segments.iter(), &HashSet::new())
-                    .await?;
-                self.collect(ctx.as_ref(), segments, snapshots).await
-            };
+            return Ok(Garbage::default());
         };
 
-        let prev_loc = self
-            .meta_location_generator
-            .snapshot_location_from_uuid(&prev_id, prev_ver)?;
-
-        let mut snapshot_history =
-            reader.snapshot_history(prev_loc, prev_ver, self.meta_location_generator.clone());
-
         let mut snapshots_to_be_deleted: Vec<_> = Vec::new();
-        if !keep_last_snapshot {
+        let mut segments_referenced_by_gc_root: HashSet<Location> = HashSet::new();
+        let mut segments_referenced_by_garbage: HashSet<Location> = HashSet::new();
+        if keep_last_snapshot {
+            // segment gc root contains all the segments referenced by pivot snapshot;
+            segments_referenced_by_gc_root.extend(last_snapshot.segments.clone());
+        } else {
             snapshots_to_be_deleted
                 .push((last_snapshot.snapshot_id, self.snapshot_format_version()));
+            segments_referenced_by_garbage.extend(last_snapshot.segments.clone());
         }
 
-        let segments_referenced_by_gc_root: HashSet<Location> = if !keep_last_snapshot {
-            //  segment gc root references nothing;
-            HashSet::new()
-        } else {
-            // segment gc root contains all the segments referenced by pivot snapshot;
-            HashSet::from_iter(last_snapshot.segments.clone())
-        };
+        if let Some((prev_id, prev_ver)) = last_snapshot.prev_snapshot_id {
+            let prev_loc = self
+                .meta_location_generator
+                .snapshot_location_from_uuid(&prev_id, prev_ver)?;
+            let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
+            let mut snapshot_history =
+                reader.snapshot_history(prev_loc, prev_ver, self.meta_location_generator.clone());
 
-        // segments which no longer need to be kept
-        let mut segments_to_be_deleted: HashSet<_> = HashSet::new();
-        {
-            if !keep_last_snapshot {
-                segments_to_be_deleted.extend(last_snapshot.segments.clone())
-            }
+            let retention_boundary = if keep_last_snapshot {
+                self.retention_boundary()
+            } else {
+                None
+            };
+            // timestamp of the snapshot that succeeds the current one, if it is retained
+            let mut succeeding_timestamp = last_snapshot.timestamp;
 
-            // collects
-            // - all the previous snapshots
-            // - segments referenced by previous snapshots, but not by gc_root
+            // snapshots are ordered by timestamp DESC, collects
+            // - all the previous snapshots out of the retention period
+            // - segments referenced by them
             while let Some(s) = snapshot_history.try_next().await? {
-                snapshots_to_be_deleted.push((s.snapshot_id, s.format_version()));
-                for seg in &s.segments {
-                    if !segments_referenced_by_gc_root.contains(seg) {
-                        segments_to_be_deleted.insert(seg.clone());
-                    }
+                let retained = match (retention_boundary, succeeding_timestamp) {
+                    (Some(boundary), Some(ts)) => ts >= boundary,
+                    _ => false,
+                };
+                if retained {
+                    segments_referenced_by_gc_root.extend(s.segments.clone());
+                    succeeding_timestamp = s.timestamp;
+                } else {
+                    snapshots_to_be_deleted.push((s.snapshot_id, s.format_version()));
+                    segments_referenced_by_garbage.extend(s.segments.clone());
+                    succeeding_timestamp = None;
                 }
             }
         }
 
+        // segments which no longer need to be kept
+        let mut segments_to_be_deleted: HashSet<_> = segments_referenced_by_garbage
+            .difference(&segments_referenced_by_gc_root)
+            .cloned()
+            .collect();
+
         let blocks_referenced_by_gc_root: HashSet<String> = self
             .blocks_of(ctx, segments_referenced_by_gc_root.iter())
             .await?;
 
-        // removed un-referenced blocks
-        self.purge_blocks(
-            ctx,
-            segments_to_be_deleted.iter(),
-            &blocks_referenced_by_gc_root,
-        )
-        .await?;
-
-        self.collect(
-            ctx.as_ref(),
-            segments_to_be_deleted,
-            snapshots_to_be_deleted,
-        )
-        .await
+        let mut blocks_to_be_deleted = self
+            .unreferenced_blocks_of(
+                ctx,
+                segments_to_be_deleted.iter(),
+                &blocks_referenced_by_gc_root,
+            )
+            .await?;
+
+        // the files shared with the clones of this table are kept, and the files of the tables
+        // that this table is cloned from are left to them
+        let shared = self.locations_referenced_by_clones(ctx.as_ref()).await?;
+        let locs = &self.meta_location_generator;
+        segments_to_be_deleted.retain(|(loc, _)| locs.owns(loc) && !shared.contains(loc));
+        blocks_to_be_deleted.retain(|loc| locs.owns(loc) && !shared.contains(loc));
+        let mut snapshots_not_shared = Vec::with_capacity(snapshots_to_be_deleted.len());
+        for (id, ver) in snapshots_to_be_deleted {
+            if !shared.contains(&locs.snapshot_location_from_uuid(&id, ver)?) {
+                snapshots_not_shared.push((id, ver));
+            }
+        }
+        let snapshots_to_be_deleted = snapshots_not_shared;
+
+        Ok(Garbage {
+            blocks: blocks_to_be_deleted,
+            segments: segments_to_be_deleted,
+            snapshots: snapshots_to_be_deleted,
+        })
+    }
+
+    /// Locations of the snapshots, segments, blocks and bloom filter indexes that are shared
+    /// with the tables cloned from this table.
+    ///
+    /// The files of this table that a clone references are all reachable from the snapshot it
+    /// is cloned at, which is recorded in this table once the clone is created. They are kept
+    /// even if the clone is dropped, since it may be undropped.
+    async fn locations_referenced_by_clones(&self, ctx: &QueryContext) -> Result<HashSet<String>> {
+        let mut result = HashSet::new();
+        let options = self.table_info.options();
+        let snapshot_locations: BTreeMap<MetaId, String> =
+            match options.get(OPT_KEY_CLONE_SNAPSHOT_LOCATIONS) {
+                Some(v) => serde_json::from_str(v)?,
+                None => return Ok(result),
+            };
+
+        let snapshot_reader = MetaReaders::table_snapshot_reader(ctx);
+        let mut segments = HashSet::new();
+        for snapshot_loc in snapshot_locations.into_values() {
+            if result.contains(&snapshot_loc) {
+                continue;
+            }
+            let ver = TableMetaLocationGenerator::snapshot_version(&snapshot_loc);
+            let snapshot = snapshot_reader.read(&snapshot_loc, None, ver).await?;
+            segments.extend(snapshot.segments.clone());
+            result.insert(snapshot_loc);
+        }
+
+        let segment_reader = MetaReaders::segment_info_reader(ctx);
+        for (loc, ver) in segments {
+            let segment = match segment_reader.read(&loc, None, ver).await {
+                Err(e) if e.code() == ErrorCode::storage_not_found_code() => continue,
+                Err(e) => return Err(e),
+                Ok(v) => v,
+            };
+            for block_meta in &segment.blocks {
+                if let Some((bloom_index_location, _)) = &block_meta.bloom_filter_index_location {
+                    result.insert(bloom_index_location.clone());
+                }
+                result.insert(block_meta.location.0.clone());
+            }
+            result.insert(loc);
+        }
+        Ok(result)
+    }
+
+    /// The earliest time point that time travel should be able to reach, snapshots
+    /// committed before it are not retained.
+    fn retention_boundary(&self) -> Option<DateTime<Utc>> {
+        let hours = self.get_option(FUSE_OPT_KEY_DATA_RETENTION_PERIOD_IN_HOURS, 0u32);
+        if hours == 0 {
+            None
+        } else {
+            Some(Utc::now() - Duration::hours(hours as i64))
+        }
     }
 
     async fn blocks_of(
@@ -168,28 +286,31 @@ impl FuseTable {
         Ok(result)
     }
 
-    /// rm all the blocks, which are
+    /// the blocks, and their bloom filter indexes, which are
     /// - referenced by any one of `segments`
     /// - but NOT referenced by `root`
-    async fn purge_blocks(
+    async fn unreferenced_blocks_of(
         &self,
         ctx: &QueryContext,
         segments: impl Iterator<Item = &Location>,
         root: &HashSet<String>,
-    ) -> Result<()> {
+    ) -> Result<Vec<String>> {
+        let mut result = vec![];
         let reader = MetaReaders::segment_info_reader(ctx);
-        let accessor = ctx.get_storage_operator()?;
         for l in segments {
             let (x, ver) = l;
             let res = reader.read(x, None, *ver).await?;
             for block_meta in &res.blocks {
                 if !root.contains(block_meta.location.0.as_str()) {
-                    self.remove_location(&accessor, block_meta.location.0.as_str())
-                        .await?;
+                    if let Some((bloom_index_location, _)) = &block_meta.bloom_filter_index_location
+                    {
+                        result.push(bloom_index_location.clone());
+                    }
+                    result.push(block_meta.location.0.clone());
                 }
             }
         }
-        Ok(())
+        Ok(result)
     }
 
     async fn remove_location(
@@ -0,0 +1,161 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::MergeIntoPlan;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::BlockCompactor;
+use crate::storages::fuse::io::BlockWriter;
+use crate::storages::fuse::io::SegmentWriter;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::operations::mutation::mutations_collector::DeletionCollector;
+use crate::storages::fuse::operations::mutation::MergeIntoMutator;
+use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::statistics::reducers::reduce_block_metas;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+
+impl FuseTable {
+    pub async fn do_merge_into(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &MergeIntoPlan,
+        source: SendableDataBlockStream,
+    ) -> Result<()> {
+        self.check_mutable()?;
+
+        let snapshot = self.read_table_snapshot(ctx.as_ref()).await?;
+        let mut mutator = MergeIntoMutator::try_create(&ctx, plan, self, snapshot.clone())?;
+        let inserted_blocks = self.probe_merge_source(&ctx, &mut mutator, source).await?;
+
+        let snapshot = match snapshot {
+            Some(snapshot) => snapshot,
+            None => {
+                // no snapshot, all the source rows are unmatched
+                return self.append_merged_blocks(&ctx, plan, inserted_blocks).await;
+            }
+        };
+
+        // the merged blocks are replaced in the same way as deletion does, and the inserted
+        // blocks are kept in a new segment, all of them are committed in one snapshot.
+        let mut mutation_collector = DeletionCollector::try_create(
+            ctx.as_ref(),
+            &self.meta_location_generator,
+            &snapshot,
+            self.column_ids()?,
+            self.bloom_index_writer(ctx.as_ref())?,
+        )?;
+        for (seg_idx, location) in mutator.matched_blocks() {
+            if let Some(r) = mutator.merge_block(&location).await? {
+                mutation_collector
+                    .replace_with(seg_idx, location, r)
+                    .await?
+            }
+        }
+        for block_meta in inserted_blocks {
+            mutation_collector.append_block_meta(block_meta);
+        }
+
+        let new_snapshot = mutation_collector.into_new_snapshot().await?;
+        self.commit_mutation(ctx.as_ref(), &snapshot, new_snapshot, &plan.catalog_name)
+            .await
+    }
+
+    /// Probes the target blocks with the source rows, in batches of about `row_per_block` rows,
+    /// each of which takes a pass of pruning. Returns the blocks of the inserted rows, which
+    /// are written as soon as they are generated.
+    async fn probe_merge_source(
+        &self,
+        ctx: &Arc<QueryContext>,
+        mutator: &mut MergeIntoMutator<'_>,
+        mut source: SendableDataBlockStream,
+    ) -> Result<Vec<BlockMeta>> {
+        let data_accessor = ctx.get_storage_operator()?;
+        let column_ids = self.column_ids()?;
+        let bloom_index_writer = self.bloom_index_writer(ctx.as_ref())?;
+        let block_writer = BlockWriter::new(
+            &data_accessor,
+            &self.meta_location_generator,
+            &column_ids,
+            &bloom_index_writer,
+        );
+
+        let row_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let mut compactor = BlockCompactor::new(row_per_block);
+        let mut block_metas = vec![];
+        let mut batch = vec![];
+        let mut batch_rows = 0;
+        loop {
+            let block = source.next().await.transpose()?;
+            if let Some(block) = &block {
+                batch_rows += block.num_rows();
+                batch.push(block.clone());
+            }
+            if batch_rows > 0 && (batch_rows >= row_per_block || block.is_none()) {
+                let rows = DataBlock::concat_blocks(&std::mem::take(&mut batch))?;
+                batch_rows = 0;
+                if let Some(inserted) = mutator.probe(&rows).await? {
+                    for block in compactor.compact(inserted)?.unwrap_or_default() {
+                        block_metas.push(block_writer.write(block).await?);
+                    }
+                }
+            }
+            if block.is_none() {
+                break;
+            }
+        }
+        for block in compactor.finish()?.unwrap_or_default() {
+            block_metas.push(block_writer.write(block).await?);
+        }
+        Ok(block_metas)
+    }
+
+    /// Commits the inserted blocks to a table without any snapshot.
+    async fn append_merged_blocks(
+        &self,
+        ctx: &Arc<QueryContext>,
+        plan: &MergeIntoPlan,
+        block_metas: Vec<BlockMeta>,
+    ) -> Result<()> {
+        if block_metas.is_empty() {
+            return Ok(());
+        }
+        let data_accessor = ctx.get_storage_operator()?;
+        let summary = reduce_block_metas(&block_metas)?;
+        let segment = SegmentInfo::new(block_metas, summary);
+        let segment_info_cache = ctx.get_storage_cache_manager().get_table_segment_cache();
+        let seg_writer = SegmentWriter::new(
+            &data_accessor,
+            &self.meta_location_generator,
+            &segment_info_cache,
+        );
+        let (segment_location, _) = seg_writer.write_segment(segment.clone()).await?;
+
+        let operation_log = vec![AppendOperationLogEntry::new(
+            segment_location,
+            Arc::new(segment),
+        )];
+        self.do_commit(ctx.clone(), &plan.catalog_name, operation_log, None, false)
+            .await
+    }
+}
@@ -0,0 +1,129 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Series;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::operations::mutation::block_filter::all_the_columns_ids;
+use crate::storages::fuse::FuseTable;
+
+/// Applies the `update_list` to the rows of the block that satisfy the `filter_expr`.
+///
+/// Returns the updated block, or `None` if none of the rows should be updated.
+pub async fn update_block(
+    table: &FuseTable,
+    block_meta: &BlockMeta,
+    ctx: &Arc<QueryContext>,
+    filter_column_ids: Vec<usize>,
+    filter_expr: Option<&Expression>,
+    update_list: &[(usize, Expression)],
+) -> Result<Option<DataBlock>> {
+    let schema = table.table_info.schema();
+
+    if let Some(filter_expr) = filter_expr {
+        // read the cols that we are going to filtering on, to check if any of the rows
+        // should be updated before reading the whole block
+        let col_ids = if filter_column_ids.is_empty() {
+            // nullary filter expression, e.g. `update t set c = 1 where now()`
+            all_the_columns_ids(table)
+        } else {
+            filter_column_ids
+        };
+        let reader = table.create_block_reader(ctx, col_ids)?;
+        let data_block = reader.read_with_block_meta(block_meta).await?;
+        if !any_row_matches(ctx, &schema, &data_block, filter_expr)? {
+            return Ok(None);
+        }
+    }
+
+    let mut exprs = Vec::with_capacity(schema.num_fields());
+    for (idx, field) in schema.fields().iter().enumerate() {
+        let column = Expression::Column(field.name().clone());
+        let expr = match update_list.iter().find(|(col_idx, _)| *col_idx == idx) {
+            None => column,
+            Some((_, value)) => {
+                let value = Expression::Cast {
+                    expr: Box::new(value.clone()),
+                    data_type: field.data_type().clone(),
+                    pg_style: false,
+                };
+                // keep the original value of the rows that do not satisfy the filter
+                let value = match filter_expr {
+                    None => value,
+                    Some(filter_expr) => Expression::create_scalar_function("if", vec![
+                        filter_expr.clone(),
+                        value,
+                        column,
+                    ]),
+                };
+                Expression::Alias(field.name().clone(), Box::new(value))
+            }
+        };
+        exprs.push(expr);
+    }
+
+    let whole_table_proj = all_the_columns_ids(table);
+    let whole_block_reader = table.create_block_reader(ctx, whole_table_proj)?;
+    let whole_block = whole_block_reader.read_with_block_meta(block_meta).await?;
+
+    let expr_exec = ExpressionExecutor::try_create(
+        ctx.clone(),
+        "update expression executor",
+        schema.clone(),
+        schema,
+        exprs,
+        true,
+    )?;
+    let updated_block = expr_exec.execute(&whole_block)?;
+    Ok(Some(updated_block))
+}
+
+fn any_row_matches(
+    ctx: &Arc<QueryContext>,
+    schema: &DataSchemaRef,
+    data_block: &DataBlock,
+    filter_expr: &Expression,
+) -> Result<bool> {
+    let expr_field = filter_expr.to_data_field(schema)?;
+    let expr_schema = DataSchemaRefExt::create(vec![expr_field]);
+
+    let expr_exec = ExpressionExecutor::try_create(
+        ctx.clone(),
+        "filter expression executor (update) ",
+        schema.clone(),
+        expr_schema,
+        vec![filter_expr.clone()],
+        false,
+    )?;
+    let filter_result = expr_exec.execute(data_block)?;
+
+    let predicates = DataBlock::cast_to_nonull_boolean(filter_result.column(0))?;
+    if let Some(const_bool) = DataBlock::try_as_const_bool(&predicates)? {
+        return Ok(const_bool);
+    }
+
+    let boolean_col: &BooleanColumn = Series::check_get(&predicates)?;
+    let values = boolean_col.values();
+    Ok(values.len() > values.null_count())
+}
@@ -0,0 +1,587 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::remove_nullable;
+use common_datavalues::BooleanColumn;
+use common_datavalues::Column;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Series;
+use common_datavalues::SmallVu8;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::MergeIntoPlan;
+use common_planners::MergeMatchedOperation;
+use common_planners::RequireColumnsVisitor;
+use common_planners::RewriteHelper;
+
+use super::block_filter::all_the_columns_ids;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::Location;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::accumulator;
+use crate::storages::fuse::FuseTable;
+
+/// Merges the source rows into the blocks of the target table.
+///
+/// The source is probed batch by batch as it is streamed in. For each batch, the blocks of the
+/// target table are pruned by the range of its join keys, the rows of the remaining blocks are
+/// hashed by the join keys, which the source rows probe to find out the matched rows. The
+/// unmatched source rows are inserted right away, while the matched ones are kept with the
+/// blocks they are matched with, which are merged after the whole source is probed.
+pub struct MergeIntoMutator<'a> {
+    ctx: &'a Arc<QueryContext>,
+    plan: &'a MergeIntoPlan,
+    table: &'a FuseTable,
+    schema: DataSchemaRef,
+    snapshot: Option<Arc<TableSnapshot>>,
+    // the columns of the target table which the join keys and the join filter depend on
+    probe_column_ids: Vec<usize>,
+    // the target keys referring to the columns by the names of the table, `None` if the key
+    // depends on none of the columns, which prunes nothing
+    pruning_keys: Vec<Option<Expression>>,
+    probed_blocks: HashMap<Location, ProbedBlock>,
+}
+
+/// A block of the target table that has been probed by the source rows.
+struct ProbedBlock {
+    seg_idx: usize,
+    block_meta: BlockMeta,
+    // the columns of the block which the probing depends on
+    block: DataBlock,
+    // the rows of the block hashed by the join keys
+    rows: HashMap<SmallVu8, Vec<u32>>,
+    matched: Vec<bool>,
+    // the matched rows of the block, and the source rows they are matched with, in pairs
+    target_indices: Vec<u32>,
+    source_rows: Vec<DataBlock>,
+}
+
+impl<'a> MergeIntoMutator<'a> {
+    pub fn try_create(
+        ctx: &'a Arc<QueryContext>,
+        plan: &'a MergeIntoPlan,
+        table: &'a FuseTable,
+        snapshot: Option<Arc<TableSnapshot>>,
+    ) -> Result<Self> {
+        let schema = table.table_info.schema();
+
+        let mut probe_column_ids = vec![];
+        let mut pruning_keys = Vec::with_capacity(plan.target_keys.len());
+        for expr in &plan.target_keys {
+            let mut pruning_key = None;
+            for column in RequireColumnsVisitor::collect_columns_from_expr(expr)? {
+                if let Some(idx) = plan.target_columns.iter().position(|c| *c == column) {
+                    probe_column_ids.push(idx);
+                    let key = pruning_key.as_ref().unwrap_or(expr);
+                    pruning_key = Some(RewriteHelper::rewrite_column_expr(
+                        key,
+                        &column,
+                        schema.field(idx).name(),
+                    )?);
+                }
+            }
+            pruning_keys.push(pruning_key);
+        }
+        if let Some(join_filter) = &plan.join_filter {
+            for column in RequireColumnsVisitor::collect_columns_from_expr(join_filter)? {
+                if let Some(idx) = plan.target_columns.iter().position(|c| *c == column) {
+                    probe_column_ids.push(idx);
+                }
+            }
+        }
+        if probe_column_ids.is_empty() {
+            // nullary join keys, e.g. `on 1 = s.id`, one column is read for the number of rows
+            probe_column_ids.push(0);
+        }
+        probe_column_ids.sort_unstable();
+        probe_column_ids.dedup();
+
+        Ok(Self {
+            ctx,
+            plan,
+            table,
+            schema,
+            snapshot,
+            probe_column_ids,
+            pruning_keys,
+            probed_blocks: HashMap::new(),
+        })
+    }
+
+    /// Probes the blocks of the target table with a batch of the source rows.
+    ///
+    /// Returns the rows to be inserted by the `WHEN NOT MATCHED` clauses, or `None` if there is
+    /// nothing to insert.
+    pub async fn probe(&mut self, source: &DataBlock) -> Result<Option<DataBlock>> {
+        let keys = eval_block(
+            self.ctx,
+            &self.plan.source_schema,
+            source,
+            &self.plan.source_keys,
+        )?;
+        let source_keys = build_keys(keys.columns(), source.num_rows())?;
+        let mut source_matched = vec![false; source.num_rows()];
+        // NULL never equals to anything, the rows are left unmatched
+        if source_keys.iter().any(Option::is_some) {
+            for (seg_idx, block_meta) in self.prune(&keys).await? {
+                self.probe_block(
+                    seg_idx,
+                    block_meta,
+                    source,
+                    &source_keys,
+                    &mut source_matched,
+                )
+                .await?;
+            }
+        }
+        self.unmatched_rows(source, &source_matched)
+    }
+
+    /// Returns the blocks matched by any of the source rows, it should be called after the
+    /// whole source is probed.
+    pub fn matched_blocks(&mut self) -> Vec<(usize, Location)> {
+        self.probed_blocks
+            .retain(|_, probed| !probed.target_indices.is_empty());
+        self.probed_blocks
+            .iter()
+            .map(|(location, probed)| (probed.seg_idx, location.clone()))
+            .collect()
+    }
+
+    /// Prunes the blocks of the target table, by the range of the join keys of the source rows.
+    async fn prune(&self, keys: &DataBlock) -> Result<Vec<(usize, BlockMeta)>> {
+        let snapshot = match &self.snapshot {
+            None => return Ok(vec![]),
+            Some(snapshot) => snapshot.clone(),
+        };
+
+        let stats = accumulator::columns_statistics(keys)?;
+        let mut filter: Option<Expression> = None;
+        for (idx, pruning_key) in self.pruning_keys.iter().enumerate() {
+            let (key, col_stats) = match (pruning_key, stats.get(&(idx as u32))) {
+                (Some(key), Some(col_stats)) if !col_stats.min.is_null() => (key, col_stats),
+                _ => continue,
+            };
+            let data_type = remove_nullable(keys.schema().field(idx).data_type());
+            let min =
+                Expression::create_literal_with_type(col_stats.min.clone(), data_type.clone());
+            let max = Expression::create_literal_with_type(col_stats.max.clone(), data_type);
+            let predicate = if col_stats.min == col_stats.max {
+                key.eq(min)
+            } else {
+                key.gt_eq(min).and(key.lt_eq(max))
+            };
+            filter = Some(match filter {
+                None => predicate,
+                Some(filter) => filter.and(predicate),
+            });
+        }
+
+        let push_downs = filter.map(|filter| Extras {
+            filters: vec![filter],
+            ..Extras::default()
+        });
+        BlockPruner::new(snapshot)
+            .apply(self.ctx.as_ref(), self.schema.clone(), &push_downs)
+            .await
+    }
+
+    async fn probe_block(
+        &mut self,
+        seg_idx: usize,
+        block_meta: BlockMeta,
+        source: &DataBlock,
+        source_keys: &[Option<SmallVu8>],
+        source_matched: &mut [bool],
+    ) -> Result<()> {
+        let location = block_meta.location.clone();
+        let mut probed = match self.probed_blocks.remove(&location) {
+            Some(probed) => probed,
+            None => self.read_probed_block(seg_idx, block_meta).await?,
+        };
+
+        let mut target_indices = vec![];
+        let mut source_indices = vec![];
+        for (source_row, key) in source_keys.iter().enumerate() {
+            if let Some(target_rows) = key.as_ref().and_then(|key| probed.rows.get(key)) {
+                for target_row in target_rows {
+                    target_indices.push(*target_row);
+                    source_indices.push(source_row as u32);
+                }
+            }
+        }
+        if let Some(join_filter) = &self.plan.join_filter {
+            if !target_indices.is_empty() {
+                let joined = join(&probed.block, &target_indices, source, &source_indices)?;
+                let passed = eval_predicate(self.ctx, &joined, join_filter)?;
+                (target_indices, source_indices) = target_indices
+                    .iter()
+                    .zip(&source_indices)
+                    .zip(passed)
+                    .filter(|(_, passed)| *passed)
+                    .map(|((target_row, source_row), _)| (*target_row, *source_row))
+                    .unzip();
+            }
+        }
+
+        if !target_indices.is_empty() {
+            // a row of the target table can not be merged with more than one source row
+            for (target_row, source_row) in target_indices.iter().zip(&source_indices) {
+                let target_row = *target_row as usize;
+                if probed.matched[target_row] {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "a row of table {} is matched by more than one row of the source of MERGE INTO",
+                        self.table.table_info.desc
+                    )));
+                }
+                probed.matched[target_row] = true;
+                source_matched[*source_row as usize] = true;
+            }
+            probed.target_indices.extend(target_indices);
+            probed
+                .source_rows
+                .push(DataBlock::block_take_by_indices(source, &source_indices)?);
+        }
+        // the probed block is kept, even if nothing is matched, to avoid reading it again
+        // for the other batches of the source
+        self.probed_blocks.insert(location, probed);
+        Ok(())
+    }
+
+    async fn read_probed_block(
+        &self,
+        seg_idx: usize,
+        block_meta: BlockMeta,
+    ) -> Result<ProbedBlock> {
+        let col_ids = self.probe_column_ids.clone();
+        let reader = self.table.create_block_reader(self.ctx, col_ids.clone())?;
+        let block = reader.read_with_block_meta(&block_meta).await?;
+        let block = self.rename_target_block(&col_ids, block);
+        let keys = eval_block(self.ctx, block.schema(), &block, &self.plan.target_keys)?;
+
+        let mut rows: HashMap<SmallVu8, Vec<u32>> = HashMap::new();
+        for (row, key) in build_keys(keys.columns(), block.num_rows())?
+            .into_iter()
+            .enumerate()
+        {
+            if let Some(key) = key {
+                rows.entry(key).or_default().push(row as u32);
+            }
+        }
+        let matched = vec![false; block.num_rows()];
+        Ok(ProbedBlock {
+            seg_idx,
+            block_meta,
+            block,
+            rows,
+            matched,
+            target_indices: vec![],
+            source_rows: vec![],
+        })
+    }
+
+    /// Merges the matched source rows into the block, by the `WHEN MATCHED` clauses.
+    ///
+    /// Returns the merged block, or `None` if none of the rows of the block is changed.
+    pub async fn merge_block(&mut self, location: &Location) -> Result<Option<DataBlock>> {
+        let probed = match self.probed_blocks.remove(location) {
+            Some(probed) if !probed.target_indices.is_empty() => probed,
+            _ => return Ok(None),
+        };
+        let target_indices = probed.target_indices;
+
+        let whole_table_proj = all_the_columns_ids(self.table);
+        let whole_block_reader = self
+            .table
+            .create_block_reader(self.ctx, whole_table_proj.clone())?;
+        let whole_block = whole_block_reader
+            .read_with_block_meta(&probed.block_meta)
+            .await?;
+        let target = self.rename_target_block(&whole_table_proj, whole_block.clone());
+
+        // the source rows are kept in the same order as the target rows they are matched with
+        let source = DataBlock::concat_blocks(&probed.source_rows)?;
+        let source_indices = (0..source.num_rows() as u32).collect::<Vec<_>>();
+        let joined = join(&target, &target_indices, &source, &source_indices)?;
+
+        // each pair of the matched rows is merged by the first clause it satisfies
+        let clauses = &self.plan.matched_clauses;
+        let num_pairs = joined.num_rows();
+        let mut conditions = Vec::with_capacity(clauses.len());
+        for clause in clauses {
+            conditions.push(match &clause.condition {
+                Some(condition) => eval_predicate(self.ctx, &joined, condition)?,
+                None => vec![true; num_pairs],
+            });
+        }
+        let clause_of_pairs = (0..num_pairs)
+            .map(|pair| conditions.iter().position(|c| c[pair]))
+            .collect::<Vec<_>>();
+        if clause_of_pairs.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+
+        // the rows updated by each of the clauses are placed after the rows of the block,
+        // the merged block is taken from them.
+        let num_rows = whole_block.num_rows();
+        let mut blocks = vec![whole_block];
+        let mut offsets = vec![None; clauses.len()];
+        let mut offset = num_rows;
+        for (idx, clause) in clauses.iter().enumerate() {
+            if let MergeMatchedOperation::Update { update_list } = &clause.operation {
+                if clause_of_pairs.contains(&Some(idx)) {
+                    blocks.push(self.update_rows(&joined, update_list)?);
+                    offsets[idx] = Some(offset);
+                    offset += num_pairs;
+                }
+            }
+        }
+
+        let mut merged_rows = vec![None; num_rows];
+        for (pair, clause) in clause_of_pairs.iter().enumerate() {
+            if let Some(clause) = clause {
+                merged_rows[target_indices[pair] as usize] = Some((*clause, pair));
+            }
+        }
+        let mut indices = Vec::with_capacity(num_rows);
+        for (row, merged) in merged_rows.into_iter().enumerate() {
+            match merged {
+                None => indices.push(row as u32),
+                Some((clause, pair)) => {
+                    // the row is removed, if it is merged by `DELETE`
+                    if let Some(offset) = offsets[clause] {
+                        indices.push((offset + pair) as u32);
+                    }
+                }
+            }
+        }
+        Ok(Some(take_rows(&self.schema, &blocks, &indices)?))
+    }
+
+    /// Generates the rows to be inserted from the unmatched source rows, by the `WHEN NOT MATCHED` clauses.
+    fn unmatched_rows(&self, source: &DataBlock, matched: &[bool]) -> Result<Option<DataBlock>> {
+        let clauses = &self.plan.unmatched_clauses;
+        if clauses.is_empty() {
+            return Ok(None);
+        }
+        let rows = matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| !**matched)
+            .map(|(row, _)| row as u32)
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let unmatched = DataBlock::block_take_by_indices(source, &rows)?;
+
+        let num_rows = unmatched.num_rows();
+        let mut conditions = Vec::with_capacity(clauses.len());
+        for clause in clauses {
+            conditions.push(match &clause.condition {
+                Some(condition) => eval_predicate(self.ctx, &unmatched, condition)?,
+                None => vec![true; num_rows],
+            });
+        }
+        let clause_of_rows = (0..num_rows)
+            .map(|row| conditions.iter().position(|c| c[row]))
+            .collect::<Vec<_>>();
+
+        let mut blocks = vec![];
+        let mut indices = vec![];
+        for (idx, clause) in clauses.iter().enumerate() {
+            let rows = (0..num_rows)
+                .filter(|row| clause_of_rows[*row] == Some(idx))
+                .collect::<Vec<_>>();
+            if rows.is_empty() {
+                continue;
+            }
+            let exprs = self
+                .schema
+                .fields()
+                .iter()
+                .zip(&clause.values)
+                .map(|(field, value)| cast_to_field(field, value.clone()))
+                .collect::<Vec<_>>();
+            let expr_exec = ExpressionExecutor::try_create(
+                self.ctx.clone(),
+                "merge into insert executor",
+                unmatched.schema().clone(),
+                self.schema.clone(),
+                exprs,
+                true,
+            )?;
+            let offset = blocks.len() * num_rows;
+            indices.extend(rows.iter().map(|row| (offset + row) as u32));
+            blocks.push(expr_exec.execute(&unmatched)?);
+        }
+        if blocks.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(take_rows(&self.schema, &blocks, &indices)?))
+    }
+
+    fn update_rows(
+        &self,
+        joined: &DataBlock,
+        update_list: &[(usize, Expression)],
+    ) -> Result<DataBlock> {
+        let mut exprs = Vec::with_capacity(self.schema.num_fields());
+        for (idx, field) in self.schema.fields().iter().enumerate() {
+            let expr = match update_list.iter().find(|(col_idx, _)| *col_idx == idx) {
+                None => Expression::Alias(
+                    field.name().clone(),
+                    Box::new(Expression::Column(self.plan.target_columns[idx].clone())),
+                ),
+                Some((_, value)) => cast_to_field(field, value.clone()),
+            };
+            exprs.push(expr);
+        }
+        let expr_exec = ExpressionExecutor::try_create(
+            self.ctx.clone(),
+            "merge into update executor",
+            joined.schema().clone(),
+            self.schema.clone(),
+            exprs,
+            true,
+        )?;
+        expr_exec.execute(joined)
+    }
+
+    /// Renames the columns of the block read from the target table, as the expressions refer to them.
+    fn rename_target_block(&self, col_ids: &[usize], block: DataBlock) -> DataBlock {
+        let fields = col_ids
+            .iter()
+            .map(|idx| {
+                let data_type = self.schema.field(*idx).data_type().clone();
+                DataField::new(&self.plan.target_columns[*idx], data_type)
+            })
+            .collect();
+        DataBlock::create(DataSchemaRefExt::create(fields), block.columns().to_vec())
+    }
+}
+
+/// Joins the rows of the target block and the source block in pairs.
+fn join(
+    target: &DataBlock,
+    target_indices: &[u32],
+    source: &DataBlock,
+    source_indices: &[u32],
+) -> Result<DataBlock> {
+    let mut fields = target.schema().fields().clone();
+    fields.extend(source.schema().fields().iter().cloned());
+    let mut columns = Vec::with_capacity(fields.len());
+    for column in target.columns() {
+        columns.push(Series::take(column, target_indices)?);
+    }
+    for column in source.columns() {
+        columns.push(Series::take(column, source_indices)?);
+    }
+    Ok(DataBlock::create(DataSchemaRefExt::create(fields), columns))
+}
+
+fn cast_to_field(field: &DataField, value: Expression) -> Expression {
+    Expression::Alias(
+        field.name().clone(),
+        Box::new(Expression::Cast {
+            expr: Box::new(value),
+            data_type: field.data_type().clone(),
+            pg_style: false,
+        }),
+    )
+}
+
+fn eval_block(
+    ctx: &Arc<QueryContext>,
+    schema: &DataSchemaRef,
+    block: &DataBlock,
+    exprs: &[Expression],
+) -> Result<DataBlock> {
+    let fields = exprs
+        .iter()
+        .map(|expr| expr.to_data_field(schema))
+        .collect::<Result<Vec<_>>>()?;
+    let expr_exec = ExpressionExecutor::try_create(
+        ctx.clone(),
+        "merge into expression executor",
+        schema.clone(),
+        DataSchemaRefExt::create(fields),
+        exprs.to_vec(),
+        false,
+    )?;
+    expr_exec.execute(block)
+}
+
+fn eval_predicate(
+    ctx: &Arc<QueryContext>,
+    block: &DataBlock,
+    predicate: &Expression,
+) -> Result<Vec<bool>> {
+    let result = eval_block(ctx, block.schema(), block, &[predicate.clone()])?;
+    let predicates = DataBlock::cast_to_nonull_boolean(result.column(0))?;
+    if let Some(const_bool) = DataBlock::try_as_const_bool(&predicates)? {
+        return Ok(vec![const_bool; block.num_rows()]);
+    }
+    let boolean_col: &BooleanColumn = Series::check_get(&predicates)?;
+    Ok(boolean_col.values().iter().collect())
+}
+
+/// Serializes the join keys of each row, `None` if any of the keys is NULL.
+fn build_keys(keys: &[ColumnRef], num_rows: usize) -> Result<Vec<Option<SmallVu8>>> {
+    let columns = keys
+        .iter()
+        .map(|c| c.convert_full_column())
+        .collect::<Vec<_>>();
+    let column_refs = columns.iter().collect::<Vec<_>>();
+    let serialized = HashMethodSerializer::default().build_keys(&column_refs, num_rows)?;
+    Ok(serialized
+        .into_iter()
+        .enumerate()
+        .map(|(row, key)| {
+            if columns.iter().any(|c| c.null_at(row)) {
+                None
+            } else {
+                Some(key)
+            }
+        })
+        .collect())
+}
+
+/// Takes the rows by `indices` from the blocks, as if they were concatenated.
+fn take_rows(schema: &DataSchemaRef, blocks: &[DataBlock], indices: &[u32]) -> Result<DataBlock> {
+    let mut columns = Vec::with_capacity(schema.num_fields());
+    for idx in 0..schema.num_fields() {
+        let parts = blocks
+            .iter()
+            .map(|block| block.column(idx).convert_full_column())
+            .collect::<Vec<_>>();
+        let column = Series::concat(&parts)?;
+        columns.push(Series::take(&column, indices)?);
+    }
+    Ok(DataBlock::create(schema.clone(), columns))
+}
@@ -13,8 +13,14 @@
 //  limitations under the License.
 
 pub mod block_filter;
+pub mod block_update;
 pub mod compact_mutator;
+pub mod merge_into_mutator;
 pub mod mutations_collector;
+pub mod recluster_mutator;
 
 pub use block_filter::delete_from_block;
+pub use block_update::update_block;
 pub use compact_mutator::CompactMutator;
+pub use merge_into_mutator::MergeIntoMutator;
+pub use recluster_mutator::ReclusterMutator;
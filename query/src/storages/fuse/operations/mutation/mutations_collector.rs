@@ -13,6 +13,7 @@
 //  limitations under the License.
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
@@ -20,12 +21,13 @@ use common_exception::Result;
 use opendal::Operator;
 
 use crate::sessions::QueryContext;
-use crate::storages::fuse::io::write_meta;
 use crate::storages::fuse::io::BlockWriter;
+use crate::storages::fuse::io::BloomIndexWriter;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::io::SegmentWriter;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::Location;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::TableSnapshot;
@@ -46,9 +48,12 @@ pub type SegmentIndex = usize;
 
 pub struct DeletionCollector<'a> {
     mutations: HashMap<SegmentIndex, Vec<Replacement>>,
+    appended_blocks: Vec<BlockMeta>,
     ctx: &'a QueryContext,
     location_generator: &'a TableMetaLocationGenerator,
     base_snapshot: &'a TableSnapshot,
+    column_ids: ColumnIds,
+    bloom_index_writer: Option<BloomIndexWriter>,
     data_accessor: Operator,
 }
 
@@ -57,18 +62,24 @@ impl<'a> DeletionCollector<'a> {
         ctx: &'a QueryContext,
         location_generator: &'a TableMetaLocationGenerator,
         base_snapshot: &'a TableSnapshot,
+        column_ids: ColumnIds,
+        bloom_index_writer: Option<BloomIndexWriter>,
     ) -> Result<Self> {
         let data_accessor = ctx.get_storage_operator()?;
         Ok(Self {
             mutations: HashMap::new(),
+            appended_blocks: vec![],
             ctx,
             location_generator,
             base_snapshot,
+            column_ids,
+            bloom_index_writer,
             data_accessor,
         })
     }
 
-    pub async fn into_new_snapshot(self) -> Result<(TableSnapshot, String)> {
+    /// Writes the mutated segments, and returns the new snapshot based on the base snapshot.
+    pub async fn into_new_snapshot(self) -> Result<TableSnapshot> {
         let snapshot = self.base_snapshot;
         let mut new_snapshot = TableSnapshot::from_previous(snapshot);
         let segment_reader = MetaReaders::segment_info_reader(self.ctx);
@@ -83,45 +94,58 @@ impl<'a> DeletionCollector<'a> {
             &segment_info_cache,
         );
 
+        // the segments whose blocks are all removed
+        let mut removed_segments = HashSet::new();
         for (seg_idx, replacements) in self.mutations {
             let seg_loc = &snapshot.segments[seg_idx];
             let segment = segment_reader.read(&seg_loc.0, None, seg_loc.1).await?;
 
-            let block_positions = segment
-                .blocks
-                .iter()
-                .enumerate()
-                .map(|(idx, meta)| (&meta.location, idx))
+            let mut replacements = replacements
+                .into_iter()
+                .map(|r| (r.original_block_loc, r.new_block_meta))
                 .collect::<HashMap<_, _>>();
-
-            let mut new_segment = SegmentInfo::new(segment.blocks.clone(), segment.summary.clone());
-
-            for replacement in replacements {
-                let position = block_positions
-                    .get(&replacement.original_block_loc)
-                    .ok_or_else(|| {
-                        ErrorCode::LogicalError(format!(
-                            "block location not found {:?}",
-                            &replacement.original_block_loc
-                        ))
-                    })?;
-                if let Some(block_meta) = replacement.new_block_meta {
-                    new_segment.blocks[*position] = block_meta;
-                } else {
-                    new_segment.blocks.remove(*position);
+            let mut new_blocks = Vec::with_capacity(segment.blocks.len());
+            for block_meta in &segment.blocks {
+                match replacements.remove(&block_meta.location) {
+                    None => new_blocks.push(block_meta.clone()),
+                    Some(Some(new_block_meta)) => new_blocks.push(new_block_meta),
+                    Some(None) => {}
                 }
             }
+            if let Some(location) = replacements.keys().next() {
+                return Err(ErrorCode::LogicalError(format!(
+                    "block location not found {:?}",
+                    location
+                )));
+            }
 
-            if new_segment.blocks.is_empty() {
+            if new_blocks.is_empty() {
                 // remove the segment if no blocks there
-                new_snapshot.segments.remove(seg_idx);
+                removed_segments.insert(seg_idx);
             } else {
-                let new_summary = reduce_block_metas(&new_segment.blocks)?;
-                new_segment.summary = new_summary;
+                let new_summary = reduce_block_metas(&new_blocks)?;
+                let new_segment = SegmentInfo::new(new_blocks, new_summary);
                 let new_segment_location = seg_writer.write_segment(new_segment).await?;
                 new_snapshot.segments[seg_idx] = new_segment_location;
             }
         }
+        if !removed_segments.is_empty() {
+            new_snapshot.segments = std::mem::take(&mut new_snapshot.segments)
+                .into_iter()
+                .enumerate()
+                .filter(|(seg_idx, _)| !removed_segments.contains(seg_idx))
+                .map(|(_, seg_loc)| seg_loc)
+                .collect();
+        }
+
+        if !self.appended_blocks.is_empty() {
+            // the appended blocks are kept in a new segment, which is placed in front of
+            // the others, in the same way as appending does.
+            let summary = reduce_block_metas(&self.appended_blocks)?;
+            let new_segment = SegmentInfo::new(self.appended_blocks, summary);
+            let new_segment_location = seg_writer.write_segment(new_segment).await?;
+            new_snapshot.segments.insert(0, new_segment_location);
+        }
 
         let mut new_segment_summaries = vec![];
         for (loc, ver) in &new_snapshot.segments {
@@ -133,14 +157,7 @@ impl<'a> DeletionCollector<'a> {
         // update the summary of new snapshot
         let new_summary = reduce_statistics(&new_segment_summaries)?;
         new_snapshot.summary = new_summary;
-
-        // write the new snapshot out (and keep it in undo log)
-        let snapshot_loc = self.location_generator.snapshot_location_from_uuid(
-            &new_snapshot.snapshot_id,
-            new_snapshot.format_version(),
-        )?;
-        write_meta(&self.data_accessor, &snapshot_loc, &new_snapshot).await?;
-        Ok((new_snapshot, snapshot_loc))
+        Ok(new_snapshot)
     }
 
     /// Replaces
@@ -156,7 +173,12 @@ impl<'a> DeletionCollector<'a> {
         let new_block_meta = if replace_with.num_rows() == 0 {
             None
         } else {
-            let block_writer = BlockWriter::new(&self.data_accessor, self.location_generator);
+            let block_writer = BlockWriter::new(
+                &self.data_accessor,
+                self.location_generator,
+                &self.column_ids,
+                &self.bloom_index_writer,
+            );
             Some(block_writer.write(replace_with).await?)
         };
         let original_block_loc = location_of_block_to_be_replaced;
@@ -169,4 +191,36 @@ impl<'a> DeletionCollector<'a> {
             });
         Ok(())
     }
+
+    /// Appends a new block `block`, which will be kept in a new segment
+    pub async fn append(&mut self, block: DataBlock) -> Result<()> {
+        if block.num_rows() == 0 {
+            return Ok(());
+        }
+        let block_writer = BlockWriter::new(
+            &self.data_accessor,
+            self.location_generator,
+            &self.column_ids,
+            &self.bloom_index_writer,
+        );
+        let block_meta = block_writer.write(block).await?;
+        self.appended_blocks.push(block_meta);
+        Ok(())
+    }
+
+    /// Removes the block located at `block_location` of segment indexed by `seg_idx`
+    pub fn remove(&mut self, seg_idx: usize, block_location: Location) {
+        self.mutations
+            .entry(seg_idx)
+            .or_default()
+            .push(Replacement {
+                original_block_loc: block_location,
+                new_block_meta: None,
+            });
+    }
+
+    /// Appends a block that has already been written, which will be kept in a new segment
+    pub fn append_block_meta(&mut self, block_meta: BlockMeta) {
+        self.appended_blocks.push(block_meta);
+    }
 }
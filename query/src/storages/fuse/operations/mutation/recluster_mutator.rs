@@ -0,0 +1,282 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use common_planners::Expression;
+use opendal::Operator;
+
+use super::block_filter::all_the_columns_ids;
+use super::mutations_collector::DeletionCollector;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::BlockWriter;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::accumulator::BlockStatistics;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_RECLUSTER_BLOCK_SIZE;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_RECLUSTER_BLOCK_SIZE;
+use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+
+/// Re-sorts the blocks whose ranges of cluster key overlap, by the cluster key.
+///
+/// The blocks are grouped by the union of their ranges, i.e. a block joins a group if its
+/// range overlaps with the range covered by the group. Since no other block overlaps with
+/// the range of a group, the blocks re-sorted from the groups do not overlap with any block.
+pub struct ReclusterMutator<'a> {
+    ctx: &'a Arc<QueryContext>,
+    table: &'a FuseTable,
+    base_snapshot: &'a Arc<TableSnapshot>,
+    data_accessor: Operator,
+    cluster_key_id: u32,
+    /// positions of the cluster keys in the block extended by `key_executor`
+    cluster_key_index: Vec<usize>,
+    /// evaluates the cluster keys which are not columns of the table
+    key_executor: Option<ExpressionExecutor>,
+    /// removes the columns added by `key_executor`
+    projection: Option<ExpressionExecutor>,
+    sort_descs: Vec<SortColumnDescription>,
+    row_per_block: usize,
+    /// the size of blocks to be re-sorted, unlimited if `None`
+    block_size_limit: Option<usize>,
+}
+
+impl<'a> ReclusterMutator<'a> {
+    pub fn try_create(
+        ctx: &'a Arc<QueryContext>,
+        table: &'a FuseTable,
+        base_snapshot: &'a Arc<TableSnapshot>,
+        is_final: bool,
+    ) -> Result<Self> {
+        let cluster_key_id = table.cluster_key_meta.as_ref().map_or(0, |(id, _)| *id);
+        let input_schema = table.table_info.schema();
+        let mut merged = input_schema.fields().clone();
+        let mut cluster_key_index = Vec::with_capacity(table.cluster_keys.len());
+        for expr in &table.cluster_keys {
+            let cname = expr.column_name();
+            let index = match merged.iter().position(|x| x.name() == &cname) {
+                None => {
+                    merged.push(expr.to_data_field(&input_schema)?);
+                    merged.len() - 1
+                }
+                Some(idx) => idx,
+            };
+            cluster_key_index.push(index);
+        }
+
+        let output_schema = DataSchemaRefExt::create(merged);
+        let (key_executor, projection) = if output_schema != input_schema {
+            let key_executor = ExpressionExecutor::try_create(
+                ctx.clone(),
+                "evaluate cluster keys",
+                input_schema.clone(),
+                output_schema.clone(),
+                table.cluster_keys.clone(),
+                false,
+            )?;
+            key_executor.validate()?;
+
+            let exprs: Vec<Expression> = input_schema
+                .fields()
+                .iter()
+                .map(|f| Expression::Column(f.name().to_owned()))
+                .collect();
+            let projection = ExpressionExecutor::try_create(
+                ctx.clone(),
+                "remove unused columns",
+                output_schema,
+                input_schema,
+                exprs,
+                true,
+            )?;
+            projection.validate()?;
+            (Some(key_executor), Some(projection))
+        } else {
+            (None, None)
+        };
+
+        let sort_descs = table
+            .cluster_keys
+            .iter()
+            .map(|expr| SortColumnDescription {
+                column_name: expr.column_name(),
+                asc: true,
+                nulls_first: false,
+            })
+            .collect();
+
+        let block_size_limit = if is_final {
+            None
+        } else {
+            Some(table.get_option(
+                FUSE_OPT_KEY_RECLUSTER_BLOCK_SIZE,
+                DEFAULT_RECLUSTER_BLOCK_SIZE,
+            ))
+        };
+
+        Ok(Self {
+            ctx,
+            table,
+            base_snapshot,
+            data_accessor: ctx.get_storage_operator()?,
+            cluster_key_id,
+            cluster_key_index,
+            key_executor,
+            projection,
+            sort_descs,
+            row_per_block: table.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK),
+            block_size_limit,
+        })
+    }
+
+    /// Returns the new snapshot, or [None] if none of the blocks overlaps with the others.
+    pub async fn recluster(&self) -> Result<Option<TableSnapshot>> {
+        let schema = self.table.table_info.schema();
+        let blocks = BlockPruner::new(self.base_snapshot.clone())
+            .apply(self.ctx.as_ref(), schema, &None)
+            .await?;
+        let groups = self.select_groups(&blocks);
+        if groups.is_empty() {
+            return Ok(None);
+        }
+
+        let mut mutation_collector = DeletionCollector::try_create(
+            self.ctx.as_ref(),
+            &self.table.meta_location_generator,
+            self.base_snapshot,
+            self.table.column_ids()?,
+            self.table.bloom_index_writer(self.ctx.as_ref())?,
+        )?;
+        for group in groups {
+            let metas = group.iter().map(|idx| &blocks[*idx].1).collect::<Vec<_>>();
+            for block_meta in self.sort_blocks(&metas).await? {
+                mutation_collector.append_block_meta(block_meta);
+            }
+            for idx in group {
+                let (seg_idx, block_meta) = &blocks[idx];
+                mutation_collector.remove(*seg_idx, block_meta.location.clone());
+            }
+        }
+        Ok(Some(mutation_collector.into_new_snapshot().await?))
+    }
+
+    /// Returns the groups of overlapping blocks to be re-sorted, the deepest first.
+    fn select_groups(&self, blocks: &[(usize, BlockMeta)]) -> Vec<Vec<usize>> {
+        let mut ranges = Vec::with_capacity(blocks.len());
+        for (idx, (_, block_meta)) in blocks.iter().enumerate() {
+            match &block_meta.cluster_stats {
+                Some(stats) if stats.cluster_key_id == self.cluster_key_id => {
+                    ranges.push((&stats.min, &stats.max, idx))
+                }
+                // the blocks written before the cluster key being defined could be of any range,
+                // thus all the blocks are re-sorted together
+                _ => return vec![(0..blocks.len()).collect()],
+            }
+        }
+        ranges.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut groups = vec![];
+        let mut group = vec![];
+        let mut group_max = None;
+        for (min, max, idx) in ranges {
+            match group_max {
+                // blocks that merely touch at the boundary do not overlap
+                Some(group_max_val) if min < group_max_val => {
+                    group.push(idx);
+                    if max > group_max_val {
+                        group_max = Some(max);
+                    }
+                }
+                _ => {
+                    if group.len() > 1 {
+                        groups.push(std::mem::take(&mut group));
+                    }
+                    group = vec![idx];
+                    group_max = Some(max);
+                }
+            }
+        }
+        if group.len() > 1 {
+            groups.push(group);
+        }
+        groups.sort_by(|a, b| b.len().cmp(&a.len()));
+
+        if let Some(limit) = self.block_size_limit {
+            let mut size = 0;
+            let mut selected = 0;
+            for group in &groups {
+                if selected > 0 && size >= limit {
+                    break;
+                }
+                size += group
+                    .iter()
+                    .map(|idx| blocks[*idx].1.block_size as usize)
+                    .sum::<usize>();
+                selected += 1;
+            }
+            groups.truncate(selected);
+        }
+        groups
+    }
+
+    /// Reads and sorts the blocks by the cluster key, and writes them as new blocks.
+    async fn sort_blocks(&self, metas: &[&BlockMeta]) -> Result<Vec<BlockMeta>> {
+        let reader = self
+            .table
+            .create_block_reader(self.ctx, all_the_columns_ids(self.table))?;
+        let mut data_blocks = Vec::with_capacity(metas.len());
+        for block_meta in metas {
+            data_blocks.push(reader.read_with_block_meta(block_meta).await?);
+        }
+        let mut data_block = DataBlock::concat_blocks(&data_blocks)?;
+        if let Some(executor) = &self.key_executor {
+            data_block = executor.execute(&data_block)?;
+        }
+        let data_block = DataBlock::sort_block(&data_block, &self.sort_descs, None)?;
+
+        let column_ids = self.table.column_ids()?;
+        let bloom_index_writer = self.table.bloom_index_writer(self.ctx.as_ref())?;
+        let block_writer = BlockWriter::new(
+            &self.data_accessor,
+            &self.table.meta_location_generator,
+            &column_ids,
+            &bloom_index_writer,
+        );
+        let mut new_metas = vec![];
+        for block in DataBlock::split_block_by_size(&data_block, self.row_per_block)? {
+            let cluster_stats = BlockStatistics::clusters_statistics(
+                self.cluster_key_id,
+                &self.cluster_key_index,
+                &block,
+            )?;
+            let block = match &self.projection {
+                Some(executor) => executor.execute(&block)?,
+                None => block,
+            };
+            new_metas.push(
+                block_writer
+                    .write_with_cluster_stats(block, cluster_stats)
+                    .await?,
+            );
+        }
+        Ok(new_metas)
+    }
+}
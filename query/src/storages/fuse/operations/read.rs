@@ -19,6 +19,7 @@ use std::sync::Arc;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
 use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Extras;
@@ -28,6 +29,7 @@ use common_streams::SendableDataBlockStream;
 use common_tracing::tracing_futures::Instrument;
 use futures::StreamExt;
 
+use crate::common::RuntimeFilter;
 use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::processor::Event;
 use crate::pipelines::new::processors::processor::ProcessorPtr;
@@ -35,6 +37,7 @@ use crate::pipelines::new::processors::Processor;
 use crate::pipelines::new::NewPipeline;
 use crate::pipelines::new::SourcePipeBuilder;
 use crate::sessions::QueryContext;
+use crate::storages::fuse::fuse_part::FusePartInfo;
 use crate::storages::fuse::io::BlockReader;
 use crate::storages::fuse::operations::read::State::Generated;
 use crate::storages::fuse::FuseTable;
@@ -92,6 +95,28 @@ impl FuseTable {
         }
     }
 
+    /// Resolves the runtime filters set to the context by the pipeline builder to the columns
+    /// of the table.
+    fn runtime_filters_of(
+        &self,
+        ctx: &Arc<QueryContext>,
+        projection: &[usize],
+    ) -> Result<Vec<ScanRuntimeFilter>> {
+        let schema = self.table_info.schema();
+        let mut runtime_filters = vec![];
+        for (name, filter) in ctx.get_runtime_filters() {
+            let column_index = schema.index_of(&name)?;
+            if let Some(block_index) = projection.iter().position(|idx| *idx == column_index) {
+                runtime_filters.push(ScanRuntimeFilter {
+                    column_index,
+                    block_index,
+                    filter,
+                });
+            }
+        }
+        Ok(runtime_filters)
+    }
+
     #[inline]
     pub fn do_read2(
         &self,
@@ -99,8 +124,9 @@ impl FuseTable {
         plan: &ReadDataSourcePlan,
         pipeline: &mut NewPipeline,
     ) -> Result<()> {
-        let block_reader =
-            self.create_block_reader(&ctx, self.projection_of_push_downs(&plan.push_downs))?;
+        let projection = self.projection_of_push_downs(&plan.push_downs);
+        let runtime_filters = self.runtime_filters_of(&ctx, &projection)?;
+        let block_reader = self.create_block_reader(&ctx, projection)?;
 
         let parts_len = plan.parts.len();
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
@@ -112,7 +138,12 @@ impl FuseTable {
             let output = OutputPort::create();
             source_builder.add_source(
                 output.clone(),
-                FuseTableSource::create(ctx.clone(), output, block_reader.clone())?,
+                FuseTableSource::create(
+                    ctx.clone(),
+                    output,
+                    block_reader.clone(),
+                    runtime_filters.clone(),
+                )?,
             );
         }
 
@@ -128,12 +159,23 @@ enum State {
     Finish,
 }
 
+/// A runtime filter of hash join on a column of the table.
+#[derive(Clone)]
+struct ScanRuntimeFilter {
+    /// The index of the column in the table schema
+    column_index: usize,
+    /// The index of the column in the blocks read
+    block_index: usize,
+    filter: Arc<RuntimeFilter>,
+}
+
 struct FuseTableSource {
     state: State,
     ctx: Arc<QueryContext>,
     scan_progress: Arc<Progress>,
     block_reader: Arc<BlockReader>,
     output: Arc<OutputPort>,
+    runtime_filters: Vec<ScanRuntimeFilter>,
 }
 
 impl FuseTableSource {
@@ -141,6 +183,7 @@ impl FuseTableSource {
         ctx: Arc<QueryContext>,
         output: Arc<OutputPort>,
         block_reader: Arc<BlockReader>,
+        runtime_filters: Vec<ScanRuntimeFilter>,
     ) -> Result<ProcessorPtr> {
         let scan_progress = ctx.get_scan_progress();
         let mut partitions = ctx.try_get_partitions(1)?;
@@ -150,6 +193,7 @@ impl FuseTableSource {
                 output,
                 block_reader,
                 scan_progress,
+                runtime_filters,
                 state: State::Finish,
             }))),
             false => Ok(ProcessorPtr::create(Box::new(FuseTableSource {
@@ -157,10 +201,38 @@ impl FuseTableSource {
                 output,
                 block_reader,
                 scan_progress,
+                runtime_filters,
                 state: State::ReadData(partitions.remove(0)),
             }))),
         }
     }
+
+    // The part can be skipped if the range of any column can't match its runtime filter.
+    fn is_pruned(&self, part: &PartInfoPtr) -> Result<bool> {
+        let part = FusePartInfo::from_part(part)?;
+        for runtime_filter in self.runtime_filters.iter() {
+            let values = match runtime_filter.filter.values() {
+                None => continue,
+                Some(values) => values,
+            };
+            if let Some(stats) = part.columns_stats.get(&runtime_filter.column_index) {
+                if !values.may_intersect(&stats.min, &stats.max) {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    fn filter_rows(&self, mut data_block: DataBlock) -> Result<DataBlock> {
+        for runtime_filter in self.runtime_filters.iter() {
+            if let Some(values) = runtime_filter.filter.values() {
+                let predicate = values.filter(data_block.column(runtime_filter.block_index));
+                data_block = DataBlock::filter_block(data_block, &predicate)?;
+            }
+        }
+        Ok(data_block)
+    }
 }
 
 #[async_trait::async_trait]
@@ -219,6 +291,8 @@ impl Processor for FuseTableSource {
                 };
                 self.scan_progress.incr(&progress_values);
 
+                let data_block = self.filter_rows(data_block)?;
+
                 self.state = match partitions.is_empty() {
                     true => State::Generated(None, data_block),
                     false => State::Generated(Some(partitions.remove(0)), data_block),
@@ -231,7 +305,14 @@ impl Processor for FuseTableSource {
 
     async fn async_process(&mut self) -> Result<()> {
         match std::mem::replace(&mut self.state, State::Finish) {
-            State::ReadData(part) => {
+            State::ReadData(mut part) => {
+                while self.is_pruned(&part)? {
+                    match self.ctx.try_get_partitions(1)?.pop() {
+                        None => return Ok(()),
+                        Some(next_part) => part = next_part,
+                    }
+                }
+
                 let chunks = self.block_reader.read_columns_data(part.clone()).await?;
                 self.state = State::Deserialize(part, chunks);
                 Ok(())
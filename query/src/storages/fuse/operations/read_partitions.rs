@@ -26,6 +26,7 @@ use crate::sessions::QueryContext;
 use crate::storages::fuse::fuse_part::ColumnMeta;
 use crate::storages::fuse::fuse_part::FusePartInfo;
 use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::TableSnapshot;
 use crate::storages::fuse::pruning::BlockPruner;
 use crate::storages::fuse::FuseTable;
@@ -44,6 +45,7 @@ impl FuseTable {
                     return Ok(result);
                 }
                 let schema = self.table_info.schema();
+                let column_ids = ColumnIds::from_schema(&schema)?;
                 let block_metas = BlockPruner::new(snapshot.clone())
                     .apply(ctx.as_ref(), schema, &push_downs)
                     .await?
@@ -51,6 +53,20 @@ impl FuseTable {
                     .map(|(_, v)| v)
                     .collect::<Vec<_>>();
 
+                // partitions address the columns by their indices in the table schema
+                let block_metas = if column_ids.is_identity() {
+                    block_metas
+                } else {
+                    block_metas
+                        .into_iter()
+                        .map(|mut meta| {
+                            meta.col_stats = column_ids.id_to_index(&meta.col_stats);
+                            meta.col_metas = column_ids.leaf_id_to_index(&meta.col_metas);
+                            meta
+                        })
+                        .collect::<Vec<_>>()
+                };
+
                 let partitions_scanned = block_metas.len();
                 let partitions_total = snapshot.summary.block_count as usize;
 
@@ -157,9 +173,10 @@ impl FuseTable {
 
             statistics.read_rows += rows;
             for projection_index in indices {
-                let column_stats = &block_meta.col_stats;
-                let column_stats = &column_stats[&(*projection_index as u32)];
-                statistics.read_bytes += column_stats.in_memory_size as usize;
+                // columns added after the block was written have no stats
+                if let Some(column_stats) = block_meta.col_stats.get(&(*projection_index as u32)) {
+                    statistics.read_bytes += column_stats.in_memory_size as usize;
+                }
             }
 
             if remaining > rows {
@@ -177,6 +194,7 @@ impl FuseTable {
 
     pub(crate) fn all_columns_part(meta: &BlockMeta) -> PartInfoPtr {
         let mut columns_meta = HashMap::with_capacity(meta.col_metas.len());
+        let mut columns_stats = HashMap::with_capacity(meta.col_stats.len());
 
         for (idx, column_meta) in &meta.col_metas {
             columns_meta.insert(
@@ -185,6 +203,10 @@ impl FuseTable {
             );
         }
 
+        for (idx, column_stats) in &meta.col_stats {
+            columns_stats.insert(*idx as usize, column_stats.clone());
+        }
+
         let rows_count = meta.row_count;
         let location = meta.location.0.clone();
         let format_version = meta.location.1;
@@ -193,20 +215,27 @@ impl FuseTable {
             format_version,
             rows_count,
             columns_meta,
+            columns_stats,
             meta.compression(),
         )
     }
 
     fn projection_part(meta: &BlockMeta, projections: &[usize]) -> PartInfoPtr {
         let mut columns_meta = HashMap::with_capacity(projections.len());
+        let mut columns_stats = HashMap::with_capacity(projections.len());
 
         for projection in projections {
-            let column_meta = &meta.col_metas[&(*projection as u32)];
-
-            columns_meta.insert(
-                *projection,
-                ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values),
-            );
+            // columns added after the block was written are absent, and
+            // will be filled with their default values by the block reader
+            if let Some(column_meta) = meta.col_metas.get(&(*projection as u32)) {
+                columns_meta.insert(
+                    *projection,
+                    ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values),
+                );
+            }
+            if let Some(column_stats) = meta.col_stats.get(&(*projection as u32)) {
+                columns_stats.insert(*projection, column_stats.clone());
+            }
         }
 
         let rows_count = meta.row_count;
@@ -220,6 +249,7 @@ impl FuseTable {
             format_version,
             rows_count,
             columns_meta,
+            columns_stats,
             meta.compression(),
         )
     }
@@ -0,0 +1,59 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::operations::mutation::ReclusterMutator;
+use crate::storages::fuse::FuseTable;
+use crate::storages::Table;
+
+impl FuseTable {
+    /// Re-sorts the overlapping blocks by the cluster key.
+    ///
+    /// Without `is_final`, at most `recluster_block_size` bytes of blocks are re-sorted
+    /// (at least one group of overlapping blocks), thus several passes may be needed.
+    pub async fn do_recluster(
+        &self,
+        ctx: Arc<QueryContext>,
+        catalog_name: &str,
+        is_final: bool,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        if self.cluster_keys.is_empty() {
+            return Err(ErrorCode::InvalidClusterKeys(format!(
+                "Invalid clustering keys or table {} is not clustered",
+                self.name()
+            )));
+        }
+
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) if snapshot.summary.block_count > 1 => snapshot,
+            // nothing to re-sort
+            _ => return Ok(()),
+        };
+
+        let mutator = ReclusterMutator::try_create(&ctx, self, &snapshot, is_final)?;
+        match mutator.recluster().await? {
+            Some(new_snapshot) => {
+                self.commit_mutation(ctx.as_ref(), &snapshot, new_snapshot, catalog_name)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+}
@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_exception::Result;
 use common_meta_app::schema::TableStatistics;
+use common_meta_app::schema::TruncateTableReq;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::MatchSeq;
 use uuid::Uuid;
@@ -75,6 +76,16 @@ impl FuseTable {
                     table_id,
                     seq: MatchSeq::Exact(table_version),
                     new_table_meta,
+                    copied_files: None,
+                    // forget the files loaded by `COPY INTO <table>`, they could be loaded again.
+                    truncate_copied_files: true,
+                })
+                .await?;
+        } else {
+            // nothing to commit, but the files loaded by `COPY INTO <table>` are forgotten still.
+            ctx.get_catalog(catalog_name)?
+                .truncate_table(TruncateTableReq {
+                    table_id: self.table_info.ident.table_id,
                 })
                 .await?;
         }
@@ -0,0 +1,81 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::Extras;
+use common_planners::UpdatePlan;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::operations::mutation::mutations_collector::DeletionCollector;
+use crate::storages::fuse::operations::mutation::update_block;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    pub async fn do_update(&self, ctx: Arc<QueryContext>, plan: &UpdatePlan) -> Result<()> {
+        self.check_mutable()?;
+
+        let snapshot_opt = self.read_table_snapshot(ctx.as_ref()).await?;
+
+        // check if table is empty
+        let snapshot = if let Some(val) = snapshot_opt {
+            val
+        } else {
+            // no snapshot, no update
+            return Ok(());
+        };
+
+        if snapshot.summary.row_count == 0 {
+            // empty snapshot, no update
+            return Ok(());
+        }
+
+        // the blocks being updated are replaced in the same way as deletion does
+        let mut mutation_collector = DeletionCollector::try_create(
+            ctx.as_ref(),
+            &self.meta_location_generator,
+            &snapshot,
+            self.column_ids()?,
+            self.bloom_index_writer(ctx.as_ref())?,
+        )?;
+        let schema = self.table_info.schema();
+        let push_downs = plan.selection.as_ref().map(|filter| Extras {
+            projection: Some(plan.projection.clone()),
+            filters: vec![filter.clone()],
+            limit: None,
+            order_by: vec![],
+        });
+        let block_metas = BlockPruner::new(snapshot.clone())
+            .apply(ctx.as_ref(), schema, &push_downs)
+            .await?;
+
+        // update block one by one.
+        for (seg_idx, block_meta) in block_metas {
+            let proj = plan.projection.clone();
+            let filter = plan.selection.as_ref();
+            if let Some(r) =
+                update_block(self, &block_meta, &ctx, proj, filter, &plan.update_list).await?
+            {
+                mutation_collector
+                    .replace_with(seg_idx, block_meta.location.clone(), r)
+                    .await?
+            }
+        }
+        let new_snapshot = mutation_collector.into_new_snapshot().await?;
+        self.commit_mutation(ctx.as_ref(), &snapshot, new_snapshot, &plan.catalog_name)
+            .await
+    }
+}
@@ -28,8 +28,10 @@ use futures::TryStreamExt;
 use crate::sessions::QueryContext;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::pruning::BloomFilterPruner;
 use crate::storages::index::RangeFilter;
 use crate::storages::index::StatisticsOfColumns;
 
@@ -52,14 +54,32 @@ impl BlockPruner {
     ) -> Result<Vec<(usize, BlockMeta)>> {
         let block_pred: Pred = match push_down {
             Some(exprs) if !exprs.filters.is_empty() => {
+                let column_ids = ColumnIds::from_schema(&schema)?;
                 // for the time being, we only handle the first expr
-                let range_filter =
-                    RangeFilter::try_create(Arc::new(ctx.clone()), &exprs.filters[0], schema)?;
-                Box::new(move |v: &StatisticsOfColumns| range_filter.eval(v))
+                let range_filter = RangeFilter::try_create(
+                    Arc::new(ctx.clone()),
+                    &exprs.filters[0],
+                    schema.clone(),
+                )?;
+                if column_ids.is_identity() {
+                    Box::new(move |v: &StatisticsOfColumns| range_filter.eval(v))
+                } else {
+                    // the range filter works on column indices, while the stats are keyed by column ids
+                    Box::new(move |v: &StatisticsOfColumns| {
+                        range_filter.eval(&column_ids.id_to_index(v))
+                    })
+                }
             }
             _ => Box::new(|_: &StatisticsOfColumns| Ok(true)),
         };
 
+        let bloom_pruner = match push_down {
+            Some(exprs) if !exprs.filters.is_empty() => {
+                BloomFilterPruner::try_create(ctx, schema.clone(), &exprs.filters[0])?
+            }
+            _ => None,
+        };
+
         let segment_locs = self.table_snapshot.segments.clone();
         let segment_num = segment_locs.len();
 
@@ -101,9 +121,11 @@ impl BlockPruner {
                         Self::filter_segment(
                             segment_info.as_ref(),
                             &block_pred,
+                            &bloom_pruner,
                             &accumulated_rows,
                             limit,
-                        )?
+                        )
+                        .await?
                         .into_iter()
                         .map(|v| (idx, v))
                         .collect::<Vec<_>>(),
@@ -123,9 +145,10 @@ impl BlockPruner {
     }
 
     #[inline]
-    fn filter_segment(
+    async fn filter_segment(
         segment_info: &SegmentInfo,
         pred: &Pred,
+        bloom_pruner: &Option<BloomFilterPruner>,
         accumulated_rows: &AtomicUsize,
         limit: usize,
     ) -> Result<Vec<BlockMeta>> {
@@ -133,7 +156,16 @@ impl BlockPruner {
             let block_num = segment_info.blocks.len();
             let mut acc = Vec::with_capacity(block_num);
             for block_meta in &segment_info.blocks {
-                if pred(&block_meta.col_stats)? {
+                if !pred(&block_meta.col_stats)? {
+                    continue;
+                }
+                // the bloom filter index is checked only if the min/max statistics do not
+                // prune the block, since it takes extra IO to load the index
+                let may_match = match bloom_pruner {
+                    Some(pruner) => pruner.may_match(block_meta).await?,
+                    None => true,
+                };
+                if may_match {
                     let num_rows = block_meta.row_count as usize;
                     if accumulated_rows.fetch_add(num_rows, Ordering::Release) < limit {
                         acc.push(block_meta.clone());
@@ -0,0 +1,121 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use common_planners::Expression;
+use common_tracing::tracing::warn;
+use opendal::Operator;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::read_bloom_index;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnIds;
+use crate::storages::index::BloomFilterIndexer;
+
+/// Prunes the blocks by their bloom filter indexes, for point lookups like `col = 'x'`.
+pub struct BloomFilterPruner {
+    ctx: Arc<QueryContext>,
+    data_accessor: Operator,
+    schema: DataSchemaRef,
+    column_ids: ColumnIds,
+    filter: Expression,
+}
+
+impl BloomFilterPruner {
+    /// Returns [None] if the filter could not be evaluated by bloom filters, so that
+    /// the bloom filter indexes are not loaded in vain.
+    pub fn try_create(
+        ctx: &QueryContext,
+        schema: DataSchemaRef,
+        filter: &Expression,
+    ) -> Result<Option<Self>> {
+        if !Self::is_point_lookup(filter) {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            ctx: Arc::new(ctx.clone()),
+            data_accessor: ctx.get_storage_operator()?,
+            column_ids: ColumnIds::from_schema(&schema)?,
+            schema,
+            filter: filter.clone(),
+        }))
+    }
+
+    /// Returns false if none of the rows of the block could match the filter.
+    pub async fn may_match(&self, block_meta: &BlockMeta) -> Result<bool> {
+        let location = match &block_meta.bloom_filter_index_location {
+            None => return Ok(true),
+            Some((location, _)) => location,
+        };
+
+        // the index is merely an optimization, the block is kept if the index is unavailable
+        let bloom_block = match read_bloom_index(&self.data_accessor, location).await {
+            Ok(bloom_block) => bloom_block,
+            Err(e) => {
+                warn!("failed to read bloom filter index {}, {}", location, e);
+                return Ok(true);
+            }
+        };
+
+        let indexer = BloomFilterIndexer::from_bloom_block(
+            self.schema.clone(),
+            self.by_column_name(&bloom_block)?,
+            self.ctx.clone(),
+        )?;
+        indexer.maybe_true(&self.filter)
+    }
+
+    /// The bloom filters are stored under the ids of the columns, while the filter refers to
+    /// the columns by name, the filters of the dropped columns are discarded.
+    fn by_column_name(&self, bloom_block: &DataBlock) -> Result<DataBlock> {
+        let bloom_schema = bloom_block.schema();
+        let mut fields = vec![];
+        let mut columns = vec![];
+        for (idx, field) in self.schema.fields().iter().enumerate() {
+            let id = self.column_ids.column_id(idx);
+            let name = BloomFilterIndexer::to_bloom_column_name(&id.to_string());
+            if let Some((bloom_idx, bloom_field)) = bloom_schema.column_with_name(&name) {
+                let name = BloomFilterIndexer::to_bloom_column_name(field.name());
+                fields.push(DataField::new(&name, bloom_field.data_type().clone()));
+                columns.push(bloom_block.column(bloom_idx).clone());
+            }
+        }
+        Ok(DataBlock::create(DataSchemaRefExt::create(fields), columns))
+    }
+
+    /// Returns true if the filter is an equality between a column and a constant, or
+    /// the conjunction/disjunction of them, which bloom filters are able to evaluate.
+    fn is_point_lookup(filter: &Expression) -> bool {
+        match filter {
+            Expression::BinaryExpression { left, op, right } => match op.to_lowercase().as_str() {
+                "=" => matches!(
+                    (left.as_ref(), right.as_ref()),
+                    (Expression::Column(_), Expression::Literal { .. })
+                        | (Expression::Literal { .. }, Expression::Column(_))
+                ),
+                "and" => Self::is_point_lookup(left) || Self::is_point_lookup(right),
+                "or" => Self::is_point_lookup(left) && Self::is_point_lookup(right),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
@@ -13,5 +13,7 @@
 //  limitations under the License.
 
 mod block_pruner;
+mod bloom_pruner;
 
 pub use block_pruner::BlockPruner;
+pub use bloom_pruner::BloomFilterPruner;
@@ -23,7 +23,9 @@ use common_functions::aggregates::eval_aggr;
 
 use crate::storages::fuse::meta::BlockMeta;
 use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::meta::ColumnIds;
 use crate::storages::fuse::meta::ColumnMeta;
+use crate::storages::fuse::meta::Location;
 use crate::storages::fuse::meta::Versioned;
 use crate::storages::fuse::operations::column_metas;
 use crate::storages::index::ClusterStatistics;
@@ -38,6 +40,7 @@ pub struct StatisticsAccumulator {
     pub summary_block_count: u64,
     pub in_memory_size: u64,
     pub file_size: u64,
+    pub column_ids: ColumnIds,
 }
 
 impl StatisticsAccumulator {
@@ -45,6 +48,13 @@ impl StatisticsAccumulator {
         Default::default()
     }
 
+    pub fn with_column_ids(column_ids: ColumnIds) -> Self {
+        Self {
+            column_ids,
+            ..Default::default()
+        }
+    }
+
     pub fn begin(
         mut self,
         block: &DataBlock,
@@ -56,7 +66,7 @@ impl StatisticsAccumulator {
         self.summary_block_count += 1;
         self.summary_row_count += row_count;
         self.in_memory_size += block_in_memory_size;
-        let block_stats = columns_statistics(block)?;
+        let block_stats = self.column_ids.index_to_id(columns_statistics(block)?);
         self.blocks_statistics.push(block_stats.clone());
         Ok(PartiallyAccumulated {
             accumulator: self,
@@ -64,6 +74,7 @@ impl StatisticsAccumulator {
             block_size: block.memory_size() as u64,
             block_columns_statistics: block_stats,
             block_cluster_statistics: cluster_stats,
+            block_bloom_filter_index_location: None,
         })
     }
 
@@ -77,15 +88,17 @@ impl StatisticsAccumulator {
         self.summary_block_count += 1;
         self.in_memory_size += statistics.block_bytes_size;
         self.summary_row_count += statistics.block_rows_size;
-        self.blocks_statistics
-            .push(statistics.block_column_statistics.clone());
 
         let row_count = statistics.block_rows_size;
         let block_size = statistics.block_bytes_size;
-        let col_stats = statistics.block_column_statistics.clone();
+        let col_stats = self
+            .column_ids
+            .index_to_id(statistics.block_column_statistics);
+        self.blocks_statistics.push(col_stats.clone());
         let location = (statistics.block_file_location, DataBlock::VERSION);
-        let col_metas = column_metas(&meta)?;
+        let col_metas = self.column_ids.leaf_index_to_id(column_metas(&meta)?);
         let cluster_stats = statistics.block_cluster_statistics;
+        let bloom_filter_index_location = statistics.block_bloom_filter_index_location;
 
         self.blocks_metas.push(BlockMeta::new(
             row_count,
@@ -95,6 +108,7 @@ impl StatisticsAccumulator {
             col_metas,
             cluster_stats,
             location,
+            bloom_filter_index_location,
         ));
 
         Ok(())
@@ -119,6 +133,7 @@ impl PartiallyAccumulated {
         file_size: u64,
         location: String,
         col_metas: HashMap<ColumnId, ColumnMeta>,
+        bloom_filter_index_location: Option<Location>,
     ) -> StatisticsAccumulator {
         let mut stats = &mut self.accumulator;
         stats.file_size += file_size;
@@ -126,6 +141,7 @@ impl PartiallyAccumulated {
         let row_count = self.block_row_count;
         let block_size = self.block_size;
         let col_stats = self.block_columns_statistics;
+        let col_metas = stats.column_ids.leaf_index_to_id(col_metas);
         let cluster_stats = self.block_cluster_statistics;
         let location = (location, DataBlock::VERSION);
 
@@ -137,6 +153,7 @@ impl PartiallyAccumulated {
             col_metas,
             cluster_stats,
             location,
+            bloom_filter_index_location,
         );
         stats.blocks_metas.push(block_meta);
         self.accumulator
@@ -149,6 +166,7 @@ pub struct BlockStatistics {
     pub block_file_location: String,
     pub block_column_statistics: HashMap<ColumnId, ColumnStatistics>,
     pub block_cluster_statistics: Option<ClusterStatistics>,
+    pub block_bloom_filter_index_location: Option<Location>,
 }
 
 impl BlockStatistics {
@@ -163,6 +181,7 @@ impl BlockStatistics {
             block_bytes_size: data_block.memory_size() as u64,
             block_column_statistics: columns_statistics(data_block)?,
             block_cluster_statistics: cluster_stats,
+            block_bloom_filter_index_location: None,
         })
     }
 
@@ -35,7 +35,7 @@ use crate::sessions::QueryContext;
 
 pub type StatisticsOfColumns = HashMap<u32, ColumnStatistics>;
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ColumnStatistics {
     pub min: DataValue,
     pub max: DataValue,
@@ -232,24 +232,21 @@ impl StatColumn {
         if self.stat_type == StatType::Nulls {
             // The len of column_fields is 1.
             let (k, _) = self.column_fields.iter().next().unwrap();
-            let stat = stats.get(k).ok_or_else(|| {
-                ErrorCode::UnknownException(format!(
-                    "Unable to get the colStats by ColumnId: {}",
-                    k
-                ))
-            })?;
+            // The stats may be absent, e.g. the column is added after the block was written.
+            let stat = match stats.get(k) {
+                Some(stat) => stat,
+                None => return Ok(None),
+            };
             return Ok(Some(Series::from_data(vec![stat.null_count])));
         }
 
         let mut single_point = true;
         let mut variables = HashMap::with_capacity(self.column_fields.len());
         for (k, v) in &self.column_fields {
-            let stat = stats.get(k).ok_or_else(|| {
-                ErrorCode::UnknownException(format!(
-                    "Unable to get the colStats by ColumnId: {}",
-                    k
-                ))
-            })?;
+            let stat = match stats.get(k) {
+                Some(stat) => stat,
+                None => return Ok(None),
+            };
 
             if single_point && stat.min != stat.max {
                 single_point = false;
@@ -24,6 +24,8 @@ use common_datablocks::DataBlock;
 use common_datavalues::ColumnRef;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TruncateTableReq;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_planners::Extras;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
@@ -73,6 +75,15 @@ impl MemoryTable {
         Ok(Box::new(table))
     }
 
+    /// Creates a table holding the given blocks, which is not backed by the shared in-memory data,
+    /// so its blocks are dropped together with the table.
+    pub fn create_with_blocks(table_info: TableInfo, blocks: Vec<DataBlock>) -> Box<dyn Table> {
+        Box::new(Self {
+            table_info,
+            blocks: Arc::new(RwLock::new(blocks)),
+        })
+    }
+
     pub fn description() -> StorageDescription {
         StorageDescription {
             engine_name: "MEMORY".to_string(),
@@ -264,8 +275,9 @@ impl Table for MemoryTable {
     async fn commit_insertion(
         &self,
         ctx: Arc<QueryContext>,
-        _catalog_name: &str,
+        catalog_name: &str,
         operations: Vec<DataBlock>,
+        copied_files: Option<UpsertTableCopiedFileReq>,
         overwrite: bool,
     ) -> Result<()> {
         let written_bytes: usize = operations.iter().map(|b| b.memory_size()).sum();
@@ -278,20 +290,36 @@ impl Table for MemoryTable {
             let mut blocks = self.blocks.write();
             blocks.clear();
         }
-        let mut blocks = self.blocks.write();
-        for block in operations {
-            blocks.push(block);
+        {
+            let mut blocks = self.blocks.write();
+            for block in operations {
+                blocks.push(block);
+            }
+        }
+
+        if let Some(req) = copied_files {
+            ctx.get_catalog(catalog_name)?
+                .upsert_table_copied_file_info(req)
+                .await?;
         }
         Ok(())
     }
 
     async fn truncate(
         &self,
-        _ctx: Arc<QueryContext>,
-        _truncate_plan: TruncateTablePlan,
+        ctx: Arc<QueryContext>,
+        truncate_plan: TruncateTablePlan,
     ) -> Result<()> {
-        let mut blocks = self.blocks.write();
-        blocks.clear();
+        {
+            let mut blocks = self.blocks.write();
+            blocks.clear();
+        }
+
+        ctx.get_catalog(&truncate_plan.catalog)?
+            .truncate_table(TruncateTableReq {
+                table_id: self.table_info.ident.table_id,
+            })
+            .await?;
         Ok(())
     }
 }
@@ -14,11 +14,14 @@
 
 pub mod block_buffer;
 mod download;
+mod result_cache;
 mod result_locations;
 mod result_table;
 mod result_table_sink;
 mod writer;
 
+pub use result_cache::purge_expired_results;
+pub use result_cache::ResultCache;
 pub use result_table::ResultQueryInfo;
 pub use result_table::ResultTable;
 pub use result_table_sink::ResultTableSink;
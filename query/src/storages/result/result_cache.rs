@@ -0,0 +1,462 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use chrono::Duration;
+use chrono::Utc;
+use common_ast::parser::tokenize_sql;
+use common_base::base::TrySpawn;
+use common_base::infallible::Mutex;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_metrics::label_counter;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+use serde_json::Value as JsonValue;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::BlocksSource;
+use crate::pipelines::new::processors::Processor;
+use crate::pipelines::new::NewPipeline;
+use crate::pipelines::new::SourcePipeBuilder;
+use crate::sessions::QueryContext;
+use crate::sql::exec::PhysicalPlan;
+use crate::sql::has_non_deterministic_function;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::Exchange;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::MetadataRef;
+use crate::sql::ScalarExpr;
+use crate::storages::fuse::FuseTable;
+use crate::storages::result::result_locations::ResultLocations;
+use crate::storages::result::result_locations::RESULT_CACHE_PREFIX;
+use crate::storages::result::ResultQueryInfo;
+use crate::storages::result::ResultTable;
+use crate::storages::result::ResultTableWriter;
+use crate::storages::Table;
+
+const RESULT_CACHE_KEY_PREFIX: &str = "cache_";
+const RESULT_CACHE_HIT_COUNT: &str = "result_cache_hit_count";
+const RESULT_CACHE_MISS_COUNT: &str = "result_cache_miss_count";
+/// The expired results are swept by a query node at most once in the interval, since the
+/// sweep lists all the results in the cache.
+const RESULT_CACHE_PURGE_INTERVAL_SECS: i64 = 300;
+
+/// The time in seconds that the expired results are swept last time by this node.
+static LAST_PURGE_SECS: AtomicI64 = AtomicI64::new(0);
+
+/// Result cache of the queries of planner v2.
+///
+/// A result is keyed on the normalized physical plan of the query and the snapshot of every
+/// fuse table it reads, so it is only served while none of the tables has changed. The
+/// un-rendered output of the plan is stored with the layout of `ResultTable` at
+/// `_res/cache_{key}`, and expires after `query_result_cache_ttl_secs`. Expired results are
+/// removed when they are read, or swept when a new result is written, at most once every
+/// `RESULT_CACHE_PURGE_INTERVAL_SECS`.
+pub struct ResultCache {
+    ctx: Arc<QueryContext>,
+    key: String,
+    ttl: Duration,
+}
+
+impl ResultCache {
+    /// Returns `None` if the result cache is disabled or the query can't be cached, i.e. it
+    /// reads a table other than fuse tables or contains non-deterministic expressions.
+    pub fn try_create(
+        ctx: &Arc<QueryContext>,
+        s_expr: &SExpr,
+        metadata: &MetadataRef,
+        physical_plan: &PhysicalPlan,
+    ) -> Result<Option<ResultCache>> {
+        let settings = ctx.get_settings();
+        if settings.get_enable_query_result_cache()? == 0 || !is_deterministic(s_expr) {
+            return Ok(None);
+        }
+        // Non-deterministic functions without arguments, e.g. `now()`, are folded into
+        // constants at binding, so they are only seen in the query text.
+        if has_non_deterministic_function(&tokenize_sql(&ctx.get_query_str())?) {
+            return Ok(None);
+        }
+
+        let metadata = metadata.read();
+        if metadata.tables().is_empty() {
+            return Ok(None);
+        }
+        let mut snapshots = Vec::with_capacity(metadata.tables().len());
+        for table_entry in metadata.tables() {
+            match FuseTable::try_from_table(table_entry.table.as_ref()) {
+                Ok(table) => {
+                    snapshots.push((table.get_id(), table.snapshot_loc().unwrap_or_default()))
+                }
+                Err(_) => return Ok(None),
+            }
+        }
+
+        let timezone = String::from_utf8(settings.get_timezone()?)?;
+        let fingerprint = serde_json::to_vec(&(
+            ctx.get_tenant(),
+            timezone,
+            normalize_plan(physical_plan)?,
+            snapshots,
+        ))?;
+        let key = format!(
+            "{}{}",
+            RESULT_CACHE_KEY_PREFIX,
+            hex::encode(Sha256::digest(&fingerprint))
+        );
+
+        let ttl = Duration::seconds(settings.get_query_result_cache_ttl_secs()? as i64);
+
+        Ok(Some(ResultCache {
+            ctx: ctx.clone(),
+            key,
+            ttl,
+        }))
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Read the cached result, a result with a different schema is taken as a miss.
+    pub async fn try_read(&self, schema: &DataSchemaRef) -> Option<Vec<DataBlock>> {
+        let tenant = self.ctx.get_tenant();
+        let cluster_id = self.ctx.get_config().query.cluster_id;
+
+        match self.read(schema).await {
+            Ok(Some(blocks)) => {
+                label_counter(RESULT_CACHE_HIT_COUNT, &tenant, &cluster_id);
+                Some(blocks)
+            }
+            Ok(None) => {
+                label_counter(RESULT_CACHE_MISS_COUNT, &tenant, &cluster_id);
+                None
+            }
+            Err(cause) => {
+                tracing::debug!("read result cache {} failed: {}", self.key, cause);
+                label_counter(RESULT_CACHE_MISS_COUNT, &tenant, &cluster_id);
+                None
+            }
+        }
+    }
+
+    async fn read(&self, schema: &DataSchemaRef) -> Result<Option<Vec<DataBlock>>> {
+        let table = ResultTable::try_get(self.ctx.clone(), &self.key).await?;
+        // The stale result is removed, or its files would be left behind once it's rewritten.
+        if is_expired(&table, self.ttl) || table.get_table_info().schema() != *schema {
+            remove_result(&self.ctx, &self.key).await?;
+            return Ok(None);
+        }
+        Ok(Some(table.read_blocks(&self.ctx).await?))
+    }
+
+    /// Build the source of pipeline with the cached result.
+    pub fn build_source(&self, blocks: Vec<DataBlock>, pipeline: &mut NewPipeline) -> Result<()> {
+        let blocks = Arc::new(Mutex::new(VecDeque::from(blocks)));
+        let output = OutputPort::create();
+        let mut builder = SourcePipeBuilder::create();
+        builder.add_source(
+            output.clone(),
+            BlocksSource::create(self.ctx.clone(), output, blocks)?,
+        );
+        pipeline.add_pipe(builder.finalize());
+        Ok(())
+    }
+
+    /// Collect the output of pipeline, which is written to the cache once the pipeline
+    /// finishes without being aborted.
+    pub fn add_writer(&self, schema: DataSchemaRef, pipeline: &mut NewPipeline) -> Result<()> {
+        let query_info = ResultQueryInfo {
+            query_id: self.key.clone(),
+            schema,
+            user: self.ctx.get_current_user()?.identity(),
+        };
+        let max_bytes = self.ctx.get_settings().get_query_result_cache_max_bytes()? as usize;
+
+        pipeline.resize(1)?;
+        pipeline.add_transform(|input, output| {
+            Ok(TransformResultCacheWriter::create(
+                input,
+                output,
+                self.ctx.clone(),
+                query_info.clone(),
+                max_bytes,
+                self.ttl,
+            ))
+        })
+    }
+}
+
+fn is_expired(table: &ResultTable, ttl: Duration) -> bool {
+    match table.created_on() {
+        Some(created_on) => Utc::now() - created_on > ttl,
+        None => true,
+    }
+}
+
+async fn remove_result(ctx: &Arc<QueryContext>, key: &str) -> Result<()> {
+    let operator = ctx.get_storage_operator()?;
+    let root = ResultLocations::new(key).get_root();
+    operator.batch().remove_all(&root).await?;
+    Ok(())
+}
+
+/// Returns true if the expired results are due to be swept, and the sweep is claimed by the
+/// caller, so that it's not run by the other writers concurrently.
+fn claim_purge() -> bool {
+    let now = Utc::now().timestamp();
+    let last = LAST_PURGE_SECS.load(Ordering::Relaxed);
+    now - last >= RESULT_CACHE_PURGE_INTERVAL_SECS
+        && LAST_PURGE_SECS
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+}
+
+/// Remove the expired results in the cache, including the ones whose keys are not used any more,
+/// e.g. the tables they read have changed.
+pub async fn purge_expired_results(ctx: &Arc<QueryContext>, ttl: Duration) -> Result<()> {
+    let operator = ctx.get_storage_operator()?;
+    let mut entries = operator
+        .object(&format!("{}/", RESULT_CACHE_PREFIX))
+        .list()
+        .await?;
+
+    let mut expired = vec![];
+    while let Some(entry) = entries.try_next().await? {
+        let key = entry.path().trim_end_matches('/').rsplit('/').next();
+        match key {
+            Some(key) if entry.mode().is_dir() && key.starts_with(RESULT_CACHE_KEY_PREFIX) => {
+                expired.push(key.to_string())
+            }
+            _ => {}
+        }
+    }
+
+    for key in expired {
+        match ResultTable::try_get(ctx.clone(), &key).await {
+            Ok(table) if !is_expired(&table, ttl) => {}
+            // The result being written has no meta yet.
+            Err(cause) if cause.code() == ErrorCode::HttpNotFound("").code() => {}
+            _ => remove_result(ctx, &key).await?,
+        }
+    }
+    Ok(())
+}
+
+/// Partitions and statistics of table scans are derived from the snapshots of tables, which
+/// are part of the key already, and they can be too large to be hashed for every query.
+fn normalize_plan(physical_plan: &PhysicalPlan) -> Result<JsonValue> {
+    fn strip_partitions(value: &mut JsonValue) {
+        match value {
+            JsonValue::Object(map) => {
+                if map.contains_key("source_info") {
+                    map.remove("parts");
+                    map.remove("statistics");
+                }
+                map.values_mut().for_each(strip_partitions);
+            }
+            JsonValue::Array(values) => values.iter_mut().for_each(strip_partitions),
+            _ => {}
+        }
+    }
+
+    let mut plan = serde_json::to_value(physical_plan)?;
+    strip_partitions(&mut plan);
+    Ok(plan)
+}
+
+fn is_deterministic(s_expr: &SExpr) -> bool {
+    let deterministic = match s_expr.plan() {
+        RelOperator::EvalScalar(op) => all_deterministic(op.items.iter().map(|item| &item.scalar)),
+        RelOperator::Filter(op) => all_deterministic(&op.predicates),
+        RelOperator::Aggregate(op) => op
+            .group_items
+            .iter()
+            .chain(op.aggregate_functions.iter())
+            .all(|item| match &item.scalar {
+                // Aggregate functions are deterministic on deterministic arguments
+                Scalar::AggregateFunction(agg) => all_deterministic(&agg.args),
+                scalar => scalar.is_deterministic(),
+            }),
+        RelOperator::Window(op) => all_deterministic(
+            op.function
+                .args
+                .iter()
+                .chain(op.function.partition_by.iter())
+                .chain(op.function.order_by.iter().map(|order_by| &order_by.expr)),
+        ),
+        RelOperator::LogicalInnerJoin(op) => all_deterministic(
+            op.left_conditions
+                .iter()
+                .chain(op.right_conditions.iter())
+                .chain(op.other_conditions.iter()),
+        ),
+        RelOperator::PhysicalHashJoin(op) => all_deterministic(
+            op.build_keys
+                .iter()
+                .chain(op.probe_keys.iter())
+                .chain(op.other_conditions.iter()),
+        ),
+        RelOperator::PhysicalMergeJoin(op) => all_deterministic(
+            op.left_keys
+                .iter()
+                .chain(op.right_keys.iter())
+                .chain(op.other_conditions.iter()),
+        ),
+        RelOperator::PhysicalNestedLoopJoin(op) => all_deterministic(&op.conditions),
+        RelOperator::Exchange(Exchange::Hash(keys)) => all_deterministic(keys),
+        _ => true,
+    };
+
+    deterministic && s_expr.children().iter().all(is_deterministic)
+}
+
+fn all_deterministic<'a>(scalars: impl IntoIterator<Item = &'a Scalar>) -> bool {
+    scalars.into_iter().all(|scalar| scalar.is_deterministic())
+}
+
+pub struct TransformResultCacheWriter {
+    ctx: Arc<QueryContext>,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    query_info: ResultQueryInfo,
+    max_bytes: usize,
+    ttl: Duration,
+    bytes: usize,
+    // None if the result is too large to be cached
+    blocks: Option<Vec<DataBlock>>,
+}
+
+impl TransformResultCacheWriter {
+    pub fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        query_info: ResultQueryInfo,
+        max_bytes: usize,
+        ttl: Duration,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Box::new(TransformResultCacheWriter {
+            ctx,
+            input,
+            output,
+            query_info,
+            max_bytes,
+            ttl,
+            bytes: 0,
+            blocks: Some(vec![]),
+        }))
+    }
+
+    fn collect(&mut self, block: &DataBlock) {
+        if let Some(blocks) = &mut self.blocks {
+            self.bytes += block.memory_size();
+            if self.bytes > self.max_bytes {
+                self.blocks = None;
+            } else if !block.is_empty() {
+                blocks.push(block.clone());
+            }
+        }
+    }
+
+    fn spawn_write(&mut self) {
+        let blocks = match self.blocks.take() {
+            Some(blocks) if !self.ctx.query_need_abort().load(Ordering::Relaxed) => blocks,
+            _ => return,
+        };
+
+        let ctx = self.ctx.clone();
+        let query_info = self.query_info.clone();
+        let ttl = self.ttl;
+        self.ctx.get_storage_runtime().spawn(async move {
+            if let Err(cause) = write_result(ctx.clone(), query_info, blocks).await {
+                tracing::warn!("write result cache failed: {}", cause);
+            }
+            if !claim_purge() {
+                return;
+            }
+            if let Err(cause) = purge_expired_results(&ctx, ttl).await {
+                tracing::warn!("purge expired result cache failed: {}", cause);
+            }
+        });
+    }
+}
+
+async fn write_result(
+    ctx: Arc<QueryContext>,
+    query_info: ResultQueryInfo,
+    blocks: Vec<DataBlock>,
+) -> Result<()> {
+    let mut writer = ResultTableWriter::new(ctx, query_info).await?;
+    for block in blocks {
+        if let Err(cause) = writer.append_block(block).await {
+            writer.abort().await?;
+            return Err(cause);
+        }
+    }
+    writer.commit().await
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformResultCacheWriter {
+    fn name(&self) -> &'static str {
+        "TransformResultCacheWriter"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        // The result is incomplete if the downstream stops pulling data.
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input.has_data() {
+            let block = self.input.pull_data().unwrap()?;
+            self.collect(&block);
+            self.output.push_data(Ok(block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input.is_finished() {
+            self.spawn_write();
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+}
@@ -31,6 +31,11 @@ impl ResultLocations {
         }
     }
 
+    /// The directory of all the files of the result.
+    pub fn get_root(&self) -> String {
+        format!("{}/", &self.prefix)
+    }
+
     pub fn get_meta_location(&self) -> String where {
         format!("{}/_t/meta_v{}.json", &self.prefix, SegmentInfo::VERSION,)
     }
@@ -15,6 +15,9 @@
 use std::any::Any;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
+use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -56,6 +59,9 @@ pub struct ResultQueryInfo {
 pub struct ResultTableMeta {
     pub query: ResultQueryInfo,
     pub storage: ResultStorageInfo,
+    /// None for the results written before it was recorded.
+    #[serde(default)]
+    pub created_on: Option<DateTime<Utc>>,
 }
 
 impl ResultTableMeta {
@@ -76,7 +82,6 @@ impl ResultTableMeta {
 pub struct ResultTable {
     #[allow(dead_code)]
     query_id: String,
-    #[allow(dead_code)]
     meta: ResultTableMeta,
     pub(crate) locations: ResultLocations,
     table_info: TableInfo,
@@ -112,6 +117,26 @@ impl ResultTable {
         }))
     }
 
+    pub fn created_on(&self) -> Option<DateTime<Utc>> {
+        self.meta.created_on
+    }
+
+    /// Read all the blocks of the result, in the order they were written.
+    pub async fn read_blocks(&self, ctx: &Arc<QueryContext>) -> Result<Vec<DataBlock>> {
+        let block_reader = self.create_block_reader(ctx, &None)?;
+        let parts = match &self.meta.storage {
+            ResultStorageInfo::FuseSegment(seg) => {
+                FuseTable::all_columns_partitions(&seg.blocks, usize::MAX).1
+            }
+        };
+
+        let mut blocks = Vec::with_capacity(parts.len());
+        for part in parts {
+            blocks.push(block_reader.read(part).await?);
+        }
+        Ok(blocks)
+    }
+
     fn create_block_reader(
         &self,
         ctx: &Arc<QueryContext>,
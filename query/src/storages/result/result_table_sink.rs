@@ -17,6 +17,7 @@ use std::any::Any;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::Utc;
 use common_datablocks::serialize_data_blocks;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
@@ -131,6 +132,7 @@ impl ResultTableSink {
         let meta = ResultTableMeta {
             query: self.query_info.clone(),
             storage: ResultStorageInfo::FuseSegment(segment_info),
+            created_on: Some(Utc::now()),
         };
         let meta_data = serde_json::to_vec(&meta)?;
         let meta_location = self.locations.get_meta_location();
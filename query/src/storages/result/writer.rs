@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use chrono::Utc;
 use common_datablocks::serialize_data_blocks;
 use common_datablocks::DataBlock;
 use common_exception::Result;
@@ -82,6 +83,7 @@ impl ResultTableWriter {
         let meta = ResultTableMeta {
             query: self.query_info.clone(),
             storage: ResultStorageInfo::FuseSegment(segment_info),
+            created_on: Some(Utc::now()),
         };
         let meta_data = serde_json::to_vec(&meta)?;
         let meta_location = self.locations.get_meta_location();
@@ -0,0 +1,83 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+
+/// The load result of a file in `COPY INTO <table>`, one row of the COPY result set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyFileStatus {
+    pub file: String,
+    pub rows_loaded: usize,
+    pub errors_seen: usize,
+    pub first_error: Option<String>,
+    /// The line of the first error, `None` if the error is not about a single row.
+    pub first_error_line: Option<usize>,
+    /// Whether the file is skipped by `ON_ERROR`, it's not a column of the result set.
+    pub skipped: bool,
+}
+
+impl CopyFileStatus {
+    pub fn create(file: &str) -> Self {
+        CopyFileStatus {
+            file: file.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add_error(&mut self, line: Option<usize>, message: impl Into<String>) {
+        if self.first_error.is_none() {
+            self.first_error = Some(message.into());
+            self.first_error_line = line;
+        }
+        self.errors_seen += 1;
+    }
+
+    pub fn schema() -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("file", Vu8::to_data_type()),
+            DataField::new("rows_loaded", u64::to_data_type()),
+            DataField::new("errors_seen", u64::to_data_type()),
+            DataField::new_nullable("first_error", Vu8::to_data_type()),
+            DataField::new_nullable("first_error_line", u64::to_data_type()),
+        ])
+    }
+
+    /// Build the COPY result set, the files are sorted by name.
+    pub fn to_data_block(statuses: &[CopyFileStatus]) -> DataBlock {
+        let mut statuses = statuses.iter().collect::<Vec<_>>();
+        statuses.sort_by(|a, b| a.file.cmp(&b.file));
+
+        let mut file: Vec<Vec<u8>> = Vec::with_capacity(statuses.len());
+        let mut rows_loaded: Vec<u64> = Vec::with_capacity(statuses.len());
+        let mut errors_seen: Vec<u64> = Vec::with_capacity(statuses.len());
+        let mut first_error: Vec<Option<Vec<u8>>> = Vec::with_capacity(statuses.len());
+        let mut first_error_line: Vec<Option<u64>> = Vec::with_capacity(statuses.len());
+        for status in statuses {
+            file.push(status.file.clone().into_bytes());
+            rows_loaded.push(status.rows_loaded as u64);
+            errors_seen.push(status.errors_seen as u64);
+            first_error.push(status.first_error.clone().map(|e| e.into_bytes()));
+            first_error_line.push(status.first_error_line.map(|l| l as u64));
+        }
+
+        DataBlock::create(Self::schema(), vec![
+            Series::from_data(file),
+            Series::from_data(rows_loaded),
+            Series::from_data(errors_seen),
+            Series::from_data(first_error),
+            Series::from_data(first_error_line),
+        ])
+    }
+}
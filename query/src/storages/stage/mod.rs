@@ -12,8 +12,11 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod copy_status;
+mod stage_file_writer;
 mod stage_source;
 mod stage_table;
 
+pub use copy_status::CopyFileStatus;
 pub use stage_source::StageSource;
 pub use stage_table::StageTable;
@@ -0,0 +1,210 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use bzip2::write::BzEncoder;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_formats::output_format::OutputFormat;
+use common_meta_types::StageFileCompression;
+use flate2::write::DeflateEncoder;
+use flate2::write::GzEncoder;
+use flate2::write::ZlibEncoder;
+use xz2::write::XzEncoder;
+
+/// A block to be unloaded with its serialized bytes.
+pub struct SerializedBlock {
+    pub block: DataBlock,
+    /// `None` if the format buffers the blocks until the file is finalized, e.g. Parquet.
+    pub bytes: Option<Vec<u8>>,
+    /// The size of the serialized block before compression, estimated if `bytes` is `None`.
+    pub size: usize,
+}
+
+/// Compresses the bytes written into it and owns the compressed bytes.
+trait FileEncoder: Write {
+    fn compressed_len(&self) -> usize;
+
+    fn finish(self: Box<Self>) -> Result<Vec<u8>>;
+}
+
+impl FileEncoder for Vec<u8> {
+    fn compressed_len(&self) -> usize {
+        self.len()
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<u8>> {
+        Ok(*self)
+    }
+}
+
+macro_rules! impl_file_encoder {
+    ($encoder: ty) => {
+        impl FileEncoder for $encoder {
+            fn compressed_len(&self) -> usize {
+                self.get_ref().len()
+            }
+
+            fn finish(self: Box<Self>) -> Result<Vec<u8>> {
+                Ok((*self).finish()?)
+            }
+        }
+    };
+}
+
+impl_file_encoder!(GzEncoder<Vec<u8>>);
+impl_file_encoder!(BzEncoder<Vec<u8>>);
+impl_file_encoder!(ZlibEncoder<Vec<u8>>);
+impl_file_encoder!(DeflateEncoder<Vec<u8>>);
+impl_file_encoder!(XzEncoder<Vec<u8>>);
+impl_file_encoder!(zstd::stream::write::Encoder<'static, Vec<u8>>);
+
+impl FileEncoder for brotli::CompressorWriter<Vec<u8>> {
+    fn compressed_len(&self) -> usize {
+        self.get_ref().len()
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<u8>> {
+        Ok((*self).into_inner())
+    }
+}
+
+/// Writes the blocks of a file unloaded into a stage, through the compression of the stage.
+///
+/// The size of the file is tracked while the blocks are written, so that the unloaded data can
+/// be split into files of `MAX_FILE_SIZE` bytes on disk.
+pub struct StageFileWriter {
+    format: Box<dyn OutputFormat>,
+    encoder: Box<dyn FileEncoder>,
+    // Bytes written into the encoder since it was flushed, they are counted uncompressed.
+    unflushed: usize,
+    // Bytes of the blocks buffered by the format until it is finalized.
+    buffered: usize,
+    num_blocks: usize,
+}
+
+impl StageFileWriter {
+    pub fn try_create(
+        format: Box<dyn OutputFormat>,
+        compression: &StageFileCompression,
+    ) -> Result<Self> {
+        let mut encoder = Self::create_encoder(compression)?;
+        let prefix = format.serialize_prefix()?;
+        encoder.write_all(&prefix)?;
+        Ok(StageFileWriter {
+            format,
+            encoder,
+            unflushed: prefix.len(),
+            buffered: 0,
+            num_blocks: 0,
+        })
+    }
+
+    /// The extension appended to the names of the files compressed by `compression`.
+    ///
+    /// `Auto` is the same as `None` here, there is no file name to detect the compression from.
+    pub fn compression_extension(
+        compression: &StageFileCompression,
+    ) -> Result<Option<&'static str>> {
+        match compression {
+            StageFileCompression::Auto | StageFileCompression::None => Ok(None),
+            StageFileCompression::Gzip => Ok(Some("gz")),
+            StageFileCompression::Bz2 => Ok(Some("bz2")),
+            StageFileCompression::Brotli => Ok(Some("br")),
+            StageFileCompression::Zstd => Ok(Some("zst")),
+            StageFileCompression::Deflate => Ok(Some("deflate")),
+            StageFileCompression::RawDeflate => Ok(Some("raw_deflate")),
+            StageFileCompression::Xz => Ok(Some("xz")),
+            StageFileCompression::Lzo => {
+                Err(ErrorCode::UnImplement("compress type lzo is unimplemented"))
+            }
+            StageFileCompression::Snappy => Err(ErrorCode::UnImplement(
+                "compress type snappy is unimplemented",
+            )),
+        }
+    }
+
+    fn create_encoder(compression: &StageFileCompression) -> Result<Box<dyn FileEncoder>> {
+        // Check the compression is supported.
+        Self::compression_extension(compression)?;
+
+        let encoder: Box<dyn FileEncoder> = match compression {
+            StageFileCompression::Gzip => {
+                Box::new(GzEncoder::new(vec![], flate2::Compression::default()))
+            }
+            StageFileCompression::Bz2 => {
+                Box::new(BzEncoder::new(vec![], bzip2::Compression::default()))
+            }
+            StageFileCompression::Brotli => {
+                Box::new(brotli::CompressorWriter::new(vec![], 4096, 9, 22))
+            }
+            StageFileCompression::Zstd => Box::new(zstd::stream::write::Encoder::new(vec![], 0)?),
+            StageFileCompression::Deflate => {
+                Box::new(ZlibEncoder::new(vec![], flate2::Compression::default()))
+            }
+            StageFileCompression::RawDeflate => {
+                Box::new(DeflateEncoder::new(vec![], flate2::Compression::default()))
+            }
+            StageFileCompression::Xz => Box::new(XzEncoder::new(vec![], 6)),
+            _ => Box::new(vec![]),
+        };
+        Ok(encoder)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_blocks == 0
+    }
+
+    /// Whether the file would be larger than `max_file_size` bytes after `block` is written.
+    ///
+    /// The bytes not flushed yet are counted uncompressed, the encoder is flushed only if the
+    /// file seems to be too large with them, to tell its size after compression.
+    pub fn exceeds(&mut self, block: &SerializedBlock, max_file_size: usize) -> Result<bool> {
+        let size = self.encoder.compressed_len() + self.buffered + block.size;
+        if size + self.unflushed <= max_file_size {
+            return Ok(false);
+        }
+
+        if self.unflushed > 0 {
+            self.encoder.flush()?;
+            self.unflushed = 0;
+        }
+        Ok(self.encoder.compressed_len() + self.buffered + block.size > max_file_size)
+    }
+
+    pub fn write(&mut self, block: SerializedBlock) -> Result<()> {
+        match block.bytes {
+            Some(bytes) => {
+                self.encoder.write_all(&bytes)?;
+                self.unflushed += block.size;
+            }
+            None => {
+                let bytes = self.format.serialize_block(&block.block)?;
+                self.encoder.write_all(&bytes)?;
+                self.buffered += block.size;
+            }
+        }
+        self.num_blocks += 1;
+        Ok(())
+    }
+
+    /// Finalize the format and the compression, return the bytes of the file.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        let bytes = self.format.finalize()?;
+        self.encoder.write_all(&bytes)?;
+        self.encoder.finish()
+    }
+}
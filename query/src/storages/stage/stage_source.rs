@@ -20,14 +20,17 @@ use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_formats::FormatFactory;
 use common_io::prelude::init_s3_operator;
 use common_io::prelude::StorageParams;
+use common_meta_types::OnErrorMode;
 use common_meta_types::StageFileCompression;
 use common_meta_types::StageFileFormatType;
 use common_meta_types::StageType;
 use common_meta_types::UserStageInfo;
 use common_planners::StageTableInfo;
 use common_streams::CsvSourceBuilder;
+use common_streams::InputFormatSource;
 use common_streams::NDJsonSourceBuilder;
 use common_streams::ParquetSourceBuilder;
 use common_streams::Source;
@@ -43,6 +46,7 @@ use crate::pipelines::new::processors::processor::ProcessorPtr;
 use crate::pipelines::new::processors::AsyncSource;
 use crate::pipelines::new::processors::AsyncSourcer;
 use crate::sessions::QueryContext;
+use crate::storages::stage::CopyFileStatus;
 
 pub struct StageSource {
     ctx: Arc<QueryContext>,
@@ -51,7 +55,10 @@ pub struct StageSource {
     initialized: bool,
     source: Option<Box<dyn Source>>,
     files: Arc<Mutex<VecDeque<String>>>,
-    current_file: Option<String>,
+    status: CopyFileStatus,
+    // Blocks of the current file held back until the whole file is read, see `ON_ERROR`.
+    pending_blocks: Vec<DataBlock>,
+    ready_blocks: VecDeque<DataBlock>,
 }
 
 impl StageSource {
@@ -69,10 +76,20 @@ impl StageSource {
             initialized: false,
             source: None,
             files,
-            current_file: None,
+            status: CopyFileStatus::default(),
+            pending_blocks: vec![],
+            ready_blocks: VecDeque::new(),
         })
     }
 
+    // Skip the rows which can't be parsed unless the statement aborts on the first error.
+    fn skip_error_rows(stage_info: &UserStageInfo) -> bool {
+        !matches!(
+            stage_info.copy_options.on_error,
+            OnErrorMode::None | OnErrorMode::AbortStatement
+        )
+    }
+
     // Get csv source stream.
     async fn csv_source(
         ctx: Arc<QueryContext>,
@@ -102,6 +119,11 @@ impl StageSource {
             builder.skip_header(stage_info.file_format_options.skip_header > 0);
         }
 
+        // On error.
+        {
+            builder.skip_error_rows(Self::skip_error_rows(stage_info));
+        }
+
         // Field delimiter, default ','.
         {
             let field_delimiter = &stage_info.file_format_options.field_delimiter;
@@ -140,6 +162,11 @@ impl StageSource {
             builder.block_size(max_block_size as usize);
         }
 
+        // On error.
+        {
+            builder.skip_error_rows(Self::skip_error_rows(stage_info));
+        }
+
         Ok(Box::new(builder.build(BufReader::new(reader))?))
     }
 
@@ -161,6 +188,29 @@ impl StageSource {
         Ok(Box::new(builder.build(reader)?))
     }
 
+    // Get source stream of the formats which need the whole file, like avro and orc.
+    async fn input_format_source(
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        stage_info: &UserStageInfo,
+        reader: BytesReader,
+    ) -> Result<Box<dyn Source>> {
+        let format_name = format!("{:?}", stage_info.file_format_options.format);
+        let settings = ctx.get_format_settings()?;
+        let input_format = FormatFactory::instance().get_input(format_name, schema, settings)?;
+        let mut source = InputFormatSource::create(reader, input_format);
+
+        // Size limit.
+        {
+            let size_limit = stage_info.copy_options.size_limit;
+            if size_limit > 0 {
+                source = source.size_limit(size_limit);
+            }
+        }
+
+        Ok(Box::new(source))
+    }
+
     pub async fn get_op(ctx: &Arc<QueryContext>, stage: &UserStageInfo) -> Result<Operator> {
         if stage.stage_type == StageType::Internal {
             ctx.get_storage_operator()
@@ -194,6 +244,14 @@ impl StageSource {
 
         // TODO(xuanwo): we need to unify with MultipartFormat.
         let compression_algo = match stage.file_format_options.compression {
+            // `.deflate` and `.raw_deflate` are the extensions of the unloaded files compressed
+            // by `DEFLATE` (zlib) and `RAW_DEFLATE`.
+            StageFileCompression::Auto if path.ends_with(".deflate") => {
+                Some(CompressAlgorithm::Zlib)
+            }
+            StageFileCompression::Auto if path.ends_with(".raw_deflate") => {
+                Some(CompressAlgorithm::Deflate)
+            }
             StageFileCompression::Auto => CompressAlgorithm::from_path(&path),
             StageFileCompression::Gzip => Some(CompressAlgorithm::Gzip),
             StageFileCompression::Bz2 => Some(CompressAlgorithm::Bz2),
@@ -217,7 +275,7 @@ impl StageSource {
             stage.stage_name, &path, compression_algo
         );
 
-        // Get the format(CSV, Parquet) source stream.
+        // Get the format(CSV, Parquet, ...) source stream.
         let source = match &file_format {
             StageFileFormatType::Csv => Ok(Self::csv_source(
                 ctx.clone(),
@@ -246,6 +304,16 @@ impl StageSource {
                 object.seekable_reader(..),
             )
             .await?),
+            StageFileFormatType::Avro | StageFileFormatType::Orc => Ok(Self::input_format_source(
+                ctx.clone(),
+                self.schema.clone(),
+                stage,
+                match compression_algo {
+                    None => Box::new(object.reader().await?),
+                    Some(algo) => Box::new(object.decompress_reader_with(algo).await?),
+                },
+            )
+            .await?),
             // Unsupported.
             format => Err(ErrorCode::LogicalError(format!(
                 "Unsupported file format: {:?}",
@@ -253,7 +321,6 @@ impl StageSource {
             ))),
         }?;
         self.source = Some(source);
-        self.current_file = Some(path.clone());
 
         Ok(())
     }
@@ -265,33 +332,128 @@ impl AsyncSource for StageSource {
 
     #[async_trait::unboxed_simple]
     async fn generate(&mut self) -> Result<Option<DataBlock>> {
-        let file_name = if !self.initialized {
-            let mut files_guard = self.files.lock();
-            let file_name = files_guard.pop_front();
-            drop(files_guard);
+        loop {
+            if let Some(block) = self.ready_blocks.pop_front() {
+                return Ok(Some(block));
+            }
 
-            file_name
-        } else {
-            self.current_file.clone()
-        };
+            if !self.initialized {
+                let file_name = self.files.lock().pop_front();
+                let file_name = match file_name {
+                    None => return Ok(None),
+                    Some(file_name) => file_name,
+                };
+
+                self.status = CopyFileStatus::create(&file_name);
+                self.initialized = true;
+                if let Err(cause) = self.initialize(file_name).await {
+                    self.on_file_error(cause)?;
+                    continue;
+                }
+            }
+
+            let source = match &mut self.source {
+                None => return Err(ErrorCode::LogicalError("Please init source first!")),
+                Some(source) => source,
+            };
 
-        if !self.initialized {
-            if file_name.is_none() {
-                return Ok(None);
+            match source.read().await {
+                Ok(None) => self.finish_file(false),
+                Ok(Some(block)) => {
+                    if let Some(block) = self.on_block(block) {
+                        return Ok(Some(block));
+                    }
+                }
+                Err(cause) => self.on_file_error(cause)?,
             }
-            self.initialize(file_name.unwrap()).await?;
-            self.initialized = true;
         }
+    }
+}
 
-        match &mut self.source {
-            None => Err(ErrorCode::LogicalError("Please init source first!")),
-            Some(source) => match source.read().await? {
-                None => {
-                    self.initialized = false;
-                    Ok(Some(DataBlock::empty_with_schema(self.schema.clone())))
-                }
-                Some(data) => Ok(Some(data)),
-            },
+/// Handling of the `ON_ERROR` copy option.
+///
+/// - `NONE`, `ABORT_STATEMENT`: the first error fails the statement.
+/// - `CONTINUE`: bad rows are skipped, a file which can't be read any further stops there.
+/// - `SKIP_FILE`, `SKIP_FILE_<num>`: the file is skipped if it has any (or `num`) errors.
+///   Its blocks are held back until the whole file is read.
+impl StageSource {
+    fn on_error_mode(&self) -> &OnErrorMode {
+        &self.table_info.stage_info.copy_options.on_error
+    }
+
+    fn collect_row_errors(&mut self) {
+        if let Some(source) = &self.source {
+            let errors = source.row_errors();
+            self.status.errors_seen = errors.num_errors;
+            if let Some((line, message)) = errors.first_error {
+                self.status.first_error = Some(message);
+                self.status.first_error_line = Some(line);
+            }
+        }
+    }
+
+    fn error_limit_reached(&self) -> bool {
+        let errors_seen = self.status.errors_seen;
+        match self.on_error_mode() {
+            OnErrorMode::SkipFile => errors_seen > 0,
+            OnErrorMode::SkipFileNum(num) => errors_seen > 0 && errors_seen as u64 >= *num,
+            _ => false,
+        }
+    }
+
+    fn on_block(&mut self, block: DataBlock) -> Option<DataBlock> {
+        self.collect_row_errors();
+        self.status.rows_loaded += block.num_rows();
+
+        let hold_back = matches!(
+            self.on_error_mode(),
+            OnErrorMode::SkipFile | OnErrorMode::SkipFileNum(_)
+        );
+        if !hold_back {
+            return Some(block);
+        }
+
+        match self.error_limit_reached() {
+            true => self.finish_file(true),
+            false => self.pending_blocks.push(block),
+        }
+        None
+    }
+
+    fn on_file_error(&mut self, cause: ErrorCode) -> Result<()> {
+        let skip = match self.on_error_mode() {
+            OnErrorMode::None | OnErrorMode::AbortStatement => return Err(cause),
+            OnErrorMode::Continue => false,
+            OnErrorMode::SkipFile | OnErrorMode::SkipFileNum(_) => true,
+        };
+
+        self.collect_row_errors();
+        self.source = None;
+        self.status.add_error(None, cause.message());
+        self.finish_file(skip);
+        Ok(())
+    }
+
+    fn finish_file(&mut self, skip: bool) {
+        self.collect_row_errors();
+
+        let skip = skip || self.error_limit_reached();
+        let pending_blocks = std::mem::take(&mut self.pending_blocks);
+        if skip {
+            self.status.rows_loaded = 0;
+            self.status.skipped = true;
+        } else {
+            self.ready_blocks.extend(pending_blocks);
         }
+
+        let status = std::mem::take(&mut self.status);
+        info!(
+            "Copy file {} finished, rows loaded: {}, errors seen: {}, skipped: {}",
+            status.file, status.rows_loaded, status.errors_seen, skip
+        );
+        self.ctx.push_copy_file_status(status);
+
+        self.source = None;
+        self.initialized = false;
     }
 }
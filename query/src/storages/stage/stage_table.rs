@@ -14,7 +14,6 @@
 
 use std::any::Any;
 use std::collections::VecDeque;
-use std::str::FromStr;
 use std::sync::Arc;
 
 use common_base::infallible::Mutex;
@@ -22,7 +21,11 @@ use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_formats::output_format::OutputFormatType;
+use common_io::prelude::FormatSettings;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
+use common_meta_types::StageFileCompression;
+use common_meta_types::StageFileFormatType;
 use common_planners::Extras;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
@@ -31,6 +34,8 @@ use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
 
+use super::stage_file_writer::SerializedBlock;
+use super::stage_file_writer::StageFileWriter;
 use super::StageSource;
 use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::TransformLimit;
@@ -55,6 +60,92 @@ impl StageTable {
             table_info_placeholder,
         }))
     }
+
+    fn output_format_type(format: &StageFileFormatType) -> Result<OutputFormatType> {
+        match format {
+            StageFileFormatType::Csv => Ok(OutputFormatType::CSV),
+            StageFileFormatType::Tsv => Ok(OutputFormatType::TSV),
+            StageFileFormatType::Json => Ok(OutputFormatType::JsonEachRow),
+            StageFileFormatType::Parquet => Ok(OutputFormatType::Parquet),
+            format => Err(ErrorCode::UnImplement(format!(
+                "Unload to {:?} file is unimplemented",
+                format
+            ))),
+        }
+    }
+
+    /// Serialize a block to be unloaded, the block is sliced if it is larger than
+    /// `max_file_size` bytes serialized, so that it fits in a file.
+    fn serialize_block(
+        &self,
+        fmt: &OutputFormatType,
+        format_settings: &FormatSettings,
+        block: DataBlock,
+        max_file_size: usize,
+    ) -> Result<Vec<SerializedBlock>> {
+        let (bytes, size) = match fmt {
+            // Parquet buffers the blocks until the file is finalized, the block is serialized
+            // only once by the file writer, and its size is estimated by its size in memory,
+            // which is no less than its size encoded in most cases.
+            OutputFormatType::Parquet => (None, block.memory_size()),
+            _ => {
+                let mut output_format =
+                    fmt.create_format(self.table_info.schema(), format_settings.clone());
+                let bytes = output_format.serialize_block(&block)?;
+                let size = bytes.len();
+                (Some(bytes), size)
+            }
+        };
+
+        let num_rows = block.num_rows();
+        if max_file_size == 0 || size <= max_file_size || num_rows <= 1 {
+            return Ok(vec![SerializedBlock { block, bytes, size }]);
+        }
+
+        let num_slices = (size + max_file_size - 1) / max_file_size;
+        let rows_per_slice = (num_rows + num_slices - 1) / num_slices;
+        let mut blocks = Vec::with_capacity(num_slices);
+        for offset in (0..num_rows).step_by(rows_per_slice) {
+            let slice = block.slice(offset, rows_per_slice.min(num_rows - offset));
+            blocks.extend(self.serialize_block(fmt, format_settings, slice, max_file_size)?);
+        }
+        Ok(blocks)
+    }
+
+    /// Serialize the blocks into files of `max_file_size` bytes at most after compression,
+    /// unless a single row is larger. At least one file is returned.
+    fn serialize_files(
+        &self,
+        fmt: &OutputFormatType,
+        format_settings: &FormatSettings,
+        operations: Vec<DataBlock>,
+    ) -> Result<Vec<Vec<u8>>> {
+        let stage_info = &self.table_info.stage_info;
+        let compression = &stage_info.file_format_options.compression;
+        let max_file_size = stage_info.copy_options.max_file_size;
+        let create_writer = || {
+            let output_format =
+                fmt.create_format(self.table_info.schema(), format_settings.clone());
+            StageFileWriter::try_create(output_format, compression)
+        };
+
+        let mut files = vec![];
+        let mut writer = create_writer()?;
+        for block in operations {
+            for block in self.serialize_block(fmt, format_settings, block, max_file_size)? {
+                if max_file_size > 0
+                    && !writer.is_empty()
+                    && writer.exceeds(&block, max_file_size)?
+                {
+                    let full = std::mem::replace(&mut writer, create_writer()?);
+                    files.push(full.finish()?);
+                }
+                writer.write(block)?;
+            }
+        }
+        files.push(writer.finish()?);
+        Ok(files)
+    }
 }
 
 #[async_trait::async_trait]
@@ -150,25 +241,26 @@ impl Table for StageTable {
         ctx: Arc<QueryContext>,
         _catalog_name: &str,
         operations: Vec<DataBlock>,
+        _copied_files: Option<UpsertTableCopiedFileReq>,
         _overwrite: bool,
     ) -> Result<()> {
-        let format_name = format!(
-            "{:?}",
-            self.table_info.stage_info.file_format_options.format
-        );
-        let path = format!(
-            "{}/{}.{}",
-            self.table_info.path,
-            uuid::Uuid::new_v4(),
-            format_name.to_ascii_lowercase()
-        );
-
-        let op = StageSource::get_op(&ctx, &self.table_info.stage_info).await?;
-
-        let fmt = OutputFormatType::from_str(format_name.as_str())?;
-        let mut format_settings = ctx.get_format_settings()?;
+        let stage_info = &self.table_info.stage_info;
+        let format_options = &stage_info.file_format_options;
+        let fmt = Self::output_format_type(&format_options.format)?;
+        if fmt == OutputFormatType::Parquet
+            && !matches!(
+                format_options.compression,
+                StageFileCompression::Auto | StageFileCompression::None
+            )
+        {
+            return Err(ErrorCode::UnImplement(
+                "compress type of parquet file is unimplemented",
+            ));
+        }
 
-        let format_options = &self.table_info.stage_info.file_format_options;
+        let op = StageSource::get_op(&ctx, stage_info).await?;
+
+        let mut format_settings = ctx.get_format_settings()?;
         {
             format_settings.skip_header = format_options.skip_header > 0;
             if !format_options.field_delimiter.is_empty() {
@@ -180,26 +272,31 @@ impl Table for StageTable {
                     format_options.record_delimiter.as_bytes().to_vec();
             }
         }
-        let mut output_format = fmt.create_format(self.table_info.schema(), format_settings);
 
-        let prefix = output_format.serialize_prefix()?;
-        let written_bytes: usize = operations.iter().map(|b| b.memory_size()).sum();
-        let mut bytes = Vec::with_capacity(written_bytes + prefix.len());
-        bytes.extend_from_slice(&prefix);
-        for block in operations {
-            let bs = output_format.serialize_block(&block)?;
-            bytes.extend_from_slice(bs.as_slice());
-        }
+        let files = self.serialize_files(&fmt, &format_settings, operations)?;
 
-        let bs = output_format.finalize()?;
-        bytes.extend_from_slice(bs.as_slice());
+        let format_name = format!("{:?}", format_options.format).to_ascii_lowercase();
+        let compression_ext = StageFileWriter::compression_extension(&format_options.compression)?;
+        let file_prefix = uuid::Uuid::new_v4();
+        for (index, bytes) in files.into_iter().enumerate() {
+            let mut path = format!(
+                "{}/{}_{}.{}",
+                self.table_info.path.trim_end_matches('/'),
+                file_prefix,
+                index,
+                format_name
+            );
+            if let Some(ext) = compression_ext {
+                path = format!("{}.{}", path, ext);
+            }
 
-        ctx.get_dal_context()
-            .get_metrics()
-            .inc_write_bytes(bytes.len());
+            ctx.get_dal_context()
+                .get_metrics()
+                .inc_write_bytes(bytes.len());
 
-        let object = op.object(&path);
-        object.write(bytes.as_slice()).await?;
+            let object = op.object(&path);
+            object.write(bytes.as_slice()).await?;
+        }
         Ok(())
     }
 
@@ -19,23 +19,28 @@ use std::sync::Arc;
 use chrono::DateTime;
 use chrono::Utc;
 use common_datablocks::DataBlock;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_meta_types::MetaId;
 use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::MergeIntoPlan;
 use common_planners::OptimizeTablePlan;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
 use common_streams::SendableDataBlockStream;
 
 use crate::pipelines::new::NewPipeline;
 use crate::sessions::QueryContext;
+use crate::storages::index::StatisticsOfColumns;
 
 #[async_trait::async_trait]
 pub trait Table: Sync + Send {
@@ -108,6 +113,60 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn add_column(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _catalog_name: &str,
+        _field: DataField,
+        _comment: String,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {}, of engine type {}, does not support ADD COLUMN",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
+    async fn drop_column(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _catalog_name: &str,
+        _column: &str,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {}, of engine type {}, does not support DROP COLUMN",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
+    async fn rename_column(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _catalog_name: &str,
+        _column: &str,
+        _new_column: &str,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {}, of engine type {}, does not support RENAME COLUMN",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
+    async fn recluster(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _catalog_name: &str,
+        _is_final: bool,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {}, of engine type {}, does not support RECLUSTER",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     // defaults to generate one single part and empty statistics
     async fn read_partitions(
         &self,
@@ -160,6 +219,7 @@ pub trait Table: Sync + Send {
         _ctx: Arc<QueryContext>,
         _catalog_name: &str,
         _operations: Vec<DataBlock>,
+        _copied_files: Option<UpsertTableCopiedFileReq>,
         _overwrite: bool,
     ) -> Result<()> {
         Ok(())
@@ -180,10 +240,24 @@ pub trait Table: Sync + Send {
         Ok(())
     }
 
+    /// Returns the files that `optimize` would purge, without purging them.
+    async fn optimize_dry_run(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _keep_last_snapshot: bool,
+    ) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
     async fn statistics(&self, _ctx: Arc<QueryContext>) -> Result<Option<TableStatistics>> {
         Ok(None)
     }
 
+    /// Returns the statistics of each column, keyed by the index of the column in table schema.
+    async fn column_statistics(&self, _ctx: Arc<QueryContext>) -> Result<StatisticsOfColumns> {
+        Ok(StatisticsOfColumns::new())
+    }
+
     async fn navigate_to(
         &self,
         _ctx: Arc<QueryContext>,
@@ -204,6 +278,28 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn update(&self, _ctx: Arc<QueryContext>, _update_plan: UpdatePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support UPDATE",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
+    /// Merges the `source` rows into the table, the blocks of `source` are of `plan.source_schema`.
+    async fn merge_into(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _plan: MergeIntoPlan,
+        _source: SendableDataBlockStream,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support MERGE INTO",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     async fn compact(&self, _ctx: Arc<QueryContext>, _plan: OptimizeTablePlan) -> Result<()> {
         Err(ErrorCode::UnImplement(format!(
             "table {},  of engine type {}, does not support compact",
@@ -46,7 +46,10 @@ use crate::pipelines::new::NewPipeline;
 use crate::pipelines::new::SourcePipeBuilder;
 use crate::pipelines::transforms::get_sort_descriptions;
 use crate::sessions::QueryContext;
+use crate::storages::index::ColumnStatistics;
+use crate::storages::index::StatisticsOfColumns;
 use crate::storages::Table;
+use crate::storages::TableStatistics;
 use crate::table_functions::generate_numbers_parts;
 use crate::table_functions::numbers_part::NumbersPartInfo;
 use crate::table_functions::table_function_factory::TableArgs;
@@ -178,6 +181,28 @@ impl Table for NumbersTable {
         ))])
     }
 
+    async fn statistics(&self, _ctx: Arc<QueryContext>) -> Result<Option<TableStatistics>> {
+        Ok(Some(TableStatistics {
+            num_rows: Some(self.total),
+            data_size: Some(self.total * size_of::<u64>() as u64),
+            data_size_compressed: None,
+            index_length: None,
+        }))
+    }
+
+    async fn column_statistics(&self, _ctx: Arc<QueryContext>) -> Result<StatisticsOfColumns> {
+        let mut statistics = StatisticsOfColumns::new();
+        if self.total > 0 {
+            statistics.insert(0, ColumnStatistics {
+                min: DataValue::UInt64(0),
+                max: DataValue::UInt64(self.total - 1),
+                null_count: 0,
+                in_memory_size: self.total * size_of::<u64>() as u64,
+            });
+        }
+        Ok(statistics)
+    }
+
     async fn read(
         &self,
         ctx: Arc<QueryContext>,
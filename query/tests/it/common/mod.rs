@@ -14,3 +14,5 @@
 
 mod evaluator;
 mod expression_evaluator;
+mod runtime_filter;
+mod spill;
@@ -0,0 +1,73 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::common::RuntimeFilter;
+
+#[test]
+fn test_runtime_filter() -> Result<()> {
+    let filter = RuntimeFilter::create();
+    filter.collect(&Series::from_data(vec![Some(5i32), None, Some(3)]))?;
+    filter.collect(&Series::from_data(vec![8i32, 3]))?;
+    // Nothing is filtered before the build side is finished.
+    assert!(filter.values().is_none());
+
+    filter.publish();
+    let values = filter.values().unwrap();
+    assert!(values.may_intersect(&DataValue::Int64(0), &DataValue::Int64(3)));
+    assert!(values.may_intersect(&DataValue::Int64(8), &DataValue::Int64(10)));
+    // Out of the range of the keys.
+    assert!(!values.may_intersect(&DataValue::Int64(9), &DataValue::Int64(10)));
+    // In the range but no key in it.
+    assert!(!values.may_intersect(&DataValue::Int64(6), &DataValue::Int64(7)));
+
+    let column = Series::from_data(vec![Some(3i32), Some(4), None, Some(8)]);
+    let predicate = values.filter(&column);
+    assert_eq!(predicate.to_values(), vec![
+        DataValue::Boolean(true),
+        DataValue::Boolean(false),
+        DataValue::Boolean(false),
+        DataValue::Boolean(true),
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_runtime_filter_without_in_list() -> Result<()> {
+    let filter = RuntimeFilter::create();
+    filter.collect(&Series::from_data(
+        (0..5000u64).map(|v| v * 2).collect::<Vec<_>>(),
+    ))?;
+    filter.publish();
+
+    // Too many keys to keep, only the range is checked.
+    let values = filter.values().unwrap();
+    assert!(values.may_intersect(&DataValue::UInt64(3), &DataValue::UInt64(3)));
+    assert!(!values.may_intersect(&DataValue::UInt64(10000), &DataValue::UInt64(20000)));
+    Ok(())
+}
+
+#[test]
+fn test_empty_runtime_filter() -> Result<()> {
+    let filter = RuntimeFilter::create();
+    filter.collect(&Series::from_data(vec![None::<&str>, None]))?;
+    filter.publish();
+
+    // No key can be joined.
+    let values = filter.values().unwrap();
+    assert!(!values.may_intersect(&DataValue::from("a"), &DataValue::from("z")));
+    assert!(!values.may_contain(&DataValue::from("a")));
+    Ok(())
+}
@@ -0,0 +1,111 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_datablocks::assert_blocks_sorted_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::common::scatter_by_hash;
+use databend_query::common::MemoryBudget;
+use databend_query::common::MemoryReservation;
+use databend_query::common::Spiller;
+
+use crate::tests::create_query_context;
+
+#[test]
+fn test_memory_reservation() -> Result<()> {
+    let budget = MemoryBudget::create(100);
+    let mut r1 = MemoryReservation::create(budget.clone());
+    let mut r2 = MemoryReservation::create(budget.clone());
+    assert!(r1.can_spill());
+
+    assert!(r1.try_resize(60));
+    assert!(!r2.try_resize(50));
+    assert_eq!(r2.size(), 0);
+    assert!(r2.try_resize(40));
+    assert_eq!(budget.used(), 100);
+
+    assert!(r1.try_resize(10));
+    assert_eq!(budget.used(), 50);
+    r1.free();
+    assert_eq!(budget.used(), 40);
+    drop(r2);
+    assert_eq!(budget.used(), 0);
+
+    // Unlimited budget never fails.
+    let budget = MemoryBudget::create(0);
+    let mut r3 = MemoryReservation::create(budget.clone());
+    assert!(!r3.can_spill());
+    assert!(r3.try_resize(usize::MAX / 2));
+    assert_eq!(budget.used(), usize::MAX / 2);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_spiller() -> Result<()> {
+    let ctx = create_query_context().await?;
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i32::to_data_type()),
+        DataField::new_nullable("b", Vu8::to_data_type()),
+    ]);
+    let block = DataBlock::create(schema.clone(), vec![
+        Series::from_data(vec![1i32, 2, 3, 1, 2]),
+        Series::from_data(vec![Some("x"), None, Some("y"), Some("z"), None]),
+    ]);
+
+    let blocks = scatter_by_hash(&block, &[block.column(0).clone()], 3)?;
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(blocks.iter().map(|b| b.num_rows()).sum::<usize>(), 5);
+
+    let mut spiller = Spiller::create(&ctx, "test", schema)?;
+    assert!(spiller.is_empty());
+    spiller.spill_scattered(&blocks)?;
+
+    // Rows with equal keys are always in the same partition.
+    let mut partition_keys = vec![];
+    for partition in spiller.partitions() {
+        let blocks = spiller.read(partition)?;
+        partition_keys.push(DataBlock::concat_blocks(&blocks)?.column(0).to_values());
+    }
+    assert_eq!(partition_keys.iter().map(|k| k.len()).sum::<usize>(), 5);
+    for (i, keys) in partition_keys.iter().enumerate() {
+        for other_keys in partition_keys.iter().skip(i + 1) {
+            assert!(keys.iter().all(|key| !other_keys.contains(key)));
+        }
+    }
+
+    spiller.spill(7, &[block.clone()])?;
+    spiller.spill(7, &[block])?;
+    assert_eq!(spiller.num_files(7), 2);
+    let expected = vec![
+        "+---+------+",
+        "| a | b    |",
+        "+---+------+",
+        "| 1 | x    |",
+        "| 1 | z    |",
+        "| 2 | NULL |",
+        "| 2 | NULL |",
+        "| 3 | y    |",
+        "+---+------+",
+    ];
+    assert_blocks_sorted_eq(expected, &spiller.read_file(7, 1)?);
+    assert_eq!(spiller.read(7)?.len(), 2);
+
+    for partition in spiller.partitions() {
+        spiller.remove(partition)?;
+    }
+    assert!(spiller.is_empty());
+    Ok(())
+}
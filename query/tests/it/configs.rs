@@ -71,6 +71,9 @@ jwt_key_file = ""
 async_insert_max_data_size = 10000
 async_insert_busy_timeout = 200
 async_insert_stale_timeout = 0
+auto_compaction_interval_secs = 60
+auto_compaction_max_concurrent_jobs = 1
+spill_local_path = ""
 
 [log]
 level = "INFO"
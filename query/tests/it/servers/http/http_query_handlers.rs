@@ -445,6 +445,31 @@ async fn test_http_session() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_with_params() -> Result<()> {
+    let ep = create_endpoint();
+
+    let json = serde_json::json!({"sql": "select $2, ? + 1", "params": [1, "databend"]});
+    let (status, result) = post_json_to_endpoint(&ep, &json).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert!(result.error.is_none(), "{:?}", result);
+    assert_eq!(result.data.len(), 1, "{:?}", result);
+    assert_eq!(result.data[0][0], "databend", "{:?}", result);
+    assert_eq!(result.data[0][1].as_u64().unwrap(), 2, "{:?}", result);
+
+    let json = serde_json::json!({"sql": "select ?, ?", "params": [1]});
+    let (status, result) = post_json_to_endpoint(&ep, &json).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert!(result.error.is_some(), "{:?}", result);
+
+    // The parameters are rejected if the new planner is disabled.
+    let json = serde_json::json!({"sql": "select ?", "params": [1], "session": { "settings": {"enable_planner_v2": "0"}}});
+    let (status, result) = post_json_to_endpoint(&ep, &json).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert!(result.error.is_some(), "{:?}", result);
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[ignore = "flaky, sleep time unreliable"]
 async fn test_result_timeout() -> Result<()> {
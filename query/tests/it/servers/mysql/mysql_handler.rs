@@ -21,6 +21,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
 use databend_query::servers::MySQLHandler;
+use mysql_async::consts::ColumnType;
 use mysql_async::prelude::FromRow;
 use mysql_async::prelude::Queryable;
 use mysql_async::FromRowError;
@@ -45,6 +46,99 @@ async fn test_generic_code_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_prepared_statement() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port()).await?;
+
+    let statement = connection
+        .prep("SELECT concat(?, $2)")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+    for (param, expected) in [("data", "databend"), ("hello ", "hello bend")] {
+        let result: Option<String> = connection
+            .exec_first(&statement, (param, "bend"))
+            .await
+            .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+        assert_eq!(result.as_deref(), Some(expected));
+    }
+
+    connection
+        .close(statement)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Close failed")?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_prepared_statement_with_typed_params() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port()).await?;
+
+    connection
+        .query_drop("CREATE TABLE t(a INT, b VARCHAR)")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Create table failed")?;
+    connection
+        .query_drop("INSERT INTO t VALUES (1, 'a'), (2, 'b')")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Insert failed")?;
+
+    let statement = connection
+        .prep("SELECT a, b FROM t WHERE a = ? OR b = ?")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+    let param_types = statement
+        .params()
+        .iter()
+        .map(|param| param.column_type())
+        .collect::<Vec<_>>();
+    assert_eq!(param_types, vec![
+        ColumnType::MYSQL_TYPE_LONGLONG,
+        ColumnType::MYSQL_TYPE_VARCHAR
+    ]);
+    let column_types = statement
+        .columns()
+        .iter()
+        .map(|column| column.column_type())
+        .collect::<Vec<_>>();
+    assert_eq!(column_types, vec![
+        ColumnType::MYSQL_TYPE_LONGLONG,
+        ColumnType::MYSQL_TYPE_VARCHAR
+    ]);
+
+    for (params, expected) in [((1, "x"), vec![1]), ((3, "b"), vec![2])] {
+        let result: Vec<(i32, String)> = connection
+            .exec(&statement, params)
+            .await
+            .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+        let result = result.into_iter().map(|(a, _)| a).collect::<Vec<_>>();
+        assert_eq!(result, expected);
+    }
+
+    // The cached plan is not reused once the table is changed.
+    connection
+        .query_drop("INSERT INTO t VALUES (3, 'c')")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Insert failed")?;
+    let result: Vec<(i32, String)> = connection
+        .exec(&statement, (3, "x"))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    assert_eq!(result, vec![(3, "c".to_string())]);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_rejected_session_with_sequence() -> Result<()> {
     let mut handler =
@@ -0,0 +1,130 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Backtrace;
+use common_base::base::tokio;
+use common_base::infallible::RwLock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use databend_query::sql::optimizer::optimize_distributed_query;
+use databend_query::sql::optimizer::HeuristicOptimizer;
+use databend_query::sql::optimizer::RuleList;
+use databend_query::sql::optimizer::SExpr;
+use databend_query::sql::optimizer::DEFAULT_REWRITE_RULES;
+use databend_query::sql::plans::AggregateMode;
+use databend_query::sql::plans::Exchange;
+use databend_query::sql::plans::Plan;
+use databend_query::sql::plans::RelOperator;
+use databend_query::sql::Binder;
+use databend_query::sql::Metadata;
+
+use crate::tests::create_query_context;
+
+async fn optimize_distributed(ctx: Arc<QueryContext>, query: &str) -> Result<SExpr> {
+    let tokens = tokenize_sql(query)?;
+    let bt = Backtrace::new();
+    let (stmt, _) = parse_sql(&tokens, &bt)?;
+    let binder = Binder::new(
+        ctx.clone(),
+        ctx.get_catalogs(),
+        Arc::new(RwLock::new(Metadata::create())),
+    );
+    match binder.bind(&stmt).await? {
+        Plan::Query {
+            s_expr, metadata, ..
+        } => {
+            let rules = RuleList::create(DEFAULT_REWRITE_RULES.clone())?;
+            let mut heuristic_opt = HeuristicOptimizer::new(ctx.clone(), metadata, rules);
+            optimize_distributed_query(&heuristic_opt.optimize(s_expr)?)
+        }
+        _ => Err(ErrorCode::LogicalError("Unsupported non-query statement")),
+    }
+}
+
+fn find_aggregate<'a>(s_expr: &'a SExpr, mode: &AggregateMode) -> Option<&'a SExpr> {
+    match s_expr.plan() {
+        RelOperator::Aggregate(agg) if &agg.mode == mode => Some(s_expr),
+        _ => s_expr
+            .children()
+            .iter()
+            .find_map(|child| find_aggregate(child, mode)),
+    }
+}
+
+fn count_exchanges(s_expr: &SExpr) -> usize {
+    let exchanges = s_expr.children().iter().map(count_exchanges).sum();
+    match s_expr.plan() {
+        RelOperator::Exchange(_) => exchanges + 1,
+        _ => exchanges,
+    }
+}
+
+#[tokio::test]
+async fn test_distributed_group_by_exchange() -> Result<()> {
+    let ctx = create_query_context().await?;
+    let query = "select number % 3 as k, count(*) from numbers(10) group by k";
+    let s_expr = optimize_distributed(ctx, query).await?;
+
+    // the aggregation is split, and the partial states are shuffled by the group keys
+    assert!(find_aggregate(&s_expr, &AggregateMode::Initial).is_none());
+    let final_agg = find_aggregate(&s_expr, &AggregateMode::Final).unwrap();
+    let exchange = final_agg.child(0)?;
+    assert!(matches!(
+        exchange.plan(),
+        RelOperator::Exchange(Exchange::Hash(_))
+    ));
+    let partial_agg = exchange.child(0)?;
+    assert!(matches!(
+        partial_agg.plan(),
+        RelOperator::Aggregate(agg) if agg.mode == AggregateMode::Partial
+    ));
+
+    // the partial aggregation is done locally, and the final results are merged
+    assert_eq!(count_exchanges(partial_agg), 0);
+    assert!(matches!(
+        s_expr.plan(),
+        RelOperator::Exchange(Exchange::Merge)
+    ));
+    assert_eq!(count_exchanges(&s_expr), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_distributed_scalar_aggregate_exchange() -> Result<()> {
+    let ctx = create_query_context().await?;
+    let query = "select count(*), sum(number) from numbers(10)";
+    let s_expr = optimize_distributed(ctx, query).await?;
+
+    // the partial states are merged to the final aggregation on the local node
+    let final_agg = find_aggregate(&s_expr, &AggregateMode::Final).unwrap();
+    let exchange = final_agg.child(0)?;
+    assert!(matches!(
+        exchange.plan(),
+        RelOperator::Exchange(Exchange::Merge)
+    ));
+    let partial_agg = exchange.child(0)?;
+    assert!(matches!(
+        partial_agg.plan(),
+        RelOperator::Aggregate(agg) if agg.mode == AggregateMode::Partial
+    ));
+
+    // no more exchange is required since the final results are already local
+    assert_eq!(count_exchanges(&s_expr), 1);
+    Ok(())
+}
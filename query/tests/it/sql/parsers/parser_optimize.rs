@@ -27,6 +27,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             action: OptimizeTableAction::Purge,
+            dry_run: false,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -36,6 +37,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             action: OptimizeTableAction::Purge,
+            dry_run: false,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -45,6 +47,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             action: OptimizeTableAction::Purge,
+            dry_run: false,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -54,6 +57,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             action: OptimizeTableAction::Compact,
+            dry_run: false,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -63,10 +67,36 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             action: OptimizeTableAction::All,
+            dry_run: false,
         });
         expect_parse_ok(sql, expected)?;
     }
 
+    {
+        let sql = "optimize TABLE t1 dry run";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: OptimizeTableAction::Purge,
+            dry_run: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 purge dry run";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: OptimizeTableAction::Purge,
+            dry_run: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 compact dry run";
+        expect_parse_err(sql, "sql parser error: DRY RUN is only supported by PURGE")?;
+    }
+
     {
         let sql = "optimize TABLE t1 unacceptable";
         expect_parse_err(
@@ -39,7 +39,7 @@ async fn test_statement_copy() -> Result<()> {
         credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
         encryption=(master_key = 'my_master_key')
         file_format = (type = csv field_delimiter = '|' skip_header = 1)",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:None"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, max_file_size: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:None"#,
             err: "",
         },
 
@@ -52,7 +52,7 @@ async fn test_statement_copy() -> Result<()> {
         file_format = (type = csv field_delimiter = '|' skip_header = 1)
         VALIDATION_MODE = RETURN_13_ROWS
         ",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:ReturnNRows(13)"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, max_file_size: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,validation_mode:ReturnNRows(13)"#,
             err: "",
         },
 
@@ -66,7 +66,7 @@ async fn test_statement_copy() -> Result<()> {
         file_format = (type = csv field_delimiter = '|' skip_header = 1)
         VALIDATION_MODE = RETURN_13_ROWS
         ",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, max_file_size: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
             err: "",
         },
 
@@ -81,7 +81,7 @@ async fn test_statement_copy() -> Result<()> {
         on_error = CONTINUE size_limit = 10
         VALIDATION_MODE = RETURN_13_ROWS
         ",
-            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: Continue, size_limit: 10 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: Continue, size_limit: 10, max_file_size: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,files:["file1.csv", "file2.csv"] ,validation_mode:ReturnNRows(13)"#,
             err: "",
         },
 
@@ -100,6 +100,32 @@ async fn test_statement_copy() -> Result<()> {
             err: "Code: 1005, displayText = size_limit must be number, got: x0.",
         },
 
+        TestCase {
+            name: "copy-external-force-ok",
+            query: "copy into system.configs
+        from 's3://mybucket/data/files'
+        credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
+        encryption=(master_key = 'my_master_key')
+        file_format = (type = csv field_delimiter = '|' skip_header = 1)
+        force = true
+        ",
+            expect: r#"Copy into system.configs, ReadDataSourcePlan { catalog: "default", source_info: StageSource(UserStageInfo { stage_name: "s3://mybucket/data/files", stage_type: External, stage_params: StageParams { storage: S3(StorageS3Config { endpoint_url: "https://s3.amazonaws.com", region: "", bucket: "mybucket", root: "/", disable_credential_loader: true, enable_virtual_host_style: false, access_key_id: "******_id", secret_access_key: "******key", master_key: "******key" }) }, file_format_options: FileFormatOptions { format: Csv, skip_header: 1, field_delimiter: "|", record_delimiter: "", compression: None }, copy_options: CopyOptions { on_error: None, size_limit: 0, max_file_size: 0 }, comment: "", number_of_files: 0, creator: None }), scan_fields: None, parts: [], statistics: Statistics { read_rows: 0, read_bytes: 0, partitions_scanned: 0, partitions_total: 0, is_exact: false }, description: "", tbl_args: None, push_downs: None } ,force:true ,validation_mode:None"#,
+            err: "",
+        },
+
+        TestCase {
+            name: "copy-external-force-error",
+            query: "copy into system.configs
+        from 's3://mybucket/data/files'
+        credentials=(aws_key_id='my_key_id' aws_secret_key='my_secret_key')
+        encryption=(master_key = 'my_master_key')
+        file_format = (type = csv field_delimiter = '|' skip_header = 1)
+        force = yes
+        ",
+            expect: "",
+            err: "Code: 1005, displayText = force must be true or false, got: yes.",
+        },
+
         TestCase {
             name: "copy-external-validation-mode-error",
             query: "copy into system.configs
@@ -27,6 +27,7 @@ use databend_query::storages::fuse::io::BlockCompactor;
 use databend_query::storages::fuse::io::BlockReader;
 use databend_query::storages::fuse::io::BlockStreamWriter;
 use databend_query::storages::fuse::io::TableMetaLocationGenerator;
+use databend_query::storages::fuse::meta::ColumnIds;
 use databend_query::storages::fuse::meta::TableSnapshot;
 use databend_query::storages::fuse::meta::Versioned;
 use databend_query::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
@@ -60,6 +61,8 @@ async fn test_fuse_table_block_appender() -> Result<()> {
         0,
         locs.clone(),
         None,
+        ColumnIds::default(),
+        None,
     )
     .await?
     .collect::<Vec<_>>()
@@ -87,6 +90,8 @@ async fn test_fuse_table_block_appender() -> Result<()> {
         max_blocks_per_segment,
         locs.clone(),
         None,
+        ColumnIds::default(),
+        None,
     )
     .await?
     .collect::<Vec<_>>()
@@ -107,6 +112,8 @@ async fn test_fuse_table_block_appender() -> Result<()> {
         0,
         locs,
         None,
+        ColumnIds::default(),
+        None,
     )
     .await?
     .collect::<Vec<_>>()
@@ -276,6 +283,8 @@ async fn test_block_stream_writer() -> Result<()> {
             max_blocks_per_segment,
             locs,
             None,
+            ColumnIds::default(),
+            None,
         )
         .await?;
         let segs = stream.try_collect::<Vec<_>>().await?;
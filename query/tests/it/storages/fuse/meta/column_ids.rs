@@ -0,0 +1,77 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::storages::fuse::meta::ColumnIds;
+
+fn tuple_type() -> DataTypeImpl {
+    StructType::new_impl(vec!["x".to_owned(), "y".to_owned()], vec![
+        i32::to_data_type(),
+        i32::to_data_type(),
+    ])
+}
+
+#[test]
+fn test_column_ids_drop_column_next_to_tuple() -> Result<()> {
+    let schema = DataSchema::new(vec![
+        DataField::new("a", i32::to_data_type()),
+        DataField::new("b", tuple_type()),
+        DataField::new("c", i32::to_data_type()),
+    ]);
+    let mut column_ids = ColumnIds::from_schema(&schema)?;
+    assert!(column_ids.is_identity());
+
+    // a block written before the drop, the statistics are keyed by the fields, and the
+    // column metas are keyed by the leaf columns, the tuple is stored as two leaf columns.
+    let old_stats = column_ids.index_to_id(HashMap::from([(0, "a"), (1, "b"), (2, "c")]));
+    let old_metas =
+        column_ids.leaf_index_to_id(HashMap::from([(0, "a"), (1, "b.x"), (2, "b.y"), (3, "c")]));
+
+    column_ids.drop_column(0);
+    let mut fields = schema.fields().clone();
+    fields.remove(0);
+    let schema = column_ids.to_schema(fields, &schema)?;
+    let column_ids = ColumnIds::from_schema(&schema)?;
+    assert!(!column_ids.is_identity());
+
+    // a block written after the drop
+    let new_stats = column_ids.index_to_id(HashMap::from([(0, "b"), (1, "c")]));
+    let new_metas = column_ids.leaf_index_to_id(HashMap::from([(0, "b.x"), (1, "b.y"), (2, "c")]));
+    assert_eq!(new_stats, HashMap::from([(1, "b"), (2, "c")]));
+    assert_eq!(new_metas, HashMap::from([(1, "b.x"), (2, "b.y"), (3, "c")]));
+
+    // both blocks are addressed by the indices in the new schema
+    for (stats, metas) in [(&old_stats, &old_metas), (&new_stats, &new_metas)] {
+        assert_eq!(
+            column_ids.id_to_index(stats),
+            HashMap::from([(0, "b"), (1, "c")])
+        );
+        assert_eq!(
+            column_ids.leaf_id_to_index(metas),
+            HashMap::from([(0, "b.x"), (1, "b.y"), (2, "c")])
+        );
+    }
+
+    // a column added after the drop takes new ids
+    let mut column_ids = column_ids;
+    column_ids.add_column(&DataField::new("d", tuple_type()), None);
+    assert_eq!(column_ids.column_id(2), 3);
+    assert_eq!(column_ids.leaf_column_id(3), 4);
+    assert_eq!(column_ids.leaf_column_id(4), 5);
+
+    Ok(())
+}
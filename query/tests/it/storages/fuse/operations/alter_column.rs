@@ -0,0 +1,65 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::catalogs::CATALOG_DEFAULT;
+use databend_query::storages::fuse::FuseTable;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_add_column_with_non_deterministic_default() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(2, &fixture).await?;
+
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let field = DataField::new("ts", TimestampType::new_impl(6))
+        .with_default_expr(Some("now()".to_owned()));
+    fuse_table
+        .do_add_column(ctx.clone(), CATALOG_DEFAULT, field, "".to_owned())
+        .await?;
+
+    // the blocks written before the column was added, are read with the same value every time
+    let qry = format!("select distinct ts from '{}'.'{}'", db, tbl);
+    let first = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let second = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+
+    let first = common_datablocks::pretty_format_blocks(&first)?;
+    let second = common_datablocks::pretty_format_blocks(&second)?;
+    // the header, and one row
+    assert_eq!(first.lines().count(), 5);
+    assert_eq!(first, second);
+    Ok(())
+}
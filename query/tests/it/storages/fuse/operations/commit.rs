@@ -12,13 +12,23 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 //
+use std::sync::Arc;
+
 use common_base::base::tokio;
 use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use databend_query::catalogs::CATALOG_DEFAULT;
+use databend_query::sessions::QueryContext;
+use databend_query::storages::fuse::io::MetaReaders;
+use databend_query::storages::fuse::meta::TableSnapshot;
+use databend_query::storages::fuse::FuseTable;
 use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::append_sample_data;
 use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
 #[tokio::test]
@@ -50,7 +60,13 @@ async fn test_fuse_occ_retry() -> Result<()> {
             TestFixture::gen_sample_blocks_stream_ex(num_blocks, rows_per_block, value_start_from);
         let r = table.append_data(ctx.clone(), stream).await?;
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                false,
+            )
             .await?;
     }
 
@@ -60,6 +76,7 @@ async fn test_fuse_occ_retry() -> Result<()> {
             ctx.clone(),
             CATALOG_DEFAULT,
             pending.try_collect().await?,
+            None,
             false,
         )
         .await?;
@@ -83,3 +100,131 @@ async fn test_fuse_occ_retry() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fuse_mutation_rebased_onto_appends() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+
+    // a mutation, which removes the only segment, based on the current snapshot
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let base = read_snapshot(ctx.as_ref(), fuse_table).await?;
+    let mut mutated = TableSnapshot::from_previous(&base);
+    mutated.segments = vec![];
+
+    // meanwhile, another segment is appended
+    append_sample_data(1, &fixture).await?;
+
+    // the mutation should be rebased onto the appending
+    fuse_table
+        .commit_mutation(ctx.as_ref(), &base, mutated, CATALOG_DEFAULT)
+        .await?;
+
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let latest = read_snapshot(ctx.as_ref(), fuse_table).await?;
+    assert_eq!(latest.segments.len(), 1);
+    assert_ne!(latest.segments[0], base.segments[0]);
+    assert_eq!(latest.summary.block_count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_mutation_write_write_conflict() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+
+    // two mutations, which remove the same segment, based on the same snapshot
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let base = read_snapshot(ctx.as_ref(), fuse_table).await?;
+    let mut mutated = TableSnapshot::from_previous(&base);
+    mutated.segments = vec![];
+    let mut another_mutated = TableSnapshot::from_previous(&base);
+    another_mutated.segments = vec![];
+
+    fuse_table
+        .commit_mutation(ctx.as_ref(), &base, another_mutated, CATALOG_DEFAULT)
+        .await?;
+
+    // the latter one should fail
+    let r = fuse_table
+        .commit_mutation(ctx.as_ref(), &base, mutated, CATALOG_DEFAULT)
+        .await;
+    expects_err(
+        "write_write_conflict",
+        ErrorCode::table_write_conflict_code(),
+        r,
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_schema_change_rebased_onto_appends() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+
+    // a schema change, based on the current table
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+
+    // meanwhile, another segment is appended
+    append_sample_data(1, &fixture).await?;
+
+    // the schema change should be rebased onto the appending
+    let field = DataField::new("c", u64::to_data_type());
+    fuse_table
+        .do_add_column(ctx.clone(), CATALOG_DEFAULT, field, "".to_owned())
+        .await?;
+
+    let table = fixture.latest_default_table().await?;
+    assert!(table.schema().has_field("c"));
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let latest = read_snapshot(ctx.as_ref(), fuse_table).await?;
+    assert_eq!(latest.segments.len(), 2);
+    assert_eq!(latest.summary.block_count, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_schema_change_conflict() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // two schema changes, based on the same table
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+
+    let field = DataField::new("c", u64::to_data_type());
+    fuse_table
+        .do_add_column(ctx.clone(), CATALOG_DEFAULT, field, "".to_owned())
+        .await?;
+
+    // the latter one should fail, since the schema has been changed
+    let field = DataField::new("d", u64::to_data_type());
+    let r = fuse_table
+        .do_add_column(ctx.clone(), CATALOG_DEFAULT, field, "".to_owned())
+        .await;
+    expects_err(
+        "schema_change_conflict",
+        ErrorCode::table_write_conflict_code(),
+        r,
+    );
+    Ok(())
+}
+
+async fn read_snapshot(ctx: &QueryContext, table: &FuseTable) -> Result<Arc<TableSnapshot>> {
+    let reader = MetaReaders::table_snapshot_reader(ctx);
+    let loc = table.snapshot_loc().unwrap();
+    reader
+        .read(loc.as_str(), None, table.snapshot_format_version())
+        .await
+}
@@ -13,6 +13,7 @@
 //  limitations under the License.
 //
 
+mod alter_column;
 mod commit;
 mod navigate;
 mod optimize;
@@ -167,7 +167,9 @@ async fn test_fuse_historical_table_is_read_only() -> Result<()> {
     assert_not_writable(res, "append_data");
 
     // check  commit_insertion
-    let res = tbl.commit_insertion(ctx.clone(), "", vec![], false).await;
+    let res = tbl
+        .commit_insertion(ctx.clone(), "", vec![], None, false)
+        .await;
     assert_not_writable(res, "commit_insertion");
 
     // check truncate
@@ -14,8 +14,10 @@
 //
 
 use common_base::base::tokio;
+use common_datablocks::DataBlock;
 use common_exception::Result;
 use databend_query::catalogs::CATALOG_DEFAULT;
+use databend_query::storages::fuse::FuseTable;
 use futures::TryStreamExt;
 
 use crate::storages::fuse::table_test_fixture::append_sample_data;
@@ -81,7 +83,13 @@ async fn test_fuse_snapshot_optimize_compact() -> Result<()> {
         let stream = TestFixture::gen_sample_blocks_stream(num_blocks, 1);
         let r = table.append_data(ctx.clone(), stream).await?;
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                false,
+            )
             .await?;
     }
 
@@ -107,3 +115,67 @@ async fn test_fuse_snapshot_optimize_compact() -> Result<()> {
     )
     .await
 }
+
+#[tokio::test]
+async fn test_fuse_snapshot_optimize_purge_dry_run() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+    append_sample_data_overwrite(1, true, &fixture).await?;
+
+    // the snapshot, segment and block of the first insertion should be reported
+    let qry = format!("optimize table '{}'.'{}' purge dry run", db, tbl);
+    let blocks: Vec<DataBlock> = execute_query(ctx, qry.as_str())
+        .await?
+        .try_collect()
+        .await?;
+    let num_files: usize = blocks.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(num_files, 3);
+
+    // but nothing should be removed
+    check_data_dir(&fixture, "purge dry run", 2, 2, 2).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_needs_compaction() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // insert 5 small blocks, each of them in its own segment
+    for _ in 0..5 {
+        let table = fixture.latest_default_table().await?;
+        let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                false,
+            )
+            .await?;
+    }
+
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    // auto compaction is opted in by table option
+    assert!(!fuse_table.is_auto_compaction_enabled());
+    assert!(fuse_table.needs_compaction(ctx.as_ref()).await?);
+
+    let qry = format!("optimize table '{}'.'{}' compact", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the blocks are compacted into one block, which is not worth compacting again
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    assert!(!fuse_table.needs_compaction(ctx.as_ref()).await?);
+    Ok(())
+}
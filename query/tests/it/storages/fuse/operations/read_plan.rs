@@ -73,6 +73,7 @@ fn test_to_partitions() -> Result<()> {
         cols_metas,
         cluster_stats,
         location,
+        None,
     );
 
     let blocks_metas = (0..num_of_block)
@@ -135,7 +136,13 @@ async fn test_fuse_table_exact_statistic() -> Result<()> {
 
         let r = table.append_data(ctx.clone(), stream).await?;
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                false,
+            )
             .await?;
 
         table = fixture.latest_default_table().await?;
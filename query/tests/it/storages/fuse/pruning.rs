@@ -36,6 +36,7 @@ use databend_query::storages::fuse::meta::BlockMeta;
 use databend_query::storages::fuse::meta::TableSnapshot;
 use databend_query::storages::fuse::pruning::BlockPruner;
 use databend_query::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
+use databend_query::storages::fuse::FUSE_OPT_KEY_BLOOM_INDEX_COLUMNS;
 use databend_query::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
 use futures::TryStreamExt;
 
@@ -126,7 +127,13 @@ async fn test_block_pruner() -> Result<()> {
     let stream = Box::pin(futures::stream::iter(blocks));
     let r = table.append_data(ctx.clone(), stream).await?;
     table
-        .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+        .commit_insertion(
+            ctx.clone(),
+            CATALOG_DEFAULT,
+            r.try_collect().await?,
+            None,
+            false,
+        )
         .await?;
 
     // get the latest tbl
@@ -263,7 +270,13 @@ async fn test_block_pruner_monotonic() -> Result<()> {
     let stream = Box::pin(futures::stream::iter(blocks));
     let r = table.append_data(ctx.clone(), stream).await?;
     table
-        .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+        .commit_insertion(
+            ctx.clone(),
+            CATALOG_DEFAULT,
+            r.try_collect().await?,
+            None,
+            false,
+        )
         .await?;
 
     // get the latest tbl
@@ -315,3 +328,131 @@ async fn test_block_pruner_monotonic() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_block_pruner_bloom_filter() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let test_tbl_name = "test_bloom_filter_pruning";
+    let test_schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", Vu8::to_data_type()),
+        DataField::new("v", u64::to_data_type()),
+    ]);
+
+    let num_blocks = 10;
+    let create_table_plan = CreateTablePlan {
+        catalog: "default".to_owned(),
+        if_not_exists: false,
+        tenant: fixture.default_tenant(),
+        database: fixture.default_db_name(),
+        table: test_tbl_name.to_string(),
+        table_meta: TableMeta {
+            schema: test_schema.clone(),
+            engine: "FUSE".to_string(),
+            options: [
+                (FUSE_OPT_KEY_ROW_PER_BLOCK.to_owned(), "3".to_owned()),
+                (FUSE_OPT_KEY_BLOCK_PER_SEGMENT.to_owned(), "1".to_owned()),
+                (FUSE_OPT_KEY_BLOOM_INDEX_COLUMNS.to_owned(), "id".to_owned()),
+                (OPT_KEY_DATABASE_ID.to_owned(), "1".to_owned()),
+            ]
+            .into(),
+            ..Default::default()
+        },
+        as_select: None,
+        cluster_keys: vec![],
+    };
+
+    let interpreter = CreateTableInterpreter::try_create(ctx.clone(), create_table_plan)?;
+    interpreter.execute(None).await?;
+
+    let catalog = ctx.get_catalog("default")?;
+    let table = catalog
+        .get_table(
+            fixture.default_tenant().as_str(),
+            fixture.default_db_name().as_str(),
+            test_tbl_name,
+        )
+        .await?;
+
+    // the min/max of column id are the same for all the blocks, i.e. `a` and `z`,
+    // only the bloom filters are able to tell the blocks apart.
+    let blocks = (0..num_blocks)
+        .into_iter()
+        .map(|idx| {
+            let middle = format!("k{}", idx);
+            Ok(DataBlock::create(test_schema.clone(), vec![
+                Series::from_data(vec!["a", middle.as_str(), "z"]),
+                Series::from_data(vec![idx as u64; 3]),
+            ]))
+        })
+        .collect::<Vec<_>>();
+
+    let stream = Box::pin(futures::stream::iter(blocks));
+    let r = table.append_data(ctx.clone(), stream).await?;
+    table
+        .commit_insertion(
+            ctx.clone(),
+            CATALOG_DEFAULT,
+            r.try_collect().await?,
+            None,
+            false,
+        )
+        .await?;
+
+    let table = catalog
+        .get_table(
+            fixture.default_tenant().as_str(),
+            fixture.default_db_name().as_str(),
+            test_tbl_name,
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(OPT_KEY_SNAPSHOT_LOCATION)
+        .unwrap();
+    let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
+    let snapshot = reader.read(snapshot_loc.as_str(), None, 1).await?;
+
+    // all the blocks are indexed
+    let blocks = apply_block_pruning(
+        snapshot.clone(),
+        table.get_table_info().schema(),
+        &None,
+        ctx.clone(),
+    )
+    .await?;
+    assert_eq!(num_blocks, blocks.len());
+    assert!(blocks
+        .iter()
+        .all(|b| b.bloom_filter_index_location.is_some()));
+
+    // point lookup, the block holding the value is always kept, while others are likely
+    // pruned (the bloom filter may have false positives).
+    let mut extra = Extras::default();
+    extra.filters = vec![col("id").eq(lit("k3".as_bytes()))];
+    let blocks = apply_block_pruning(
+        snapshot.clone(),
+        table.get_table_info().schema(),
+        &Some(extra),
+        ctx.clone(),
+    )
+    .await?;
+    assert!(!blocks.is_empty());
+    assert!(blocks.len() < num_blocks);
+
+    // the min/max statistics are not able to prune any block
+    let mut extra = Extras::default();
+    extra.filters = vec![col("id").gt(lit("a".as_bytes()))];
+    let blocks = apply_block_pruning(
+        snapshot.clone(),
+        table.get_table_info().schema(),
+        &Some(extra),
+        ctx.clone(),
+    )
+    .await?;
+    assert_eq!(num_blocks, blocks.len());
+
+    Ok(())
+}
@@ -61,7 +61,13 @@ async fn test_fuse_table_normal_case() -> Result<()> {
 
         let r = table.append_data(ctx.clone(), stream).await?;
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                false,
+            )
             .await?;
 
         // get the latest tbl
@@ -119,7 +125,13 @@ async fn test_fuse_table_normal_case() -> Result<()> {
 
         let r = table.append_data(ctx.clone(), stream).await?;
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, true)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                true,
+            )
             .await?;
 
         // get the latest tbl
@@ -201,7 +213,13 @@ async fn test_fuse_table_truncate() -> Result<()> {
 
     let r = table.append_data(ctx.clone(), stream).await?;
     table
-        .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+        .commit_insertion(
+            ctx.clone(),
+            CATALOG_DEFAULT,
+            r.try_collect().await?,
+            None,
+            false,
+        )
         .await?;
     let source_plan = table.read_plan(ctx.clone(), None).await?;
 
@@ -256,7 +274,13 @@ async fn test_fuse_table_optimize() -> Result<()> {
         let stream = TestFixture::gen_sample_blocks_stream(num_blocks, 1);
         let r = table.append_data(ctx.clone(), stream).await?;
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                false,
+            )
             .await?;
     }
 
@@ -64,7 +64,13 @@ async fn test_memorytable() -> Result<()> {
             .unwrap();
         // with overwrite false
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, false)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                false,
+            )
             .await?;
     }
 
@@ -157,7 +163,13 @@ async fn test_memorytable() -> Result<()> {
             .unwrap();
         // with overwrite = true
         table
-            .commit_insertion(ctx.clone(), CATALOG_DEFAULT, r.try_collect().await?, true)
+            .commit_insertion(
+                ctx.clone(),
+                CATALOG_DEFAULT,
+                r.try_collect().await?,
+                None,
+                true,
+            )
             .await?;
     }
 
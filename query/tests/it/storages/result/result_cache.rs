@@ -0,0 +1,197 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_meta_types::UserIdentity;
+use databend_query::interpreters::InterpreterFactory;
+use databend_query::sessions::QueryContext;
+use databend_query::sql::exec::PhysicalPlanBuilder;
+use databend_query::sql::plans::Plan;
+use databend_query::sql::PlanParser;
+use databend_query::sql::Planner;
+use databend_query::storages::result::ResultCache;
+use databend_query::storages::result::ResultQueryInfo;
+use databend_query::storages::result::ResultTable;
+use databend_query::storages::result::ResultTableWriter;
+use futures::TryStreamExt;
+
+async fn execute_command(ctx: &Arc<QueryContext>, query: &str) -> Result<()> {
+    let plan = PlanParser::parse(ctx.clone(), query).await?;
+    let stream = InterpreterFactory::get(ctx.clone(), plan)?
+        .execute(None)
+        .await?;
+    stream.try_collect::<Vec<DataBlock>>().await?;
+    Ok(())
+}
+
+async fn try_create_cache(ctx: &Arc<QueryContext>, query: &str) -> Result<Option<ResultCache>> {
+    ctx.attach_query_str(query);
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(query).await?;
+    match plan {
+        Plan::Query {
+            s_expr, metadata, ..
+        } => {
+            let physical_plan = PhysicalPlanBuilder::new(metadata.clone()).build(&s_expr)?;
+            ResultCache::try_create(ctx, &s_expr, &metadata, &physical_plan)
+        }
+        _ => unreachable!(),
+    }
+}
+
+async fn cache_key(ctx: &Arc<QueryContext>, query: &str) -> Result<String> {
+    let cache = try_create_cache(ctx, query).await?;
+    Ok(cache
+        .unwrap_or_else(|| panic!("{} should be cached", query))
+        .key()
+        .to_string())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_result_cache_key() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+    ctx.get_settings().set_settings(
+        "enable_query_result_cache".to_string(),
+        "1".to_string(),
+        false,
+    )?;
+    execute_command(&ctx, "CREATE TABLE t_cache_key(a INT, b INT)").await?;
+    execute_command(&ctx, "INSERT INTO t_cache_key VALUES (1, 2), (3, 4)").await?;
+
+    // The key is derived from the plan, so it doesn't depend on how the query is written.
+    let key = cache_key(&ctx, "SELECT a FROM t_cache_key WHERE b > 1").await?;
+    assert!(key.starts_with("cache_"), "{}", key);
+    assert_eq!(
+        key,
+        cache_key(&ctx, "select  a\nfrom t_cache_key where (b>1)").await?
+    );
+    assert_ne!(
+        key,
+        cache_key(&ctx, "SELECT a FROM t_cache_key WHERE b > 2").await?
+    );
+    assert_ne!(
+        key,
+        cache_key(&ctx, "SELECT b FROM t_cache_key WHERE b > 1").await?
+    );
+
+    // The key changes with the snapshot of the table.
+    execute_command(&ctx, "INSERT INTO t_cache_key VALUES (5, 6)").await?;
+    assert_ne!(
+        key,
+        cache_key(&ctx, "SELECT a FROM t_cache_key WHERE b > 1").await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_result_cache_deterministic() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+    execute_command(&ctx, "CREATE TABLE t_cache_det(a INT)").await?;
+    execute_command(&ctx, "INSERT INTO t_cache_det VALUES (1)").await?;
+
+    // Disabled by default.
+    assert!(try_create_cache(&ctx, "SELECT a FROM t_cache_det")
+        .await?
+        .is_none());
+
+    ctx.get_settings().set_settings(
+        "enable_query_result_cache".to_string(),
+        "1".to_string(),
+        false,
+    )?;
+    assert!(try_create_cache(&ctx, "SELECT a FROM t_cache_det")
+        .await?
+        .is_some());
+
+    for query in [
+        "SELECT a, now() FROM t_cache_det",
+        "SELECT a FROM t_cache_det WHERE to_date(now()) > '2022-01-01'",
+        "SELECT a, rand() FROM t_cache_det",
+        "SELECT a FROM t_cache_det WHERE rand() > 0.5",
+        "SELECT count(*) FROM t_cache_det GROUP BY rand() > 0.5",
+    ] {
+        assert!(
+            try_create_cache(&ctx, query).await?.is_none(),
+            "{} should not be cached",
+            query
+        );
+    }
+
+    // Tables other than fuse tables are not cached.
+    assert!(try_create_cache(&ctx, "SELECT * FROM numbers(10)")
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_result_cache_expire() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+    ctx.get_settings().set_settings(
+        "enable_query_result_cache".to_string(),
+        "1".to_string(),
+        false,
+    )?;
+    execute_command(&ctx, "CREATE TABLE t_cache_ttl(a INT)").await?;
+    execute_command(&ctx, "INSERT INTO t_cache_ttl VALUES (1)").await?;
+
+    let query = "SELECT a FROM t_cache_ttl";
+    let cache = try_create_cache(&ctx, query).await?.unwrap();
+
+    // Write the result as the query does.
+    let plan = PlanParser::parse(ctx.clone(), query).await?;
+    let blocks = InterpreterFactory::get(ctx.clone(), plan)?
+        .execute(None)
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let schema = blocks[0].schema().clone();
+    let query_info = ResultQueryInfo {
+        query_id: cache.key().to_string(),
+        schema: schema.clone(),
+        user: UserIdentity {
+            username: "u1".to_string(),
+            hostname: "h1".to_string(),
+        },
+    };
+    let mut writer = ResultTableWriter::new(ctx.clone(), query_info).await?;
+    for block in blocks {
+        writer.append_block(block).await?;
+    }
+    writer.commit().await?;
+
+    assert!(cache.try_read(&schema).await.is_some());
+
+    // An expired result is a miss, and it's removed.
+    ctx.get_settings().set_settings(
+        "query_result_cache_ttl_secs".to_string(),
+        "0".to_string(),
+        false,
+    )?;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let cache = try_create_cache(&ctx, query).await?.unwrap();
+    assert!(cache.try_read(&schema).await.is_none());
+    assert!(ResultTable::try_get(ctx.clone(), cache.key())
+        .await
+        .is_err());
+
+    Ok(())
+}
@@ -63,6 +63,8 @@ async fn test_configs_table() -> Result<()> {
         "| query   | async_insert_busy_timeout            | 200                       |             |",
         "| query   | async_insert_max_data_size           | 10000                     |             |",
         "| query   | async_insert_stale_timeout           | 0                         |             |",
+        "| query   | auto_compaction_interval_secs        | 60                        |             |",
+        "| query   | auto_compaction_max_concurrent_jobs  | 1                         |             |",
         "| query   | clickhouse_handler_host              | 127.0.0.1                 |             |",
         "| query   | clickhouse_handler_port              | 9000                      |             |",
         "| query   | clickhouse_http_handler_host         | 127.0.0.1                 |             |",
@@ -88,6 +90,7 @@ async fn test_configs_table() -> Result<()> {
         "| query   | rpc_tls_query_service_domain_name    | localhost                 |             |",
         "| query   | rpc_tls_server_cert                  |                           |             |",
         "| query   | rpc_tls_server_key                   |                           |             |",
+        "| query   | spill_local_path                     |                           |             |",
         "| query   | table_cache_block_meta_count         | 102400                    |             |",
         "| query   | table_cache_enabled                  | false                     |             |",
         "| query   | table_cache_segment_count            | 10240                     |             |",
@@ -179,6 +182,8 @@ async fn test_configs_table_redact() -> Result<()> {
         "| query   | async_insert_busy_timeout            | 200                       |             |",
         "| query   | async_insert_max_data_size           | 10000                     |             |",
         "| query   | async_insert_stale_timeout           | 0                         |             |",
+        "| query   | auto_compaction_interval_secs        | 60                        |             |",
+        "| query   | auto_compaction_max_concurrent_jobs  | 1                         |             |",
         "| query   | clickhouse_handler_host              | 127.0.0.1                 |             |",
         "| query   | clickhouse_handler_port              | 9000                      |             |",
         "| query   | clickhouse_http_handler_host         | 127.0.0.1                 |             |",
@@ -204,6 +209,7 @@ async fn test_configs_table_redact() -> Result<()> {
         "| query   | rpc_tls_query_service_domain_name    | localhost                 |             |",
         "| query   | rpc_tls_server_cert                  |                           |             |",
         "| query   | rpc_tls_server_key                   |                           |             |",
+        "| query   | spill_local_path                     |                           |             |",
         "| query   | table_cache_block_meta_count         | 102400                    |             |",
         "| query   | table_cache_enabled                  | false                     |             |",
         "| query   | table_cache_segment_count            | 10240                     |             |",
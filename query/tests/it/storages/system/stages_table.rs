@@ -40,11 +40,11 @@ async fn test_stages_table() -> Result<()> {
     let block = &result[0];
     assert_eq!(block.num_columns(), 8);
     let expected = vec![
-        "+------------+------------+----------------------------------------------------------------+-----------------------------------------------+--------------------------------------------------------------------------------------------------------------------+-----------------+---------+---------+",
-        "| name       | stage_type | stage_params                                                   | copy_options                                  | file_format_options                                                                                                | number_of_files | creator | comment |",
-        "+------------+------------+----------------------------------------------------------------+-----------------------------------------------+--------------------------------------------------------------------------------------------------------------------+-----------------+---------+---------+",
-        r#"| test_stage | External   | StageParams { storage: Fs(StorageFsConfig { root: "_data" }) } | CopyOptions { on_error: None, size_limit: 0 } | FileFormatOptions { format: Csv, skip_header: 0, field_delimiter: ",", record_delimiter: "\n", compression: None } | NULL            | NULL    |         |"#,
-        "+------------+------------+----------------------------------------------------------------+-----------------------------------------------+--------------------------------------------------------------------------------------------------------------------+-----------------+---------+---------+",
+        "+------------+------------+----------------------------------------------------------------+-----------------------------------------------------------------+--------------------------------------------------------------------------------------------------------------------+-----------------+---------+---------+",
+        "| name       | stage_type | stage_params                                                   | copy_options                                                    | file_format_options                                                                                                | number_of_files | creator | comment |",
+        "+------------+------------+----------------------------------------------------------------+-----------------------------------------------------------------+--------------------------------------------------------------------------------------------------------------------+-----------------+---------+---------+",
+        r#"| test_stage | External   | StageParams { storage: Fs(StorageFsConfig { root: "_data" }) } | CopyOptions { on_error: None, size_limit: 0, max_file_size: 0 } | FileFormatOptions { format: Csv, skip_header: 0, field_delimiter: ",", record_delimiter: "\n", compression: None } | NULL            | NULL    |         |"#,
+        "+------------+------------+----------------------------------------------------------------+-----------------------------------------------------------------+--------------------------------------------------------------------------------------------------------------------+-----------------+---------+---------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
 
@@ -0,0 +1,41 @@
+set enable_planner_v2 = 1;
+
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+-- setup
+CREATE TABLE IF NOT EXISTS t(c1 Int, c2 Int, c3 String null);
+INSERT INTO t VALUES(1, 2, 'a');
+INSERT INTO t VALUES(3, 4, 'b');
+
+select 'selection not match, nothing updated';
+update t set c2 = 0 where c1 > 3;
+select * from t order by c1;
+
+select 'update one row';
+update t set c2 = c2 * 10, c3 = 'c' where c1 = 1;
+select * from t order by c1;
+
+select 'update unconditionally';
+update t set c1 = c1 + 1;
+select * from t order by c1;
+
+select 'update with const false';
+update t set c2 = 0 where 1 = 0;
+select * from t order by c1;
+
+select 'update to null';
+update t set c3 = NULL where c1 = 4;
+select count(*) = 1 from t where c3 IS NULL;
+
+select 'swap columns';
+update t set c1 = c2, c2 = c1;
+select * from t order by c1;
+
+select 'unknown column';
+update t set c4 = 1; -- {ErrorCode 1058}
+
+drop table t all;
+
+DROP DATABASE db1;
@@ -0,0 +1,49 @@
+set enable_planner_v2 = 1;
+
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+-- setup
+CREATE TABLE IF NOT EXISTS t(c1 Int, c2 Int, c3 String null);
+INSERT INTO t VALUES(1, 2, 'a'),(3, 4, 'b'),(5, 6, 'c');
+CREATE TABLE IF NOT EXISTS s(id Int, v Int);
+INSERT INTO s VALUES(1, 10),(3, 30),(7, 70);
+
+select 'delete, update and insert';
+merge into t using s on t.c1 = s.id when matched and s.v > 20 then delete when matched then update set c2 = s.v when not matched then insert (c1, c2) values (s.id, s.v);
+select * from t order by c1;
+
+select 'merge from subquery with alias';
+merge into t as x using (select 5 as k, 'z' as n) as y on x.c1 = y.k when matched then update set c3 = y.n;
+select * from t order by c1;
+
+select 'merge into empty table';
+CREATE TABLE IF NOT EXISTS e(a Int, b Int);
+merge into e using s on e.a = s.id when not matched and s.v < 50 then insert values (s.id, s.v);
+select * from e order by a;
+
+select 'merge into blocks pruned by the join keys';
+CREATE TABLE IF NOT EXISTS m(k Int, v Int);
+INSERT INTO m VALUES(1, 1),(2, 2);
+INSERT INTO m VALUES(10, 10),(20, 20);
+INSERT INTO m VALUES(100, 100),(200, 200);
+CREATE TABLE IF NOT EXISTS u(k Int, v Int);
+INSERT INTO u VALUES(10, 11),(15, 16),(20, 0);
+merge into m using u on m.k = u.k and u.v > 0 when matched then update set v = u.v when not matched then insert values (u.k, u.v);
+select * from m order by k, v;
+
+select 'target row matches multiple source rows';
+INSERT INTO s VALUES(1, 100);
+merge into t using s on t.c1 = s.id when matched then delete; -- {ErrorCode 1006}
+
+select 'no equality condition';
+merge into t using s on t.c1 > s.id when matched then delete; -- {ErrorCode 1065}
+
+drop table t all;
+drop table s all;
+drop table e all;
+drop table m all;
+drop table u all;
+
+DROP DATABASE db1;
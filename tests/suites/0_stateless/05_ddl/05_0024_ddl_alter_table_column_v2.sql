@@ -0,0 +1,32 @@
+set enable_planner_v2 = 1;
+
+DROP TABLE IF EXISTS `05_0024_t`;
+
+CREATE TABLE `05_0024_t`(a int, b int);
+INSERT INTO `05_0024_t` values(1, 2);
+
+-- add column, the blocks written before are filled with the default value
+ALTER TABLE `05_0024_t` ADD COLUMN c int default 3;
+SELECT * FROM `05_0024_t`;
+INSERT INTO `05_0024_t` values(4, 5, 6);
+SELECT * FROM `05_0024_t` ORDER BY a;
+ALTER TABLE `05_0024_t` ADD COLUMN a int; -- {ErrorCode 1006}
+
+-- drop column
+ALTER TABLE `05_0024_t` DROP COLUMN b;
+SELECT * FROM `05_0024_t` ORDER BY a;
+ALTER TABLE `05_0024_t` DROP COLUMN b; -- {ErrorCode 1058}
+
+-- a column added after a drop does not see the data of the dropped one
+ALTER TABLE `05_0024_t` ADD COLUMN b int;
+SELECT * FROM `05_0024_t` ORDER BY a;
+
+-- rename column
+ALTER TABLE `05_0024_t` RENAME COLUMN c TO d;
+SELECT d FROM `05_0024_t` ORDER BY d;
+SELECT * FROM `05_0024_t` WHERE d > 3;
+ALTER TABLE `05_0024_t` RENAME COLUMN d TO a; -- {ErrorCode 1006}
+
+DROP TABLE IF EXISTS `05_0024_t`;
+
+set enable_planner_v2 = 0;
@@ -0,0 +1,30 @@
+DROP DATABASE IF EXISTS db1;
+
+CREATE DATABASE db1;
+
+USE db1;
+
+select '-- create table with bloom filter index';
+CREATE TABLE IF NOT EXISTS t09_0018(user_id varchar, v int) Engine = fuse bloom_index_columns = 'user_id';
+
+INSERT INTO t09_0018 VALUES('alice', 1),('bob', 2);
+INSERT INTO t09_0018 VALUES('carol', 3),('dave', 4);
+INSERT INTO t09_0018 VALUES('eve', 5),('frank', 6);
+
+select '-- point lookup';
+select * from t09_0018 where user_id = 'carol';
+select * from t09_0018 where user_id = 'carol' or user_id = 'frank' order by v;
+select * from t09_0018 where user_id = 'carol' and v = 4;
+select count(*) from t09_0018 where user_id = 'nobody';
+
+select '-- mutations keep the index';
+delete from t09_0018 where user_id = 'bob';
+select * from t09_0018 where user_id = 'alice';
+select count(*) from t09_0018 where user_id = 'bob';
+
+select '-- compact keeps the index';
+optimize table t09_0018 compact;
+select * from t09_0018 where user_id = 'eve';
+
+DROP TABLE t09_0018;
+DROP DATABASE db1;
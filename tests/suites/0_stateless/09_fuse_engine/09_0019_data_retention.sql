@@ -0,0 +1,37 @@
+DROP DATABASE IF EXISTS db_09_0019;
+CREATE DATABASE db_09_0019;
+USE db_09_0019;
+
+---------------------------
+-- snapshots within the retention period are kept by purge
+
+create table t(a uint64) data_retention_period_in_hours = 1;
+
+insert into t values (1);
+insert into t values (2);
+insert into t values (3);
+
+-- nothing would be purged
+optimize table t purge dry run;
+optimize table t purge;
+-- expects all the 3 snapshots are kept
+select count(*)=3 from fuse_snapshot('db_09_0019', 't');
+select * from t order by a;
+
+---------------------------
+-- without retention period, only the last snapshot is kept
+
+create table t1(a uint64);
+
+insert into t1 values (1);
+insert into t1 values (2);
+insert into t1 values (3);
+
+select count(*)=3 from fuse_snapshot('db_09_0019', 't1');
+optimize table t1 purge;
+select count(*)=1 from fuse_snapshot('db_09_0019', 't1');
+select * from t1 order by a;
+
+DROP TABLE t;
+DROP TABLE t1;
+DROP DATABASE db_09_0019;
@@ -0,0 +1,59 @@
+DROP DATABASE IF EXISTS db_09_0020;
+CREATE DATABASE db_09_0020;
+USE db_09_0020;
+
+set enable_planner_v2 = 1;
+
+create table t1(a uint64);
+insert into t1 values (1);
+insert into t1 values (2);
+
+---------------------------
+-- the clone shares the data of the source table
+
+create table t2 clone t1;
+select * from t2 order by a;
+
+---------------------------
+-- the clone and the source table are modified independently
+
+insert into t2 values (3);
+insert into t1 values (4);
+select * from t1 order by a;
+select * from t2 order by a;
+
+---------------------------
+-- purging the source table does not remove the data shared with the clone
+
+optimize table t1 purge;
+truncate table t1 purge;
+select count(*) from t1;
+select * from t2 order by a;
+
+---------------------------
+-- the clone could be purged, without touching the data of the source table
+
+create table t3(a uint64);
+insert into t3 values (5);
+insert into t3 values (6);
+create table t4 clone t3;
+insert into t4 values (7);
+optimize table t4 purge;
+truncate table t4 purge;
+select * from t3 order by a;
+
+---------------------------
+-- the clone of a clone shares the data of the both tables it is cloned from
+
+create table t5 clone t2;
+insert into t2 values (8);
+optimize table t2 purge;
+optimize table t1 purge;
+select * from t5 order by a;
+
+DROP TABLE t1;
+DROP TABLE t2;
+DROP TABLE t3;
+DROP TABLE t4;
+DROP TABLE t5;
+DROP DATABASE db_09_0020;
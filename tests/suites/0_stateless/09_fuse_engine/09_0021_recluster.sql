@@ -0,0 +1,38 @@
+DROP DATABASE IF EXISTS db_09_0021;
+CREATE DATABASE db_09_0021;
+USE db_09_0021;
+
+set enable_planner_v2 = 1;
+
+create table t(a int, b int) cluster by(a);
+
+insert into t values (1, 1), (3, 3);
+insert into t values (2, 2), (4, 4);
+insert into t values (5, 5), (6, 6);
+
+select total_block_count, block_depth_histogram from clustering_information('db_09_0021','t');
+
+---------------------------
+-- the overlapping blocks are re-sorted, the others are kept
+
+alter table t recluster final;
+
+select total_block_count, block_depth_histogram from clustering_information('db_09_0021','t');
+select * from t order by a;
+
+---------------------------
+-- nothing to re-sort
+
+alter table t recluster;
+select total_block_count from clustering_information('db_09_0021','t');
+
+---------------------------
+-- the table must be clustered
+
+create table t1(a int);
+insert into t1 values (1);
+alter table t1 recluster; -- {ErrorCode 1070}
+
+DROP TABLE t;
+DROP TABLE t1;
+DROP DATABASE db_09_0021;
@@ -0,0 +1,33 @@
+set enable_planner_v2 = 1;
+
+DROP DATABASE IF EXISTS db_09_0022;
+CREATE DATABASE db_09_0022;
+USE db_09_0022;
+
+CREATE TABLE t1(a Int, b Int);
+CREATE TABLE t2(a Int, c String);
+INSERT INTO t1 VALUES(1, 10),(2, 20),(3, 30);
+INSERT INTO t1 VALUES(4, 40),(1, 11),(2, 22);
+INSERT INTO t2 VALUES(1, 'x'),(2, 'y');
+INSERT INTO t2 VALUES(2, 'z'),(5, 'w');
+
+select 'aggregation';
+select count(*), sum(b) from t1;
+select a, count(*), sum(b) from t1 group by a order by a;
+select sum(number) from numbers(1000);
+select number % 3 as k, count(*) from numbers(10) group by k order by k;
+
+select 'join';
+select t1.a, t1.b, t2.c from t1 join t2 on t1.a = t2.a order by t1.b, t2.c;
+select t1.a, t2.c from t1 left join t2 on t1.a = t2.a order by t1.a, t2.c;
+select count(*) from t1, t2;
+
+select 'join with aggregation';
+select t2.c, count(*), sum(t1.b) from t1 join t2 on t1.a = t2.a group by t2.c order by t2.c;
+
+select 'window';
+select a, b, count(*) over (partition by a) from t1 order by a, b;
+
+DROP TABLE t1;
+DROP TABLE t2;
+DROP DATABASE db_09_0022;
@@ -0,0 +1,35 @@
+DROP DATABASE IF EXISTS data_type_decimal;
+CREATE DATABASE data_type_decimal;
+USE data_type_decimal;
+
+CREATE TABLE t(a DECIMAL(10, 2), b DECIMAL(18, 4) NULL);
+
+-- rounding check, half away from zero
+INSERT INTO t VALUES ('1.005', '0.1'), ('2.10', '0.2'), ('-3.456', NULL);
+SELECT a, b FROM t ORDER BY a;
+
+-- arithmetic check
+SELECT a + b, a * 2, a / 3, -a FROM t ORDER BY a;
+SELECT CAST('9.123456789' AS DECIMAL(10, 9)) * CAST('9.000000001' AS DECIMAL(10, 9));
+
+-- aggregate check, the sums are exact
+SELECT sum(a), avg(a), sum(b) FROM t;
+SELECT a > 1, sum(b) FROM t GROUP BY a > 1 ORDER BY a > 1;
+
+-- comparison check
+SELECT count(*) FROM t WHERE a > 1;
+SELECT count(*) FROM t WHERE a = 2.1;
+SELECT count(*) FROM t WHERE b = 0.1;
+
+-- conversion check
+SELECT CAST('1.235' AS DECIMAL(5, 2)), CAST('-1.235' AS DECIMAL(5, 2)), CAST('1.2e1' AS DECIMAL(5, 2));
+SELECT CAST(CAST('12.5' AS DECIMAL(3, 1)) AS INT), CAST(CAST('-12.5' AS DECIMAL(3, 1)) AS INT);
+SELECT CAST(CAST('12.5' AS DECIMAL(3, 1)) AS VARCHAR), CAST(CAST('12.5' AS DECIMAL(3, 1)) AS DECIMAL(3, 0));
+SELECT TRY_CAST('123.45' AS DECIMAL(4, 2)) IS NULL;
+SELECT CAST('123.45' AS DECIMAL(4, 2)); -- {ErrorCode 1010}
+
+-- bound check
+CREATE TABLE t1(a DECIMAL(19, 2)); -- {ErrorCode 1007}
+INSERT INTO t VALUES ('123456789.123', NULL); -- {ErrorCode 1010}
+
+DROP DATABASE data_type_decimal;
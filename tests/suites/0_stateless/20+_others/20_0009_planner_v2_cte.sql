@@ -0,0 +1,30 @@
+set enable_planner_v2 = 1;
+
+select '====CTE====';
+with t as (select number as a from numbers(5)) select a from t where a > 2 order by a;
+with t (a, b) as (select number, number + 1 from numbers(3)) select a, b from t order by a;
+with t1 as (select number as a from numbers(3)), t2 as (select a + 1 as b from t1) select b from t2 order by b;
+with t as (select number as a from numbers(3)) select * from (with t as (select a * 10 as a from t) select a from t) order by a;
+with t as (select 1), t as (select 2) select * from t; -- {ErrorCode 1065}
+
+-- a common table expression shadows the table of the same name
+create table t(a int);
+insert into t values(100);
+with t as (select 1 as a) select a from t;
+select a from t;
+drop table t;
+
+select '====MATERIALIZED====';
+with t as materialized (select number as a from numbers(3)) select t1.a, t2.a from t as t1, t as t2 where t1.a = t2.a order by t1.a;
+
+select '====RECURSIVE====';
+with recursive t (n) as (select 1 union all select n + 1 from t where n < 5) select n from t order by n;
+create table employees(id int, name varchar, manager_id int null);
+insert into employees values(1, 'ceo', null), (2, 'cto', 1), (3, 'engineer', 2), (4, 'cfo', 1), (5, 'intern', 3);
+with recursive chain (id, name, depth) as (select id, name, 0 from employees where manager_id is null union all select e.id, e.name, c.depth + 1 from employees as e join chain as c on e.manager_id = c.id) select id, name, depth from chain order by id;
+drop table employees;
+set cte_max_recursion_depth = 10;
+with recursive t (n) as (select 1 union all select n + 1 from t) select count(*) from t; -- {ErrorCode 1065}
+set cte_max_recursion_depth = 1000;
+
+set enable_planner_v2 = 0;
@@ -0,0 +1,30 @@
+set enable_planner_v2 = 1;
+
+create table w(g int, v int);
+insert into w values(1, 1), (1, 2), (1, 2), (2, 3), (2, 5);
+
+select '====RANKING====';
+select g, v, row_number() over (partition by g order by v) as rn, rank() over (partition by g order by v) as r, dense_rank() over (partition by g order by v) as dr from w order by g, v, rn;
+select v, ntile(2) over (order by v) as n from w order by v, n;
+
+select '====AGGREGATE====';
+select g, v, sum(v) over (partition by g order by v) as s from w order by g, v;
+select g, v, count(*) over (partition by g) as c from w order by g, v;
+select g, sum(sum(v)) over () as s from w group by g order by g;
+
+select '====FRAME====';
+select g, v, sum(v) over (order by g, v rows between 1 preceding and current row) as s from w order by g, v, s;
+select v, sum(v) over (order by v range between 1 preceding and current row) as s from w order by v;
+select g, first_value(v) over (partition by g order by v rows between unbounded preceding and unbounded following) as f, last_value(v) over (partition by g order by v rows between unbounded preceding and unbounded following) as l from w order by g, f;
+
+select '====OFFSET====';
+select v, lag(v) over (order by v) as l1, lead(v, 1, 0) over (order by v) as l2 from w order by v, l2;
+
+select '====ERROR====';
+select row_number() from w; -- {ErrorCode 1065}
+select v from w where row_number() over () > 1; -- {ErrorCode 1065}
+select sum(row_number() over ()) from w; -- {ErrorCode 1065}
+
+drop table w;
+
+set enable_planner_v2 = 0;
@@ -0,0 +1,20 @@
+set enable_planner_v2 = 1;
+set max_block_size = 100;
+set spill_memory_budget = 1;
+set spill_partitions = 4;
+
+select '====AGGREGATE====';
+select count(*), sum(c) from (select number % 1000 as k, count(*) as c from numbers(10000) group by k) as t;
+select k, c from (select number % 1000 as k, count(*) as c from numbers(10000) group by k) as t order by k limit 3;
+select count(*) from (select number % 500 as k from numbers(5000) group by k) as t;
+
+select '====JOIN====';
+select count(*), sum(t.number) from numbers(1000) as t inner join numbers(1000) as t1 on t.number = t1.number;
+select t.number from numbers(10000) as t inner join numbers(1000) as t1 on t.number % 1000 = t1.number order by number limit 5;
+select count(*) from numbers(1000) as t left join numbers(500) as t1 on t.number = t1.number;
+
+select '====SORT====';
+select number from numbers(10000) order by number desc limit 3;
+select number % 3 as a, number from numbers(1000) order by a, number desc limit 4;
+
+set spill_memory_budget = 0;
@@ -0,0 +1,27 @@
+set enable_planner_v2 = 1;
+set enable_cbo = 1;
+
+select '====COMMUTE====';
+explain select t.number from numbers(10) as t, numbers(1000) as t1 where t.number = t1.number;
+select count(*) from numbers(10) as t, numbers(1000) as t1 where t.number = t1.number;
+
+select '====STAR SCHEMA====';
+drop table if exists fact;
+drop table if exists dim1;
+drop table if exists dim2;
+create table fact(d1 int, d2 int, v int);
+create table dim1(k int);
+create table dim2(k int);
+insert into fact select number % 10, number % 5, number from numbers(1000);
+insert into dim1 select number from numbers(10);
+insert into dim2 select number from numbers(5);
+
+explain select fact.v from dim2, fact, dim1 where fact.d1 = dim1.k and fact.d2 = dim2.k and dim1.k = 3;
+select dim1.k, dim2.k, fact.v from dim2, fact, dim1 where fact.d1 = dim1.k and fact.d2 = dim2.k and dim1.k = 3 order by fact.v limit 3;
+
+drop table fact;
+drop table dim1;
+drop table dim2;
+
+set enable_cbo = 0;
+set enable_planner_v2 = 0;
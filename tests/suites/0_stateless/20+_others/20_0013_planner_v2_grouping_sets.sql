@@ -0,0 +1,24 @@
+set enable_planner_v2 = 1;
+
+create table g(a int, b int, v int);
+insert into g values(1, 1, 10), (1, 2, 20), (2, 1, 30);
+
+select '====ROLLUP====';
+select a, b, sum(v) from g group by rollup(a, b) order by a, b;
+select a, count(a) from g group by rollup(a) order by a;
+select a + 1 as x, sum(v) from g group by rollup(a) order by x;
+
+select '====CUBE====';
+select a, b, count(*) from g group by cube(a, b) order by a, b;
+
+select '====GROUPING SETS====';
+select a, b, grouping(a), grouping(b), grouping(a, b), sum(v) from g group by grouping sets ((a), (b)) order by a, b;
+select a, sum(v) from g group by grouping sets ((a), ()) having grouping(a) = 1;
+select a, grouping(a) from g group by a order by a;
+
+select '====ERROR====';
+select grouping(v) from g group by rollup(a); -- {ErrorCode 1065}
+
+drop table g;
+
+set enable_planner_v2 = 0;
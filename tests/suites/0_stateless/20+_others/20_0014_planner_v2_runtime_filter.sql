@@ -0,0 +1,37 @@
+set enable_planner_v2 = 1;
+
+create table fact(k int null, s varchar, v int);
+insert into fact values(1, 'a', 10), (2, 'b', 20);
+insert into fact values(3, 'c', 30), (4, 'd', 40);
+insert into fact values(5, 'e', 50), (null, 'f', 60);
+create table dim(k int null, s varchar);
+insert into dim values(2, 'b'), (3, 'x'), (null, 'f');
+
+select '====INNER====';
+select fact.k, fact.v from fact inner join dim on fact.k = dim.k order by fact.k;
+select fact.v from fact inner join dim on fact.s = dim.s order by fact.v;
+select fact.v from fact inner join dim on fact.k = dim.k where fact.v > 20;
+select count(*) from fact inner join (select k from dim where k > 2) as d on fact.k = d.k;
+select count(*) from fact inner join (select k from dim where k > 100) as d on fact.k = d.k;
+
+select '====SEMI====';
+select k, v from fact where k in (select k from dim) order by k;
+
+select '====LEFT====';
+select fact.v, dim.s from fact left join dim on fact.k = dim.k order by fact.v;
+
+select '====MANY KEYS====';
+create table n(a UInt64);
+insert into n select number from numbers(2000);
+select count(*), sum(n.a) from n inner join (select number from numbers(1500)) as t on n.a = t.number;
+
+select '====DISABLED====';
+set enable_runtime_filter = 0;
+select fact.k, fact.v from fact inner join dim on fact.k = dim.k order by fact.k;
+set enable_runtime_filter = 1;
+
+drop table fact;
+drop table dim;
+drop table n;
+
+set enable_planner_v2 = 0;
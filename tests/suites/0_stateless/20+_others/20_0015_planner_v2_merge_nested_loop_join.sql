@@ -0,0 +1,25 @@
+set enable_planner_v2 = 1;
+
+create table t1(a int null, b int);
+insert into t1 values(1, 10), (2, 20), (2, 21), (3, 30), (5, 50), (null, 60);
+create table t2(a int null, c int);
+insert into t2 values(2, 200), (2, 201), (3, 300), (4, 400), (5, 500), (null, 600);
+
+select '====MERGE JOIN====';
+explain select x.a, x.b, y.c from (select a, b from t1 order by a asc) as x join (select a, c from t2 order by a asc) as y on x.a = y.a;
+select x.a, x.b, y.c from (select a, b from t1 order by a asc) as x join (select a, c from t2 order by a asc) as y on x.a = y.a order by x.a, x.b, y.c;
+select x.a, x.b, y.c from (select a, b from t1 order by a asc) as x join (select a, c from t2 order by a asc) as y on x.a = y.a and x.b * 10 < y.c;
+explain select x.a, x.b, y.c from (select a, b from t1 order by a desc) as x join (select a, c from t2 order by a asc) as y on x.a = y.a;
+
+select '====NESTED LOOP JOIN====';
+explain select t1.a, t2.a from t1 join t2 on t1.a < t2.a;
+select t1.a, t2.a from t1 join t2 on t1.a < t2.a order by t1.a, t2.a;
+select count(*) from numbers(1000) as l join numbers(100) as r on l.number > r.number and l.number < r.number + 10;
+set enable_cbo = 1;
+select count(*) from numbers(1000) as l join numbers(100) as r on l.number > r.number and l.number < r.number + 10;
+set enable_cbo = 0;
+
+drop table t1;
+drop table t2;
+
+set enable_planner_v2 = 0;
@@ -0,0 +1,29 @@
+set enable_planner_v2 = 1;
+set enable_query_result_cache = 1;
+
+create table t(a int, b varchar);
+insert into t values(1, 'a'), (2, 'b');
+
+select '====CACHED====';
+select a, b from t order by a;
+select a, b from t order by a;
+select sum(a) as s from t;
+select sum(a) as total from t;
+
+select '====SNAPSHOT CHANGED====';
+insert into t values(3, 'c');
+select a, b from t order by a;
+select sum(a) as s from t;
+
+select '====NON-DETERMINISTIC====';
+select count(*) from t where a < rand() + 10;
+
+select '====TOO LARGE====';
+set query_result_cache_max_bytes = 1;
+select a from t order by a;
+select a from t order by a;
+
+drop table t;
+
+set enable_query_result_cache = 0;
+set enable_planner_v2 = 0;
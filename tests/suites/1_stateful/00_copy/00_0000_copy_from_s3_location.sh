@@ -3,13 +3,18 @@
 CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
 . "$CURDIR"/../../../shell_env.sh
 
+## Print the file name, rows loaded, errors seen and first error of each file loaded by COPY.
+print_copy_result() {
+  awk -F'\t' '{n = split($1, path, "/"); print path[n], $2, $3, $4}' | sort
+}
+
 echo "drop table if exists ontime200;" | $MYSQL_CLIENT_CONNECT
 ## Create table
 cat $CURDIR/../ddl/ontime.sql | sed 's/ontime/ontime200/g' | $MYSQL_CLIENT_CONNECT
 
 ## Copy from s3.
 echo "Test copy from file"
-echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT
+echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT | print_copy_result
 
 ## Result.
 echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
@@ -19,7 +24,7 @@ echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 
 ## Copy from s3 with compression gzip.
 echo "Test copy from gzip file"
-echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv.gz' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'gzip'  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT
+echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv.gz' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'gzip'  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT | print_copy_result
 
 ## Result.
 echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
@@ -29,7 +34,7 @@ echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 
 ## Copy from s3 with compression zstd.
 echo "Test copy from zstd file"
-echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv.zst' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'zstd'  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT
+echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv.zst' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'zstd'  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT | print_copy_result
 
 ## Result.
 echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
@@ -39,7 +44,7 @@ echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 
 ## Copy from s3 with compression bz2.
 echo "Test copy from bzip2 file"
-echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv.bz2' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'bz2'  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT
+echo "copy into ontime200 from 's3://testbucket/admin/data/ontime_200.csv.bz2' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'bz2'  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT | print_copy_result
 
 ## Result.
 echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
@@ -48,21 +53,21 @@ echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIEN
 echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 
 ## Copy from s3 with files.
-echo "copy into ontime200 from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILES = ('ontime_200.csv', 'ontime_200_v1.csv') FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT
+echo "copy into ontime200 from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILES = ('ontime_200.csv', 'ontime_200_v1.csv') FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT | print_copy_result
 ## Result.
 echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
 echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 
 
 ## Copy from s3 by directory with pattern.
-echo "copy into ontime200 from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') PATTERN = 'ontime.*csv$' FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT
+echo "copy into ontime200 from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') PATTERN = 'ontime.*csv$' FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n' skip_header = 1)" | $MYSQL_CLIENT_CONNECT | print_copy_result
 ## Result.
 echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
 echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 
 
 ## Copy from parquet
-echo "copy into ontime200 from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') PATTERN = 'ontime.*parquet' FILE_FORMAT = (type = 'PARQUET')" | $MYSQL_CLIENT_CONNECT
+echo "copy into ontime200 from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') PATTERN = 'ontime.*parquet' FILE_FORMAT = (type = 'PARQUET')" | $MYSQL_CLIENT_CONNECT | print_copy_result
 echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
 echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 echo "drop table if exists ontime200;" | $MYSQL_CLIENT_CONNECT
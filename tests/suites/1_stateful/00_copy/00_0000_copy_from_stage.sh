@@ -3,6 +3,11 @@
 CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
 . "$CURDIR"/../../../shell_env.sh
 
+## Print the file name, rows loaded, errors seen and first error of each file loaded by COPY.
+print_copy_result() {
+  awk -F'\t' '{n = split($1, path, "/"); print path[n], $2, $3, $4}' | sort
+}
+
 echo "drop table if exists ontime200;" | $MYSQL_CLIENT_CONNECT
 echo "drop stage if exists s1" | $MYSQL_CLIENT_CONNECT
 echo "drop stage if exists named_external_stage" | $MYSQL_CLIENT_CONNECT
@@ -16,6 +21,12 @@ aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/ontim
 aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/ontime_200.csv.bz2 s3://testbucket/admin/stage/s1/ontime_200.csv.bz2 >/dev/null 2>&1
 aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/ontime_200.parquet s3://testbucket/admin/stage/s1/ontime_200.parquet >/dev/null 2>&1
 
+## The second file has two rows which can't be parsed.
+printf "1,a\n2,b\n" > /tmp/on_error_1.csv
+printf "3,c\nx,d\ny,e\n6,f\n" > /tmp/on_error_2.csv
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp /tmp/on_error_1.csv s3://testbucket/admin/stage/s1/on_error_1.csv >/dev/null 2>&1
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp /tmp/on_error_2.csv s3://testbucket/admin/stage/s1/on_error_2.csv >/dev/null 2>&1
+
 ## Copy from internal stage
 echo "CREATE STAGE s1;" | $MYSQL_CLIENT_CONNECT
 echo "list @s1 PATTERN = 'ontime.*'" | $MYSQL_CLIENT_CONNECT | awk '{print $1}' | sort
@@ -34,7 +45,7 @@ copy_from_stage_cases=(
 )
 
 for i in "${copy_from_stage_cases[@]}"; do
-  echo "$i" | $MYSQL_CLIENT_CONNECT
+  echo "$i" | $MYSQL_CLIENT_CONNECT | print_copy_result
   echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
   echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 done
@@ -54,16 +65,39 @@ copy_from_named_external_stage_cases=(
   "copy into ontime200 from '@named_external_stage' FILES = ('ontime_200.csv.bz2') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'bz2'  record_delimiter = '\n' skip_header = 1);"
   # copy auto csv
   "copy into ontime200 from '@named_external_stage' FILES = ('ontime_200.csv.gz','ontime_200.csv.bz2','ontime_200.csv.zst') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'auto'  record_delimiter = '\n' skip_header = 1);"
-  # copy auto csv with limit
-  "copy into ontime200 from '@named_external_stage' FILES = ('ontime_200.csv.gz','ontime_200.csv.bz2','ontime_200.csv.zst') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'auto'  record_delimiter = '\n' skip_header = 1) SIZE_LIMIT = 10;"
 )
 
 for i in "${copy_from_named_external_stage_cases[@]}"; do
-  echo "$i" | $MYSQL_CLIENT_CONNECT
+  echo "$i" | $MYSQL_CLIENT_CONNECT | print_copy_result
   echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
   echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
 done
 
+## Copy auto csv with limit, the rows loaded of each file depend on which source reaches the limit first.
+echo "copy into ontime200 from '@named_external_stage' FILES = ('ontime_200.csv.gz','ontime_200.csv.bz2','ontime_200.csv.zst') FILE_FORMAT = (type = 'CSV' field_delimiter = ',' compression = 'auto'  record_delimiter = '\n' skip_header = 1) SIZE_LIMIT = 10;" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{print $3, $4}' | sort -u
+echo "select count(1), avg(Year), sum(DayOfWeek)  from ontime200" | $MYSQL_CLIENT_CONNECT
+echo "truncate table ontime200" | $MYSQL_CLIENT_CONNECT
+
+
+## Copy with on_error from internal stage
+echo "drop table if exists copy_on_error;" | $MYSQL_CLIENT_CONNECT
+echo "create table copy_on_error(a int, b varchar);" | $MYSQL_CLIENT_CONNECT
+
+on_error_cases=(
+  "abort_statement"
+  "continue"
+  "skip_file"
+  "skip_file_2"
+  "skip_file_3"
+)
+
+for i in "${on_error_cases[@]}"; do
+  echo "Test copy with on_error = $i"
+  echo "copy into copy_on_error from '@s1' FILES = ('on_error_1.csv', 'on_error_2.csv') FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n') ON_ERROR = $i;" | $MYSQL_CLIENT_CONNECT 2>/dev/null | print_copy_result
+  echo "select count(1) from copy_on_error" | $MYSQL_CLIENT_CONNECT
+  echo "truncate table copy_on_error" | $MYSQL_CLIENT_CONNECT
+done
+
 
 ## List stage use http API
 
@@ -72,5 +106,7 @@ curl -s -u root: -XPOST "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/query" -
 
 ## Drop table.
 echo "drop table ontime200" | $MYSQL_CLIENT_CONNECT
+echo "drop table copy_on_error" | $MYSQL_CLIENT_CONNECT
 echo "drop stage if exists named_external_stage" | $MYSQL_CLIENT_CONNECT
 echo "drop stage if exists s1" | $MYSQL_CLIENT_CONNECT
+rm -f /tmp/on_error_1.csv /tmp/on_error_2.csv
@@ -21,8 +21,42 @@ done
 echo "set enable_planner_v2 = 1; copy into @s2 from test_table FILE_FORMAT = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
 echo "set enable_planner_v2 = 1; copy into @s2 from (select name, age, id from test_table limit 100) FILE_FORMAT = (type = 'PARQUET');" | $MYSQL_CLIENT_CONNECT
 echo "list @s2;" | $MYSQL_CLIENT_CONNECT | wc -l | sed 's/ //g'
+
+## Unload with compression.
+echo "set enable_planner_v2 = 1; copy into @s2/tsv/ from test_table FILE_FORMAT = (type = 'TSV' compression = 'gzip');" | $MYSQL_CLIENT_CONNECT
+echo "list @s2/tsv/ pattern = '.*[.]tsv[.]gz';" | $MYSQL_CLIENT_CONNECT | wc -l | sed 's/ //g'
+
+## Unload into more than one file by max_file_size, and load them back.
+echo "set enable_planner_v2 = 1; copy into @s2/ndjson/ from test_table FILE_FORMAT = (type = 'NDJSON') MAX_FILE_SIZE = 1;" | $MYSQL_CLIENT_CONNECT
+echo "list @s2/ndjson/;" | $MYSQL_CLIENT_CONNECT | wc -l | awk '{print ($1 > 1)}'
+echo "drop table if exists test_table_ndjson;" | $MYSQL_CLIENT_CONNECT
+echo "CREATE TABLE test_table_ndjson (
+    id INTEGER,
+    name VARCHAR,
+    age INT
+);" | $MYSQL_CLIENT_CONNECT
+echo "set enable_planner_v2 = 1; copy into test_table_ndjson from @s2/ndjson/ FILE_FORMAT = (type = 'NDJSON');" | $MYSQL_CLIENT_CONNECT > /dev/null
+echo "select count(*), sum(id), sum(age) from test_table_ndjson;" | $MYSQL_CLIENT_CONNECT
+
+## Unload with compression into more than one file, and load them back by the file extensions.
+echo "drop table if exists test_table_csv;" | $MYSQL_CLIENT_CONNECT
+echo "CREATE TABLE test_table_csv (
+    id INTEGER,
+    name VARCHAR,
+    age INT
+);" | $MYSQL_CLIENT_CONNECT
+for c in gzip zstd deflate raw_deflate; do
+  echo "set enable_planner_v2 = 1; copy into @s2/$c/ from test_table FILE_FORMAT = (type = 'CSV' compression = '$c') MAX_FILE_SIZE = 1;" | $MYSQL_CLIENT_CONNECT
+  echo "list @s2/$c/;" | $MYSQL_CLIENT_CONNECT | wc -l | awk '{print ($1 > 1)}'
+  echo "set enable_planner_v2 = 1; copy into test_table_csv from @s2/$c/ FILE_FORMAT = (type = 'CSV' compression = AUTO);" | $MYSQL_CLIENT_CONNECT > /dev/null
+  echo "select count(*), sum(id), sum(age) from test_table_csv;" | $MYSQL_CLIENT_CONNECT
+  echo "truncate table test_table_csv;" | $MYSQL_CLIENT_CONNECT
+done
+
 echo "drop STAGE s2;" | $MYSQL_CLIENT_CONNECT
 echo "drop table test_table;" | $MYSQL_CLIENT_CONNECT
+echo "drop table test_table_ndjson;" | $MYSQL_CLIENT_CONNECT
+echo "drop table test_table_csv;" | $MYSQL_CLIENT_CONNECT
 
 aws --endpoint-url http://127.0.0.1:9900/ s3 rm s3://testbucket/admin/stage/s2 --recursive  > /dev/null 2>&1
 
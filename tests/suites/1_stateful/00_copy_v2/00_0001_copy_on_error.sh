@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists copy_on_error;" | $MYSQL_CLIENT_CONNECT
+echo "create table copy_on_error(a int, b varchar);" | $MYSQL_CLIENT_CONNECT
+
+## The third line can't be parsed.
+printf "1,a\n2,b\nx,c\n4,d\n" > /tmp/copy_on_error.csv
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp /tmp/copy_on_error.csv s3://testbucket/admin/data/copy_on_error.csv >/dev/null 2>&1
+
+on_error_cases=(
+  "abort_statement"
+  "continue"
+  "skip_file"
+  "skip_file_2"
+)
+
+## Print the rows loaded, errors seen and the first error line of the file.
+for i in "${on_error_cases[@]}"; do
+  echo "Test copy with on_error = $i"
+  echo "set enable_planner_v2 = 1; copy into copy_on_error from 's3://testbucket/admin/data/copy_on_error.csv' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n') ON_ERROR = $i" | $MYSQL_CLIENT_CONNECT 2>/dev/null | awk -F'\t' '{print $2, $3, $5}'
+  echo "select count(1) from copy_on_error" | $MYSQL_CLIENT_CONNECT
+  echo "truncate table copy_on_error" | $MYSQL_CLIENT_CONNECT
+done
+
+## Drop table.
+echo "drop table copy_on_error" | $MYSQL_CLIENT_CONNECT
+rm -f /tmp/copy_on_error.csv
@@ -0,0 +1,42 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists copy_force;" | $MYSQL_CLIENT_CONNECT
+echo "create table copy_force(a int, b varchar);" | $MYSQL_CLIENT_CONNECT
+
+printf "1,a\n2,b\n" > /tmp/copy_force_1.csv
+printf "3,c\n" > /tmp/copy_force_2.csv
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp /tmp/copy_force_1.csv s3://testbucket/admin/data/copy_force/copy_force_1.csv >/dev/null 2>&1
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp /tmp/copy_force_2.csv s3://testbucket/admin/data/copy_force/copy_force_2.csv >/dev/null 2>&1
+
+copy_sql="copy into copy_force from 's3://testbucket/admin/data/copy_force/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') FILE_FORMAT = (type = 'CSV' field_delimiter = ','  record_delimiter = '\n')"
+
+## Print the rows loaded of each file and the rows of the table.
+echo "Test copy the files"
+echo "set enable_planner_v2 = 1; $copy_sql" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{print $2}' | sort
+echo "select count(1) from copy_force" | $MYSQL_CLIENT_CONNECT
+
+echo "Test copy the files again"
+echo "set enable_planner_v2 = 1; $copy_sql" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{print $2}' | sort
+echo "select count(1) from copy_force" | $MYSQL_CLIENT_CONNECT
+
+echo "Test copy the files after one changed"
+printf "3,c\n4,d\n" > /tmp/copy_force_2.csv
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp /tmp/copy_force_2.csv s3://testbucket/admin/data/copy_force/copy_force_2.csv >/dev/null 2>&1
+echo "set enable_planner_v2 = 1; $copy_sql" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{print $2}' | sort
+echo "select count(1) from copy_force" | $MYSQL_CLIENT_CONNECT
+
+echo "Test copy the files with force"
+echo "set enable_planner_v2 = 1; $copy_sql FORCE = true" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{print $2}' | sort
+echo "select count(1) from copy_force" | $MYSQL_CLIENT_CONNECT
+
+echo "Test copy the files after truncate"
+echo "truncate table copy_force" | $MYSQL_CLIENT_CONNECT
+echo "set enable_planner_v2 = 1; $copy_sql" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{print $2}' | sort
+echo "select count(1) from copy_force" | $MYSQL_CLIENT_CONNECT
+
+## Drop table.
+echo "drop table copy_force" | $MYSQL_CLIENT_CONNECT
+rm -f /tmp/copy_force_1.csv /tmp/copy_force_2.csv
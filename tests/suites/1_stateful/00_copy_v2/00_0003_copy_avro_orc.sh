@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists copy_avro;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists copy_orc;" | $MYSQL_CLIENT_CONNECT
+
+## The nested columns of the files are loaded into variant columns, the missing ones get the defaults.
+echo "create table copy_avro(id int, name varchar, tags variant, point variant, note varchar null, age tinyint unsigned);" | $MYSQL_CLIENT_CONNECT
+echo "create table copy_orc(ID bigint, name varchar null, score variant, age int);" | $MYSQL_CLIENT_CONNECT
+
+## Print the rows loaded and errors seen of each file, and the rows of the table.
+echo "Test copy avro files"
+echo "set enable_planner_v2 = 1; copy into copy_avro from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') PATTERN = 'sample.*avro$' FILE_FORMAT = (type = 'AVRO')" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{n = split($1, path, "/"); print path[n], $2, $3}' | sort
+echo "select * from copy_avro order by id" | $MYSQL_CLIENT_CONNECT
+
+echo "Test copy orc files"
+echo "set enable_planner_v2 = 1; copy into copy_orc from 's3://testbucket/admin/data/' credentials=(aws_key_id='minioadmin' aws_secret_key='minioadmin') PATTERN = 'sample.*orc$' FILE_FORMAT = (type = 'ORC')" | $MYSQL_CLIENT_CONNECT | awk -F'\t' '{n = split($1, path, "/"); print path[n], $2, $3}' | sort
+echo "select * from copy_orc order by id" | $MYSQL_CLIENT_CONNECT
+
+## Drop table.
+echo "drop table copy_avro" | $MYSQL_CLIENT_CONNECT
+echo "drop table copy_orc" | $MYSQL_CLIENT_CONNECT